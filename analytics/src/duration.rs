@@ -0,0 +1,48 @@
+/// Parse a duration such as `3d12h`, `90m`, `2w` or `45s` into seconds.
+///
+/// A bare number is read as hours, matching the old `--duration 168` form.
+pub fn parse(value: &str) -> Result<u64, String> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Err("duration is empty".to_string());
+    }
+    if let Ok(hours) = value.parse::<u64>() {
+        return hours.checked_mul(3600).ok_or_else(|| format!("'{}' hours is too long", value));
+    }
+
+    let mut total: u64 = 0;
+    let mut digits = String::new();
+    for c in value.chars() {
+        if c.is_ascii_digit() {
+            digits.push(c);
+            continue;
+        }
+        let unit = match c.to_ascii_lowercase() {
+            'w' => 7 * 86_400,
+            'd' => 86_400,
+            'h' => 3_600,
+            'm' => 60,
+            's' => 1,
+            _ => return Err(format!("unknown unit '{}' in '{}' (use w, d, h, m or s)", c, value)),
+        };
+        let amount: u64 = digits.parse().map_err(|_| format!("missing number before '{}' in '{}'", c, value))?;
+        digits.clear();
+        total = amount.checked_mul(unit)
+            .and_then(|secs| total.checked_add(secs))
+            .ok_or_else(|| format!("'{}' is too long", value))?;
+    }
+    if !digits.is_empty() {
+        return Err(format!("'{}' ends without a unit (use w, d, h, m or s)", value));
+    }
+    Ok(total)
+}
+
+/// Render seconds as `3d 12h`, `1h 30m` or `45s`
+pub fn format(seconds: u64) -> String {
+    let parts = [(seconds / 86_400, "d"), (seconds % 86_400 / 3_600, "h"), (seconds % 3_600 / 60, "m"), (seconds % 60, "s")];
+    let rendered: Vec<String> = parts.iter()
+        .filter(|(amount, _)| *amount > 0)
+        .map(|(amount, unit)| format!("{}{}", amount, unit))
+        .collect();
+    if rendered.is_empty() { "0s".to_string() } else { rendered.join(" ") }
+}
@@ -0,0 +1,12 @@
+//! The read-only core of the polling CLI: duration parsing, number formatting and the analytics
+//! math behind `whales`, `project` and the results tables.
+//!
+//! Nothing here touches the chain, the file system or the network, so it builds for
+//! `wasm32-unknown-unknown`; with the `wasm` feature the same functions are exported to
+//! JavaScript and the web dashboard gets the exact numbers the CLI prints.
+
+pub mod duration;
+pub mod numbers;
+pub mod stats;
+#[cfg(feature = "wasm")]
+pub mod wasm;
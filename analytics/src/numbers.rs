@@ -0,0 +1,84 @@
+/// Digit grouping and decimal mark for the user's locale
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Separators {
+    pub group: char,
+    pub decimal: char,
+}
+
+impl Separators {
+    pub const EN: Separators = Separators { group: ',', decimal: '.' };
+
+    /// Pick separators from a locale name such as `de_DE.UTF-8`
+    pub fn for_locale(locale: &str) -> Self {
+        let lang = locale.split(['.', '@']).next().unwrap_or_default();
+        let (language, region) = lang.split_once(['_', '-']).unwrap_or((lang, ""));
+
+        match (language, region) {
+            ("de", "CH") | ("it", "CH") => Separators { group: '\'', decimal: '.' },
+            ("de" | "es" | "it" | "nl" | "pt" | "id" | "tr" | "da" | "el", _) => Separators { group: '.', decimal: ',' },
+            ("fr" | "ru" | "pl" | "cs" | "sk" | "sv" | "fi" | "nb" | "no" | "uk" | "hu", _) => Separators { group: ' ', decimal: ',' },
+            _ => Separators::EN,
+        }
+    }
+
+    /// Separators from LC_ALL, LC_NUMERIC or LANG, in that order of precedence
+    pub fn from_env() -> Self {
+        ["LC_ALL", "LC_NUMERIC", "LANG"].iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find(|value| !value.is_empty())
+            .map(|locale| Self::for_locale(&locale))
+            .unwrap_or(Separators::EN)
+    }
+}
+
+/// A run of decimal digits with `separator` between every group of three
+pub fn group_digits(digits: &str, separator: char) -> String {
+    let mut grouped = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            grouped.push(separator);
+        }
+        grouped.push(c);
+    }
+    grouped
+}
+
+/// A token amount given as its raw decimal digits, scaled by `decimals` and rounded half up to
+/// `precision` fractional digits. Working on digits keeps full precision for any uint256.
+pub fn format_amount_digits(digits: &str, decimals: u32, precision: usize, separators: Separators) -> String {
+    let decimals = decimals as usize;
+    let padded = format!("{:0>width$}", digits, width = decimals + 1);
+    let (whole, fraction) = padded.split_at(padded.len() - decimals);
+
+    // Round half up on the first dropped digit, carrying into the whole part if needed
+    let mut kept: Vec<u8> = format!("{}{}", whole, &fraction[..precision.min(decimals)]).into_bytes();
+    if precision < decimals && fraction.as_bytes()[precision] >= b'5' {
+        let mut i = kept.len();
+        loop {
+            if i == 0 {
+                kept.insert(0, b'1');
+                break;
+            }
+            i -= 1;
+            if kept[i] == b'9' {
+                kept[i] = b'0';
+            } else {
+                kept[i] += 1;
+                break;
+            }
+        }
+    }
+    let kept = String::from_utf8(kept).unwrap_or_default();
+    let fraction_len = precision.min(decimals);
+    let (whole, fraction) = kept.split_at(kept.len() - fraction_len);
+
+    let mut formatted = group_digits(whole, separators.group);
+    if precision > 0 {
+        formatted.push(separators.decimal);
+        formatted.push_str(fraction);
+        for _ in fraction_len..precision {
+            formatted.push('0');
+        }
+    }
+    formatted
+}
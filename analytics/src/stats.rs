@@ -0,0 +1,166 @@
+use std::cmp::Ordering;
+
+/// Half-life used by the decay model: voting interest halves every day
+pub const DECAY_HALF_LIFE_HOURS: f64 = 24.0;
+
+/// `part` as a percentage of `total`; 0 when nothing was cast
+pub fn share(part: f64, total: f64) -> f64 {
+    if total > 0.0 { part / total * 100.0 } else { 0.0 }
+}
+
+/// Whether `votes` meets `min_participation`; `None` when the poll sets no quorum
+pub fn quorum_reached(votes: u64, min_participation: u64) -> Option<bool> {
+    (min_participation > 0).then_some(votes >= min_participation)
+}
+
+/// Gini coefficient of a set of non-negative weights (0 = perfectly equal, 1 = one holder)
+pub fn gini(weights: &[f64]) -> f64 {
+    let n = weights.len();
+    let total: f64 = weights.iter().sum();
+    if n == 0 || total == 0.0 {
+        return 0.0;
+    }
+
+    let mut sorted = weights.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+    let weighted_sum: f64 = sorted.iter().enumerate().map(|(i, w)| (i + 1) as f64 * w).sum();
+
+    (2.0 * weighted_sum) / (n as f64 * total) - (n as f64 + 1.0) / n as f64
+}
+
+/// How many of the largest weights (given largest first) together hold more than half
+pub fn nakamoto_coefficient(sorted_desc: &[f64]) -> usize {
+    let total: f64 = sorted_desc.iter().sum();
+    let mut running = 0.0;
+    for (i, w) in sorted_desc.iter().enumerate() {
+        running += w;
+        if running > total / 2.0 {
+            return i + 1;
+        }
+    }
+    sorted_desc.len()
+}
+
+/// Expected number of additional votes before the poll ends under the `linear`, `recent` or
+/// `decay` model; `None` for any other model name
+pub fn expected_additional(model: &str, overall_rate: f64, recent_rate: f64, hours_remaining: f64) -> Option<f64> {
+    match model.to_lowercase().as_str() {
+        "linear" => Some(overall_rate * hours_remaining),
+        "recent" => Some(recent_rate * hours_remaining),
+        "decay" => {
+            // Integral of recent_rate * 2^(-t / half_life) over the remaining time
+            let k = std::f64::consts::LN_2 / DECAY_HALF_LIFE_HOURS;
+            Some(recent_rate * (1.0 - (-k * hours_remaining).exp()) / k)
+        }
+        _ => None,
+    }
+}
+
+/// Standard normal cumulative distribution function (Abramowitz & Stegun 7.1.26)
+pub fn normal_cdf(x: f64) -> f64 {
+    let t = 1.0 / (1.0 + 0.3275911 * x.abs() / std::f64::consts::SQRT_2);
+    let poly = t * (0.254829592 + t * (-0.284496736 + t * (1.421413741 + t * (-1.453152027 + t * 1.061405429))));
+    let erf = 1.0 - poly * (-(x * x) / 2.0).exp();
+    if x >= 0.0 { 0.5 * (1.0 + erf) } else { 0.5 * (1.0 - erf) }
+}
+
+/// Probability that a Poisson arrival process with mean `lambda` produces at least `needed` events
+pub fn poisson_at_least(lambda: f64, needed: u64) -> f64 {
+    if needed == 0 {
+        return 1.0;
+    }
+    if lambda <= 0.0 {
+        return 0.0;
+    }
+    if lambda > 100.0 {
+        // Normal approximation with continuity correction
+        return 1.0 - normal_cdf((needed as f64 - 0.5 - lambda) / lambda.sqrt());
+    }
+
+    let mut term = (-lambda).exp();
+    let mut below = term;
+    for k in 1..needed {
+        term *= lambda / k as f64;
+        below += term;
+    }
+    (1.0 - below).clamp(0.0, 1.0)
+}
+
+/// Probability the current leader stays ahead if the remaining votes split like the votes so far
+pub fn leader_hold_probability(tally: &[f64], vote_count: u64, expected_votes: f64) -> f64 {
+    let mut ranked: Vec<f64> = tally.to_vec();
+    ranked.sort_by(|a, b| b.partial_cmp(a).unwrap_or(Ordering::Equal));
+    let total: f64 = ranked.iter().sum();
+    if ranked.len() < 2 || total == 0.0 || vote_count == 0 {
+        return 0.0;
+    }
+
+    let lead = ranked[0] - ranked[1];
+    if expected_votes <= 0.0 {
+        return if lead > 0.0 { 1.0 } else { 0.5 };
+    }
+
+    let avg_weight = total / vote_count as f64;
+    let p1 = ranked[0] / total;
+    let p2 = ranked[1] / total;
+    let drift = expected_votes * (p1 - p2) * avg_weight;
+    let spread = avg_weight * (expected_votes * (p1 + p2 - (p1 - p2).powi(2))).sqrt();
+    if spread == 0.0 {
+        return if lead + drift > 0.0 { 1.0 } else { 0.0 };
+    }
+
+    normal_cdf((lead + drift) / spread)
+}
+
+/// Cramér's V of a contingency table (0 = independent, 1 = each row determines the column).
+/// Rows and columns without any counts are left out; 0 when fewer than two of either remain.
+pub fn cramers_v(table: &[Vec<f64>]) -> f64 {
+    let columns = table.iter().map(Vec::len).max().unwrap_or(0);
+    let cell = |r: usize, c: usize| table[r].get(c).copied().unwrap_or(0.0);
+    let row_totals: Vec<f64> = (0..table.len()).map(|r| (0..columns).map(|c| cell(r, c)).sum()).collect();
+    let column_totals: Vec<f64> = (0..columns).map(|c| (0..table.len()).map(|r| cell(r, c)).sum()).collect();
+    let total: f64 = row_totals.iter().sum();
+    let rows = row_totals.iter().filter(|t| **t > 0.0).count();
+    let cols = column_totals.iter().filter(|t| **t > 0.0).count();
+    if rows < 2 || cols < 2 {
+        return 0.0;
+    }
+
+    let mut chi_squared = 0.0;
+    for (r, row_total) in row_totals.iter().enumerate().filter(|(_, t)| **t > 0.0) {
+        for (c, column_total) in column_totals.iter().enumerate().filter(|(_, t)| **t > 0.0) {
+            let expected = row_total * column_total / total;
+            chi_squared += (cell(r, c) - expected).powi(2) / expected;
+        }
+    }
+    (chi_squared / (total * (rows.min(cols) - 1) as f64)).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn measures_concentration_and_projections() {
+        assert_eq!(gini(&[5.0, 5.0, 5.0]), 0.0);
+        assert!((gini(&[0.0, 0.0, 0.0, 10.0]) - 0.75).abs() < 1e-9);
+        assert_eq!(nakamoto_coefficient(&[60.0, 30.0, 10.0]), 1);
+        assert_eq!(nakamoto_coefficient(&[30.0, 30.0, 40.0]), 2);
+
+        assert!((normal_cdf(0.0) - 0.5).abs() < 1e-6);
+        assert_eq!(poisson_at_least(3.0, 0), 1.0);
+        assert!((poisson_at_least(2.0, 1) - (1.0 - (-2.0f64).exp())).abs() < 1e-9);
+        assert_eq!(expected_additional("Linear", 2.0, 5.0, 10.0), Some(20.0));
+        assert_eq!(expected_additional("guess", 2.0, 5.0, 10.0), None);
+        assert_eq!(leader_hold_probability(&[10.0, 4.0], 14, 0.0), 1.0);
+
+        assert_eq!(share(1.0, 4.0), 25.0);
+        assert_eq!(share(1.0, 0.0), 0.0);
+        assert_eq!(quorum_reached(5, 0), None);
+        assert_eq!(quorum_reached(5, 10), Some(false));
+
+        assert!((cramers_v(&[vec![5.0, 0.0], vec![0.0, 5.0]]) - 1.0).abs() < 1e-9);
+        assert_eq!(cramers_v(&[vec![2.0, 2.0], vec![3.0, 3.0]]), 0.0);
+        assert_eq!(cramers_v(&[vec![4.0, 1.0], vec![0.0, 0.0]]), 0.0);
+    }
+}
@@ -0,0 +1,62 @@
+//! JavaScript bindings, for `wasm-pack build analytics --features wasm`.
+//!
+//! Counts and seconds cross as numbers (exact up to 2^53), token amounts as decimal strings.
+
+use wasm_bindgen::prelude::*;
+
+use crate::numbers::Separators;
+use crate::{duration, numbers, stats};
+
+/// Seconds in a duration such as `3d12h`; throws on anything `poll create --duration` rejects
+#[wasm_bindgen(js_name = parseDuration)]
+pub fn parse_duration(value: &str) -> Result<f64, JsError> {
+    duration::parse(value).map(|seconds| seconds as f64).map_err(|e| JsError::new(&e))
+}
+
+/// Seconds rendered the way the CLI does, e.g. `3d 12h`
+#[wasm_bindgen(js_name = formatDuration)]
+pub fn format_duration(seconds: f64) -> String {
+    duration::format(seconds as u64)
+}
+
+/// A raw token amount (decimal digits) scaled by `decimals` with the separators of `locale`
+/// (e.g. `de_DE`)
+#[wasm_bindgen(js_name = formatAmount)]
+pub fn format_amount(digits: &str, decimals: u32, precision: usize, locale: &str) -> Result<String, JsError> {
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(JsError::new(&format!("'{}' is not an unsigned integer", digits)));
+    }
+    Ok(numbers::format_amount_digits(digits, decimals, precision, Separators::for_locale(locale)))
+}
+
+#[wasm_bindgen]
+pub fn share(part: f64, total: f64) -> f64 {
+    stats::share(part, total)
+}
+
+#[wasm_bindgen]
+pub fn gini(weights: &[f64]) -> f64 {
+    stats::gini(weights)
+}
+
+/// Expects weights sorted largest first, as `whales` ranks voters
+#[wasm_bindgen(js_name = nakamotoCoefficient)]
+pub fn nakamoto_coefficient(sorted_desc: &[f64]) -> usize {
+    stats::nakamoto_coefficient(sorted_desc)
+}
+
+#[wasm_bindgen(js_name = expectedAdditionalVotes)]
+pub fn expected_additional_votes(model: &str, overall_rate: f64, recent_rate: f64, hours_remaining: f64) -> Result<f64, JsError> {
+    stats::expected_additional(model, overall_rate, recent_rate, hours_remaining)
+        .ok_or_else(|| JsError::new("Invalid projection model. Use: linear, recent, decay"))
+}
+
+#[wasm_bindgen(js_name = quorumProbability)]
+pub fn quorum_probability(expected_votes: f64, needed: f64) -> f64 {
+    stats::poisson_at_least(expected_votes, needed as u64)
+}
+
+#[wasm_bindgen(js_name = leaderHoldProbability)]
+pub fn leader_hold_probability(tally: &[f64], vote_count: f64, expected_votes: f64) -> f64 {
+    stats::leader_hold_probability(tally, vote_count as u64, expected_votes)
+}
@@ -0,0 +1,72 @@
+use std::sync::Arc;
+
+use polling_core::client::PollClient;
+use pyo3::exceptions::PyRuntimeError;
+use pyo3::prelude::*;
+use pyo3_async_runtimes::tokio::future_into_py;
+
+/// JSON from the core, handed to Python as the dicts and lists `json.loads` makes
+struct Json(serde_json::Value);
+
+impl<'py> IntoPyObject<'py> for Json {
+    type Target = PyAny;
+    type Output = Bound<'py, PyAny>;
+    type Error = PyErr;
+
+    fn into_pyobject(self, py: Python<'py>) -> PyResult<Self::Output> {
+        py.import("json")?.call_method1("loads", (self.0.to_string(),))
+    }
+}
+
+fn to_py_err(err: anyhow::Error) -> PyErr {
+    PyRuntimeError::new_err(format!("{:#}", err))
+}
+
+/// A read-only connection to a polls contract; every method returns an awaitable
+#[pyclass(name = "PollClient", frozen)]
+struct PyPollClient {
+    inner: Arc<PollClient>,
+}
+
+#[pymethods]
+impl PyPollClient {
+    /// `await PollClient.connect(rpc_url=None, contract_address=None)`; falls back to RPC_URL and CONTRACT_ADDRESS
+    #[staticmethod]
+    #[pyo3(signature = (rpc_url=None, contract_address=None))]
+    fn connect(py: Python<'_>, rpc_url: Option<String>, contract_address: Option<String>) -> PyResult<Bound<'_, PyAny>> {
+        future_into_py(py, async move {
+            let client = PollClient::connect(rpc_url, contract_address).await.map_err(to_py_err)?;
+            Ok(PyPollClient { inner: Arc::new(client) })
+        })
+    }
+
+    /// Number of polls on the contract
+    fn poll_count<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let inner = self.inner.clone();
+        future_into_py(py, async move { inner.poll_count().await.map_err(to_py_err) })
+    }
+
+    /// A poll with its results, as a dict shaped like `export -f json`
+    fn poll<'py>(&self, py: Python<'py>, poll_id: u64) -> PyResult<Bound<'py, PyAny>> {
+        let inner = self.inner.clone();
+        future_into_py(py, async move { inner.poll(poll_id).await.map(Json).map_err(to_py_err) })
+    }
+
+    /// Per-option votes, weights and shares
+    fn results<'py>(&self, py: Python<'py>, poll_id: u64) -> PyResult<Bound<'py, PyAny>> {
+        let inner = self.inner.clone();
+        future_into_py(py, async move { inner.results(poll_id).await.map(Json).map_err(to_py_err) })
+    }
+
+    /// Contract events as a list of dicts, optionally only one poll's
+    #[pyo3(signature = (poll_id=None))]
+    fn events<'py>(&self, py: Python<'py>, poll_id: Option<u64>) -> PyResult<Bound<'py, PyAny>> {
+        let inner = self.inner.clone();
+        future_into_py(py, async move { inner.events(poll_id).await.map(Json).map_err(to_py_err) })
+    }
+}
+
+#[pymodule]
+fn polling_cli(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyPollClient>()
+}
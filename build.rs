@@ -0,0 +1,9 @@
+fn main() {
+    println!("cargo:rerun-if-changed=proto/polls.proto");
+    // Builds should not need protoc installed
+    std::env::set_var("PROTOC", protoc_bin_vendored::protoc_bin_path().expect("vendored protoc"));
+    tonic_prost_build::configure()
+        .build_client(false)
+        .compile_protos(&["proto/polls.proto"], &["proto"])
+        .expect("compile proto/polls.proto");
+}
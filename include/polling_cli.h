@@ -0,0 +1,42 @@
+/*
+ * C API of the polling CLI library, built with the `ffi` feature:
+ *
+ *   cargo rustc --release --lib --features ffi --crate-type cdylib
+ *
+ * Calls block until the RPC endpoint answers. Functions returning a pointer return NULL on
+ * failure; polling_last_error() then says why. Strings returned by the library are freed with
+ * polling_string_free().
+ */
+#ifndef POLLING_CLI_H
+#define POLLING_CLI_H
+
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct PollingClient PollingClient;
+
+/* Either argument may be NULL to use RPC_URL / CONTRACT_ADDRESS from the environment. */
+PollingClient *polling_client_new(const char *rpc_url, const char *contract_address);
+void polling_client_free(PollingClient *client);
+
+/* A poll with its results, as JSON shaped like `export -f json`. */
+char *polling_poll_json(const PollingClient *client, uint64_t poll_id);
+
+/* The vote transaction to sign, as JSON: {"to", "data", "chain_id"}. */
+char *polling_vote_request(const PollingClient *client, uint64_t poll_id, uint64_t option_index);
+
+/* Broadcast a signed vote ("0x..." raw transaction) and wait for its receipt, returned as JSON. */
+char *polling_send_vote(const PollingClient *client, const char *signed_tx);
+
+/* Why the last call on this thread failed; owned by the library. */
+const char *polling_last_error(void);
+void polling_string_free(char *value);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
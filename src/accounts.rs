@@ -0,0 +1,251 @@
+use std::collections::BTreeMap;
+use std::io::{BufRead, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use colored::*;
+use ethers::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{bail_config, bail_user};
+use crate::{redact, store};
+
+const ACCOUNTS_FILE: &str = "accounts.json";
+
+/// Service name that imported keys are stored under in the OS keyring
+const KEYRING_SERVICE: &str = "polling-cli";
+
+/// A named signing identity backed by an encrypted JSON keystore or the OS keyring
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Account {
+    pub address: Address,
+    /// Encrypted JSON keystore; `None` when the key is held in the OS keyring (`key import`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keystore: Option<PathBuf>,
+}
+
+/// Every registered account by name
+pub fn all() -> Result<BTreeMap<String, Account>> {
+    store::load(ACCOUNTS_FILE)
+}
+
+/// Look up a registered account by name
+pub fn get(name: &str) -> Result<Account> {
+    let accounts = all()?;
+    match accounts.get(name) {
+        Some(account) => Ok(account.clone()),
+        None if accounts.is_empty() => bail_user!("No account named '{}'; register one with `accounts add`", name),
+        None => bail_user!(
+            "No account named '{}'. Registered: {}",
+            name,
+            accounts.keys().cloned().collect::<Vec<_>>().join(", ")
+        ),
+    }
+}
+
+/// The address a keystore file declares, without decrypting it
+fn keystore_address(path: &Path) -> Result<Option<Address>> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) => bail_user!("Failed to read keystore {}: {}", path.display(), e),
+    };
+    let json: serde_json::Value = match serde_json::from_str(&content) {
+        Ok(json) => json,
+        Err(_) => bail_user!("{} is not a JSON keystore", path.display()),
+    };
+    Ok(json["address"].as_str().and_then(|a| a.trim_start_matches("0x").parse().ok()))
+}
+
+/// Read a line from the terminal with echo turned off
+#[cfg(unix)]
+fn read_hidden() -> Result<String> {
+    use std::os::unix::io::AsRawFd;
+    let fd = std::io::stdin().as_raw_fd();
+    // SAFETY: termios is plain data filled in by tcgetattr on a valid descriptor
+    let original = unsafe {
+        let mut term = std::mem::zeroed::<libc::termios>();
+        if libc::tcgetattr(fd, &mut term) != 0 {
+            return Err(std::io::Error::last_os_error().into());
+        }
+        term
+    };
+    let mut hidden = original;
+    hidden.c_lflag &= !libc::ECHO;
+    // SAFETY: same descriptor; the original settings are restored below
+    unsafe { libc::tcsetattr(fd, libc::TCSANOW, &hidden) };
+    let mut line = String::new();
+    let read = std::io::stdin().lock().read_line(&mut line);
+    unsafe { libc::tcsetattr(fd, libc::TCSANOW, &original) };
+    eprintln!();
+    read?;
+    Ok(line.trim_end_matches(['\r', '\n']).to_string())
+}
+
+#[cfg(not(unix))]
+fn read_hidden() -> Result<String> {
+    let mut line = String::new();
+    std::io::stdin().lock().read_line(&mut line)?;
+    Ok(line.trim_end_matches(['\r', '\n']).to_string())
+}
+
+/// KEYSTORE_PASSWORD, else a hidden prompt on stderr when attached to a terminal
+fn keystore_password(name: &str) -> Result<String> {
+    if let Ok(password) = std::env::var("KEYSTORE_PASSWORD") {
+        redact::register(&password);
+        return Ok(password);
+    }
+    if !std::io::stdin().is_terminal() {
+        bail_config!("Set KEYSTORE_PASSWORD to unlock account '{}' without a terminal", name);
+    }
+    eprint!("Password for account '{}': ", name);
+    std::io::stderr().flush()?;
+    read_hidden()
+}
+
+fn keyring_entry(name: &str) -> Result<keyring::Entry> {
+    keyring::Entry::new(KEYRING_SERVICE, name)
+        .map_err(|e| crate::error::CliError::Config(format!("The OS keyring is not available: {}", e)).into())
+}
+
+/// A private key as a wallet, registering it for redaction first
+pub fn wallet_from_key(key: &str) -> Result<LocalWallet> {
+    redact::register(key);
+    match key.trim().parse::<LocalWallet>() {
+        Ok(wallet) => Ok(wallet),
+        // The parse error can echo its input, so only say what was wrong
+        Err(_) => bail_config!("Invalid private key: expected 32 bytes of hex, with or without 0x"),
+    }
+}
+
+/// Decrypt a registered account's keystore, or read its key from the OS keyring
+pub fn unlock(name: &str, account: &Account) -> Result<LocalWallet> {
+    let Some(keystore) = &account.keystore else {
+        let key = match keyring_entry(name)?.get_password() {
+            Ok(key) => key,
+            Err(keyring::Error::NoEntry) => bail_config!("Account '{}' has no key in the OS keyring; run `key import {}` again", name, name),
+            Err(e) => bail_config!("Could not read account '{}' from the OS keyring: {}", name, e),
+        };
+        return wallet_from_key(&key);
+    };
+    let password = keystore_password(name)?;
+    match LocalWallet::decrypt_keystore(keystore, password) {
+        Ok(wallet) => {
+            redact::register(&ethers::utils::hex::encode(wallet.signer().to_bytes()));
+            Ok(wallet)
+        }
+        Err(e) => bail_config!("Could not unlock account '{}' from {} (wrong password?): {}", name, keystore.display(), e),
+    }
+}
+
+pub fn add_account(name: &str, keystore: &Path) -> Result<()> {
+    let keystore = std::fs::canonicalize(keystore).unwrap_or_else(|_| keystore.to_path_buf());
+    let address = match keystore_address(&keystore)? {
+        Some(address) => address,
+        // Keystores without an address field have to be unlocked once to learn it
+        None => unlock(name, &Account { address: Address::zero(), keystore: Some(keystore.clone()) })?.address(),
+    };
+
+    let mut accounts = all()?;
+    let replaced = accounts.insert(name.to_string(), Account { address, keystore: Some(keystore) }).is_some();
+    store::save(ACCOUNTS_FILE, &accounts)?;
+
+    let verb = if replaced { "updated" } else { "added" };
+    say!("{} {} {} ({:?})", "✅ Account".green().bold(), name.yellow(), verb.green().bold(), address);
+    Ok(())
+}
+
+/// `key import`: store a private key in the OS keyring and register it as an account.
+/// The key comes from PRIVATE_KEY with `from_env`, else a hidden prompt, else stdin.
+pub fn import_key(name: &str, from_env: bool) -> Result<()> {
+    let key = if from_env {
+        match std::env::var("PRIVATE_KEY") {
+            Ok(key) if !key.trim().is_empty() => key,
+            _ => bail_user!("PRIVATE_KEY is not set; nothing to import"),
+        }
+    } else if std::io::stdin().is_terminal() {
+        eprint!("Private key for '{}': ", name);
+        std::io::stderr().flush()?;
+        read_hidden()?
+    } else {
+        let mut key = String::new();
+        std::io::stdin().lock().read_line(&mut key)?;
+        key
+    };
+    let key = key.trim().to_string();
+    let address = wallet_from_key(&key)?.address();
+
+    if let Err(e) = keyring_entry(name)?.set_password(&key) {
+        bail_config!("Could not store the key in the OS keyring: {}", e);
+    }
+    let mut accounts = all()?;
+    accounts.insert(name.to_string(), Account { address, keystore: None });
+    store::save(ACCOUNTS_FILE, &accounts)?;
+
+    say!("{} {} ({:?}) {}", "✅ Key".green().bold(), name.yellow(), address, "stored in the OS keyring".green().bold());
+    say!("Sign with `--from {}` or POLL_ACCOUNT={}.", name, name);
+    if from_env {
+        say!("{}", "⚠️  Remove PRIVATE_KEY from your .env now that the key is in the keyring.".yellow());
+    }
+    Ok(())
+}
+
+pub fn remove_account(name: &str) -> Result<()> {
+    let mut accounts = all()?;
+    let Some(account) = accounts.remove(name) else {
+        bail_user!("No account named '{}'", name);
+    };
+    store::save(ACCOUNTS_FILE, &accounts)?;
+    if account.keystore.is_none() {
+        match keyring_entry(name)?.delete_credential() {
+            Ok(()) | Err(keyring::Error::NoEntry) => {}
+            Err(e) => bail_config!("Removed account '{}', but could not delete its key from the OS keyring: {}", name, e),
+        }
+        say!("{} {} and its key in the OS keyring", "🗑️  Removed account".green().bold(), name.yellow());
+    } else {
+        say!("{} {} (the keystore file was left in place)", "🗑️  Removed account".green().bold(), name.yellow());
+    }
+    Ok(())
+}
+
+pub fn list_accounts() -> Result<()> {
+    let accounts = all()?;
+
+    say!("\n{}", "🔑 Signing Accounts:".cyan().bold());
+    if accounts.is_empty() {
+        say!("No accounts registered yet. Add one with `accounts add <name> --keystore <path>`.");
+        return Ok(());
+    }
+    for (name, account) in &accounts {
+        say!("  {} {:?}", format!("{:<16}", name).yellow().bold(), account.address);
+        let source = match &account.keystore {
+            Some(keystore) => keystore.display().to_string(),
+            None => "OS keyring".to_string(),
+        };
+        say!("  {:<16} {}", "", source.dimmed());
+    }
+    say!("\nUse one with `--from <name>`.");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_keystore_address_without_decrypting() {
+        let dir = std::env::temp_dir().join(format!("poll-keystore-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let with_address = dir.join("with.json");
+        std::fs::write(&with_address, r#"{"address":"f39fd6e51aad88f6f4ce6ab8827279cfffb92266","crypto":{}}"#).unwrap();
+        let without_address = dir.join("without.json");
+        std::fs::write(&without_address, r#"{"crypto":{}}"#).unwrap();
+
+        assert_eq!(
+            keystore_address(&with_address).unwrap(),
+            Some("0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266".parse().unwrap())
+        );
+        assert_eq!(keystore_address(&without_address).unwrap(), None);
+        assert!(keystore_address(&dir.join("missing.json")).is_err());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,392 @@
+use std::collections::HashSet;
+
+use anyhow::Result;
+use colored::*;
+use ethers::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::error::bail_user;
+use crate::indexer::{ContractEvent, IndexedEvent, DEFAULT_REORG_DEPTH};
+use crate::finality::{self, Finality};
+use crate::my_polls::{CreatorPoll, PollState};
+use crate::numbers::SaturatingU64;
+use crate::{duration, numbers, output, quorum, timezone, PollManager};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertKind {
+    /// Ends within the alert window without having reached minParticipation
+    ExpiringShortOfQuorum,
+    /// Past its end time but never closed
+    Unclosed,
+    /// Still open with a lead smaller than the weight non-voters hold
+    Contested,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CreatorAlert {
+    pub poll_id: u64,
+    pub question: String,
+    pub kind: AlertKind,
+    pub end_time: u64,
+    pub total_votes: u64,
+    pub min_participation: u64,
+    /// Command that deals with the alert
+    pub suggestion: String,
+    /// Leader, margin and outstanding weight of a contested poll
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub finality: Option<Finality>,
+}
+
+/// Extension that lets the average vote rate so far reach quorum, rounded up
+/// to whole hours; `fallback` when there are no votes to extrapolate from
+pub fn suggested_extension(poll: &CreatorPoll, now: u64, fallback: u64) -> u64 {
+    if poll.total_votes == 0 {
+        return fallback;
+    }
+    let elapsed = now.saturating_sub(poll.created_at).max(60) as f64;
+    let rate = poll.total_votes as f64 / elapsed;
+    let needed = poll.min_participation.saturating_sub(poll.total_votes) as f64 / rate;
+    let extra = (needed - poll.end_time.saturating_sub(now) as f64).max(0.0) as u64;
+    extra.div_ceil(3_600).max(1) * 3_600
+}
+
+/// Alerts for the creator's polls as of `now`, looking `within` seconds ahead
+pub fn creator_alerts(polls: &[CreatorPoll], within: u64, now: u64) -> Vec<CreatorAlert> {
+    let mut alerts = Vec::new();
+    for poll in polls {
+        let (kind, suggestion) = match poll.state {
+            PollState::NeedsClosing => (AlertKind::Unclosed, format!("close -p {}", poll.poll_id)),
+            PollState::Active
+                if poll.end_time <= now.saturating_add(within)
+                    && quorum::reached(poll.total_votes, poll.min_participation) == Some(false) =>
+            {
+                let extension = duration::format(suggested_extension(poll, now, within)).replace(' ', "");
+                (AlertKind::ExpiringShortOfQuorum, format!("extend -p {} -d {}", poll.poll_id, extension))
+            }
+            _ => continue,
+        };
+        alerts.push(CreatorAlert {
+            poll_id: poll.poll_id,
+            question: poll.question.clone(),
+            kind,
+            end_time: poll.end_time,
+            total_votes: poll.total_votes,
+            min_participation: poll.min_participation,
+            suggestion,
+            finality: None,
+        });
+    }
+    alerts
+}
+
+/// An alert for an active poll whose outcome non-voters could still flip
+pub fn contested_alert(poll: &CreatorPoll, finality: Finality) -> Option<CreatorAlert> {
+    (poll.state == PollState::Active && finality.contested).then(|| CreatorAlert {
+        poll_id: poll.poll_id,
+        question: poll.question.clone(),
+        kind: AlertKind::Contested,
+        end_time: poll.end_time,
+        total_votes: poll.total_votes,
+        min_participation: poll.min_participation,
+        suggestion: format!("results -p {} --follow", poll.poll_id),
+        finality: Some(finality),
+    })
+}
+
+fn print_alert(alert: &CreatorAlert, now: u64) {
+    match alert.kind {
+        AlertKind::ExpiringShortOfQuorum => say!("{} Poll #{} \"{}\" ends {} with {} of {} votes",
+            "⏳".yellow(), alert.poll_id, alert.question, timezone::relative_to(alert.end_time, now),
+            numbers::format_integer(alert.total_votes), numbers::format_integer(alert.min_participation)),
+        AlertKind::Unclosed => say!("{} Poll #{} \"{}\" ended {} but is still open",
+            "🔔".red(), alert.poll_id, alert.question, timezone::relative_to(alert.end_time, now)),
+        AlertKind::Contested => {
+            say!("{} Poll #{} \"{}\" ends {} and could still flip",
+                "⚖️ ".yellow(), alert.poll_id, alert.question, timezone::relative_to(alert.end_time, now));
+            if let Some(finality) = &alert.finality {
+                print!("   ");
+                finality::print_finality(finality);
+            }
+        }
+    }
+    say!("   {} {}", "Suggested:".cyan(), alert.suggestion.white().bold());
+}
+
+/// Something about the caller's delegation that needs attention
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum DelegationAlert {
+    /// The caller's delegate has not voted for them on a poll ending within the alert window
+    DelegateMissedVote { poll_id: u64, question: String, end_time: u64, delegate: Address },
+    DelegatedToYou { delegator: Address, timestamp: u64, transaction_hash: H256 },
+    UndelegatedFromYou { delegator: Address, timestamp: u64, transaction_hash: H256 },
+}
+
+impl DelegationAlert {
+    /// Identity used to report each alert once per run of the watcher
+    fn key(&self) -> String {
+        match self {
+            DelegationAlert::DelegateMissedVote { poll_id, delegate, .. } => format!("missed:{}:{:?}", poll_id, delegate),
+            DelegationAlert::DelegatedToYou { transaction_hash, delegator, .. }
+            | DelegationAlert::UndelegatedFromYou { transaction_hash, delegator, .. } => format!("event:{:?}:{:?}", transaction_hash, delegator),
+        }
+    }
+}
+
+/// Delegations to and away from `me` at or after `since`
+pub fn delegation_changes(events: &[IndexedEvent], me: Address, since: u64) -> Vec<DelegationAlert> {
+    events.iter()
+        .filter(|event| event.timestamp >= since)
+        .filter_map(|event| match event.event {
+            ContractEvent::DelegateSet { delegator, delegate } if delegate == me => Some(DelegationAlert::DelegatedToYou {
+                delegator, timestamp: event.timestamp, transaction_hash: event.transaction_hash,
+            }),
+            ContractEvent::DelegateRemoved { delegator, delegate } if delegate == me => Some(DelegationAlert::UndelegatedFromYou {
+                delegator, timestamp: event.timestamp, transaction_hash: event.transaction_hash,
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+fn print_delegation_alert(alert: &DelegationAlert, now: u64) {
+    match alert {
+        DelegationAlert::DelegateMissedVote { poll_id, question, end_time, delegate } => {
+            say!("{} Your delegate {:?} has not voted for you on poll #{} \"{}\", which ends {}",
+                "⏳".yellow(), delegate, poll_id, question, timezone::relative_to(*end_time, now));
+            say!("   {} {}", "Suggested:".cyan(), format!("vote -p {} -o <option>", poll_id).white().bold());
+        }
+        DelegationAlert::DelegatedToYou { delegator, timestamp, .. } => say!("{} {:?} delegated to you {}",
+            "🤝".green(), delegator, timezone::relative_to(*timestamp, now)),
+        DelegationAlert::UndelegatedFromYou { delegator, timestamp, .. } => say!("{} {:?} stopped delegating to you {}",
+            "👋".yellow(), delegator, timezone::relative_to(*timestamp, now)),
+    }
+}
+
+impl<M: Middleware + 'static> PollManager<M> {
+    /// Creator alerts as of `now`, plus contested active polls whose electorate is known
+    async fn current_creator_alerts(&self, within: u64, now: u64) -> Result<Vec<CreatorAlert>> {
+        let polls = self.creator_polls(now).await?;
+        let mut alerts = creator_alerts(&polls, within, now);
+        for poll in polls.iter().filter(|poll| poll.state == PollState::Active) {
+            let poll_data = self.poll_data(U256::from(poll.poll_id)).await?;
+            let tally = self.poll_tally(poll.poll_id, &poll_data.2, poll_data.7).await?;
+            if let Some(finality) = self.results_finality(poll.poll_id, &poll_data, &tally, &mut None).await {
+                alerts.extend(contested_alert(poll, finality));
+            }
+        }
+        Ok(alerts)
+    }
+
+    /// Warn about the caller's polls that are about to miss quorum or were never
+    /// closed; with `watch`, re-check every `watch` seconds and report new alerts only
+    pub async fn creator_alerts(&self, within: u64, watch: Option<u64>, format: &str) -> Result<()> {
+        let json = match format.to_lowercase().as_str() {
+            "json" => true,
+            "table" => false,
+            _ => bail_user!("Unsupported format: {}. Use table or json", format),
+        };
+
+        let Some(interval) = watch else {
+            let now = chrono::Utc::now().timestamp() as u64;
+            let alerts = self.current_creator_alerts(within, now).await?;
+            if json {
+                println!("{}", serde_json::to_string_pretty(&alerts)?);
+                return Ok(());
+            }
+            say!("\n{}", "🚨 Creator Alerts:".cyan().bold());
+            if alerts.is_empty() {
+                say!("Nothing needs attention: no unclosed or contested polls and none expiring within {} short of quorum.", duration::format(within));
+            }
+            for alert in &alerts {
+                print_alert(alert, now);
+            }
+            return Ok(());
+        };
+
+        if interval == 0 {
+            bail_user!("--watch interval must be longer than zero");
+        }
+        if !json {
+            say!("{} every {} (Ctrl-C to stop)", "👀 Watching your polls".cyan().bold(), duration::format(interval));
+        }
+
+        // An alert is reported once per run of the daemon, until it stops applying
+        let mut reported: HashSet<(u64, AlertKind)> = HashSet::new();
+        loop {
+            let now = chrono::Utc::now().timestamp() as u64;
+            match self.current_creator_alerts(within, now).await {
+                Ok(alerts) => {
+                    let current: HashSet<(u64, AlertKind)> = alerts.iter().map(|a| (a.poll_id, a.kind)).collect();
+                    for alert in alerts.iter().filter(|a| !reported.contains(&(a.poll_id, a.kind))) {
+                        if json {
+                            println!("{}", serde_json::to_string(alert)?);
+                        } else {
+                            print_alert(alert, now);
+                        }
+                    }
+                    reported = current;
+                }
+                // Keep watching through RPC hiccups
+                Err(e) => eprintln!("{} {:#}", output::render("⚠️  Check failed:".to_string()).yellow(), e),
+            }
+            tokio::time::sleep(std::time::Duration::from_secs(interval)).await;
+        }
+    }
+
+    /// Delegation alerts as of `now`: polls ending within `within` that the delegate has not
+    /// voted on for the caller, and delegations to or from the caller since `since`
+    async fn current_delegation_alerts(&self, within: u64, since: u64, now: u64) -> Result<Vec<DelegationAlert>> {
+        let me = self.sender;
+        let mut alerts = Vec::new();
+
+        let delegate = self.contract.get_delegate(me).call().await?;
+        if !delegate.is_zero() {
+            let poll_count = self.contract.poll_count().call().await?.to_u64_saturating();
+            for poll_id in 0..poll_count {
+                let poll = self.poll_data(U256::from(poll_id)).await?;
+                let end_time = poll.5.to_u64_saturating();
+                if poll.6 != 0 || end_time <= now || end_time > now.saturating_add(within) {
+                    continue;
+                }
+                if !self.contract.has_user_voted(U256::from(poll_id), me).call().await? {
+                    alerts.push(DelegationAlert::DelegateMissedVote { poll_id, question: poll.1.clone(), end_time, delegate });
+                }
+            }
+        }
+
+        // Synced fresh on every check so a watcher sees new delegations
+        let (index, _) = self.sync_events(None, None, DEFAULT_REORG_DEPTH, &indicatif::ProgressBar::hidden()).await?;
+        alerts.extend(delegation_changes(&index.events, me, since));
+        Ok(alerts)
+    }
+
+    /// Warn when the caller's delegate is letting polls close without their vote, and when
+    /// someone delegates to or away from them; with `watch`, re-check and report new alerts only
+    pub async fn delegation_alerts(&self, within: u64, since: u64, watch: Option<u64>, format: &str) -> Result<()> {
+        let json = match format.to_lowercase().as_str() {
+            "json" => true,
+            "table" => false,
+            _ => bail_user!("Unsupported format: {}. Use table or json", format),
+        };
+        let started = chrono::Utc::now().timestamp() as u64;
+
+        let Some(interval) = watch else {
+            let alerts = self.current_delegation_alerts(within, started.saturating_sub(since), started).await?;
+            if json {
+                println!("{}", serde_json::to_string_pretty(&alerts)?);
+                return Ok(());
+            }
+            say!("\n{}", "🚨 Delegation Alerts:".cyan().bold());
+            if alerts.is_empty() {
+                say!("Nothing needs attention: no polls ending within {} without your vote, and no delegation changes in the last {}.",
+                    duration::format(within), duration::format(since));
+            }
+            for alert in &alerts {
+                print_delegation_alert(alert, started);
+            }
+            return Ok(());
+        };
+
+        if interval == 0 {
+            bail_user!("--watch interval must be longer than zero");
+        }
+        if !json {
+            say!("{} every {} (Ctrl-C to stop)", "👀 Watching your delegation".cyan().bold(), duration::format(interval));
+        }
+
+        let mut reported: HashSet<String> = HashSet::new();
+        loop {
+            let now = chrono::Utc::now().timestamp() as u64;
+            match self.current_delegation_alerts(within, started.saturating_sub(since), now).await {
+                Ok(alerts) => {
+                    for alert in alerts.iter().filter(|a| !reported.contains(&a.key())) {
+                        if json {
+                            println!("{}", serde_json::to_string(alert)?);
+                        } else {
+                            print_delegation_alert(alert, now);
+                        }
+                    }
+                    reported = alerts.iter().map(DelegationAlert::key).collect();
+                }
+                Err(e) => eprintln!("{} {:#}", output::render("⚠️  Check failed:".to_string()).yellow(), e),
+            }
+            tokio::time::sleep(std::time::Duration::from_secs(interval)).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOUR: u64 = 3_600;
+
+    fn poll(poll_id: u64, state: PollState, end_time: u64, total_votes: u64, min_participation: u64) -> CreatorPoll {
+        CreatorPoll { poll_id, question: format!("Q{}", poll_id), created_at: 0, end_time, state, total_votes, min_participation }
+    }
+
+    #[test]
+    fn alerts_on_expiring_and_unclosed_polls() {
+        let now = 10 * HOUR;
+        let polls = [
+            poll(0, PollState::Active, 12 * HOUR, 5, 20),
+            poll(1, PollState::Active, 48 * HOUR, 5, 20),
+            poll(2, PollState::Active, 12 * HOUR, 20, 20),
+            poll(3, PollState::NeedsClosing, 9 * HOUR, 0, 0),
+            poll(4, PollState::Closed, 9 * HOUR, 0, 20),
+        ];
+        let alerts = creator_alerts(&polls, 24 * HOUR, now);
+        let kinds: Vec<(u64, AlertKind)> = alerts.iter().map(|a| (a.poll_id, a.kind)).collect();
+        assert_eq!(kinds, [(0, AlertKind::ExpiringShortOfQuorum), (3, AlertKind::Unclosed)]);
+        assert_eq!(alerts[1].suggestion, "close -p 3");
+    }
+
+    #[test]
+    fn alerts_on_contested_active_polls() {
+        let finality = |contested| Finality {
+            leader: "Yes".to_string(),
+            runner_up: Some("No".to_string()),
+            margin: U256::from(3),
+            outstanding: U256::from(if contested { 5 } else { 2 }),
+            contested,
+            metric: "votes".to_string(),
+        };
+        let open = poll(0, PollState::Active, 12 * HOUR, 5, 0);
+        let alert = contested_alert(&open, finality(true)).unwrap();
+        assert_eq!((alert.kind, alert.suggestion.as_str()), (AlertKind::Contested, "results -p 0 --follow"));
+        assert_eq!(contested_alert(&open, finality(false)), None);
+        assert_eq!(contested_alert(&poll(1, PollState::NeedsClosing, 9 * HOUR, 5, 0), finality(true)), None);
+    }
+
+    #[test]
+    fn suggests_extension_from_vote_rate() {
+        // 5 votes in 10h is 0.5/h: 15 more votes need 30h, 2h are left
+        let expiring = poll(0, PollState::Active, 12 * HOUR, 5, 20);
+        assert_eq!(suggested_extension(&expiring, 10 * HOUR, 24 * HOUR), 28 * HOUR);
+        assert_eq!(creator_alerts(&[expiring], 24 * HOUR, 10 * HOUR)[0].suggestion, "extend -p 0 -d 1d4h");
+
+        let silent = poll(1, PollState::Active, 12 * HOUR, 0, 20);
+        assert_eq!(suggested_extension(&silent, 10 * HOUR, 24 * HOUR), 24 * HOUR);
+    }
+
+    #[test]
+    fn reports_delegations_to_me_since_the_cutoff() {
+        let (me, alice, bob) = (Address::repeat_byte(1), Address::repeat_byte(2), Address::repeat_byte(3));
+        let event = |timestamp, event| IndexedEvent {
+            block_number: timestamp, block_hash: H256::zero(), transaction_hash: H256::from_low_u64_be(timestamp), log_index: 0, timestamp, event,
+        };
+        let events = [
+            event(100, ContractEvent::DelegateSet { delegator: alice, delegate: me }),
+            event(200, ContractEvent::DelegateSet { delegator: bob, delegate: me }),
+            event(300, ContractEvent::DelegateSet { delegator: alice, delegate: bob }),
+            event(400, ContractEvent::DelegateRemoved { delegator: bob, delegate: me }),
+        ];
+        let changes = delegation_changes(&events, me, 150);
+        assert_eq!(changes, [
+            DelegationAlert::DelegatedToYou { delegator: bob, timestamp: 200, transaction_hash: H256::from_low_u64_be(200) },
+            DelegationAlert::UndelegatedFromYou { delegator: bob, timestamp: 400, transaction_hash: H256::from_low_u64_be(400) },
+        ]);
+    }
+}
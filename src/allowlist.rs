@@ -0,0 +1,209 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use colored::*;
+use ethers::prelude::*;
+use ethers::utils::keccak256;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{bail_config, bail_user};
+use crate::PollManager;
+
+/// Leaf for `account`: keccak256(abi.encodePacked(account)), as checked by OpenZeppelin's MerkleProof
+pub fn leaf(account: Address) -> H256 {
+    H256(keccak256(account.as_bytes()))
+}
+
+/// Parent of two nodes, hashed in sorted order so proofs need no left/right flags
+fn hash_pair(a: H256, b: H256) -> H256 {
+    let (low, high) = if a <= b { (a, b) } else { (b, a) };
+    H256(keccak256([low.as_bytes(), high.as_bytes()].concat()))
+}
+
+/// A sorted-pair Merkle tree; a node without a sibling moves up a level unchanged
+pub struct MerkleTree {
+    /// Leaves first, root last
+    layers: Vec<Vec<H256>>,
+}
+
+impl MerkleTree {
+    pub fn new(mut leaves: Vec<H256>) -> Self {
+        leaves.sort();
+        let mut layers = vec![leaves];
+        while layers.last().is_some_and(|layer| layer.len() > 1) {
+            let next = layers.last().expect("checked above").chunks(2)
+                .map(|pair| match pair {
+                    [a, b] => hash_pair(*a, *b),
+                    [a] => *a,
+                    _ => unreachable!("chunks of two"),
+                })
+                .collect();
+            layers.push(next);
+        }
+        MerkleTree { layers }
+    }
+
+    pub fn root(&self) -> H256 {
+        self.layers.last().and_then(|layer| layer.first()).copied().unwrap_or_default()
+    }
+
+    /// Sibling hashes from `leaf` up to the root; `None` if it is not in the tree
+    pub fn proof(&self, leaf: H256) -> Option<Vec<H256>> {
+        let mut index = self.layers[0].binary_search(&leaf).ok()?;
+        let mut proof = Vec::new();
+        for layer in &self.layers[..self.layers.len() - 1] {
+            if let Some(sibling) = layer.get(index ^ 1) {
+                proof.push(*sibling);
+            }
+            index /= 2;
+        }
+        Some(proof)
+    }
+}
+
+/// Whether `proof` links `leaf` to `root`, the same check MerkleProof.verify makes on-chain
+pub fn verify(proof: &[H256], root: H256, leaf: H256) -> bool {
+    proof.iter().fold(leaf, |node, sibling| hash_pair(node, *sibling)) == root
+}
+
+/// What `allowlist build` writes: the root to configure on the poll and every voter's proof
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Allowlist {
+    pub root: H256,
+    pub count: usize,
+    /// The CSV file or `token 0x… at block N`
+    pub source: String,
+    pub proofs: BTreeMap<Address, Vec<H256>>,
+}
+
+impl Allowlist {
+    pub fn new(accounts: &BTreeSet<Address>, source: String) -> Self {
+        let tree = MerkleTree::new(accounts.iter().map(|account| leaf(*account)).collect());
+        let proofs = accounts.iter()
+            .map(|account| (*account, tree.proof(leaf(*account)).expect("every account is a leaf")))
+            .collect();
+        Allowlist { root: tree.root(), count: accounts.len(), source, proofs }
+    }
+}
+
+/// Addresses from the first column of a CSV; a first row that is not an address is taken as a header
+pub fn read_csv(path: &Path) -> Result<BTreeSet<Address>> {
+    let mut reader = csv::ReaderBuilder::new().has_headers(false).flexible(true).trim(csv::Trim::All)
+        .from_path(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let mut accounts = BTreeSet::new();
+    for (line, record) in reader.records().enumerate() {
+        let record = record?;
+        let field = record.get(0).unwrap_or_default();
+        if field.is_empty() {
+            continue;
+        }
+        match field.parse::<Address>() {
+            Ok(account) => {
+                accounts.insert(account);
+            }
+            Err(_) if line == 0 => {}
+            Err(_) => bail_user!("{} line {}: '{}' is not an address", path.display(), line + 1, field),
+        }
+    }
+    if accounts.is_empty() {
+        bail_user!("{} lists no addresses", path.display());
+    }
+    Ok(accounts)
+}
+
+fn save(allowlist: &Allowlist, output_path: &str) -> Result<()> {
+    std::fs::write(output_path, serde_json::to_string_pretty(allowlist)?)?;
+    say!("{} {} addresses from {}", "🌳 Allowlist built:".green().bold(), allowlist.count, allowlist.source);
+    say!("{} {:?}", "Merkle root:".cyan().bold(), allowlist.root);
+    say!("{} {}", "Proofs:".cyan().bold(), output_path.yellow());
+    say!("Voters can look up theirs with `allowlist prove <address> --file {}`.", output_path);
+    Ok(())
+}
+
+/// `allowlist build --csv`: no chain access needed
+pub fn build_from_csv(csv_path: &Path, output_path: &str) -> Result<()> {
+    let accounts = read_csv(csv_path)?;
+    save(&Allowlist::new(&accounts, csv_path.display().to_string()), output_path)
+}
+
+/// `allowlist prove`: print `address`'s proof from an allowlist file, checked against its root
+pub fn prove(address: Address, file: &Path, format: &str) -> Result<()> {
+    let json = match format.to_lowercase().as_str() {
+        "json" => true,
+        "table" => false,
+        _ => bail_user!("Unsupported format: {}. Use table or json", format),
+    };
+    if !file.exists() {
+        bail_config!("Allowlist {} not found; build it with `allowlist build`", file.display());
+    }
+    let allowlist: Allowlist = serde_json::from_slice(&std::fs::read(file)?)
+        .map_err(|e| crate::error::CliError::Config(format!("{} is not an allowlist: {}", file.display(), e)))?;
+    let Some(proof) = allowlist.proofs.get(&address) else {
+        bail_user!("{:?} is not on the allowlist in {}", address, file.display());
+    };
+    if !verify(proof, allowlist.root, leaf(address)) {
+        bail_config!("The proof for {:?} in {} does not match its root; rebuild the allowlist", address, file.display());
+    }
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&serde_json::json!({
+            "address": address,
+            "root": allowlist.root,
+            "proof": proof,
+        }))?);
+    } else {
+        say!("{} {:?}", "✅ On the allowlist:".green().bold(), address);
+        say!("{} {:?}", "Merkle root:".cyan().bold(), allowlist.root);
+        say!("{}", "Proof:".cyan().bold());
+        for node in proof {
+            say!("  {:?}", node);
+        }
+    }
+    Ok(())
+}
+
+impl<M: Middleware + 'static> PollManager<M> {
+    /// `allowlist build --holders-at`: everyone holding at least `min_balance` tokens at `block`
+    pub async fn build_from_holders(&self, token: Option<Address>, block: u64, min_balance: u64, output_path: &str) -> Result<()> {
+        let token = self.token_or_governance(token)?;
+        // Whole tokens, converted to wei like createPoll's minimum token balance; 0 keeps any non-zero holder
+        let minimum = if min_balance == 0 { U256::one() } else { U256::from(min_balance) * U256::exp10(18) };
+        let accounts: BTreeSet<Address> = self.holder_balances(token.address(), block).await?.into_iter()
+            .filter(|(_, balance)| *balance >= minimum)
+            .map(|(account, _)| account)
+            .collect();
+        if accounts.is_empty() {
+            bail_user!("No holder of {:?} had the minimum balance at block {}", token.address(), block);
+        }
+        save(&Allowlist::new(&accounts, format!("token {:?} at block {}", token.address(), block)), output_path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_member_proves_and_outsiders_do_not() {
+        for size in [1u8, 2, 5, 8] {
+            let accounts: BTreeSet<Address> = (1..=size).map(Address::repeat_byte).collect();
+            let allowlist = Allowlist::new(&accounts, "test".to_string());
+            for (account, proof) in &allowlist.proofs {
+                assert!(verify(proof, allowlist.root, leaf(*account)), "{} members", size);
+            }
+            let (first, proof) = allowlist.proofs.iter().next().unwrap();
+            assert!(!verify(proof, allowlist.root, leaf(Address::repeat_byte(0xee))));
+            if size > 1 {
+                assert!(!verify(&[], allowlist.root, leaf(*first)));
+            }
+
+            let json = serde_json::to_string(&allowlist).unwrap();
+            assert_eq!(serde_json::from_str::<Allowlist>(&json).unwrap().proofs, allowlist.proofs);
+        }
+        // Two leaves: the root is their sorted pair hash
+        let (a, b) = (Address::repeat_byte(1), Address::repeat_byte(2));
+        assert_eq!(MerkleTree::new(vec![leaf(a), leaf(b)]).root(), hash_pair(leaf(b), leaf(a)));
+    }
+}
@@ -0,0 +1,308 @@
+use std::collections::BTreeSet;
+
+use anyhow::{Context, Result};
+use colored::*;
+use ethers::prelude::*;
+use serde_json::{json, Value};
+
+use crate::error::{bail_config, bail_user, CliError};
+use crate::matrix::MatrixClient;
+use crate::numbers::SaturatingU64;
+use crate::{duration, output, poll_ref, redact, timezone, PollManager};
+
+/// Length every announcement is kept to: X allows 280 characters, Farcaster 320 bytes
+const MAX_LENGTH: usize = 280;
+
+/// Where announcements are posted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, clap::ValueEnum)]
+pub enum Channel {
+    /// X (Twitter), with an OAuth 2.0 user access token in X_ACCESS_TOKEN
+    X,
+    /// Farcaster through Neynar, with NEYNAR_API_KEY and FARCASTER_SIGNER_UUID
+    Farcaster,
+    /// A Matrix room: MATRIX_HOMESERVER, MATRIX_ACCESS_TOKEN and MATRIX_ROOM_ID
+    Matrix,
+}
+
+/// The poll lifecycle event an announcement is about
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum PollEvent {
+    Created,
+    Closed,
+}
+
+/// What an announcement says about a poll
+#[derive(Debug, Clone)]
+pub struct Announcement {
+    pub event: PollEvent,
+    pub poll_id: u64,
+    pub question: String,
+    pub options: Vec<String>,
+    pub end_time: u64,
+    pub link: String,
+    pub total_votes: u64,
+    /// Winning option and its share of the deciding metric; `None` on a tie or without votes
+    pub leader: Option<(String, f64)>,
+    pub metric: &'static str,
+}
+
+fn truncate(text: &str, max_bytes: usize) -> String {
+    if text.len() <= max_bytes {
+        return text.to_string();
+    }
+    let mut end = max_bytes.saturating_sub('…'.len_utf8());
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}…", text[..end].trim_end())
+}
+
+impl Announcement {
+    /// The post text, at most `MAX_LENGTH` bytes: the options collapse to a count and then the
+    /// question is shortened when the full text does not fit
+    pub fn render(&self) -> String {
+        let headline = match self.event {
+            PollEvent::Created => format!("🗳️ New poll #{}", self.poll_id),
+            PollEvent::Closed => format!("✅ Poll #{} closed", self.poll_id),
+        };
+        let votes = format!("{} vote{}", self.total_votes, if self.total_votes == 1 { "" } else { "s" });
+        // The second line in full and in its short form, then the lines that always stay
+        let (second, short, rest) = match self.event {
+            PollEvent::Created => (
+                format!("Options: {}", self.options.join(" / ")),
+                format!("{} options", self.options.len()),
+                vec![format!("Voting ends {}", timezone::format_in(self.end_time, timezone::DisplayZone::Utc))],
+            ),
+            PollEvent::Closed => {
+                let result = match &self.leader {
+                    Some((option, share)) => format!("Result: {} ({:.1}% of {}), {}", option, share, self.metric, votes),
+                    None if self.total_votes == 0 => "No votes were cast".to_string(),
+                    None => format!("Result: tie, {}", votes),
+                };
+                (result.clone(), result, Vec::new())
+            }
+        };
+
+        let compose = |question: &str, second: &str| {
+            let mut lines = vec![format!("{}: {}", headline, question), second.to_string()];
+            lines.extend(rest.iter().cloned());
+            lines.push(self.link.clone());
+            lines.join("\n")
+        };
+        let full = compose(&self.question, &second);
+        if full.len() <= MAX_LENGTH {
+            return full;
+        }
+        let shorter = compose(&self.question, &short);
+        if shorter.len() <= MAX_LENGTH {
+            return shorter;
+        }
+        let room = MAX_LENGTH.saturating_sub(shorter.len() - self.question.len());
+        truncate(&compose(&truncate(&self.question, room), &short), MAX_LENGTH)
+    }
+}
+
+/// Credentials for the chosen channels, read from the environment
+struct Publisher {
+    channels: Vec<Channel>,
+    matrix: Option<MatrixClient>,
+    http: reqwest::Client,
+}
+
+fn env(name: &str) -> Result<String> {
+    match std::env::var(name).ok().filter(|value| !value.is_empty()) {
+        Some(value) => Ok(value),
+        None => bail_config!("{} must be set to announce there", name),
+    }
+}
+
+impl Publisher {
+    /// Check every channel's configuration up front, so nothing is posted when one is missing
+    fn from_env(channels: &[Channel]) -> Result<Self> {
+        let mut matrix = None;
+        for channel in channels {
+            let secrets = match channel {
+                Channel::X => vec![env("X_ACCESS_TOKEN")?],
+                Channel::Farcaster => vec![env("NEYNAR_API_KEY")?, env("FARCASTER_SIGNER_UUID")?],
+                Channel::Matrix => {
+                    // The client checks and redacts its own settings
+                    matrix = Some(MatrixClient::from_env()?);
+                    env("MATRIX_ROOM_ID")?;
+                    Vec::new()
+                }
+            };
+            for secret in &secrets {
+                redact::register(secret);
+            }
+        }
+        let channels = channels.iter().copied().collect::<BTreeSet<_>>().into_iter().collect();
+        Ok(Self { channels, matrix, http: reqwest::Client::new() })
+    }
+
+    async fn post(&self, channel: Channel, text: &str) -> Result<String> {
+        let request = match channel {
+            Channel::Matrix => {
+                let matrix = self.matrix.as_ref().expect("created for the Matrix channel");
+                return Ok(format!("event {}", matrix.notify(text).await?));
+            }
+            Channel::X => self.http
+                .post(format!("{}/2/tweets", std::env::var("X_API_URL").unwrap_or_else(|_| "https://api.x.com".to_string()).trim_end_matches('/')))
+                .bearer_auth(env("X_ACCESS_TOKEN")?)
+                .json(&json!({ "text": text })),
+            Channel::Farcaster => self.http
+                .post(format!("{}/v2/farcaster/cast", std::env::var("NEYNAR_API_URL").unwrap_or_else(|_| "https://api.neynar.com".to_string()).trim_end_matches('/')))
+                .header("x-api-key", env("NEYNAR_API_KEY")?)
+                .json(&json!({ "signer_uuid": env("FARCASTER_SIGNER_UUID")?, "text": text })),
+        };
+        let response = request.send().await.with_context(|| format!("Failed to reach {:?}", channel))?;
+        let status = response.status();
+        if !status.is_success() {
+            let text = response.text().await.unwrap_or_default();
+            return Err(CliError::Rpc(format!("{:?} returned {}: {}", channel, status, text)).into());
+        }
+        let body: Value = response.json().await.with_context(|| format!("{:?} returned invalid JSON", channel))?;
+        Ok(match channel {
+            Channel::X => match body["data"]["id"].as_str() {
+                Some(id) => format!("https://x.com/i/web/status/{}", id),
+                None => "posted".to_string(),
+            },
+            Channel::Farcaster => match body["cast"]["hash"].as_str() {
+                Some(hash) => format!("cast {}", hash),
+                None => "posted".to_string(),
+            },
+            Channel::Matrix => unreachable!("posted above"),
+        })
+    }
+
+    async fn publish(&self, announcement: &Announcement) -> Result<()> {
+        let text = announcement.render();
+        for channel in &self.channels {
+            let posted = self.post(*channel, &text).await?;
+            say!("{} poll #{} {:?} on {:?}: {}", "📣 Announced".green().bold(), announcement.poll_id,
+                announcement.event, channel, posted.white());
+        }
+        Ok(())
+    }
+}
+
+impl<M: Middleware + 'static> PollManager<M> {
+    async fn announcement(&self, poll_id: u64, event: PollEvent, link_template: Option<&str>) -> Result<Announcement> {
+        let poll = self.poll_data(U256::from(poll_id)).await?;
+        let tally = self.poll_tally(poll_id, &poll.2, poll.7).await?;
+        let link = match link_template {
+            Some(template) => template.replace("{id}", &poll_id.to_string()),
+            None => poll_ref::link(self.wallet.chain_id(), self.contract.address(), poll_id),
+        };
+        Ok(Announcement {
+            event,
+            poll_id,
+            question: poll.1.clone(),
+            options: poll.2.clone(),
+            end_time: poll.5.to_u64_saturating(),
+            link,
+            total_votes: tally.total_votes,
+            leader: tally.leader().map(|leader| (leader.option.clone(), tally.deciding_share(leader))),
+            metric: tally.winner_metric(),
+        })
+    }
+
+    /// `announce`: post that a poll was created or closed; without channels, only print the text
+    pub async fn announce(&self, poll_id: u64, event: PollEvent, channels: &[Channel], link_template: Option<&str>) -> Result<()> {
+        let publisher = if channels.is_empty() { None } else { Some(Publisher::from_env(channels)?) };
+        let poll = self.poll_data(U256::from(poll_id)).await?;
+        if event == PollEvent::Closed && poll.6 == 0 {
+            bail_user!("Poll #{} is not closed yet; announce its result after `close`", poll_id);
+        }
+        let announcement = self.announcement(poll_id, event, link_template).await?;
+        match publisher {
+            Some(publisher) => publisher.publish(&announcement).await,
+            None => {
+                println!("{}", announcement.render());
+                Ok(())
+            }
+        }
+    }
+
+    /// `announce --watch`: post every poll created or closed from now on, checking every `interval` seconds
+    pub async fn announce_watch(&self, channels: &[Channel], link_template: Option<&str>, interval: u64) -> Result<()> {
+        if channels.is_empty() {
+            bail_user!("--watch needs --to with at least one channel");
+        }
+        if interval == 0 {
+            bail_user!("--watch interval must be longer than zero");
+        }
+        let publisher = Publisher::from_env(channels)?;
+
+        // Only what happens after startup is announced
+        let mut known = self.contract.poll_count().call().await?.to_u64_saturating();
+        let mut open = BTreeSet::new();
+        for poll_id in 0..known {
+            if self.poll_data(U256::from(poll_id)).await?.6 == 0 {
+                open.insert(poll_id);
+            }
+        }
+        say!("{} every {} (Ctrl-C to stop)", "👀 Watching for new and closed polls".cyan().bold(), duration::format(interval));
+
+        loop {
+            tokio::time::sleep(std::time::Duration::from_secs(interval)).await;
+            let result: Result<()> = async {
+                let count = self.contract.poll_count().call().await?.to_u64_saturating();
+                while known < count {
+                    publisher.publish(&self.announcement(known, PollEvent::Created, link_template).await?).await?;
+                    open.insert(known);
+                    known += 1;
+                }
+                for poll_id in open.clone() {
+                    let status = self.poll_data(U256::from(poll_id)).await?.6;
+                    if status == 1 {
+                        publisher.publish(&self.announcement(poll_id, PollEvent::Closed, link_template).await?).await?;
+                    }
+                    if status != 0 {
+                        open.remove(&poll_id);
+                    }
+                }
+                Ok(())
+            }.await;
+            // Keep watching through RPC and API hiccups; a failed announcement is retried next round
+            if let Err(e) = result {
+                eprintln!("{} {:#}", output::render("⚠️  Announcement failed:".to_string()).yellow(), e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_announcements_within_the_post_limit() {
+        let mut announcement = Announcement {
+            event: PollEvent::Created,
+            poll_id: 7,
+            question: "Fund the grant?".to_string(),
+            options: vec!["Yes".to_string(), "No".to_string()],
+            end_time: 1_767_830_400,
+            link: "https://vote.example.org/polls/7".to_string(),
+            total_votes: 0,
+            leader: None,
+            metric: "votes",
+        };
+        assert_eq!(announcement.render(),
+            "🗳️ New poll #7: Fund the grant?\nOptions: Yes / No\nVoting ends 2026-01-08 00:00:00 UTC\nhttps://vote.example.org/polls/7");
+
+        announcement.options = (0..40).map(|i| format!("Candidate number {}", i)).collect();
+        assert!(announcement.render().contains("\n40 options\n"));
+
+        announcement.question = "Ö".repeat(400);
+        let text = announcement.render();
+        assert!(text.len() <= MAX_LENGTH && text.ends_with("https://vote.example.org/polls/7"), "{}", text);
+
+        announcement.event = PollEvent::Closed;
+        announcement.question = "Fund the grant?".to_string();
+        announcement.total_votes = 4;
+        announcement.leader = Some(("Yes".to_string(), 75.0));
+        assert_eq!(announcement.render(),
+            "✅ Poll #7 closed: Fund the grant?\nResult: Yes (75.0% of votes), 4 votes\nhttps://vote.example.org/polls/7");
+    }
+}
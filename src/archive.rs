@@ -0,0 +1,445 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use colored::*;
+use ethers::prelude::*;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+
+use crate::error::bail_user;
+use crate::indexer::{self, Checkpoint, ContractEvent, EventIndex, IndexedEvent, DEFAULT_REORG_DEPTH};
+use crate::numbers::SaturatingU64;
+use crate::{format_timestamp, numbers, output, store, u8_to_category, u8_to_poll_type, u8_to_status, PollManager};
+
+/// Bumped whenever the bundle layout changes incompatibly
+pub const FORMAT_VERSION: u32 = 1;
+
+/// A poll as stored on-chain at the archive's block
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArchivedPoll {
+    pub id: u64,
+    pub question: String,
+    pub options: Vec<String>,
+    pub creator: Address,
+    pub created_at: u64,
+    pub end_time: u64,
+    pub status: String,
+    pub poll_type: String,
+    pub category: String,
+    #[serde(with = "numbers::decimal")]
+    pub min_participation: U256,
+    pub description: String,
+    pub tags: Vec<String>,
+    pub is_archived: bool,
+    #[serde(with = "numbers::decimal_vec")]
+    pub results: Vec<U256>,
+    #[serde(with = "numbers::decimal")]
+    pub total_votes: U256,
+    #[serde(with = "numbers::decimal")]
+    pub total_weight: U256,
+}
+
+/// A contract's complete history: poll state at one block plus every event up to it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Archive {
+    pub format_version: u32,
+    pub chain_id: u64,
+    pub contract: Address,
+    /// First block the events cover
+    pub start_block: u64,
+    /// Block the archive was taken at; polls are read there
+    pub checkpoint: Checkpoint,
+    pub archived_at: u64,
+    pub polls: Vec<ArchivedPoll>,
+    pub events: Vec<IndexedEvent>,
+}
+
+impl Archive {
+    pub fn vote_count(&self) -> usize {
+        self.events.iter().filter(|e| matches!(e.event, ContractEvent::VoteCast { .. })).count()
+    }
+
+    pub fn delegation_count(&self) -> usize {
+        self.events.iter()
+            .filter(|e| matches!(e.event, ContractEvent::DelegateSet { .. } | ContractEvent::DelegateRemoved { .. }))
+            .count()
+    }
+}
+
+/// `.gz` bundles are gzip-compressed JSON, anything else plain JSON
+fn is_compressed(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("gz"))
+}
+
+pub fn write_archive(path: &Path, archive: &Archive) -> Result<()> {
+    let file = File::create(path).with_context(|| format!("Failed to create {}", path.display()))?;
+    if is_compressed(path) {
+        let mut encoder = GzEncoder::new(BufWriter::new(file), Compression::default());
+        serde_json::to_writer(&mut encoder, archive)?;
+        encoder.finish()?.flush()?;
+    } else {
+        let mut writer = BufWriter::new(file);
+        serde_json::to_writer_pretty(&mut writer, archive)?;
+        writer.flush()?;
+    }
+    Ok(())
+}
+
+pub fn read_archive(path: &Path) -> Result<Archive> {
+    let file = File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+    let mut content = String::new();
+    if is_compressed(path) {
+        GzDecoder::new(BufReader::new(file)).read_to_string(&mut content)
+    } else {
+        BufReader::new(file).read_to_string(&mut content)
+    }
+    .with_context(|| format!("Failed to read {}", path.display()))?;
+
+    let archive: Archive = match serde_json::from_str(&content) {
+        Ok(archive) => archive,
+        Err(e) => bail_user!("{} is not a poll archive: {}", path.display(), e),
+    };
+    if archive.format_version > FORMAT_VERSION {
+        bail_user!("{} uses archive format {}; this CLI reads up to {}", path.display(), archive.format_version, FORMAT_VERSION);
+    }
+    Ok(archive)
+}
+
+
+/// A poll that is new, or whose status or votes moved, between two archives
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PollDelta {
+    pub poll_id: u64,
+    pub question: String,
+    /// Status in the older archive; absent for polls created in between
+    pub status_before: Option<String>,
+    pub status: String,
+    #[serde(with = "numbers::decimal")]
+    pub votes_before: U256,
+    #[serde(with = "numbers::decimal")]
+    pub votes_after: U256,
+    #[serde(with = "numbers::decimal_vec")]
+    pub results_before: Vec<U256>,
+    #[serde(with = "numbers::decimal_vec")]
+    pub results_after: Vec<U256>,
+}
+
+/// A delegator whose delegate was set, changed or removed in between
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DelegationChange {
+    pub delegator: Address,
+    pub before: Option<Address>,
+    pub after: Option<Address>,
+}
+
+/// What happened between two archives of the same contract
+#[derive(Debug, Clone, Serialize)]
+pub struct ArchiveDiff {
+    pub chain_id: u64,
+    pub contract: Address,
+    pub from_block: u64,
+    pub to_block: u64,
+    pub new_polls: usize,
+    pub new_votes: usize,
+    pub polls: Vec<PollDelta>,
+    pub delegations: Vec<DelegationChange>,
+}
+
+pub fn diff_archives(before: &Archive, after: &Archive) -> Result<ArchiveDiff> {
+    if (before.chain_id, before.contract) != (after.chain_id, after.contract) {
+        bail_user!("The archives are of different contracts: {:?} on chain {} and {:?} on chain {}",
+            before.contract, before.chain_id, after.contract, after.chain_id);
+    }
+    if before.checkpoint.block_number > after.checkpoint.block_number {
+        bail_user!("The first archive (block {}) is newer than the second (block {}); pass the older one first",
+            before.checkpoint.block_number, after.checkpoint.block_number);
+    }
+
+    let old: BTreeMap<u64, &ArchivedPoll> = before.polls.iter().map(|p| (p.id, p)).collect();
+    let polls: Vec<PollDelta> = after.polls.iter().filter_map(|poll| {
+        let previous = old.get(&poll.id);
+        let unchanged = previous.is_some_and(|p| p.status == poll.status && p.total_votes == poll.total_votes);
+        (!unchanged).then(|| PollDelta {
+            poll_id: poll.id,
+            question: poll.question.clone(),
+            status_before: previous.map(|p| p.status.clone()),
+            status: poll.status.clone(),
+            votes_before: previous.map(|p| p.total_votes).unwrap_or_default(),
+            votes_after: poll.total_votes,
+            results_before: previous.map(|p| p.results.clone()).unwrap_or_default(),
+            results_after: poll.results.clone(),
+        })
+    }).collect();
+
+    Ok(ArchiveDiff {
+        chain_id: after.chain_id,
+        contract: after.contract,
+        from_block: before.checkpoint.block_number,
+        to_block: after.checkpoint.block_number,
+        new_polls: polls.iter().filter(|p| p.status_before.is_none()).count(),
+        new_votes: after.events.iter()
+            .filter(|e| e.block_number > before.checkpoint.block_number && matches!(e.event, ContractEvent::VoteCast { .. }))
+            .count(),
+        polls,
+        delegations: delegation_changes(&before.events, &after.events),
+    })
+}
+
+/// Delegators whose delegate differs after replaying `after` instead of `before`
+pub fn delegation_changes(before: &[IndexedEvent], after: &[IndexedEvent]) -> Vec<DelegationChange> {
+    let (old_delegates, new_delegates) = (indexer::delegations(before), indexer::delegations(after));
+    let delegators: BTreeSet<&Address> = old_delegates.keys().chain(new_delegates.keys()).collect();
+    delegators.into_iter().filter_map(|delegator| {
+        let (was, is) = (old_delegates.get(delegator).copied(), new_delegates.get(delegator).copied());
+        (was != is).then_some(DelegationChange { delegator: *delegator, before: was, after: is })
+    }).collect()
+}
+
+fn print_diff(diff: &ArchiveDiff) {
+    say!("\n{} {} → {}", "🔍 Changes between blocks".cyan().bold(), diff.from_block, diff.to_block);
+    say!("{} {}", "New Polls:".cyan().bold(), diff.new_polls.to_string().yellow());
+    say!("{} {}", "New Votes:".cyan().bold(), diff.new_votes.to_string().yellow());
+
+    if !diff.polls.is_empty() {
+        say!("\n{}", "📊 Polls:".cyan().bold());
+    }
+    for poll in &diff.polls {
+        let votes = format!("{} → {} votes", numbers::format_integer(poll.votes_before), numbers::format_integer(poll.votes_after));
+        match &poll.status_before {
+            None => say!("  {} #{} \"{}\" ({}, {})", "+".green().bold(), poll.poll_id, poll.question, poll.status, votes),
+            Some(before) if *before != poll.status => {
+                say!("  {} #{} \"{}\" ({} → {}, {})", "~".yellow().bold(), poll.poll_id, poll.question, before, poll.status, votes)
+            }
+            Some(_) => say!("  {} #{} \"{}\" ({})", "~".yellow().bold(), poll.poll_id, poll.question, votes),
+        }
+    }
+
+    print_delegation_changes(&diff.delegations);
+    if diff.polls.is_empty() && diff.delegations.is_empty() {
+        say!("No poll or delegation changes.");
+    }
+}
+
+pub fn print_delegation_changes(changes: &[DelegationChange]) {
+    if !changes.is_empty() {
+        say!("\n{}", "🤝 Delegations:".cyan().bold());
+    }
+    for change in changes {
+        match (change.before, change.after) {
+            (None, Some(after)) => say!("  {} {:?} → {:?}", "+".green().bold(), change.delegator, after),
+            (Some(before), None) => say!("  {} {:?} no longer delegates to {:?}", "-".red().bold(), change.delegator, before),
+            (before, after) => say!("  {} {:?}: {:?} → {:?}", "~".yellow().bold(), change.delegator, before.unwrap_or_default(), after.unwrap_or_default()),
+        }
+    }
+}
+
+/// Compare an archive taken before a governance cycle with one taken after it
+pub fn diff_archive_files(before: &str, after: &str, format: &str) -> Result<()> {
+    let json = match format.to_lowercase().as_str() {
+        "json" => true,
+        "table" => false,
+        _ => bail_user!("Unsupported format: {}. Use table or json", format),
+    };
+    let diff = diff_archives(&read_archive(Path::new(before))?, &read_archive(Path::new(after))?)?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&diff)?);
+    } else {
+        print_diff(&diff);
+    }
+    Ok(())
+}
+
+/// Restore an archive's events as the local event index for its chain and contract,
+/// so the next `sync` resumes from the archive's block instead of from genesis
+pub fn import_archive(path: &str, force: bool) -> Result<()> {
+    let archive = read_archive(Path::new(path))?;
+    let index_path = indexer::index_path(archive.chain_id, archive.contract)?;
+    let existing: EventIndex = store::load_from(&index_path).unwrap_or_default();
+    if let Some(current) = existing.checkpoint.filter(|c| c.block_number > archive.checkpoint.block_number) {
+        if !force {
+            bail_user!("The local index is already synced to block {}, past the archive's block {}; pass --force to replace it",
+                current.block_number, archive.checkpoint.block_number);
+        }
+    }
+
+    let index = EventIndex { start_block: archive.start_block, checkpoint: Some(archive.checkpoint), events: archive.events };
+    store::save_to(&index_path, &index)?;
+
+    say!("{}", "✅ Archive imported!".green().bold());
+    say!("{} {:?} on chain {}", "Contract:".cyan().bold(), archive.contract, archive.chain_id);
+    say!("{} {}..{}, {} events", "Index:".cyan().bold(), index.start_block, archive.checkpoint.block_number, index.events.len());
+    say!("Run `sync` to catch up from block {}.", archive.checkpoint.block_number + 1);
+    Ok(())
+}
+
+impl<M: Middleware + 'static> PollManager<M> {
+    /// Every poll, vote and delegation in the contract's history, with poll state
+    /// pinned to the block the event index was synced to
+    pub async fn export_archive(&self, output_path: Option<String>) -> Result<()> {
+        say!("{}", "📦 Archiving contract history...".cyan().bold());
+
+        let progress = output::spinner("🔄 Syncing contract events...");
+        let synced = self.sync_events(None, None, DEFAULT_REORG_DEPTH, &progress).await;
+        let (index, _) = match synced {
+            Ok(synced) => synced,
+            Err(e) => {
+                progress.finish_and_clear();
+                return Err(e);
+            }
+        };
+        let Some(checkpoint) = index.checkpoint else {
+            progress.finish_and_clear();
+            bail_user!("No blocks indexed yet; run `sync` first");
+        };
+
+        let block_id = BlockId::Number(BlockNumber::Number(checkpoint.block_number.into()));
+        let poll_count = self.contract.poll_count().block(block_id).call().await?.to_u64_saturating();
+        let mut polls = Vec::with_capacity(poll_count as usize);
+        for id in 0..poll_count {
+            progress.set_message(format!("Reading poll {} of {}", id + 1, poll_count));
+            let poll = self.contract.get_poll(U256::from(id)).block(block_id).call().await?;
+            let (results, total_votes, total_weight) = self.contract.get_poll_results(U256::from(id)).block(block_id).call().await?;
+            polls.push(ArchivedPoll {
+                id,
+                question: poll.1,
+                options: poll.2,
+                creator: poll.3,
+                created_at: poll.4.to_u64_saturating(),
+                end_time: poll.5.to_u64_saturating(),
+                status: u8_to_status(poll.6).to_string(),
+                poll_type: u8_to_poll_type(poll.7).to_string(),
+                category: u8_to_category(poll.8).to_string(),
+                min_participation: poll.9,
+                description: poll.12,
+                tags: poll.13,
+                is_archived: poll.15,
+                results,
+                total_votes,
+                total_weight,
+            });
+        }
+        progress.finish_and_clear();
+
+        let chain_id = self.wallet.chain_id();
+        let archive = Archive {
+            format_version: FORMAT_VERSION,
+            chain_id,
+            contract: self.contract.address(),
+            start_block: index.start_block,
+            checkpoint,
+            archived_at: chrono::Utc::now().timestamp() as u64,
+            polls,
+            events: index.events.clone(),
+        };
+
+        let path = output_path.unwrap_or_else(|| format!("archive-{}-{}.json.gz", chain_id, checkpoint.block_number));
+        write_archive(Path::new(&path), &archive)?;
+
+        say!("{}", "✅ Archive written!".green().bold());
+        say!("{} {}..{} ({:?})", "Blocks:".cyan().bold(), archive.start_block, checkpoint.block_number, checkpoint.block_hash);
+        say!("{} {}", "Polls:".cyan().bold(), archive.polls.len().to_string().yellow());
+        say!("{} {}", "Votes:".cyan().bold(), archive.vote_count().to_string().yellow());
+        say!("{} {}", "Delegation Events:".cyan().bold(), archive.delegation_count().to_string().yellow());
+        say!("{} {}", "Archived At:".cyan().bold(), format_timestamp(archive.archived_at).white());
+        say!("{} {}", "File:".cyan().bold(), path.yellow());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Archive {
+        let event = |block_number: u64, event: ContractEvent| IndexedEvent {
+            block_number,
+            block_hash: H256::from_low_u64_be(block_number),
+            transaction_hash: H256::from_low_u64_be(block_number + 1_000),
+            log_index: 0,
+            timestamp: 1_700_000_000 + block_number,
+            event,
+        };
+        Archive {
+            format_version: FORMAT_VERSION,
+            chain_id: 31337,
+            contract: Address::from_low_u64_be(0xc0),
+            start_block: 0,
+            checkpoint: Checkpoint { block_number: 9, block_hash: H256::from_low_u64_be(9) },
+            archived_at: 1_700_000_100,
+            polls: vec![ArchivedPoll {
+                id: 0,
+                question: "Ship it?".to_string(),
+                options: vec!["Yes".to_string(), "No".to_string()],
+                creator: Address::from_low_u64_be(1),
+                created_at: 1_700_000_001,
+                end_time: 1_700_086_401,
+                status: "Active".to_string(),
+                poll_type: "Standard".to_string(),
+                category: "General".to_string(),
+                min_participation: U256::zero(),
+                description: String::new(),
+                tags: vec![],
+                is_archived: false,
+                results: vec![U256::one(), U256::zero()],
+                total_votes: U256::one(),
+                total_weight: U256::one(),
+            }],
+            events: vec![
+                event(2, ContractEvent::VoteCast { poll_id: 0, voter: Address::from_low_u64_be(2), option_index: 0, weight: U256::one() }),
+                event(3, ContractEvent::DelegateSet { delegator: Address::from_low_u64_be(3), delegate: Address::from_low_u64_be(2) }),
+            ],
+        }
+    }
+
+    #[test]
+    fn round_trips_plain_and_compressed_bundles() {
+        let dir = std::env::temp_dir().join(format!("poll-archive-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        for name in ["archive.json", "archive.json.gz"] {
+            let path = dir.join(name);
+            write_archive(&path, &sample()).unwrap();
+            let read = read_archive(&path).unwrap();
+            assert_eq!(read.polls, sample().polls);
+            assert_eq!(read.events, sample().events);
+            assert_eq!((read.vote_count(), read.delegation_count()), (1, 1));
+        }
+        let compressed = std::fs::read(dir.join("archive.json.gz")).unwrap();
+        assert_eq!(&compressed[..2], &[0x1f, 0x8b], "gzip magic bytes");
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn diffs_polls_votes_and_delegations() {
+        let before = sample();
+        let mut after = sample();
+        after.checkpoint = Checkpoint { block_number: 20, block_hash: H256::from_low_u64_be(20) };
+        after.polls[0].status = "Closed".to_string();
+        after.polls[0].total_votes = U256::from(2);
+        after.polls[0].results = vec![U256::one(), U256::one()];
+        let mut created = after.polls[0].clone();
+        created.id = 1;
+        after.polls.push(created);
+        let event = |block_number: u64, event: ContractEvent| IndexedEvent { block_number, event, ..before.events[0].clone() };
+        after.events.push(event(12, ContractEvent::VoteCast { poll_id: 0, voter: Address::from_low_u64_be(4), option_index: 1, weight: U256::one() }));
+        after.events.push(event(13, ContractEvent::DelegateRemoved { delegator: Address::from_low_u64_be(3), delegate: Address::from_low_u64_be(2) }));
+        after.events.push(event(14, ContractEvent::DelegateSet { delegator: Address::from_low_u64_be(5), delegate: Address::from_low_u64_be(2) }));
+
+        let diff = diff_archives(&before, &after).unwrap();
+        assert_eq!((diff.new_polls, diff.new_votes), (1, 1));
+        assert_eq!(diff.polls[0].status_before.as_deref(), Some("Active"));
+        assert_eq!((diff.polls[0].votes_before, diff.polls[0].votes_after), (U256::one(), U256::from(2)));
+        assert_eq!(diff.polls[1].status_before, None);
+        let delegators: Vec<(u64, bool, bool)> = diff.delegations.iter()
+            .map(|d| (d.delegator.to_low_u64_be(), d.before.is_some(), d.after.is_some()))
+            .collect();
+        assert_eq!(delegators, [(3, true, false), (5, false, true)]);
+
+        assert!(diff_archives(&after, &before).is_err(), "newer archive first");
+        assert!(diff_archives(&before, &before).unwrap().polls.is_empty());
+    }
+}
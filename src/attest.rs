@@ -0,0 +1,275 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use anyhow::Result;
+use colored::*;
+use ethers::prelude::*;
+use ethers::utils::keccak256;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{bail_config, bail_user, CliError};
+use crate::{u8_to_status, PollManager};
+
+/// The final result of a closed poll, as each observer reads it from the chain. It contains
+/// nothing that depends on when or where it was read, so every honest observer gets the same hash.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResultStatement {
+    pub chain_id: u64,
+    pub contract: Address,
+    pub poll_id: u64,
+    pub question: String,
+    pub options: Vec<String>,
+    pub status: String,
+    pub results: Vec<U256>,
+    pub total_votes: U256,
+    pub total_weight: U256,
+}
+
+impl ResultStatement {
+    pub fn hash(&self) -> Result<H256> {
+        Ok(H256::from(keccak256(serde_json::to_vec(self)?)))
+    }
+}
+
+/// A set of designated observers confirming one result statement
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AttestationBundle {
+    pub statement: ResultStatement,
+    /// keccak256 of the JSON-serialized statement
+    pub result_hash: H256,
+    pub observers: Vec<Address>,
+    /// Signatures needed for the attestation to hold
+    pub threshold: usize,
+    /// EIP-191 personal signatures over `signed_hash()`, by observer
+    pub signatures: BTreeMap<Address, String>,
+}
+
+/// What `attest verify` found
+#[derive(Debug, PartialEq)]
+pub struct Verification {
+    pub valid: Vec<Address>,
+    /// Signatures that are malformed, by someone else, or not by a designated observer
+    pub rejected: Vec<Address>,
+}
+
+impl AttestationBundle {
+    /// What observers sign: the result together with who must confirm it and how many,
+    /// so neither can be edited in the file without invalidating every signature
+    pub fn signed_hash(&self) -> Result<H256> {
+        Ok(H256::from(keccak256(serde_json::to_vec(&(self.result_hash, &self.observers, self.threshold))?)))
+    }
+
+    /// Check the signatures for the `observers` and `threshold` the verifier expects, not the ones the file claims
+    pub fn verify(&self, observers: &[Address], threshold: usize) -> Result<Verification> {
+        if self.statement.hash()? != self.result_hash {
+            bail_user!("The result statement does not match its hash; the bundle was edited");
+        }
+        let mut expected = observers.to_vec();
+        expected.sort();
+        expected.dedup();
+        if self.observers != expected || self.threshold != threshold {
+            bail_user!("The bundle names {} of {:?}, not the expected {} of {:?}", self.threshold, self.observers, threshold, expected);
+        }
+        let signed_hash = self.signed_hash()?;
+        let mut verification = Verification { valid: Vec::new(), rejected: Vec::new() };
+        for (observer, signature) in &self.signatures {
+            let signed = self.observers.contains(observer)
+                && signature.parse::<Signature>().is_ok_and(|s| s.verify(signed_hash.as_bytes(), *observer).is_ok());
+            if signed {
+                verification.valid.push(*observer);
+            } else {
+                verification.rejected.push(*observer);
+            }
+        }
+        Ok(verification)
+    }
+}
+
+fn load(path: &Path) -> Result<AttestationBundle> {
+    if !path.exists() {
+        bail_config!("Attestation bundle {} not found", path.display());
+    }
+    let bundle: AttestationBundle = serde_json::from_slice(&std::fs::read(path)?)
+        .map_err(|e| CliError::Config(format!("{} is not an attestation bundle: {}", path.display(), e)))?;
+    if bundle.threshold == 0 || bundle.threshold > bundle.observers.len() {
+        bail_user!("{} needs {} of {} observers; the threshold must be between 1 and the number of observers",
+            path.display(), bundle.threshold, bundle.observers.len());
+    }
+    Ok(bundle)
+}
+
+fn save(path: &Path, bundle: &AttestationBundle) -> Result<()> {
+    std::fs::write(path, serde_json::to_string_pretty(bundle)?)?;
+    Ok(())
+}
+
+/// `attest collect`: merge the signatures from observers' copies of a bundle into `path`
+pub fn collect(path: &Path, copies: &[std::path::PathBuf]) -> Result<()> {
+    let mut bundle = load(path)?;
+    let before = bundle.signatures.len();
+    for copy in copies {
+        let other = load(copy)?;
+        if other.result_hash != bundle.result_hash {
+            bail_user!("{} attests a different result ({:?}); it cannot be merged", copy.display(), other.result_hash);
+        }
+        if other.observers != bundle.observers || other.threshold != bundle.threshold {
+            bail_user!("{} names different observers or a different threshold; it cannot be merged", copy.display());
+        }
+        for (observer, signature) in other.signatures {
+            bundle.signatures.entry(observer).or_insert(signature);
+        }
+    }
+    save(path, &bundle)?;
+    say!("{} {} new signature{}, {} in total", "✅ Collected".green().bold(),
+        bundle.signatures.len() - before, if bundle.signatures.len() - before == 1 { "" } else { "s" }, bundle.signatures.len());
+    Ok(())
+}
+
+/// `attest verify`: check every signature from the expected `observers` and whether `threshold` is met
+pub fn verify(path: &Path, observers: &[Address], threshold: usize) -> Result<()> {
+    let bundle = load(path)?;
+    let verification = bundle.verify(observers, threshold)?;
+    let statement = &bundle.statement;
+
+    say!("\n{}", "🤝 RESULT ATTESTATION".cyan().bold().underline());
+    say!("{} #{} {} ({})", "Poll:".yellow().bold(), statement.poll_id, statement.question, statement.status);
+    say!("{} {:?} on chain {}", "Contract:".yellow().bold(), statement.contract, statement.chain_id);
+    for (option, votes) in statement.options.iter().zip(&statement.results) {
+        say!("  {}: {}", option, votes);
+    }
+    say!("{} {:?}", "Result Hash:".yellow().bold(), bundle.result_hash);
+    for observer in &bundle.observers {
+        let status = if verification.valid.contains(observer) {
+            "signed".green()
+        } else if verification.rejected.contains(observer) {
+            "invalid signature".red()
+        } else {
+            "not signed".yellow()
+        };
+        say!("  {:?} {}", observer, status);
+    }
+    for outsider in verification.rejected.iter().filter(|address| !bundle.observers.contains(address)) {
+        say!("  {:?} {}", outsider, "not a designated observer".red());
+    }
+
+    if verification.valid.len() < bundle.threshold {
+        bail_user!("Only {} of the {} required observers have attested this result", verification.valid.len(), bundle.threshold);
+    }
+    say!("{} {} of {} observers (threshold {})", "✅ Attested by".green().bold(), verification.valid.len(), bundle.observers.len(), bundle.threshold);
+    Ok(())
+}
+
+impl<M: Middleware + 'static> PollManager<M> {
+    /// The result of a poll that has been closed on-chain, so it can no longer change
+    async fn result_statement(&self, poll_id: u64) -> Result<ResultStatement> {
+        let poll = self.contract.get_poll(U256::from(poll_id)).call().await?;
+        if poll.6 == 0 {
+            bail_user!("Poll #{} is not closed; results can only be attested once they are final", poll_id);
+        }
+        let (results, total_votes, total_weight) = self.contract.get_poll_results(U256::from(poll_id)).call().await?;
+        Ok(ResultStatement {
+            chain_id: self.wallet.chain_id(),
+            contract: self.contract.address(),
+            poll_id,
+            question: poll.1,
+            options: poll.2,
+            status: u8_to_status(poll.6).to_string(),
+            results,
+            total_votes,
+            total_weight,
+        })
+    }
+
+    /// `attest init`: start a bundle for `observers`, to be passed around for signing
+    pub async fn init_attestation(&self, poll_id: u64, observers: Vec<Address>, threshold: usize, output: Option<String>) -> Result<()> {
+        let mut observers = observers;
+        observers.sort();
+        observers.dedup();
+        if observers.is_empty() {
+            bail_user!("--observers needs at least one address");
+        }
+        if threshold == 0 || threshold > observers.len() {
+            bail_user!("--threshold must be between 1 and the number of observers ({})", observers.len());
+        }
+        let statement = self.result_statement(poll_id).await?;
+        let bundle = AttestationBundle { result_hash: statement.hash()?, statement, observers, threshold, signatures: BTreeMap::new() };
+        let path = output.unwrap_or_else(|| format!("poll-{}-attestation.json", poll_id));
+        save(Path::new(&path), &bundle)?;
+
+        say!("{}", "✅ Attestation bundle created".green().bold());
+        say!("{} {:?}", "Result Hash:".cyan().bold(), bundle.result_hash);
+        say!("{} {} of {}", "Needs:".cyan().bold(), threshold, bundle.observers.len());
+        say!("{} {}", "Bundle:".cyan().bold(), path.yellow());
+        say!("Each observer runs `attest sign {}` against their own RPC endpoint; merge their copies with `attest collect`.", path);
+        Ok(())
+    }
+
+    /// `attest sign`: re-read the result from the chain and, if it matches the bundle, sign it
+    pub async fn sign_attestation(&self, path: &Path) -> Result<()> {
+        let mut bundle = load(path)?;
+        if self.sender != self.wallet.address() {
+            bail_user!("Attestations are signed by the key; --impersonate cannot sign one");
+        }
+        let observer = self.wallet.address();
+        if !bundle.observers.contains(&observer) {
+            bail_user!("{:?} is not one of the bundle's observers", observer);
+        }
+        if bundle.statement.chain_id != self.wallet.chain_id() || bundle.statement.contract != self.contract.address() {
+            bail_user!("The bundle is for contract {:?} on chain {}; connect to it to sign", bundle.statement.contract, bundle.statement.chain_id);
+        }
+        let independent = self.result_statement(bundle.statement.poll_id).await?;
+        if independent.hash()? != bundle.result_hash {
+            bail_user!("The chain reports a different result than the bundle ({:?} vs {:?}); not signing", independent.hash()?, bundle.result_hash);
+        }
+
+        let signature = self.wallet.sign_message(bundle.signed_hash()?.as_bytes()).await?;
+        bundle.signatures.insert(observer, format!("0x{}", signature));
+        save(path, &bundle)?;
+        say!("{} {:?}", "✅ Result confirmed and signed by".green().bold(), observer);
+        say!("{} {}/{}", "Signatures:".cyan().bold(), bundle.signatures.len(), bundle.threshold);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::utils::hash_message;
+
+    #[test]
+    fn counts_only_designated_observers() {
+        let key = |n: u8| LocalWallet::from_bytes(&[n; 32]).unwrap();
+        let (alice, bob, mallory) = (key(1), key(2), key(3));
+        let statement = ResultStatement {
+            chain_id: 1, contract: Address::zero(), poll_id: 4, question: "Ship?".to_string(),
+            options: vec!["Yes".to_string(), "No".to_string()], status: "Closed".to_string(),
+            results: vec![U256::from(3), U256::from(1)], total_votes: U256::from(4), total_weight: U256::from(4),
+        };
+        let result_hash = statement.hash().unwrap();
+        let mut observers = vec![alice.address(), bob.address()];
+        observers.sort();
+        let mut bundle = AttestationBundle { statement, result_hash, observers: observers.clone(), threshold: 2, signatures: BTreeMap::new() };
+        let signed_hash = bundle.signed_hash().unwrap();
+        let sign = |wallet: &LocalWallet| format!("0x{}", wallet.sign_hash(hash_message(signed_hash.as_bytes())).unwrap());
+        bundle.signatures.insert(alice.address(), sign(&alice));
+        bundle.signatures.insert(mallory.address(), sign(&mallory));
+        bundle.signatures.insert(bob.address(), sign(&alice));
+        let verification = bundle.verify(&observers, 2).unwrap();
+        assert_eq!(verification.valid, [alice.address()]);
+        assert_eq!(verification.rejected.len(), 2);
+
+        // Rewriting the observers or threshold in the file is caught by the verifier's own expectations
+        bundle.observers = vec![mallory.address()];
+        bundle.threshold = 1;
+        bundle.signatures = BTreeMap::from([(mallory.address(), format!("0x{}", mallory.sign_hash(hash_message(bundle.signed_hash().unwrap().as_bytes())).unwrap()))]);
+        assert!(bundle.verify(&observers, 2).is_err(), "observers swapped out");
+        bundle.observers = observers.clone();
+        assert!(bundle.verify(&observers, 2).is_err(), "threshold lowered");
+        bundle.threshold = 2;
+        bundle.signatures = BTreeMap::from([(alice.address(), sign(&alice)), (bob.address(), sign(&bob))]);
+        assert_eq!(bundle.verify(&observers, 2).unwrap().valid.len(), 2);
+
+        bundle.statement.results[1] = U256::from(2);
+        assert!(bundle.verify(&observers, 2).is_err(), "statement edited after signing");
+    }
+}
@@ -0,0 +1,253 @@
+use std::io::{IsTerminal, Write};
+use std::sync::Mutex;
+
+use anyhow::{Context, Result};
+use colored::*;
+use ethers::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::error::bail_user;
+use crate::fields::SelectArgs;
+use crate::{my_polls, output, store, timezone};
+
+/// Append-only log of write operations, one JSON object per line
+const AUDIT_FILE: &str = "audit.jsonl";
+
+/// One write operation: who ran what, from where, and what it sent
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub timestamp: u64,
+    /// Subcommand path, e.g. `close` or `draft submit`
+    pub command: String,
+    /// Arguments as typed, without the program name; `history --rerun` replays them
+    pub args: Vec<String>,
+    pub signer: Option<Address>,
+    pub chain_id: Option<u64>,
+    pub contract: Option<Address>,
+    pub machine: String,
+    pub user: Option<String>,
+    /// Hashes of every transaction sent, including ones that later failed
+    pub transactions: Vec<H256>,
+    /// `ok`, or the error the command ended with
+    pub result: String,
+    pub exit_code: i32,
+}
+
+/// What this run connected to and sent, filled in as it happens
+struct Session {
+    signer: Option<Address>,
+    chain_id: Option<u64>,
+    contract: Option<Address>,
+    transactions: Vec<H256>,
+}
+
+static SESSION: Mutex<Session> = Mutex::new(Session { signer: None, chain_id: None, contract: None, transactions: Vec::new() });
+
+/// Note the signer, chain and contract once connected
+pub fn record_connection(signer: Address, chain_id: u64, contract: Address) {
+    if let Ok(mut session) = SESSION.lock() {
+        session.signer = Some(signer);
+        session.chain_id = Some(chain_id);
+        session.contract = Some(contract);
+    }
+}
+
+/// Note a transaction as soon as it is sent, before waiting for it to be mined
+pub fn record_tx(hash: H256) {
+    if let Ok(mut session) = SESSION.lock() {
+        session.transactions.push(hash);
+    }
+}
+
+/// Whether this run has sent any transaction
+pub fn sent_transactions() -> bool {
+    SESSION.lock().map(|session| !session.transactions.is_empty()).unwrap_or(false)
+}
+
+/// The subcommand path that was run, e.g. `draft submit`
+pub fn command_name(matches: &clap::ArgMatches) -> String {
+    let mut names = Vec::new();
+    let mut matches = matches;
+    while let Some((name, sub)) = matches.subcommand() {
+        names.push(name);
+        matches = sub;
+    }
+    names.join(" ")
+}
+
+fn machine() -> String {
+    let mut buf = [0u8; 256];
+    // SAFETY: the buffer is valid for its full length and gethostname NUL-terminates within it on success
+    let name = if unsafe { libc::gethostname(buf.as_mut_ptr().cast(), buf.len()) } == 0 {
+        let end = buf.iter().position(|&b| b == 0).unwrap_or(buf.len());
+        String::from_utf8_lossy(&buf[..end]).into_owned()
+    } else {
+        String::new()
+    };
+    if name.is_empty() { "unknown".to_string() } else { name }
+}
+
+/// Append this run to the audit log. A log that cannot be written is reported but never fails the command.
+pub fn append(command: &str, args: Vec<String>, result: String, exit_code: i32) {
+    let entry = {
+        let session = SESSION.lock().unwrap_or_else(|e| e.into_inner());
+        AuditEntry {
+            timestamp: chrono::Utc::now().timestamp() as u64,
+            command: command.to_string(),
+            args,
+            signer: session.signer,
+            chain_id: session.chain_id,
+            contract: session.contract,
+            machine: machine(),
+            user: std::env::var("USER").or_else(|_| std::env::var("USERNAME")).ok(),
+            transactions: session.transactions.clone(),
+            result,
+            exit_code,
+        }
+    };
+    if let Err(e) = write_entry(&entry) {
+        eprintln!("{} {:#}", output::render("⚠️  Could not write the audit log:".to_string()).yellow(), e);
+    }
+}
+
+/// Log one operation that `serve` carried out for an API client as its own entry. `client` is
+/// the name of the API key used, recorded as the user; `transactions` are only this operation's.
+pub fn append_operation(command: &str, args: Vec<String>, client: &str, transactions: Vec<H256>, result: String, exit_code: i32) {
+    let entry = {
+        let session = SESSION.lock().unwrap_or_else(|e| e.into_inner());
+        AuditEntry {
+            timestamp: chrono::Utc::now().timestamp() as u64,
+            command: command.to_string(),
+            args,
+            signer: session.signer,
+            chain_id: session.chain_id,
+            contract: session.contract,
+            machine: machine(),
+            user: Some(format!("api:{}", client)),
+            transactions,
+            result,
+            exit_code,
+        }
+    };
+    if let Err(e) = write_entry(&entry) {
+        eprintln!("{} {:#}", output::render("⚠️  Could not write the audit log:".to_string()).yellow(), e);
+    }
+}
+
+fn write_entry(entry: &AuditEntry) -> Result<()> {
+    let path = store::data_dir()?.join(AUDIT_FILE);
+    let mut file = std::fs::OpenOptions::new().create(true).append(true).open(&path)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+    writeln!(file, "{}", serde_json::to_string(entry)?).with_context(|| format!("Failed to append to {}", path.display()))?;
+    Ok(())
+}
+
+/// Every logged operation, oldest first; lines that do not parse are skipped
+pub fn entries() -> Result<Vec<AuditEntry>> {
+    let path = store::data_dir()?.join(AUDIT_FILE);
+    match std::fs::read_to_string(&path) {
+        Ok(content) => Ok(content.lines().filter_map(|line| serde_json::from_str(line).ok()).collect()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(e).with_context(|| format!("Failed to read {}", path.display())),
+    }
+}
+
+/// `history`: the most recent `limit` operations, numbered for `--rerun`
+pub fn show_history(limit: usize, format: &str, select: &SelectArgs) -> Result<()> {
+    let entries = entries()?;
+    let start = entries.len().saturating_sub(limit);
+    if select.is_set() {
+        for entry in &entries[start..] {
+            select.print(entry)?;
+        }
+        return Ok(());
+    }
+
+    match format.to_lowercase().as_str() {
+        "json" => println!("{}", serde_json::to_string_pretty(&entries[start..])?),
+        "ndjson" => {
+            for entry in &entries[start..] {
+                output::ndjson(entry)?;
+            }
+        }
+        "table" => {
+            say!("\n{}", "📜 OPERATION HISTORY".cyan().bold().underline());
+            if entries.is_empty() {
+                say!("No write operations recorded yet.");
+                return Ok(());
+            }
+            for (number, entry) in entries.iter().enumerate().skip(start) {
+                let result = if entry.exit_code == 0 { entry.result.green() } else { entry.result.red() };
+                say!("\n{} {} {}", format!("#{}", number + 1).yellow().bold(), entry.command.white().bold(), result);
+                say!("  {} {}", "When:".cyan(), timezone::format_with_relative(entry.timestamp));
+                say!("  {} {}", "Ran:".cyan(), entry.args.join(" "));
+                let signer = entry.signer.map(|s| format!("{:?}", s)).unwrap_or_else(|| "-".to_string());
+                say!("  {} {} on {}{}", "By:".cyan(), signer, entry.machine,
+                    entry.user.as_ref().map(|user| format!(" ({})", user)).unwrap_or_default());
+                if let (Some(chain_id), Some(contract)) = (entry.chain_id, entry.contract) {
+                    say!("  {} {:?} on chain {}", "Contract:".cyan(), contract, chain_id);
+                }
+                for tx in &entry.transactions {
+                    say!("  {} {:?}", "Transaction:".cyan(), tx);
+                }
+            }
+            say!("\nRe-run one with `history --rerun <#>`.");
+        }
+        _ => bail_user!("Unsupported format: {}. Use table, json or ndjson", format),
+    }
+    Ok(())
+}
+
+/// `history --rerun <n>`: replay a logged operation's arguments after confirmation
+pub fn rerun(number: usize) -> Result<()> {
+    let entries = entries()?;
+    let Some(entry) = number.checked_sub(1).and_then(|i| entries.get(i)) else {
+        bail_user!("No operation #{} in the history ({} recorded)", number, entries.len());
+    };
+
+    say!("{} {}", "🔁 Re-running:".cyan().bold(), entry.args.join(" ").yellow());
+    if !std::io::stdin().is_terminal() {
+        bail_user!("Re-running sends transactions again; confirm it from an interactive terminal");
+    }
+    let answer = my_polls::prompt("Send it again? [y/N]")?;
+    if !(answer.eq_ignore_ascii_case("y") || answer.eq_ignore_ascii_case("yes")) {
+        say!("Cancelled.");
+        return Ok(());
+    }
+
+    let status = std::process::Command::new(std::env::current_exe()?)
+        .args(&entry.args)
+        .status()
+        .context("Failed to start the re-run")?;
+    if !status.success() {
+        anyhow::bail!("The re-run of #{} failed ({})", number, status);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entries_round_trip_through_a_log_line() {
+        let entry = AuditEntry {
+            timestamp: 1_700_000_000,
+            command: "close".to_string(),
+            args: vec!["close".to_string(), "-p".to_string(), "3".to_string()],
+            signer: Some(Address::repeat_byte(1)),
+            chain_id: Some(31337),
+            contract: Some(Address::repeat_byte(2)),
+            machine: machine(),
+            user: None,
+            transactions: vec![H256::repeat_byte(3)],
+            result: "ok".to_string(),
+            exit_code: 0,
+        };
+        let line = serde_json::to_string(&entry).unwrap();
+        assert!(!line.contains('\n'), "one entry per line");
+        let parsed: AuditEntry = serde_json::from_str(&line).unwrap();
+        assert_eq!((parsed.args, parsed.transactions), (entry.args, entry.transactions));
+        assert!(!parsed.machine.is_empty());
+    }
+}
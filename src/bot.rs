@@ -0,0 +1,146 @@
+use std::collections::BTreeSet;
+
+use anyhow::Result;
+use ethers::prelude::*;
+
+use crate::chart::{self, Series};
+use crate::numbers::SaturatingU64;
+use crate::{duration, timezone, u8_to_status, EnhancedPollsEvents, PollManager};
+
+/// Width of result bars in chat messages, narrow enough for a phone
+const CHART_WIDTH: usize = 16;
+
+/// Most open polls listed by `polls`
+const MAX_LISTED: usize = 20;
+
+/// A command sent to a chat bot, whatever the platform's prefix
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BotCommand {
+    /// Open polls, newest first
+    Polls,
+    Results(u64),
+    Help,
+}
+
+pub const HELP: &str = "polls - open polls\nresults <id> - a poll's results, kept up to date while it is open\nhelp - this list";
+
+impl BotCommand {
+    /// Parse the words after the platform's prefix, e.g. `["results", "7"]`; `None` for anything else
+    pub fn parse(words: &[&str]) -> Option<Self> {
+        match words {
+            [command] if command.eq_ignore_ascii_case("polls") => Some(BotCommand::Polls),
+            [command, id] if command.eq_ignore_ascii_case("results") => id.trim_start_matches('#').parse().ok().map(BotCommand::Results),
+            [command] if command.eq_ignore_ascii_case("help") || command.eq_ignore_ascii_case("start") => Some(BotCommand::Help),
+            _ => None,
+        }
+    }
+}
+
+/// A poll's results for a chat message; each platform decides the markup
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResultsView {
+    pub poll_id: u64,
+    pub title: String,
+    /// Bar chart lines, to show in a monospace block
+    pub chart: Vec<String>,
+    pub summary: String,
+    pub updated: String,
+    pub end_time: u64,
+    /// The poll can no longer change, so the message needs no more updates
+    pub is_final: bool,
+}
+
+impl ResultsView {
+    pub fn plain(&self) -> String {
+        format!("{}\n{}\n{}\n{}", self.title, self.chart.join("\n"), self.summary, self.updated)
+    }
+}
+
+/// `&`, `<` and `>` escaped for chat platforms that take HTML
+pub fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+impl<M: Middleware + 'static> PollManager<M> {
+    pub(crate) async fn results_view(&self, poll_id: u64) -> Result<ResultsView> {
+        let poll = self.poll_data(U256::from(poll_id)).await?;
+        let tally = self.poll_tally(poll_id, &poll.2, poll.7).await?;
+        let now = chrono::Utc::now().timestamp() as u64;
+        let end_time = poll.5.to_u64_saturating();
+        let is_final = poll.6 != 0 || now >= end_time;
+
+        let timing = if poll.6 != 0 {
+            u8_to_status(poll.6).to_string()
+        } else if is_final {
+            "Ended, not closed yet".to_string()
+        } else {
+            format!("Closes in {}", duration::format(end_time - now))
+        };
+        Ok(ResultsView {
+            poll_id,
+            title: format!("📊 Poll #{}: {}", poll_id, poll.1),
+            chart: chart::terminal_bars(&Series::from_tally(&poll.1, &tally), CHART_WIDTH, false)
+                .into_iter()
+                .take(tally.options.len())
+                .map(|line| line.trim_end().to_string())
+                .collect(),
+            summary: format!("{} votes · {}", tally.total_votes, timing),
+            updated: format!("Updated {}", timezone::format(now)),
+            end_time,
+            is_final,
+        })
+    }
+
+    /// Open polls as `(id, question)`, newest first
+    pub(crate) async fn open_polls(&self) -> Result<Vec<(u64, String)>> {
+        let count = self.contract.poll_count().call().await?.to_u64_saturating();
+        let now = chrono::Utc::now().timestamp() as u64;
+        let mut open = Vec::new();
+        for poll_id in (0..count).rev() {
+            let poll = self.poll_data(U256::from(poll_id)).await?;
+            if poll.6 == 0 && poll.5.to_u64_saturating() > now {
+                open.push((poll_id, poll.1.clone()));
+                if open.len() == MAX_LISTED {
+                    break;
+                }
+            }
+        }
+        Ok(open)
+    }
+
+    /// Polls with votes, status changes or extensions in `from..=to`; their cached reads are dropped
+    pub(crate) async fn changed_polls(&self, from: u64, to: u64) -> Result<BTreeSet<u64>> {
+        if from > to {
+            return Ok(BTreeSet::new());
+        }
+        let events = self.contract.events().from_block(from).to_block(to).query().await?;
+        let changed: BTreeSet<u64> = events.into_iter()
+            .filter_map(|event| match event {
+                EnhancedPollsEvents::VoteCastFilter(e) => Some(e.poll_id),
+                EnhancedPollsEvents::PollStatusChangedFilter(e) => Some(e.poll_id),
+                EnhancedPollsEvents::PollExtendedFilter(e) => Some(e.poll_id),
+                _ => None,
+            })
+            .map(|poll_id| poll_id.to_u64_saturating())
+            .collect();
+        for poll_id in &changed {
+            self.forget_reads(*poll_id);
+        }
+        Ok(changed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_commands_after_the_prefix() {
+        assert_eq!(BotCommand::parse(&["results", "7"]), Some(BotCommand::Results(7)));
+        assert_eq!(BotCommand::parse(&["Results", "#7"]), Some(BotCommand::Results(7)));
+        assert_eq!(BotCommand::parse(&["polls"]), Some(BotCommand::Polls));
+        assert_eq!(BotCommand::parse(&["start"]), Some(BotCommand::Help));
+        assert_eq!(BotCommand::parse(&["results"]), None);
+        assert_eq!(BotCommand::parse(&["vote", "7", "1"]), None);
+    }
+}
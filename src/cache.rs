@@ -0,0 +1,295 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+use anyhow::{Context, Result};
+use colored::*;
+use ethers::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::numbers::SaturatingU64;
+use crate::{store, PollManager};
+
+/// How long live poll data (end time, status, totals) is reused by default
+pub const DEFAULT_TTL_SECS: u64 = 30;
+
+const CACHE_DIR: &str = "cache";
+
+/// `getPoll` as returned by the contract binding
+pub type PollData = (U256, String, Vec<String>, Address, U256, U256, u8, u8, u8, U256, U256, U256, String, Vec<String>, U256, bool, U256);
+
+/// `getPollResults`: per-option weights, total votes, total weight
+pub type PollResults = (Vec<U256>, U256, U256);
+
+static TTL: AtomicU64 = AtomicU64::new(DEFAULT_TTL_SECS);
+
+/// Set the live-data TTL once at startup; 0 disables the cache, including on disk
+pub fn init(ttl: u64) {
+    TTL.store(ttl, Ordering::Relaxed);
+}
+
+fn ttl() -> u64 {
+    TTL.load(Ordering::Relaxed)
+}
+
+fn is_fresh(fetched_at: u64, now: u64, ttl: u64) -> bool {
+    now.saturating_sub(fetched_at) < ttl
+}
+
+/// Fields that change over a poll's life, with when they were read
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct LivePoll {
+    end_time: U256,
+    status: u8,
+    total_votes: U256,
+    total_weight: U256,
+    is_archived: bool,
+    archived_at: U256,
+    fetched_at: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedResults {
+    results: PollResults,
+    fetched_at: u64,
+}
+
+/// A poll's fields fixed at creation, kept indefinitely, plus TTL-bound live data
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedPoll {
+    question: String,
+    options: Vec<String>,
+    creator: Address,
+    created_at: U256,
+    poll_type: u8,
+    category: u8,
+    min_participation: U256,
+    description: String,
+    tags: Vec<String>,
+    template_id: U256,
+    live: Option<LivePoll>,
+    results: Option<CachedResults>,
+}
+
+impl CachedPoll {
+    fn new(data: &PollData, now: u64) -> Self {
+        Self {
+            question: data.1.clone(),
+            options: data.2.clone(),
+            creator: data.3,
+            created_at: data.4,
+            poll_type: data.7,
+            category: data.8,
+            min_participation: data.9,
+            description: data.12.clone(),
+            tags: data.13.clone(),
+            template_id: data.14,
+            live: Some(LivePoll {
+                end_time: data.5,
+                status: data.6,
+                total_votes: data.10,
+                total_weight: data.11,
+                is_archived: data.15,
+                archived_at: data.16,
+                fetched_at: now,
+            }),
+            results: None,
+        }
+    }
+
+    fn to_data(&self, poll_id: u64, live: &LivePoll) -> PollData {
+        (
+            U256::from(poll_id),
+            self.question.clone(),
+            self.options.clone(),
+            self.creator,
+            self.created_at,
+            live.end_time,
+            live.status,
+            self.poll_type,
+            self.category,
+            self.min_participation,
+            live.total_votes,
+            live.total_weight,
+            self.description.clone(),
+            self.tags.clone(),
+            self.template_id,
+            live.is_archived,
+            live.archived_at,
+        )
+    }
+}
+
+#[derive(Debug, Default)]
+struct CacheState {
+    polls: BTreeMap<u64, CachedPoll>,
+    dirty: bool,
+}
+
+/// Poll reads shared across commands: in memory for this process and, for a
+/// real connection, on disk per chain and contract for the commands that follow
+#[derive(Debug)]
+pub struct PollCache {
+    path: Option<PathBuf>,
+    state: Mutex<CacheState>,
+}
+
+impl PollCache {
+    pub fn memory() -> Self {
+        Self { path: None, state: Mutex::new(CacheState::default()) }
+    }
+
+    /// The on-disk cache for `contract` on `chain_id`; memory only when disabled or unreadable
+    pub fn open(chain_id: u64, contract: Address) -> Self {
+        if ttl() == 0 {
+            return Self::memory();
+        }
+        let Ok(dir) = store::data_dir().map(|dir| dir.join(CACHE_DIR)) else {
+            return Self::memory();
+        };
+        let path = dir.join(format!("{}-{:?}.json", chain_id, contract));
+        // A corrupt cache is only a cache: start over rather than fail the command
+        let polls = store::load_from(&path).unwrap_or_default();
+        Self { path: Some(path), state: Mutex::new(CacheState { polls, dirty: false }) }
+    }
+
+    fn poll(&self, poll_id: u64, now: u64) -> Option<PollData> {
+        let state = self.state.lock().ok()?;
+        let cached = state.polls.get(&poll_id)?;
+        let live = cached.live.as_ref().filter(|l| is_fresh(l.fetched_at, now, ttl()))?;
+        Some(cached.to_data(poll_id, live))
+    }
+
+    fn store_poll(&self, poll_id: u64, data: &PollData, now: u64) {
+        let Ok(mut state) = self.state.lock() else { return };
+        let fresh = CachedPoll::new(data, now);
+        let entry = state.polls.entry(poll_id).or_insert_with(|| fresh.clone());
+        entry.live = fresh.live;
+        state.dirty = true;
+    }
+
+    fn results(&self, poll_id: u64, now: u64) -> Option<PollResults> {
+        let state = self.state.lock().ok()?;
+        let cached = state.polls.get(&poll_id)?.results.as_ref()?;
+        is_fresh(cached.fetched_at, now, ttl()).then(|| cached.results.clone())
+    }
+
+    fn store_results(&self, poll_id: u64, results: &PollResults, now: u64) {
+        let Ok(mut state) = self.state.lock() else { return };
+        // Results are only kept alongside a poll already cached
+        if let Some(cached) = state.polls.get_mut(&poll_id) {
+            cached.results = Some(CachedResults { results: results.clone(), fetched_at: now });
+            state.dirty = true;
+        }
+    }
+
+    /// Drop a poll's live data after this CLI changed it (vote, close, extend)
+    pub fn invalidate(&self, poll_id: u64) {
+        let Ok(mut state) = self.state.lock() else { return };
+        if let Some(cached) = state.polls.get_mut(&poll_id) {
+            cached.live = None;
+            cached.results = None;
+            state.dirty = true;
+        }
+    }
+}
+
+impl Drop for PollCache {
+    fn drop(&mut self) {
+        let (Some(path), Ok(state)) = (&self.path, self.state.get_mut()) else { return };
+        if !state.dirty || ttl() == 0 {
+            return;
+        }
+        // Best effort, like the price cache: a failed write only costs RPC calls next time
+        if let Some(dir) = path.parent() {
+            let _ = std::fs::create_dir_all(dir);
+        }
+        let _ = store::save_to(path, &state.polls);
+    }
+}
+
+impl<M: Middleware + 'static> PollManager<M> {
+    /// `getPoll`, served from the cache while its live fields are fresh; pinned reads skip the cache
+    pub async fn poll_data(&self, poll_id: U256) -> Result<PollData> {
+        if let Some(pinned) = self.pinned {
+            return self.contract.get_poll(poll_id).block(pinned.id()).call().await.with_context(|| pinned.archive_hint());
+        }
+        let id = poll_id.to_u64_saturating();
+        let now = chrono::Utc::now().timestamp() as u64;
+        if let Some(data) = self.cache.poll(id, now) {
+            return Ok(data);
+        }
+        let data = self.contract.get_poll(poll_id).call().await?;
+        self.cache.store_poll(id, &data, now);
+        Ok(data)
+    }
+
+    /// `getPollResults`, served from the cache while fresh
+    pub async fn poll_results(&self, poll_id: U256) -> Result<PollResults> {
+        if let Some(pinned) = self.pinned {
+            return self.contract.get_poll_results(poll_id).block(pinned.id()).call().await.with_context(|| pinned.archive_hint());
+        }
+        let id = poll_id.to_u64_saturating();
+        let now = chrono::Utc::now().timestamp() as u64;
+        if let Some(results) = self.cache.results(id, now) {
+            return Ok(results);
+        }
+        let results = self.contract.get_poll_results(poll_id).call().await?;
+        self.cache.store_results(id, &results, now);
+        Ok(results)
+    }
+}
+
+/// Delete every cached poll, for all chains and contracts
+pub fn clear_cache() -> Result<()> {
+    let dir = store::data_dir()?.join(CACHE_DIR);
+    match std::fs::remove_dir_all(&dir) {
+        Ok(()) => say!("{} {}", "🧹 Cleared".green().bold(), dir.display()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => say!("Cache is already empty."),
+        Err(e) => return Err(e.into()),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(status: u8) -> PollData {
+        (
+            U256::from(3), "Q?".to_string(), vec!["A".to_string(), "B".to_string()], Address::zero(),
+            U256::from(100), U256::from(200), status, 0, 1, U256::from(5), U256::from(2), U256::from(2),
+            "desc".to_string(), vec!["tag".to_string()], U256::zero(), false, U256::zero(),
+        )
+    }
+
+    #[test]
+    fn live_fields_expire_but_fixed_fields_stay() {
+        let cache = PollCache::memory();
+        cache.store_poll(3, &sample(0), 1_000);
+        let hit = cache.poll(3, 1_000 + DEFAULT_TTL_SECS - 1).unwrap();
+        assert_eq!((hit.0, hit.1.as_str(), hit.5, hit.9, hit.13), (U256::from(3), "Q?", U256::from(200), U256::from(5), vec!["tag".to_string()]));
+        assert!(cache.poll(3, 1_000 + DEFAULT_TTL_SECS).is_none());
+
+        cache.store_poll(3, &sample(1), 2_000);
+        assert_eq!(cache.poll(3, 2_000).unwrap().6, 1);
+        assert_eq!(cache.state.lock().unwrap().polls[&3].question, "Q?");
+    }
+
+    #[test]
+    fn invalidate_drops_live_data_and_results() {
+        let cache = PollCache::memory();
+        let results: PollResults = (vec![U256::one(), U256::one()], U256::from(2), U256::from(2));
+        cache.store_results(3, &results, 1_000);
+        assert!(cache.results(3, 1_000).is_none(), "results need the poll cached first");
+
+        cache.store_poll(3, &sample(0), 1_000);
+        cache.store_results(3, &results, 1_000);
+        assert_eq!(cache.results(3, 1_001), Some(results));
+
+        cache.invalidate(3);
+        assert!(cache.poll(3, 1_001).is_none());
+        assert!(cache.results(3, 1_001).is_none());
+    }
+}
@@ -0,0 +1,147 @@
+use anyhow::Result;
+use colored::*;
+use ethers::prelude::*;
+
+use crate::numbers::SaturatingU64;
+use crate::{u8_to_category, PollManager};
+
+struct CalendarEvent {
+    uid: String,
+    start: u64,
+    summary: String,
+    description: String,
+    alarm_minutes: Option<u64>,
+}
+
+fn ics_timestamp(timestamp: u64) -> String {
+    chrono::DateTime::from_timestamp(timestamp as i64, 0)
+        .unwrap_or_default()
+        .format("%Y%m%dT%H%M%SZ")
+        .to_string()
+}
+
+/// Escape text values per RFC 5545 section 3.3.11
+fn ics_escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\r', "")
+        .replace('\n', "\\n")
+}
+
+/// Fold content lines longer than 75 octets, continuing with a leading space
+fn ics_fold(line: &str) -> String {
+    let mut folded = String::new();
+    let mut width = 0;
+    for ch in line.chars() {
+        let len = ch.len_utf8();
+        if width + len > 75 {
+            folded.push_str("\r\n ");
+            width = 1;
+        }
+        folded.push(ch);
+        width += len;
+    }
+    folded
+}
+
+fn render_calendar(events: &[CalendarEvent]) -> String {
+    let now = ics_timestamp(chrono::Utc::now().timestamp() as u64);
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//polling-cli//Poll Deadlines//EN".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+        "X-WR-CALNAME:Poll Deadlines".to_string(),
+    ];
+
+    for event in events {
+        lines.push("BEGIN:VEVENT".to_string());
+        lines.push(format!("UID:{}", event.uid));
+        lines.push(format!("DTSTAMP:{}", now));
+        lines.push(format!("DTSTART:{}", ics_timestamp(event.start)));
+        lines.push("DURATION:PT15M".to_string());
+        lines.push(format!("SUMMARY:{}", ics_escape(&event.summary)));
+        lines.push(format!("DESCRIPTION:{}", ics_escape(&event.description)));
+        if let Some(minutes) = event.alarm_minutes {
+            lines.push("BEGIN:VALARM".to_string());
+            lines.push("ACTION:DISPLAY".to_string());
+            lines.push(format!("DESCRIPTION:{}", ics_escape(&event.summary)));
+            lines.push(format!("TRIGGER:-PT{}M", minutes));
+            lines.push("END:VALARM".to_string());
+        }
+        lines.push("END:VEVENT".to_string());
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    lines.iter().map(|l| ics_fold(l)).collect::<Vec<_>>().join("\r\n") + "\r\n"
+}
+
+impl<M: Middleware + 'static> PollManager<M> {
+    pub async fn export_calendar(&self, mine_only: bool, reminder_minutes: u64, output_path: Option<String>) -> Result<()> {
+        let me = self.sender;
+        let chain_id = self.wallet.chain_id();
+        let contract = self.contract.address();
+        let now = chrono::Utc::now().timestamp() as u64;
+
+        let poll_ids: Vec<U256> = if mine_only {
+            self.contract.get_user_created_polls(me).call().await?
+        } else {
+            let poll_count = self.contract.poll_count().call().await?;
+            (0..poll_count.to_u64_saturating()).map(U256::from).collect()
+        };
+
+        let mut events = Vec::new();
+        for poll_id in poll_ids {
+            let poll = self.poll_data(poll_id).await?;
+            let end_time = poll.5.to_u64_saturating();
+            let is_mine = poll.3 == me;
+
+            if poll.6 != 0 {
+                continue;
+            }
+
+            let uid = format!("poll-{}-{:?}-{}@polling-cli", chain_id, contract, poll_id);
+            let mut description = format!(
+                "{}\nCategory: {}\nOptions: {}\nVotes so far: {}",
+                poll.1, u8_to_category(poll.8), poll.2.join(" / "), poll.10
+            );
+            if !poll.12.is_empty() {
+                description.push_str(&format!("\n\n{}", poll.12));
+            }
+
+            if end_time > now {
+                events.push(CalendarEvent {
+                    uid,
+                    start: end_time,
+                    summary: if is_mine {
+                        format!("Your poll #{} closes: {}", poll_id, poll.1)
+                    } else {
+                        format!("Poll #{} closes: {}", poll_id, poll.1)
+                    },
+                    description,
+                    alarm_minutes: Some(reminder_minutes),
+                });
+            } else if is_mine {
+                // Ended but never closed: remind the creator to close it
+                events.push(CalendarEvent {
+                    uid,
+                    start: end_time,
+                    summary: format!("Close poll #{}: {}", poll_id, poll.1),
+                    description: format!("{}\n\nThis poll has ended but is still open on-chain. Run: close -p {}", description, poll_id),
+                    alarm_minutes: None,
+                });
+            }
+        }
+
+        let calendar = render_calendar(&events);
+        if let Some(path) = output_path {
+            std::fs::write(&path, &calendar)?;
+            say!("{} {} {}", "📅 Wrote".green().bold(), events.len().to_string().yellow(), format!("poll deadlines to {}", path).green().bold());
+        } else {
+            print!("{}", calendar);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,148 @@
+use anyhow::Result;
+use colored::*;
+use ethers::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::error::bail_user;
+use crate::numbers::SaturatingU64;
+use crate::{category_to_u8, numbers, output, timezone, PollManager, CATEGORIES};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecentPoll {
+    pub poll_id: u64,
+    pub question: String,
+    pub created_at: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CategorySummary {
+    pub category: String,
+    pub active: u64,
+    pub closed: u64,
+    pub total_votes: u64,
+    pub average_votes: f64,
+    pub most_recent: Option<RecentPoll>,
+}
+
+/// Poll facts a category summary is built from
+pub struct PollFacts {
+    pub poll_id: u64,
+    pub question: String,
+    pub created_at: u64,
+    pub active: bool,
+    pub total_votes: u64,
+}
+
+pub fn summarize(category: &str, polls: &[PollFacts]) -> CategorySummary {
+    let active = polls.iter().filter(|p| p.active).count() as u64;
+    let total_votes = polls.iter().map(|p| p.total_votes).fold(0u64, u64::saturating_add);
+    let most_recent = polls.iter()
+        .max_by_key(|p| (p.created_at, p.poll_id))
+        .map(|p| RecentPoll { poll_id: p.poll_id, question: p.question.clone(), created_at: p.created_at });
+
+    CategorySummary {
+        category: category.to_string(),
+        active,
+        closed: polls.len() as u64 - active,
+        total_votes,
+        average_votes: if polls.is_empty() { 0.0 } else { total_votes as f64 / polls.len() as f64 },
+        most_recent,
+    }
+}
+
+impl<M: Middleware + 'static> PollManager<M> {
+    pub async fn category_dashboard(&self, format: &str, output_path: Option<String>) -> Result<()> {
+        let now = chrono::Utc::now().timestamp() as u64;
+        let mut summaries = Vec::with_capacity(CATEGORIES.len());
+
+        for category in CATEGORIES {
+            let poll_ids = self.contract.get_polls_by_category(category_to_u8(category)?).call().await?;
+            let mut polls = Vec::with_capacity(poll_ids.len());
+            for poll_id in poll_ids {
+                let poll = self.poll_data(poll_id).await?;
+                let (_, total_votes, _) = self.poll_results(poll_id).await?;
+                polls.push(PollFacts {
+                    poll_id: poll_id.to_u64_saturating(),
+                    question: poll.1.clone(),
+                    created_at: poll.4.to_u64_saturating(),
+                    active: poll.6 == 0 && now <= poll.5.to_u64_saturating(),
+                    total_votes: total_votes.to_u64_saturating(),
+                });
+            }
+            summaries.push(summarize(category, &polls));
+        }
+
+        match format.to_lowercase().as_str() {
+            "json" => {
+                let json_output = serde_json::to_string_pretty(&summaries)?;
+                if let Some(path) = output_path {
+                    std::fs::write(&path, &json_output)?;
+                    say!("{} {}", "✅ Exported to:".green().bold(), path.yellow());
+                } else {
+                    println!("{}", json_output);
+                }
+            }
+            "table" => print_dashboard(&summaries),
+            _ => bail_user!("Unsupported format: {}. Use table or json", format),
+        }
+        Ok(())
+    }
+}
+
+fn print_dashboard(summaries: &[CategorySummary]) {
+    say!("\n{}", "🗂️  CATEGORY OVERVIEW".cyan().bold().underline());
+    say!("{}", "═".repeat(70).cyan());
+    if output::is_accessible() {
+        let rows: Vec<Vec<String>> = summaries.iter()
+            .map(|summary| vec![
+                summary.category.clone(),
+                summary.active.to_string(),
+                summary.closed.to_string(),
+                numbers::format_integer(summary.total_votes),
+                format!("{:.1}", summary.average_votes),
+                summary.most_recent.as_ref()
+                    .map(|recent| format!("#{} {} ({})", recent.poll_id, recent.question, timezone::relative(recent.created_at)))
+                    .unwrap_or_else(|| "none".to_string()),
+            ])
+            .collect();
+        say!("{}", output::linear(&["Category", "Active", "Closed", "Total votes", "Average votes", "Latest"], &rows));
+        return;
+    }
+    say!("{:<12} {:>7} {:>7} {:>12} {:>10}", "Category", "Active", "Closed", "Total Votes", "Avg Votes");
+    say!("{}", "─".repeat(70).cyan());
+
+    for summary in summaries {
+        say!("{:<12} {} {} {:>12} {:>10.1}",
+            summary.category,
+            format!("{:>7}", summary.active).green(),
+            format!("{:>7}", summary.closed).red(),
+            numbers::format_integer(summary.total_votes),
+            summary.average_votes
+        );
+        if let Some(recent) = &summary.most_recent {
+            say!("  {} #{} {} ({})", "Latest:".yellow(), recent.poll_id, recent.question, timezone::relative(recent.created_at));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summarizes_a_category() {
+        let polls = [
+            PollFacts { poll_id: 1, question: "Old".to_string(), created_at: 100, active: false, total_votes: 6 },
+            PollFacts { poll_id: 4, question: "New".to_string(), created_at: 300, active: true, total_votes: 3 },
+            PollFacts { poll_id: 2, question: "Mid".to_string(), created_at: 200, active: true, total_votes: 0 },
+        ];
+        let summary = summarize("governance", &polls);
+        assert_eq!((summary.active, summary.closed, summary.total_votes), (2, 1, 9));
+        assert_eq!(summary.average_votes, 3.0);
+        assert_eq!(summary.most_recent.unwrap().poll_id, 4);
+
+        let empty = summarize("finance", &[]);
+        assert_eq!(empty.average_votes, 0.0);
+        assert!(empty.most_recent.is_none());
+    }
+}
@@ -0,0 +1,197 @@
+use anyhow::Result;
+use colored::*;
+use ethers::prelude::*;
+use ethers::utils::keccak256;
+use serde::{Deserialize, Serialize};
+
+use crate::numbers::SaturatingU64;
+use crate::error::{bail_user, CliError};
+use crate::events::VoteRecord;
+use crate::{format_timestamp, u8_to_category, u8_to_poll_type, u8_to_status, PollManager};
+
+/// Everything that is covered by the operator's signature
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CertifiedResults {
+    pub chain_id: u64,
+    pub contract: Address,
+    pub poll_id: u64,
+    pub question: String,
+    pub options: Vec<String>,
+    pub creator: Address,
+    pub created_at: u64,
+    pub end_time: u64,
+    pub status: String,
+    pub poll_type: String,
+    pub category: String,
+    pub min_participation: U256,
+    pub results: Vec<U256>,
+    pub total_votes: U256,
+    pub total_weight: U256,
+    pub block_number: u64,
+    pub block_hash: H256,
+    pub certified_at: u64,
+    pub votes: Vec<VoteRecord>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResultCertificate {
+    pub payload: CertifiedResults,
+    /// keccak256 of the JSON-serialized payload
+    pub payload_hash: H256,
+    pub signer: Address,
+    /// EIP-191 personal signature over `payload_hash`
+    pub signature: String,
+    /// UID of the EAS attestation of `payload_hash`, with `certify --eas`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub eas_uid: Option<H256>,
+}
+
+impl CertifiedResults {
+    pub fn hash(&self) -> Result<H256> {
+        Ok(H256::from(keccak256(serde_json::to_vec(self)?)))
+    }
+}
+
+impl ResultCertificate {
+    /// Check the payload is unchanged and `signer` signed its hash
+    pub fn verify(&self) -> Result<()> {
+        if self.payload.hash()? != self.payload_hash {
+            bail_user!("The certificate was edited after it was certified");
+        }
+        let signature: Signature = self.signature.parse()
+            .map_err(|_| CliError::User("Certificate signature is malformed".to_string()))?;
+        if signature.verify(self.payload_hash.as_bytes(), self.signer).is_err() {
+            bail_user!("The certificate is not signed by its signer {:?}", self.signer);
+        }
+        Ok(())
+    }
+}
+
+impl<M: Middleware + 'static> PollManager<M> {
+    /// Every field of a closed poll's results, read at the latest block, and that block's timestamp
+    pub async fn certified_results(&self, poll_id: u64) -> Result<(CertifiedResults, u64)> {
+        // Pin every read to a single block so the snapshot is internally consistent
+        let block = self.client
+            .get_block(BlockNumber::Latest)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Latest block not available"))?;
+        let block_number = block.number.ok_or_else(|| anyhow::anyhow!("Latest block has no number"))?;
+        let block_hash = block.hash.ok_or_else(|| anyhow::anyhow!("Latest block has no hash"))?;
+        let block_id = BlockId::Number(BlockNumber::Number(block_number));
+
+        let poll_data = self.contract.get_poll(U256::from(poll_id)).block(block_id).call().await?;
+        // extendPoll only checks for Active, so a poll past its end time can still be reopened
+        if poll_data.6 == 0 {
+            bail_user!("Poll {} is not closed; results can only be certified once it is (see `maintain close-expired`)", poll_id);
+        }
+
+        let (results, total_votes, total_weight) = self.contract
+            .get_poll_results(U256::from(poll_id))
+            .block(block_id)
+            .call()
+            .await?;
+
+        let votes: Vec<VoteRecord> = self.fetch_vote_events(poll_id)
+            .await?
+            .into_iter()
+            .filter(|v| v.block_number <= block_number.as_u64())
+            .collect();
+
+        let payload = CertifiedResults {
+            chain_id: self.wallet.chain_id(),
+            contract: self.contract.address(),
+            poll_id,
+            question: poll_data.1.clone(),
+            options: poll_data.2.clone(),
+            creator: poll_data.3,
+            created_at: poll_data.4.to_u64_saturating(),
+            end_time: poll_data.5.to_u64_saturating(),
+            status: u8_to_status(poll_data.6).to_string(),
+            poll_type: u8_to_poll_type(poll_data.7).to_string(),
+            category: u8_to_category(poll_data.8).to_string(),
+            min_participation: poll_data.9,
+            results,
+            total_votes,
+            total_weight,
+            block_number: block_number.as_u64(),
+            block_hash,
+            certified_at: chrono::Utc::now().timestamp() as u64,
+            votes,
+        };
+        Ok((payload, block.timestamp.to_u64_saturating()))
+    }
+
+    pub async fn certify_poll(&self, poll_id: u64, output_path: Option<String>, eas: bool) -> Result<()> {
+        say!("{} {}", "🔏 Certifying results for poll".cyan().bold(), poll_id.to_string().yellow());
+
+        let (payload, read_at) = self.certified_results(poll_id).await?;
+        let payload_hash = payload.hash()?;
+        let signature = self.wallet.sign_message(payload_hash.as_bytes()).await?;
+
+        let mut certificate = ResultCertificate {
+            payload,
+            payload_hash,
+            signer: self.wallet.address(),
+            signature: format!("0x{}", signature),
+            eas_uid: None,
+        };
+        if eas {
+            certificate.eas_uid = Some(self.attest_certificate(&certificate).await?);
+        }
+
+        let path = output_path.unwrap_or_else(|| format!("poll-{}-certificate.json", poll_id));
+        std::fs::write(&path, serde_json::to_string_pretty(&certificate)?)?;
+
+        say!("{}", "✅ Results certified successfully!".green().bold());
+        say!("{} {}", "Block:".cyan().bold(), format!("{} ({:?})", certificate.payload.block_number, certificate.payload.block_hash).white());
+        say!("{} {}", "Read At:".cyan().bold(), format_timestamp(read_at).white());
+        say!("{} {}", "Votes Included:".cyan().bold(), certificate.payload.votes.len().to_string().yellow());
+        say!("{} {:?}", "Payload Hash:".cyan().bold(), certificate.payload_hash);
+        say!("{} {:?}", "Signer:".cyan().bold(), certificate.signer);
+        say!("{} {}", "Certificate:".cyan().bold(), path.yellow());
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::utils::hash_message;
+
+    #[test]
+    fn certificates_verify_only_for_their_signer() {
+        let wallet: LocalWallet = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80".parse().unwrap();
+        let payload = CertifiedResults {
+            chain_id: 31337,
+            contract: Address::repeat_byte(1),
+            poll_id: 0,
+            question: "Ship it?".to_string(),
+            options: vec!["Yes".to_string(), "No".to_string()],
+            creator: wallet.address(),
+            created_at: 0,
+            end_time: 100,
+            status: "Closed".to_string(),
+            poll_type: "Standard".to_string(),
+            category: "General".to_string(),
+            min_participation: U256::zero(),
+            results: vec![U256::from(3), U256::one()],
+            total_votes: U256::from(4),
+            total_weight: U256::from(4),
+            block_number: 10,
+            block_hash: H256::zero(),
+            certified_at: 200,
+            votes: vec![],
+        };
+        let payload_hash = payload.hash().unwrap();
+        let signature = wallet.sign_hash(hash_message(payload_hash.as_bytes())).unwrap();
+        let mut certificate = ResultCertificate { payload, payload_hash, signer: wallet.address(), signature: format!("0x{}", signature), eas_uid: None };
+        assert!(certificate.verify().is_ok());
+
+        certificate.signer = Address::repeat_byte(9);
+        assert!(certificate.verify().is_err(), "claims someone else signed it");
+        certificate.signer = wallet.address();
+        certificate.payload.results[1] = U256::from(5);
+        assert!(certificate.verify().is_err(), "edited after signing");
+    }
+}
@@ -0,0 +1,270 @@
+use std::f64::consts::PI;
+use std::path::Path;
+
+use anyhow::Result;
+use colored::*;
+use ethers::prelude::*;
+use polling_analytics::stats::share;
+
+use crate::error::bail_user;
+use crate::feed::xml_escape;
+use crate::tally::PollTally;
+use crate::{numbers, output, u256_to_f64, PollManager};
+
+/// Width of the bar area of terminal charts, in characters
+const TERMINAL_WIDTH: usize = 40;
+const TICKS: usize = 4;
+
+/// Fill colors for bars and slices, repeated when there are more options
+pub(crate) const PALETTE: [&str; 8] = ["#4e79a7", "#f28e2b", "#59a14f", "#e15759", "#76b7b2", "#edc948", "#b07aa1", "#9c755f"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ChartKind {
+    Bar,
+    Pie,
+}
+
+/// One value per option, under the metric that decides the poll
+#[derive(Debug, Clone)]
+pub struct Series {
+    pub title: String,
+    pub metric: &'static str,
+    pub points: Vec<(String, f64)>,
+}
+
+impl Series {
+    pub fn from_tally(title: &str, tally: &PollTally) -> Self {
+        let points = tally.options.iter()
+            .map(|o| (o.option.clone(), if tally.weighted { u256_to_f64(o.weight) } else { o.votes as f64 }))
+            .collect();
+        Self { title: title.to_string(), metric: tally.winner_metric(), points }
+    }
+
+    pub(crate) fn total(&self) -> f64 {
+        self.points.iter().map(|(_, v)| v).sum()
+    }
+
+    pub(crate) fn max(&self) -> f64 {
+        self.points.iter().map(|(_, v)| *v).fold(0.0, f64::max)
+    }
+}
+
+pub(crate) fn format_value(value: f64) -> String {
+    if value.fract() == 0.0 && value >= 0.0 && value < u64::MAX as f64 {
+        numbers::format_integer(value as u64)
+    } else {
+        format!("{:.1}", value)
+    }
+}
+
+/// A bar of `cells` character cells, using eighth blocks for the remainder (`#` in ASCII)
+fn bar(cells: f64, ascii: bool) -> String {
+    if ascii {
+        return "#".repeat(cells.round() as usize);
+    }
+    const EIGHTHS: [&str; 8] = ["", "▏", "▎", "▍", "▌", "▋", "▊", "▉"];
+    let eighths = (cells * 8.0).round() as usize;
+    format!("{}{}", "█".repeat(eighths / 8), EIGHTHS[eighths % 8])
+}
+
+/// Horizontal bar chart with a labelled value axis, one line per option
+pub fn terminal_bars(series: &Series, width: usize, ascii: bool) -> Vec<String> {
+    let label_width = series.points.iter().map(|(label, _)| label.chars().count()).max().unwrap_or(0).min(24);
+    let max = series.max();
+    let total = series.total();
+    let (vertical, horizontal, corner, tick) = if ascii { ('|', '-', '+', '+') } else { ('│', '─', '└', '┴') };
+
+    let mut lines = Vec::with_capacity(series.points.len() + 2);
+    for (label, value) in &series.points {
+        let label: String = label.chars().take(label_width).collect();
+        let cells = if max > 0.0 { value / max * width as f64 } else { 0.0 };
+        let bar = bar(cells, ascii);
+        let padding = width.saturating_sub(bar.chars().count());
+        lines.push(format!("{:<lw$} {}{}{} {:>8} ({:>5.1}%)",
+            label, vertical, bar, " ".repeat(padding), format_value(*value), share(*value, total), lw = label_width));
+    }
+
+    let segment = width / TICKS;
+    let mut axis = format!("{:<lw$} {}", "", corner, lw = label_width);
+    let mut labels = format!("{:<lw$} ", "", lw = label_width);
+    for i in 0..TICKS {
+        axis.push_str(&horizontal.to_string().repeat(segment - 1));
+        axis.push(tick);
+        let value = format_value(max * i as f64 / TICKS as f64);
+        labels.push_str(&format!("{:<w$}", value, w = segment));
+    }
+    labels.push_str(&format_value(max));
+    lines.push(axis);
+    lines.push(labels);
+    lines
+}
+
+/// Horizontal bar chart as a standalone SVG document
+pub fn svg_bars(series: &Series) -> String {
+    const LABEL: f64 = 180.0;
+    const PLOT: f64 = 400.0;
+    const ROW: f64 = 34.0;
+    const TOP: f64 = 56.0;
+    let width = LABEL + PLOT + 120.0;
+    let height = TOP + ROW * series.points.len() as f64 + 48.0;
+    let max = series.max();
+    let total = series.total();
+
+    let mut svg = svg_header(width, height, &series.title, &format!("by {}", series.metric));
+    for (i, (label, value)) in series.points.iter().enumerate() {
+        let y = TOP + ROW * i as f64;
+        let length = if max > 0.0 { value / max * PLOT } else { 0.0 };
+        svg.push_str(&format!(
+            "  <text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"end\">{}</text>\n",
+            LABEL - 10.0, y + ROW / 2.0 + 5.0, xml_escape(label)));
+        svg.push_str(&format!(
+            "  <rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\" fill=\"{}\"/>\n",
+            LABEL, y + 6.0, length, ROW - 12.0, PALETTE[i % PALETTE.len()]));
+        svg.push_str(&format!(
+            "  <text x=\"{:.1}\" y=\"{:.1}\">{} ({:.1}%)</text>\n",
+            LABEL + length + 6.0, y + ROW / 2.0 + 5.0, format_value(*value), share(*value, total)));
+    }
+
+    let axis_y = TOP + ROW * series.points.len() as f64;
+    svg.push_str(&format!("  <line x1=\"{0:.1}\" y1=\"{1:.1}\" x2=\"{0:.1}\" y2=\"{2:.1}\" stroke=\"#333\"/>\n", LABEL, TOP, axis_y));
+    svg.push_str(&format!("  <line x1=\"{:.1}\" y1=\"{2:.1}\" x2=\"{:.1}\" y2=\"{2:.1}\" stroke=\"#333\"/>\n", LABEL, LABEL + PLOT, axis_y));
+    for i in 0..=TICKS {
+        let x = LABEL + PLOT * i as f64 / TICKS as f64;
+        svg.push_str(&format!("  <line x1=\"{0:.1}\" y1=\"{1:.1}\" x2=\"{0:.1}\" y2=\"{2:.1}\" stroke=\"#333\"/>\n", x, axis_y, axis_y + 5.0));
+        svg.push_str(&format!(
+            "  <text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"middle\" font-size=\"12\">{}</text>\n",
+            x, axis_y + 20.0, format_value(max * i as f64 / TICKS as f64)));
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Pie chart with a legend as a standalone SVG document
+pub fn svg_pie(series: &Series) -> Result<String> {
+    let total = series.total();
+    if total <= 0.0 {
+        bail_user!("Nothing to draw yet: no {} have been cast", if series.metric == "weight" { "weighted votes" } else { "votes" });
+    }
+    const RADIUS: f64 = 140.0;
+    let (cx, cy) = (40.0 + RADIUS, 60.0 + RADIUS);
+    let width = cx + RADIUS + 300.0;
+    let height = (cy + RADIUS + 30.0).max(80.0 + 24.0 * series.points.len() as f64);
+
+    let mut svg = svg_header(width, height, &series.title, &format!("by {}", series.metric));
+    let mut angle = -PI / 2.0;
+    for (i, (_, value)) in series.points.iter().enumerate() {
+        let color = PALETTE[i % PALETTE.len()];
+        let sweep = value / total * 2.0 * PI;
+        if sweep <= 0.0 {
+            continue;
+        }
+        if sweep >= 2.0 * PI - 1e-9 {
+            // A single option with every vote: an arc cannot start and end at the same point
+            svg.push_str(&format!("  <circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"{:.1}\" fill=\"{}\"/>\n", cx, cy, RADIUS, color));
+        } else {
+            let (x1, y1) = (cx + RADIUS * angle.cos(), cy + RADIUS * angle.sin());
+            let end = angle + sweep;
+            let (x2, y2) = (cx + RADIUS * end.cos(), cy + RADIUS * end.sin());
+            let large_arc = if sweep > PI { 1 } else { 0 };
+            svg.push_str(&format!(
+                "  <path d=\"M {:.2} {:.2} L {:.2} {:.2} A {:.1} {:.1} 0 {} 1 {:.2} {:.2} Z\" fill=\"{}\" stroke=\"#fff\"/>\n",
+                cx, cy, x1, y1, RADIUS, RADIUS, large_arc, x2, y2, color));
+        }
+        angle += sweep;
+    }
+
+    let legend_x = cx + RADIUS + 40.0;
+    for (i, (label, value)) in series.points.iter().enumerate() {
+        let y = 70.0 + 24.0 * i as f64;
+        svg.push_str(&format!("  <rect x=\"{:.1}\" y=\"{:.1}\" width=\"14\" height=\"14\" fill=\"{}\"/>\n", legend_x, y, PALETTE[i % PALETTE.len()]));
+        svg.push_str(&format!(
+            "  <text x=\"{:.1}\" y=\"{:.1}\">{}: {} ({:.1}%)</text>\n",
+            legend_x + 22.0, y + 12.0, xml_escape(label), format_value(*value), share(*value, total)));
+    }
+    svg.push_str("</svg>\n");
+    Ok(svg)
+}
+
+fn svg_header(width: f64, height: f64, title: &str, subtitle: &str) -> String {
+    format!(concat!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w:.0}\" height=\"{h:.0}\" viewBox=\"0 0 {w:.0} {h:.0}\" ",
+        "font-family=\"Helvetica, Arial, sans-serif\" font-size=\"14\">\n",
+        "  <rect width=\"100%\" height=\"100%\" fill=\"#fff\"/>\n",
+        "  <text x=\"20\" y=\"28\" font-size=\"18\" font-weight=\"bold\">{title}</text>\n",
+        "  <text x=\"20\" y=\"46\" font-size=\"12\" fill=\"#666\">{subtitle}</text>\n"),
+        w = width, h = height, title = xml_escape(title), subtitle = xml_escape(subtitle))
+}
+
+impl<M: Middleware + 'static> PollManager<M> {
+    /// `chart`: draw a poll's results in the terminal, or to an SVG file for reports
+    pub async fn chart(&self, poll_id: u64, kind: ChartKind, output_path: Option<String>) -> Result<()> {
+        let poll_data = self.poll_data(U256::from(poll_id)).await?;
+        let tally = self.poll_tally(poll_id, &poll_data.2, poll_data.7).await?;
+        let series = Series::from_tally(&format!("Poll #{}: {}", poll_id, poll_data.1), &tally);
+
+        let Some(path) = output_path else {
+            if kind == ChartKind::Pie {
+                bail_user!("Pie charts are drawn to a file; pass -o results.svg");
+            }
+            say!("\n{} {}", format!("📊 {}", series.title).cyan().bold(), format!("(by {})", series.metric).dimmed());
+            for line in terminal_bars(&series, TERMINAL_WIDTH, output::is_plain()) {
+                say!("{}", line);
+            }
+            say!("\nRead at: {}", self.read_at());
+            return Ok(());
+        };
+
+        match Path::new(&path).extension().and_then(|e| e.to_str()).map(|e| e.to_lowercase()).as_deref() {
+            Some("svg") => {}
+            Some("png") => bail_user!("PNG output is not supported; write an .svg and convert it, e.g. `rsvg-convert -o chart.png chart.svg`"),
+            _ => bail_user!("Chart files are SVG; name the output something.svg"),
+        }
+        let svg = match kind {
+            ChartKind::Bar => svg_bars(&series),
+            ChartKind::Pie => svg_pie(&series)?,
+        };
+        std::fs::write(&path, svg)?;
+        say!("{} {}", "✅ Chart written to:".green().bold(), path.yellow());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn series(points: &[(&str, f64)]) -> Series {
+        Series {
+            title: "Poll #0: A & B?".to_string(),
+            metric: "votes",
+            points: points.iter().map(|(l, v)| (l.to_string(), *v)).collect(),
+        }
+    }
+
+    #[test]
+    fn terminal_bars_scale_to_the_largest_option() {
+        let lines = terminal_bars(&series(&[("Yes", 12.0), ("No", 6.0), ("Abstain", 0.0)]), 40, true);
+        assert_eq!(lines.len(), 5);
+        assert!(lines[0].starts_with("Yes     |########################################"));
+        assert!(lines[1].starts_with("No      |#################### "));
+        assert!(lines[0].ends_with("12 ( 66.7%)"));
+        assert_eq!(lines[3], "        +---------+---------+---------+---------+");
+        assert!(lines[4].trim_start().starts_with("0         3         6         9         12"));
+    }
+
+    #[test]
+    fn svg_escapes_labels_and_draws_every_option() {
+        let svg = svg_bars(&series(&[("<Yes>", 3.0), ("No", 1.0)]));
+        assert!(svg.contains("Poll #0: A &amp; B?"));
+        assert!(svg.contains("&lt;Yes&gt;"));
+        assert_eq!(svg.matches("<rect").count(), 3, "background plus one bar per option");
+    }
+
+    #[test]
+    fn pie_with_one_option_is_a_full_circle() {
+        let svg = svg_pie(&series(&[("Yes", 5.0), ("No", 0.0)])).unwrap();
+        assert!(svg.contains("<circle"));
+        assert!(!svg.contains("<path"));
+        assert!(svg_pie(&series(&[("Yes", 0.0)])).is_err());
+    }
+}
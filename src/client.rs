@@ -0,0 +1,99 @@
+use anyhow::Result;
+use ethers::abi::AbiDecode;
+use ethers::prelude::*;
+use ethers::utils::rlp::Rlp;
+use serde_json::{json, Value};
+
+use crate::connection::{Connection, SignerSource};
+use crate::error::{bail_user, CliError};
+use crate::numbers::SaturatingU64;
+use crate::{Client, PollManager, VoteCall};
+
+/// Read-only access to a polls contract for programs embedding this crate, such as the Python
+/// bindings and the C API. Results come back as the same JSON the CLI's `export -f json` prints.
+/// Votes are signed by the embedding program's own wallet and only broadcast from here.
+pub struct PollClient {
+    manager: PollManager<Client>,
+}
+
+impl PollClient {
+    /// Connect to `contract_address` through `rpc_url`, falling back to RPC_URL and CONTRACT_ADDRESS
+    pub async fn connect(rpc_url: Option<String>, contract_address: Option<String>) -> Result<Self> {
+        let rpc_url = rpc_url.or_else(|| std::env::var("RPC_URL").ok()).unwrap_or_else(|| "http://localhost:8545".to_string());
+        let Some(contract_address) = contract_address.or_else(|| std::env::var("CONTRACT_ADDRESS").ok()) else {
+            crate::error::bail_config!("Pass a contract address or set CONTRACT_ADDRESS");
+        };
+        // Nothing is ever signed, so no configured key is unlocked
+        let signer = SignerSource::PrivateKey(format!("{:?}", H256::random()));
+        let connection = Connection { rpc_url, signer, chain_id: None, impersonate: None, block: None };
+        Ok(Self { manager: PollManager::new(&connection, &contract_address).await? })
+    }
+
+    pub async fn poll_count(&self) -> Result<u64> {
+        Ok(self.manager.contract.poll_count().call().await?.to_u64_saturating())
+    }
+
+    /// A poll with its results, as `export -f json` has it
+    pub async fn poll(&self, poll_id: u64) -> Result<Value> {
+        Ok(serde_json::to_value(self.manager.build_poll_export(poll_id).await?)?)
+    }
+
+    /// Per-option votes, weights and shares
+    pub async fn results(&self, poll_id: u64) -> Result<Value> {
+        let poll = self.manager.poll_data(U256::from(poll_id)).await?;
+        Ok(serde_json::to_value(self.manager.poll_tally(poll_id, &poll.2, poll.7).await?)?)
+    }
+
+    /// The contract's events from the local event index, synced first; only one poll's with `poll_id`
+    pub async fn events(&self, poll_id: Option<u64>) -> Result<Value> {
+        let index = self.manager.event_index().await?;
+        let events = index.events.iter()
+            .map(serde_json::to_value)
+            .filter(|event| match (poll_id, event) {
+                (Some(poll_id), Ok(event)) => event["poll_id"] == poll_id,
+                _ => true,
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Value::Array(events))
+    }
+
+    /// What a wallet needs to sign a vote: the contract, the ABI-encoded `vote` call and the chain
+    pub fn vote_request(&self, poll_id: u64, option_index: u64) -> Value {
+        let call = self.manager.contract.vote(U256::from(poll_id), U256::from(option_index));
+        json!({
+            "to": self.manager.contract.address(),
+            "data": call.calldata().unwrap_or_default(),
+            "chain_id": self.manager.wallet.chain_id(),
+        })
+    }
+
+    /// Broadcast a vote transaction signed elsewhere and wait for it to be mined. Anything but a
+    /// `vote` call on this contract is refused, so the endpoint cannot be used to relay other transactions.
+    pub async fn send_signed_vote(&self, raw: Bytes) -> Result<Value> {
+        let tx: Transaction = Rlp::new(&raw).as_val()
+            .map_err(|e| CliError::User(format!("Not a signed transaction: {}", e)))?;
+        if tx.to != Some(self.manager.contract.address()) {
+            bail_user!("The transaction is not addressed to the polls contract {:?}", self.manager.contract.address());
+        }
+        let Ok(vote) = VoteCall::decode(&tx.input) else {
+            bail_user!("The transaction does not call vote(uint256,uint256)");
+        };
+        let voter = tx.recover_from().map_err(|e| CliError::User(format!("Invalid transaction signature: {}", e)))?;
+
+        let pending = self.manager.client.send_raw_transaction(raw).await
+            .map_err(|e| CliError::Rpc(format!("The node rejected the vote: {}", e)))?;
+        let hash = pending.tx_hash();
+        let receipt = pending.await?
+            .ok_or_else(|| CliError::Rpc(format!("Vote transaction {:?} was dropped", hash)))?;
+        let poll_id = vote.poll_id.to_u64_saturating();
+        self.manager.cache.invalidate(poll_id);
+        Ok(json!({
+            "transaction_hash": receipt.transaction_hash,
+            "block_number": receipt.block_number,
+            "success": receipt.status == Some(1.into()),
+            "voter": voter,
+            "poll_id": poll_id,
+            "option_index": vote.option_index.to_u64_saturating(),
+        }))
+    }
+}
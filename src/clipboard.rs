@@ -0,0 +1,61 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use anyhow::Result;
+use colored::*;
+
+use crate::events::CreatedPoll;
+
+/// Clipboard helpers by platform, tried in order. Shelling out keeps the
+/// clipboard contents alive after we exit, which X11 clipboards otherwise lose.
+const COPY_COMMANDS: &[(&str, &[&str])] = &[
+    ("pbcopy", &[]),
+    ("clip.exe", &[]),
+    ("wl-copy", &[]),
+    ("xclip", &["-selection", "clipboard"]),
+    ("xsel", &["--clipboard", "--input"]),
+];
+
+/// Copy text to the system clipboard with the first helper that is installed
+pub fn copy(text: &str) -> Result<()> {
+    for (program, args) in COPY_COMMANDS {
+        let Ok(mut child) = Command::new(program)
+            .args(*args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+        else {
+            continue;
+        };
+
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(text.as_bytes())?;
+        }
+        if child.wait()?.success() {
+            return Ok(());
+        }
+    }
+    anyhow::bail!("No clipboard helper found (pbcopy, clip.exe, wl-copy, xclip or xsel)")
+}
+
+/// What `--copy` puts on the clipboard after a poll is created
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+pub enum CopyTarget {
+    /// The new poll ID
+    Id,
+    /// The creation transaction hash
+    Tx,
+}
+
+/// Copy part of a created poll, warning instead of failing: the poll already exists
+pub fn copy_created(created: &CreatedPoll, target: CopyTarget) {
+    let (label, text) = match target {
+        CopyTarget::Id => ("poll ID", created.poll_id.to_string()),
+        CopyTarget::Tx => ("transaction hash", format!("{:?}", created.transaction_hash)),
+    };
+    match copy(&text) {
+        Ok(()) => say!("{} {} {}", "📋 Copied".green(), label, text.yellow()),
+        Err(e) => say!("{} {}", "⚠️  Could not copy to clipboard:".yellow(), e),
+    }
+}
@@ -0,0 +1,47 @@
+use anyhow::Result;
+use clap::CommandFactory;
+use clap_complete::Shell;
+use ethers::providers::Middleware;
+
+use crate::numbers::SaturatingU64;
+use crate::{Cli, PollManager};
+
+/// Bash wrapper that completes `-p/--poll-id` values from the live contract
+const BASH_POLL_ID_COMPLETION: &str = r#"
+_polling_cli_with_poll_ids() {
+    local prev="${COMP_WORDS[COMP_CWORD-1]}"
+    if [[ "$prev" == "-p" || "$prev" == "--poll-id" ]]; then
+        COMPREPLY=($(compgen -W "$(polling-cli __poll-ids 2>/dev/null)" -- "${COMP_WORDS[COMP_CWORD]}"))
+        return 0
+    fi
+    _polling-cli "$@"
+}
+complete -F _polling_cli_with_poll_ids -o bashdefault -o default polling-cli
+"#;
+
+pub fn print_completions(shell: Shell) {
+    let mut command = Cli::command();
+    let name = command.get_name().to_string();
+    clap_complete::generate(shell, &mut command, name, &mut std::io::stdout());
+
+    if shell == Shell::Bash {
+        print!("{}", BASH_POLL_ID_COMPLETION);
+    }
+}
+
+pub fn print_manpage() -> Result<()> {
+    let man = clap_mangen::Man::new(Cli::command());
+    man.render(&mut std::io::stdout())?;
+    Ok(())
+}
+
+impl<M: Middleware + 'static> PollManager<M> {
+    /// Print every poll ID, one per line, for shell completion scripts
+    pub async fn print_poll_ids(&self) -> Result<()> {
+        let poll_count = self.contract.poll_count().call().await?;
+        for id in 0..poll_count.to_u64_saturating() {
+            println!("{}", id);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,76 @@
+use std::io::IsTerminal;
+
+use anyhow::Result;
+use colored::*;
+use ethers::prelude::*;
+
+use crate::error::bail_user;
+use crate::numbers::SaturatingU64;
+use crate::tally::PollTally;
+use crate::{duration, my_polls, numbers, timezone, PollManager};
+
+/// Ask before an irreversible action. `--yes` answers for scripts; without it a
+/// non-interactive run is refused rather than acting unconfirmed.
+pub fn confirm(question: &str, yes: bool) -> Result<bool> {
+    if yes {
+        return Ok(true);
+    }
+    if !std::io::stdin().is_terminal() {
+        bail_user!("Refusing to continue without confirmation; pass --yes to skip the prompt in scripts");
+    }
+    let answer = my_polls::prompt(&format!("{} [y/N]", question))?;
+    let proceed = answer.eq_ignore_ascii_case("y") || answer.eq_ignore_ascii_case("yes");
+    if !proceed {
+        say!("Cancelled; nothing was sent.");
+    }
+    Ok(proceed)
+}
+
+fn print_tally(tally: &PollTally) {
+    say!("{}", "Current tally:".cyan().bold());
+    for option in &tally.options {
+        say!("  {}: {} votes ({:.1}%)", option.option, numbers::format_integer(option.votes), tally.deciding_share(option));
+    }
+    match tally.leader() {
+        Some(leader) => say!("  {} {}", "Leading:".yellow(), leader.option.yellow().bold()),
+        None if tally.total_votes > 0 => say!("  {}", "Tied for the lead".yellow()),
+        None => say!("  {}", "No votes yet".yellow()),
+    }
+}
+
+impl<M: Middleware + 'static> PollManager<M> {
+    /// Show what closing `poll_id` would finalize and ask first.
+    /// Polls without votes are refused unless `force`, since closing them ends the poll with no outcome.
+    pub async fn confirm_close(&self, poll_id: u64, yes: bool, force: bool) -> Result<bool> {
+        self.cache.invalidate(poll_id);
+        let poll_data = self.poll_data(U256::from(poll_id)).await?;
+        let tally = self.poll_tally(poll_id, &poll_data.2, poll_data.7).await?;
+        if poll_data.6 != 0 {
+            bail_user!("Poll #{} is already closed", poll_id);
+        }
+        if tally.total_votes == 0 && !force {
+            bail_user!("Poll #{} has no votes; closing it ends the poll with no outcome. Pass --force to close it anyway", poll_id);
+        }
+
+        say!("\n{} #{}: {}", "🛑 Close poll".red().bold(), poll_id, poll_data.1.white().bold());
+        say!("{} {}", "Scheduled end:".cyan(), timezone::format_with_relative(poll_data.5.to_u64_saturating()));
+        print_tally(&tally);
+        say!("{}", "Closing stops voting now and cannot be undone; this tally becomes the final result.".yellow());
+        confirm("Close this poll?", yes)
+    }
+
+    /// Show the new deadline for an extension and ask first
+    pub(crate) async fn confirm_extend(&self, poll_id: u64, question: &str, current_end: u64, new_end: u64, yes: bool) -> Result<bool> {
+        if yes {
+            return Ok(true);
+        }
+        let poll_data = self.poll_data(U256::from(poll_id)).await?;
+        let tally = self.poll_tally(poll_id, &poll_data.2, poll_data.7).await?;
+        say!("\n{} #{}: {}", "⏰ Extend poll".cyan().bold(), poll_id, question.white().bold());
+        say!("{} {}", "Current end:".cyan(), timezone::format_with_relative(current_end));
+        say!("{} {} ({} later)", "New end:".cyan(), timezone::format_with_relative(new_end), duration::format(new_end - current_end));
+        print_tally(&tally);
+        say!("{}", "The contract cannot shorten a poll again; the extra time stays.".yellow());
+        confirm("Extend this poll?", yes)
+    }
+}
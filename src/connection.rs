@@ -0,0 +1,168 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+use ethers::prelude::*;
+
+use crate::accounts::{self, Account};
+use crate::contracts;
+use crate::error::bail_config;
+
+/// Default Anvil test account, used when no key is configured
+const ANVIL_PRIVATE_KEY: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+const PLACEHOLDER_CONTRACT: &str = "0x5FbDB2315678afecb367f032d93F642f64180aa3";
+
+/// Global flags that override RPC_URL, PRIVATE_KEY and CONTRACT_ADDRESS for one invocation
+#[derive(Debug, Clone, Default, clap::Args)]
+pub struct ConnectionArgs {
+    /// RPC endpoint to use instead of RPC_URL
+    #[arg(long, global = true)]
+    pub rpc_url: Option<String>,
+
+    /// Read the signing key from this file instead of PRIVATE_KEY
+    #[arg(long, global = true)]
+    pub private_key_file: Option<PathBuf>,
+
+    /// Sign as a registered account (see `accounts add`) instead of PRIVATE_KEY
+    #[arg(long, global = true, conflicts_with = "private_key_file")]
+    pub from: Option<String>,
+
+    /// Chain id the RPC endpoint must report; commands refuse to run against any other chain
+    #[arg(long, global = true)]
+    pub chain_id: Option<u64>,
+
+    /// Send transactions as this address on an Anvil node (anvil_impersonateAccount), e.g. a mainnet fork
+    #[arg(long, global = true)]
+    pub impersonate: Option<Address>,
+
+    /// Evaluate reads at this block number or tag (latest, safe, finalized, earliest); past blocks need an archive node
+    #[arg(long, global = true, value_name = "NUMBER|TAG")]
+    pub block: Option<BlockNumber>,
+
+    /// Evaluate reads at the finalized block, so results cannot change under a reorg
+    #[arg(long, global = true, conflicts_with = "block")]
+    pub finalized: bool,
+
+    /// Contract address to use instead of CONTRACT_ADDRESS
+    #[arg(long, global = true, conflicts_with_all = ["contract", "all_contracts"])]
+    pub contract_address: Option<String>,
+
+    /// Registered contract name (see `contracts add`) or address to use instead of CONTRACT_ADDRESS
+    #[arg(long, global = true)]
+    pub contract: Option<String>,
+
+    /// Run `list` or `analytics` across every registered contract
+    #[arg(long, global = true, conflicts_with = "contract")]
+    pub all_contracts: bool,
+}
+
+/// Where the signing key comes from
+#[derive(Debug, Clone)]
+pub enum SignerSource {
+    /// A hex private key from PRIVATE_KEY or --private-key-file
+    PrivateKey(String),
+    /// An account registered with `accounts add` or `key import`, chosen with --from or POLL_ACCOUNT
+    Account { name: String, account: Account },
+}
+
+/// Where to connect and who signs, after flags, environment and defaults are applied
+#[derive(Debug, Clone)]
+pub struct Connection {
+    pub rpc_url: String,
+    pub signer: SignerSource,
+    /// Expected chain id, checked against the endpoint when given
+    pub chain_id: Option<u64>,
+    /// Address transactions are sent from instead of the signer's, unlocked on the node
+    pub impersonate: Option<Address>,
+    /// Block that reads are evaluated at instead of the head
+    pub block: Option<BlockNumber>,
+}
+
+impl ConnectionArgs {
+    /// Flags first, then RPC_URL / PRIVATE_KEY / POLL_ACCOUNT, then the local Anvil defaults
+    pub fn connection(&self) -> Result<Connection> {
+        let rpc_url = match &self.rpc_url {
+            Some(url) => url.clone(),
+            None => std::env::var("RPC_URL").unwrap_or_else(|_| "http://localhost:8545".to_string()),
+        };
+        let signer = match (&self.from, &self.private_key_file) {
+            (Some(name), _) => SignerSource::Account { name: name.clone(), account: accounts::get(name)? },
+            (None, Some(path)) => match std::fs::read_to_string(path) {
+                Ok(key) => SignerSource::PrivateKey(key.trim().to_string()),
+                Err(e) => bail_config!("Failed to read --private-key-file {}: {}", path.display(), e),
+            },
+            (None, None) => match (std::env::var("PRIVATE_KEY"), std::env::var("POLL_ACCOUNT")) {
+                (Ok(key), _) => SignerSource::PrivateKey(key),
+                (Err(_), Ok(name)) if !name.is_empty() => SignerSource::Account { account: accounts::get(&name)?, name },
+                _ => SignerSource::PrivateKey(ANVIL_PRIVATE_KEY.to_string()),
+            },
+        };
+        Ok(Connection { rpc_url, signer, chain_id: self.chain_id, impersonate: self.impersonate, block: if self.finalized { Some(BlockNumber::Finalized) } else { self.block } })
+    }
+
+    /// `--contract-address`, then `--contract`, then CONTRACT_ADDRESS, then the first Anvil deployment address
+    pub fn contract_address(&self) -> Result<String> {
+        if let Some(address) = &self.contract_address {
+            return Ok(address.clone());
+        }
+        if let Some(name) = &self.contract {
+            return Ok(format!("{:?}", contracts::resolve(name)?));
+        }
+        Ok(std::env::var("CONTRACT_ADDRESS").unwrap_or_else(|_| {
+            say!("⚠️  CONTRACT_ADDRESS not set, using placeholder");
+            PLACEHOLDER_CONTRACT.to_string()
+        }))
+    }
+}
+
+impl Connection {
+    /// The signing wallet, unlocking a keystore account when one was chosen
+    pub fn wallet(&self) -> Result<LocalWallet> {
+        match &self.signer {
+            SignerSource::PrivateKey(key) => accounts::wallet_from_key(key),
+            SignerSource::Account { name, account } => accounts::unlock(name, account),
+        }
+    }
+
+    /// Refuse to continue when the endpoint is not on the chain `--chain-id` asked for
+    pub fn check_chain_id(&self, actual: u64) -> Result<()> {
+        match self.chain_id {
+            Some(expected) if expected != actual => {
+                bail_config!("{} is on chain {}, not the chain {} given with --chain-id", self.rpc_url, actual, expected)
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flags_take_precedence_and_key_files_are_trimmed() {
+        let path = std::env::temp_dir().join(format!("poll-key-test-{}", std::process::id()));
+        std::fs::write(&path, format!("{}\n", ANVIL_PRIVATE_KEY)).unwrap();
+        let args = ConnectionArgs {
+            rpc_url: Some("http://node:8545".to_string()),
+            private_key_file: Some(path.clone()),
+            chain_id: Some(5),
+            contract_address: Some(PLACEHOLDER_CONTRACT.to_string()),
+            ..Default::default()
+        };
+        let connection = args.connection().unwrap();
+        std::fs::remove_file(path).unwrap();
+
+        assert_eq!(connection.rpc_url, "http://node:8545");
+        assert!(matches!(&connection.signer, SignerSource::PrivateKey(key) if key == ANVIL_PRIVATE_KEY));
+        assert_eq!(args.contract_address().unwrap(), PLACEHOLDER_CONTRACT);
+        assert!(connection.check_chain_id(5).is_ok());
+        assert!(connection.check_chain_id(1).is_err());
+    }
+
+    #[test]
+    fn missing_key_file_is_a_config_error() {
+        let args = ConnectionArgs { private_key_file: Some(PathBuf::from("/nonexistent/key")), ..Default::default() };
+        let err = args.connection().unwrap_err();
+        assert!(matches!(crate::error::classify(&err), crate::error::ErrorKind::Config));
+    }
+}
@@ -0,0 +1,168 @@
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+use colored::*;
+use ethers::prelude::*;
+use serde::Serialize;
+use tabled::Tabled;
+
+use crate::error::bail_user;
+use crate::numbers::SaturatingU64;
+use crate::{numbers, output, store, PollManager};
+
+const CONTRACTS_FILE: &str = "contracts.json";
+
+/// Every registered EnhancedPolls deployment by name
+pub fn all() -> Result<BTreeMap<String, Address>> {
+    store::load(CONTRACTS_FILE)
+}
+
+/// A registered contract's address; a literal address is accepted as is
+pub fn resolve(name: &str) -> Result<Address> {
+    if let Ok(address) = name.parse::<Address>() {
+        return Ok(address);
+    }
+    let contracts = all()?;
+    match contracts.get(name) {
+        Some(address) => Ok(*address),
+        None if contracts.is_empty() => bail_user!("No contract named '{}'; register one with `contracts add`", name),
+        None => bail_user!(
+            "No contract named '{}'. Registered: {}",
+            name,
+            contracts.keys().cloned().collect::<Vec<_>>().join(", ")
+        ),
+    }
+}
+
+pub fn add_contract(name: &str, address: &str) -> Result<()> {
+    if name.parse::<Address>().is_ok() {
+        bail_user!("Contract names cannot be addresses; pick a name like `main` or `grants`");
+    }
+    let Ok(address) = address.parse::<Address>() else {
+        bail_user!("Invalid contract address '{}'", address);
+    };
+    let mut contracts = all()?;
+    let replaced = contracts.insert(name.to_string(), address).is_some();
+    store::save(CONTRACTS_FILE, &contracts)?;
+
+    let verb = if replaced { "updated" } else { "registered" };
+    say!("{} {} {} ({:?})", "✅ Contract".green().bold(), name.yellow(), verb.green().bold(), address);
+    Ok(())
+}
+
+pub fn remove_contract(name: &str) -> Result<()> {
+    let mut contracts = all()?;
+    if contracts.remove(name).is_none() {
+        bail_user!("No contract named '{}'", name);
+    }
+    store::save(CONTRACTS_FILE, &contracts)?;
+    say!("{} {}", "🗑️  Removed contract".green().bold(), name.yellow());
+    Ok(())
+}
+
+pub fn list_contracts() -> Result<()> {
+    let contracts = all()?;
+
+    say!("\n{}", "🏛️  Registered Contracts:".cyan().bold());
+    if contracts.is_empty() {
+        say!("No contracts registered yet. Add one with `contracts add <name> <address>`.");
+        return Ok(());
+    }
+    for (name, address) in &contracts {
+        say!("  {} {:?}", format!("{:<16}", name).yellow().bold(), address);
+    }
+    Ok(())
+}
+
+/// One contract's row in the all-contracts analytics
+#[derive(Debug, Clone, Default, Serialize, Tabled)]
+pub struct ContractSummary {
+    pub contract: String,
+    #[tabled(skip)]
+    pub address: Address,
+    pub polls: u64,
+    pub active: u64,
+    pub closed: u64,
+    #[tabled(display_with = "display_votes")]
+    pub total_votes: u64,
+}
+
+fn display_votes(votes: &u64) -> String {
+    numbers::format_integer(*votes)
+}
+
+impl ContractSummary {
+    /// Totals across contracts, labelled `All contracts`
+    pub fn total(summaries: &[ContractSummary]) -> Self {
+        summaries.iter().fold(
+            ContractSummary { contract: "All contracts".to_string(), ..Default::default() },
+            |mut total, s| {
+                total.polls += s.polls;
+                total.active += s.active;
+                total.closed += s.closed;
+                total.total_votes = total.total_votes.saturating_add(s.total_votes);
+                total
+            },
+        )
+    }
+}
+
+pub fn print_summaries(summaries: &[ContractSummary]) {
+    say!("\n{}", "📊 ALL-CONTRACTS ANALYTICS".cyan().bold().underline());
+    let total = ContractSummary::total(summaries);
+    let rows: Vec<&ContractSummary> = summaries.iter().chain(std::iter::once(&total)).collect();
+    say!("{}", output::table(rows));
+    if total.polls > 0 {
+        say!("{} {:.1}", "Average Votes per Poll:".yellow().bold(), total.total_votes as f64 / total.polls as f64);
+    }
+}
+
+impl<M: Middleware + 'static> PollManager<M> {
+    /// Poll and vote counts for this manager's contract
+    pub async fn contract_summary(&self, name: &str) -> Result<ContractSummary> {
+        let now = self.now();
+        let poll_count = self.at_block(self.contract.poll_count()).call().await?.to_u64_saturating();
+        let mut summary = ContractSummary {
+            contract: name.to_string(),
+            address: self.contract.address(),
+            polls: poll_count,
+            ..Default::default()
+        };
+        for id in 0..poll_count {
+            let poll_data = self.poll_data(U256::from(id)).await?;
+            if poll_data.6 == 0 && now <= poll_data.5.to_u64_saturating() {
+                summary.active += 1;
+            } else {
+                summary.closed += 1;
+            }
+            summary.total_votes = summary.total_votes.saturating_add(poll_data.10.to_u64_saturating());
+        }
+        Ok(summary)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn totals_across_contracts() {
+        let summary = |contract: &str, polls, active, total_votes| ContractSummary {
+            contract: contract.to_string(),
+            polls,
+            active,
+            closed: polls - active,
+            total_votes,
+            ..Default::default()
+        };
+        let total = ContractSummary::total(&[summary("main", 3, 1, 40), summary("grants", 2, 2, 7)]);
+        assert_eq!((total.polls, total.active, total.closed, total.total_votes), (5, 3, 2, 47));
+        assert_eq!(total.contract, "All contracts");
+    }
+
+    #[test]
+    fn literal_addresses_resolve_without_the_registry() {
+        let address = "0x5FbDB2315678afecb367f032d93F642f64180aa3";
+        assert_eq!(resolve(address).unwrap(), address.parse::<Address>().unwrap());
+    }
+}
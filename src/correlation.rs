@@ -0,0 +1,276 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use anyhow::Result;
+use colored::*;
+use ethers::prelude::*;
+use polling_analytics::stats;
+use serde::Serialize;
+
+use crate::error::bail_user;
+use crate::events::VoteRecord;
+use crate::preview::truncate;
+use crate::{numbers, PollManager};
+
+/// Voters per (X option, Y option) pair, from the two polls' VoteCast events.
+/// A voter who picked several options in a multi-choice poll counts once for each.
+#[derive(Debug, Default, PartialEq)]
+struct Tabulation {
+    /// `cells[x][y]`: voters who chose option x in poll X and option y in poll Y
+    cells: Vec<Vec<usize>>,
+    /// Voters per X option who did not vote in poll Y
+    not_voted_y: Vec<usize>,
+    voters_x: usize,
+    voters_y: usize,
+    /// Voters who voted in both polls
+    shared: usize,
+}
+
+fn choices(votes: &[VoteRecord]) -> BTreeMap<Address, BTreeSet<u64>> {
+    let mut choices: BTreeMap<Address, BTreeSet<u64>> = BTreeMap::new();
+    for vote in votes {
+        choices.entry(vote.voter).or_default().insert(vote.option_index);
+    }
+    choices
+}
+
+/// Cross-tabulate poll X's votes against poll Y's; each side has at least `options_x`/`options_y` columns
+fn tabulate(x: &[VoteRecord], y: &[VoteRecord], options_x: usize, options_y: usize) -> Tabulation {
+    let (x, y) = (choices(x), choices(y));
+    let width = |choices: &BTreeMap<Address, BTreeSet<u64>>, options: usize| {
+        choices.values().flatten().map(|index| *index as usize + 1).fold(options, usize::max)
+    };
+    let (rows, columns) = (width(&x, options_x), width(&y, options_y));
+
+    let mut tabulation = Tabulation {
+        cells: vec![vec![0; columns]; rows],
+        not_voted_y: vec![0; rows],
+        voters_x: x.len(),
+        voters_y: y.len(),
+        shared: 0,
+    };
+    for (voter, picked_x) in &x {
+        let picked_y = y.get(voter);
+        tabulation.shared += picked_y.is_some() as usize;
+        for option_x in picked_x {
+            let row = *option_x as usize;
+            match picked_y {
+                Some(picked_y) => picked_y.iter().for_each(|option_y| tabulation.cells[row][*option_y as usize] += 1),
+                None => tabulation.not_voted_y[row] += 1,
+            }
+        }
+    }
+    tabulation
+}
+
+/// How strongly the two votes go together, by Cramér's V
+fn strength(cramers_v: f64) -> &'static str {
+    match cramers_v {
+        v if v < 0.1 => "negligible",
+        v if v < 0.3 => "weak",
+        v if v < 0.5 => "moderate",
+        _ => "strong",
+    }
+}
+
+fn option_name(options: &[String], index: usize) -> String {
+    options.get(index).cloned().unwrap_or_else(|| format!("Option {}", index))
+}
+
+/// How the voters of one option in poll X voted in poll Y
+#[derive(Debug, Clone, Serialize)]
+pub struct Bloc {
+    pub option: String,
+    pub voters: usize,
+    /// Voters per poll Y option, in option order
+    pub votes_y: Vec<usize>,
+    pub not_voted_y: usize,
+    /// The poll Y option most of them chose; absent when they split evenly or did not vote in Y
+    pub top_choice: Option<String>,
+    /// That option's share of the bloc's votes in poll Y
+    pub top_share: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Correlation {
+    pub poll_x: u64,
+    pub question_x: String,
+    pub poll_y: u64,
+    pub question_y: String,
+    pub options_y: Vec<String>,
+    pub voters_x: usize,
+    pub voters_y: usize,
+    pub shared_voters: usize,
+    /// Cramér's V of the cross-tab between voters of both polls: 0 independent, 1 one vote decides the other
+    pub cramers_v: f64,
+    pub strength: String,
+    pub blocs: Vec<Bloc>,
+}
+
+impl Correlation {
+    fn new(poll_x: (u64, String, Vec<String>), poll_y: (u64, String, Vec<String>), tabulation: Tabulation) -> Self {
+        let table: Vec<Vec<f64>> = tabulation.cells.iter().map(|row| row.iter().map(|n| *n as f64).collect()).collect();
+        let cramers_v = stats::cramers_v(&table);
+        let columns = tabulation.cells.first().map_or(0, Vec::len);
+        let options_y: Vec<String> = (0..columns).map(|index| option_name(&poll_y.2, index)).collect();
+        let blocs = tabulation.cells.iter().zip(&tabulation.not_voted_y).enumerate().map(|(index, (row, not_voted))| {
+            let voted: usize = row.iter().sum();
+            let top = row.iter().copied().max().filter(|top| *top > 0);
+            let top = top.filter(|top| row.iter().filter(|n| *n == top).count() == 1);
+            let top_index = top.and_then(|top| row.iter().position(|n| *n == top));
+            Bloc {
+                option: option_name(&poll_x.2, index),
+                voters: voted + not_voted,
+                votes_y: row.clone(),
+                not_voted_y: *not_voted,
+                top_choice: top_index.map(|index| options_y[index].clone()),
+                top_share: top.map(|top| stats::share(top as f64, voted as f64)),
+            }
+        }).collect();
+        Self {
+            poll_x: poll_x.0,
+            question_x: poll_x.1,
+            poll_y: poll_y.0,
+            question_y: poll_y.1,
+            options_y,
+            voters_x: tabulation.voters_x,
+            voters_y: tabulation.voters_y,
+            shared_voters: tabulation.shared,
+            cramers_v,
+            strength: strength(cramers_v).to_string(),
+            blocs,
+        }
+    }
+}
+
+fn render_csv(correlation: &Correlation) -> Result<String> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record(["x_option", "y_option", "voters", "row_share"])?;
+    for bloc in &correlation.blocs {
+        let voted: usize = bloc.votes_y.iter().sum();
+        for (option_y, voters) in correlation.options_y.iter().zip(&bloc.votes_y) {
+            writer.write_record([
+                bloc.option.clone(),
+                option_y.clone(),
+                voters.to_string(),
+                format!("{:.2}", stats::share(*voters as f64, voted as f64)),
+            ])?;
+        }
+        writer.write_record([bloc.option.clone(), String::new(), bloc.not_voted_y.to_string(), String::new()])?;
+    }
+    Ok(String::from_utf8(writer.into_inner()?)?)
+}
+
+fn print_correlation(correlation: &Correlation) {
+    say!("\n{}", format!("🔗 CORRELATION: POLL #{} → POLL #{}", correlation.poll_x, correlation.poll_y).cyan().bold().underline());
+    say!("{}", "═".repeat(60).cyan());
+    say!("{} #{} {}", "X:".yellow().bold(), correlation.poll_x, correlation.question_x);
+    say!("{} #{} {}", "Y:".yellow().bold(), correlation.poll_y, correlation.question_y);
+    say!("{} {} of {} in X, {} in Y",
+        "Shared Voters:".yellow().bold(),
+        numbers::format_integer(correlation.shared_voters as u64).green(),
+        numbers::format_integer(correlation.voters_x as u64),
+        numbers::format_integer(correlation.voters_y as u64),
+    );
+    if correlation.shared_voters == 0 {
+        say!("\nNo one voted in both polls.");
+        return;
+    }
+
+    // Row percentages: where each X option's voters went in Y
+    print!("\n{:<20}", "X \\ Y");
+    for option in &correlation.options_y {
+        print!("  {:>14}", truncate(option, 14));
+    }
+    say!("  {:>11}", "Didn't vote");
+    for bloc in &correlation.blocs {
+        let voted: usize = bloc.votes_y.iter().sum();
+        print!("{:<20}", truncate(&bloc.option, 20));
+        for voters in &bloc.votes_y {
+            let cell = format!("{} ({:.0}%)", numbers::format_integer(*voters as u64), stats::share(*voters as f64, voted as f64));
+            print!("  {:>14}", cell);
+        }
+        say!("  {:>11}", numbers::format_integer(bloc.not_voted_y as u64));
+    }
+
+    say!("\n{}", "🧭 BLOCS".cyan().bold());
+    say!("{}", "─".repeat(60).cyan());
+    for bloc in &correlation.blocs {
+        match (&bloc.top_choice, bloc.top_share) {
+            (Some(choice), Some(share)) => say!("{} voters → {} ({:.1}%)", bloc.option, choice.green().bold(), share),
+            _ if bloc.votes_y.iter().all(|n| *n == 0) => say!("{} voters → {}", bloc.option, "none voted in Y".dimmed()),
+            _ => say!("{} voters → {}", bloc.option, "split".yellow()),
+        }
+    }
+    say!("\n{} {:.3} ({})", "Cramér's V:".yellow().bold(), correlation.cramers_v, correlation.strength.bold());
+}
+
+impl<M: Middleware + 'static> PollManager<M> {
+    /// `correlate`: how the voters of each option in one poll voted in another
+    pub async fn correlate(&self, poll_x: u64, poll_y: u64, format: &str, output_path: Option<String>) -> Result<()> {
+        let format = format.to_lowercase();
+        if !matches!(format.as_str(), "table" | "csv" | "json") {
+            bail_user!("Unsupported format: {}. Use table, csv or json", format);
+        }
+        if poll_x == poll_y {
+            bail_user!("--with must name a different poll than --poll-id");
+        }
+
+        let x = self.poll_data(U256::from(poll_x)).await?;
+        let y = self.poll_data(U256::from(poll_y)).await?;
+        let tabulation = tabulate(&self.fetch_vote_events(poll_x).await?, &self.fetch_vote_events(poll_y).await?, x.2.len(), y.2.len());
+        let correlation = Correlation::new((poll_x, x.1, x.2), (poll_y, y.1, y.2), tabulation);
+
+        let rendered = match format.as_str() {
+            "csv" => render_csv(&correlation)?,
+            "json" => serde_json::to_string_pretty(&correlation)?,
+            _ => {
+                print_correlation(&correlation);
+                return Ok(());
+            }
+        };
+        match output_path {
+            Some(path) => {
+                std::fs::write(&path, rendered)?;
+                say!("{} {}", "✅ Exported to:".green().bold(), path.yellow());
+            }
+            None => println!("{}", rendered.trim_end()),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vote(voter: u64, option_index: u64) -> VoteRecord {
+        VoteRecord {
+            poll_id: 0,
+            voter: Address::from_low_u64_be(voter),
+            option_index,
+            weight: U256::one(),
+            block_number: 0,
+            transaction_hash: H256::zero(),
+            timestamp: 0,
+        }
+    }
+
+    #[test]
+    fn cross_tabulates_shared_voters() {
+        let x = vec![vote(1, 0), vote(2, 0), vote(3, 1), vote(4, 1), vote(5, 1)];
+        let y = vec![vote(1, 0), vote(2, 0), vote(3, 1), vote(4, 1), vote(6, 2)];
+        let tabulation = tabulate(&x, &y, 2, 3);
+        assert_eq!(tabulation.cells, vec![vec![2, 0, 0], vec![0, 2, 0]]);
+        assert_eq!(tabulation.not_voted_y, vec![0, 1]);
+        assert_eq!((tabulation.voters_x, tabulation.voters_y, tabulation.shared), (5, 5, 4));
+
+        let options = |names: &[&str]| names.iter().map(|name| name.to_string()).collect::<Vec<_>>();
+        let correlation = Correlation::new((0, "X".into(), options(&["Yes", "No"])), (1, "Y".into(), options(&["A", "B", "C"])), tabulation);
+        assert_eq!(correlation.strength, "strong");
+        assert_eq!(correlation.blocs[0].top_choice.as_deref(), Some("A"));
+        assert_eq!((correlation.blocs[1].voters, correlation.blocs[1].top_share), (3, Some(100.0)));
+
+        // Votes for an option the poll doesn't list still get a column
+        assert_eq!(tabulate(&x, &[vote(1, 4)], 2, 3).cells[0], vec![0, 0, 0, 0, 1]);
+    }
+}
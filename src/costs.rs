@@ -0,0 +1,439 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use anyhow::Result;
+use colored::*;
+use ethers::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::error::bail_user;
+use crate::{numbers, output, price, u256_to_f64, PollManager};
+
+/// Gas per call used for planned operations the signer has never sent before
+const DEFAULT_GAS: &[(&str, u64)] = &[
+    ("createPoll", 450_000),
+    ("vote", 120_000),
+    ("voteAsDelegate", 140_000),
+    ("closePoll", 60_000),
+    ("extendPoll", 50_000),
+    ("setDelegate", 70_000),
+    ("removeDelegate", 40_000),
+];
+
+/// Short names accepted by `--plan`, alongside the contract function names
+const PLAN_ALIASES: &[(&str, &str)] = &[
+    ("create", "createPoll"),
+    ("vote", "vote"),
+    ("delegate-vote", "voteAsDelegate"),
+    ("close", "closePoll"),
+    ("extend", "extendPoll"),
+    ("delegate", "setDelegate"),
+    ("undelegate", "removeDelegate"),
+];
+
+/// One mined transaction sent by the signer to the contract
+#[derive(Debug, Clone)]
+pub struct GasSpend {
+    pub function: String,
+    pub gas_used: U256,
+    /// gas_used × effective gas price, in wei
+    pub cost: U256,
+    /// ETH sent along with the call (platform fees)
+    pub value: U256,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FunctionCost {
+    pub function: String,
+    pub transactions: u64,
+    pub gas_used: u64,
+    pub average_gas: u64,
+    pub cost_eth: f64,
+    pub fees_eth: f64,
+    /// Cost including fees in the `--fiat` currency
+    pub cost_fiat: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlannedCost {
+    pub function: String,
+    pub count: u64,
+    pub gas_per_call: u64,
+    /// "history" when averaged from the signer's own transactions, else "default"
+    pub basis: String,
+    pub cost_eth: f64,
+    pub cost_fiat: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CostEstimate {
+    pub gas_price_gwei: f64,
+    pub operations: Vec<PlannedCost>,
+    pub total_eth: f64,
+    pub total_fiat: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CostReport {
+    pub signer: Address,
+    pub contract: Address,
+    /// Currency of the `*_fiat` fields, when prices were available
+    pub fiat: Option<String>,
+    pub eth_price: Option<f64>,
+    pub transactions: u64,
+    pub total_gas_used: u64,
+    pub total_cost_eth: f64,
+    pub total_fees_eth: f64,
+    pub total_cost_fiat: Option<f64>,
+    pub by_function: Vec<FunctionCost>,
+    pub estimate: Option<CostEstimate>,
+}
+
+fn wei_to_eth(wei: U256) -> f64 {
+    u256_to_f64(wei) / 1e18
+}
+
+/// Parse a plan such as `create=3,vote=20,close=3` into contract functions and counts
+pub fn parse_plan(plan: &str) -> Result<Vec<(String, u64)>> {
+    let mut operations = Vec::new();
+    for entry in plan.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+        let (name, count) = entry.split_once('=').unwrap_or((entry, "1"));
+        let name = name.trim();
+        let Some(function) = PLAN_ALIASES.iter()
+            .find(|(alias, function)| alias.eq_ignore_ascii_case(name) || function.eq_ignore_ascii_case(name))
+            .map(|(_, function)| function.to_string())
+        else {
+            let known: Vec<&str> = PLAN_ALIASES.iter().map(|(alias, _)| *alias).collect();
+            bail_user!("Unknown operation '{}' in --plan. Use: {}", name, known.join(", "));
+        };
+        let Ok(count) = count.trim().parse::<u64>() else {
+            bail_user!("Invalid count '{}' for {} in --plan", count, name);
+        };
+        operations.push((function, count));
+    }
+    if operations.is_empty() {
+        bail_user!("--plan needs at least one operation, e.g. create=3,vote=20");
+    }
+    Ok(operations)
+}
+
+/// Per-function totals, most expensive first
+pub fn summarize(spends: &[GasSpend], eth_price: Option<f64>) -> Vec<FunctionCost> {
+    let mut by_function: BTreeMap<&str, (u64, U256, U256, U256)> = BTreeMap::new();
+    for spend in spends {
+        let entry = by_function.entry(spend.function.as_str()).or_default();
+        entry.0 += 1;
+        entry.1 = entry.1.saturating_add(spend.gas_used);
+        entry.2 = entry.2.saturating_add(spend.cost);
+        entry.3 = entry.3.saturating_add(spend.value);
+    }
+
+    let mut costs: Vec<FunctionCost> = by_function.into_iter()
+        .map(|(function, (transactions, gas, cost, value))| {
+            let cost_eth = wei_to_eth(cost);
+            let fees_eth = wei_to_eth(value);
+            FunctionCost {
+                function: function.to_string(),
+                transactions,
+                gas_used: gas.low_u64(),
+                average_gas: (gas / U256::from(transactions)).low_u64(),
+                cost_eth,
+                fees_eth,
+                cost_fiat: eth_price.map(|p| (cost_eth + fees_eth) * p),
+            }
+        })
+        .collect();
+    costs.sort_by(|a, b| (b.cost_eth + b.fees_eth).partial_cmp(&(a.cost_eth + a.fees_eth)).unwrap_or(std::cmp::Ordering::Equal));
+    costs
+}
+
+/// Cost of `plan` at `gas_price`, using the signer's average gas per function where known
+pub fn estimate(plan: &[(String, u64)], history: &[FunctionCost], gas_price: U256, platform_fee: U256, eth_price: Option<f64>) -> CostEstimate {
+    let operations: Vec<PlannedCost> = plan.iter()
+        .map(|(function, count)| {
+            let known = history.iter().find(|c| &c.function == function);
+            let (gas_per_call, basis) = match known {
+                Some(cost) => (cost.average_gas, "history"),
+                None => (DEFAULT_GAS.iter().find(|(f, _)| f == function).map(|(_, g)| *g).unwrap_or(100_000), "default"),
+            };
+            let fee = if function == "createPoll" { platform_fee } else { U256::zero() };
+            let per_call = gas_price.saturating_mul(U256::from(gas_per_call)).saturating_add(fee);
+            let cost_eth = wei_to_eth(per_call.saturating_mul(U256::from(*count)));
+            PlannedCost {
+                function: function.clone(),
+                count: *count,
+                gas_per_call,
+                basis: basis.to_string(),
+                cost_eth,
+                cost_fiat: eth_price.map(|p| cost_eth * p),
+            }
+        })
+        .collect();
+
+    let total_eth = operations.iter().map(|o| o.cost_eth).sum();
+    CostEstimate {
+        gas_price_gwei: u256_to_f64(gas_price) / 1e9,
+        operations,
+        total_eth,
+        total_fiat: eth_price.map(|p| total_eth * p),
+    }
+}
+
+impl<M: Middleware + 'static> PollManager<M> {
+    /// Hashes of transactions that emitted an event naming the signer, or one of their polls
+    async fn signer_transaction_hashes(&self, signer: Address) -> Result<BTreeSet<(u64, H256)>> {
+        let mut hashes = BTreeSet::new();
+        let mut add = |metas: Vec<LogMeta>| {
+            for meta in metas {
+                hashes.insert((meta.block_number.as_u64(), meta.transaction_hash));
+            }
+        };
+
+        let created = self.contract.poll_created_filter().topic2(signer).from_block(0u64).query_with_meta().await?;
+        add(created.into_iter().map(|(_, meta)| meta).collect());
+        let votes = self.contract.vote_cast_filter().topic2(signer).from_block(0u64).query_with_meta().await?;
+        add(votes.into_iter().map(|(_, meta)| meta).collect());
+        let delegated = self.contract.delegate_set_filter().topic1(signer).from_block(0u64).query_with_meta().await?;
+        add(delegated.into_iter().map(|(_, meta)| meta).collect());
+        let undelegated = self.contract.delegate_removed_filter().topic1(signer).from_block(0u64).query_with_meta().await?;
+        add(undelegated.into_iter().map(|(_, meta)| meta).collect());
+
+        // Closing and extending only emit the poll ID, so look at the signer's own polls
+        let poll_ids: Vec<H256> = self.contract.get_user_created_polls(signer).call().await?
+            .into_iter()
+            .map(|id| H256::from_uint(&id))
+            .collect();
+        if !poll_ids.is_empty() {
+            let closed = self.contract.poll_status_changed_filter().topic1(poll_ids.clone()).from_block(0u64).query_with_meta().await?;
+            add(closed.into_iter().map(|(_, meta)| meta).collect());
+            let extended = self.contract.poll_extended_filter().topic1(poll_ids).from_block(0u64).query_with_meta().await?;
+            add(extended.into_iter().map(|(_, meta)| meta).collect());
+        }
+        Ok(hashes)
+    }
+
+    /// Gas spent by the signer on this contract, from their mined transactions
+    async fn signer_gas_spends(&self, signer: Address) -> Result<Vec<GasSpend>> {
+        let selectors: HashMap<[u8; 4], String> = self.contract.abi().functions()
+            .map(|f| (f.short_signature(), f.name.clone()))
+            .collect();
+
+        let mut spends = Vec::new();
+        for (_, hash) in self.signer_transaction_hashes(signer).await? {
+            let Some(tx) = self.client.get_transaction(hash).await? else { continue };
+            // Events name the signer, but someone else may have sent the transaction
+            if tx.from != signer || tx.to != Some(self.contract.address()) {
+                continue;
+            }
+            let Some(receipt) = self.client.get_transaction_receipt(hash).await? else { continue };
+
+            let function = tx.input.get(..4)
+                .and_then(|s| selectors.get(<&[u8; 4]>::try_from(s).ok()?))
+                .cloned()
+                .unwrap_or_else(|| "unknown".to_string());
+            let gas_used = receipt.gas_used.unwrap_or_default();
+            let gas_price = receipt.effective_gas_price.or(tx.gas_price).unwrap_or_default();
+            spends.push(GasSpend { function, gas_used, cost: gas_used.saturating_mul(gas_price), value: tx.value });
+        }
+        Ok(spends)
+    }
+
+    pub async fn cost_report(&self, plan: Option<String>, eth_price: Option<f64>, format: &str, output_path: Option<String>) -> Result<()> {
+        let plan = plan.as_deref().map(parse_plan).transpose()?;
+        let eth_price = match eth_price {
+            Some(price) => Some(price),
+            None => self.eth_price().await,
+        };
+
+        let signer = self.sender;
+        let spends = self.signer_gas_spends(signer).await?;
+        let by_function = summarize(&spends, eth_price);
+
+        let estimate = match plan {
+            Some(plan) => {
+                let gas_price = self.client.get_gas_price().await?;
+                let platform_fee = self.contract.platform_fee().call().await?;
+                Some(estimate(&plan, &by_function, gas_price, platform_fee, eth_price))
+            }
+            None => None,
+        };
+
+        let total_cost_eth: f64 = by_function.iter().map(|c| c.cost_eth).sum();
+        let total_fees_eth: f64 = by_function.iter().map(|c| c.fees_eth).sum();
+        let report = CostReport {
+            signer,
+            contract: self.contract.address(),
+            fiat: eth_price.map(|_| price::fiat().code().to_string()),
+            eth_price,
+            transactions: spends.len() as u64,
+            total_gas_used: by_function.iter().map(|c| c.gas_used).sum(),
+            total_cost_eth,
+            total_fees_eth,
+            total_cost_fiat: eth_price.map(|p| (total_cost_eth + total_fees_eth) * p),
+            by_function,
+            estimate,
+        };
+
+        match format.to_lowercase().as_str() {
+            "json" => {
+                let json_output = serde_json::to_string_pretty(&report)?;
+                if let Some(path) = output_path {
+                    std::fs::write(&path, &json_output)?;
+                    say!("{} {}", "✅ Exported to:".green().bold(), path.yellow());
+                } else {
+                    println!("{}", json_output);
+                }
+            }
+            "table" => print_report(&report),
+            _ => bail_user!("Unsupported format: {}. Use table or json", format),
+        }
+        Ok(())
+    }
+}
+
+fn fiat(amount: Option<f64>) -> String {
+    amount.map(|v| price::fiat().format(v)).unwrap_or_else(|| "-".to_string())
+}
+
+/// The cost tables as key: value records, for `--accessible`
+fn print_report_linear(report: &CostReport) {
+    let currency = price::fiat().code().to_uppercase();
+    if report.by_function.is_empty() {
+        say!("\nNo transactions from this signer to the contract yet.");
+    } else {
+        let mut rows: Vec<Vec<String>> = report.by_function.iter()
+            .map(|cost| vec![
+                cost.function.clone(),
+                cost.transactions.to_string(),
+                numbers::format_integer(cost.gas_used),
+                numbers::format_integer(cost.average_gas),
+                format!("{:.6}", cost.cost_eth + cost.fees_eth),
+                fiat(cost.cost_fiat),
+            ])
+            .collect();
+        rows.push(vec![
+            "Total".to_string(),
+            report.transactions.to_string(),
+            numbers::format_integer(report.total_gas_used),
+            "-".to_string(),
+            format!("{:.6}", report.total_cost_eth + report.total_fees_eth),
+            fiat(report.total_cost_fiat),
+        ]);
+        say!();
+        say!("{}", output::linear(&["Function", "Transactions", "Gas used", "Average gas", "ETH", currency.as_str()], &rows));
+        if report.total_fees_eth > 0.0 {
+            say!("Includes {:.6} ETH in platform fees", report.total_fees_eth);
+        }
+    }
+
+    if let Some(estimate) = &report.estimate {
+        say!("\nEstimate at {:.2} gwei", estimate.gas_price_gwei);
+        let mut rows: Vec<Vec<String>> = estimate.operations.iter()
+            .map(|op| vec![
+                op.function.clone(),
+                op.count.to_string(),
+                format!("{} ({})", numbers::format_integer(op.gas_per_call), op.basis),
+                format!("{:.6}", op.cost_eth),
+                fiat(op.cost_fiat),
+            ])
+            .collect();
+        rows.push(vec!["Total".to_string(), "-".to_string(), "-".to_string(), format!("{:.6}", estimate.total_eth), fiat(estimate.total_fiat)]);
+        say!("{}", output::linear(&["Function", "Calls", "Gas per call", "ETH", currency.as_str()], &rows));
+    }
+}
+
+fn print_report(report: &CostReport) {
+    say!("\n{}", "⛽ GAS COSTS".cyan().bold().underline());
+    say!("{}", "═".repeat(72).cyan());
+    say!("{} {:?}", "Signer:".yellow().bold(), report.signer);
+    if let Some(price) = report.eth_price {
+        say!("{} {}", format!("ETH/{}:", price::fiat().code().to_uppercase()).yellow().bold(), price::fiat().format(price));
+    }
+
+    if output::is_accessible() {
+        print_report_linear(report);
+        return;
+    }
+
+    if report.by_function.is_empty() {
+        say!("\nNo transactions from this signer to the contract yet.");
+    } else {
+        say!("\n{:<16} {:>6} {:>14} {:>10} {:>14} {:>10}", "Function", "Txs", "Gas Used", "Avg Gas", "ETH", price::fiat().code().to_uppercase());
+        say!("{}", "─".repeat(72).cyan());
+        for cost in &report.by_function {
+            say!("{:<16} {:>6} {:>14} {:>10} {:>14.6} {:>10}",
+                cost.function,
+                cost.transactions,
+                numbers::format_integer(cost.gas_used),
+                numbers::format_integer(cost.average_gas),
+                cost.cost_eth + cost.fees_eth,
+                fiat(cost.cost_fiat)
+            );
+        }
+        say!("{}", "─".repeat(72).cyan());
+        say!("{:<16} {:>6} {:>14} {:>10} {:>14.6} {:>10}",
+            "Total".bold(),
+            report.transactions,
+            numbers::format_integer(report.total_gas_used),
+            "",
+            report.total_cost_eth + report.total_fees_eth,
+            fiat(report.total_cost_fiat)
+        );
+        if report.total_fees_eth > 0.0 {
+            say!("  (includes {:.6} ETH in platform fees)", report.total_fees_eth);
+        }
+    }
+
+    if let Some(estimate) = &report.estimate {
+        say!("\n{} at {:.2} gwei", "📐 ESTIMATE".cyan().bold(), estimate.gas_price_gwei);
+        say!("{}", "─".repeat(72).cyan());
+        for op in &estimate.operations {
+            say!("{:<16} ×{:<5} {:>10} gas ({:<7}) {:>14.6} {:>10}",
+                op.function, op.count, numbers::format_integer(op.gas_per_call), op.basis, op.cost_eth, fiat(op.cost_fiat));
+        }
+        say!("{}", "─".repeat(72).cyan());
+        say!("{:<47} {:>14.6} {:>10}", "Total".bold(), estimate.total_eth, fiat(estimate.total_fiat));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GWEI: u64 = 1_000_000_000;
+
+    fn spend(function: &str, gas: u64) -> GasSpend {
+        GasSpend { function: function.to_string(), gas_used: gas.into(), cost: U256::from(gas) * U256::from(GWEI), value: U256::zero() }
+    }
+
+    #[test]
+    fn parses_plans() {
+        let plan = parse_plan("create=3, vote=20,closePoll").unwrap();
+        assert_eq!(plan, [("createPoll".to_string(), 3), ("vote".to_string(), 20), ("closePoll".to_string(), 1)]);
+        assert!(parse_plan("mint=1").is_err());
+        assert!(parse_plan("vote=lots").is_err());
+        assert!(parse_plan(" , ").is_err());
+    }
+
+    #[test]
+    fn summarizes_by_function() {
+        let costs = summarize(&[spend("vote", 100_000), spend("createPoll", 400_000), spend("vote", 120_000)], Some(2_000.0));
+        assert_eq!(costs[0].function, "createPoll");
+        assert_eq!((costs[1].transactions, costs[1].gas_used, costs[1].average_gas), (2, 220_000, 110_000));
+        assert!((costs[1].cost_eth - 0.00022).abs() < 1e-12);
+        assert!((costs[1].cost_fiat.unwrap() - 0.44).abs() < 1e-9);
+    }
+
+    #[test]
+    fn estimates_from_history_then_defaults() {
+        let history = summarize(&[spend("vote", 100_000)], None);
+        let plan = [("vote".to_string(), 10), ("closePoll".to_string(), 2)];
+        let estimate = estimate(&plan, &history, U256::from(2 * GWEI), U256::zero(), None);
+
+        assert_eq!(estimate.operations[0].basis, "history");
+        assert!((estimate.operations[0].cost_eth - 0.002).abs() < 1e-12);
+        assert_eq!(estimate.operations[1].basis, "default");
+        assert_eq!(estimate.operations[1].gas_per_call, 60_000);
+        assert_eq!(estimate.gas_price_gwei, 2.0);
+        assert!(estimate.total_fiat.is_none());
+    }
+}
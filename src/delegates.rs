@@ -0,0 +1,178 @@
+use std::collections::{BTreeMap, HashSet};
+
+use anyhow::Result;
+use colored::*;
+use ethers::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::error::bail_user;
+use crate::indexer::{ContractEvent, IndexedEvent};
+use crate::{numbers, profiles, PollManager};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DelegateRank {
+    pub rank: usize,
+    pub delegate: Address,
+    pub delegators: Vec<Address>,
+    /// Governance token balance of every current delegator combined; `None` without a token
+    #[serde(with = "numbers::decimal_option")]
+    pub delegated_weight: Option<U256>,
+    /// Polls that ended since the delegate's oldest current delegation
+    pub eligible_polls: usize,
+    pub polls_voted: usize,
+    /// `polls_voted` as a percentage of `eligible_polls`; `None` when nothing has ended yet
+    pub participation: Option<f64>,
+}
+
+/// Current delegators of each delegate, with when the oldest of those delegations began
+pub fn current_delegations(events: &[IndexedEvent]) -> BTreeMap<Address, (Vec<Address>, u64)> {
+    let mut since: BTreeMap<Address, (Address, u64)> = BTreeMap::new();
+    for event in events {
+        match event.event {
+            ContractEvent::DelegateSet { delegator, delegate } => {
+                since.insert(delegator, (delegate, event.timestamp));
+            }
+            ContractEvent::DelegateRemoved { delegator, .. } => {
+                since.remove(&delegator);
+            }
+            _ => {}
+        }
+    }
+    let mut by_delegate: BTreeMap<Address, (Vec<Address>, u64)> = BTreeMap::new();
+    for (delegator, (delegate, started)) in since {
+        let entry = by_delegate.entry(delegate).or_insert((Vec::new(), u64::MAX));
+        entry.0.push(delegator);
+        entry.1 = entry.1.min(started);
+    }
+    by_delegate
+}
+
+/// `(polls ended since `since`, how many of them `delegate` voted in)`
+pub fn participation(events: &[IndexedEvent], delegate: Address, since: u64, now: u64) -> (usize, usize) {
+    let eligible: HashSet<u64> = events.iter().filter_map(|e| match e.event {
+        ContractEvent::PollCreated { poll_id, end_time, .. } if e.timestamp >= since && end_time <= now => Some(poll_id),
+        _ => None,
+    }).collect();
+    let voted: HashSet<u64> = events.iter().filter_map(|e| match e.event {
+        ContractEvent::VoteCast { poll_id, voter, .. } if voter == delegate && eligible.contains(&poll_id) => Some(poll_id),
+        _ => None,
+    }).collect();
+    (eligible.len(), voted.len())
+}
+
+fn print_leaderboard(ranks: &[DelegateRank], weighted: bool) {
+    say!("\n{}", "🏛️  TOP DELEGATES".cyan().bold().underline());
+    say!("{}", "═".repeat(60).cyan());
+    if ranks.is_empty() {
+        say!("Nobody has delegated on this contract yet.");
+        return;
+    }
+    if !weighted {
+        say!("{}", "GOVERNANCE_TOKEN_ADDRESS is not set; ranking by delegator count.".yellow());
+    }
+    for rank in ranks {
+        let weight = rank.delegated_weight
+            .map(|weight| format!(" | {} delegated", numbers::format_amount(weight, 18, 2)))
+            .unwrap_or_default();
+        let participation = match rank.participation {
+            Some(share) => format!("{:.0}% ({}/{})", share, rank.polls_voted, rank.eligible_polls),
+            None => "no polls ended yet".to_string(),
+        };
+        say!("{:>3}. {:?} | {} delegator{}{} | voted {}",
+            rank.rank, rank.delegate, rank.delegators.len(), if rank.delegators.len() == 1 { "" } else { "s" }, weight, participation);
+        if let Some(summary) = profiles::summary(rank.delegate) {
+            say!("     {}", summary.white());
+        }
+    }
+}
+
+impl<M: Middleware + 'static> PollManager<M> {
+    /// `delegates top`: delegates ranked by the token weight delegated to them, with how often they vote
+    pub async fn top_delegates(&self, limit: usize, format: &str, output_path: Option<String>) -> Result<()> {
+        let json = match format.to_lowercase().as_str() {
+            "json" => true,
+            "table" => false,
+            _ => bail_user!("Unsupported format: {}. Use table or json", format),
+        };
+
+        let index = self.event_index().await?;
+        let now = chrono::Utc::now().timestamp() as u64;
+        let mut ranks = Vec::new();
+        for (delegate, (delegators, since)) in current_delegations(&index.events) {
+            let delegated_weight = match &self.governance_token {
+                Some(token) => {
+                    let mut total = U256::zero();
+                    for delegator in &delegators {
+                        total = total.saturating_add(token.balance_of(*delegator).call().await?);
+                    }
+                    Some(total)
+                }
+                None => None,
+            };
+            let (eligible_polls, polls_voted) = participation(&index.events, delegate, since, now);
+            ranks.push(DelegateRank {
+                rank: 0,
+                delegate,
+                delegators,
+                delegated_weight,
+                eligible_polls,
+                polls_voted,
+                participation: (eligible_polls > 0).then(|| polls_voted as f64 / eligible_polls as f64 * 100.0),
+            });
+        }
+        ranks.sort_by(|a, b| b.delegated_weight.cmp(&a.delegated_weight).then(b.delegators.len().cmp(&a.delegators.len())));
+        ranks.truncate(limit);
+        for (i, rank) in ranks.iter_mut().enumerate() {
+            rank.rank = i + 1;
+        }
+
+        if json {
+            let rendered = serde_json::to_string_pretty(&ranks)?;
+            match output_path {
+                Some(path) => {
+                    std::fs::write(&path, rendered)?;
+                    say!("{} {}", "✅ Exported to:".green().bold(), path.yellow());
+                }
+                None => println!("{}", rendered),
+            }
+        } else {
+            print_leaderboard(&ranks, self.governance_token.is_some());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(timestamp: u64, event: ContractEvent) -> IndexedEvent {
+        IndexedEvent { block_number: timestamp, block_hash: H256::zero(), transaction_hash: H256::zero(), log_index: 0, timestamp, event }
+    }
+
+    fn created(poll_id: u64, end_time: u64) -> ContractEvent {
+        ContractEvent::PollCreated {
+            poll_id, creator: Address::zero(), question: String::new(), poll_type: 0, category: 0, end_time, tags: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn ranks_current_delegations_and_participation() {
+        let (alice, bob, carol, dave) = (Address::repeat_byte(1), Address::repeat_byte(2), Address::repeat_byte(3), Address::repeat_byte(4));
+        let events = [
+            event(10, created(0, 50)),
+            event(100, ContractEvent::DelegateSet { delegator: alice, delegate: dave }),
+            event(110, created(1, 200)),
+            event(120, ContractEvent::DelegateSet { delegator: bob, delegate: dave }),
+            event(130, ContractEvent::DelegateSet { delegator: carol, delegate: dave }),
+            event(140, ContractEvent::DelegateRemoved { delegator: carol, delegate: dave }),
+            event(150, created(2, 300)),
+            event(160, ContractEvent::VoteCast { poll_id: 1, voter: dave, option_index: 0, weight: U256::one() }),
+            event(170, created(3, 10_000)),
+        ];
+        let delegations = current_delegations(&events);
+        assert_eq!(delegations[&dave], (vec![alice, bob], 100));
+        // Poll 0 predates the delegation and poll 3 is still running
+        assert_eq!(participation(&events, dave, 100, 1_000), (2, 1));
+    }
+}
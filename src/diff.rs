@@ -0,0 +1,278 @@
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+use colored::*;
+use ethers::prelude::*;
+use serde::Serialize;
+
+use crate::archive::{self, DelegationChange};
+use crate::error::bail_user;
+use crate::indexer::{self, ContractEvent, EventIndex, IndexedEvent, DEFAULT_REORG_DEPTH};
+use crate::numbers::SaturatingU64;
+use crate::{duration, format_timestamp, numbers, output, store, u8_to_status, PollManager};
+
+/// What the events mined after the snapshot did to one poll
+#[derive(Debug, Default, PartialEq)]
+struct Activity {
+    created: bool,
+    /// New votes and weight per option index
+    votes: BTreeMap<u64, (u64, U256)>,
+    /// Status at the snapshot, when it changed since
+    status_before: Option<u8>,
+    /// End time at the snapshot (if known), when the poll was extended since
+    extended_from: Option<Option<u64>>,
+    /// Its end time passed since the snapshot while it was still active
+    ended: bool,
+}
+
+/// Replay `events`, collecting what every poll went through after `snapshot_block`.
+/// `since` and `now` are the snapshot's and the head's block timestamps.
+fn activity(events: &[IndexedEvent], snapshot_block: u64, since: u64, now: u64) -> BTreeMap<u64, Activity> {
+    let mut status: BTreeMap<u64, u8> = BTreeMap::new();
+    let mut end_times: BTreeMap<u64, u64> = BTreeMap::new();
+    let mut polls: BTreeMap<u64, Activity> = BTreeMap::new();
+    for event in events {
+        let after = event.block_number > snapshot_block;
+        match &event.event {
+            ContractEvent::PollCreated { poll_id, end_time, .. } => {
+                status.insert(*poll_id, 0);
+                end_times.insert(*poll_id, *end_time);
+                if after {
+                    polls.entry(*poll_id).or_default().created = true;
+                }
+            }
+            ContractEvent::VoteCast { poll_id, option_index, weight, .. } if after => {
+                let option = polls.entry(*poll_id).or_default().votes.entry(*option_index).or_default();
+                option.0 += 1;
+                option.1 = option.1.saturating_add(*weight);
+            }
+            ContractEvent::PollStatusChanged { poll_id, status: new } => {
+                let old = status.insert(*poll_id, *new).unwrap_or(0);
+                if after {
+                    polls.entry(*poll_id).or_default().status_before.get_or_insert(old);
+                }
+            }
+            ContractEvent::PollExtended { poll_id, end_time } => {
+                let old = end_times.insert(*poll_id, *end_time);
+                if after {
+                    polls.entry(*poll_id).or_default().extended_from.get_or_insert(old);
+                }
+            }
+            _ => {}
+        }
+    }
+    for (poll_id, end_time) in end_times {
+        if since < end_time && end_time <= now && status.get(&poll_id) == Some(&0) {
+            polls.entry(poll_id).or_default().ended = true;
+        }
+    }
+    polls
+}
+
+/// Votes one option received since the snapshot
+#[derive(Debug, Clone, Serialize)]
+pub struct OptionDelta {
+    pub index: u64,
+    pub option: String,
+    pub new_votes: u64,
+    #[serde(with = "numbers::decimal")]
+    pub new_weight: U256,
+}
+
+/// A poll that was created, voted on, extended or changed status since the snapshot
+#[derive(Debug, Clone, Serialize)]
+pub struct PollChange {
+    pub poll_id: u64,
+    pub question: String,
+    pub created: bool,
+    /// Status at the snapshot, when it has changed since
+    pub status_before: Option<String>,
+    pub status: String,
+    pub extended: bool,
+    /// End time at the snapshot, when the poll was extended and it is known
+    pub end_time_before: Option<u64>,
+    pub end_time: u64,
+    pub votes_before: u64,
+    pub votes: u64,
+    /// Only options that received votes
+    pub options: Vec<OptionDelta>,
+}
+
+/// Everything that changed between the last synced index and the head
+#[derive(Debug, Clone, Serialize)]
+pub struct Changes {
+    pub chain_id: u64,
+    pub contract: Address,
+    pub from_block: u64,
+    pub to_block: u64,
+    /// When the snapshot block was mined
+    pub since: u64,
+    pub until: u64,
+    pub new_polls: usize,
+    pub new_votes: usize,
+    pub polls: Vec<PollChange>,
+    pub delegations: Vec<DelegationChange>,
+}
+
+fn print_changes(changes: &Changes) {
+    say!("\n{} {} → {} ({} ago)", "🔍 Changes since block".cyan().bold(), changes.from_block, changes.to_block,
+        duration::format(changes.until.saturating_sub(changes.since)));
+    say!("{} {}", "New Polls:".cyan().bold(), changes.new_polls.to_string().yellow());
+    say!("{} {}", "New Votes:".cyan().bold(), changes.new_votes.to_string().yellow());
+
+    if !changes.polls.is_empty() {
+        say!("\n{}", "📊 Polls:".cyan().bold());
+    }
+    for poll in &changes.polls {
+        let votes = format!("{} → {} votes", numbers::format_integer(poll.votes_before), numbers::format_integer(poll.votes));
+        match &poll.status_before {
+            _ if poll.created => say!("  {} #{} \"{}\" ({}, {})", "+".green().bold(), poll.poll_id, poll.question, poll.status, votes),
+            Some(before) => say!("  {} #{} \"{}\" ({} → {}, {})", "~".yellow().bold(), poll.poll_id, poll.question, before, poll.status, votes),
+            None => say!("  {} #{} \"{}\" ({})", "~".yellow().bold(), poll.poll_id, poll.question, votes),
+        }
+        if poll.extended {
+            match poll.end_time_before {
+                Some(before) => say!("      Extended to {} (was {})", format_timestamp(poll.end_time), format_timestamp(before)),
+                None => say!("      Extended to {}", format_timestamp(poll.end_time)),
+            }
+        }
+        for option in &poll.options {
+            let weight = if option.new_weight == U256::from(option.new_votes) {
+                String::new()
+            } else {
+                format!(" ({} weight)", numbers::format_integer(option.new_weight))
+            };
+            say!("      {}: {}{}", option.option, format!("+{}", numbers::format_integer(option.new_votes)).green(), weight);
+        }
+    }
+
+    archive::print_delegation_changes(&changes.delegations);
+    if changes.polls.is_empty() && changes.delegations.is_empty() {
+        say!("No changes since block {}.", changes.from_block);
+    }
+}
+
+impl<M: Middleware + 'static> PollManager<M> {
+    /// `diff`: sync the event index and report what changed since it was last synced
+    pub async fn diff_since_sync(&self, format: &str) -> Result<()> {
+        let json = match format.to_lowercase().as_str() {
+            "json" => true,
+            "table" => false,
+            _ => bail_user!("Unsupported format: {}. Use table or json", format),
+        };
+        let path = indexer::index_path(self.wallet.chain_id(), self.contract.address())?;
+        let snapshot: EventIndex = store::load_from(&path).unwrap_or_default();
+
+        let progress = output::spinner("🔄 Syncing contract events...");
+        let synced = self.sync_events(None, None, DEFAULT_REORG_DEPTH, &progress).await;
+        progress.finish_and_clear();
+        let (index, _) = synced?;
+
+        let (Some(from), Some(to)) = (snapshot.checkpoint, index.checkpoint) else {
+            if let Some(checkpoint) = index.checkpoint {
+                say!("{} block {}; run `diff` again later to see what changed since", "📸 Snapshot taken at".cyan().bold(), checkpoint.block_number);
+            }
+            return Ok(());
+        };
+        let since = self.block_timestamp(from.block_number).await?;
+        let until = self.block_timestamp(to.block_number).await?;
+
+        let mut polls = Vec::new();
+        for (poll_id, activity) in activity(&index.events, from.block_number, since, until) {
+            let poll = self.poll_data(U256::from(poll_id)).await?;
+            let votes = poll.10.to_u64_saturating();
+            let new_votes: u64 = activity.votes.values().map(|(count, _)| count).sum();
+            let ended = activity.ended && poll.6 == 0;
+            let status_before = match activity.status_before {
+                Some(before) => Some(u8_to_status(before).to_string()),
+                None if ended => Some("Active".to_string()),
+                None => None,
+            };
+            polls.push(PollChange {
+                poll_id,
+                question: poll.1,
+                created: activity.created,
+                status_before,
+                status: if ended { "Ended" } else { u8_to_status(poll.6) }.to_string(),
+                extended: activity.extended_from.is_some(),
+                end_time_before: activity.extended_from.flatten(),
+                end_time: poll.5.to_u64_saturating(),
+                votes_before: votes.saturating_sub(new_votes),
+                votes,
+                options: activity.votes.iter().map(|(index, (count, weight))| OptionDelta {
+                    index: *index,
+                    option: poll.2.get(*index as usize).cloned().unwrap_or_else(|| format!("Option {}", index)),
+                    new_votes: *count,
+                    new_weight: *weight,
+                }).collect(),
+            });
+        }
+
+        let split = index.events.partition_point(|e| e.block_number <= from.block_number);
+        let changes = Changes {
+            chain_id: self.wallet.chain_id(),
+            contract: self.contract.address(),
+            from_block: from.block_number,
+            to_block: to.block_number,
+            since,
+            until,
+            new_polls: polls.iter().filter(|p| p.created).count(),
+            new_votes: index.events[split..].iter().filter(|e| matches!(e.event, ContractEvent::VoteCast { .. })).count(),
+            polls,
+            delegations: archive::delegation_changes(&index.events[..split], &index.events),
+        };
+        if json {
+            println!("{}", serde_json::to_string_pretty(&changes)?);
+        } else {
+            print_changes(&changes);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(block_number: u64, event: ContractEvent) -> IndexedEvent {
+        IndexedEvent {
+            block_number,
+            block_hash: H256::from_low_u64_be(block_number),
+            transaction_hash: H256::from_low_u64_be(block_number),
+            log_index: 0,
+            timestamp: 1_000 + block_number,
+            event,
+        }
+    }
+
+    fn created(poll_id: u64, end_time: u64) -> ContractEvent {
+        ContractEvent::PollCreated { poll_id, creator: Address::zero(), question: String::new(), poll_type: 0, category: 0, end_time, tags: vec![] }
+    }
+
+    fn vote(poll_id: u64, option_index: u64, weight: u64) -> ContractEvent {
+        ContractEvent::VoteCast { poll_id, voter: Address::zero(), option_index, weight: U256::from(weight) }
+    }
+
+    #[test]
+    fn collects_what_happened_after_the_snapshot() {
+        let events = vec![
+            at(1, created(0, 5_000)),
+            at(2, created(1, 1_050)),
+            at(3, vote(0, 0, 1)),
+            // Snapshot at block 10
+            at(11, vote(0, 1, 1)),
+            at(12, vote(0, 1, 3)),
+            at(13, ContractEvent::PollExtended { poll_id: 0, end_time: 9_000 }),
+            at(14, created(2, 9_000)),
+            at(15, ContractEvent::PollStatusChanged { poll_id: 2, status: 1 }),
+        ];
+        let polls = activity(&events, 10, 1_010, 1_100);
+        assert_eq!(polls.keys().copied().collect::<Vec<_>>(), vec![0, 1, 2]);
+
+        assert_eq!(polls[&0].votes, BTreeMap::from([(1, (2, U256::from(4)))]));
+        assert_eq!(polls[&0].extended_from, Some(Some(5_000)));
+        assert!(!polls[&0].created && !polls[&0].ended);
+        assert!(polls[&1].ended, "end time passed between the snapshot and now");
+        assert!(polls[&2].created && polls[&2].status_before == Some(0));
+    }
+}
@@ -0,0 +1,168 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use colored::*;
+use ethers::prelude::*;
+
+use crate::numbers::SaturatingU64;
+use crate::error::{bail_config, CliError};
+use crate::connection::{Connection, SignerSource};
+use crate::{ENHANCEDPOLLS_ABI, GovernanceToken};
+
+/// Maximum tolerated difference between the local clock and the latest block timestamp
+const MAX_CLOCK_SKEW_SECS: i64 = 120;
+/// Below this balance (0.001 ETH) the signer is unlikely to afford a poll creation
+const MIN_GAS_BALANCE_WEI: u64 = 1_000_000_000_000_000;
+
+enum Status {
+    Pass,
+    Warn,
+    Fail,
+}
+
+struct Doctor {
+    failures: usize,
+    warnings: usize,
+}
+
+impl Doctor {
+    fn report(&mut self, status: Status, check: &str, detail: &str, hint: Option<&str>) {
+        let plain = crate::output::is_plain();
+        let marker = match status {
+            Status::Pass => if plain { "[PASS]" } else { "✅" },
+            Status::Warn => {
+                self.warnings += 1;
+                if plain { "[WARN]" } else { "⚠️ " }
+            }
+            Status::Fail => {
+                self.failures += 1;
+                if plain { "[FAIL]" } else { "❌" }
+            }
+        };
+        say!("{} {} {}", marker, format!("{}:", check).yellow().bold(), detail);
+        if let Some(hint) = hint {
+            say!("   {} {}", "→".cyan(), hint.white());
+        }
+    }
+}
+
+/// PUSH4 <selector> appears in the dispatcher of every function a contract exposes
+fn bytecode_has_selector(code: &[u8], selector: [u8; 4]) -> bool {
+    code.windows(5).any(|w| w[0] == 0x63 && w[1..] == selector)
+}
+
+pub async fn run_doctor(connection: &Connection, contract_address: &str, governance_token: Option<String>) -> Result<()> {
+    let rpc_url = connection.rpc_url.as_str();
+    say!("\n{}", "🩺 ENVIRONMENT DIAGNOSTICS".cyan().bold().underline());
+    say!("{}", "═".repeat(60).cyan());
+
+    let mut doctor = Doctor { failures: 0, warnings: 0 };
+
+    // RPC reachability and chain id
+    let provider = match Provider::<Http>::try_from(rpc_url) {
+        Ok(provider) => provider.interval(Duration::from_millis(500)),
+        Err(e) => {
+            doctor.report(Status::Fail, "RPC URL", &format!("'{}' is not a valid URL ({})", rpc_url, e), Some("Set RPC_URL to an http(s) endpoint, e.g. http://localhost:8545"));
+            bail_config!("{} check(s) failed", doctor.failures);
+        }
+    };
+    let chain_id = match provider.get_chainid().await {
+        Ok(chain_id) => {
+            match connection.check_chain_id(chain_id.to_u64_saturating()) {
+                Ok(()) => doctor.report(Status::Pass, "RPC", &format!("{} reachable, chain id {}", rpc_url, chain_id), None),
+                Err(e) => doctor.report(Status::Fail, "RPC", &e.to_string(), Some("Point --rpc-url or RPC_URL at the intended network, or fix --chain-id")),
+            }
+            chain_id
+        }
+        Err(e) => {
+            doctor.report(Status::Fail, "RPC", &format!("{} unreachable ({})", rpc_url, e), Some("Start a node with 'make anvil' or point RPC_URL at a reachable endpoint"));
+            return Err(CliError::Rpc(format!("{} check(s) failed", doctor.failures)).into());
+        }
+    };
+
+    // Signer key and gas balance
+    // A keystore account is checked by its registered address, without asking for the password
+    let signer = match &connection.signer {
+        SignerSource::Account { account, .. } => Ok(account.address),
+        SignerSource::PrivateKey(key) => key.parse::<LocalWallet>().map(|wallet| wallet.address()),
+    };
+    match signer {
+        Ok(address) => {
+            match provider.get_balance(address, None).await {
+                Ok(balance) if balance >= U256::from(MIN_GAS_BALANCE_WEI) => doctor.report(
+                    Status::Pass, "Signer", &format!("{:?} holds {} ETH", address, ethers::utils::format_ether(balance)), None),
+                Ok(balance) => doctor.report(
+                    Status::Warn, "Signer", &format!("{:?} holds only {} ETH", address, ethers::utils::format_ether(balance)),
+                    Some("Fund the signer account so it can pay gas for create/vote/close")),
+                Err(e) => doctor.report(Status::Fail, "Signer", &format!("Could not read balance ({})", e), None),
+            }
+        }
+        Err(e) => doctor.report(Status::Fail, "Signer", &format!("PRIVATE_KEY is invalid ({})", e), Some("PRIVATE_KEY must be a 32-byte hex string, with or without 0x")),
+    }
+
+    // Contract code and ABI compatibility
+    match contract_address.parse::<Address>() {
+        Ok(address) => match provider.get_code(address, None).await {
+            Ok(code) if code.is_empty() => doctor.report(
+                Status::Fail, "Contract", &format!("No code at {:?} on chain {}", address, chain_id),
+                Some("Deploy with 'make deploy' and set CONTRACT_ADDRESS to the deployed address")),
+            Ok(code) => {
+                doctor.report(Status::Pass, "Contract", &format!("{} bytes of code at {:?}", code.len(), address), None);
+
+                let missing: Vec<String> = ENHANCEDPOLLS_ABI.functions()
+                    .filter(|f| !bytecode_has_selector(&code, f.short_signature()))
+                    .map(|f| f.name.clone())
+                    .collect();
+                if missing.is_empty() {
+                    doctor.report(Status::Pass, "ABI", "All EnhancedPolls function selectors found in bytecode", None);
+                } else {
+                    doctor.report(Status::Warn, "ABI", &format!("Selectors not found for: {}", missing.join(", ")),
+                        Some("The deployed contract may be an older version or a proxy; redeploy EnhancedPolls if calls revert"));
+                }
+            }
+            Err(e) => doctor.report(Status::Fail, "Contract", &format!("Could not fetch code ({})", e), None),
+        },
+        Err(_) => doctor.report(Status::Fail, "Contract", &format!("CONTRACT_ADDRESS '{}' is not a valid address", contract_address), None),
+    }
+
+    // Governance token sanity
+    match governance_token {
+        Some(token) => match token.parse::<Address>() {
+            Ok(address) => {
+                let token = GovernanceToken::new(address, std::sync::Arc::new(provider.clone()));
+                match (token.name().call().await, token.symbol().call().await, token.decimals().call().await, token.total_supply().call().await) {
+                    (Ok(name), Ok(symbol), Ok(decimals), Ok(supply)) => doctor.report(
+                        Status::Pass, "Governance Token",
+                        &format!("{} ({}), {} decimals, supply {}", name, symbol, decimals, ethers::utils::format_units(supply, decimals as u32).unwrap_or_else(|_| supply.to_string())),
+                        None),
+                    _ => doctor.report(Status::Fail, "Governance Token", &format!("{:?} does not behave like an ERC-20 token", address),
+                        Some("Check GOVERNANCE_TOKEN_ADDRESS points at the deployed GovernanceToken")),
+                }
+            }
+            Err(_) => doctor.report(Status::Fail, "Governance Token", &format!("GOVERNANCE_TOKEN_ADDRESS '{}' is not a valid address", token), None),
+        },
+        None => doctor.report(Status::Warn, "Governance Token", "GOVERNANCE_TOKEN_ADDRESS not set", Some("Only needed for token-balance and weighted poll features")),
+    }
+
+    // Clock skew between this machine and the chain
+    match provider.get_block(BlockNumber::Latest).await {
+        Ok(Some(block)) => {
+            let skew = chrono::Utc::now().timestamp() - block.timestamp.to_u64_saturating() as i64;
+            if skew.abs() <= MAX_CLOCK_SKEW_SECS {
+                doctor.report(Status::Pass, "Clock", &format!("Local clock within {}s of the latest block", skew.abs()), None);
+            } else {
+                doctor.report(Status::Warn, "Clock", &format!("Local clock differs from the latest block by {}s", skew),
+                    Some("Time remaining and expiry checks may be off; sync your clock or mine a block on Anvil"));
+            }
+        }
+        _ => doctor.report(Status::Warn, "Clock", "Could not read the latest block", None),
+    }
+
+    say!("{}", "─".repeat(60).cyan());
+    if doctor.failures > 0 {
+        bail_config!("{} check(s) failed, {} warning(s)", doctor.failures, doctor.warnings);
+    }
+    say!("{} {}", "✅ All checks passed".green().bold(), format!("({} warning(s))", doctor.warnings).white());
+
+    Ok(())
+}
@@ -0,0 +1,285 @@
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+use colored::*;
+use ethers::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::error::bail_user;
+use crate::numbers::SaturatingU64;
+use crate::spec::{PollFields, PollSpec};
+use crate::templates::PollTemplate;
+use crate::{duration, lint, store, timezone, PollManager};
+
+const DRAFTS_FILE: &str = "drafts.json";
+
+/// A poll composed locally and reviewed before it goes on-chain
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Draft {
+    pub id: u64,
+    pub spec: PollSpec,
+    pub created_at: u64,
+    pub updated_at: u64,
+    /// Reviewers who approved the current text; cleared on every edit
+    pub approvals: Vec<String>,
+    pub submitted_poll_id: Option<u64>,
+}
+
+impl Draft {
+    pub fn status(&self) -> &'static str {
+        match (self.submitted_poll_id, self.approvals.is_empty()) {
+            (Some(_), _) => "submitted",
+            (None, false) => "approved",
+            (None, true) => "pending review",
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct DraftStore {
+    next_id: u64,
+    drafts: BTreeMap<u64, Draft>,
+}
+
+impl DraftStore {
+    fn get(&self, id: u64) -> Result<&Draft> {
+        match self.drafts.get(&id) {
+            Some(draft) => Ok(draft),
+            None => bail_user!("No draft #{}; see `draft list`", id),
+        }
+    }
+
+    fn get_mut(&mut self, id: u64) -> Result<&mut Draft> {
+        match self.drafts.get_mut(&id) {
+            Some(draft) => Ok(draft),
+            None => bail_user!("No draft #{}; see `draft list`", id),
+        }
+    }
+}
+
+fn now() -> u64 {
+    chrono::Utc::now().timestamp() as u64
+}
+
+/// Surface content-check findings early; `submit` enforces them
+fn print_lint(spec: &PollSpec) {
+    let issues = lint::lint(spec, spec.duration_seconds().unwrap_or_default(), None);
+    for issue in issues {
+        say!("{} {}", "⚠️ ".yellow(), issue);
+    }
+}
+
+/// Overwrite the fields set on the command line, keeping the rest of the draft
+fn apply_edits(spec: &mut PollSpec, fields: PollFields) -> Result<()> {
+    let fields = fields.load_file()?;
+    let merged = PollFields {
+        question: fields.question.or_else(|| Some(spec.question.clone())),
+        options: fields.options.or_else(|| Some(spec.options.join(","))),
+        duration: fields.duration.or(Some(spec.duration)),
+        // A new --duration replaces a previous absolute end and vice versa
+        ends_at: if fields.duration.is_some() { fields.ends_at } else { fields.ends_at.or(spec.ends_at) },
+        poll_type: fields.poll_type.or_else(|| Some(spec.poll_type.clone())),
+        category: fields.category.or_else(|| Some(spec.category.clone())),
+        min_participation: fields.min_participation.or(Some(spec.min_participation)),
+        token_address: fields.token_address.or_else(|| spec.token_address.clone()),
+        min_token_balance: fields.min_token_balance.or(Some(spec.min_token_balance)),
+        description: fields.description.or_else(|| spec.description.clone()),
+        tags: fields.tags.or_else(|| Some(spec.tags.join(","))),
+        from_file: None,
+    };
+    *spec = merged.resolve(None)?;
+    Ok(())
+}
+
+pub fn render_markdown(draft: &Draft) -> String {
+    let spec = &draft.spec;
+    let mut md = format!("# Draft #{}: {}\n\n", draft.id, spec.question);
+    if let Some(description) = &spec.description {
+        md.push_str(&format!("{}\n\n", description));
+    }
+    md.push_str("## Options\n\n");
+    for (i, option) in spec.options.iter().enumerate() {
+        md.push_str(&format!("{}. {}\n", i + 1, option));
+    }
+    md.push_str("\n## Settings\n\n| Setting | Value |\n|---|---|\n");
+    let ends = match spec.ends_at {
+        Some(ends_at) => timezone::format_in(ends_at, timezone::DisplayZone::Utc),
+        None => format!("{} after submission", duration::format(spec.duration)),
+    };
+    md.push_str(&format!("| Type | {} |\n| Category | {} |\n| Ends | {} |\n", spec.poll_type, spec.category, ends));
+    md.push_str(&format!("| Min participation | {} |\n", spec.min_participation));
+    if let Some(token) = &spec.token_address {
+        md.push_str(&format!("| Token | {} (min balance {}) |\n", token, spec.min_token_balance));
+    }
+    if !spec.tags.is_empty() {
+        md.push_str(&format!("| Tags | {} |\n", spec.tags.join(", ")));
+    }
+    let approvals = if draft.approvals.is_empty() { "none yet".to_string() } else { draft.approvals.join(", ") };
+    md.push_str(&format!("\n**Status:** {} (approvals: {})\n", draft.status(), approvals));
+    md
+}
+
+/// Store `spec` as a new draft pending review; returns its id
+pub fn add_draft(spec: PollSpec) -> Result<u64> {
+    let mut book: DraftStore = store::load(DRAFTS_FILE)?;
+    let id = book.next_id;
+    book.next_id += 1;
+    book.drafts.insert(id, Draft { id, spec, created_at: now(), updated_at: now(), approvals: vec![], submitted_poll_id: None });
+    store::save(DRAFTS_FILE, &book)?;
+    Ok(id)
+}
+
+/// A copy of draft `id`
+pub fn get_draft(id: u64) -> Result<Draft> {
+    let book: DraftStore = store::load(DRAFTS_FILE)?;
+    book.get(id).cloned()
+}
+
+pub fn new_draft(fields: PollFields, template: Option<&PollTemplate>) -> Result<()> {
+    let spec = fields.resolve(template)?;
+    let id = add_draft(spec.clone())?;
+    say!("{} {}", "📝 Created draft".green().bold(), format!("#{}", id).yellow());
+    print_lint(&spec);
+    say!("Share it for review with `draft show {} --format markdown`", id);
+    Ok(())
+}
+
+pub fn edit_draft(id: u64, fields: PollFields) -> Result<()> {
+    let mut book: DraftStore = store::load(DRAFTS_FILE)?;
+    let draft = book.get_mut(id)?;
+    if draft.submitted_poll_id.is_some() {
+        bail_user!("Draft #{} was already submitted; on-chain polls cannot be edited", id);
+    }
+    apply_edits(&mut draft.spec, fields)?;
+    print_lint(&draft.spec);
+    draft.updated_at = now();
+    let had_approvals = !std::mem::take(&mut draft.approvals).is_empty();
+    store::save(DRAFTS_FILE, &book)?;
+
+    say!("{} {}", "✏️ Updated draft".green().bold(), format!("#{}", id).yellow());
+    if had_approvals {
+        say!("{}", "⚠️  Previous approvals were cleared; the draft needs a fresh review".yellow());
+    }
+    Ok(())
+}
+
+pub fn list_drafts() -> Result<()> {
+    let book: DraftStore = store::load(DRAFTS_FILE)?;
+
+    say!("\n{}", "📝 Poll Drafts:".cyan().bold());
+    if book.drafts.is_empty() {
+        say!("No drafts yet. Start one with `draft new -q ... -o ...`.");
+        return Ok(());
+    }
+    for draft in book.drafts.values() {
+        let status = match draft.status() {
+            "submitted" => format!("submitted as poll #{}", draft.submitted_poll_id.unwrap_or_default()).blue(),
+            "approved" => format!("approved by {}", draft.approvals.join(", ")).green(),
+            other => other.yellow(),
+        };
+        say!("\n#{}: {}", draft.id, draft.spec.question);
+        say!("  Status: {}", status);
+        say!("  Updated: {}", timezone::format_with_relative(draft.updated_at));
+    }
+    Ok(())
+}
+
+pub fn show_draft(id: u64, format: &str, output_path: Option<String>) -> Result<()> {
+    let book: DraftStore = store::load(DRAFTS_FILE)?;
+    let draft = book.get(id)?;
+    let rendered = match format.to_lowercase().as_str() {
+        "json" => serde_json::to_string_pretty(draft)?,
+        "markdown" | "md" => render_markdown(draft),
+        _ => bail_user!("Unsupported format: {}. Use json or markdown", format),
+    };
+
+    match output_path {
+        Some(path) => {
+            std::fs::write(&path, &rendered)?;
+            say!("{} {}", "✅ Exported to:".green().bold(), path.yellow());
+        }
+        None => println!("{}", rendered),
+    }
+    Ok(())
+}
+
+pub fn approve_draft(id: u64, reviewer: &str) -> Result<()> {
+    let mut book: DraftStore = store::load(DRAFTS_FILE)?;
+    let draft = book.get_mut(id)?;
+    if draft.submitted_poll_id.is_some() {
+        bail_user!("Draft #{} was already submitted", id);
+    }
+    if !draft.approvals.iter().any(|r| r == reviewer) {
+        draft.approvals.push(reviewer.to_string());
+    }
+    store::save(DRAFTS_FILE, &book)?;
+
+    say!("{} {} {}", "✅ Draft".green().bold(), format!("#{}", id).yellow(), format!("approved by {}", reviewer).green().bold());
+    Ok(())
+}
+
+impl<M: Middleware + 'static> PollManager<M> {
+    /// Push an approved draft on-chain and remember the resulting poll ID
+    pub async fn submit_draft(&self, id: u64, force: bool) -> Result<()> {
+        let mut book: DraftStore = store::load(DRAFTS_FILE)?;
+        let draft = book.get_mut(id)?;
+        if let Some(poll_id) = draft.submitted_poll_id {
+            bail_user!("Draft #{} was already submitted as poll #{}", id, poll_id);
+        }
+        if draft.approvals.is_empty() {
+            bail_user!("Draft #{} has not been approved; run `draft approve {} --by <reviewer>` first", id, id);
+        }
+
+        say!("{} {} {}", "📤 Submitting draft".cyan().bold(), format!("#{}", id).yellow(), format!("(approved by {})", draft.approvals.join(", ")).cyan());
+        let created = self.create_checked(&draft.spec, force).await?;
+
+        draft.submitted_poll_id = Some(created.poll_id.to_u64_saturating());
+        draft.updated_at = now();
+        store::save(DRAFTS_FILE, &book)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn draft() -> Draft {
+        let spec = PollFields {
+            question: Some("Adopt teh roadmap?".to_string()),
+            options: Some("Yes,No".to_string()),
+            tags: Some("roadmap".to_string()),
+            ..Default::default()
+        }
+        .resolve(None)
+        .unwrap();
+        Draft { id: 3, spec, created_at: 0, updated_at: 0, approvals: vec![], submitted_poll_id: None }
+    }
+
+    #[test]
+    fn edits_only_touch_given_fields() {
+        let mut draft = draft();
+        apply_edits(&mut draft.spec, PollFields { question: Some("Adopt the roadmap?".to_string()), ..Default::default() }).unwrap();
+        assert_eq!(draft.spec.question, "Adopt the roadmap?");
+        assert_eq!(draft.spec.options, ["Yes", "No"]);
+        assert_eq!(draft.spec.tags, ["roadmap"]);
+
+        apply_edits(&mut draft.spec, PollFields { ends_at: Some(1_751_392_800), ..Default::default() }).unwrap();
+        apply_edits(&mut draft.spec, PollFields { duration: Some(3_600), ..Default::default() }).unwrap();
+        assert_eq!(draft.spec.ends_at, None);
+        assert_eq!(draft.spec.duration, 3_600);
+    }
+
+    #[test]
+    fn markdown_shows_review_status() {
+        let mut draft = draft();
+        assert!(render_markdown(&draft).contains("**Status:** pending review"));
+
+        draft.approvals.push("alice".to_string());
+        let markdown = render_markdown(&draft);
+        assert!(markdown.starts_with("# Draft #3: Adopt teh roadmap?"));
+        assert!(markdown.contains("1. Yes\n2. No\n"));
+        assert!(markdown.contains("| Ends | 7d after submission |"));
+        assert!(markdown.contains("approved (approvals: alice)"));
+    }
+}
@@ -0,0 +1,96 @@
+use anyhow::Result;
+use chrono::{DateTime, NaiveDate, NaiveDateTime};
+use ethers::prelude::*;
+
+use crate::error::bail_user;
+use crate::numbers::SaturatingU64;
+use crate::PollManager;
+
+pub use polling_analytics::duration::{format, parse};
+
+/// Parse an absolute end time into a unix timestamp.
+///
+/// Accepts RFC 3339 (`2025-07-01T18:00:00+02:00`), minute precision
+/// (`2025-07-01T18:00Z`) and bare dates (`2025-07-01`, midnight); times without
+/// an offset are UTC.
+pub fn parse_ends_at(value: &str) -> std::result::Result<u64, String> {
+    let value = value.trim();
+    let timestamp = if let Ok(dt) = DateTime::parse_from_rfc3339(value) {
+        dt.timestamp()
+    } else if let Ok(dt) = DateTime::parse_from_str(value, "%Y-%m-%dT%H:%M%:z") {
+        dt.timestamp()
+    } else {
+        let naive = value.trim_end_matches(['Z', 'z']);
+        ["%Y-%m-%dT%H:%M:%S", "%Y-%m-%dT%H:%M", "%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M"].iter()
+            .find_map(|format| NaiveDateTime::parse_from_str(naive, format).ok())
+            .or_else(|| NaiveDate::parse_from_str(naive, "%Y-%m-%d").ok().and_then(|d| d.and_hms_opt(0, 0, 0)))
+            .map(|dt| dt.and_utc().timestamp())
+            .ok_or_else(|| format!("'{}' is not a date/time like 2025-07-01T18:00Z", value))?
+    };
+    u64::try_from(timestamp).map_err(|_| format!("'{}' is before 1970", value))
+}
+
+/// The contract's allowed poll duration range in seconds
+#[derive(Debug, Clone, Copy)]
+pub struct DurationLimits {
+    pub min: u64,
+    pub max: u64,
+}
+
+impl DurationLimits {
+    /// Reject durations `createPoll` would revert on with `InvalidDuration`
+    pub fn check(&self, seconds: u64) -> Result<()> {
+        if seconds < self.min {
+            bail_user!("Duration {} is shorter than the contract minimum of {}", format(seconds), format(self.min));
+        }
+        if seconds > self.max {
+            bail_user!("Duration {} is longer than the contract maximum of {}", format(seconds), format(self.max));
+        }
+        Ok(())
+    }
+}
+
+impl<M: Middleware + 'static> PollManager<M> {
+    pub async fn duration_limits(&self) -> Result<DurationLimits> {
+        let min = self.contract.min_poll_duration().call().await?;
+        let max = self.contract.max_poll_duration().call().await?;
+        Ok(DurationLimits { min: min.to_u64_saturating(), max: max.to_u64_saturating() })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_compound_durations() {
+        assert_eq!(parse("3d12h"), Ok(3 * 86_400 + 12 * 3_600));
+        assert_eq!(parse("90m"), Ok(5_400));
+        assert_eq!(parse("1w"), Ok(604_800));
+        assert_eq!(parse("168"), Ok(168 * 3_600));
+        assert!(parse("12").is_ok());
+        assert!(parse("3x").is_err());
+        assert!(parse("3d12").is_err());
+        assert!(parse("h").is_err());
+    }
+
+    #[test]
+    fn parses_end_times() {
+        assert_eq!(parse_ends_at("2025-07-01T18:00Z"), Ok(1_751_392_800));
+        assert_eq!(parse_ends_at("2025-07-01T18:00:00Z"), Ok(1_751_392_800));
+        assert_eq!(parse_ends_at("2025-07-01T20:00+02:00"), Ok(1_751_392_800));
+        assert_eq!(parse_ends_at("2025-07-01"), Ok(1_751_328_000));
+        assert!(parse_ends_at("next tuesday").is_err());
+    }
+
+    #[test]
+    fn formats_and_checks_limits() {
+        assert_eq!(format(3 * 86_400 + 12 * 3_600), "3d 12h");
+        assert_eq!(format(0), "0s");
+
+        let limits = DurationLimits { min: 3_600, max: 365 * 86_400 };
+        assert!(limits.check(3_600).is_ok());
+        assert!(limits.check(60).is_err());
+        assert!(limits.check(366 * 86_400).is_err());
+    }
+}
@@ -0,0 +1,209 @@
+use std::path::Path;
+
+use anyhow::Result;
+use colored::*;
+use ethers::abi::{encode, encode_packed, ParamType, RawLog, Token};
+use ethers::prelude::*;
+use ethers::utils::keccak256;
+
+use crate::certify::ResultCertificate;
+use crate::error::{bail_config, bail_user, CliError};
+use crate::{format_timestamp, PollManager};
+
+abigen!(
+    Eas,
+    r#"[
+        struct AttestationRequestData { address recipient; uint64 expirationTime; bool revocable; bytes32 refUID; bytes data; uint256 value; }
+        struct AttestationRequest { bytes32 schema; AttestationRequestData data; }
+        struct Attestation { bytes32 uid; bytes32 schema; uint64 time; uint64 expirationTime; uint64 revocationTime; bytes32 refUID; address recipient; address attester; bool revocable; bytes data; }
+        function attest(AttestationRequest request) external payable returns (bytes32)
+        function getAttestation(bytes32 uid) external view returns (Attestation memory)
+        event Attested(address indexed recipient, address indexed attester, bytes32 uid, bytes32 indexed schemaUID)
+    ]"#
+);
+
+/// The schema result attestations use; register it once per chain in EAS's SchemaRegistry
+pub const RESULT_SCHEMA: &str = "uint256 chainId,address pollContract,uint256 pollId,bytes32 resultHash";
+
+/// EAS deployments per chain
+const EAS_CONTRACTS: [(u64, &str); 6] = [
+    (1, "0xA1207F3BBa224E2c9c3c6D5aF63D0eb1582Ce587"),
+    (10, "0x4200000000000000000000000000000000000021"),
+    (8453, "0x4200000000000000000000000000000000000021"),
+    (42161, "0xbD75f629A22Dc1ceD33dDA0b68c546A1c035c458"),
+    (11155111, "0xC2679fBD37d54388Ce493F1DB75320D236e1815e"),
+    (84532, "0x4200000000000000000000000000000000000021"),
+];
+
+/// UID the SchemaRegistry gives a schema: keccak256(abi.encodePacked(schema, resolver, revocable))
+pub fn schema_uid(schema: &str, resolver: Address, revocable: bool) -> H256 {
+    let packed = encode_packed(&[Token::String(schema.to_string()), Token::Address(resolver), Token::Bool(revocable)])
+        .expect("strings, addresses and bools always pack");
+    H256(keccak256(packed))
+}
+
+/// The decoded fields of a result attestation
+#[derive(Debug, PartialEq)]
+pub struct ResultClaim {
+    pub chain_id: u64,
+    pub contract: Address,
+    pub poll_id: u64,
+    pub result_hash: H256,
+}
+
+impl ResultClaim {
+    pub fn encode(&self) -> Bytes {
+        encode(&[
+            Token::Uint(U256::from(self.chain_id)),
+            Token::Address(self.contract),
+            Token::Uint(U256::from(self.poll_id)),
+            Token::FixedBytes(self.result_hash.as_bytes().to_vec()),
+        ]).into()
+    }
+
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        let tokens = ethers::abi::decode(&[ParamType::Uint(256), ParamType::Address, ParamType::Uint(256), ParamType::FixedBytes(32)], data)
+            .map_err(|_| CliError::User(format!("Attestation data does not match the schema '{}'", RESULT_SCHEMA)))?;
+        match tokens.as_slice() {
+            [Token::Uint(chain_id), Token::Address(contract), Token::Uint(poll_id), Token::FixedBytes(hash)] => {
+                // Truncating would let an attestation for another chain or poll pass as this one
+                let (Ok(chain_id), Ok(poll_id)) = (u64::try_from(*chain_id), u64::try_from(*poll_id)) else {
+                    bail_user!("Attestation chain or poll ID is out of range");
+                };
+                Ok(ResultClaim { chain_id, contract: *contract, poll_id, result_hash: H256::from_slice(hash) })
+            }
+            _ => bail_user!("Attestation data does not match the schema '{}'", RESULT_SCHEMA),
+        }
+    }
+}
+
+/// EAS_CONTRACT_ADDRESS, or the known deployment on `chain_id`
+fn eas_address(chain_id: u64) -> Result<Address> {
+    if let Some(address) = std::env::var("EAS_CONTRACT_ADDRESS").ok().filter(|value| !value.is_empty()) {
+        return address.parse().map_err(|_| CliError::Config(format!("Invalid EAS_CONTRACT_ADDRESS '{}'", address)).into());
+    }
+    match EAS_CONTRACTS.iter().find(|(chain, _)| *chain == chain_id) {
+        Some((_, address)) => Ok(address.parse().expect("valid EAS address")),
+        None => bail_config!("No EAS deployment known for chain {}; set EAS_CONTRACT_ADDRESS", chain_id),
+    }
+}
+
+/// EAS_SCHEMA_UID, or the UID of `RESULT_SCHEMA` registered revocable and without a resolver
+fn schema() -> Result<H256> {
+    match std::env::var("EAS_SCHEMA_UID").ok().filter(|value| !value.is_empty()) {
+        Some(uid) => uid.parse().map_err(|_| CliError::Config(format!("Invalid EAS_SCHEMA_UID '{}'", uid)).into()),
+        None => Ok(schema_uid(RESULT_SCHEMA, Address::zero(), true)),
+    }
+}
+
+impl<M: Middleware + 'static> PollManager<M> {
+    /// `certify --eas`: attest the certificate's payload hash on-chain; returns the attestation UID
+    pub async fn attest_certificate(&self, certificate: &ResultCertificate) -> Result<H256> {
+        let eas = Eas::new(eas_address(self.wallet.chain_id())?, self.client.clone());
+        let schema = schema()?;
+        let claim = ResultClaim {
+            chain_id: certificate.payload.chain_id,
+            contract: certificate.payload.contract,
+            poll_id: certificate.payload.poll_id,
+            result_hash: certificate.payload_hash,
+        };
+        let request = AttestationRequest {
+            schema: schema.0,
+            data: AttestationRequestData {
+                recipient: certificate.payload.contract,
+                expiration_time: 0,
+                revocable: true,
+                ref_uid: [0; 32],
+                data: claim.encode(),
+                value: U256::zero(),
+            },
+        };
+        say!("{}", "📜 Publishing the result to EAS...".cyan().bold());
+        let call = self.as_sender(eas.attest(request));
+        let tx = self.send_call(&call).await?;
+        let receipt = tx.await?.ok_or_else(|| anyhow::anyhow!("Attestation transaction was dropped before it was mined"))?;
+        let uid = receipt.logs.iter()
+            .filter(|log| log.address == eas.address())
+            .find_map(|log| <AttestedFilter as EthEvent>::decode_log(&RawLog::from(log.clone())).ok())
+            .map(|event| H256(event.uid))
+            .ok_or_else(|| CliError::Rpc(format!("No Attested event in transaction {:?}; is schema {:?} registered?", receipt.transaction_hash, schema)))?;
+        say!("{} {:?}", "Attestation UID:".cyan().bold(), uid);
+        Ok(uid)
+    }
+
+    /// `verify-attestation`: check an EAS result attestation, and that it matches a certificate if given.
+    /// Anyone can attest on the result schema, so the attester must be `attester` or the certificate's signer.
+    pub async fn verify_attestation(&self, uid: H256, certificate: Option<&Path>, attester: Option<Address>) -> Result<()> {
+        let certificate = match certificate {
+            Some(path) => {
+                let certificate: ResultCertificate = serde_json::from_slice(&std::fs::read(path)?)
+                    .map_err(|e| CliError::User(format!("{} is not a result certificate: {}", path.display(), e)))?;
+                certificate.verify().map_err(|e| CliError::User(format!("{}: {:#}", path.display(), e)))?;
+                Some((path, certificate))
+            }
+            None => None,
+        };
+        let Some(expected_attester) = attester.or(certificate.as_ref().map(|(_, certificate)| certificate.signer)) else {
+            bail_user!("Pass --attester or --certificate; anyone can attest on the result schema");
+        };
+
+        let eas = Eas::new(eas_address(self.wallet.chain_id())?, self.client.clone());
+        let (found, attestation_schema, time, expiration_time, revocation_time, _, _, attested_by, _, data) =
+            eas.get_attestation(uid.0).call().await?;
+        if found == [0; 32] {
+            bail_user!("No attestation {:?} on {:?}", uid, eas.address());
+        }
+        let schema = schema()?;
+        if attestation_schema != schema.0 {
+            bail_user!("Attestation {:?} uses schema {:?}, not the result schema {:?}", uid, H256(attestation_schema), schema);
+        }
+        let claim = ResultClaim::decode(&data)?;
+
+        say!("\n{}", "📜 EAS RESULT ATTESTATION".cyan().bold().underline());
+        say!("{} {:?}", "UID:".yellow().bold(), uid);
+        say!("{} {:?}", "Attester:".yellow().bold(), attested_by);
+        say!("{} {}", "Attested:".yellow().bold(), format_timestamp(time));
+        say!("{} #{} on {:?} (chain {})", "Poll:".yellow().bold(), claim.poll_id, claim.contract, claim.chain_id);
+        say!("{} {:?}", "Result Hash:".yellow().bold(), claim.result_hash);
+
+        let now = chrono::Utc::now().timestamp() as u64;
+        if revocation_time != 0 {
+            bail_user!("Attestation was revoked {}", format_timestamp(revocation_time));
+        }
+        if expiration_time != 0 && expiration_time <= now {
+            bail_user!("Attestation expired {}", format_timestamp(expiration_time));
+        }
+        if attested_by != expected_attester {
+            bail_user!("Attested by {:?}, not {:?}", attested_by, expected_attester);
+        }
+        if let Some((path, certificate)) = certificate {
+            let expected = ResultClaim {
+                chain_id: certificate.payload.chain_id,
+                contract: certificate.payload.contract,
+                poll_id: certificate.payload.poll_id,
+                result_hash: certificate.payload_hash,
+            };
+            if claim != expected {
+                bail_user!("The attestation does not match {}", path.display());
+            }
+            say!("{} {}", "Certificate:".yellow().bold(), "matches".green());
+        }
+        say!("{}", "✅ Attestation is valid".green().bold());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn result_claims_round_trip_through_attestation_data() {
+        let claim = ResultClaim { chain_id: 11155111, contract: Address::repeat_byte(7), poll_id: 42, result_hash: H256::repeat_byte(9) };
+        assert_eq!(ResultClaim::decode(&claim.encode()).unwrap(), claim);
+        assert!(ResultClaim::decode(&[1, 2, 3]).is_err());
+        let oversized = encode(&[Token::Uint(U256::from(u64::MAX) + 1), Token::Address(claim.contract), Token::Uint(U256::from(42)), Token::FixedBytes(vec![9; 32])]);
+        assert!(ResultClaim::decode(&oversized).is_err(), "chain IDs past u64 are not truncated");
+        assert_ne!(schema_uid(RESULT_SCHEMA, Address::zero(), true), schema_uid(RESULT_SCHEMA, Address::zero(), false));
+    }
+}
@@ -0,0 +1,116 @@
+use anyhow::Result;
+use colored::*;
+use ethers::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::error::bail_user;
+use crate::identity::{IdentityScore, IdentityScorer};
+use crate::numbers::{self, SaturatingU64};
+use crate::{u8_to_poll_type, PollManager};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EligibilityReport {
+    pub poll_id: u64,
+    pub address: Address,
+    pub poll_type: String,
+    pub open: bool,
+    pub already_voted: bool,
+    /// Who votes on the address's behalf, if it delegated
+    pub delegate: Option<Address>,
+    /// Governance token voting power; `None` without GOVERNANCE_TOKEN_ADDRESS
+    #[serde(with = "numbers::decimal_option")]
+    pub voting_power: Option<U256>,
+    /// External identity score; `None` without IDENTITY_SCORE_URL
+    pub identity: Option<IdentityScore>,
+    pub eligible: bool,
+    /// Why the address cannot vote, if it cannot
+    pub reasons: Vec<String>,
+}
+
+fn print_report(report: &EligibilityReport, min_score: Option<f64>) {
+    let yes_no = |value: bool| if value { "yes".green() } else { "no".red() };
+    say!("\n{}", "🎟️  VOTING ELIGIBILITY".cyan().bold().underline());
+    say!("{} {}", "Poll ID:".yellow().bold(), report.poll_id);
+    say!("{} {:?}", "Address:".yellow().bold(), report.address);
+    say!("{} {}", "Poll Type:".yellow().bold(), report.poll_type);
+    say!("{} {}", "Open:".yellow().bold(), yes_no(report.open));
+    say!("{} {}", "Already Voted:".yellow().bold(), if report.already_voted { "yes".yellow() } else { "no".normal() });
+    if let Some(delegate) = report.delegate {
+        say!("{} {:?}", "Delegated To:".yellow().bold(), delegate);
+    }
+    if let Some(power) = report.voting_power {
+        say!("{} {}", "Voting Power:".yellow().bold(), numbers::format_amount(power, 18, 2));
+    }
+    if let Some(identity) = &report.identity {
+        let minimum = min_score.map(|min| format!(" (minimum {})", min)).unwrap_or_default();
+        let score = match identity.passing {
+            Some(false) => format!("{}", identity.score).red(),
+            Some(true) => format!("{}", identity.score).green(),
+            None => format!("{}", identity.score).normal(),
+        };
+        say!("{} {}{}", "Identity Score:".yellow().bold(), score, minimum);
+    }
+    say!("{} {}", "Eligible:".yellow().bold(), yes_no(report.eligible).bold());
+    for reason in &report.reasons {
+        say!("  - {}", reason);
+    }
+}
+
+impl<M: Middleware + 'static> PollManager<M> {
+    /// `eligibility`: whether `address` can vote on a poll right now, and why not
+    pub async fn check_eligibility(&self, poll_id: u64, address: Option<Address>, format: &str) -> Result<()> {
+        let json = match format.to_lowercase().as_str() {
+            "json" => true,
+            "table" => false,
+            _ => bail_user!("Unsupported format: {}. Use table or json", format),
+        };
+        let address = address.unwrap_or(self.sender);
+        let scorer = IdentityScorer::from_env()?;
+
+        let poll = self.poll_data(U256::from(poll_id)).await?;
+        let open = poll.6 == 0 && poll.5.to_u64_saturating() > chrono::Utc::now().timestamp() as u64;
+        let already_voted = self.contract.has_user_voted(U256::from(poll_id), address).call().await?;
+        let delegate = Some(self.contract.get_delegate(address).call().await?).filter(|delegate| !delegate.is_zero());
+        let voting_power = match &self.governance_token {
+            Some(token) => Some(token.get_voting_power(address).call().await?),
+            None => None,
+        };
+        let identity = match &scorer {
+            Some(scorer) => Some(scorer.score(address).await?),
+            None => None,
+        };
+
+        let mut reasons = Vec::new();
+        if !open {
+            reasons.push("the poll is closed or past its end time".to_string());
+        }
+        if already_voted {
+            reasons.push("already voted on this poll".to_string());
+        }
+        if poll.7 != 0 && voting_power.is_some_and(|power| power.is_zero()) {
+            reasons.push(format!("no voting power in a {} poll", u8_to_poll_type(poll.7).to_lowercase()));
+        }
+        if let Some(identity) = identity.as_ref().filter(|identity| identity.passing == Some(false)) {
+            reasons.push(format!("identity score {} is below the minimum of {}", identity.score, scorer.as_ref().and_then(|s| s.min_score()).unwrap_or_default()));
+        }
+
+        let report = EligibilityReport {
+            poll_id,
+            address,
+            poll_type: u8_to_poll_type(poll.7).to_string(),
+            open,
+            already_voted,
+            delegate,
+            voting_power,
+            identity,
+            eligible: reasons.is_empty(),
+            reasons,
+        };
+        if json {
+            println!("{}", serde_json::to_string_pretty(&report)?);
+        } else {
+            print_report(&report, scorer.as_ref().and_then(|s| s.min_score()));
+        }
+        Ok(())
+    }
+}
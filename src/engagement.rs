@@ -0,0 +1,359 @@
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+use anyhow::Result;
+use chrono::{DateTime, Datelike};
+use colored::*;
+use ethers::prelude::*;
+use polling_analytics::stats::share;
+use serde::Serialize;
+
+use crate::error::bail_user;
+use crate::indexer::{ContractEvent, IndexedEvent};
+use crate::{numbers, PollManager};
+
+/// What the index knows about a poll from its events
+#[derive(Debug, Default)]
+struct PollInfo {
+    question: String,
+    end_time: u64,
+    closed: bool,
+}
+
+fn poll_info(events: &[IndexedEvent]) -> BTreeMap<u64, PollInfo> {
+    let mut polls: BTreeMap<u64, PollInfo> = BTreeMap::new();
+    for event in events {
+        match &event.event {
+            ContractEvent::PollCreated { poll_id, question, end_time, .. } => {
+                let poll = polls.entry(*poll_id).or_default();
+                poll.question = question.clone();
+                poll.end_time = *end_time;
+            }
+            ContractEvent::PollExtended { poll_id, end_time } => polls.entry(*poll_id).or_default().end_time = *end_time,
+            ContractEvent::PollStatusChanged { poll_id, status } => polls.entry(*poll_id).or_default().closed = *status != 0,
+            ContractEvent::VoteCast { poll_id, .. } => {
+                polls.entry(*poll_id).or_default();
+            }
+            _ => {}
+        }
+    }
+    polls
+}
+
+/// One voter's run of consecutive polls voted in, counted from their first vote
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Streak {
+    pub rank: usize,
+    pub voter: Address,
+    /// Consecutive polls up to the latest one; polls still open that they haven't voted on yet don't break it
+    pub current: usize,
+    pub longest: usize,
+    pub polls_voted: usize,
+    /// Polls they could have voted in since their first vote
+    pub polls_since_first: usize,
+}
+
+fn streaks(events: &[IndexedEvent], now: u64) -> Vec<Streak> {
+    let polls = poll_info(events);
+    let mut voted: BTreeMap<Address, BTreeSet<u64>> = BTreeMap::new();
+    for event in events {
+        if let ContractEvent::VoteCast { poll_id, voter, .. } = event.event {
+            voted.entry(voter).or_default().insert(poll_id);
+        }
+    }
+
+    let mut streaks: Vec<Streak> = voted.into_iter().map(|(voter, voted)| {
+        let first = voted.first().copied().unwrap_or_default();
+        let (mut run, mut longest, mut eligible) = (0, 0, 0);
+        for (poll_id, poll) in polls.range(first..) {
+            if voted.contains(poll_id) {
+                run += 1;
+                longest = longest.max(run);
+            } else if poll.closed || poll.end_time <= now {
+                run = 0;
+            } else {
+                // Still open: they may yet vote
+                continue;
+            }
+            eligible += 1;
+        }
+        Streak { rank: 0, voter, current: run, longest, polls_voted: voted.len(), polls_since_first: eligible }
+    }).collect();
+    streaks.sort_by(|a, b| b.current.cmp(&a.current).then(b.longest.cmp(&a.longest)).then(b.polls_voted.cmp(&a.polls_voted)));
+    for (i, streak) in streaks.iter_mut().enumerate() {
+        streak.rank = i + 1;
+    }
+    streaks
+}
+
+/// How many of a poll's voters voted for the first time in it
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FirstTimeVoters {
+    pub poll_id: u64,
+    pub question: String,
+    pub voters: usize,
+    pub first_time: usize,
+    /// First-time voters as a percentage of the poll's voters
+    pub share: f64,
+}
+
+fn first_time_voters(events: &[IndexedEvent]) -> Vec<FirstTimeVoters> {
+    let polls = poll_info(events);
+    let mut seen: HashSet<Address> = HashSet::new();
+    let mut per_poll: BTreeMap<u64, (HashSet<Address>, usize)> = BTreeMap::new();
+    for event in events {
+        if let ContractEvent::VoteCast { poll_id, voter, .. } = event.event {
+            let (voters, first_time) = per_poll.entry(poll_id).or_default();
+            voters.insert(voter);
+            if seen.insert(voter) {
+                *first_time += 1;
+            }
+        }
+    }
+    polls.into_iter().map(|(poll_id, poll)| {
+        let (voters, first_time) = per_poll.remove(&poll_id).map(|(voters, first)| (voters.len(), first)).unwrap_or_default();
+        FirstTimeVoters { poll_id, question: poll.question, voters, first_time, share: share(first_time as f64, voters as f64) }
+    }).collect()
+}
+
+/// Activity in one calendar month (UTC)
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct MonthlyEngagement {
+    /// e.g. 2025-07
+    pub month: String,
+    pub polls_created: usize,
+    pub votes: usize,
+    pub voters: usize,
+    /// Voters whose first vote ever was this month
+    pub new_voters: usize,
+    /// Voters who had voted in an earlier month too
+    pub returning_voters: usize,
+    #[serde(with = "numbers::decimal")]
+    pub weight: U256,
+}
+
+fn month_of(timestamp: u64) -> (i32, u32) {
+    let date = DateTime::from_timestamp(timestamp as i64, 0).unwrap_or_default();
+    (date.year(), date.month())
+}
+
+/// One row per month from the first event to the last; quiet months are kept, with zeros
+fn monthly(events: &[IndexedEvent]) -> Vec<MonthlyEngagement> {
+    let (Some(first), Some(last)) = (events.first(), events.last()) else {
+        return Vec::new();
+    };
+    let mut months: BTreeMap<(i32, u32), (MonthlyEngagement, HashSet<Address>)> = BTreeMap::new();
+    let (mut month, end) = (month_of(first.timestamp), month_of(last.timestamp));
+    while month <= end {
+        let row = MonthlyEngagement { month: format!("{:04}-{:02}", month.0, month.1), ..Default::default() };
+        months.insert(month, (row, HashSet::new()));
+        month = if month.1 == 12 { (month.0 + 1, 1) } else { (month.0, month.1 + 1) };
+    }
+
+    let mut seen: HashSet<Address> = HashSet::new();
+    for event in events {
+        let Some((row, voters)) = months.get_mut(&month_of(event.timestamp)) else { continue };
+        match event.event {
+            ContractEvent::PollCreated { .. } => row.polls_created += 1,
+            ContractEvent::VoteCast { voter, weight, .. } => {
+                row.votes += 1;
+                row.weight = row.weight.saturating_add(weight);
+                if voters.insert(voter) {
+                    if seen.insert(voter) {
+                        row.new_voters += 1;
+                    } else {
+                        row.returning_voters += 1;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    months.into_values().map(|(mut row, voters)| {
+        row.voters = voters.len();
+        row
+    }).collect()
+}
+
+fn render_csv<T: Serialize>(rows: &[T]) -> Result<String> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    for row in rows {
+        writer.serialize(row)?;
+    }
+    Ok(String::from_utf8(writer.into_inner()?)?)
+}
+
+fn print_streaks(streaks: &[Streak], total: usize) {
+    say!("\n{}", "🔥 VOTING STREAKS".cyan().bold().underline());
+    say!("{}", "═".repeat(60).cyan());
+    if streaks.is_empty() {
+        say!("No votes indexed yet.");
+        return;
+    }
+    say!("{:>4}  {:<42}  {:>7}  {:>7}  {:>9}", "Rank", "Voter", "Current", "Longest", "Voted");
+    for streak in streaks {
+        let current = if streak.current > 0 { streak.current.to_string().green().bold() } else { streak.current.to_string().normal() };
+        say!("{:>4}  {:<42}  {:>7}  {:>7}  {:>9}",
+            streak.rank, format!("{:?}", streak.voter), current, streak.longest, format!("{}/{}", streak.polls_voted, streak.polls_since_first));
+    }
+    if total > streaks.len() {
+        say!("\n{} of {} voters shown; use -n to see more", streaks.len(), total);
+    }
+}
+
+fn print_first_time(polls: &[FirstTimeVoters]) {
+    say!("\n{}", "🌱 FIRST-TIME VOTERS".cyan().bold().underline());
+    say!("{}", "═".repeat(60).cyan());
+    if polls.is_empty() {
+        say!("No polls indexed yet.");
+        return;
+    }
+    for poll in polls {
+        say!("#{} {}", poll.poll_id, poll.question.white().bold());
+        say!("  {} of {} voters new ({:.1}%)", poll.first_time.to_string().green(), poll.voters, poll.share);
+    }
+}
+
+fn print_monthly(months: &[MonthlyEngagement]) {
+    say!("\n{}", "📅 MONTHLY ENGAGEMENT (UTC)".cyan().bold().underline());
+    say!("{}", "═".repeat(60).cyan());
+    if months.is_empty() {
+        say!("No activity indexed yet.");
+        return;
+    }
+    say!("{:<8}  {:>6}  {:>8}  {:>7}  {:>5}  {:>9}", "Month", "Polls", "Votes", "Voters", "New", "Returning");
+    for month in months {
+        say!("{:<8}  {:>6}  {:>8}  {:>7}  {:>5}  {:>9}",
+            month.month,
+            month.polls_created,
+            numbers::format_integer(month.votes as u64),
+            month.voters,
+            month.new_voters.to_string().green(),
+            month.returning_voters);
+    }
+}
+
+/// Which engagement view to compute
+#[derive(Debug, Clone, Copy)]
+pub enum EngagementView {
+    /// The top N voters by streak
+    Streaks(usize),
+    FirstTime,
+    Monthly,
+}
+
+impl<M: Middleware + 'static> PollManager<M> {
+    /// `engagement`: streaks, first-time voters and monthly activity from the indexed events
+    pub async fn engagement(&self, view: EngagementView, format: &str, output_path: Option<String>) -> Result<()> {
+        let format = format.to_lowercase();
+        if !matches!(format.as_str(), "table" | "csv" | "json") {
+            bail_user!("Unsupported format: {}. Use table, csv or json", format);
+        }
+        if matches!(view, EngagementView::Streaks(0)) {
+            bail_user!("-n must be at least 1");
+        }
+        let index = self.event_index().await?;
+        let events = match self.pinned {
+            Some(pinned) => &index.events[..index.events.partition_point(|e| e.block_number <= pinned.number)],
+            None => &index.events[..],
+        };
+
+        let rendered = match view {
+            EngagementView::Streaks(top) => {
+                let mut rows = streaks(events, self.now());
+                let total = rows.len();
+                rows.truncate(top);
+                match format.as_str() {
+                    "csv" => render_csv(&rows)?,
+                    "json" => serde_json::to_string_pretty(&rows)?,
+                    _ => {
+                        print_streaks(&rows, total);
+                        return Ok(());
+                    }
+                }
+            }
+            EngagementView::FirstTime => {
+                let rows = first_time_voters(events);
+                match format.as_str() {
+                    "csv" => render_csv(&rows)?,
+                    "json" => serde_json::to_string_pretty(&rows)?,
+                    _ => {
+                        print_first_time(&rows);
+                        return Ok(());
+                    }
+                }
+            }
+            EngagementView::Monthly => {
+                let rows = monthly(events);
+                match format.as_str() {
+                    "csv" => render_csv(&rows)?,
+                    "json" => serde_json::to_string_pretty(&rows)?,
+                    _ => {
+                        print_monthly(&rows);
+                        return Ok(());
+                    }
+                }
+            }
+        };
+        match output_path {
+            Some(path) => {
+                std::fs::write(&path, rendered)?;
+                say!("{} {}", "✅ Exported to:".green().bold(), path.yellow());
+            }
+            None => println!("{}", rendered.trim_end()),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const JUNE: u64 = 1_717_200_000; // 2024-06-01
+    const AUGUST: u64 = 1_722_470_400; // 2024-08-01
+
+    fn at(timestamp: u64, event: ContractEvent) -> IndexedEvent {
+        IndexedEvent { block_number: 0, block_hash: H256::zero(), transaction_hash: H256::zero(), log_index: 0, timestamp, event }
+    }
+
+    fn created(poll_id: u64, end_time: u64) -> ContractEvent {
+        ContractEvent::PollCreated { poll_id, creator: Address::zero(), question: format!("Q{}", poll_id), poll_type: 0, category: 0, end_time, tags: vec![] }
+    }
+
+    fn vote(poll_id: u64, voter: u64) -> ContractEvent {
+        ContractEvent::VoteCast { poll_id, voter: Address::from_low_u64_be(voter), option_index: 0, weight: U256::one() }
+    }
+
+    #[test]
+    fn computes_streaks_first_time_voters_and_months() {
+        let now = AUGUST + 10;
+        let events = vec![
+            at(JUNE, created(0, JUNE + 1)),
+            at(JUNE, created(1, JUNE + 2)),
+            at(JUNE, created(2, JUNE + 3)),
+            at(JUNE, created(3, now + 100)),
+            at(JUNE, vote(0, 1)),
+            at(JUNE, vote(0, 2)),
+            at(JUNE, vote(1, 1)),
+            at(AUGUST, vote(2, 2)),
+            at(AUGUST, vote(2, 3)),
+            at(AUGUST, vote(2, 1)),
+        ];
+
+        let streaks = streaks(&events, now);
+        let by_voter = |voter: u64| streaks.iter().find(|s| s.voter == Address::from_low_u64_be(voter)).unwrap().clone();
+        // Poll 3 is still open, so not having voted on it yet keeps the current streak
+        assert_eq!((by_voter(1).current, by_voter(1).longest, by_voter(1).rank), (3, 3, 1));
+        assert_eq!((by_voter(2).current, by_voter(2).longest, by_voter(2).polls_since_first), (1, 1, 3));
+        assert_eq!((by_voter(3).current, by_voter(3).polls_since_first), (1, 1));
+
+        let first_time: Vec<(u64, usize, usize)> = first_time_voters(&events).iter().map(|p| (p.poll_id, p.voters, p.first_time)).collect();
+        assert_eq!(first_time, vec![(0, 2, 2), (1, 1, 0), (2, 3, 1), (3, 0, 0)]);
+
+        let months = monthly(&events);
+        assert_eq!(months.iter().map(|m| m.month.as_str()).collect::<Vec<_>>(), vec!["2024-06", "2024-07", "2024-08"]);
+        assert_eq!((months[0].polls_created, months[0].votes, months[0].new_voters), (4, 3, 2));
+        assert_eq!(months[1], MonthlyEngagement { month: "2024-07".to_string(), ..Default::default() });
+        assert_eq!((months[2].voters, months[2].new_voters, months[2].returning_voters), (3, 1, 2));
+    }
+}
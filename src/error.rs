@@ -0,0 +1,114 @@
+use ethers::prelude::*;
+use thiserror::Error;
+
+use crate::Client;
+
+/// Failure classes, each mapped to its own process exit code
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// Anything not classified below
+    General,
+    /// Invalid input or an operation that does not apply (same code clap uses for usage errors)
+    User,
+    /// Missing or malformed environment/configuration
+    Config,
+    /// The RPC endpoint or another remote service could not be reached or failed
+    Rpc,
+    /// The contract rejected the call or transaction
+    Revert,
+}
+
+impl ErrorKind {
+    pub fn exit_code(self) -> i32 {
+        match self {
+            ErrorKind::General => 1,
+            ErrorKind::User => 2,
+            ErrorKind::Config => 3,
+            ErrorKind::Rpc => 4,
+            ErrorKind::Revert => 5,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ErrorKind::General => "Error",
+            ErrorKind::User => "Invalid input",
+            ErrorKind::Config => "Configuration error",
+            ErrorKind::Rpc => "RPC error",
+            ErrorKind::Revert => "Contract reverted",
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum CliError {
+    #[error("{0}")]
+    User(String),
+    #[error("{0}")]
+    Config(String),
+    #[error("{0}")]
+    Rpc(String),
+}
+
+/// Return early with a `CliError::User`
+macro_rules! bail_user {
+    ($($arg:tt)*) => {
+        return Err($crate::error::CliError::User(format!($($arg)*)).into())
+    };
+}
+
+/// Return early with a `CliError::Config`
+macro_rules! bail_config {
+    ($($arg:tt)*) => {
+        return Err($crate::error::CliError::Config(format!($($arg)*)).into())
+    };
+}
+
+pub(crate) use bail_config;
+pub(crate) use bail_user;
+
+/// Walk the error chain and decide which failure class it belongs to
+pub fn classify(err: &anyhow::Error) -> ErrorKind {
+    for cause in err.chain() {
+        if let Some(e) = cause.downcast_ref::<CliError>() {
+            return match e {
+                CliError::User(_) => ErrorKind::User,
+                CliError::Config(_) => ErrorKind::Config,
+                CliError::Rpc(_) => ErrorKind::Rpc,
+            };
+        }
+        if let Some(e) = cause.downcast_ref::<ContractError<Client>>() {
+            return if e.is_revert() || e.to_string().contains("revert") {
+                ErrorKind::Revert
+            } else {
+                ErrorKind::Rpc
+            };
+        }
+        if cause.is::<ProviderError>() || cause.is::<reqwest::Error>() {
+            return ErrorKind::Rpc;
+        }
+        if cause.is::<WalletError>() || cause.is::<url::ParseError>() {
+            return ErrorKind::Config;
+        }
+        if cause.is::<std::io::Error>() || cause.is::<serde_json::Error>() {
+            return ErrorKind::User;
+        }
+    }
+
+    ErrorKind::General
+}
+
+/// Send standard output to the null device so only errors (on stderr) remain
+#[cfg(unix)]
+pub fn silence_stdout() {
+    use std::os::unix::io::AsRawFd;
+    if let Ok(null) = std::fs::OpenOptions::new().write(true).open("/dev/null") {
+        // SAFETY: both descriptors are valid for the duration of the call
+        unsafe {
+            libc::dup2(null.as_raw_fd(), libc::STDOUT_FILENO);
+        }
+    }
+}
+
+#[cfg(not(unix))]
+pub fn silence_stdout() {}
@@ -0,0 +1,164 @@
+use std::collections::HashSet;
+
+use anyhow::Result;
+use ethers::abi::RawLog;
+use ethers::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::numbers::SaturatingU64;
+use crate::{PollCreatedFilter, PollManager, VoteCastFilter};
+
+/// A VoteCast event together with the block it was mined in
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VoteRecord {
+    pub poll_id: u64,
+    pub voter: Address,
+    pub option_index: u64,
+    pub weight: U256,
+    pub block_number: u64,
+    pub transaction_hash: H256,
+    pub timestamp: u64,
+}
+
+/// The PollCreated event emitted by a createPoll transaction
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreatedPoll {
+    pub poll_id: U256,
+    pub creator: Address,
+    pub end_time: u64,
+    pub transaction_hash: H256,
+}
+
+/// Decode the PollCreated event from a receipt, ignoring logs emitted by any other contract
+pub fn poll_created_from_receipt(receipt: &TransactionReceipt, contract: Address) -> Option<CreatedPoll> {
+    receipt.logs.iter()
+        .filter(|log| log.address == contract)
+        .find_map(|log| <PollCreatedFilter as EthEvent>::decode_log(&RawLog::from(log.clone())).ok())
+        .map(|event| CreatedPoll {
+            poll_id: event.poll_id,
+            creator: event.creator,
+            end_time: event.end_time.low_u64(),
+            transaction_hash: receipt.transaction_hash,
+        })
+}
+
+/// Decode the VoteCast event for `poll_id` by `voter` from a receipt, ignoring other contracts' logs
+pub fn vote_cast_from_receipt(receipt: &TransactionReceipt, contract: Address, poll_id: u64, voter: Address) -> Option<VoteCastFilter> {
+    receipt.logs.iter()
+        .filter(|log| log.address == contract)
+        .filter_map(|log| <VoteCastFilter as EthEvent>::decode_log(&RawLog::from(log.clone())).ok())
+        .find(|event| event.poll_id == U256::from(poll_id) && event.voter == voter)
+}
+
+impl<M: Middleware + 'static> PollManager<M> {
+    /// Every VoteCast event for a poll, oldest first, from the synced event index;
+    /// votes after a pinned block are left out
+    pub async fn fetch_vote_events(&self, poll_id: u64) -> Result<Vec<VoteRecord>> {
+        let mut votes = self.event_index().await?.votes(poll_id);
+        if let Some(pinned) = self.pinned {
+            votes.retain(|vote| vote.block_number <= pinned.number);
+        }
+        Ok(votes)
+    }
+
+    /// `(poll_id, tags)` for every poll, from PollCreated events
+    pub async fn fetch_poll_tags(&self) -> Result<Vec<(u64, Vec<String>)>> {
+        Ok(self.event_index().await?.poll_tags())
+    }
+
+    /// Every tag attached to a poll at creation
+    pub async fn fetch_created_tags(&self) -> Result<HashSet<String>> {
+        Ok(self.fetch_poll_tags().await?.into_iter().flat_map(|(_, tags)| tags).collect())
+    }
+
+    pub(crate) async fn block_timestamp(&self, block_number: u64) -> Result<u64> {
+        let block = self.client
+            .get_block(block_number)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Block {} not found", block_number))?;
+        Ok(block.timestamp.to_u64_saturating())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::abi::{encode, Token};
+
+    fn poll_created_log(contract: Address, poll_id: u64, creator: Address) -> Log {
+        Log {
+            address: contract,
+            topics: vec![
+                PollCreatedFilter::signature(),
+                H256::from_low_u64_be(poll_id),
+                H256::from(creator),
+            ],
+            data: encode(&[
+                Token::String("Question?".to_string()),
+                Token::Uint(0.into()),
+                Token::Uint(1.into()),
+                Token::Uint(1_700_000_000u64.into()),
+                Token::Array(vec![Token::String("tag".to_string())]),
+            ]).into(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn decodes_poll_created_after_foreign_logs() {
+        let contract = Address::from_low_u64_be(0xc0);
+        let token = Address::from_low_u64_be(0x70);
+        let creator = Address::from_low_u64_be(0xabc);
+
+        // An ERC-20 Transfer from another contract comes first, as with a fee token
+        let transfer = Log {
+            address: token,
+            topics: vec![H256::from_low_u64_be(1), H256::from(creator), H256::from(contract)],
+            data: encode(&[Token::Uint(5.into())]).into(),
+            ..Default::default()
+        };
+        let receipt = TransactionReceipt {
+            logs: vec![transfer, poll_created_log(contract, 42, creator)],
+            ..Default::default()
+        };
+
+        let created = poll_created_from_receipt(&receipt, contract).unwrap();
+        assert_eq!(created.poll_id, U256::from(42));
+        assert_eq!(created.creator, creator);
+        assert_eq!(created.end_time, 1_700_000_000);
+    }
+
+    #[test]
+    fn ignores_poll_created_from_other_contracts() {
+        let contract = Address::from_low_u64_be(0xc0);
+        let other = Address::from_low_u64_be(0xc1);
+        let receipt = TransactionReceipt {
+            logs: vec![poll_created_log(other, 1, Address::zero())],
+            ..Default::default()
+        };
+
+        assert!(poll_created_from_receipt(&receipt, contract).is_none());
+    }
+
+    #[test]
+    fn finds_vote_cast_for_poll_and_voter() {
+        let contract = Address::from_low_u64_be(0xc0);
+        let voter = Address::from_low_u64_be(0xabc);
+        let vote_log = |poll_id: u64, voter: Address| Log {
+            address: contract,
+            topics: vec![VoteCastFilter::signature(), H256::from_low_u64_be(poll_id), H256::from(voter)],
+            data: encode(&[Token::Uint(2.into()), Token::Uint(150.into())]).into(),
+            ..Default::default()
+        };
+        let receipt = TransactionReceipt {
+            logs: vec![vote_log(3, voter), vote_log(4, Address::zero()), vote_log(4, voter)],
+            ..Default::default()
+        };
+
+        let vote = vote_cast_from_receipt(&receipt, contract, 4, voter).unwrap();
+        assert_eq!(vote.option_index, U256::from(2));
+        assert_eq!(vote.weight, U256::from(150));
+        assert!(vote_cast_from_receipt(&receipt, contract, 5, voter).is_none());
+        assert!(vote_cast_from_receipt(&receipt, Address::zero(), 4, voter).is_none());
+    }
+}
@@ -0,0 +1,157 @@
+use anyhow::Result;
+use colored::*;
+use ethers::prelude::*;
+
+use crate::confirm::confirm;
+use crate::error::bail_user;
+use crate::numbers::SaturatingU64;
+use crate::preview::truncate;
+use crate::{duration, format_timestamp, output, policy, redact, u8_to_status, PollManager};
+
+/// One of the caller's polls carrying the tag, and whether it may be extended
+#[derive(Debug, Clone)]
+pub struct Extension {
+    pub poll_id: u64,
+    pub question: String,
+    pub created_at: u64,
+    pub end_time: u64,
+    pub new_end: u64,
+    /// Why the poll is left alone; `None` when it will be extended
+    pub refusal: Option<String>,
+}
+
+/// Why a poll must not be extended by `additional`; `contract_max` is maxPollDuration and
+/// `policy_max` the configured longest total duration
+fn refusal(status: u8, created_at: u64, end_time: u64, additional: u64, now: u64, contract_max: u64, policy_max: Option<u64>) -> Option<String> {
+    let new_end = end_time.saturating_add(additional);
+    if status != 0 {
+        return Some(format!("already {}", u8_to_status(status).to_lowercase()));
+    }
+    // extendPoll only checks the status, so it would reopen a poll that ended but was never closed
+    if end_time <= now {
+        return Some("ended; close it with maintain close-expired".to_string());
+    }
+    // extendPoll reverts once the end would be more than maxPollDuration from now
+    if new_end > now.saturating_add(contract_max) {
+        return Some(format!("ends over {} from now", duration::format(contract_max)));
+    }
+    match policy_max {
+        Some(maximum) if new_end.saturating_sub(created_at) > maximum => {
+            Some(format!("would run {}, over max-poll-duration {}", duration::format(new_end.saturating_sub(created_at)), duration::format(maximum)))
+        }
+        _ => None,
+    }
+}
+
+fn print_extensions(extensions: &[Extension], tag: &str, additional: u64) {
+    say!("\n{}", format!("⏰ EXTEND POLLS TAGGED {} BY {}", tag, duration::format(additional)).cyan().bold().underline());
+    say!("{}", "═".repeat(60).cyan());
+    say!("{:>5}  {:<32}  {:<17}  {:<17}  {:>9}  {}", "Poll", "Question", "Current end", "New end", "Total", "Action");
+    for extension in extensions {
+        let action = match &extension.refusal {
+            None => "extend".green().bold(),
+            Some(reason) => format!("refused: {}", reason).red(),
+        };
+        say!("{:>5}  {:<32}  {:<17}  {:<17}  {:>9}  {}",
+            format!("#{}", extension.poll_id),
+            truncate(&extension.question, 32),
+            format_timestamp(extension.end_time),
+            format_timestamp(extension.new_end),
+            duration::format(extension.new_end.saturating_sub(extension.created_at)),
+            action,
+        );
+    }
+}
+
+impl<M: Middleware + 'static> PollManager<M> {
+    /// `extend-batch`: extend every poll the caller created with `tag` by `additional` seconds,
+    /// refusing closed or ended polls and those that would run past the contract's or the policy's limit
+    pub async fn extend_batch(&self, tag: &str, additional: u64, yes: bool) -> Result<()> {
+        if additional == 0 {
+            bail_user!("Extension must be longer than zero");
+        }
+        let limits = self.duration_limits().await?;
+        let policy_max = policy::active().max_total_duration()?;
+        let now = chrono::Utc::now().timestamp() as u64;
+
+        let progress = output::spinner("🔍 Finding your tagged polls...");
+        let mut extensions = Vec::new();
+        for poll_id in self.contract.get_user_created_polls(self.sender).call().await? {
+            // Validate against the current end time, not a cached one
+            let poll = self.contract.get_poll(poll_id).call().await?;
+            if !poll.13.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+                continue;
+            }
+            let (created_at, end_time) = (poll.4.to_u64_saturating(), poll.5.to_u64_saturating());
+            extensions.push(Extension {
+                poll_id: poll_id.to_u64_saturating(),
+                question: poll.1,
+                created_at,
+                end_time,
+                new_end: end_time.saturating_add(additional),
+                refusal: refusal(poll.6, created_at, end_time, additional, now, limits.max, policy_max),
+            });
+        }
+        progress.finish_and_clear();
+
+        if extensions.is_empty() {
+            say!("You have no polls tagged '{}'.", tag);
+            return Ok(());
+        }
+        print_extensions(&extensions, tag, additional);
+        let extending: Vec<&Extension> = extensions.iter().filter(|extension| extension.refusal.is_none()).collect();
+        if extending.is_empty() {
+            say!("None of them can be extended.");
+            return Ok(());
+        }
+        say!("{}", "The contract cannot shorten a poll again; the extra time stays.".yellow());
+        if !confirm(&format!("Extend {} polls by {}?", extending.len(), duration::format(additional)), yes)? {
+            return Ok(());
+        }
+
+        let (total, mut extended) = (extending.len(), 0);
+        for extension in extending {
+            let call = self.as_sender(self.contract.extend_poll(U256::from(extension.poll_id), U256::from(additional)));
+            // Keep going after a failure so one reverted poll doesn't hold up the rest
+            let result = match self.send_call(&call).await {
+                Ok(tx) => tx.await.map_err(anyhow::Error::from),
+                Err(e) => Err(e),
+            };
+            self.cache.invalidate(extension.poll_id);
+            match result {
+                Ok(Some(receipt)) => {
+                    extended += 1;
+                    say!("{} #{} to {} in {:?}", "✅ Extended poll".green().bold(), extension.poll_id,
+                        format_timestamp(extension.new_end), receipt.transaction_hash);
+                }
+                Ok(None) => eprintln!("{} #{}: transaction was dropped before it was mined",
+                    output::render("❌ Poll".to_string()).red(), extension.poll_id),
+                Err(e) => eprintln!("{} #{}: {}", output::render("❌ Poll".to_string()).red(), extension.poll_id, redact::redact(&format!("{:#}", e))),
+            }
+        }
+        say!("{} {} of {} polls", "⏰ Extended".cyan().bold(), extended, total);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY: u64 = 86_400;
+
+    #[test]
+    fn refuses_closed_and_overlong_extensions() {
+        let now = 10 * DAY;
+        // Created on day 0, ending on day 12; extended by a day
+        assert_eq!(refusal(0, 0, 12 * DAY, DAY, now, 30 * DAY, None), None);
+        assert_eq!(refusal(1, 0, 12 * DAY, DAY, now, 30 * DAY, None).as_deref(), Some("already closed"));
+        assert_eq!(refusal(3, 0, 12 * DAY, DAY, now, 30 * DAY, None).as_deref(), Some("already cancelled"));
+        assert_eq!(refusal(0, 0, 9 * DAY, DAY, now, 30 * DAY, None).as_deref(), Some("ended; close it with maintain close-expired"));
+        assert_eq!(refusal(0, 0, now, DAY, now, 30 * DAY, None).as_deref(), Some("ended; close it with maintain close-expired"));
+        assert_eq!(refusal(0, 0, 12 * DAY, DAY, now, 2 * DAY, None).as_deref(), Some("ends over 2d from now"));
+        assert_eq!(refusal(0, 0, 12 * DAY, DAY, now, 30 * DAY, Some(13 * DAY)), None);
+        assert_eq!(refusal(0, 0, 12 * DAY, DAY, now, 30 * DAY, Some(12 * DAY)).as_deref(),
+            Some("would run 13d, over max-poll-duration 12d"));
+    }
+}
@@ -0,0 +1,154 @@
+use anyhow::Result;
+use colored::*;
+use ethers::prelude::*;
+
+use crate::numbers::SaturatingU64;
+use crate::error::bail_user;
+use crate::{category_to_u8, u8_to_category, PollManager};
+
+struct FeedItem {
+    id: u64,
+    title: String,
+    body: String,
+    link: Option<String>,
+    category: String,
+    created_at: u64,
+}
+
+pub(crate) fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+fn rfc2822(timestamp: u64) -> String {
+    chrono::DateTime::from_timestamp(timestamp as i64, 0).unwrap_or_default().to_rfc2822()
+}
+
+fn rfc3339(timestamp: u64) -> String {
+    chrono::DateTime::from_timestamp(timestamp as i64, 0).unwrap_or_default().to_rfc3339()
+}
+
+fn render_rss(title: &str, items: &[FeedItem]) -> String {
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<rss version=\"2.0\">\n<channel>\n");
+    xml.push_str(&format!("  <title>{}</title>\n", xml_escape(title)));
+    xml.push_str("  <description>Newly created polls</description>\n");
+    if let Some(latest) = items.first() {
+        xml.push_str(&format!("  <lastBuildDate>{}</lastBuildDate>\n", rfc2822(latest.created_at)));
+    }
+
+    for item in items {
+        xml.push_str("  <item>\n");
+        xml.push_str(&format!("    <title>{}</title>\n", xml_escape(&item.title)));
+        if let Some(link) = &item.link {
+            xml.push_str(&format!("    <link>{}</link>\n", xml_escape(link)));
+        }
+        xml.push_str(&format!("    <description>{}</description>\n", xml_escape(&item.body)));
+        xml.push_str(&format!("    <category>{}</category>\n", xml_escape(&item.category)));
+        xml.push_str(&format!("    <guid isPermaLink=\"false\">poll-{}</guid>\n", item.id));
+        xml.push_str(&format!("    <pubDate>{}</pubDate>\n", rfc2822(item.created_at)));
+        xml.push_str("  </item>\n");
+    }
+
+    xml.push_str("</channel>\n</rss>\n");
+    xml
+}
+
+fn render_atom(title: &str, feed_id: &str, items: &[FeedItem]) -> String {
+    let updated = items.first().map(|i| i.created_at).unwrap_or(0);
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<feed xmlns=\"http://www.w3.org/2005/Atom\">\n");
+    xml.push_str(&format!("  <title>{}</title>\n", xml_escape(title)));
+    xml.push_str(&format!("  <id>{}</id>\n", xml_escape(feed_id)));
+    xml.push_str(&format!("  <updated>{}</updated>\n", rfc3339(updated)));
+
+    for item in items {
+        xml.push_str("  <entry>\n");
+        xml.push_str(&format!("    <title>{}</title>\n", xml_escape(&item.title)));
+        xml.push_str(&format!("    <id>{}:poll:{}</id>\n", xml_escape(feed_id), item.id));
+        if let Some(link) = &item.link {
+            xml.push_str(&format!("    <link href=\"{}\"/>\n", xml_escape(link)));
+        }
+        xml.push_str(&format!("    <updated>{}</updated>\n", rfc3339(item.created_at)));
+        xml.push_str(&format!("    <category term=\"{}\"/>\n", xml_escape(&item.category)));
+        xml.push_str(&format!("    <content type=\"text\">{}</content>\n", xml_escape(&item.body)));
+        xml.push_str("  </entry>\n");
+    }
+
+    xml.push_str("</feed>\n");
+    xml
+}
+
+impl<M: Middleware + 'static> PollManager<M> {
+    pub async fn generate_feed(
+        &self,
+        format: &str,
+        category: Option<String>,
+        limit: usize,
+        link_template: Option<String>,
+        output_path: Option<String>,
+    ) -> Result<()> {
+        let poll_ids: Vec<U256> = match &category {
+            Some(category) => self.contract.get_polls_by_category(category_to_u8(category)?).call().await?,
+            None => {
+                let poll_count = self.contract.poll_count().call().await?;
+                (0..poll_count.to_u64_saturating()).map(U256::from).collect()
+            }
+        };
+
+        let mut items = Vec::new();
+        for poll_id in poll_ids {
+            let poll = self.poll_data(poll_id).await?;
+            let options = poll.2.iter()
+                .enumerate()
+                .map(|(i, o)| format!("{}. {}", i, o))
+                .collect::<Vec<_>>()
+                .join("\n");
+            let link = link_template.as_ref().map(|t| t.replace("{id}", &poll_id.to_string()));
+
+            let mut body = String::new();
+            if !poll.12.is_empty() {
+                body.push_str(&format!("{}\n\n", poll.12));
+            }
+            body.push_str(&format!("Options:\n{}", options));
+            if let Some(link) = &link {
+                body.push_str(&format!("\n\nVote: {}", link));
+            }
+
+            items.push(FeedItem {
+                id: poll_id.to_u64_saturating(),
+                title: poll.1.clone(),
+                body,
+                link,
+                category: u8_to_category(poll.8).to_string(),
+                created_at: poll.4.to_u64_saturating(),
+            });
+        }
+
+        // Newest polls first
+        items.sort_by_key(|item| std::cmp::Reverse(item.created_at));
+        items.truncate(limit);
+
+        let title = match &category {
+            Some(category) => format!("{} Polls", u8_to_category(category_to_u8(category)?)),
+            None => "All Polls".to_string(),
+        };
+        let feed_id = format!("urn:polling-cli:{}:{:?}", self.wallet.chain_id(), self.contract.address());
+
+        let xml = match format.to_lowercase().as_str() {
+            "rss" => render_rss(&title, &items),
+            "atom" => render_atom(&title, &feed_id, &items),
+            _ => bail_user!("Unsupported feed format: {}. Use rss or atom", format),
+        };
+
+        if let Some(path) = output_path {
+            std::fs::write(&path, &xml)?;
+            say!("{} {}", "✅ Feed written to:".green().bold(), path.yellow());
+        } else {
+            print!("{}", xml);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,174 @@
+//! C API for embedding the client in other programs, behind the `ffi` feature. Build the library
+//! with `cargo rustc --release --lib --features ffi --crate-type cdylib` (or `staticlib`) and
+//! include `include/polling_cli.h`.
+//!
+//! Functions that fail return NULL and leave a message for `polling_last_error` on the calling
+//! thread. Strings returned to C are owned by the caller and freed with `polling_string_free`.
+
+use std::cell::RefCell;
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use anyhow::{Context, Result};
+use ethers::types::Bytes;
+use serde_json::Value;
+
+use crate::client::PollClient;
+
+/// A connected client with the runtime its calls block on
+pub struct PollingClient {
+    runtime: tokio::runtime::Runtime,
+    client: PollClient,
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn set_last_error(message: String) {
+    let message = CString::new(message.replace('\0', " ")).unwrap_or_default();
+    LAST_ERROR.with(|last| *last.borrow_mut() = Some(message));
+}
+
+/// Run `f`, turning errors and panics into NULL plus a `polling_last_error` message; a panic
+/// must not unwind into C
+fn guarded<T>(f: impl FnOnce() -> Result<*mut T>) -> *mut T {
+    match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(value)) => value,
+        Ok(Err(e)) => {
+            set_last_error(format!("{:#}", e));
+            std::ptr::null_mut()
+        }
+        Err(_) => {
+            set_last_error("internal error (panic)".to_string());
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// An optional UTF-8 argument; NULL is `None`
+///
+/// # Safety
+/// `ptr` is NULL or a NUL-terminated string.
+unsafe fn optional_str(ptr: *const c_char, name: &str) -> Result<Option<String>> {
+    if ptr.is_null() {
+        return Ok(None);
+    }
+    let text = CStr::from_ptr(ptr).to_str().with_context(|| format!("{} is not valid UTF-8", name))?;
+    Ok(Some(text.to_string()))
+}
+
+/// # Safety
+/// `client` is NULL or a pointer from `polling_client_new` that has not been freed.
+unsafe fn client_ref<'a>(client: *const PollingClient) -> Result<&'a PollingClient> {
+    client.as_ref().context("client is NULL")
+}
+
+fn json_string(value: &Value) -> Result<*mut c_char> {
+    Ok(CString::new(value.to_string())?.into_raw())
+}
+
+/// Connect to `contract_address` through `rpc_url`. Either may be NULL to use RPC_URL and
+/// CONTRACT_ADDRESS from the environment. Returns NULL on failure.
+///
+/// # Safety
+/// Both arguments are NULL or NUL-terminated strings.
+#[no_mangle]
+pub unsafe extern "C" fn polling_client_new(rpc_url: *const c_char, contract_address: *const c_char) -> *mut PollingClient {
+    guarded(|| {
+        let rpc_url = optional_str(rpc_url, "rpc_url")?;
+        let contract_address = optional_str(contract_address, "contract_address")?;
+        let runtime = tokio::runtime::Builder::new_multi_thread().enable_all().build()?;
+        let client = runtime.block_on(PollClient::connect(rpc_url, contract_address))?;
+        Ok(Box::into_raw(Box::new(PollingClient { runtime, client })))
+    })
+}
+
+/// Disconnect and free a client. NULL is ignored.
+///
+/// # Safety
+/// `client` is NULL or a pointer from `polling_client_new` that has not been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn polling_client_free(client: *mut PollingClient) {
+    if !client.is_null() {
+        drop(Box::from_raw(client));
+    }
+}
+
+/// A poll with its results as JSON, shaped like `export -f json`. Returns NULL on failure.
+///
+/// # Safety
+/// `client` is a live pointer from `polling_client_new`.
+#[no_mangle]
+pub unsafe extern "C" fn polling_poll_json(client: *const PollingClient, poll_id: u64) -> *mut c_char {
+    guarded(|| {
+        let client = client_ref(client)?;
+        json_string(&client.runtime.block_on(client.client.poll(poll_id))?)
+    })
+}
+
+/// The transaction a wallet should sign to vote, as JSON: `{"to", "data", "chain_id"}`. Nonce,
+/// gas and fees are left to the wallet. Returns NULL on failure.
+///
+/// # Safety
+/// `client` is a live pointer from `polling_client_new`.
+#[no_mangle]
+pub unsafe extern "C" fn polling_vote_request(client: *const PollingClient, poll_id: u64, option_index: u64) -> *mut c_char {
+    guarded(|| json_string(&client_ref(client)?.client.vote_request(poll_id, option_index)))
+}
+
+/// Broadcast a signed `vote` transaction (`0x`-prefixed hex of its raw bytes) and wait until it is
+/// mined. Returns the receipt as JSON (`transaction_hash`, `block_number`, `success`, `voter`,
+/// `poll_id`, `option_index`), or NULL on failure.
+///
+/// # Safety
+/// `client` is a live pointer from `polling_client_new`; `signed_tx` is a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn polling_send_vote(client: *const PollingClient, signed_tx: *const c_char) -> *mut c_char {
+    guarded(|| {
+        let client = client_ref(client)?;
+        let signed_tx = optional_str(signed_tx, "signed_tx")?.context("signed_tx is NULL")?;
+        let raw: Bytes = signed_tx.trim().parse().map_err(|e| anyhow::anyhow!("signed_tx is not hex: {}", e))?;
+        json_string(&client.runtime.block_on(client.client.send_signed_vote(raw))?)
+    })
+}
+
+/// Why the last call on this thread returned NULL, or NULL if none has failed. The string is
+/// valid until the next failing call on the same thread and must not be freed.
+#[no_mangle]
+pub extern "C" fn polling_last_error() -> *const c_char {
+    LAST_ERROR.with(|last| last.borrow().as_ref().map_or(std::ptr::null(), |message| message.as_ptr()))
+}
+
+/// Free a string returned by this library. NULL is ignored.
+///
+/// # Safety
+/// `value` is NULL or a string returned by this library that has not been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn polling_string_free(value: *mut c_char) {
+    if !value.is_null() {
+        drop(CString::from_raw(value));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn failures_return_null_with_a_message() {
+        unsafe {
+            assert!(polling_poll_json(std::ptr::null(), 0).is_null());
+            let message = CStr::from_ptr(polling_last_error()).to_str().unwrap();
+            assert_eq!(message, "client is NULL");
+
+            let not_utf8 = b"\xff\0";
+            let client = polling_client_new(not_utf8.as_ptr().cast(), std::ptr::null());
+            assert!(client.is_null());
+            assert!(CStr::from_ptr(polling_last_error()).to_str().unwrap().contains("rpc_url is not valid UTF-8"));
+
+            polling_client_free(std::ptr::null_mut());
+            polling_string_free(json_string(&serde_json::json!({ "ok": true })).unwrap());
+        }
+    }
+}
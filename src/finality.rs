@@ -0,0 +1,170 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use anyhow::Result;
+use colored::*;
+use ethers::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::tally::{OptionTally, PollTally};
+use crate::{cache, numbers, PollManager};
+
+/// Whether the votes still to come could change an active poll's winner
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Finality {
+    pub leader: String,
+    pub runner_up: Option<String>,
+    /// Lead over the runner-up under the deciding metric (votes, or weight for weighted polls)
+    #[serde(with = "numbers::decimal")]
+    pub margin: U256,
+    /// Most the holders who haven't voted could still add
+    #[serde(with = "numbers::decimal")]
+    pub outstanding: U256,
+    /// The margin is smaller than what non-voters hold, so the outcome could still flip
+    pub contested: bool,
+    /// What margin and outstanding count: votes, or weight for weighted polls
+    pub metric: String,
+}
+
+/// The holders who could still vote on a poll
+#[derive(Debug, Clone, PartialEq)]
+pub struct Electorate {
+    pub balances: BTreeMap<Address, U256>,
+    /// The poll is token-gated; only then does EnhancedPolls weigh votes by balance
+    pub requires_token: bool,
+}
+
+/// Weight a holder's vote would carry, as EnhancedPolls computes it from an 18-decimal balance:
+/// the whole-token balance for weighted and its square root for quadratic token-gated polls, otherwise 1
+pub fn potential_weight(poll_type: u8, requires_token: bool, balance: U256) -> U256 {
+    if !requires_token {
+        return U256::one();
+    }
+    let tokens = balance / U256::exp10(18);
+    let weight = match poll_type {
+        1 => tokens,
+        2 => tokens.integer_sqrt(),
+        _ => U256::one(),
+    };
+    weight.max(U256::one())
+}
+
+/// Weight the holders in `electorate` who are not among `voters` could still cast
+pub fn outstanding(poll_type: u8, electorate: &Electorate, voters: &BTreeSet<Address>) -> U256 {
+    electorate.balances.iter()
+        .filter(|(holder, _)| !voters.contains(holder))
+        .fold(U256::zero(), |total, (_, balance)| total.saturating_add(potential_weight(poll_type, electorate.requires_token, *balance)))
+}
+
+/// Compare the leader's margin with the `outstanding` weight; `None` before any votes
+pub fn assess(tally: &PollTally, outstanding: U256) -> Option<Finality> {
+    let deciding = |option: &OptionTally| if tally.weighted { option.weight } else { U256::from(option.votes) };
+    let ranked = tally.ranked();
+    let leader = *ranked.first().filter(|leader| !deciding(leader).is_zero())?;
+    let runner_up = ranked.get(1).copied();
+    let margin = deciding(leader).saturating_sub(runner_up.map(deciding).unwrap_or_default());
+    Some(Finality {
+        leader: leader.option.clone(),
+        runner_up: runner_up.map(|option| option.option.clone()),
+        margin,
+        outstanding,
+        contested: margin < outstanding,
+        metric: tally.winner_metric().to_string(),
+    })
+}
+
+/// The warning shown under an active poll's results
+pub fn print_finality(finality: &Finality) {
+    let (margin, outstanding) = (numbers::format_integer(finality.margin), numbers::format_integer(finality.outstanding));
+    let metric = &finality.metric;
+    if !finality.contested {
+        say!("{} {} leads by {} {}, more than the {} non-voters still hold",
+            "🔒 Decided:".green().bold(), finality.leader, margin, metric, outstanding);
+        return;
+    }
+    match &finality.runner_up {
+        Some(runner_up) if finality.margin.is_zero() => say!("{} {} and {} are tied; non-voters still hold {} {}",
+            "⚠️  Contested:".yellow().bold(), finality.leader, runner_up, outstanding, metric),
+        Some(runner_up) => say!("{} {} leads {} by {} {}, but non-voters still hold {}; the outcome could still flip",
+            "⚠️  Contested:".yellow().bold(), finality.leader, runner_up, margin, metric, outstanding),
+        None => say!("{} {} leads, but non-voters still hold {} {}",
+            "⚠️  Contested:".yellow().bold(), finality.leader, outstanding, metric),
+    }
+}
+
+/// Token whose holders make up a poll's electorate: the governance token for open polls, and for
+/// token-gated ones only when the poll gates on that same token; `None` when no electorate can be vouched for
+fn electorate_token(requires_token: bool, poll_token: Address, governance: Option<Address>) -> Option<Address> {
+    let governance = governance?;
+    (!requires_token || poll_token == governance).then_some(governance)
+}
+
+impl<M: Middleware + 'static> PollManager<M> {
+    /// Balances of the holders who could vote on `poll_id`, at the pinned block (or the latest one);
+    /// holders below a token-gated poll's minTokenBalance are left out, as the contract would refuse them
+    pub(crate) async fn electorate(&self, poll_id: u64) -> Result<Option<Electorate>> {
+        let governance = self.governance_token.as_ref().map(|token| token.address());
+        // getPoll leaves out the token gate; the public polls() getter has it
+        let poll = self.at_block(self.contract.polls(U256::from(poll_id))).call().await?;
+        let (requires_token, poll_token, min_balance) = (poll.11, poll.12, poll.13);
+        let Some(token) = electorate_token(requires_token, poll_token, governance) else { return Ok(None) };
+        let block = match self.pinned {
+            Some(pinned) => pinned.number,
+            None => self.client.get_block_number().await?.as_u64(),
+        };
+        let mut balances = self.holder_balances(token, block).await?;
+        if requires_token {
+            balances.retain(|_, balance| *balance >= min_balance);
+        }
+        Ok(Some(Electorate { balances, requires_token }))
+    }
+
+    /// Finality of an active poll against the holders in `electorate`; `None` before any votes
+    pub(crate) async fn finality(&self, poll_id: u64, poll_data: &cache::PollData, tally: &PollTally, electorate: &Electorate) -> Result<Option<Finality>> {
+        let voters: BTreeSet<Address> = self.fetch_vote_events(poll_id).await?.into_iter().map(|vote| vote.voter).collect();
+        Ok(assess(tally, outstanding(poll_data.7, electorate, &voters)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokens(amount: u64) -> U256 {
+        U256::from(amount) * U256::exp10(18)
+    }
+
+    #[test]
+    fn flags_margins_non_voters_could_overturn() {
+        assert_eq!(potential_weight(0, true, tokens(50)), U256::one());
+        assert_eq!(potential_weight(1, true, tokens(50)), U256::from(50));
+        assert_eq!(potential_weight(2, true, tokens(50)), U256::from(7));
+        assert_eq!(potential_weight(2, false, tokens(50)), U256::one(), "ungated polls weigh every vote 1");
+        assert_eq!(potential_weight(1, true, U256::exp10(17)), U256::one(), "dust still votes with weight 1");
+
+        let (alice, bob, carol) = (Address::repeat_byte(1), Address::repeat_byte(2), Address::repeat_byte(3));
+        let mut electorate = Electorate {
+            balances: BTreeMap::from([(alice, tokens(40)), (bob, tokens(9)), (carol, tokens(25))]),
+            requires_token: true,
+        };
+        assert_eq!(outstanding(1, &electorate, &BTreeSet::from([alice])), U256::from(34));
+        assert_eq!(outstanding(2, &electorate, &BTreeSet::from([alice])), U256::from(8));
+        electorate.requires_token = false;
+        assert_eq!(outstanding(2, &electorate, &BTreeSet::from([alice])), U256::from(2));
+
+        let options = ["Yes".to_string(), "No".to_string()];
+        let tally = PollTally::new(&options, &[U256::from(60), U256::from(30)], &[3, 2], 5, U256::from(90), true);
+        let contested = assess(&tally, U256::from(34)).unwrap();
+        assert_eq!((contested.leader.as_str(), contested.runner_up.as_deref(), contested.margin), ("Yes", Some("No"), U256::from(30)));
+        assert!(contested.contested);
+        assert!(!assess(&tally, U256::from(8)).unwrap().contested);
+
+        let empty = PollTally::new(&options, &[U256::zero(), U256::zero()], &[0, 0], 0, U256::zero(), false);
+        assert_eq!(assess(&empty, U256::from(10)), None);
+
+        let (governance, other) = (Address::repeat_byte(7), Address::repeat_byte(8));
+        assert_eq!(electorate_token(false, Address::zero(), Some(governance)), Some(governance));
+        assert_eq!(electorate_token(true, governance, Some(governance)), Some(governance));
+        assert_eq!(electorate_token(true, other, Some(governance)), None, "gated on a token we don't track");
+        assert_eq!(electorate_token(false, Address::zero(), None), None);
+    }
+}
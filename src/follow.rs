@@ -0,0 +1,121 @@
+use std::io::Write;
+use std::time::Duration;
+
+use anyhow::Result;
+use colored::*;
+use ethers::prelude::*;
+
+use crate::error::bail_user;
+use crate::numbers::SaturatingU64;
+use crate::{duration, output, timezone, PollManager};
+
+/// How often the chain is checked for new votes between scheduled refreshes
+const VOTE_CHECK_SECS: u64 = 3;
+
+/// Clear the screen and move the cursor home
+const CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+
+/// The countdown line shown under live results
+fn countdown_line(end_time: u64, now: u64, interval: u64) -> String {
+    format!("{} {}   {}",
+        "⏳ Closes in".cyan().bold(),
+        duration::format(end_time.saturating_sub(now)).yellow().bold(),
+        format!("(refreshing every {} and on each vote; Ctrl-C to stop)", duration::format(interval)).dimmed())
+}
+
+impl<M: Middleware + 'static> PollManager<M> {
+    /// Drop cached poll data and the synced event index so the next read is current
+    pub(crate) fn forget_reads(&self, poll_id: u64) {
+        self.cache.invalidate(poll_id);
+        if let Ok(mut index) = self.index.lock() {
+            *index = None;
+        }
+    }
+
+    /// Whether anyone voted on `poll_id` in `from..=to`
+    async fn votes_between(&self, poll_id: u64, from: u64, to: u64) -> Result<bool> {
+        if from > to {
+            return Ok(false);
+        }
+        let votes = self.contract.vote_cast_filter()
+            .topic1(U256::from(poll_id))
+            .from_block(from)
+            .to_block(to)
+            .query()
+            .await?;
+        Ok(!votes.is_empty())
+    }
+
+    /// `results --follow`: redraw the results every `interval` seconds or as soon as a vote lands,
+    /// with a countdown, until the poll closes
+    pub async fn follow_results(&self, poll_id: u64, interval: u64) -> Result<()> {
+        if interval == 0 {
+            bail_user!("--interval must be longer than zero");
+        }
+        let live = !output::is_plain();
+        let mut checked_to = self.client.get_block_number().await?.as_u64();
+        let mut electorate = None;
+
+        loop {
+            self.forget_reads(poll_id);
+            let poll_data = self.poll_data(U256::from(poll_id)).await?;
+            let tally = self.poll_tally(poll_id, &poll_data.2, poll_data.7).await?;
+            let finality = self.results_finality(poll_id, &poll_data, &tally, &mut electorate).await;
+            let end_time = poll_data.5.to_u64_saturating();
+
+            if live {
+                std::print!("{}", CLEAR_SCREEN);
+            }
+            self.print_results(&poll_data, &tally, finality.as_ref());
+            say!("Updated: {}", timezone::format(chrono::Utc::now().timestamp() as u64));
+
+            let now = chrono::Utc::now().timestamp() as u64;
+            if poll_data.6 != 0 || now >= end_time {
+                say!("\n{}", "🏁 Voting has ended; these are the final results.".green().bold());
+                return Ok(());
+            }
+            if !live {
+                say!("{}", countdown_line(end_time, now, interval));
+            }
+
+            // Tick the countdown each second; check for votes every few seconds
+            let mut waited = 0;
+            while waited < interval {
+                let now = chrono::Utc::now().timestamp() as u64;
+                if now >= end_time {
+                    break;
+                }
+                if live {
+                    let mut stdout = std::io::stdout();
+                    let _ = write!(stdout, "\r\x1b[2K{}", countdown_line(end_time, now, interval));
+                    let _ = stdout.flush();
+                }
+                tokio::select! {
+                    _ = tokio::time::sleep(Duration::from_secs(1)) => {}
+                    _ = tokio::signal::ctrl_c() => {
+                        println!();
+                        return Ok(());
+                    }
+                }
+                waited += 1;
+
+                if waited % VOTE_CHECK_SECS == 0 {
+                    let head = match self.client.get_block_number().await {
+                        Ok(head) => head.as_u64(),
+                        Err(_) => continue,
+                    };
+                    // Keep following through RPC hiccups; the scheduled refresh still happens
+                    match self.votes_between(poll_id, checked_to + 1, head).await {
+                        Ok(voted) => {
+                            checked_to = head;
+                            if voted {
+                                break;
+                            }
+                        }
+                        Err(_) => continue,
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,203 @@
+use anyhow::{Context, Result};
+use colored::*;
+use ethers::prelude::*;
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::chart::{self, Series};
+use crate::error::{bail_config, bail_user, CliError};
+use crate::{poll_ref, redact, render_markdown, u256_to_f64, PollExport, PollManager};
+
+/// Width of the text bar chart in a post, in character cells
+const CHART_WIDTH: usize = 40;
+
+/// Forum software a results post is written for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ForumTarget {
+    /// Discourse; can also be published through its API with --publish
+    Discourse,
+    /// Commonwealth; rendered only, to paste into a thread
+    Commonwealth,
+}
+
+/// A rendered results post
+#[derive(Debug)]
+pub struct ForumPost {
+    pub title: String,
+    pub body: String,
+}
+
+/// The results as a forum post: the summary and table from `export --format markdown`, a bar
+/// chart in a code block, and a link back to the poll
+pub fn render_post(export: &PollExport, link: &str, target: ForumTarget) -> ForumPost {
+    let title = format!("Results: Poll #{} - {}", export.id, export.question);
+    let weighted = export.winner_metric == "weight";
+    let points = export.options.iter().enumerate()
+        .map(|(i, option)| {
+            let value = if weighted {
+                export.weights.get(i).copied().map(u256_to_f64).unwrap_or(0.0)
+            } else {
+                export.votes.get(i).copied().unwrap_or(0) as f64
+            };
+            (option.clone(), value)
+        })
+        .collect();
+    let series = Series { title: export.question.clone(), metric: if weighted { "weight" } else { "votes" }, points };
+
+    let mut body = render_markdown(export);
+    body.push_str(&format!("\n## Results by {}\n\n", series.metric));
+    // Commonwealth's editor does not render block characters evenly, so it gets the ASCII chart
+    body.push_str("```text\n");
+    for line in chart::terminal_bars(&series, CHART_WIDTH, target == ForumTarget::Commonwealth) {
+        body.push_str(line.trim_end());
+        body.push('\n');
+    }
+    body.push_str("```\n\n");
+    body.push_str(&format!("Verify on-chain: `{}`\n", link));
+    ForumPost { title, body }
+}
+
+/// A Discourse instance configured through DISCOURSE_URL, DISCOURSE_API_KEY, DISCOURSE_API_USERNAME
+/// and DISCOURSE_CATEGORY_ID
+#[derive(Debug, Clone)]
+pub struct DiscourseClient {
+    url: String,
+    api_key: String,
+    username: String,
+    category: Option<u64>,
+    http: reqwest::Client,
+}
+
+#[derive(Deserialize)]
+struct CreatedPost {
+    topic_id: u64,
+    topic_slug: String,
+    post_number: u64,
+}
+
+impl DiscourseClient {
+    pub fn from_env() -> Result<Self> {
+        let var = |name: &str| std::env::var(name).ok().filter(|value| !value.is_empty());
+        let Some(url) = var("DISCOURSE_URL") else {
+            bail_config!("DISCOURSE_URL must be set to publish to Discourse, e.g. https://forum.example.org");
+        };
+        let Some(api_key) = var("DISCOURSE_API_KEY") else {
+            bail_config!("DISCOURSE_API_KEY must be set to publish to Discourse");
+        };
+        redact::register(&api_key);
+        let category = match var("DISCOURSE_CATEGORY_ID") {
+            Some(id) => Some(id.parse()
+                .map_err(|_| CliError::Config(format!("Invalid DISCOURSE_CATEGORY_ID '{}'; expected a number", id)))?),
+            None => None,
+        };
+        Ok(Self {
+            url: url.trim_end_matches('/').to_string(),
+            api_key,
+            username: var("DISCOURSE_API_USERNAME").unwrap_or_else(|| "system".to_string()),
+            category,
+            http: reqwest::Client::new(),
+        })
+    }
+
+    /// Start a topic with the post, or reply to `topic_id`; returns the post's URL
+    pub async fn create_post(&self, post: &ForumPost, topic_id: Option<u64>) -> Result<String> {
+        let mut payload = json!({ "raw": post.body });
+        match topic_id {
+            Some(topic_id) => payload["topic_id"] = json!(topic_id),
+            None => {
+                payload["title"] = json!(post.title);
+                if let Some(category) = self.category {
+                    payload["category"] = json!(category);
+                }
+            }
+        }
+
+        let response = self.http.post(format!("{}/posts.json", self.url))
+            .header("Api-Key", &self.api_key)
+            .header("Api-Username", &self.username)
+            .json(&payload)
+            .send()
+            .await
+            .context("Failed to reach Discourse")?;
+        let status = response.status();
+        if !status.is_success() {
+            let text = response.text().await.unwrap_or_default();
+            return Err(CliError::Rpc(format!("Discourse returned {}: {}", status, text)).into());
+        }
+        let created: CreatedPost = response.json().await.context("Discourse returned an unexpected response")?;
+        Ok(format!("{}/t/{}/{}/{}", self.url, created.topic_slug, created.topic_id, created.post_number))
+    }
+}
+
+impl<M: Middleware + 'static> PollManager<M> {
+    /// `post-results`: render a finished poll's results for a forum, and optionally publish them
+    pub async fn post_results(&self, poll_id: u64, target: ForumTarget, publish: bool, topic_id: Option<u64>, output: Option<String>) -> Result<()> {
+        if publish && target != ForumTarget::Discourse {
+            bail_user!("--publish only supports Discourse; paste the {:?} post yourself", target);
+        }
+        if topic_id.is_some() && !publish {
+            bail_user!("--topic-id needs --publish");
+        }
+        // Check the configuration before reading the chain
+        let discourse = if publish { Some(DiscourseClient::from_env()?) } else { None };
+
+        let export = self.build_poll_export(poll_id).await?;
+        if export.is_active && export.end_time_unix > self.now() {
+            bail_user!("Poll #{} is still running until {}; post results once they are final", poll_id, export.end_time);
+        }
+        let link = poll_ref::link(self.wallet.chain_id(), self.contract.address(), poll_id);
+        let post = render_post(&export, &link, target);
+
+        if let Some(path) = &output {
+            std::fs::write(path, &post.body)?;
+            say!("{} {}", "✅ Post written to:".green().bold(), path.yellow());
+        }
+        match discourse {
+            Some(discourse) => {
+                say!("{} {} {}", "📣 Posting results of poll".cyan().bold(), poll_id.to_string().yellow(), "to Discourse...".cyan().bold());
+                let url = discourse.create_post(&post, topic_id).await?;
+                say!("{}", "✅ Results posted!".green().bold());
+                say!("{} {}", "URL:".cyan().bold(), url.white());
+            }
+            None if output.is_none() => {
+                println!("{}\n", post.title);
+                println!("{}", post.body.trim_end());
+            }
+            None => {}
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_table_chart_and_link() {
+        let export = PollExport {
+            id: 7,
+            question: "Fund the grant?".to_string(),
+            creator: "0x0000000000000000000000000000000000000001".to_string(),
+            created_at: "2026-01-01 00:00:00 UTC".to_string(),
+            end_time: "2026-01-08 00:00:00 UTC".to_string(),
+            created_at_unix: 1_767_225_600,
+            end_time_unix: 1_767_830_400,
+            is_active: false,
+            poll_type: "Simple".to_string(),
+            winner_metric: "votes".to_string(),
+            total_votes: 4,
+            total_weight: U256::from(4),
+            options: vec!["Yes".to_string(), "No".to_string()],
+            votes: vec![3, 1],
+            weights: vec![U256::from(3), U256::from(1)],
+            min_participation: 0,
+            quorum_status: crate::quorum::QuorumState::NotRequired,
+        };
+        let post = render_post(&export, "poll://1/0x00/7", ForumTarget::Commonwealth);
+        assert_eq!(post.title, "Results: Poll #7 - Fund the grant?");
+        assert!(post.body.contains("| 0 | Yes | 3 | 75.0% |"));
+        assert!(post.body.contains("```text\nYes |"), "ASCII chart for Commonwealth:\n{}", post.body);
+        assert!(post.body.ends_with("Verify on-chain: `poll://1/0x00/7`\n"));
+    }
+}
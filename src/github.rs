@@ -0,0 +1,245 @@
+use std::collections::BTreeMap;
+
+use anyhow::{Context, Result};
+use colored::*;
+use ethers::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::drafts;
+use crate::error::{bail_config, bail_user, CliError};
+use crate::forum::{self, ForumTarget};
+use crate::spec::PollFields;
+use crate::{lint, output, poll_ref, redact, store, PollManager};
+
+const GITHUB_FILE: &str = "github.json";
+
+/// The GitHub REST API, authenticated with GITHUB_TOKEN
+struct GithubClient {
+    api_url: String,
+    token: String,
+    http: reqwest::Client,
+}
+
+/// A proposal issue that became a draft, and what has been posted back to it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ImportedIssue {
+    repo: String,
+    issue: u64,
+    url: String,
+    /// The poll whose link was commented on the issue
+    announced_poll: Option<u64>,
+    results_posted: bool,
+}
+
+/// Imported issues by draft ID
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct GithubStore {
+    imports: BTreeMap<u64, ImportedIssue>,
+}
+
+#[derive(Deserialize)]
+struct Issue {
+    title: String,
+    body: Option<String>,
+    html_url: String,
+    #[serde(default)]
+    labels: Vec<Label>,
+    /// Set when the "issue" is a pull request
+    pull_request: Option<Value>,
+}
+
+#[derive(Deserialize)]
+struct Label {
+    name: String,
+}
+
+impl GithubClient {
+    fn from_env() -> Result<Self> {
+        let Some(token) = std::env::var("GITHUB_TOKEN").ok().filter(|token| !token.is_empty()) else {
+            bail_config!("GITHUB_TOKEN must be set to a token that can read and comment on the repository's issues");
+        };
+        redact::register(&token);
+        let api_url = std::env::var("GITHUB_API_URL").unwrap_or_else(|_| "https://api.github.com".to_string());
+        Ok(Self { api_url: api_url.trim_end_matches('/').to_string(), token, http: reqwest::Client::new() })
+    }
+
+    async fn request(&self, request: reqwest::RequestBuilder, what: &str) -> Result<Value> {
+        let response = request
+            .bearer_auth(&self.token)
+            .header("Accept", "application/vnd.github+json")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .header("User-Agent", "polling-cli")
+            .send()
+            .await
+            .context("Failed to reach GitHub")?;
+        let status = response.status();
+        let body: Value = response.json().await.context("GitHub returned invalid JSON")?;
+        if !status.is_success() {
+            let message = body["message"].as_str().unwrap_or("unknown error");
+            return Err(CliError::Rpc(format!("GitHub {} failed ({}): {}", what, status, message)).into());
+        }
+        Ok(body)
+    }
+
+    async fn issue(&self, repo: &str, number: u64) -> Result<Issue> {
+        let url = format!("{}/repos/{}/issues/{}", self.api_url, repo, number);
+        let issue = self.request(self.http.get(url), "issue lookup").await?;
+        serde_json::from_value(issue).context("GitHub returned an unexpected issue")
+    }
+
+    /// Comment on an issue; returns the comment's URL
+    async fn comment(&self, repo: &str, number: u64, body: &str) -> Result<String> {
+        let url = format!("{}/repos/{}/issues/{}/comments", self.api_url, repo, number);
+        let comment = self.request(self.http.post(url).json(&json!({ "body": body })), "comment").await?;
+        Ok(comment["html_url"].as_str().unwrap_or_default().to_string())
+    }
+}
+
+/// `owner/name`, as GitHub writes repositories
+fn check_repo(repo: &str) -> Result<()> {
+    match repo.split_once('/') {
+        Some((owner, name)) if !owner.is_empty() && !name.is_empty() && !name.contains('/') => Ok(()),
+        _ => bail_user!("--repo must look like owner/name, not '{}'", repo),
+    }
+}
+
+/// Poll fields from an issue: the title is the question, task-list items (`- [ ] Option`) are
+/// the options, the body is the description and labels are tags
+fn issue_fields(issue: &Issue) -> PollFields {
+    let body = issue.body.as_deref().unwrap_or_default().replace("\r\n", "\n");
+    let options: Vec<&str> = body.lines()
+        .filter_map(|line| {
+            let item = line.trim_start().strip_prefix("- ").or_else(|| line.trim_start().strip_prefix("* "))?;
+            let item = item.strip_prefix("[ ]").or_else(|| item.strip_prefix("[x]")).or_else(|| item.strip_prefix("[X]"))?;
+            Some(item.trim()).filter(|item| !item.is_empty())
+        })
+        .collect();
+    let description = match body.trim() {
+        "" => format!("Proposal: {}", issue.html_url),
+        body => format!("{}\n\nProposal: {}", body, issue.html_url),
+    };
+    let tags: Vec<&str> = issue.labels.iter().map(|label| label.name.as_str()).collect();
+    PollFields {
+        question: Some(issue.title.trim().to_string()),
+        options: (!options.is_empty()).then(|| options.join(",")),
+        description: Some(description),
+        tags: (!tags.is_empty()).then(|| tags.join(",")),
+        ..Default::default()
+    }
+}
+
+/// `github import`: turn a proposal issue into a poll draft and say so on the issue.
+/// Flags override what is read from the issue.
+pub async fn import_issue(repo: &str, number: u64, fields: PollFields) -> Result<()> {
+    check_repo(repo)?;
+    let client = GithubClient::from_env()?;
+    let mut book: GithubStore = store::load(GITHUB_FILE)?;
+    if let Some((id, _)) = book.imports.iter().find(|(_, import)| import.repo == repo && import.issue == number) {
+        bail_user!("{}#{} was already imported as draft #{}", repo, number, id);
+    }
+
+    let issue = client.issue(repo, number).await?;
+    if issue.pull_request.is_some() {
+        bail_user!("{}#{} is a pull request; import proposal issues", repo, number);
+    }
+    let fields = fields.load_file()?.or(issue_fields(&issue));
+    if fields.options.is_none() {
+        bail_user!("{}#{} has no task-list options (`- [ ] Option`); pass --options", repo, number);
+    }
+    let spec = fields.resolve(None)?;
+    let warnings = lint::lint(&spec, spec.duration_seconds().unwrap_or_default(), None);
+    let id = drafts::add_draft(spec)?;
+    book.imports.insert(id, ImportedIssue { repo: repo.to_string(), issue: number, url: issue.html_url.clone(), announced_poll: None, results_posted: false });
+    store::save(GITHUB_FILE, &book)?;
+
+    say!("{} {} {} {}", "📝 Created draft".green().bold(), format!("#{}", id).yellow(), "from".green().bold(), issue.html_url);
+    for warning in &warnings {
+        say!("{} {}", "⚠️ ".yellow(), warning);
+    }
+    let comment = format!("📝 This proposal is queued as poll draft #{}. It goes on-chain once reviewers approve it; the poll link will be posted here.", id);
+    match client.comment(repo, number, &comment).await {
+        Ok(url) => say!("{} {}", "💬 Commented:".cyan().bold(), url),
+        Err(e) => eprintln!("{} {:#}", output::render("⚠️  Could not comment on the issue:".to_string()).yellow(), e),
+    }
+    say!("Review it with `draft show {}`, then `draft approve` and `draft submit`; `github sync` posts the link and results back.", id);
+    Ok(())
+}
+
+impl<M: Middleware + 'static> PollManager<M> {
+    /// `github sync`: comment the poll link on imported issues whose drafts were submitted, and
+    /// the results once the poll has ended
+    pub async fn github_sync(&self) -> Result<()> {
+        let mut book: GithubStore = store::load(GITHUB_FILE)?;
+        if book.imports.is_empty() {
+            say!("No imported issues yet. Start with `github import --repo owner/name --issue <number>`.");
+            return Ok(());
+        }
+        let client = GithubClient::from_env()?;
+        let chain_id = self.wallet.chain_id();
+        let mut posted = 0;
+
+        for (draft_id, import) in book.imports.clone() {
+            if import.results_posted {
+                continue;
+            }
+            let Some(poll_id) = drafts::get_draft(draft_id)?.submitted_poll_id else {
+                continue;
+            };
+            let link = poll_ref::link(chain_id, self.contract.address(), poll_id);
+            let export = self.build_poll_export(poll_id).await?;
+
+            if import.announced_poll.is_none() {
+                let comment = format!("🗳️ Voting is open: draft #{} is poll #{}, ending {}.\n\nVote with `vote -p {} -o <option>`.",
+                    draft_id, poll_id, export.end_time, link);
+                client.comment(&import.repo, import.issue, &comment).await?;
+                say!("{} {}", "💬 Posted poll link to".cyan().bold(), import.url);
+                book.imports.get_mut(&draft_id).expect("imported above").announced_poll = Some(poll_id);
+                store::save(GITHUB_FILE, &book)?;
+                posted += 1;
+            }
+            if export.is_active && export.end_time_unix > self.now() {
+                continue;
+            }
+            let post = forum::render_post(&export, &link, ForumTarget::Discourse);
+            client.comment(&import.repo, import.issue, &post.body).await?;
+            say!("{} {}", "💬 Posted results to".cyan().bold(), import.url);
+            book.imports.get_mut(&draft_id).expect("imported above").results_posted = true;
+            store::save(GITHUB_FILE, &book)?;
+            posted += 1;
+        }
+
+        if posted == 0 {
+            say!("Nothing new to post.");
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_poll_fields_from_an_issue() {
+        let issue = Issue {
+            title: " Fund the docs sprint? ".to_string(),
+            body: Some("We want two weeks.\r\n\r\n- [ ] Fund it\r\n- [x] Fund half\r\n* [ ] Reject\r\n- plain bullet\r\n".to_string()),
+            html_url: "https://github.com/org/proposals/issues/123".to_string(),
+            labels: vec![Label { name: "grants".to_string() }, Label { name: "q3".to_string() }],
+            pull_request: None,
+        };
+        let spec = issue_fields(&issue).resolve(None).unwrap();
+        assert_eq!(spec.question, "Fund the docs sprint?");
+        assert_eq!(spec.options, ["Fund it", "Fund half", "Reject"]);
+        assert_eq!(spec.tags, ["grants", "q3"]);
+        assert!(spec.description.unwrap().ends_with("- plain bullet\n\nProposal: https://github.com/org/proposals/issues/123"));
+
+        let flags = PollFields { options: Some("Yes,No".to_string()), ..Default::default() };
+        assert_eq!(flags.or(issue_fields(&issue)).resolve(None).unwrap().options, ["Yes", "No"]);
+
+        assert!(check_repo("org/proposals").is_ok());
+        assert!(check_repo("proposals").is_err());
+        assert!(check_repo("org/proposals/extra").is_err());
+    }
+}
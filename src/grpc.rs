@@ -0,0 +1,248 @@
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use colored::*;
+use ethers::prelude::*;
+use tokio::net::TcpListener;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::{ReceiverStream, TcpListenerStream};
+use tonic::{Request, Response, Status};
+
+use crate::error::{self, bail_config, ErrorKind};
+use crate::events::VoteRecord;
+use crate::indexer::{ContractEvent, IndexedEvent};
+use crate::numbers::SaturatingU64;
+use crate::{output, poll_quorum, poll_ref, u8_to_category, u8_to_poll_type, u8_to_status, PollManager};
+
+/// Messages and service generated from `proto/polls.proto`
+pub mod proto {
+    tonic::include_proto!("polls.v1");
+}
+
+use proto::poll_service_server::{PollService, PollServiceServer};
+
+pub const DEFAULT_LISTEN: &str = "127.0.0.1:50051";
+
+/// How often `StreamEvents` checks for new blocks
+const EVENT_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Events buffered per stream before a slow client holds up its own stream
+const EVENT_BUFFER: usize = 64;
+
+/// The gRPC status for a failed request, by the same error classes the REST server uses
+fn status(e: anyhow::Error) -> Status {
+    let message = format!("{:#}", e);
+    match error::classify(&e) {
+        ErrorKind::User => Status::invalid_argument(message),
+        ErrorKind::Revert => Status::failed_precondition(message),
+        ErrorKind::Rpc => Status::unavailable(message),
+        ErrorKind::Config | ErrorKind::General => Status::internal(message),
+    }
+}
+
+fn vote_message(vote: &VoteRecord) -> proto::Vote {
+    proto::Vote {
+        poll_id: vote.poll_id,
+        voter: format!("{:?}", vote.voter),
+        option_index: vote.option_index,
+        weight: vote.weight.to_string(),
+        block_number: vote.block_number,
+        transaction_hash: format!("{:?}", vote.transaction_hash),
+        timestamp: vote.timestamp,
+    }
+}
+
+/// The streamed form of a poll event; delegation events are not about a poll and are left out
+fn event_message(event: IndexedEvent) -> Option<proto::Event> {
+    use proto::event::Kind;
+
+    let kind = match event.event {
+        ContractEvent::PollCreated { poll_id, creator, question, end_time, tags, .. } => {
+            Kind::PollCreated(proto::PollCreated { poll_id, creator: format!("{:?}", creator), question, end_time, tags })
+        }
+        ContractEvent::VoteCast { poll_id, voter, option_index, weight } => Kind::VoteCast(vote_message(&VoteRecord {
+            poll_id,
+            voter,
+            option_index,
+            weight,
+            block_number: event.block_number,
+            transaction_hash: event.transaction_hash,
+            timestamp: event.timestamp,
+        })),
+        ContractEvent::PollStatusChanged { poll_id, status } => {
+            Kind::StatusChanged(proto::StatusChanged { poll_id, status: u8_to_status(status).to_string() })
+        }
+        ContractEvent::PollExtended { poll_id, end_time } => Kind::PollExtended(proto::PollExtended { poll_id, end_time }),
+        ContractEvent::DelegateSet { .. } | ContractEvent::DelegateRemoved { .. } => return None,
+    };
+    Some(proto::Event {
+        block_number: event.block_number,
+        transaction_hash: format!("{:?}", event.transaction_hash),
+        kind: Some(kind),
+    })
+}
+
+struct Service<M> {
+    manager: Arc<PollManager<M>>,
+}
+
+#[tonic::async_trait]
+impl<M: Middleware + 'static> PollService for Service<M> {
+    async fn get_poll_count(&self, _: Request<proto::GetPollCountRequest>) -> Result<Response<proto::PollCount>, Status> {
+        let count = self.manager.contract.poll_count().call().await.map_err(|e| status(e.into()))?;
+        Ok(Response::new(proto::PollCount { count: count.to_u64_saturating() }))
+    }
+
+    async fn get_poll(&self, request: Request<proto::PollRequest>) -> Result<Response<proto::Poll>, Status> {
+        self.manager.poll_message(request.into_inner().poll_id).await.map(Response::new).map_err(status)
+    }
+
+    async fn get_results(&self, request: Request<proto::PollRequest>) -> Result<Response<proto::Results>, Status> {
+        self.manager.results_message(request.into_inner().poll_id).await.map(Response::new).map_err(status)
+    }
+
+    async fn list_votes(&self, request: Request<proto::PollRequest>) -> Result<Response<proto::VoteList>, Status> {
+        let mut votes = self.manager.fetch_vote_events(request.into_inner().poll_id).await.map_err(status)?;
+        votes.sort_by_key(|vote| vote.block_number);
+        Ok(Response::new(proto::VoteList { votes: votes.iter().map(vote_message).collect() }))
+    }
+
+    type StreamEventsStream = ReceiverStream<Result<proto::Event, Status>>;
+
+    async fn stream_events(&self, request: Request<proto::StreamEventsRequest>) -> Result<Response<Self::StreamEventsStream>, Status> {
+        let request = request.into_inner();
+        let next = match request.from_block {
+            Some(block) => block,
+            None => self.manager.client.get_block_number().await.map_err(|e| status(anyhow::anyhow!("{}", e)))?.as_u64() + 1,
+        };
+        let (sender, receiver) = mpsc::channel(EVENT_BUFFER);
+        let manager = self.manager.clone();
+        tokio::spawn(async move { manager.follow_events(next, request.poll_id, sender).await });
+        Ok(Response::new(ReceiverStream::new(receiver)))
+    }
+}
+
+impl<M: Middleware + 'static> PollManager<M> {
+    async fn poll_message(&self, poll_id: u64) -> Result<proto::Poll> {
+        let poll = self.poll_data(U256::from(poll_id)).await?;
+        Ok(proto::Poll {
+            id: poll_id,
+            question: poll.1,
+            options: poll.2,
+            creator: format!("{:?}", poll.3),
+            created_at: poll.4.to_u64_saturating(),
+            end_time: poll.5.to_u64_saturating(),
+            status: u8_to_status(poll.6).to_string(),
+            poll_type: u8_to_poll_type(poll.7).to_string(),
+            category: u8_to_category(poll.8).to_string(),
+            min_participation: poll.9.to_u64_saturating(),
+            description: poll.12,
+            tags: poll.13,
+            link: poll_ref::link(self.wallet.chain_id(), self.contract.address(), poll_id),
+        })
+    }
+
+    async fn results_message(&self, poll_id: u64) -> Result<proto::Results> {
+        let poll = self.poll_data(U256::from(poll_id)).await?;
+        let tally = self.poll_tally(poll_id, &poll.2, poll.7).await?;
+        let quorum = poll_quorum(tally.total_votes, poll.9, poll.4, poll.5, poll.6, self.now());
+        Ok(proto::Results {
+            poll_id,
+            weighted: tally.weighted,
+            total_votes: tally.total_votes,
+            total_weight: tally.total_weight.to_string(),
+            leader: tally.leader().map(|leader| leader.option.clone()),
+            options: tally.options.iter()
+                .map(|option| proto::OptionResult {
+                    option: option.option.clone(),
+                    votes: option.votes,
+                    weight: option.weight.to_string(),
+                    vote_share: option.vote_share,
+                    weight_share: option.weight_share,
+                })
+                .collect(),
+            quorum: serde_json::to_value(quorum.state)?.as_str().unwrap_or_default().to_string(),
+        })
+    }
+
+    /// The events in blocks `from..=head` and the block to continue from
+    async fn events_since(&self, from: u64, poll_id: Option<u64>) -> Result<(Vec<proto::Event>, u64)> {
+        let (events, next) = self.new_events(from).await?;
+        let events = events.into_iter()
+            .filter(|event| poll_id.is_none() || event.event.poll_id() == poll_id)
+            .filter_map(event_message)
+            .collect();
+        Ok((events, next))
+    }
+
+    /// Send events from block `next` onwards until the client disconnects. RPC hiccups are
+    /// retried, reading the same blocks again next round.
+    async fn follow_events(&self, mut next: u64, poll_id: Option<u64>, sender: mpsc::Sender<Result<proto::Event, Status>>) {
+        loop {
+            match self.events_since(next, poll_id).await {
+                Ok((events, resume)) => {
+                    for event in events {
+                        if sender.send(Ok(event)).await.is_err() {
+                            return;
+                        }
+                    }
+                    next = resume;
+                }
+                Err(e) => eprintln!("{} {:#}", output::render("⚠️  Event check failed:".to_string()).yellow(), e),
+            }
+            tokio::select! {
+                _ = tokio::time::sleep(EVENT_INTERVAL) => {}
+                _ = sender.closed() => return,
+            }
+        }
+    }
+
+    /// `grpc`: serve the read-only poll API in proto/polls.proto until Ctrl-C
+    pub async fn serve_grpc(self: Arc<Self>, listen: SocketAddr) -> Result<()> {
+        let listener = match TcpListener::bind(listen).await {
+            Ok(listener) => listener,
+            Err(e) => bail_config!("Could not listen on {}: {}", listen, e),
+        };
+        say!("{} {}", "🌐 Serving gRPC on".cyan().bold(), listen.to_string().yellow());
+        say!("polls.v1.PollService: GetPollCount, GetPoll, GetResults, ListVotes, StreamEvents; Ctrl-C to stop.");
+
+        tonic::transport::Server::builder()
+            .add_service(PollServiceServer::new(Service { manager: self }))
+            .serve_with_incoming_shutdown(TcpListenerStream::new(listener), async {
+                let _ = tokio::signal::ctrl_c().await;
+            })
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn streams_poll_events_only() {
+        let indexed = |event| IndexedEvent {
+            block_number: 9,
+            block_hash: H256::zero(),
+            transaction_hash: H256::repeat_byte(1),
+            log_index: 0,
+            timestamp: 1_700_000_000,
+            event,
+        };
+        let vote = event_message(indexed(ContractEvent::VoteCast {
+            poll_id: 4,
+            voter: Address::repeat_byte(2),
+            option_index: 1,
+            weight: U256::exp10(20),
+        })).unwrap();
+        let Some(proto::event::Kind::VoteCast(cast)) = &vote.kind else { panic!("expected a vote") };
+        assert_eq!((cast.weight.as_str(), cast.block_number), ("100000000000000000000", 9));
+
+        let closed = event_message(indexed(ContractEvent::PollStatusChanged { poll_id: 4, status: 1 })).unwrap();
+        assert!(matches!(closed.kind, Some(proto::event::Kind::StatusChanged(ref s)) if s.status == "Closed"));
+        assert!(event_message(indexed(ContractEvent::DelegateRemoved { delegator: Address::zero(), delegate: Address::zero() })).is_none());
+    }
+}
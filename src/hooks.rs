@@ -0,0 +1,236 @@
+use std::collections::BTreeMap;
+use std::process::Stdio;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use colored::*;
+use ethers::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::io::AsyncWriteExt;
+
+use crate::error::{bail_config, bail_user};
+use crate::numbers::SaturatingU64;
+use crate::{duration, output, store, u8_to_status, EnhancedPollsEvents, PollManager};
+
+const HOOKS_FILE: &str = "hooks.json";
+
+/// A hook that runs longer than this is killed, so one stuck command cannot stall the others
+const HOOK_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Shell commands to run per lifecycle event, in order
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct HookFile {
+    #[serde(default)]
+    pub on_poll_created: Vec<String>,
+    #[serde(default)]
+    pub on_vote_cast: Vec<String>,
+    #[serde(default)]
+    pub on_poll_closed: Vec<String>,
+}
+
+impl HookFile {
+    fn is_empty(&self) -> bool {
+        self.on_poll_created.is_empty() && self.on_vote_cast.is_empty() && self.on_poll_closed.is_empty()
+    }
+
+    fn commands(&self, event: &str) -> &[String] {
+        match event {
+            "poll_created" => &self.on_poll_created,
+            "vote_cast" => &self.on_vote_cast,
+            "poll_closed" => &self.on_poll_closed,
+            _ => &[],
+        }
+    }
+}
+
+/// One lifecycle event, as handed to hooks
+#[derive(Debug, Clone, PartialEq)]
+struct HookEvent {
+    /// poll_created, vote_cast or poll_closed
+    name: &'static str,
+    /// Event data by snake_case key; hooks get it as JSON on stdin and as POLL_<KEY> variables
+    /// (`poll_id` is POLL_ID)
+    fields: BTreeMap<&'static str, String>,
+}
+
+impl HookEvent {
+    fn env(&self) -> Vec<(String, String)> {
+        let mut env = vec![("POLL_EVENT".to_string(), self.name.to_string())];
+        env.extend(self.fields.iter().map(|(key, value)| (format!("POLL_{}", key.trim_start_matches("poll_").to_uppercase()), value.clone())));
+        env
+    }
+
+    fn json(&self) -> Value {
+        let mut json = serde_json::Map::new();
+        json.insert("event".to_string(), Value::from(self.name));
+        for (key, value) in &self.fields {
+            json.insert(key.to_string(), Value::from(value.as_str()));
+        }
+        Value::Object(json)
+    }
+}
+
+fn load_hooks() -> Result<HookFile> {
+    store::load(HOOKS_FILE)
+}
+
+/// Run one hook through the shell, with the event in its environment and as JSON on stdin
+async fn run_hook(command: &str, event: &HookEvent) -> Result<()> {
+    let mut shell = if cfg!(windows) {
+        let mut shell = tokio::process::Command::new("cmd");
+        shell.arg("/C");
+        shell
+    } else {
+        let mut shell = tokio::process::Command::new("sh");
+        shell.arg("-c");
+        shell
+    };
+    let mut child = shell
+        .arg(command)
+        .envs(event.env())
+        .stdin(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .with_context(|| format!("Failed to start hook `{}`", command))?;
+    let mut stdin = child.stdin.take().expect("stdin is piped");
+    // A hook that ignores its input may exit before reading it all; that is not a failure
+    let _ = stdin.write_all(format!("{}\n", event.json()).as_bytes()).await;
+    drop(stdin);
+    let status = match tokio::time::timeout(HOOK_TIMEOUT, child.wait()).await {
+        Ok(status) => status?,
+        Err(_) => anyhow::bail!("hook `{}` did not finish within {}s and was stopped", command, HOOK_TIMEOUT.as_secs()),
+    };
+    if !status.success() {
+        anyhow::bail!("hook `{}` failed with {}", command, status);
+    }
+    Ok(())
+}
+
+/// Run every hook for `event`; failures are reported and do not stop the rest
+async fn fire(hooks: &HookFile, event: &HookEvent) {
+    for command in hooks.commands(event.name) {
+        if let Err(e) = run_hook(command, event).await {
+            eprintln!("{} {:#}", output::render("⚠️  Hook failed:".to_string()).yellow(), e);
+        }
+    }
+}
+
+/// `hooks list`: the configured hooks
+pub fn list_hooks() -> Result<()> {
+    let hooks = load_hooks()?;
+    say!("\n{}", "🪝 Hooks:".cyan().bold());
+    if hooks.is_empty() {
+        say!("None yet. Add shell commands to {} under on_poll_created, on_vote_cast or on_poll_closed.",
+            store::data_dir()?.join(HOOKS_FILE).display());
+        return Ok(());
+    }
+    for (event, commands) in [("on_poll_created", &hooks.on_poll_created), ("on_vote_cast", &hooks.on_vote_cast), ("on_poll_closed", &hooks.on_poll_closed)] {
+        for command in commands {
+            say!("  {} {}", event.yellow(), command);
+        }
+    }
+    Ok(())
+}
+
+impl<M: Middleware + 'static> PollManager<M> {
+    /// The lifecycle events among the contract's logs in `from..=to`, oldest first
+    async fn hook_events(&self, from: u64, to: u64) -> Result<Vec<HookEvent>> {
+        let logs = self.contract.events().from_block(from).to_block(to).query_with_meta().await?;
+        let mut events = Vec::new();
+        for (log, meta) in logs {
+            let (name, poll_id, mut fields) = match log {
+                EnhancedPollsEvents::PollCreatedFilter(e) => ("poll_created", e.poll_id, BTreeMap::from([
+                    ("creator", format!("{:?}", e.creator)),
+                    ("question", e.question),
+                    ("end_time", e.end_time.to_string()),
+                    ("tags", e.tags.join(",")),
+                ])),
+                EnhancedPollsEvents::VoteCastFilter(e) => {
+                    let options = self.poll_data(e.poll_id).await?.2;
+                    let option = e.option_index.to_u64_saturating();
+                    ("vote_cast", e.poll_id, BTreeMap::from([
+                        ("voter", format!("{:?}", e.voter)),
+                        ("option", option.to_string()),
+                        ("option_name", options.get(option as usize).cloned().unwrap_or_default()),
+                        ("weight", e.weight.to_string()),
+                    ]))
+                }
+                // Expiry and cancellation are not closes
+                EnhancedPollsEvents::PollStatusChangedFilter(e) if e.new_status == 1 => {
+                    ("poll_closed", e.poll_id, BTreeMap::from([("status", u8_to_status(e.new_status).to_string())]))
+                }
+                _ => continue,
+            };
+            fields.insert("poll_id", poll_id.to_string());
+            fields.insert("block", meta.block_number.to_string());
+            fields.insert("tx", format!("{:?}", meta.transaction_hash));
+            fields.insert("chain_id", self.wallet.chain_id().to_string());
+            fields.insert("contract", format!("{:?}", self.contract.address()));
+            events.push(HookEvent { name, fields });
+        }
+        Ok(events)
+    }
+
+    /// `hooks watch`: run the configured hooks for every poll created, vote cast and poll closed
+    /// on the contract, until Ctrl-C
+    pub async fn watch_hooks(&self, interval: u64, from_block: Option<u64>) -> Result<()> {
+        if interval == 0 {
+            bail_user!("--interval must be longer than zero");
+        }
+        let hooks = load_hooks()?;
+        if hooks.is_empty() {
+            bail_config!("No hooks configured; add them to {}", store::data_dir()?.join(HOOKS_FILE).display());
+        }
+        // Without --from-block, only what happens after startup runs hooks
+        let mut checked_to = match from_block {
+            Some(block) => block.saturating_sub(1),
+            None => self.client.get_block_number().await?.as_u64(),
+        };
+        say!("{} every {} (Ctrl-C to stop)", "🪝 Running hooks for new events, checking".cyan().bold(), duration::format(interval));
+
+        loop {
+            let result: Result<()> = async {
+                let head = self.client.get_block_number().await?.as_u64();
+                if head > checked_to {
+                    for event in self.hook_events(checked_to + 1, head).await? {
+                        say!("{} {} for poll #{}", "▶".cyan(), event.name.yellow(), event.fields["poll_id"]);
+                        fire(&hooks, &event).await;
+                    }
+                    checked_to = head;
+                }
+                Ok(())
+            }.await;
+            // Keep watching through RPC hiccups; the same blocks are read again next round
+            if let Err(e) = result {
+                eprintln!("{} {:#}", output::render("⚠️  Event check failed:".to_string()).yellow(), e);
+            }
+            tokio::select! {
+                _ = tokio::time::sleep(Duration::from_secs(interval)) => {}
+                _ = tokio::signal::ctrl_c() => return Ok(()),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn passes_event_data_as_env_and_json() {
+        let hooks: HookFile = serde_json::from_str(r#"{ "on_vote_cast": ["./notify.sh"] }"#).unwrap();
+        assert_eq!(hooks.commands("vote_cast"), ["./notify.sh"]);
+        assert!(hooks.commands("poll_closed").is_empty());
+        assert!(serde_json::from_str::<HookFile>(r#"{ "on_vote": [] }"#).is_err(), "misspelled events are rejected");
+
+        let event = HookEvent { name: "vote_cast", fields: BTreeMap::from([("poll_id", "7".to_string()), ("option_name", "Yes".to_string())]) };
+        assert_eq!(event.env(), [
+            ("POLL_EVENT".to_string(), "vote_cast".to_string()),
+            ("POLL_OPTION_NAME".to_string(), "Yes".to_string()),
+            ("POLL_ID".to_string(), "7".to_string()),
+        ]);
+        assert_eq!(event.json(), serde_json::json!({ "event": "vote_cast", "poll_id": "7", "option_name": "Yes" }));
+    }
+}
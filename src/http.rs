@@ -0,0 +1,82 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+
+/// One HTTP/1.1 request, as much of it as the built-in servers need
+#[derive(Debug)]
+pub struct Request {
+    pub method: String,
+    /// Path without the query string
+    pub path: String,
+    /// Query string parameters, as sent (not percent-decoded)
+    pub query: HashMap<String, String>,
+    /// Header values by lowercase name
+    pub headers: HashMap<String, String>,
+    pub body: Vec<u8>,
+    pub keep_alive: bool,
+}
+
+impl Request {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(&name.to_ascii_lowercase()).map(String::as_str)
+    }
+}
+
+/// Read the next request on a connection; `None` once the client hangs up
+pub async fn read_request(reader: &mut BufReader<TcpStream>, max_body: usize) -> Result<Option<Request>> {
+    let mut line = String::new();
+    if reader.read_line(&mut line).await? == 0 {
+        return Ok(None);
+    }
+    let mut parts = line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let target = parts.next().unwrap_or("/");
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let path = path.to_string();
+    let query = query.split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (name.to_string(), value.to_string())
+        })
+        .collect();
+    let mut keep_alive = !line.trim_end().ends_with("HTTP/1.0");
+    let mut headers = HashMap::new();
+    loop {
+        line.clear();
+        if reader.read_line(&mut line).await? == 0 {
+            return Ok(None);
+        }
+        let header = line.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            let name = name.trim().to_ascii_lowercase();
+            if name == "connection" {
+                keep_alive = !value.trim().eq_ignore_ascii_case("close");
+            }
+            headers.insert(name, value.trim().to_string());
+        }
+    }
+    let content_length: usize = headers.get("content-length").and_then(|length| length.parse().ok()).unwrap_or(0);
+    if content_length > max_body {
+        anyhow::bail!("request body of {} bytes is too large", content_length);
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body).await?;
+    Ok(Some(Request { method, path, query, headers, body, keep_alive }))
+}
+
+pub async fn write_response(stream: &mut TcpStream, status: &str, content_type: &str, body: &[u8], keep_alive: bool) -> Result<()> {
+    let connection = if keep_alive { "keep-alive" } else { "close" };
+    let head = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: {}\r\n\r\n",
+        status, content_type, body.len(), connection
+    );
+    stream.write_all(head.as_bytes()).await?;
+    stream.write_all(body).await?;
+    Ok(())
+}
@@ -0,0 +1,95 @@
+use anyhow::{Context, Result};
+use ethers::types::Address;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::{bail_config, CliError};
+use crate::redact;
+
+/// An external identity or sybil-resistance score, such as Gitcoin Passport's
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IdentityScore {
+    pub address: Address,
+    pub score: f64,
+    /// Whether the score reaches IDENTITY_MIN_SCORE; `None` when no minimum is set
+    pub passing: Option<bool>,
+}
+
+/// A scoring API configured through IDENTITY_SCORE_URL, IDENTITY_SCORE_API_KEY and IDENTITY_MIN_SCORE
+#[derive(Debug, Clone)]
+pub struct IdentityScorer {
+    /// URL with `{address}` where the voter's address goes
+    url: String,
+    api_key: Option<String>,
+    min_score: Option<f64>,
+    http: reqwest::Client,
+}
+
+/// Read the score from a response: `score` as a number or a numeric string, at the top level or under `data`
+fn parse_score(response: &Value) -> Option<f64> {
+    let score = response.get("score").or_else(|| response.get("data").and_then(|data| data.get("score")))?;
+    match score {
+        Value::Number(number) => number.as_f64(),
+        Value::String(text) => text.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+impl IdentityScorer {
+    /// `None` when IDENTITY_SCORE_URL is unset, so checks without a scorer skip the score
+    pub fn from_env() -> Result<Option<Self>> {
+        let Some(url) = std::env::var("IDENTITY_SCORE_URL").ok().filter(|url| !url.is_empty()) else {
+            return Ok(None);
+        };
+        if !url.contains("{address}") {
+            bail_config!("IDENTITY_SCORE_URL must contain {{address}} where the voter's address goes");
+        }
+        let api_key = std::env::var("IDENTITY_SCORE_API_KEY").ok().filter(|key| !key.is_empty());
+        if let Some(key) = &api_key {
+            redact::register(key);
+        }
+        let min_score = match std::env::var("IDENTITY_MIN_SCORE").ok().filter(|value| !value.is_empty()) {
+            Some(value) => Some(value.parse::<f64>()
+                .map_err(|_| CliError::Config(format!("Invalid IDENTITY_MIN_SCORE '{}'; expected a number", value)))?),
+            None => None,
+        };
+        Ok(Some(IdentityScorer { url, api_key, min_score, http: reqwest::Client::new() }))
+    }
+
+    pub fn min_score(&self) -> Option<f64> {
+        self.min_score
+    }
+
+    pub async fn score(&self, address: Address) -> Result<IdentityScore> {
+        let url = self.url.replace("{address}", &format!("{:?}", address));
+        let mut request = self.http.get(&url);
+        if let Some(key) = &self.api_key {
+            // Passport's header; other services accept it or ignore it alongside the bearer token
+            request = request.header("X-API-KEY", key).bearer_auth(key);
+        }
+        let response = request.send().await.context("Identity score service unreachable")?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(CliError::Rpc(format!("Identity score service returned {} for {:?}", status, address)).into());
+        }
+        let body: Value = response.json().await.context("Identity score service returned invalid JSON")?;
+        let Some(score) = parse_score(&body) else {
+            return Err(CliError::Rpc(format!("Identity score service returned no score for {:?}", address)).into());
+        };
+        Ok(IdentityScore { address, score, passing: self.min_score.map(|min| score >= min) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn reads_numeric_and_string_scores() {
+        assert_eq!(parse_score(&json!({ "address": "0x1", "score": "23.5" })), Some(23.5));
+        assert_eq!(parse_score(&json!({ "data": { "score": 7 } })), Some(7.0));
+        assert_eq!(parse_score(&json!({ "score": null })), None);
+        assert_eq!(parse_score(&json!({ "status": "PROCESSING" })), None);
+    }
+}
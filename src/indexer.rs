@@ -0,0 +1,462 @@
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use anyhow::Result;
+use colored::*;
+use ethers::abi::RawLog;
+use ethers::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::error::bail_user;
+use crate::events::VoteRecord;
+use crate::numbers::SaturatingU64;
+use crate::{duration, numbers, output, store, EnhancedPollsEvents, PollManager};
+
+/// Blocks re-scanned below the checkpoint when the chain reorganised under it
+pub const DEFAULT_REORG_DEPTH: u64 = 12;
+
+const INDEX_DIR: &str = "index";
+
+/// Widest block range asked for in one eth_getLogs call; halved while a provider refuses it
+const MAX_LOG_RANGE: u64 = 100_000;
+
+/// An EnhancedPolls event, decoded
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum ContractEvent {
+    PollCreated {
+        poll_id: u64,
+        creator: Address,
+        question: String,
+        poll_type: u8,
+        category: u8,
+        end_time: u64,
+        tags: Vec<String>,
+    },
+    VoteCast {
+        poll_id: u64,
+        voter: Address,
+        option_index: u64,
+        #[serde(with = "numbers::decimal")]
+        weight: U256,
+    },
+    PollStatusChanged { poll_id: u64, status: u8 },
+    PollExtended { poll_id: u64, end_time: u64 },
+    DelegateSet { delegator: Address, delegate: Address },
+    DelegateRemoved { delegator: Address, delegate: Address },
+}
+
+impl From<EnhancedPollsEvents> for ContractEvent {
+    fn from(event: EnhancedPollsEvents) -> Self {
+        match event {
+            EnhancedPollsEvents::PollCreatedFilter(e) => ContractEvent::PollCreated {
+                poll_id: e.poll_id.to_u64_saturating(),
+                creator: e.creator,
+                question: e.question,
+                poll_type: e.poll_type,
+                category: e.category,
+                end_time: e.end_time.to_u64_saturating(),
+                tags: e.tags,
+            },
+            EnhancedPollsEvents::VoteCastFilter(e) => ContractEvent::VoteCast {
+                poll_id: e.poll_id.to_u64_saturating(),
+                voter: e.voter,
+                option_index: e.option_index.to_u64_saturating(),
+                weight: e.weight,
+            },
+            EnhancedPollsEvents::PollStatusChangedFilter(e) => ContractEvent::PollStatusChanged {
+                poll_id: e.poll_id.to_u64_saturating(),
+                status: e.new_status,
+            },
+            EnhancedPollsEvents::PollExtendedFilter(e) => ContractEvent::PollExtended {
+                poll_id: e.poll_id.to_u64_saturating(),
+                end_time: e.new_end_time.to_u64_saturating(),
+            },
+            EnhancedPollsEvents::DelegateSetFilter(e) => ContractEvent::DelegateSet { delegator: e.delegator, delegate: e.delegate },
+            EnhancedPollsEvents::DelegateRemovedFilter(e) => ContractEvent::DelegateRemoved { delegator: e.delegator, delegate: e.delegate },
+        }
+    }
+}
+
+impl ContractEvent {
+    /// The poll an event is about; `None` for delegation events
+    pub fn poll_id(&self) -> Option<u64> {
+        match self {
+            ContractEvent::PollCreated { poll_id, .. }
+            | ContractEvent::VoteCast { poll_id, .. }
+            | ContractEvent::PollStatusChanged { poll_id, .. }
+            | ContractEvent::PollExtended { poll_id, .. } => Some(*poll_id),
+            ContractEvent::DelegateSet { .. } | ContractEvent::DelegateRemoved { .. } => None,
+        }
+    }
+}
+
+/// A contract event with where and when it was mined
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IndexedEvent {
+    pub block_number: u64,
+    pub block_hash: H256,
+    pub transaction_hash: H256,
+    pub log_index: u64,
+    pub timestamp: u64,
+    #[serde(flatten)]
+    pub event: ContractEvent,
+}
+
+/// Last block processed, with its hash so a reorg underneath it is noticed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Checkpoint {
+    pub block_number: u64,
+    pub block_hash: H256,
+}
+
+/// Every event of one contract over the contiguous range `start_block..=checkpoint`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EventIndex {
+    pub start_block: u64,
+    pub checkpoint: Option<Checkpoint>,
+    pub events: Vec<IndexedEvent>,
+}
+
+impl EventIndex {
+    /// Block range a sync should fetch, keeping the index one contiguous range;
+    /// `None` when there is nothing new
+    pub fn plan(&self, from_block: Option<u64>, to_block: u64) -> Result<Option<(u64, u64)>> {
+        let Some(checkpoint) = self.checkpoint else {
+            let from = from_block.unwrap_or(0);
+            return Ok((from <= to_block).then_some((from, to_block)));
+        };
+        let from = from_block.unwrap_or(checkpoint.block_number + 1);
+        if from > checkpoint.block_number + 1 {
+            bail_user!("--from-block {} would leave blocks {}..{} unindexed; the index ends at block {}",
+                from, checkpoint.block_number + 1, from - 1, checkpoint.block_number);
+        }
+        if to_block.saturating_add(1) < self.start_block {
+            bail_user!("--to-block {} would leave blocks {}..{} unindexed; the index starts at block {}",
+                to_block, to_block + 1, self.start_block - 1, self.start_block);
+        }
+        Ok((from <= to_block).then_some((from, to_block)))
+    }
+
+    /// Swap in freshly fetched events for `from..=to`
+    pub fn replace_range(&mut self, from: u64, to: u64, fetched: Vec<IndexedEvent>) {
+        self.events.retain(|e| e.block_number < from || e.block_number > to);
+        self.events.extend(fetched);
+        self.events.sort_by_key(|e| (e.block_number, e.log_index));
+        if self.checkpoint.is_none() || from < self.start_block {
+            self.start_block = from;
+        }
+    }
+
+    /// Forget everything after `block`, ready to fetch it again
+    pub fn rewind(&mut self, block: u64) {
+        self.events.retain(|e| e.block_number <= block);
+    }
+
+    /// VoteCast events for a poll, oldest first
+    pub fn votes(&self, poll_id: u64) -> Vec<VoteRecord> {
+        self.events.iter().filter_map(|e| match e.event {
+            ContractEvent::VoteCast { poll_id: id, voter, option_index, weight } if id == poll_id => Some(VoteRecord {
+                poll_id,
+                voter,
+                option_index,
+                weight,
+                block_number: e.block_number,
+                transaction_hash: e.transaction_hash,
+                timestamp: e.timestamp,
+            }),
+            _ => None,
+        }).collect()
+    }
+
+    /// Block a poll's PollCreated event was mined in
+    pub fn created_block(&self, poll_id: u64) -> Option<u64> {
+        self.events.iter().find_map(|e| match e.event {
+            ContractEvent::PollCreated { poll_id: id, .. } if id == poll_id => Some(e.block_number),
+            _ => None,
+        })
+    }
+
+    /// Current delegate of every delegator, replaying DelegateSet/DelegateRemoved
+    pub fn delegations(&self) -> BTreeMap<Address, Address> {
+        delegations(&self.events)
+    }
+
+    /// `(poll_id, tags)` for every PollCreated event
+    pub fn poll_tags(&self) -> Vec<(u64, Vec<String>)> {
+        self.events.iter().filter_map(|e| match &e.event {
+            ContractEvent::PollCreated { poll_id, tags, .. } => Some((*poll_id, tags.clone())),
+            _ => None,
+        }).collect()
+    }
+}
+
+/// Delegator to delegate after replaying `events` in order
+pub fn delegations(events: &[IndexedEvent]) -> BTreeMap<Address, Address> {
+    let mut delegates = BTreeMap::new();
+    for event in events {
+        match event.event {
+            ContractEvent::DelegateSet { delegator, delegate } => {
+                delegates.insert(delegator, delegate);
+            }
+            ContractEvent::DelegateRemoved { delegator, .. } => {
+                delegates.remove(&delegator);
+            }
+            _ => {}
+        }
+    }
+    delegates
+}
+
+/// What one sync did
+#[derive(Debug, Clone, Copy)]
+pub struct SyncReport {
+    /// Blocks fetched, if any were
+    pub range: Option<(u64, u64)>,
+    pub new_events: usize,
+    /// First block re-scanned after a reorg was detected
+    pub reorg_from: Option<u64>,
+}
+
+/// Where the index for `contract` on `chain_id` is kept
+pub(crate) fn index_path(chain_id: u64, contract: Address) -> Result<PathBuf> {
+    let dir = store::data_dir()?.join(INDEX_DIR);
+    std::fs::create_dir_all(&dir)?;
+    Ok(dir.join(format!("{}-{:?}.json", chain_id, contract)))
+}
+
+impl<M: Middleware + 'static> PollManager<M> {
+    async fn block_hash(&self, block_number: u64) -> Result<Option<H256>> {
+        Ok(self.client.get_block(block_number).await?.and_then(|block| block.hash))
+    }
+
+    /// Undo whatever a reorg replaced since the last sync; returns the first block to fetch again
+    async fn handle_reorg(&self, index: &mut EventIndex, reorg_depth: u64) -> Result<Option<u64>> {
+        let Some(checkpoint) = index.checkpoint else { return Ok(None) };
+        if self.block_hash(checkpoint.block_number).await? == Some(checkpoint.block_hash) {
+            return Ok(None);
+        }
+
+        let mut keep = checkpoint.block_number.saturating_sub(reorg_depth);
+        index.rewind(keep);
+        // Deeper than the re-scan window (or a restarted dev chain): start over
+        if let Some(last) = index.events.last() {
+            if self.block_hash(last.block_number).await? != Some(last.block_hash) {
+                *index = EventIndex { start_block: index.start_block, ..EventIndex::default() };
+                return Ok(Some(index.start_block));
+            }
+        }
+        if keep < index.start_block {
+            keep = index.start_block;
+            index.checkpoint = None;
+            return Ok(Some(keep));
+        }
+        index.checkpoint = self.block_hash(keep).await?.map(|block_hash| Checkpoint { block_number: keep, block_hash });
+        Ok(Some(keep + 1))
+    }
+
+    /// Logs matching `filter` in `from..=to`, splitting the range while the provider rejects it as too large
+    pub(crate) async fn fetch_logs(&self, filter: &Filter, from: u64, to: u64, progress: &indicatif::ProgressBar) -> Result<Vec<Log>> {
+        let mut logs = Vec::new();
+        let mut start = from;
+        let mut span = MAX_LOG_RANGE;
+        while start <= to {
+            let end = to.min(start.saturating_add(span - 1));
+            let filter = filter.clone().from_block(start).to_block(end);
+            match self.client.get_logs(&filter).await {
+                Ok(batch) => {
+                    logs.extend(batch);
+                    progress.set_message(format!("Indexed blocks {}..{} of {}", from, end, to));
+                    start = end + 1;
+                }
+                Err(_) if span > 1 => span = (span / 2).max(1),
+                Err(e) => return Err(e.into()),
+            }
+        }
+        Ok(logs)
+    }
+
+    /// Events mined from block `from` up to the head, and the block to continue from. For following
+    /// the chain live; the stored index is left alone.
+    pub(crate) async fn new_events(&self, from: u64) -> Result<(Vec<IndexedEvent>, u64)> {
+        let head = self.client.get_block_number().await?.as_u64();
+        if head < from {
+            return Ok((Vec::new(), from));
+        }
+        let logs = self.fetch_logs(&Filter::new().address(self.contract.address()), from, head, &indicatif::ProgressBar::hidden()).await?;
+        Ok((self.decode_logs(logs).await?, head + 1))
+    }
+
+    pub(crate) async fn decode_logs(&self, logs: Vec<Log>) -> Result<Vec<IndexedEvent>> {
+        let mut timestamps: HashMap<u64, u64> = HashMap::new();
+        let mut events = Vec::with_capacity(logs.len());
+        for log in logs {
+            let (Some(block_number), Some(block_hash), Some(transaction_hash)) = (log.block_number, log.block_hash, log.transaction_hash) else {
+                continue;
+            };
+            let log_index = log.log_index.unwrap_or_default().to_u64_saturating();
+            // Events outside the ABI this CLI knows about are skipped
+            let Ok(event) = <EnhancedPollsEvents as EthLogDecode>::decode_log(&RawLog::from(log)) else {
+                continue;
+            };
+            let block_number = block_number.as_u64();
+            let timestamp = match timestamps.get(&block_number) {
+                Some(ts) => *ts,
+                None => {
+                    let ts = self.block_timestamp(block_number).await?;
+                    timestamps.insert(block_number, ts);
+                    ts
+                }
+            };
+            events.push(IndexedEvent { block_number, block_hash, transaction_hash, log_index, timestamp, event: event.into() });
+        }
+        Ok(events)
+    }
+
+    /// Bring the on-disk index for this contract up to `to_block` (latest by default),
+    /// resuming from its checkpoint unless `from_block` asks for a backfill
+    pub async fn sync_events(&self, from_block: Option<u64>, to_block: Option<u64>, reorg_depth: u64, progress: &indicatif::ProgressBar) -> Result<(EventIndex, SyncReport)> {
+        let path = index_path(self.wallet.chain_id(), self.contract.address())?;
+        // A corrupt index is rebuilt rather than blocking every command that reads events
+        let mut index: EventIndex = store::load_from(&path).unwrap_or_default();
+
+        let latest = self.client.get_block_number().await?.as_u64();
+        let to = match to_block {
+            Some(to) if to > latest => bail_user!("--to-block {} is past the latest block {}", to, latest),
+            Some(to) => to,
+            None => latest,
+        };
+
+        let reorg_from = self.handle_reorg(&mut index, reorg_depth).await?;
+        let from_block = match (from_block, reorg_from) {
+            (Some(from), Some(reorg)) => Some(from.min(reorg)),
+            (from, reorg) => from.or(reorg),
+        };
+
+        let mut report = SyncReport { range: None, new_events: 0, reorg_from };
+        if let Some((from, to)) = index.plan(from_block, to)? {
+            let events = self.decode_logs(self.fetch_logs(&Filter::new().address(self.contract.address()), from, to, progress).await?).await?;
+            report.range = Some((from, to));
+            report.new_events = events.len();
+            index.replace_range(from, to, events);
+            if index.checkpoint.is_none_or(|c| c.block_number <= to) {
+                let block_hash = self.block_hash(to).await?
+                    .ok_or_else(|| anyhow::anyhow!("Block {} not found", to))?;
+                index.checkpoint = Some(Checkpoint { block_number: to, block_hash });
+            }
+        }
+        if report.range.is_some() || reorg_from.is_some() {
+            store::save_to(&path, &index)?;
+        }
+        Ok((index, report))
+    }
+
+    /// The event index, synced to the latest block once per run
+    pub async fn event_index(&self) -> Result<Arc<EventIndex>> {
+        if let Some(index) = self.index.lock().ok().and_then(|index| index.clone()) {
+            return Ok(index);
+        }
+        let (index, _) = self.sync_events(None, None, DEFAULT_REORG_DEPTH, &indicatif::ProgressBar::hidden()).await?;
+        let index = Arc::new(index);
+        if let Ok(mut slot) = self.index.lock() {
+            *slot = Some(index.clone());
+        }
+        Ok(index)
+    }
+
+    /// `sync`: index contract events incrementally; with `watch`, keep following the chain
+    pub async fn sync(&self, from_block: Option<u64>, to_block: Option<u64>, reorg_depth: u64, watch: Option<u64>) -> Result<()> {
+        if watch == Some(0) {
+            bail_user!("--watch interval must be longer than zero");
+        }
+        let mut backfill = from_block;
+        loop {
+            let progress = output::spinner("🔄 Syncing contract events...");
+            let result = self.sync_events(backfill.take(), to_block, reorg_depth, &progress).await;
+            progress.finish_and_clear();
+
+            match result {
+                Ok((index, report)) => {
+                    if let Some(reorg) = report.reorg_from {
+                        say!("{} re-indexing from block {}", "⚠️  Reorg detected:".yellow().bold(), reorg);
+                    }
+                    match report.range {
+                        Some((from, to)) => say!("{} blocks {}..{}: {} events", "✅ Synced".green().bold(), from, to, report.new_events),
+                        None => say!("{}", "✅ Already up to date".green().bold()),
+                    }
+                    if let Some(checkpoint) = index.checkpoint {
+                        say!("{} blocks {}..{}, {} events", "Index:".cyan().bold(), index.start_block, checkpoint.block_number, index.events.len());
+                    }
+                }
+                Err(e) if watch.is_some() => eprintln!("{} {:#}", output::render("⚠️  Sync failed:".to_string()).yellow(), e),
+                Err(e) => return Err(e),
+            }
+
+            let Some(interval) = watch else { return Ok(()) };
+            if to_block.is_some() {
+                return Ok(());
+            }
+            say!("Next sync in {} (Ctrl-C to stop)", duration::format(interval));
+            tokio::time::sleep(std::time::Duration::from_secs(interval)).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vote(block_number: u64, log_index: u64, poll_id: u64) -> IndexedEvent {
+        IndexedEvent {
+            block_number,
+            block_hash: H256::from_low_u64_be(block_number),
+            transaction_hash: H256::from_low_u64_be(block_number * 100 + log_index),
+            log_index,
+            timestamp: 1_000 + block_number,
+            event: ContractEvent::VoteCast { poll_id, voter: Address::zero(), option_index: 0, weight: U256::one() },
+        }
+    }
+
+    fn synced_to(start_block: u64, block_number: u64, events: Vec<IndexedEvent>) -> EventIndex {
+        let checkpoint = Some(Checkpoint { block_number, block_hash: H256::from_low_u64_be(block_number) });
+        EventIndex { start_block, checkpoint, events }
+    }
+
+    #[test]
+    fn plans_resume_and_contiguous_backfills() {
+        assert_eq!(EventIndex::default().plan(None, 50).unwrap(), Some((0, 50)));
+        assert_eq!(EventIndex::default().plan(Some(40), 50).unwrap(), Some((40, 50)));
+
+        let index = synced_to(40, 50, vec![]);
+        assert_eq!(index.plan(None, 60).unwrap(), Some((51, 60)));
+        assert_eq!(index.plan(None, 50).unwrap(), None);
+        assert_eq!(index.plan(Some(10), 45).unwrap(), Some((10, 45)));
+        assert!(index.plan(Some(55), 60).is_err(), "gap after the checkpoint");
+        assert!(index.plan(Some(10), 20).is_err(), "gap before the start");
+    }
+
+    #[test]
+    fn replaced_ranges_stay_ordered() {
+        let mut index = synced_to(40, 50, vec![vote(41, 0, 1), vote(45, 0, 1), vote(50, 1, 2)]);
+        index.replace_range(45, 60, vec![vote(58, 0, 1), vote(45, 2, 1), vote(45, 1, 2)]);
+        let order: Vec<(u64, u64)> = index.events.iter().map(|e| (e.block_number, e.log_index)).collect();
+        assert_eq!(order, [(41, 0), (45, 1), (45, 2), (58, 0)]);
+        assert_eq!(index.votes(1).len(), 3);
+
+        index.replace_range(30, 39, vec![vote(33, 0, 3)]);
+        assert_eq!(index.start_block, 30);
+
+        index.rewind(45);
+        assert_eq!(index.events.last().map(|e| e.block_number), Some(45));
+    }
+
+    #[test]
+    fn indexed_events_round_trip_as_json() {
+        let event = vote(7, 3, 2);
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["event"], "vote_cast");
+        assert_eq!(json["weight"], "1");
+        assert_eq!(serde_json::from_value::<IndexedEvent>(json).unwrap(), event);
+    }
+}
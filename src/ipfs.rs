@@ -0,0 +1,130 @@
+use anyhow::{Context, Result};
+use colored::*;
+use ethers::providers::Middleware;
+use serde::Deserialize;
+
+use crate::error::{bail_config, bail_user, CliError};
+use crate::{render_markdown, PollManager};
+
+const MULTIPART_BOUNDARY: &str = "----polling-cli-boundary-7f3a9c";
+
+/// Where uploads are pinned, configured through environment variables
+#[derive(Debug, Clone)]
+pub enum PinningService {
+    /// A Kubo (go-ipfs) node's HTTP RPC API
+    Kubo { api_url: String },
+    /// Pinata's pinning API, authenticated with a JWT
+    Pinata { api_url: String, jwt: String },
+}
+
+#[derive(Debug, Clone)]
+pub struct IpfsClient {
+    service: PinningService,
+    gateway_url: String,
+    http: reqwest::Client,
+}
+
+#[derive(Deserialize)]
+struct KuboAddResponse {
+    #[serde(rename = "Hash")]
+    hash: String,
+}
+
+#[derive(Deserialize)]
+struct PinataPinResponse {
+    #[serde(rename = "IpfsHash")]
+    ipfs_hash: String,
+}
+
+impl IpfsClient {
+    /// Build a client from IPFS_PINNING_SERVICE (kubo, pinata), IPFS_API_URL, PINATA_JWT and IPFS_GATEWAY_URL
+    pub fn from_env() -> Result<Self> {
+        let service = match std::env::var("IPFS_PINNING_SERVICE").unwrap_or_else(|_| "kubo".to_string()).to_lowercase().as_str() {
+            "kubo" => PinningService::Kubo {
+                api_url: std::env::var("IPFS_API_URL").unwrap_or_else(|_| "http://127.0.0.1:5001".to_string()),
+            },
+            "pinata" => PinningService::Pinata {
+                api_url: std::env::var("IPFS_API_URL").unwrap_or_else(|_| "https://api.pinata.cloud".to_string()),
+                jwt: std::env::var("PINATA_JWT")
+                    .map_err(|_| CliError::Config("PINATA_JWT must be set to pin with Pinata".to_string()))?,
+            },
+            other => bail_config!("Invalid IPFS_PINNING_SERVICE '{}'. Use: kubo, pinata", other),
+        };
+
+        Ok(Self {
+            service,
+            gateway_url: std::env::var("IPFS_GATEWAY_URL").unwrap_or_else(|_| "https://ipfs.io".to_string()),
+            http: reqwest::Client::new(),
+        })
+    }
+
+    pub fn gateway_link(&self, cid: &str) -> String {
+        format!("{}/ipfs/{}", self.gateway_url.trim_end_matches('/'), cid)
+    }
+
+    /// Upload and pin a single file, returning its CID
+    pub async fn pin(&self, file_name: &str, content_type: &str, content: &[u8]) -> Result<String> {
+        let mut body = Vec::with_capacity(content.len() + 256);
+        body.extend_from_slice(format!(
+            "--{}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"{}\"\r\nContent-Type: {}\r\n\r\n",
+            MULTIPART_BOUNDARY, file_name, content_type
+        ).as_bytes());
+        body.extend_from_slice(content);
+        body.extend_from_slice(format!("\r\n--{}--\r\n", MULTIPART_BOUNDARY).as_bytes());
+
+        let request = match &self.service {
+            PinningService::Kubo { api_url } => self.http
+                .post(format!("{}/api/v0/add?pin=true&cid-version=1", api_url.trim_end_matches('/'))),
+            PinningService::Pinata { api_url, jwt } => self.http
+                .post(format!("{}/pinning/pinFileToIPFS", api_url.trim_end_matches('/')))
+                .bearer_auth(jwt),
+        };
+
+        let response = request
+            .header("Content-Type", format!("multipart/form-data; boundary={}", MULTIPART_BOUNDARY))
+            .body(body)
+            .send()
+            .await
+            .context("Failed to reach the IPFS pinning service")?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let text = response.text().await.unwrap_or_default();
+            return Err(CliError::Rpc(format!("IPFS pinning service returned {}: {}", status, text)).into());
+        }
+
+        let cid = match self.service {
+            PinningService::Kubo { .. } => response.json::<KuboAddResponse>().await?.hash,
+            PinningService::Pinata { .. } => response.json::<PinataPinResponse>().await?.ipfs_hash,
+        };
+        Ok(cid)
+    }
+}
+
+impl<M: Middleware + 'static> PollManager<M> {
+    pub async fn publish_poll(&self, poll_id: u64, ipfs: bool, format: &str) -> Result<()> {
+        if !ipfs {
+            bail_user!("No publish target selected. Use --ipfs");
+        }
+
+        let export_data = self.build_poll_export(poll_id).await?;
+        let (content, content_type, extension) = match format.to_lowercase().as_str() {
+            "json" => (serde_json::to_string_pretty(&export_data)?, "application/json", "json"),
+            "markdown" | "md" => (render_markdown(&export_data), "text/markdown", "md"),
+            _ => bail_user!("Unsupported format: {}. Use json or markdown", format),
+        };
+
+        let client = IpfsClient::from_env()?;
+        say!("{} {} {}", "📤 Publishing poll".cyan().bold(), poll_id.to_string().yellow(), "to IPFS...".cyan().bold());
+
+        let file_name = format!("poll-{}.{}", poll_id, extension);
+        let cid = client.pin(&file_name, content_type, content.as_bytes()).await?;
+
+        say!("{}", "✅ Poll report pinned to IPFS!".green().bold());
+        say!("{} {}", "CID:".cyan().bold(), cid.yellow());
+        say!("{} {}", "Gateway:".cyan().bold(), client.gateway_link(&cid).white());
+        say!("{} ipfs://{}", "URI:".cyan().bold(), cid);
+
+        Ok(())
+    }
+}
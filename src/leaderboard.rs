@@ -0,0 +1,261 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use anyhow::Result;
+use colored::*;
+use ethers::prelude::*;
+use serde::Serialize;
+
+use crate::error::bail_user;
+use crate::indexer::{ContractEvent, IndexedEvent};
+use crate::{duration, format_timestamp, numbers, PollManager};
+
+/// What a leaderboard ranks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, clap::ValueEnum)]
+#[serde(rename_all = "snake_case")]
+pub enum Board {
+    /// Addresses that created the most polls, then drew the most votes to them
+    Creators,
+    /// Addresses that cast the most votes
+    Voters,
+    /// Voters with the most total vote weight
+    Weight,
+}
+
+impl Board {
+    fn title(self) -> &'static str {
+        match self {
+            Board::Creators => "MOST ACTIVE POLL CREATORS",
+            Board::Voters => "MOST ACTIVE VOTERS",
+            Board::Weight => "HIGHEST-WEIGHT VOTERS",
+        }
+    }
+}
+
+/// One address on a leaderboard. For creators, votes and weight are what their polls received.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Standing {
+    pub rank: usize,
+    pub address: Address,
+    /// Polls created, or polls voted in
+    pub polls: usize,
+    pub votes: usize,
+    #[serde(with = "numbers::decimal")]
+    pub weight: U256,
+    /// Timestamp of the address's latest counted event
+    pub last_active: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Leaderboard {
+    pub board: Board,
+    /// Only events from this timestamp on were counted; absent for all time
+    pub since: Option<u64>,
+    /// Everyone who placed, of whom `standings` are the top
+    pub participants: usize,
+    pub standings: Vec<Standing>,
+}
+
+/// Rank every address by `board` over the events mined at or after `since`
+fn rank(events: &[IndexedEvent], board: Board, since: u64) -> Vec<Standing> {
+    let mut creators: BTreeMap<u64, Address> = BTreeMap::new();
+    let mut standings: BTreeMap<Address, Standing> = BTreeMap::new();
+    let mut polls: BTreeMap<Address, BTreeSet<u64>> = BTreeMap::new();
+    for event in events {
+        let counted = event.timestamp >= since;
+        let account = match &event.event {
+            ContractEvent::PollCreated { poll_id, creator, .. } => {
+                creators.insert(*poll_id, *creator);
+                if !counted || board != Board::Creators {
+                    continue;
+                }
+                polls.entry(*creator).or_default().insert(*poll_id);
+                *creator
+            }
+            ContractEvent::VoteCast { poll_id, voter, weight, .. } if counted => {
+                let account = match board {
+                    Board::Creators => match creators.get(poll_id) {
+                        Some(creator) => *creator,
+                        None => continue,
+                    },
+                    Board::Voters | Board::Weight => {
+                        polls.entry(*voter).or_default().insert(*poll_id);
+                        *voter
+                    }
+                };
+                let standing = standings.entry(account).or_default();
+                standing.votes += 1;
+                standing.weight = standing.weight.saturating_add(*weight);
+                account
+            }
+            _ => continue,
+        };
+        let standing = standings.entry(account).or_default();
+        standing.address = account;
+        standing.last_active = standing.last_active.max(event.timestamp);
+    }
+    // Creators place by polls created in the window; votes their older polls drew don't place them
+    if board == Board::Creators {
+        standings.retain(|account, _| polls.contains_key(account));
+    }
+
+    let mut ranked: Vec<Standing> = standings.into_values().map(|mut standing| {
+        standing.polls = polls.get(&standing.address).map_or(0, BTreeSet::len);
+        standing
+    }).collect();
+    ranked.sort_by(|a, b| match board {
+        Board::Creators => b.polls.cmp(&a.polls).then(b.votes.cmp(&a.votes)),
+        Board::Voters => b.votes.cmp(&a.votes).then(b.weight.cmp(&a.weight)),
+        Board::Weight => b.weight.cmp(&a.weight).then(b.votes.cmp(&a.votes)),
+    });
+    for (i, standing) in ranked.iter_mut().enumerate() {
+        standing.rank = i + 1;
+    }
+    ranked
+}
+
+fn render_csv(standings: &[Standing]) -> Result<String> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record(["rank", "address", "polls", "votes", "weight", "last_active"])?;
+    for standing in standings {
+        writer.write_record([
+            standing.rank.to_string(),
+            format!("{:?}", standing.address),
+            standing.polls.to_string(),
+            standing.votes.to_string(),
+            standing.weight.to_string(),
+            standing.last_active.to_string(),
+        ])?;
+    }
+    Ok(String::from_utf8(writer.into_inner()?)?)
+}
+
+fn print_leaderboard(leaderboard: &Leaderboard, window: Option<u64>) {
+    say!("\n{}", format!("🏆 LEADERBOARD: {}", leaderboard.board.title()).cyan().bold().underline());
+    say!("{}", "═".repeat(60).cyan());
+    match window {
+        Some(window) => say!("{} last {}", "Window:".yellow().bold(), duration::format(window)),
+        None => say!("{} all time", "Window:".yellow().bold()),
+    }
+    if leaderboard.standings.is_empty() {
+        say!("No activity in this window.");
+        return;
+    }
+
+    let polls = if leaderboard.board == Board::Creators { "Polls" } else { "Polls voted" };
+    say!("\n{:>4}  {:<42}  {:>11}  {:>8}  {:>12}  {}", "Rank", "Address", polls, "Votes", "Weight", "Last active");
+    for standing in &leaderboard.standings {
+        say!("{:>4}  {:<42}  {:>11}  {:>8}  {:>12}  {}",
+            standing.rank,
+            format!("{:?}", standing.address),
+            numbers::format_integer(standing.polls as u64),
+            numbers::format_integer(standing.votes as u64),
+            numbers::format_integer(standing.weight),
+            format_timestamp(standing.last_active),
+        );
+    }
+    if leaderboard.participants > leaderboard.standings.len() {
+        say!("\n{} of {} shown; use -n to see more", leaderboard.standings.len(), leaderboard.participants);
+    }
+}
+
+impl<M: Middleware + 'static> PollManager<M> {
+    /// `leaderboard`: rank creators or voters from the indexed events
+    pub async fn leaderboard(&self, board: Board, window: Option<u64>, top: usize, format: &str, output_path: Option<String>) -> Result<()> {
+        let format = format.to_lowercase();
+        if !matches!(format.as_str(), "table" | "csv" | "json") {
+            bail_user!("Unsupported format: {}. Use table, csv or json", format);
+        }
+        if top == 0 {
+            bail_user!("-n must be at least 1");
+        }
+
+        let index = self.event_index().await?;
+        let events = match self.pinned {
+            Some(pinned) => &index.events[..index.events.partition_point(|e| e.block_number <= pinned.number)],
+            None => &index.events[..],
+        };
+        let since = window.map(|window| self.now().saturating_sub(window));
+        let mut standings = rank(events, board, since.unwrap_or(0));
+        let participants = standings.len();
+        standings.truncate(top);
+        let leaderboard = Leaderboard { board, since, participants, standings };
+
+        let rendered = match format.as_str() {
+            "csv" => render_csv(&leaderboard.standings)?,
+            "json" => serde_json::to_string_pretty(&leaderboard)?,
+            _ => {
+                print_leaderboard(&leaderboard, window);
+                return Ok(());
+            }
+        };
+        match output_path {
+            Some(path) => {
+                std::fs::write(&path, rendered)?;
+                say!("{} {} entries to {}", "✅ Leaderboard exported:".green().bold(), leaderboard.standings.len(), path.yellow());
+            }
+            None => println!("{}", rendered.trim_end()),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(timestamp: u64, event: ContractEvent) -> IndexedEvent {
+        IndexedEvent {
+            block_number: timestamp,
+            block_hash: H256::zero(),
+            transaction_hash: H256::zero(),
+            log_index: 0,
+            timestamp,
+            event,
+        }
+    }
+
+    fn created(poll_id: u64, creator: u64) -> ContractEvent {
+        ContractEvent::PollCreated {
+            poll_id,
+            creator: Address::from_low_u64_be(creator),
+            question: String::new(),
+            poll_type: 0,
+            category: 0,
+            end_time: 0,
+            tags: vec![],
+        }
+    }
+
+    fn vote(poll_id: u64, voter: u64, weight: u64) -> ContractEvent {
+        ContractEvent::VoteCast { poll_id, voter: Address::from_low_u64_be(voter), option_index: 0, weight: U256::from(weight) }
+    }
+
+    #[test]
+    fn ranks_creators_voters_and_weight() {
+        let events = vec![
+            at(10, created(0, 1)),
+            at(20, created(1, 2)),
+            at(30, created(2, 2)),
+            at(40, vote(0, 7, 100)),
+            at(50, vote(0, 8, 1)),
+            at(60, vote(1, 8, 1)),
+            at(70, vote(2, 8, 1)),
+        ];
+        let addresses = |standings: Vec<Standing>| standings.iter().map(|s| s.address.to_low_u64_be()).collect::<Vec<_>>();
+
+        let creators = rank(&events, Board::Creators, 0);
+        assert_eq!(addresses(creators.clone()), vec![2, 1]);
+        assert_eq!((creators[0].polls, creators[0].votes, creators[0].last_active), (2, 2, 70));
+
+        let voters = rank(&events, Board::Voters, 0);
+        assert_eq!(addresses(voters.clone()), vec![8, 7]);
+        assert_eq!((voters[0].rank, voters[0].polls, voters[0].votes), (1, 3, 3));
+        assert_eq!(addresses(rank(&events, Board::Weight, 0)), vec![7, 8]);
+
+        // Address 1 drew votes inside the window but created nothing in it
+        let recent = rank(&events, Board::Creators, 25);
+        assert_eq!((recent[0].polls, recent[0].votes), (1, 2));
+        assert_eq!(addresses(recent), vec![2]);
+        assert_eq!(addresses(rank(&events, Board::Voters, 55)), vec![8]);
+    }
+}
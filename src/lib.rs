@@ -0,0 +1,3551 @@
+use ethers::prelude::*;
+use std::sync::Arc;
+use anyhow::Result;
+use clap::{CommandFactory, FromArgMatches, Parser, Subcommand};
+use colored::*;
+use tabled::Tabled;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{bail_config, bail_user, CliError};
+use crate::numbers::SaturatingU64;
+use crate::poll_ref::PollRef;
+
+// Declared first so its `say!` macro, used for human-facing output, is in scope for every other
+// module; machine output (JSON, CSV) goes through plain `println!`
+#[macro_use]
+mod output;
+
+mod accounts;
+mod alerts;
+mod allowlist;
+mod announce;
+mod archive;
+mod attest;
+mod audit;
+mod bot;
+mod cache;
+mod calendar;
+mod categories;
+mod certify;
+mod chart;
+pub mod client;
+mod clipboard;
+mod completions;
+mod confirm;
+mod connection;
+mod contracts;
+mod correlation;
+mod costs;
+mod delegates;
+mod diff;
+mod doctor;
+mod drafts;
+mod duration;
+mod eas;
+mod eligibility;
+mod engagement;
+mod error;
+mod events;
+mod extend_batch;
+mod feed;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+mod fields;
+mod finality;
+mod follow;
+mod forum;
+mod github;
+mod grpc;
+mod holders;
+mod hooks;
+mod http;
+mod identity;
+mod indexer;
+mod ipfs;
+mod leaderboard;
+mod lint;
+mod maintain;
+mod matrix;
+mod my_polls;
+mod numbers;
+mod pinned;
+mod plugins;
+mod policy;
+mod poll_ref;
+mod preview;
+mod price;
+mod profiles;
+mod projection;
+mod proxy;
+mod push;
+mod query;
+mod quorum;
+mod redact;
+mod relay;
+mod report;
+mod rewards;
+mod rpc;
+mod rules;
+mod schedule;
+mod script;
+mod serve;
+mod session;
+mod simulate;
+mod site;
+mod snapshot_org;
+mod spec;
+mod store;
+mod sybil;
+mod tags;
+mod tally;
+mod telegram;
+mod templates;
+mod timezone;
+mod turnout;
+mod user_op;
+mod user_report;
+mod velocity;
+mod verify_vote;
+mod whales;
+mod xlsx;
+
+// Contract ABI for EnhancedPolls
+abigen!(
+    EnhancedPolls,
+    r#"[
+        function createPoll(string memory _question, string[] memory _options, uint256 _durationInSeconds, uint8 _pollType, uint8 _category, uint256 _minParticipation, address _tokenAddress, uint256 _minTokenBalance, string memory _description, string[] memory _tags) external payable returns (uint256)
+        function vote(uint256 _pollId, uint256 _optionIndex) external
+        function voteAsDelegate(uint256 _pollId, uint256 _optionIndex, address _delegator) external
+        function closePoll(uint256 _pollId) external
+        function extendPoll(uint256 _pollId, uint256 _additionalTime) external
+        function setDelegate(address _delegate, uint8 _delegationType) external
+        function removeDelegate() external
+        struct PollView { uint256 id; string question; string[] options; address creator; uint256 createdAt; uint256 endTime; uint8 status; uint8 pollType; uint8 category; uint256 minParticipation; uint256 totalVotes; uint256 totalWeight; string description; string[] tags; uint256 templateId; bool isArchived; uint256 archivedAt; }
+        function getPoll(uint256 _pollId) external view returns (PollView memory)
+        function polls(uint256) external view returns (uint256 id, string question, address creator, uint256 createdAt, uint256 endTime, uint8 status, uint8 pollType, uint8 category, uint256 minParticipation, uint256 totalVotes, uint256 totalWeight, bool requiresToken, address tokenAddress, uint256 minTokenBalance, string description, uint256 templateId, bool isArchived, uint256 archivedAt, uint256 eliminationRound)
+        function getPollResults(uint256 _pollId) external view returns (uint256[] memory, uint256, uint256)
+        function getPollsByCategory(uint8 _category) external view returns (uint256[] memory)
+        function getPollsByTag(string memory _tag) external view returns (uint256[] memory)
+        function getFilteredPolls(uint8 _status, uint8 _category, bool _activeOnly) external view returns (uint256[] memory)
+        function hasUserVoted(uint256 _pollId, address _user) external view returns (bool)
+        function getUserCreatedPolls(address _user) external view returns (uint256[] memory)
+        function getUserVotedPolls(address _user) external view returns (uint256[] memory)
+        function getUserStats(address _user) external view returns (uint256 pollsCreated, uint256 pollsVoted, uint256 totalVotingWeight)
+        function getDelegators(address _delegate) external view returns (address[] memory)
+        function getDelegate(address _user) external view returns (address)
+        function isPollActive(uint256 _pollId) external view returns (bool)
+        function getTotalVotes(uint256 _pollId) external view returns (uint256)
+        function getActivePollsCount() external view returns (uint256)
+        function pollCount() external view returns (uint256)
+        function minPollDuration() external view returns (uint256)
+        function maxPollDuration() external view returns (uint256)
+        function platformFee() external view returns (uint256)
+        event PollCreated(uint256 indexed pollId, address indexed creator, string question, uint8 pollType, uint8 category, uint256 endTime, string[] tags)
+        event VoteCast(uint256 indexed pollId, address indexed voter, uint256 optionIndex, uint256 weight)
+        event PollStatusChanged(uint256 indexed pollId, uint8 newStatus)
+        event PollExtended(uint256 indexed pollId, uint256 newEndTime)
+        event DelegateSet(address indexed delegator, address indexed delegate)
+        event DelegateRemoved(address indexed delegator, address indexed delegate)
+    ]"#
+);
+
+// GovernanceToken ABI for token operations
+abigen!(
+    GovernanceToken,
+    r#"[
+        function balanceOf(address account) external view returns (uint256)
+        function transfer(address to, uint256 amount) external returns (bool)
+        function approve(address spender, uint256 amount) external returns (bool)
+        function totalSupply() external view returns (uint256)
+        function getVotingPower(address user) external view returns (uint256)
+        function name() external view returns (string memory)
+        function symbol() external view returns (string memory)
+        function decimals() external view returns (uint8)
+        event Transfer(address indexed from, address indexed to, uint256 value)
+    ]"#
+);
+
+#[derive(Debug, Serialize, Deserialize, Tabled)]
+struct PollExport {
+    id: u64,
+    question: String,
+    creator: String,
+    created_at: String,
+    end_time: String,
+    /// Raw unix timestamps alongside the formatted UTC strings
+    #[tabled(skip)]
+    created_at_unix: u64,
+    #[tabled(skip)]
+    end_time_unix: u64,
+    is_active: bool,
+    poll_type: String,
+    /// Metric that decides the winner: "votes", or "weight" for weighted/quadratic polls
+    winner_metric: String,
+    total_votes: u64,
+    #[serde(with = "numbers::decimal")]
+    total_weight: U256,
+    #[tabled(display_with = "display_vec_string")]
+    options: Vec<String>,
+    #[tabled(display_with = "display_vec_u64")]
+    votes: Vec<u64>,
+    #[serde(with = "numbers::decimal_vec")]
+    #[tabled(display_with = "display_vec_u256")]
+    weights: Vec<U256>,
+    min_participation: u64,
+    quorum_status: quorum::QuorumState,
+}
+
+fn display_vec_string(vec: &[String]) -> String {
+    vec.join(", ")
+}
+
+fn display_vec_u64(vec: &[u64]) -> String {
+    vec.iter().map(|v| v.to_string()).collect::<Vec<_>>().join(", ")
+}
+
+fn display_vec_u256(vec: &[U256]) -> String {
+    vec.iter().map(|v| v.to_string()).collect::<Vec<_>>().join(", ")
+}
+
+/// One line of `list --format ndjson`
+#[derive(Serialize)]
+struct PollListEntry {
+    contract: Address,
+    id: u64,
+    question: String,
+    active: bool,
+    poll_type: String,
+    category: String,
+    options: Vec<String>,
+    total_votes: u64,
+    creator: Address,
+    tags: Vec<String>,
+    created_at: u64,
+    end_time: u64,
+}
+
+/// Dialect options for CSV exports
+#[derive(Debug, Clone, Copy)]
+struct CsvOptions {
+    delimiter: u8,
+    header: bool,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        Self { delimiter: b',', header: true }
+    }
+}
+
+/// One CSV row per option; poll-level columns repeat on every row
+#[derive(Serialize)]
+struct CsvRow<'a> {
+    id: u64,
+    question: &'a str,
+    creator: &'a str,
+    created_at: &'a str,
+    end_time: &'a str,
+    is_active: bool,
+    poll_type: &'a str,
+    winner_metric: &'a str,
+    total_votes: u64,
+    total_weight: String,
+    option: &'a str,
+    votes: u64,
+    weight: String,
+    min_participation: u64,
+    quorum_status: quorum::QuorumState,
+}
+
+/// RFC 4180 CSV: fields containing the delimiter, quotes or newlines are quoted
+fn render_csv(export: &PollExport, options: CsvOptions) -> Result<String> {
+    let mut writer = csv::WriterBuilder::new()
+        .delimiter(options.delimiter)
+        .has_headers(options.header)
+        .terminator(csv::Terminator::CRLF)
+        .from_writer(Vec::new());
+
+    for (i, option) in export.options.iter().enumerate() {
+        writer.serialize(CsvRow {
+            id: export.id,
+            question: &export.question,
+            creator: &export.creator,
+            created_at: &export.created_at,
+            end_time: &export.end_time,
+            is_active: export.is_active,
+            poll_type: &export.poll_type,
+            winner_metric: &export.winner_metric,
+            total_votes: export.total_votes,
+            total_weight: export.total_weight.to_string(),
+            option,
+            votes: export.votes.get(i).copied().unwrap_or_default(),
+            weight: export.weights.get(i).copied().unwrap_or_default().to_string(),
+            min_participation: export.min_participation,
+            quorum_status: export.quorum_status,
+        })?;
+    }
+
+    let bytes = writer.into_inner().map_err(|e| anyhow::anyhow!("Failed to flush CSV: {}", e))?;
+    Ok(String::from_utf8(bytes)?)
+}
+
+/// Parse a CSV delimiter given as a single ASCII character, `tab` or `\t`
+fn parse_delimiter(value: &str) -> std::result::Result<u8, String> {
+    match value {
+        "tab" | "\\t" | "\t" => Ok(b'\t'),
+        _ if value.len() == 1 && value.is_ascii() && value != "\"" => Ok(value.as_bytes()[0]),
+        _ => Err(format!("'{}' is not a single ASCII delimiter character", value)),
+    }
+}
+
+fn render_markdown(export: &PollExport) -> String {
+    let mut md = String::new();
+    md.push_str(&format!("# Poll #{}: {}\n\n", export.id, export.question));
+    md.push_str(&format!("- **Status:** {}\n", if export.is_active { "Active" } else { "Closed" }));
+    md.push_str(&format!("- **Creator:** `{}`\n", export.creator));
+    md.push_str(&format!("- **Created:** {}\n", export.created_at));
+    md.push_str(&format!("- **Ends:** {}\n", export.end_time));
+    md.push_str(&format!("- **Total votes:** {}\n", export.total_votes));
+    if export.min_participation > 0 {
+        md.push_str(&format!("- **Quorum:** {} of {} votes ({})\n", export.total_votes, export.min_participation, export.quorum_status));
+    }
+
+    let weighted = export.winner_metric == "weight";
+    if weighted {
+        md.push_str(&format!("- **Total weight:** {}\n", numbers::format_integer(export.total_weight)));
+        md.push_str(&format!("- **Decided by:** weight ({} poll)\n\n", export.poll_type));
+        md.push_str("| # | Option | Votes | Weight | Share |\n");
+        md.push_str("|---|--------|------:|-------:|------:|\n");
+    } else {
+        md.push('\n');
+        md.push_str("| # | Option | Votes | Share |\n");
+        md.push_str("|---|--------|------:|------:|\n");
+    }
+
+    let deciding: Vec<U256> = if weighted {
+        export.weights.clone()
+    } else {
+        export.votes.iter().map(|v| U256::from(*v)).collect()
+    };
+    let total = u256_to_f64(deciding.iter().fold(U256::zero(), |sum, v| sum.saturating_add(*v)));
+    for (i, option) in export.options.iter().enumerate() {
+        let votes = export.votes.get(i).copied().unwrap_or(0);
+        let amount = deciding.get(i).copied().unwrap_or_default();
+        let share = if total > 0.0 { u256_to_f64(amount) / total * 100.0 } else { 0.0 };
+        let option = option.replace('|', "\\|");
+        if weighted {
+            md.push_str(&format!("| {} | {} | {} | {} | {:.1}% |\n", i, option,
+                numbers::format_integer(votes), numbers::format_integer(amount), share));
+        } else {
+            md.push_str(&format!("| {} | {} | {} | {:.1}% |\n", i, option, numbers::format_integer(votes), share));
+        }
+    }
+
+    md
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct PollAnalytics {
+    poll_id: u64,
+    question: String,
+    total_votes: u64,
+    #[serde(with = "numbers::decimal")]
+    total_weight: U256,
+    winner_metric: String,
+    participation_rate: f64,
+    min_participation: u64,
+    quorum: quorum::Quorum,
+    leading_option: String,
+    margin: f64,
+    time_remaining: Option<String>,
+    created_at: String,
+    created_at_unix: u64,
+    end_time_unix: u64,
+    options_detail: Vec<OptionDetail>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct OptionDetail {
+    index: usize,
+    option: String,
+    votes: u64,
+    percentage: f64,
+    #[serde(with = "numbers::decimal")]
+    weight: U256,
+    weight_percentage: f64,
+}
+
+#[derive(Parser)]
+#[command(name = "polling-cli")]
+#[command(about = "A CLI for interacting with the DecentralizedPolls smart contract")]
+#[command(after_help = "Exit codes:\n  0  success\n  1  unclassified error\n  2  invalid input or usage\n  3  configuration error (env vars, keys, addresses)\n  4  RPC or remote service failure\n  5  contract call reverted")]
+struct Cli {
+    /// Suppress standard output; errors are still reported on stderr
+    #[arg(long, global = true)]
+    quiet: bool,
+
+    /// Disable colored output (also honors the NO_COLOR environment variable)
+    #[arg(long, global = true)]
+    no_color: bool,
+
+    /// Plain output for logs: no colors, spinners or emoji (automatic when stdout is not a terminal)
+    #[arg(long, global = true)]
+    plain: bool,
+
+    /// Screen-reader friendly output: plain text, statuses spelled out like [ACTIVE], tables as key: value lines (also POLL_ACCESSIBLE)
+    #[arg(long, global = true)]
+    accessible: bool,
+
+    /// Timezone for displayed times: utc, local or an IANA name like Europe/Berlin (default: POLL_TIMEZONE, else utc)
+    #[arg(long, global = true)]
+    timezone: Option<timezone::DisplayZone>,
+
+    /// Skip the poll cache and read everything from the RPC endpoint
+    #[arg(long, global = true)]
+    no_cache: bool,
+
+    /// Cap RPC requests per second, for metered providers (default: POLL_MAX_RPS, else unlimited)
+    #[arg(long, global = true, value_name = "N")]
+    max_rps: Option<f64>,
+
+    /// Print how many RPC calls were made, per method, when the command finishes
+    #[arg(long, global = true)]
+    rpc_stats: bool,
+
+    /// Currency for fiat equivalents of gas costs and token amounts, or off (default: POLL_FIAT, else usd)
+    #[arg(long, global = true, value_enum)]
+    fiat: Option<price::Fiat>,
+
+    /// Stop signing once the key has been unlocked this long, e.g. 8h (for long-running commands)
+    #[arg(long, global = true, value_name = "DURATION", value_parser = duration::parse)]
+    session_timeout: Option<u64>,
+
+    /// Stop after sending this many transactions in one run
+    #[arg(long, global = true, value_name = "N")]
+    session_max_txs: Option<u64>,
+
+    /// Stop before total gas across this run's transactions would exceed N
+    #[arg(long, global = true, value_name = "N")]
+    session_max_gas: Option<u64>,
+
+    /// Send transactions on Ethereum mainnet even when the policy blocks mainnet writes
+    #[arg(long, global = true)]
+    allow_mainnet: bool,
+
+    /// Have the relayer at SPONSOR_URL pay gas for write transactions; you still sign them
+    #[arg(long, global = true)]
+    sponsor: bool,
+
+    /// Send write transactions through a private relay (Flashbots Protect, or PRIVATE_TX_URL) instead of the public mempool
+    #[arg(long, global = true, conflicts_with = "sponsor")]
+    private_tx: bool,
+
+    #[command(flatten)]
+    connection: connection::ConnectionArgs,
+
+    #[command(subcommand)]
+    command: Commands,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    /// Create a new enhanced poll
+    Create {
+        #[command(flatten)]
+        fields: spec::PollFields,
+        /// Start from a saved template; flags given here override it
+        #[arg(long)]
+        template: Option<String>,
+        /// Create the poll even if the content check finds problems
+        #[arg(long)]
+        force: bool,
+        /// Copy the new poll ID (or `tx` hash) to the clipboard
+        #[arg(long, value_enum, num_args = 0..=1, default_missing_value = "id")]
+        copy: Option<clipboard::CopyTarget>,
+    },
+    /// Vote on a poll
+    Vote {
+        /// Poll ID to vote on
+        #[arg(short, long)]
+        poll_id: PollRef,
+        /// Option index to vote for
+        #[arg(short, long)]
+        option: u64,
+        /// Queue the vote to be cast at this time instead of now, e.g. "2025-07-01 12:00" (UTC unless an offset is given)
+        #[arg(long, value_parser = duration::parse_ends_at, conflicts_with = "smart_account")]
+        at: Option<u64>,
+        /// Queue the vote to be cast once this block is mined
+        #[arg(long, value_name = "N", conflicts_with_all = ["at", "smart_account"])]
+        at_block: Option<u64>,
+        #[command(flatten)]
+        smart_account: user_op::SmartAccountArgs,
+    },
+    /// Vote as a delegate for someone else
+    VoteDelegate {
+        /// Poll ID to vote on
+        #[arg(short, long)]
+        poll_id: PollRef,
+        /// Option index to vote for
+        #[arg(short, long)]
+        option: u64,
+        /// Address of the person you're voting for
+        #[arg(short, long)]
+        delegator: String,
+    },
+    /// Set a delegate for your votes
+    SetDelegate {
+        /// Address of the delegate
+        #[arg(short, long)]
+        delegate: String,
+    },
+    /// Remove your current delegate
+    RemoveDelegate,
+    /// View poll details
+    View {
+        /// Poll ID to view
+        #[arg(short, long)]
+        poll_id: PollRef,
+    },
+    /// List polls with filtering options
+    List {
+        /// Filter by category
+        #[arg(short, long, value_parser = CATEGORIES, ignore_case = true)]
+        category: Option<String>,
+        /// Filter by tag
+        #[arg(short, long)]
+        tag: Option<String>,
+        /// Show only active polls
+        #[arg(long)]
+        active_only: bool,
+        /// Output format (table, or ndjson to stream one JSON object per poll as it is read)
+        #[arg(short, long, default_value = "table")]
+        format: String,
+        #[command(flatten)]
+        select: fields::SelectArgs,
+        #[command(flatten)]
+        query: query::QueryArgs,
+    },
+    /// View poll results
+    Results {
+        /// Poll ID to get results for
+        #[arg(short, long)]
+        poll_id: PollRef,
+        /// Keep the results on screen, redrawn as votes arrive, with a countdown until the poll closes
+        #[arg(long)]
+        follow: bool,
+        /// With --follow, redraw at least this often, e.g. 30s or 5m
+        #[arg(long, default_value = "30s", value_parser = duration::parse, requires = "follow")]
+        interval: u64,
+    },
+    /// Chart poll results as terminal bars, or as an SVG bar or pie chart
+    Chart {
+        /// Poll ID to chart
+        #[arg(short, long)]
+        poll_id: PollRef,
+        /// Chart type; pie charts need --output
+        #[arg(long, value_enum, default_value = "bar")]
+        kind: chart::ChartKind,
+        /// Write an SVG file instead of drawing in the terminal
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+    /// Close a poll (creator only)
+    Close {
+        /// Poll ID to close
+        #[arg(short, long)]
+        poll_id: PollRef,
+        /// Close without asking for confirmation (required when not at a terminal)
+        #[arg(short, long)]
+        yes: bool,
+        /// Close even if nobody has voted
+        #[arg(long)]
+        force: bool,
+    },
+    /// Extend a poll duration (creator only)
+    Extend {
+        /// Poll ID to extend
+        #[arg(short, long)]
+        poll_id: PollRef,
+        /// Time to add, such as 2d or 90m; a bare number is hours
+        #[arg(short = 'd', long, short_alias = 'H', alias = "hours", value_parser = duration::parse, required_unless_present = "ends_at", conflicts_with = "ends_at")]
+        duration: Option<u64>,
+        /// New absolute end time, e.g. 2025-07-01T18:00Z
+        #[arg(long, value_parser = duration::parse_ends_at)]
+        ends_at: Option<u64>,
+        /// Extend without asking for confirmation (required when not at a terminal)
+        #[arg(short, long)]
+        yes: bool,
+    },
+    /// Housekeeping across every poll on the contract
+    Maintain {
+        #[command(subcommand)]
+        action: MaintainCommands,
+    },
+    /// Extend every poll you created with a tag, after a table of new end times; closed or overlong polls are refused
+    ExtendBatch {
+        /// Tag the polls carry, e.g. cycle-12 (case-insensitive)
+        #[arg(long)]
+        tag: String,
+        /// Time to add to each, such as 2d or 90m; a bare number is hours
+        #[arg(short = 'd', long, short_alias = 'H', alias = "hours", value_parser = duration::parse)]
+        duration: u64,
+        /// Extend without asking for confirmation (required when not at a terminal)
+        #[arg(short, long)]
+        yes: bool,
+    },
+    /// View your created polls with what needs closing, extending or attention
+    MyPolls {
+        /// Only report; never ask to close or extend polls
+        #[arg(long)]
+        no_prompt: bool,
+    },
+    /// Confirm on-chain that a vote was recorded and save a proof bundle
+    VerifyVote {
+        /// Poll ID the vote was cast on
+        #[arg(short, long)]
+        poll_id: PollRef,
+        /// Voter address (defaults to your wallet)
+        #[arg(short, long)]
+        address: Option<String>,
+        /// Vote transaction hash, to check that specific transaction
+        #[arg(long)]
+        tx: Option<H256>,
+        /// Output format (table, json)
+        #[arg(short, long, default_value = "table")]
+        format: String,
+        /// Write the proof bundle (JSON) to this file
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+    /// Gas you have spent on this contract by function, with an estimator for planned operations
+    Costs {
+        /// Planned operations to estimate, e.g. create=3,vote=20,close=3
+        #[arg(long)]
+        plan: Option<String>,
+        /// ETH price in the --fiat currency instead of looking it up
+        #[arg(long)]
+        eth_price: Option<f64>,
+        /// Output format (table, json)
+        #[arg(short, long, default_value = "table")]
+        format: String,
+        /// Output file path (json only)
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+    /// Warn about your polls expiring short of quorum, ended but never closed, or open with a lead non-voters could overturn
+    CreatorAlerts {
+        /// How far ahead to look for polls expiring short of quorum, e.g. 24h or 2d
+        #[arg(long, default_value = "24h", value_parser = duration::parse)]
+        within: u64,
+        /// Keep running and re-check at this interval, e.g. 15m, reporting only new alerts
+        #[arg(long, value_parser = duration::parse)]
+        watch: Option<u64>,
+        /// Output format (table, json; json prints one alert per line with --watch)
+        #[arg(short, long, default_value = "table")]
+        format: String,
+    },
+    /// Warn when your delegate hasn't voted for you on polls closing soon, and when someone delegates to you or stops
+    DelegationAlerts {
+        /// How far ahead to look for polls your delegate hasn't voted on, e.g. 24h or 2d
+        #[arg(long, default_value = "24h", value_parser = duration::parse)]
+        within: u64,
+        /// How far back to report delegations to and from you
+        #[arg(long, default_value = "7d", value_parser = duration::parse)]
+        since: u64,
+        /// Keep running and re-check at this interval, e.g. 15m, reporting only new alerts
+        #[arg(long, value_parser = duration::parse)]
+        watch: Option<u64>,
+        /// Output format (table, json; json prints one alert per line with --watch)
+        #[arg(short, long, default_value = "table")]
+        format: String,
+    },
+    /// View polls user has voted on
+    MyVotes,
+    /// View user statistics
+    MyStats,
+    /// Rank delegates, and publish, fetch and show signed delegate profiles kept on IPFS
+    Delegates {
+        #[command(subcommand)]
+        action: DelegateCommands,
+    },
+    /// One address's full profile: stats, created polls and their outcomes, votes cast, delegation and voting power
+    UserReport {
+        /// Address to report on (defaults to your address)
+        address: Option<Address>,
+        /// Output format (table, json)
+        #[arg(short, long, default_value = "table")]
+        format: String,
+        /// Output file path (json only)
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+    /// View delegation information
+    Delegation {
+        /// Address to check delegation for (optional, defaults to your address)
+        #[arg(short, long)]
+        address: Option<String>,
+    },
+    /// Check token balance
+    TokenBalance {
+        /// Token contract address (optional, uses governance token if not specified)
+        #[arg(short, long)]
+        token: Option<String>,
+        /// Address to check (optional, defaults to your address)
+        #[arg(short, long)]
+        address: Option<String>,
+    },
+    /// Export poll data to various formats
+    Export {
+        /// Poll ID to export; xlsx exports every poll without it
+        #[arg(short, long)]
+        poll_id: Option<PollRef>,
+        /// Export format (json, csv, table, markdown, png for a social preview card, pdf for a closed poll's report, xlsx for a workbook)
+        #[arg(short, long, default_value = "json")]
+        format: String,
+        /// Output file path
+        #[arg(short, long)]
+        output: Option<String>,
+        /// CSV field delimiter: a single character, or `tab`
+        #[arg(long, default_value = ",", value_parser = parse_delimiter)]
+        delimiter: u8,
+        /// Omit the CSV header row
+        #[arg(long)]
+        no_header: bool,
+        #[command(flatten)]
+        select: fields::SelectArgs,
+    },
+    /// Generate comprehensive poll analytics
+    Analytics {
+        /// Poll ID for analytics (optional, shows all if not provided)
+        #[arg(short, long)]
+        poll_id: Option<PollRef>,
+    },
+    /// Analyze how voting activity evolves over time
+    Velocity {
+        /// Poll ID to analyze
+        #[arg(short, long)]
+        poll_id: PollRef,
+        /// Bucket size: hourly, daily
+        #[arg(short, long, default_value = "hourly")]
+        bucket: String,
+        /// Output format (table, json)
+        #[arg(short, long, default_value = "table")]
+        format: String,
+        /// Output file path (json only)
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+    /// Analyze voting weight concentration for weighted/quadratic polls
+    Whales {
+        /// Poll ID to analyze
+        #[arg(short, long)]
+        poll_id: PollRef,
+        /// Number of top voters to examine
+        #[arg(short = 'n', long, default_value = "5")]
+        top: usize,
+        /// Output format (table, json)
+        #[arg(short, long, default_value = "table")]
+        format: String,
+        /// Output file path (json only)
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+    /// Relate a poll's voters and cast weight to the token's holders and supply at its start block
+    Turnout {
+        /// Poll ID to benchmark
+        #[arg(short, long)]
+        poll_id: PollRef,
+        /// Token whose holders make up the electorate (default: GOVERNANCE_TOKEN_ADDRESS)
+        #[arg(short, long)]
+        token: Option<Address>,
+        /// Output format (table, json)
+        #[arg(short, long, default_value = "table")]
+        format: String,
+        /// Output file path (json only)
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+    /// Rank the most active poll creators, most active voters or highest-weight voters from indexed events
+    Leaderboard {
+        /// What to rank
+        #[arg(short, long, value_enum, default_value = "voters")]
+        by: leaderboard::Board,
+        /// Only count activity from this far back, e.g. 30d (default: all time)
+        #[arg(long, value_parser = duration::parse)]
+        since: Option<u64>,
+        /// Number of places to show
+        #[arg(short = 'n', long, default_value = "10")]
+        top: usize,
+        /// Output format (table, csv, json)
+        #[arg(short, long, default_value = "table")]
+        format: String,
+        /// Output file path (csv and json)
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+    /// Cross-tab how the voters of each option in one poll voted in another, revealing voting blocs
+    Correlate {
+        /// Poll whose options group the voters
+        #[arg(short, long)]
+        poll_id: PollRef,
+        /// Poll to see how each group voted in
+        #[arg(long)]
+        with: PollRef,
+        /// Output format (table, csv, json)
+        #[arg(short, long, default_value = "table")]
+        format: String,
+        /// Output file path (csv and json)
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+    /// Voting streaks, first-time voters per poll and monthly engagement, for community incentive programs
+    Engagement {
+        #[command(subcommand)]
+        action: EngagementCommands,
+    },
+    /// Project final turnout, quorum and leader for a poll
+    Project {
+        /// Poll ID to project
+        #[arg(short, long)]
+        poll_id: PollRef,
+        /// Projection model: linear, recent, decay
+        #[arg(short, long, default_value = "recent")]
+        model: String,
+        /// Output format (table, json)
+        #[arg(short, long, default_value = "table")]
+        format: String,
+        /// Output file path (json only)
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+    /// Replay hypothetical votes on a local Anvil fork and report the outcome and quorum they would produce
+    Simulate {
+        /// Poll ID to simulate
+        #[arg(short, long)]
+        poll_id: PollRef,
+        /// Hypothetical vote as ADDRESS=OPTION (repeatable)
+        #[arg(long = "vote", value_name = "ADDRESS=OPTION", value_parser = simulate::parse_hypothetical_vote)]
+        votes: Vec<(Address, u64)>,
+        /// Also have the N delegates with the most delegators vote --option
+        #[arg(long, value_name = "N", requires = "option")]
+        top_delegates: Option<usize>,
+        /// Option the top delegates vote for
+        #[arg(long, requires = "top_delegates")]
+        option: Option<u64>,
+        /// Anvil binary used to fork the chain
+        #[arg(long, default_value = "anvil")]
+        anvil: String,
+        /// Output format (table, json)
+        #[arg(short, long, default_value = "table")]
+        format: String,
+    },
+    /// Produce a signed, tamper-evident certificate of a closed poll's results
+    Certify {
+        /// Poll ID to certify
+        #[arg(short, long)]
+        poll_id: PollRef,
+        /// Output file path (default: poll-<id>-certificate.json)
+        #[arg(short, long)]
+        output: Option<String>,
+        /// Also attest the result hash on-chain with the Ethereum Attestation Service
+        #[arg(long)]
+        eas: bool,
+    },
+    /// Check an EAS result attestation made by `certify --eas`
+    VerifyAttestation {
+        /// Attestation UID
+        uid: H256,
+        /// Certificate the attestation should match
+        #[arg(long)]
+        certificate: Option<std::path::PathBuf>,
+        /// Require this attester (default: the certificate's signer)
+        #[arg(long)]
+        attester: Option<Address>,
+    },
+    /// Have designated observers each sign a closed poll's result, and verify the combined bundle
+    Attest {
+        #[command(subcommand)]
+        action: AttestCommands,
+    },
+    /// Publish a poll report to external storage
+    Publish {
+        /// Poll ID to publish
+        #[arg(short, long)]
+        poll_id: PollRef,
+        /// Pin the report to IPFS
+        #[arg(long)]
+        ipfs: bool,
+        /// Report format (json, markdown)
+        #[arg(short, long, default_value = "json")]
+        format: String,
+    },
+    /// Announce a poll's creation or result on X, Farcaster or Matrix, once or for every poll from now on
+    Announce {
+        /// Poll ID to announce (not needed with --watch)
+        #[arg(short, long, required_unless_present = "watch")]
+        poll_id: Option<PollRef>,
+        /// What to announce
+        #[arg(short, long, value_enum, default_value = "created")]
+        event: announce::PollEvent,
+        /// Comma-separated channels to post to (x, farcaster, matrix); prints the announcement if omitted
+        #[arg(long, value_enum, value_delimiter = ',')]
+        to: Vec<announce::Channel>,
+        /// Link template for the poll, with {id} replaced by the poll ID
+        #[arg(long)]
+        link: Option<String>,
+        /// Keep running and announce every poll created or closed, checking at this interval, e.g. 1m
+        #[arg(long, value_parser = duration::parse, conflicts_with_all = ["poll_id", "event"])]
+        watch: Option<u64>,
+    },
+    /// Render a finished poll's results as a forum post, optionally publishing it to Discourse
+    PostResults {
+        /// Poll ID to post
+        #[arg(short, long)]
+        poll_id: PollRef,
+        /// Forum the post is written for
+        #[arg(short, long, value_enum, default_value = "discourse")]
+        target: forum::ForumTarget,
+        /// Publish through the Discourse API (DISCOURSE_URL, DISCOURSE_API_KEY)
+        #[arg(long)]
+        publish: bool,
+        /// Reply in an existing Discourse topic instead of starting one
+        #[arg(long)]
+        topic_id: Option<u64>,
+        /// Also write the post's Markdown to this file
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+    /// Per-category overview: active/closed polls, votes and the latest poll
+    Categories {
+        /// Output format (table, json)
+        #[arg(short, long, default_value = "table")]
+        format: String,
+        /// Output file path (json only)
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+    /// Discover tags and analyze polls by tag
+    Tags {
+        #[command(subcommand)]
+        action: TagCommands,
+    },
+    /// Save, list and apply reusable poll templates
+    Template {
+        #[command(subcommand)]
+        action: TemplateCommands,
+    },
+    /// Compose polls locally, review them and submit once approved
+    Draft {
+        #[command(subcommand)]
+        action: DraftCommands,
+    },
+    /// List, cancel and cast votes queued with `vote --at`
+    Schedule {
+        #[command(subcommand)]
+        action: ScheduleCommands,
+    },
+    /// Vote or queue recommendations on new polls according to rules.json
+    Rules {
+        #[command(subcommand)]
+        action: RuleCommands,
+    },
+    /// Register keystore signing accounts by name for --from
+    Accounts {
+        #[command(subcommand)]
+        action: AccountCommands,
+    },
+    /// Keep signing keys in the OS keyring instead of PRIVATE_KEY
+    Key {
+        #[command(subcommand)]
+        action: KeyCommands,
+    },
+    /// Register EnhancedPolls deployments by name for --contract
+    Contracts {
+        #[command(subcommand)]
+        action: ContractCommands,
+    },
+    /// Client-side limits on transactions and new polls
+    Policy {
+        #[command(subcommand)]
+        action: PolicyCommands,
+    },
+    /// Flag sybil patterns in a poll for election review: fresh voters in the same blocks, shared funders, late weight
+    Audit {
+        /// Poll ID to review
+        #[arg(short, long)]
+        poll_id: PollRef,
+        /// Output format (table, json)
+        #[arg(short, long, default_value = "table")]
+        format: String,
+        /// Output file path (json only)
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+    /// Check whether an address can vote on a poll now: status, prior vote, delegation, voting power and identity score
+    Eligibility {
+        /// Poll ID to check
+        #[arg(short, long)]
+        poll_id: PollRef,
+        /// Address to check (default: your address)
+        #[arg(short, long)]
+        address: Option<Address>,
+        /// Output format (table, json)
+        #[arg(short, long, default_value = "table")]
+        format: String,
+    },
+    /// Split a reward pool between a closed poll's voters and export the payouts as CSV or a Merkle distribution
+    Rewards {
+        /// Poll ID to reward
+        #[arg(short, long)]
+        poll_id: PollRef,
+        /// Reward pool in whole tokens
+        #[arg(long)]
+        pool: u64,
+        /// How to split the pool
+        #[arg(long, value_enum, default_value = "flat")]
+        scheme: rewards::RewardScheme,
+        /// Decimals of the reward token
+        #[arg(long, default_value_t = 18, value_parser = clap::value_parser!(u8).range(0..=36))]
+        decimals: u8,
+        /// Output format (table, csv, json)
+        #[arg(short, long, default_value = "table")]
+        format: String,
+        /// Output file path (csv and json)
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+    /// Every holder's balance of a token at a block (--block), from its Transfer events
+    Snapshot {
+        /// Token contract (default: GOVERNANCE_TOKEN_ADDRESS)
+        #[arg(short, long)]
+        token: Option<Address>,
+        /// Output format (csv, json, table)
+        #[arg(short, long, default_value = "csv")]
+        format: String,
+        /// Output file path (csv and json)
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+    /// Build Merkle allowlists for gated polls and look up voters' proofs
+    Allowlist {
+        #[command(subcommand)]
+        action: AllowlistCommands,
+    },
+    /// Review write operations recorded on this machine, and re-run one
+    History {
+        /// Show this many of the most recent operations
+        #[arg(short, long, default_value_t = 20)]
+        limit: usize,
+        /// Output format (table, json, ndjson)
+        #[arg(short, long, default_value = "table")]
+        format: String,
+        /// Re-run operation number N from the list, after confirmation
+        #[arg(long, value_name = "N", conflicts_with_all = ["limit", "format", "fields", "template"])]
+        rerun: Option<usize>,
+        #[command(flatten)]
+        select: fields::SelectArgs,
+    },
+    /// Manage the local poll cache
+    Cache {
+        #[command(subcommand)]
+        action: CacheCommands,
+    },
+    /// Bridge polls to and from Snapshot.org proposals
+    SnapshotOrg {
+        #[command(subcommand)]
+        action: SnapshotOrgCommands,
+    },
+    /// Generate an iCalendar (.ics) file of poll deadlines
+    Calendar {
+        /// Only include polls you created
+        #[arg(long)]
+        mine: bool,
+        /// Minutes before each deadline to trigger a reminder
+        #[arg(short, long, default_value = "60")]
+        reminder: u64,
+        /// Output file path (prints to stdout if omitted)
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+    /// Generate an RSS/Atom feed of newly created polls
+    Feed {
+        /// Feed format (rss, atom)
+        #[arg(short, long, default_value = "rss")]
+        format: String,
+        /// Filter by category
+        #[arg(short, long, value_parser = CATEGORIES, ignore_case = true)]
+        category: Option<String>,
+        /// Maximum number of polls to include
+        #[arg(short, long, default_value = "50")]
+        limit: usize,
+        /// Link template for each poll, with {id} replaced by the poll ID
+        #[arg(long)]
+        link: Option<String>,
+        /// Output file path (prints to stdout if omitted)
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+    /// Index contract events locally, resuming from the last synced block
+    Sync {
+        /// Backfill from this block instead of resuming after the checkpoint
+        #[arg(long)]
+        from_block: Option<u64>,
+        /// Stop at this block instead of the latest
+        #[arg(long)]
+        to_block: Option<u64>,
+        /// Blocks re-scanned below the checkpoint when a reorg is detected
+        #[arg(long, default_value_t = indexer::DEFAULT_REORG_DEPTH)]
+        reorg_depth: u64,
+        /// Keep syncing at this interval, e.g. 30s
+        #[arg(long, value_parser = duration::parse)]
+        watch: Option<u64>,
+    },
+    /// Sync the event index and show what changed since its last sync: new polls, votes per option, status changes and delegations
+    Diff {
+        /// Output format (table, json)
+        #[arg(short, long, default_value = "table")]
+        format: String,
+    },
+    /// Bundle the contract's complete history for retention and offline analysis
+    Archive {
+        #[command(subcommand)]
+        action: ArchiveCommands,
+    },
+    /// Generate shell completions or a man page
+    Completions {
+        /// Shell to generate completions for
+        #[arg(value_enum, required_unless_present = "mangen")]
+        shell: Option<clap_complete::Shell>,
+        /// Print a roff man page instead of completions
+        #[arg(long, conflicts_with = "shell")]
+        mangen: bool,
+    },
+    /// Validate RPC, contract, signer and token configuration
+    Doctor,
+    /// Serve a caching JSON-RPC proxy for the polls contract that teammates can share as their RPC_URL
+    Proxy {
+        /// Address to listen on; use 0.0.0.0:<port> to accept other machines
+        #[arg(long, default_value = proxy::DEFAULT_LISTEN)]
+        listen: std::net::SocketAddr,
+    },
+    /// Turn GitHub proposal issues into poll drafts and post links and results back to them
+    Github {
+        #[command(subcommand)]
+        action: GithubCommands,
+    },
+    /// Run a chat bot that answers poll commands
+    Bot {
+        #[command(subcommand)]
+        platform: BotCommands,
+    },
+    /// Serve poll data over HTTP; with --operator, also let trusted bots request pre-approved votes
+    Serve {
+        /// Address to listen on; use 0.0.0.0:<port> to accept other machines
+        #[arg(long, default_value = serve::DEFAULT_LISTEN)]
+        listen: std::net::SocketAddr,
+        /// Operator file (YAML) with the API keys and the operations each may request
+        #[arg(long)]
+        operator: Option<std::path::PathBuf>,
+    },
+    /// Serve the read-only poll API over gRPC (proto/polls.proto) for typed clients in other languages
+    Grpc {
+        /// Address to listen on; use 0.0.0.0:<port> to accept other machines
+        #[arg(long, default_value = grpc::DEFAULT_LISTEN)]
+        listen: std::net::SocketAddr,
+    },
+    /// Build a static results site (HTML, CSS and JSON) for GitHub Pages
+    Site {
+        #[command(subcommand)]
+        action: SiteCommands,
+    },
+    /// Run Rhai scripts for custom reports over polls, results and events
+    Script {
+        #[command(subcommand)]
+        action: ScriptCommands,
+    },
+    /// Run shell commands from hooks.json when polls are created, votes cast or polls closed
+    Hooks {
+        #[command(subcommand)]
+        action: HookCommands,
+    },
+    /// List installed plugins: polling-cli-<name> executables on PATH or in POLL_CLI_HOME/plugins
+    Plugins,
+    /// List poll IDs for shell completion
+    #[command(name = "__poll-ids", hide = true)]
+    PollIds,
+    /// Any other command runs the polling-cli-<name> plugin
+    #[command(external_subcommand)]
+    Plugin(Vec<String>),
+}
+
+impl Commands {
+    /// Whether this command sends transactions, and so is recorded in the audit log
+    fn is_write(&self) -> bool {
+        matches!(self,
+            Commands::Create { .. }
+            | Commands::Vote { at: None, at_block: None, .. }
+            | Commands::VoteDelegate { .. }
+            | Commands::SetDelegate { .. }
+            | Commands::RemoveDelegate
+            | Commands::Close { .. }
+            | Commands::Extend { .. }
+            | Commands::ExtendBatch { .. }
+            | Commands::Maintain { action: MaintainCommands::CloseExpired { dry_run: false, .. } }
+            | Commands::Certify { eas: true, .. }
+            | Commands::Draft { action: DraftCommands::Submit { .. } }
+            | Commands::Schedule { action: ScheduleCommands::Run { .. } }
+            | Commands::Rules { action: RuleCommands::Check { .. } | RuleCommands::Approve { .. } }
+            | Commands::Template { action: TemplateCommands::Apply { .. } }
+            | Commands::SnapshotOrg { action: SnapshotOrgCommands::Import { .. } })
+    }
+
+    /// The poll this command acts on, as given on the command line
+    fn poll_ref(&self) -> Option<&PollRef> {
+        match self {
+            Commands::Vote { poll_id, .. }
+            | Commands::VoteDelegate { poll_id, .. }
+            | Commands::View { poll_id }
+            | Commands::Results { poll_id, .. }
+            | Commands::Chart { poll_id, .. }
+            | Commands::Close { poll_id, .. }
+            | Commands::Extend { poll_id, .. }
+            | Commands::VerifyVote { poll_id, .. }
+            | Commands::Export { poll_id: Some(poll_id), .. }
+            | Commands::Analytics { poll_id: Some(poll_id) }
+            | Commands::Velocity { poll_id, .. }
+            | Commands::Whales { poll_id, .. }
+            | Commands::Correlate { poll_id, .. }
+            | Commands::Turnout { poll_id, .. }
+            | Commands::Audit { poll_id, .. }
+            | Commands::Rewards { poll_id, .. }
+            | Commands::Eligibility { poll_id, .. }
+            | Commands::Project { poll_id, .. }
+            | Commands::Simulate { poll_id, .. }
+            | Commands::Certify { poll_id, .. }
+            | Commands::Attest { action: AttestCommands::Init { poll_id, .. } }
+            | Commands::Publish { poll_id, .. }
+            | Commands::PostResults { poll_id, .. }
+            | Commands::Announce { poll_id: Some(poll_id), .. }
+            | Commands::SnapshotOrg { action: SnapshotOrgCommands::Export { poll_id, .. } } => Some(poll_id),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Subcommand)]
+enum SiteCommands {
+    /// Render every poll, with index, category and tag pages; only changed polls are read again
+    Build {
+        /// Directory to write the site to
+        #[arg(long, default_value = "public")]
+        out: std::path::PathBuf,
+        /// Site title shown on every page
+        #[arg(long, default_value = "Poll Results")]
+        title: String,
+        /// Rebuild every poll page, not only those with new events
+        #[arg(long)]
+        full: bool,
+        /// Public URL the site is served from; link previews need absolute image URLs
+        #[arg(long)]
+        base_url: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum ScriptCommands {
+    /// Evaluate a script with get_poll, get_results, poll_count and events; prints what it returns
+    Run {
+        /// Path to the .rhai script
+        file: std::path::PathBuf,
+    },
+}
+
+#[derive(Subcommand)]
+enum HookCommands {
+    /// Show the configured hooks
+    List,
+    /// Follow the contract's events and run the hooks for each, until Ctrl-C
+    Watch {
+        /// How often to check for new events, e.g. 30s
+        #[arg(long, default_value = "15s", value_parser = duration::parse)]
+        interval: u64,
+        /// Also run hooks for events since this block, instead of only new ones
+        #[arg(long)]
+        from_block: Option<u64>,
+    },
+}
+
+#[derive(Subcommand)]
+#[allow(clippy::large_enum_variant)] // parsed once per run
+enum GithubCommands {
+    /// Create a draft from an issue (GITHUB_TOKEN): title, task-list options, body and labels as tags
+    Import {
+        /// Repository as owner/name
+        #[arg(long)]
+        repo: String,
+        /// Issue number
+        #[arg(long)]
+        issue: u64,
+        /// Flags override what is read from the issue
+        #[command(flatten)]
+        fields: spec::PollFields,
+    },
+    /// Comment the poll link on imported issues once submitted, and the results once the poll ends
+    Sync,
+}
+
+#[derive(Subcommand)]
+enum BotCommands {
+    /// Telegram bot (TELEGRAM_BOT_TOKEN): /polls, /results <id> with live-updating results
+    Telegram {
+        /// How often live results check the chain for new votes, e.g. 10s
+        #[arg(long, default_value = "5s", value_parser = duration::parse)]
+        interval: u64,
+    },
+    /// Matrix bot (MATRIX_HOMESERVER, MATRIX_ACCESS_TOKEN): answers `!poll results <id>` in joined rooms
+    Matrix,
+}
+
+#[derive(Subcommand)]
+enum ArchiveCommands {
+    /// Export every poll, vote and delegation event to a JSON bundle (gzip-compressed for .gz paths)
+    Export {
+        /// Output file path (defaults to archive-<chain>-<block>.json.gz)
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+    /// Restore an archive into the local event index, e.g. on a new machine
+    Import {
+        /// Archive file (.json or .json.gz)
+        file: String,
+        /// Replace a local index that is synced past the archive's block
+        #[arg(long)]
+        force: bool,
+    },
+    /// Compare two archives: new polls, vote deltas and delegation changes
+    Diff {
+        /// The older archive
+        before: String,
+        /// The newer archive
+        after: String,
+        /// Output format (table, json)
+        #[arg(short, long, default_value = "table")]
+        format: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum TagCommands {
+    /// List every tag used across polls with poll counts
+    List {
+        /// Output format (table, json)
+        #[arg(short, long, default_value = "table")]
+        format: String,
+        /// Output file path (json only)
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+    /// Turnout and outcomes over time for polls with a tag (case-insensitive)
+    Stats {
+        /// Tag to analyze
+        tag: String,
+        /// Output format (table, json)
+        #[arg(short, long, default_value = "table")]
+        format: String,
+        /// Output file path (json only)
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum EngagementCommands {
+    /// Each voter's current and longest run of consecutive polls voted in
+    Streaks {
+        /// Number of voters to show
+        #[arg(short = 'n', long, default_value = "20")]
+        top: usize,
+        /// Output format (table, csv, json)
+        #[arg(short, long, default_value = "table")]
+        format: String,
+        /// Output file path (csv and json)
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+    /// How many of each poll's voters had never voted before
+    FirstTime {
+        /// Output format (table, csv, json)
+        #[arg(short, long, default_value = "table")]
+        format: String,
+        /// Output file path (csv and json)
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+    /// Polls created, votes, and new and returning voters per calendar month (UTC)
+    Monthly {
+        /// Output format (table, csv, json)
+        #[arg(short, long, default_value = "table")]
+        format: String,
+        /// Output file path (csv and json)
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum TemplateCommands {
+    /// Save a poll definition (options, type, category, tags, duration) under a name
+    Save {
+        /// Template name
+        name: String,
+        #[command(flatten)]
+        fields: spec::PollFields,
+    },
+    /// List saved templates
+    List,
+    /// Create a poll from a template, e.g. `template apply proposal -q "Fund grant #42"`
+    Apply {
+        /// Template name
+        name: String,
+        #[command(flatten)]
+        fields: spec::PollFields,
+        /// Create the poll even if the content check finds problems
+        #[arg(long)]
+        force: bool,
+        /// Copy the new poll ID (or `tx` hash) to the clipboard
+        #[arg(long, value_enum, num_args = 0..=1, default_missing_value = "id")]
+        copy: Option<clipboard::CopyTarget>,
+    },
+}
+
+#[derive(Subcommand)]
+enum DraftCommands {
+    /// Start a draft from the same flags as `create`
+    New {
+        #[command(flatten)]
+        fields: spec::PollFields,
+        /// Start from a saved template
+        #[arg(long)]
+        template: Option<String>,
+    },
+    /// Change fields of a draft; clears its approvals
+    Edit {
+        /// Draft ID
+        id: u64,
+        #[command(flatten)]
+        fields: spec::PollFields,
+    },
+    /// List drafts and their review status
+    List,
+    /// Print a draft for review
+    Show {
+        /// Draft ID
+        id: u64,
+        /// Output format (markdown, json)
+        #[arg(short, long, default_value = "markdown")]
+        format: String,
+        /// Output file path
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+    /// Record a reviewer's approval of the current draft text
+    Approve {
+        /// Draft ID
+        id: u64,
+        /// Name of the approving reviewer
+        #[arg(long)]
+        by: String,
+    },
+    /// Create the poll on-chain from an approved draft
+    Submit {
+        /// Draft ID
+        id: u64,
+        /// Submit even if the content check finds problems
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum MaintainCommands {
+    /// Close every poll you created that has ended but is still Active
+    CloseExpired {
+        /// Gas budget for the whole batch; polls past it are left for the next run
+        #[arg(long)]
+        max_gas: Option<u64>,
+        /// Show what would be closed and the gas it would take, without sending anything
+        #[arg(long)]
+        dry_run: bool,
+        /// Close without asking for confirmation (required when not at a terminal)
+        #[arg(short, long)]
+        yes: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum ScheduleCommands {
+    /// List scheduled votes and how they ran
+    List,
+    /// Drop a scheduled vote that has not run yet
+    Cancel {
+        /// Scheduled vote ID
+        id: u64,
+    },
+    /// Cast the scheduled votes that are due for this signer and contract
+    Run {
+        /// Keep running and check again at this interval, e.g. 1m
+        #[arg(long, value_parser = duration::parse)]
+        watch: Option<u64>,
+    },
+}
+
+#[derive(Subcommand)]
+enum AttestCommands {
+    /// Start an attestation bundle for a closed poll's result
+    Init {
+        /// Poll ID to attest
+        #[arg(short, long)]
+        poll_id: PollRef,
+        /// Comma-separated addresses of the designated observers
+        #[arg(long, value_delimiter = ',', required = true)]
+        observers: Vec<Address>,
+        /// Signatures needed for the attestation to hold
+        #[arg(long)]
+        threshold: usize,
+        /// Output file path (default: poll-<id>-attestation.json)
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+    /// Re-read the result from the chain and sign the bundle with your key if it matches
+    Sign {
+        /// Attestation bundle
+        file: std::path::PathBuf,
+    },
+    /// Merge the signatures from other observers' copies of the bundle
+    Collect {
+        /// Bundle to merge into
+        file: std::path::PathBuf,
+        /// Observers' copies
+        #[arg(required = true)]
+        copies: Vec<std::path::PathBuf>,
+    },
+    /// Check every signature and whether enough observers signed
+    Verify {
+        /// Attestation bundle
+        file: std::path::PathBuf,
+        /// Comma-separated addresses of the observers you expect; the bundle's own list is not trusted
+        #[arg(long, value_delimiter = ',', required = true)]
+        observers: Vec<Address>,
+        /// Signatures you require
+        #[arg(long)]
+        threshold: usize,
+    },
+}
+
+#[derive(Subcommand)]
+enum AllowlistCommands {
+    /// Compute the Merkle root and every address's proof from a CSV or a token-holder snapshot
+    Build {
+        /// CSV file whose first column lists the eligible addresses
+        #[arg(long, required_unless_present = "holders_at", conflicts_with = "holders_at")]
+        csv: Option<std::path::PathBuf>,
+        /// Take the holders of the governance token (or --token) at this block instead
+        #[arg(long, value_name = "BLOCK")]
+        holders_at: Option<u64>,
+        /// Token to snapshot instead of GOVERNANCE_TOKEN_ADDRESS
+        #[arg(long, requires = "holders_at")]
+        token: Option<Address>,
+        /// Minimum whole-token balance at the snapshot block (0 keeps every holder)
+        #[arg(long, default_value_t = 0, requires = "holders_at")]
+        min_balance: u64,
+        /// Where to write the root and proofs
+        #[arg(short, long, default_value = "allowlist.json")]
+        output: String,
+    },
+    /// Print the proof a voter submits with their vote
+    Prove {
+        /// Voter address
+        address: Address,
+        /// Allowlist written by `allowlist build`
+        #[arg(long, default_value = "allowlist.json")]
+        file: std::path::PathBuf,
+        /// Output format (table, json)
+        #[arg(short, long, default_value = "table")]
+        format: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum DelegateCommands {
+    /// Rank delegates by the governance token weight delegated to them, with their participation
+    Top {
+        /// How many delegates to show
+        #[arg(short, long, default_value = "20")]
+        limit: usize,
+        /// Output format (table, json)
+        #[arg(short, long, default_value = "table")]
+        format: String,
+        /// Output file path (json only)
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+    /// Sign your delegate profile and pin it to IPFS
+    Publish {
+        /// Why delegators should choose you
+        #[arg(long)]
+        statement: String,
+        /// How to reach you, e.g. a forum handle or email
+        #[arg(long)]
+        contact: Option<String>,
+        /// Comma-separated areas you focus on, e.g. treasury,security
+        #[arg(long)]
+        focus: Option<String>,
+    },
+    /// Download a profile by CID, verify its signature and keep it for `delegation`
+    Fetch {
+        /// IPFS CID of the profile
+        cid: String,
+    },
+    /// Show a delegate's locally known profile
+    Show {
+        /// Delegate address
+        address: Address,
+    },
+}
+
+#[derive(Subcommand)]
+enum RuleCommands {
+    /// Evaluate the rules against active polls, voting where a rule says auto and queueing the rest
+    Check {
+        /// Rules file to use instead of rules.json in the data directory
+        #[arg(long)]
+        file: Option<std::path::PathBuf>,
+        /// Keep running and evaluate new polls at this interval, e.g. 10m
+        #[arg(long, value_parser = duration::parse)]
+        watch: Option<u64>,
+    },
+    /// List recommendations and what became of them
+    Queue,
+    /// Cast the vote a queued recommendation suggests
+    Approve {
+        /// Recommendation ID
+        id: u64,
+    },
+    /// Drop a queued recommendation without voting
+    Dismiss {
+        /// Recommendation ID
+        id: u64,
+    },
+}
+
+#[derive(Subcommand)]
+enum AccountCommands {
+    /// Register an encrypted JSON keystore under a name, e.g. `accounts add treasury --keystore ~/keys/treasury.json`
+    Add {
+        /// Account name
+        name: String,
+        /// Path to the keystore file (as written by geth, cast wallet or clef)
+        #[arg(long)]
+        keystore: std::path::PathBuf,
+    },
+    /// Forget a registered account; the keystore file is kept
+    Remove {
+        /// Account name
+        name: String,
+    },
+    /// List registered accounts and their addresses
+    List,
+}
+
+#[derive(Subcommand)]
+enum KeyCommands {
+    /// Store a private key in the OS keyring (Keychain, Secret Service, Credential Manager) as account <name>
+    Import {
+        /// Account name to sign as with --from
+        name: String,
+        /// Import the PRIVATE_KEY environment variable instead of prompting
+        #[arg(long)]
+        from_env: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum PolicyCommands {
+    /// Show the default policy and per-contract overrides
+    Show,
+    /// Set a limit: max-gas-price, block-mainnet-writes, token-gated-categories, min-poll-duration or max-poll-duration
+    Set {
+        /// Policy name
+        key: String,
+        /// New value, e.g. 40 (gwei), true, governance,finance or 2d
+        value: String,
+        /// Apply only to this registered contract instead of setting the default
+        #[arg(long = "for", value_name = "CONTRACT")]
+        contract: Option<String>,
+    },
+    /// Remove a limit
+    Unset {
+        /// Policy name
+        key: String,
+        /// Remove the override for this registered contract instead of the default
+        #[arg(long = "for", value_name = "CONTRACT")]
+        contract: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum ContractCommands {
+    /// Register a contract address under a name, e.g. `contracts add grants 0x...`
+    Add {
+        /// Contract name
+        name: String,
+        /// EnhancedPolls deployment address
+        address: String,
+    },
+    /// Forget a registered contract
+    Remove {
+        /// Contract name
+        name: String,
+    },
+    /// List registered contracts
+    List,
+}
+
+#[derive(Subcommand)]
+enum CacheCommands {
+    /// Delete cached poll data for every chain and contract
+    Clear,
+}
+
+#[derive(Subcommand)]
+enum SnapshotOrgCommands {
+    /// Export a closed poll as Snapshot proposal JSON
+    Export {
+        /// Poll ID to export
+        #[arg(short, long)]
+        poll_id: PollRef,
+        /// Snapshot space the proposal belongs to
+        #[arg(short, long)]
+        space: String,
+        /// Output file path
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+    /// Create an on-chain poll from a Snapshot proposal JSON file
+    Import {
+        /// Path to the Snapshot proposal JSON
+        #[arg(short, long)]
+        file: String,
+        /// Category: general, governance, technical, community, finance
+        #[arg(short = 'c', long, default_value = "governance", value_parser = CATEGORIES, ignore_case = true)]
+        category: String,
+        /// Minimum participation required
+        #[arg(short = 'm', long, default_value = "0")]
+        min_participation: u64,
+    },
+}
+
+/// Signing middleware every contract binding is instantiated with
+pub type Client = SignerMiddleware<Provider<rpc::MeteredHttp>, LocalWallet>;
+
+/// Poll operations over any middleware stack: `Client` against a live node, a mocked provider in tests
+pub struct PollManager<M = Client> {
+    contract: EnhancedPolls<M>,
+    governance_token: Option<GovernanceToken<M>>,
+    client: Arc<M>,
+    /// Signs transactions and certificates
+    wallet: LocalWallet,
+    /// Address acting as "me": the wallet's, or the one impersonated on an Anvil node
+    sender: Address,
+    cache: cache::PollCache,
+    /// Event index, once synced during this run
+    index: std::sync::Mutex<Option<Arc<indexer::EventIndex>>>,
+    /// Token balances replayed from Transfer events during this run
+    holders: std::sync::Mutex<holders::HolderCache>,
+    /// Block that reads are evaluated at (`--block`); the head when unset
+    pinned: Option<pinned::PinnedBlock>,
+}
+
+const POLL_TYPES: [&str; 3] = ["standard", "weighted", "quadratic"];
+const CATEGORIES: [&str; 5] = ["general", "governance", "technical", "community", "finance"];
+/// `DelegationType.PROXY` in EnhancedPolls: the delegate casts votes on the delegator's behalf
+const DELEGATION_PROXY: u8 = 1;
+
+// Helper functions for enum conversions
+fn poll_type_to_u8(poll_type: &str) -> Result<u8> {
+    match poll_type.to_lowercase().as_str() {
+        "standard" => Ok(0),
+        "weighted" => Ok(1),
+        "quadratic" => Ok(2),
+        _ => bail_user!("Invalid poll type. Use: standard, weighted, quadratic"),
+    }
+}
+
+fn category_to_u8(category: &str) -> Result<u8> {
+    match category.to_lowercase().as_str() {
+        "general" => Ok(0),
+        "governance" => Ok(1),
+        "technical" => Ok(2),
+        "community" => Ok(3),
+        "finance" => Ok(4),
+        _ => bail_user!("Invalid category. Use: general, governance, technical, community, finance"),
+    }
+}
+
+fn u8_to_poll_type(poll_type: u8) -> &'static str {
+    match poll_type {
+        0 => "Standard",
+        1 => "Weighted",
+        2 => "Quadratic",
+        _ => "Unknown",
+    }
+}
+
+fn u8_to_category(category: u8) -> &'static str {
+    match category {
+        0 => "General",
+        1 => "Governance",
+        2 => "Technical",
+        3 => "Community",
+        4 => "Finance",
+        _ => "Unknown",
+    }
+}
+
+fn u8_to_status(status: u8) -> &'static str {
+    match status {
+        0 => "Active",
+        1 => "Closed",
+        2 => "Expired",
+        3 => "Cancelled",
+        4 => "Archived",
+        _ => "Unknown",
+    }
+}
+
+fn u256_to_f64(value: U256) -> f64 {
+    value.to_string().parse().unwrap_or(f64::MAX)
+}
+
+/// A timestamp in the configured display timezone
+/// Quorum progress from `getPoll` fields 9 (minParticipation), 4, 5 and 6 and the current vote count
+fn poll_quorum(total_votes: u64, min_participation: U256, created_at: U256, end_time: U256, status: u8, now: u64) -> quorum::Quorum {
+    quorum::Quorum::assess(total_votes, min_participation.to_u64_saturating(), created_at.to_u64_saturating(), end_time.to_u64_saturating(), status == 0, now)
+}
+
+fn format_timestamp(timestamp: u64) -> String {
+    timezone::format(timestamp)
+}
+
+impl PollManager {
+    pub async fn new(connection: &connection::Connection, contract_address: &str) -> Result<Self> {
+        let rpc_url = &connection.rpc_url;
+        // Setup provider and wallet
+        let provider = rpc::MeteredHttp::new(rpc_url)
+            .map(Provider::new)
+            .map_err(|e| CliError::Config(format!("Invalid RPC_URL '{}': {}", rpc_url, e)))?;
+        let wallet = connection.wallet()?;
+        let chain_id = provider.get_chainid().await
+            .map_err(|e| CliError::Rpc(format!("Could not reach {}: {}", rpc_url, e)))?;
+        connection.check_chain_id(chain_id.to_u64_saturating())?;
+        let wallet = wallet.with_chain_id(chain_id.to_u64_saturating());
+        
+        // Create signer middleware
+        let signer = Arc::new(SignerMiddleware::new(provider, wallet.clone()));
+        
+        let contract_address: Address = match contract_address.parse() {
+            Ok(address) => address,
+            Err(_) => bail_config!("Invalid CONTRACT_ADDRESS '{}'", contract_address),
+        };
+
+        let mut manager = Self::with_client(signer, wallet, contract_address);
+        manager.cache = cache::PollCache::open(chain_id.to_u64_saturating(), contract_address);
+        if let Some(address) = connection.impersonate {
+            manager.impersonate(address).await?;
+        }
+        if let Some(block) = connection.block {
+            manager.pin_block(block).await?;
+        }
+        Ok(manager)
+    }
+}
+
+impl<M: Middleware + 'static> PollManager<M> {
+    pub fn with_client(client: Arc<M>, wallet: LocalWallet, contract_address: Address) -> Self {
+        let contract = EnhancedPolls::new(contract_address, client.clone());
+
+        Self {
+            contract,
+            governance_token: None,
+            client,
+            sender: wallet.address(),
+            wallet,
+            cache: cache::PollCache::memory(),
+            index: std::sync::Mutex::new(None),
+            holders: std::sync::Mutex::new(holders::HolderCache::new()),
+            pinned: None,
+        }
+    }
+
+    /// Unlock `address` on an Anvil node and send every transaction from it
+    pub async fn impersonate(&mut self, address: Address) -> Result<()> {
+        if let Err(e) = self.client.provider().request::<_, ()>("anvil_impersonateAccount", [address]).await {
+            bail_config!("--impersonate needs an Anvil node (anvil_impersonateAccount failed: {})", e);
+        }
+        self.sender = address;
+        say!("{} {:?}", "🎭 Impersonating".magenta().bold(), address);
+        Ok(())
+    }
+
+    /// Send `call` from the impersonated address, if any; otherwise the wallet signs it
+    fn as_sender<D: ethers::abi::Detokenize>(&self, call: ContractCall<M, D>) -> ContractCall<M, D> {
+        if self.sender == self.wallet.address() {
+            call
+        } else {
+            call.from(self.sender)
+        }
+    }
+
+    pub async fn set_governance_token(&mut self, token_address: &str) -> Result<()> {
+        let token_address: Address = token_address.parse()?;
+        let governance_token = GovernanceToken::new(token_address, self.client.clone());
+        self.governance_token = Some(governance_token);
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub async fn create_enhanced_poll(
+        &self,
+        question: String,
+        options: Vec<String>,
+        duration_seconds: u64,
+        poll_type: &str,
+        category: &str,
+        min_participation: u64,
+        token_address: Option<String>,
+        min_token_balance: u64,
+        description: Option<String>,
+        tags: Option<String>,
+    ) -> Result<events::CreatedPoll> {
+        say!("{}", "🚀 Creating enhanced poll...".cyan().bold());
+        say!("{} {}", "Question:".yellow().bold(), question);
+        say!("{} {:?}", "Options:".yellow().bold(), options);
+        say!("{} {}", "Duration:".yellow().bold(), duration::format(duration_seconds));
+        say!("{} {}", "Type:".yellow().bold(), poll_type);
+        say!("{} {}", "Category:".yellow().bold(), category);
+
+        // Convert parameters
+        let poll_type_u8 = poll_type_to_u8(poll_type)?;
+        let category_u8 = category_to_u8(category)?;
+        self.duration_limits().await?.check(duration_seconds)?;
+        
+        let token_addr = if let Some(addr) = token_address {
+            addr.parse::<Address>()?
+        } else {
+            Address::zero()
+        };
+        policy::active().check_create(category, token_addr, duration_seconds)?;
+
+        let desc = description.unwrap_or_else(|| "No description provided".to_string());
+        let tags_vec: Vec<String> = if let Some(tags_str) = tags {
+            tags_str.split(',').map(|s| s.trim().to_string()).collect()
+        } else {
+            vec![]
+        };
+
+        let pb = output::spinner("Submitting transaction...");
+
+        let contract_call = self.contract.create_poll(
+            question,
+            options,
+            U256::from(duration_seconds),
+            poll_type_u8,
+            category_u8,
+            U256::from(min_participation),
+            token_addr,
+            U256::from(min_token_balance) * U256::from(10).pow(U256::from(18)), // Convert to wei
+            desc,
+            tags_vec.clone(),
+        );
+
+        let contract_call = self.as_sender(contract_call);
+
+        let tx = self.send_call(&contract_call).await?;
+        let receipt = tx.await?;
+        pb.finish_and_clear();
+        
+        let receipt = receipt.ok_or_else(|| anyhow::anyhow!("Transaction was dropped before it was mined"))?;
+        let created = events::poll_created_from_receipt(&receipt, self.contract.address())
+            .ok_or_else(|| anyhow::anyhow!("No PollCreated event in transaction {:?}", receipt.transaction_hash))?;
+
+        say!("{}", "✅ Enhanced poll created successfully!".green().bold());
+        say!("{} {}", "Poll ID:".cyan().bold(), created.poll_id.to_string().yellow());
+        say!("{} {}", "Link:".cyan().bold(), poll_ref::link(self.wallet.chain_id(), self.contract.address(), created.poll_id.to_u64_saturating()).white());
+        say!("{} {}", "Type:".cyan().bold(), u8_to_poll_type(poll_type_u8).green());
+        say!("{} {}", "Category:".cyan().bold(), u8_to_category(category_u8).green());
+        say!("{} {}", "Ends:".cyan().bold(), format_timestamp(created.end_time).white());
+        if !token_addr.is_zero() {
+            let value = price::approx(min_token_balance as f64, self.token_price(token_addr).await);
+            say!("{} at least {} tokens of {:?}{}", "Token Gate:".cyan().bold(), min_token_balance, token_addr, value);
+        }
+        if !tags_vec.is_empty() {
+            say!("{} {:?}", "Tags:".cyan().bold(), tags_vec);
+        }
+        say!("{} {:?}", "Transaction hash:".cyan().bold(), created.transaction_hash);
+
+        Ok(created)
+    }
+
+    pub async fn vote(&self, poll_id: u64, option_index: u64) -> Result<()> {
+        let poll_id_str = poll_id.to_string();
+        let option_str = option_index.to_string();
+        say!("{} {} {} {}", "🗳️ Voting on poll".cyan().bold(), poll_id_str.yellow(), "with option".cyan().bold(), option_str.yellow());
+
+        let pb = output::spinner("Submitting vote...");
+
+        let contract_call = self.contract.vote(U256::from(poll_id), U256::from(option_index));
+        let contract_call = self.as_sender(contract_call);
+        let tx = self.send_call(&contract_call).await?;
+
+        let receipt = tx.await?;
+        self.cache.invalidate(poll_id);
+        pb.finish_and_clear();
+        
+        if let Some(receipt) = receipt {
+            say!("{}", "✅ Vote cast successfully!".green().bold());
+            say!("{} {:?}", "Transaction hash:".cyan().bold(), receipt.transaction_hash);
+        }
+
+        Ok(())
+    }
+
+    pub async fn view_poll(&self, poll_id: u64) -> Result<()> {
+        let poll_data = self.poll_data(U256::from(poll_id)).await?;
+
+        say!("\n📊 Poll Details:");
+        say!("ID: {}", poll_data.0);
+        say!("Question: {}", poll_data.1);
+        say!("Options:");
+        for (i, option) in poll_data.2.iter().enumerate() {
+            say!("  {}: {}", i, option);
+        }
+        say!("Creator: {:?}", poll_data.3);
+        say!("Created: {}", timezone::format_with_relative(poll_data.4.to_u64_saturating()));
+        say!("End Time: {}", timezone::format_with_relative(poll_data.5.to_u64_saturating()));
+        say!("Status: {}", u8_to_status(poll_data.6));
+        say!("Link: {}", poll_ref::link(self.wallet.chain_id(), self.contract.address(), poll_id));
+        say!("Read at: {}", self.read_at());
+
+        // Get results
+        let tally = self.poll_tally(poll_id, &poll_data.2, poll_data.7).await?;
+        if let Some(quorum) = poll_quorum(tally.total_votes, poll_data.9, poll_data.4, poll_data.5, poll_data.6, self.now()).summary() {
+            say!("Quorum: {}", quorum);
+        }
+
+        say!("\n📈 Current Results:");
+        for option in &tally.options {
+            if tally.weighted {
+                say!("  {}: {} votes ({:.1}%), weight {} ({:.1}%)",
+                    option.option, numbers::format_integer(option.votes), option.vote_share,
+                    numbers::format_integer(option.weight), option.weight_share);
+            } else {
+                say!("  {}: {} votes ({:.1}%)", option.option, numbers::format_integer(option.votes), option.vote_share);
+            }
+        }
+        say!("Total votes: {}", numbers::format_integer(tally.total_votes));
+        if tally.weighted {
+            say!("Total weight: {}", numbers::format_integer(tally.total_weight));
+        }
+        say!("Winner decided by: {}", tally.winner_metric());
+
+        Ok(())
+    }
+
+    pub async fn list_polls(&self) -> Result<()> {
+        let poll_count = self.contract.poll_count().call().await?;
+        
+        say!("\n📋 All Polls:");
+        say!("Total polls: {}", poll_count);
+        
+        for i in 0..poll_count.to_u64_saturating() {
+            let poll_data = self.poll_data(U256::from(i)).await?;
+            
+            let is_active = self.contract
+                .is_poll_active(U256::from(i))
+                .call()
+                .await?;
+
+            let status = if is_active { output::status("🟢", "Active").green() } else { output::status("🔴", "Closed").red() };
+            
+            say!("\nPoll #{}: {}", i, poll_data.1);
+            say!("  Status: {}", status);
+            say!("  Options: {}", poll_data.2.len());
+            say!("  Creator: {:?}", poll_data.3);
+            say!("  Ends: {}", timezone::format_with_relative(poll_data.5.to_u64_saturating()));
+        }
+
+        Ok(())
+    }
+
+    pub async fn get_results(&self, poll_id: u64) -> Result<()> {
+        let poll_data = self.poll_data(U256::from(poll_id)).await?;
+        let tally = self.poll_tally(poll_id, &poll_data.2, poll_data.7).await?;
+        let finality = self.results_finality(poll_id, &poll_data, &tally, &mut None).await;
+        self.print_results(&poll_data, &tally, finality.as_ref());
+        Ok(())
+    }
+
+    /// Whether an active poll's outcome could still flip, when the electorate is known. `electorate`
+    /// keeps the holders between calls so `--follow` replays the token's transfers only once; the
+    /// results don't depend on finality, so failures only warn.
+    async fn results_finality(&self, poll_id: u64, poll_data: &cache::PollData, tally: &tally::PollTally,
+        electorate: &mut Option<Option<finality::Electorate>>) -> Option<finality::Finality> {
+        if poll_data.6 != 0 || poll_data.5.to_u64_saturating() <= self.now() {
+            return None;
+        }
+        if electorate.is_none() {
+            *electorate = Some(self.electorate(poll_id).await.unwrap_or_else(|e| {
+                eprintln!("{} {:#}", output::render("⚠️  No finality:".to_string()).yellow(), e);
+                None
+            }));
+        }
+        let holders = electorate.as_ref()?.as_ref()?;
+        self.finality(poll_id, poll_data, tally, holders).await.unwrap_or_else(|e| {
+            eprintln!("{} {:#}", output::render("⚠️  No finality:".to_string()).yellow(), e);
+            None
+        })
+    }
+
+    /// Per-option bars, totals, quorum and, for active polls, whether the outcome could still flip, as shown by `results`
+    fn print_results(&self, poll_data: &cache::PollData, tally: &tally::PollTally, finality: Option<&finality::Finality>) {
+        say!("\n📊 Poll Results for: {}", poll_data.1);
+        say!("{}", "=".repeat(50));
+        
+        for option in &tally.options {
+            let percentage = tally.deciding_share(option);
+            let bar = "█".repeat((percentage / 2.0) as usize);
+            if tally.weighted {
+                say!("{}: {:>3} votes, weight {:>3} ({:>4.1}%) {}",
+                    option.option, numbers::format_integer(option.votes), numbers::format_integer(option.weight), percentage, bar);
+            } else {
+                say!("{}: {:>3} votes ({:>4.1}%) {}",
+                    option.option, numbers::format_integer(option.votes), percentage, bar);
+            }
+        }
+        
+        say!("{}", "=".repeat(50));
+        say!("Total votes: {}", numbers::format_integer(tally.total_votes));
+        if tally.weighted {
+            say!("Total weight: {} (winner decided by weight)", numbers::format_integer(tally.total_weight));
+        }
+        if let Some(quorum) = poll_quorum(tally.total_votes, poll_data.9, poll_data.4, poll_data.5, poll_data.6, self.now()).summary() {
+            say!("Quorum: {}", quorum);
+        }
+        if let Some(finality) = finality {
+            finality::print_finality(finality);
+        }
+        say!("Read at: {}", self.read_at());
+    }
+
+    pub async fn close_poll(&self, poll_id: u64) -> Result<()> {
+        say!("Closing poll {}", poll_id);
+
+        let contract_call = self.contract.close_poll(U256::from(poll_id));
+        let contract_call = self.as_sender(contract_call);
+        let tx = self.send_call(&contract_call).await?;
+
+        let receipt = tx.await?;
+        self.cache.invalidate(poll_id);
+        
+        if let Some(receipt) = receipt {
+            say!("✅ Poll closed successfully!");
+            say!("Transaction hash: {:?}", receipt.transaction_hash);
+        }
+
+        Ok(())
+    }
+
+    pub async fn my_votes(&self) -> Result<()> {
+        let address = self.sender;
+        let voted_polls = self.contract
+            .get_user_voted_polls(address)
+            .call()
+            .await?;
+
+        say!("\n🗳️  Polls You've Voted On:");
+        if voted_polls.is_empty() {
+            say!("You haven't voted on any polls yet.");
+            return Ok(());
+        }
+
+        for poll_id in voted_polls {
+            let poll_data = self.poll_data(poll_id).await?;
+            
+            say!("\nPoll #{}: {}", poll_id, poll_data.1);
+        }
+
+        Ok(())
+    }
+
+    async fn build_poll_export(&self, poll_id: u64) -> Result<PollExport> {
+        let poll_data = self.poll_data(U256::from(poll_id)).await?;
+        let tally = self.poll_tally(poll_id, &poll_data.2, poll_data.7).await?;
+
+        // Exports are machine-facing, so their formatted times stay in UTC
+        let created_at_unix = poll_data.4.to_u64_saturating();
+        let end_time_unix = poll_data.5.to_u64_saturating();
+        let created_at = timezone::format_in(created_at_unix, timezone::DisplayZone::Utc);
+        let end_time = timezone::format_in(end_time_unix, timezone::DisplayZone::Utc);
+        let quorum = poll_quorum(tally.total_votes, poll_data.9, poll_data.4, poll_data.5, poll_data.6, self.now());
+
+        Ok(PollExport {
+            id: poll_id,
+            question: poll_data.1.clone(),
+            creator: format!("{:?}", poll_data.3),
+            created_at,
+            end_time,
+            created_at_unix,
+            end_time_unix,
+            is_active: poll_data.6 == 0,
+            poll_type: u8_to_poll_type(poll_data.7).to_string(),
+            winner_metric: tally.winner_metric().to_string(),
+            total_votes: tally.total_votes,
+            total_weight: tally.total_weight,
+            options: poll_data.2.clone(),
+            votes: tally.options.iter().map(|o| o.votes).collect(),
+            weights: tally.options.iter().map(|o| o.weight).collect(),
+            min_participation: quorum.min_participation,
+            quorum_status: quorum.state,
+        })
+    }
+
+    async fn export_poll(&self, poll_id: u64, format: &str, output_path: Option<String>, csv_options: CsvOptions, select: &fields::SelectArgs) -> Result<()> {
+        if select.is_set() {
+            let selected = select.render(&self.build_poll_export(poll_id).await?)?;
+            match output_path {
+                Some(path) => {
+                    std::fs::write(&path, format!("{}\n", selected))?;
+                    say!("{} {}", "✅ Exported to:".green().bold(), path.yellow());
+                }
+                None => println!("{}", selected),
+            }
+            return Ok(());
+        }
+        say!("{} {} {} {}", "📊 Exporting poll".cyan().bold(), poll_id.to_string().yellow(), "in".cyan().bold(), format.yellow());
+
+        let export_data = self.build_poll_export(poll_id).await?;
+
+        match format.to_lowercase().as_str() {
+            "json" => {
+                let json_output = serde_json::to_string_pretty(&export_data)?;
+                if let Some(path) = output_path {
+                    std::fs::write(&path, &json_output)?;
+                    say!("{} {}", "✅ Exported to:".green().bold(), path.yellow());
+                } else {
+                    println!("{}", json_output);
+                }
+            }
+            "csv" => {
+                let csv_output = render_csv(&export_data, csv_options)?;
+                if let Some(path) = output_path {
+                    std::fs::write(&path, &csv_output)?;
+                    say!("{} {}", "✅ Exported to:".green().bold(), path.yellow());
+                } else {
+                    println!("{}", csv_output);
+                }
+            }
+            "table" => {
+                let table = output::table([export_data]);
+                if let Some(path) = output_path {
+                    std::fs::write(&path, &table)?;
+                    say!("{} {}", "✅ Exported to:".green().bold(), path.yellow());
+                } else {
+                    say!("{}", table);
+                }
+            }
+            "markdown" | "md" => {
+                let markdown = render_markdown(&export_data);
+                if let Some(path) = output_path {
+                    std::fs::write(&path, &markdown)?;
+                    say!("{} {}", "✅ Exported to:".green().bold(), path.yellow());
+                } else {
+                    println!("{}", markdown);
+                }
+            }
+            "png" | "pdf" => {
+                let rendered = if format.eq_ignore_ascii_case("png") {
+                    self.poll_card_png(poll_id, "polling-cli").await?
+                } else {
+                    self.poll_report_pdf(poll_id).await?
+                };
+                if let Some(path) = output_path {
+                    std::fs::write(&path, &rendered)?;
+                    say!("{} {}", "✅ Exported to:".green().bold(), path.yellow());
+                } else {
+                    std::io::Write::write_all(&mut std::io::stdout(), &rendered)?;
+                }
+            }
+            plugin => match plugins::run_exporter(plugin, &serde_json::to_string(&export_data)?)? {
+                Some(rendered) => {
+                    if let Some(path) = output_path {
+                        std::fs::write(&path, &rendered)?;
+                        say!("{} {}", "✅ Exported to:".green().bold(), path.yellow());
+                    } else {
+                        std::io::Write::write_all(&mut std::io::stdout(), &rendered)?;
+                    }
+                }
+                None => bail_user!("Unsupported format: {}. Use json, csv, table, markdown, png, pdf, xlsx, or install a {}export-{} plugin", format, plugins::PREFIX, plugin),
+            },
+        };
+
+        Ok(())
+    }
+
+    pub async fn generate_analytics(&self, poll_id: Option<u64>) -> Result<()> {
+        match poll_id {
+            Some(id) => {
+                say!("{} {}", "📈 Generating analytics for poll".cyan().bold(), id.to_string().yellow());
+                self.generate_single_poll_analytics(id).await
+            }
+            None => {
+                say!("{}", "📈 Generating comprehensive analytics for all polls".cyan().bold());
+                self.generate_all_polls_analytics().await
+            }
+        }
+    }
+
+    async fn poll_analytics(&self, poll_id: u64) -> Result<PollAnalytics> {
+        let poll_data = self.poll_data(U256::from(poll_id)).await?;
+        let tally = self.poll_tally(poll_id, &poll_data.2, poll_data.7).await?;
+
+        let created_at_unix = poll_data.4.to_u64_saturating();
+        let end_time_unix = poll_data.5.to_u64_saturating();
+        let created_at = timezone::format_with_relative(created_at_unix);
+
+        let options_detail: Vec<OptionDetail> = tally.options.iter().enumerate()
+            .map(|(i, option)| OptionDetail {
+                index: i,
+                option: option.option.clone(),
+                votes: option.votes,
+                percentage: option.vote_share,
+                weight: option.weight,
+                weight_percentage: option.weight_share,
+            })
+            .collect();
+
+        // Leader and margin follow the metric that decides the poll
+        let ranked = tally.ranked();
+        let top_share = ranked.first().map(|o| tally.deciding_share(o)).unwrap_or(0.0);
+        let second_share = ranked.get(1).map(|o| tally.deciding_share(o)).unwrap_or(0.0);
+        let leading_option = match ranked.first() {
+            Some(top) if top_share > 0.0 => top.option.clone(),
+            _ => String::new(),
+        };
+        let margin = top_share - second_share;
+
+        let time_remaining = if poll_data.6 == 0 {
+            let now = chrono::Utc::now().timestamp() as u64;
+            if end_time_unix > now {
+                Some(format!("ends {}", timezone::relative_to(end_time_unix, now)))
+            } else {
+                Some(format!("Expired (ended {})", timezone::relative_to(end_time_unix, now)))
+            }
+        } else {
+            Some("Closed".to_string())
+        };
+
+        // Share of the poll's minimum participation target reached so far
+        let min_participation = poll_data.9.to_u64_saturating();
+        let participation_rate = if min_participation > 0 {
+            (tally.total_votes as f64 / min_participation as f64) * 100.0
+        } else {
+            100.0
+        };
+
+        Ok(PollAnalytics {
+            poll_id,
+            question: poll_data.1.clone(),
+            total_votes: tally.total_votes,
+            total_weight: tally.total_weight,
+            winner_metric: tally.winner_metric().to_string(),
+            participation_rate,
+            min_participation,
+            quorum: poll_quorum(tally.total_votes, poll_data.9, poll_data.4, poll_data.5, poll_data.6, self.now()),
+            leading_option,
+            margin,
+            time_remaining,
+            created_at,
+            created_at_unix,
+            end_time_unix,
+            options_detail,
+        })
+    }
+
+    async fn generate_single_poll_analytics(&self, poll_id: u64) -> Result<()> {
+        let analytics = self.poll_analytics(poll_id).await?;
+
+        say!("\n{}", "📊 POLL ANALYTICS".cyan().bold().underline());
+        say!("{}", "═".repeat(50).cyan());
+        say!("{} {} - {}", "Poll ID:".yellow().bold(), analytics.poll_id.to_string().white(), analytics.question.white().bold());
+        say!("{} {}", "Total Votes:".yellow().bold(), numbers::format_integer(analytics.total_votes).green().bold());
+        let weighted = analytics.winner_metric == "weight";
+        if weighted {
+            say!("{} {}", "Total Weight:".yellow().bold(), numbers::format_integer(analytics.total_weight).green().bold());
+        }
+        say!("{} {}", "Decided By:".yellow().bold(), analytics.winner_metric.white());
+        say!("{} {}", "Leading Option:".yellow().bold(), analytics.leading_option.green().bold());
+        say!("{} {:.1}%", "Margin:".yellow().bold(), analytics.margin);
+        if analytics.min_participation > 0 {
+            say!("{} {:.1}% of {}", "Participation:".yellow().bold(), analytics.participation_rate, analytics.min_participation);
+        }
+        if let Some(quorum) = analytics.quorum.summary() {
+            say!("{} {}", "Quorum:".yellow().bold(), quorum);
+        }
+        if let Some(time) = &analytics.time_remaining {
+            say!("{} {}", "Time Remaining:".yellow().bold(), time.white());
+        }
+        say!("{} {}", "Created:".yellow().bold(), analytics.created_at.white());
+        say!("{} {}", "Ends:".yellow().bold(), timezone::format(analytics.end_time_unix).white());
+        say!("{} {}", "Read At:".yellow().bold(), self.read_at());
+        
+        say!("\n{}", "📋 DETAILED RESULTS".cyan().bold());
+        say!("{}", "─".repeat(50).cyan());
+        
+        for detail in &analytics.options_detail {
+            if weighted {
+                let bar = "█".repeat((detail.weight_percentage / 2.0) as usize);
+                say!("{}: {} votes ({:.1}%), weight {} ({:.1}%) {}",
+                    detail.option.white().bold(),
+                    numbers::format_integer(detail.votes).yellow(),
+                    detail.percentage,
+                    numbers::format_integer(detail.weight).yellow(),
+                    detail.weight_percentage,
+                    bar.green()
+                );
+            } else {
+                let bar = "█".repeat((detail.percentage / 2.0) as usize);
+                say!("{}: {} votes ({:.1}%) {}",
+                    detail.option.white().bold(),
+                    numbers::format_integer(detail.votes).yellow(),
+                    detail.percentage,
+                    bar.green()
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn generate_all_polls_analytics(&self) -> Result<()> {
+        let poll_count = self.at_block(self.contract.poll_count()).call().await?;
+        
+        say!("\n{}", "📊 COMPREHENSIVE POLL ANALYTICS".cyan().bold().underline());
+        say!("{}", "═".repeat(60).cyan());
+        say!("{} {}", "Read At:".yellow().bold(), self.read_at());
+        
+        let mut total_system_votes = 0u64;
+        let mut active_polls = 0u64;
+        let mut closed_polls = 0u64;
+        let mut quorum_at_risk = 0u64;
+        
+        for i in 0..poll_count.to_u64_saturating() {
+            let poll_data = self.poll_data(U256::from(i)).await?;
+            let (_, total_votes, _) = self.poll_results(U256::from(i)).await?;
+            let is_active = poll_data.6 == 0 && self.now() <= poll_data.5.to_u64_saturating();
+            let quorum = poll_quorum(total_votes.to_u64_saturating(), poll_data.9, poll_data.4, poll_data.5, poll_data.6, self.now());
+            
+            total_system_votes = total_system_votes.saturating_add(total_votes.to_u64_saturating());
+            if is_active {
+                active_polls += 1;
+            } else {
+                closed_polls += 1;
+            }
+            if quorum.state == quorum::QuorumState::AtRisk {
+                quorum_at_risk += 1;
+            }
+            
+            say!("\n{} {} - {}", "Poll".yellow().bold(), i.to_string().white(), poll_data.1.white().bold());
+            say!("  {} {} | {} {}", 
+                "Votes:".cyan(), numbers::format_integer(total_votes).green(),
+                "Status:".cyan(), if is_active { output::status("🟢", "Active").green() } else { output::status("🔴", "Closed").red() }
+            );
+            if let Some(summary) = quorum.summary() {
+                say!("  {} {}", "Quorum:".cyan(), summary);
+            }
+        }
+        
+        say!("\n{}", "📈 SYSTEM SUMMARY".cyan().bold().underline());
+        say!("{}", "═".repeat(30).cyan());
+        say!("{} {}", "Total Polls:".yellow().bold(), poll_count.to_string().white());
+        say!("{} {}", "Active Polls:".yellow().bold(), active_polls.to_string().green());
+        say!("{} {}", "Closed Polls:".yellow().bold(), closed_polls.to_string().red());
+        if quorum_at_risk > 0 {
+            say!("{} {}", "Polls at Risk of Missing Quorum:".yellow().bold(), quorum_at_risk.to_string().yellow());
+        }
+        say!("{} {}", "Total Votes Cast:".yellow().bold(), numbers::format_integer(total_system_votes).cyan());
+        
+        if poll_count.to_u64_saturating() > 0 {
+            let avg_votes = total_system_votes as f64 / poll_count.to_u64_saturating() as f64;
+            say!("{} {:.1}", "Average Votes per Poll:".yellow().bold(), avg_votes);
+        }
+
+        Ok(())
+    }
+
+    // Enhanced methods for new functionality
+    pub async fn vote_as_delegate(&self, poll_id: u64, option_index: u64, delegator: &str) -> Result<()> {
+        let delegator_addr: Address = delegator.parse()?;
+        say!("{} {} {} {} {} {}", 
+            "🗳️ Voting as delegate on poll".cyan().bold(), 
+            poll_id.to_string().yellow(), 
+            "with option".cyan().bold(), 
+            option_index.to_string().yellow(),
+            "for".cyan().bold(),
+            delegator.yellow()
+        );
+
+        let pb = output::spinner("Submitting delegate vote...");
+
+        let contract_call = self.contract.vote_as_delegate(
+            U256::from(poll_id), 
+            U256::from(option_index), 
+            delegator_addr
+        );
+        let contract_call = self.as_sender(contract_call);
+        let tx = self.send_call(&contract_call).await?;
+        let receipt = tx.await?;
+        self.cache.invalidate(poll_id);
+        pb.finish_and_clear();
+        
+        if let Some(receipt) = receipt {
+            say!("{}", "✅ Delegate vote cast successfully!".green().bold());
+            say!("{} {:?}", "Transaction hash:".cyan().bold(), receipt.transaction_hash);
+        }
+
+        Ok(())
+    }
+
+    pub async fn set_delegate(&self, delegate: &str) -> Result<()> {
+        let delegate_addr: Address = delegate.parse()?;
+        say!("{} {}", "👥 Setting delegate to".cyan().bold(), delegate.yellow());
+
+        let pb = output::spinner("Setting delegate...");
+
+        let contract_call = self.contract.set_delegate(delegate_addr, DELEGATION_PROXY);
+        let contract_call = self.as_sender(contract_call);
+        let tx = self.send_call(&contract_call).await?;
+        let receipt = tx.await?;
+        pb.finish_and_clear();
+        
+        if let Some(receipt) = receipt {
+            say!("{}", "✅ Delegate set successfully!".green().bold());
+            say!("{} {:?}", "Transaction hash:".cyan().bold(), receipt.transaction_hash);
+        }
+
+        Ok(())
+    }
+
+    pub async fn remove_delegate(&self) -> Result<()> {
+        say!("{}", "👥 Removing current delegate".cyan().bold());
+
+        let pb = output::spinner("Removing delegate...");
+
+        let contract_call = self.contract.remove_delegate();
+        let contract_call = self.as_sender(contract_call);
+        let tx = self.send_call(&contract_call).await?;
+        let receipt = tx.await?;
+        pb.finish_and_clear();
+        
+        if let Some(receipt) = receipt {
+            say!("{}", "✅ Delegate removed successfully!".green().bold());
+            say!("{} {:?}", "Transaction hash:".cyan().bold(), receipt.transaction_hash);
+        }
+
+        Ok(())
+    }
+
+    /// Extend a poll by `additional` seconds, or to the absolute `ends_at` timestamp
+    pub async fn extend_poll(&self, poll_id: u64, additional: Option<u64>, ends_at: Option<u64>, yes: bool) -> Result<()> {
+        // Validate against the current end time, not a cached one
+        let poll = self.contract.get_poll(U256::from(poll_id)).call().await?;
+        let current_end = poll.5.to_u64_saturating();
+        let additional_seconds = match (additional, ends_at) {
+            (Some(seconds), _) => seconds,
+            (None, Some(ends_at)) if ends_at > current_end => ends_at - current_end,
+            (None, Some(_)) => bail_user!("New end time must be after the current end, {}", format_timestamp(current_end)),
+            (None, None) => bail_user!("Pass --duration or --ends-at"),
+        };
+        if additional_seconds == 0 {
+            bail_user!("Extension must be longer than zero");
+        }
+
+        // extendPoll reverts once the end would be more than maxPollDuration from now
+        let limits = self.duration_limits().await?;
+        let now = chrono::Utc::now().timestamp() as u64;
+        let new_end = current_end.saturating_add(additional_seconds);
+        if new_end > now.saturating_add(limits.max) {
+            bail_user!("New end {} is more than {} from now", format_timestamp(new_end), duration::format(limits.max));
+        }
+        policy::active().check_total_duration(new_end.saturating_sub(poll.4.to_u64_saturating()))?;
+        if !self.confirm_extend(poll_id, &poll.1, current_end, new_end, yes).await? {
+            return Ok(());
+        }
+
+        say!("{} {} {} {}", 
+            "⏰ Extending poll".cyan().bold(), 
+            poll_id.to_string().yellow(), 
+            "by".cyan().bold(),
+            duration::format(additional_seconds).yellow()
+        );
+
+        let contract_call = self.contract.extend_poll(U256::from(poll_id), U256::from(additional_seconds));
+        let contract_call = self.as_sender(contract_call);
+        let tx = self.send_call(&contract_call).await?;
+        let receipt = tx.await?;
+        self.cache.invalidate(poll_id);
+        
+        if let Some(receipt) = receipt {
+            say!("{}", "✅ Poll extended successfully!".green().bold());
+            say!("{} {}", "Ends:".cyan().bold(), format_timestamp(new_end).white());
+            say!("{} {:?}", "Transaction hash:".cyan().bold(), receipt.transaction_hash);
+        }
+
+        Ok(())
+    }
+
+    pub async fn check_token_balance(&self, token_address: Option<String>, check_address: Option<String>) -> Result<()> {
+        let address_to_check = if let Some(addr) = check_address {
+            addr.parse::<Address>()?
+        } else {
+            self.sender
+        };
+
+        if let Some(token_addr) = token_address {
+            let token_address: Address = token_addr.parse()?;
+            let token = GovernanceToken::new(token_address, self.client.clone());
+            
+            let balance = self.at_block(token.balance_of(address_to_check)).call().await?;
+            let name = token.name().call().await?;
+            let symbol = token.symbol().call().await?;
+            let decimals = token.decimals().call().await?;
+            
+            
+            say!("\n💰 Token Balance Information:");
+            say!("{} {}", "Token:".yellow().bold(), format!("{} ({})", name, symbol).green());
+            let value = price::approx(u256_to_f64(balance) / 10f64.powi(decimals as i32), self.token_price(token_address).await);
+            say!("{} {}{}", "Balance:".yellow().bold(), numbers::format_amount(balance, decimals as u32, 2), value);
+            say!("{} {:?}", "Address:".yellow().bold(), address_to_check);
+            say!("{} {}", "Read At:".yellow().bold(), self.read_at());
+        } else if let Some(ref gov_token) = self.governance_token {
+            let balance = self.at_block(gov_token.balance_of(address_to_check)).call().await?;
+            let voting_power = self.at_block(gov_token.get_voting_power(address_to_check)).call().await?;
+            let name = gov_token.name().call().await?;
+            let symbol = gov_token.symbol().call().await?;
+            
+            
+            say!("\n💰 Governance Token Information:");
+            say!("{} {}", "Token:".yellow().bold(), format!("{} ({})", name, symbol).green());
+            let value = price::approx(u256_to_f64(balance) / 1e18, self.token_price(gov_token.address()).await);
+            say!("{} {}{}", "Balance:".yellow().bold(), numbers::format_amount(balance, 18, 2), value);
+            say!("{} {}", "Voting Power:".yellow().bold(), numbers::format_amount(voting_power, 18, 2));
+            say!("{} {:?}", "Address:".yellow().bold(), address_to_check);
+            say!("{} {}", "Read At:".yellow().bold(), self.read_at());
+        } else {
+            bail_user!("No token address provided and no governance token set");
+        }
+
+        Ok(())
+    }
+
+    pub async fn view_user_stats(&self, user_address: Option<String>) -> Result<()> {
+        let address_to_check = if let Some(addr) = user_address {
+            addr.parse::<Address>()?
+        } else {
+            self.sender
+        };
+
+        let (polls_created, polls_voted, total_voting_weight) = self.contract
+            .get_user_stats(address_to_check)
+            .call()
+            .await?;
+
+        say!("\n📊 User Statistics:");
+        say!("{} {:?}", "Address:".yellow().bold(), address_to_check);
+        say!("{} {}", "Polls Created:".yellow().bold(), polls_created.to_string().green());
+        say!("{} {}", "Polls Voted On:".yellow().bold(), polls_voted.to_string().green());
+        say!("{} {}", "Total Voting Weight:".yellow().bold(), numbers::format_integer(total_voting_weight).cyan());
+
+        Ok(())
+    }
+
+    pub async fn view_delegation_info(&self, user_address: Option<String>) -> Result<()> {
+        let address_to_check = if let Some(addr) = user_address {
+            addr.parse::<Address>()?
+        } else {
+            self.sender
+        };
+
+        let delegate = self.contract.get_delegate(address_to_check).call().await?;
+        let delegators = self.contract.get_delegators(address_to_check).call().await?;
+
+        say!("\n👥 Delegation Information:");
+        say!("{} {:?}", "Address:".yellow().bold(), address_to_check);
+        
+        if let Some(summary) = profiles::summary(address_to_check) {
+            say!("{} {}", "Profile:".yellow().bold(), summary);
+        }
+        if delegate != Address::zero() {
+            say!("{} {:?}", "Delegated To:".yellow().bold(), delegate);
+            if let Some(summary) = profiles::summary(delegate) {
+                say!("  {}", summary.white());
+            }
+        } else {
+            say!("{} {}", "Delegated To:".yellow().bold(), "None".red());
+        }
+
+        if !delegators.is_empty() {
+            say!("{} {}", "Delegators Count:".yellow().bold(), delegators.len().to_string().green());
+            say!("{}", "Delegators:".yellow().bold());
+            for (i, delegator) in delegators.iter().enumerate() {
+                say!("  {}: {:?}", i + 1, delegator);
+            }
+        } else {
+            say!("{} {}", "Delegators:".yellow().bold(), "None".red());
+        }
+
+        Ok(())
+    }
+
+    pub async fn list_enhanced_polls(&self, category: Option<String>, tag: Option<String>, active_only: bool, format: &str, select: &fields::SelectArgs, query: &query::QueryArgs) -> Result<()> {
+        let ndjson = match format.to_lowercase().as_str() {
+            "table" => select.is_set(),
+            "ndjson" => true,
+            _ => bail_user!("Unsupported format: {}. Use table or ndjson", format),
+        };
+        // Headings would break the stream, so ndjson output is the records alone
+        let heading = |text: String| if !ndjson { say!("{}", text) };
+
+        if let Some(tag_str) = tag {
+            // Filter by tag
+            let poll_ids = self.contract.get_polls_by_tag(tag_str.clone()).call().await?;
+            heading(format!("\n📋 Polls with tag '{}':", tag_str.green()));
+            self.display_poll_list(poll_ids, active_only, ndjson, select, query).await?;
+        } else if let Some(category_str) = category {
+            // Filter by category
+            let category_u8 = category_to_u8(&category_str)?;
+            let poll_ids = self.contract.get_polls_by_category(category_u8).call().await?;
+            heading(format!("\n📋 {} Polls:", u8_to_category(category_u8).green()));
+            self.display_poll_list(poll_ids, active_only, ndjson, select, query).await?;
+        } else {
+            // List all polls
+            let poll_count = self.contract.poll_count().call().await?;
+            let poll_ids: Vec<U256> = (0..poll_count.to_u64_saturating()).map(U256::from).collect();
+            
+            if active_only {
+                heading("\n📋 Active Polls:".to_string());
+            } else {
+                heading("\n📋 All Polls:".to_string());
+            }
+            
+            self.display_poll_list(poll_ids, active_only, ndjson, select, query).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn display_poll_list(&self, poll_ids: Vec<U256>, active_only: bool, ndjson: bool, select: &fields::SelectArgs, query: &query::QueryArgs) -> Result<()> {
+        if !ndjson {
+            if poll_ids.is_empty() {
+                say!("No polls found.");
+                return Ok(());
+            }
+            say!("Total polls: {}", poll_ids.len());
+        }
+
+        // Sorting needs every poll first; otherwise each one is printed as soon as it is read
+        let now = self.now();
+        let mut entries = Vec::new();
+        for poll_id in poll_ids {
+            let poll = self.poll_data(poll_id).await?;
+            let active = self.contract.is_poll_active(poll_id).call().await?;
+            if active_only && !active {
+                continue;
+            }
+            let entry = PollListEntry {
+                contract: self.contract.address(),
+                id: poll_id.to_u64_saturating(),
+                question: poll.1,
+                active,
+                poll_type: u8_to_poll_type(poll.7).to_string(),
+                category: u8_to_category(poll.8).to_string(),
+                options: poll.2,
+                total_votes: poll.10.to_u64_saturating(),
+                creator: poll.3,
+                tags: poll.13,
+                created_at: poll.4.to_u64_saturating(),
+                end_time: poll.5.to_u64_saturating(),
+            };
+            if !query.matches(&entry, now)? {
+                continue;
+            }
+            if query.sort_by.is_empty() {
+                print_poll_list_entry(&entry, ndjson, select)?;
+            } else {
+                entries.push(entry);
+            }
+        }
+        for entry in query.sort(entries)? {
+            print_poll_list_entry(&entry, ndjson, select)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// One poll of `list`: a table block, an ndjson line or what `--fields`/`--template` select
+fn print_poll_list_entry(entry: &PollListEntry, ndjson: bool, select: &fields::SelectArgs) -> Result<()> {
+    if select.is_set() {
+        return select.print(entry);
+    }
+    if ndjson {
+        return output::ndjson(entry);
+    }
+    let status_emoji = if entry.active { "🟢" } else { "🔴" };
+    let status_text = if entry.active { "Active".green() } else { "Closed".red() };
+
+    say!("\n{} Poll #{}: {}", status_emoji, entry.id, entry.question);
+    say!("  Status: {}", status_text);
+    say!("  Type: {}", entry.poll_type);
+    say!("  Category: {}", entry.category);
+    say!("  Options: {}", entry.options.len());
+    say!("  Total Votes: {}", entry.total_votes);
+    say!("  Creator: {:?}", entry.creator);
+
+    if !entry.tags.is_empty() {
+        say!("  Tags: {:?}", entry.tags);
+    }
+    Ok(())
+}
+
+/// The `Rust_Foundry` command line: parse the arguments, run the command and exit with its status
+pub async fn cli_main() {
+    dotenv::dotenv().ok();
+
+    let matches = Cli::command().get_matches();
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+    output::init(cli.no_color, cli.plain, cli.accessible);
+    if cli.quiet {
+        error::silence_stdout();
+    }
+
+    let rpc_stats = cli.rpc_stats;
+    let is_write = cli.command.is_write();
+    let result = run(cli).await;
+    if rpc_stats {
+        rpc::print_summary();
+    }
+    session::print_summary();
+    if is_write || audit::sent_transactions() {
+        let (outcome, exit_code) = match &result {
+            Ok(()) => ("ok".to_string(), 0),
+            Err(err) => {
+                let kind = error::classify(err);
+                (format!("{}: {:#}", kind.label(), err), kind.exit_code())
+            }
+        };
+        let args = std::env::args().skip(1).map(|arg| redact::redact(&arg)).collect();
+        audit::append(&audit::command_name(&matches), args, redact::redact(&outcome), exit_code);
+    }
+    if let Err(err) = result {
+        let kind = error::classify(&err);
+        eprintln!("{} {}", output::render(format!("❌ {}:", kind.label())).red().bold(), redact::redact(&format!("{:#}", err)));
+        std::process::exit(kind.exit_code());
+    }
+}
+
+async fn run(mut cli: Cli) -> Result<()> {
+    let zone = match cli.timezone {
+        Some(zone) => Some(zone),
+        None => match std::env::var("POLL_TIMEZONE") {
+            Ok(name) if !name.is_empty() => match name.parse() {
+                Ok(zone) => Some(zone),
+                Err(e) => bail_config!("POLL_TIMEZONE: {}", e),
+            },
+            _ => None,
+        },
+    };
+    timezone::init(zone);
+
+    let fiat = match cli.fiat {
+        Some(fiat) => Some(fiat),
+        None => match std::env::var("POLL_FIAT") {
+            Ok(name) if !name.is_empty() => match <price::Fiat as clap::ValueEnum>::from_str(&name, true) {
+                Ok(fiat) => Some(fiat),
+                Err(_) => bail_config!("POLL_FIAT: '{}' is not a currency (use usd, eur or off)", name),
+            },
+            _ => None,
+        },
+    };
+    price::init(fiat);
+
+    let cache_ttl = match std::env::var("POLL_CACHE_TTL") {
+        _ if cli.no_cache => 0,
+        Ok(value) if !value.is_empty() => match value.parse() {
+            Ok(ttl) => ttl,
+            Err(_) => bail_config!("POLL_CACHE_TTL: '{}' is not a number of seconds", value),
+        },
+        _ => cache::DEFAULT_TTL_SECS,
+    };
+    cache::init(cache_ttl);
+
+    let max_rps = match cli.max_rps {
+        Some(rps) => Some(rps),
+        None => match std::env::var("POLL_MAX_RPS") {
+            Ok(value) if !value.is_empty() => match value.parse() {
+                Ok(rps) => Some(rps),
+                Err(_) => bail_config!("POLL_MAX_RPS: '{}' is not a number of requests per second", value),
+            },
+            _ => None,
+        },
+    };
+    rpc::init(max_rps);
+
+    // Completions and man pages are generated offline, without touching the RPC endpoint
+    if let Commands::Completions { shell, mangen } = &cli.command {
+        match shell {
+            Some(shell) if !mangen => completions::print_completions(*shell),
+            _ => completions::print_manpage()?,
+        }
+        return Ok(());
+    }
+
+    // Local template management needs no RPC connection either
+    match &cli.command {
+        Commands::Template { action: TemplateCommands::Save { name, fields } } => {
+            return templates::save_template(name, fields.clone());
+        }
+        Commands::Template { action: TemplateCommands::List } => return templates::list_templates(),
+        Commands::Cache { action: CacheCommands::Clear } => return cache::clear_cache(),
+        Commands::Accounts { action: AccountCommands::Add { name, keystore } } => return accounts::add_account(name, keystore),
+        Commands::Accounts { action: AccountCommands::Remove { name } } => return accounts::remove_account(name),
+        Commands::Accounts { action: AccountCommands::List } => return accounts::list_accounts(),
+        Commands::Key { action: KeyCommands::Import { name, from_env } } => return accounts::import_key(name, *from_env),
+        Commands::Contracts { action: ContractCommands::Add { name, address } } => return contracts::add_contract(name, address),
+        Commands::Contracts { action: ContractCommands::Remove { name } } => return contracts::remove_contract(name),
+        Commands::Contracts { action: ContractCommands::List } => return contracts::list_contracts(),
+        Commands::Policy { action: PolicyCommands::Show } => return policy::show_policy(),
+        Commands::Policy { action: PolicyCommands::Set { key, value, contract } } => {
+            return policy::set_policy(key, Some(value), contract.as_deref());
+        }
+        Commands::Policy { action: PolicyCommands::Unset { key, contract } } => return policy::set_policy(key, None, contract.as_deref()),
+        Commands::History { rerun: Some(number), .. } => return audit::rerun(*number),
+        Commands::History { limit, format, select, .. } => return audit::show_history(*limit, format, select),
+        Commands::Archive { action: ArchiveCommands::Import { file, force } } => return archive::import_archive(file, *force),
+        Commands::Archive { action: ArchiveCommands::Diff { before, after, format } } => {
+            return archive::diff_archive_files(before, after, format);
+        }
+        Commands::Draft { action } => match action {
+            DraftCommands::New { fields, template } => {
+                let template = template.as_deref().map(templates::get).transpose()?;
+                return drafts::new_draft(fields.clone(), template.as_ref());
+            }
+            DraftCommands::Edit { id, fields } => return drafts::edit_draft(*id, fields.clone()),
+            DraftCommands::List => return drafts::list_drafts(),
+            DraftCommands::Show { id, format, output } => return drafts::show_draft(*id, format, output.clone()),
+            DraftCommands::Approve { id, by } => return drafts::approve_draft(*id, by),
+            DraftCommands::Submit { .. } => {}
+        },
+        Commands::Github { action: GithubCommands::Import { repo, issue, fields } } => {
+            return github::import_issue(repo, *issue, fields.clone()).await;
+        }
+        Commands::Schedule { action: ScheduleCommands::List } => return schedule::list_scheduled(),
+        Commands::Schedule { action: ScheduleCommands::Cancel { id } } => return schedule::cancel_scheduled(*id),
+        Commands::Delegates { action: DelegateCommands::Fetch { cid } } => return profiles::fetch_profile(cid).await,
+        Commands::Delegates { action: DelegateCommands::Show { address } } => return profiles::show_profile(*address),
+        Commands::Rules { action: RuleCommands::Queue } => return rules::show_queue(),
+        Commands::Rules { action: RuleCommands::Dismiss { id } } => return rules::dismiss(*id),
+        Commands::Attest { action: AttestCommands::Collect { file, copies } } => return attest::collect(file, copies),
+        Commands::Attest { action: AttestCommands::Verify { file, observers, threshold } } => return attest::verify(file, observers, *threshold),
+        Commands::Allowlist { action: AllowlistCommands::Build { csv: Some(csv), output, .. } } => return allowlist::build_from_csv(csv, output),
+        Commands::Allowlist { action: AllowlistCommands::Prove { address, file, format } } => return allowlist::prove(*address, file, format),
+        Commands::Hooks { action: HookCommands::List } => return hooks::list_hooks(),
+        Commands::Plugins => return plugins::list_plugins(),
+        Commands::Plugin(args) => return plugins::run_plugin(args, &cli.connection),
+        _ => {}
+    }
+
+    if (cli.connection.block.is_some() || cli.connection.finalized)
+        && !matches!(cli.command, Commands::View { .. } | Commands::Results { .. } | Commands::Chart { .. } | Commands::TokenBalance { .. } | Commands::Analytics { .. } | Commands::Snapshot { .. })
+    {
+        bail_user!("--block and --finalized only apply to {}", pinned::PINNABLE_COMMANDS);
+    }
+
+    // A pasted polling:// link or explorer URL picks the chain and contract
+    if let Some(poll) = cli.command.poll_ref() {
+        cli.connection.follow_link(poll)?;
+    }
+    if let Commands::Correlate { with, .. } = &cli.command {
+        cli.connection.follow_link(with)?;
+    }
+
+    // Flags override environment variables, which override the local Anvil defaults
+    let connection = cli.connection.connection()?;
+
+    if cli.connection.all_contracts {
+        return run_all_contracts(cli.command, &connection).await;
+    }
+
+    let contract_address = cli.connection.contract_address()?;
+    policy::init(&contract_address, cli.allow_mainnet)?;
+
+    if let Commands::Doctor = cli.command {
+        return doctor::run_doctor(&connection, &contract_address, std::env::var("GOVERNANCE_TOKEN_ADDRESS").ok()).await;
+    }
+    if let Commands::Proxy { listen } = cli.command {
+        return proxy::serve(&connection.rpc_url, &contract_address, listen).await;
+    }
+
+    let mut poll_manager = PollManager::new(&connection, &contract_address).await?;
+    
+    // Set governance token if provided
+    if let Ok(token_address) = std::env::var("GOVERNANCE_TOKEN_ADDRESS") {
+        poll_manager.set_governance_token(&token_address).await?;
+    }
+    session::init(session::Limits {
+        timeout: cli.session_timeout,
+        max_transactions: cli.session_max_txs,
+        max_gas: cli.session_max_gas,
+    });
+    relay::init(cli.sponsor, cli.private_tx, poll_manager.wallet.chain_id())?;
+    audit::record_connection(poll_manager.sender, poll_manager.wallet.chain_id(), poll_manager.contract.address());
+
+    match cli.command {
+        Commands::Create { fields, template, force, copy } => {
+            let template = template.as_deref().map(templates::get).transpose()?;
+            let spec = fields.resolve(template.as_ref())?;
+            let created = poll_manager.create_checked(&spec, force).await?;
+            if let Some(target) = copy {
+                clipboard::copy_created(&created, target);
+            }
+        }
+        Commands::Draft { action: DraftCommands::Submit { id, force } } => {
+            poll_manager.submit_draft(id, force).await?;
+        }
+        Commands::Template { action: TemplateCommands::Apply { name, fields, force, copy } } => {
+            let template = templates::get(&name)?;
+            let spec = fields.resolve(Some(&template))?;
+            let created = poll_manager.create_checked(&spec, force).await?;
+            if let Some(target) = copy {
+                clipboard::copy_created(&created, target);
+            }
+        }
+        Commands::Vote { poll_id, option, at, at_block, smart_account } => {
+            if let Some(trigger) = at.map(schedule::Trigger::At).or(at_block.map(schedule::Trigger::AtBlock)) {
+                poll_manager.schedule_vote(poll_id.id, option, trigger).await?;
+            } else if smart_account.smart_account.is_some() {
+                poll_manager.vote_from_smart_account(poll_id.id, option, &smart_account).await?;
+            } else {
+                poll_manager.vote(poll_id.id, option).await?;
+            }
+        }
+        Commands::VoteDelegate { poll_id, option, delegator } => {
+            poll_manager.vote_as_delegate(poll_id.id, option, &delegator).await?;
+        }
+        Commands::SetDelegate { delegate } => {
+            poll_manager.set_delegate(&delegate).await?;
+        }
+        Commands::RemoveDelegate => {
+            poll_manager.remove_delegate().await?;
+        }
+        Commands::View { poll_id } => {
+            poll_manager.view_poll(poll_id.id).await?;
+        }
+        Commands::List { category, tag, active_only, format, select, query } => {
+            poll_manager.list_enhanced_polls(category, tag, active_only, &format, &select, &query).await?;
+        }
+        Commands::Results { poll_id, follow: true, interval } => {
+            if connection.block.is_some() {
+                bail_user!("--follow tracks the head of the chain; drop --block or --finalized");
+            }
+            poll_manager.follow_results(poll_id.id, interval).await?;
+        }
+        Commands::Results { poll_id, .. } => {
+            poll_manager.get_results(poll_id.id).await?;
+        }
+        Commands::Chart { poll_id, kind, output } => {
+            poll_manager.chart(poll_id.id, kind, output).await?;
+        }
+        Commands::Close { poll_id, yes, force } => {
+            if poll_manager.confirm_close(poll_id.id, yes, force).await? {
+                poll_manager.close_poll(poll_id.id).await?;
+            }
+        }
+        Commands::Extend { poll_id, duration, ends_at, yes } => {
+            poll_manager.extend_poll(poll_id.id, duration, ends_at, yes).await?;
+        }
+        Commands::ExtendBatch { tag, duration, yes } => {
+            poll_manager.extend_batch(&tag, duration, yes).await?;
+        }
+        Commands::Maintain { action: MaintainCommands::CloseExpired { max_gas, dry_run, yes } } => {
+            poll_manager.close_expired(max_gas, dry_run, yes).await?;
+        }
+        Commands::MyPolls { no_prompt } => {
+            poll_manager.my_polls(no_prompt).await?;
+        }
+        Commands::VerifyVote { poll_id, address, tx, format, output } => {
+            poll_manager.verify_vote(poll_id.id, address, tx, &format, output).await?;
+        }
+        Commands::Costs { plan, eth_price, format, output } => {
+            poll_manager.cost_report(plan, eth_price, &format, output).await?;
+        }
+        Commands::DelegationAlerts { within, since, watch, format } => {
+            poll_manager.delegation_alerts(within, since, watch, &format).await?;
+        }
+        Commands::CreatorAlerts { within, watch, format } => {
+            poll_manager.creator_alerts(within, watch, &format).await?;
+        }
+        Commands::MyVotes => {
+            poll_manager.my_votes().await?;
+        }
+        Commands::MyStats => {
+            poll_manager.view_user_stats(None).await?;
+        }
+        Commands::Audit { poll_id, format, output } => {
+            poll_manager.sybil_report(poll_id.id, &format, output).await?;
+        }
+        Commands::UserReport { address, format, output } => {
+            poll_manager.user_report(address, &format, output).await?;
+        }
+        Commands::Delegation { address } => {
+            poll_manager.view_delegation_info(address).await?;
+        }
+        Commands::Delegates { action: DelegateCommands::Publish { statement, contact, focus } } => {
+            let focus_areas = focus.as_deref().map(spec::split_list).unwrap_or_default();
+            poll_manager.publish_profile(&statement, contact, focus_areas).await?;
+        }
+        Commands::Delegates { action: DelegateCommands::Top { limit, format, output } } => {
+            poll_manager.top_delegates(limit, &format, output).await?;
+        }
+        Commands::Delegates { .. } => unreachable!("handled before connecting"),
+        Commands::TokenBalance { token, address } => {
+            poll_manager.check_token_balance(token, address).await?;
+        }
+        Commands::Export { poll_id, format, output, .. } if format.eq_ignore_ascii_case("xlsx") => {
+            poll_manager.export_xlsx(poll_id.map(|poll| poll.id), output).await?;
+        }
+        Commands::Export { poll_id, format, output, delimiter, no_header, select } => {
+            let Some(poll_id) = poll_id else {
+                bail_user!("--poll-id is required for {} exports; only xlsx exports every poll", format);
+            };
+            let csv_options = CsvOptions { delimiter, header: !no_header };
+            poll_manager.export_poll(poll_id.id, &format, output, csv_options, &select).await?;
+        }
+        Commands::Analytics { poll_id } => {
+            poll_manager.generate_analytics(poll_id.map(|poll| poll.id)).await?;
+        }
+        Commands::Velocity { poll_id, bucket, format, output } => {
+            poll_manager.voting_velocity(poll_id.id, &bucket, &format, output).await?;
+        }
+        Commands::Categories { format, output } => {
+            poll_manager.category_dashboard(&format, output).await?;
+        }
+        Commands::Tags { action: TagCommands::List { format, output } } => {
+            poll_manager.list_tags(&format, output).await?;
+        }
+        Commands::Tags { action: TagCommands::Stats { tag, format, output } } => {
+            poll_manager.tag_stats(&tag, &format, output).await?;
+        }
+        Commands::Whales { poll_id, top, format, output } => {
+            poll_manager.whale_analysis(poll_id.id, top, &format, output).await?;
+        }
+        Commands::Turnout { poll_id, token, format, output } => {
+            poll_manager.turnout(poll_id.id, token, &format, output).await?;
+        }
+        Commands::Leaderboard { by, since, top, format, output } => {
+            poll_manager.leaderboard(by, since, top, &format, output).await?;
+        }
+        Commands::Correlate { poll_id, with, format, output } => {
+            poll_manager.correlate(poll_id.id, with.id, &format, output).await?;
+        }
+        Commands::Engagement { action } => {
+            let (view, format, output) = match action {
+                EngagementCommands::Streaks { top, format, output } => (engagement::EngagementView::Streaks(top), format, output),
+                EngagementCommands::FirstTime { format, output } => (engagement::EngagementView::FirstTime, format, output),
+                EngagementCommands::Monthly { format, output } => (engagement::EngagementView::Monthly, format, output),
+            };
+            poll_manager.engagement(view, &format, output).await?;
+        }
+        Commands::Project { poll_id, model, format, output } => {
+            poll_manager.project_outcome(poll_id.id, &model, &format, output).await?;
+        }
+        Commands::Simulate { poll_id, votes, top_delegates, option, anvil, format } => {
+            let top_delegates = top_delegates.zip(option);
+            poll_manager.simulate(&connection.rpc_url, poll_id.id, votes, top_delegates, &anvil, &format).await?;
+        }
+        Commands::Certify { poll_id, output, eas } => {
+            poll_manager.certify_poll(poll_id.id, output, eas).await?;
+        }
+        Commands::VerifyAttestation { uid, certificate, attester } => {
+            poll_manager.verify_attestation(uid, certificate.as_deref(), attester).await?;
+        }
+        Commands::Publish { poll_id, ipfs, format } => {
+            poll_manager.publish_poll(poll_id.id, ipfs, &format).await?;
+        }
+        Commands::PostResults { poll_id, target, publish, topic_id, output } => {
+            poll_manager.post_results(poll_id.id, target, publish, topic_id, output).await?;
+        }
+        Commands::Announce { poll_id, event, to, link, watch } => match (watch, poll_id) {
+            (Some(interval), _) => poll_manager.announce_watch(&to, link.as_deref(), interval).await?,
+            (None, Some(poll_id)) => poll_manager.announce(poll_id.id, event, &to, link.as_deref()).await?,
+            (None, None) => unreachable!("clap requires --poll-id without --watch"),
+        },
+        Commands::SnapshotOrg { action } => match action {
+            SnapshotOrgCommands::Export { poll_id, space, output } => {
+                poll_manager.snapshot_export(poll_id.id, &space, output).await?;
+            }
+            SnapshotOrgCommands::Import { file, category, min_participation } => {
+                poll_manager.snapshot_import(&file, &category, min_participation).await?;
+            }
+        },
+        Commands::Calendar { mine, reminder, output } => {
+            poll_manager.export_calendar(mine, reminder, output).await?;
+        }
+        Commands::Feed { format, category, limit, link, output } => {
+            poll_manager.generate_feed(&format, category, limit, link, output).await?;
+        }
+        Commands::Sync { from_block, to_block, reorg_depth, watch } => {
+            poll_manager.sync(from_block, to_block, reorg_depth, watch).await?;
+        }
+        Commands::Diff { format } => {
+            poll_manager.diff_since_sync(&format).await?;
+        }
+        Commands::Archive { action: ArchiveCommands::Export { output } } => {
+            poll_manager.export_archive(output).await?;
+        }
+        Commands::Schedule { action: ScheduleCommands::Run { watch } } => {
+            poll_manager.run_schedule(watch).await?;
+        }
+        Commands::Schedule { .. } => unreachable!("handled before connecting"),
+        Commands::Rules { action: RuleCommands::Check { file, watch } } => {
+            poll_manager.check_rules(file.as_deref(), watch).await?;
+        }
+        Commands::Rules { action: RuleCommands::Approve { id } } => {
+            poll_manager.approve_recommendation(id).await?;
+        }
+        Commands::Rules { .. } => unreachable!("handled before connecting"),
+        Commands::Eligibility { poll_id, address, format } => {
+            poll_manager.check_eligibility(poll_id.id, address, &format).await?;
+        }
+        Commands::Rewards { poll_id, pool, scheme, decimals, format, output } => {
+            poll_manager.poll_rewards(poll_id.id, pool, decimals, scheme, &format, output).await?;
+        }
+        Commands::Snapshot { token, format, output } => {
+            poll_manager.token_snapshot(token, &format, output).await?;
+        }
+        Commands::Attest { action: AttestCommands::Init { poll_id, observers, threshold, output } } => {
+            poll_manager.init_attestation(poll_id.id, observers, threshold, output).await?;
+        }
+        Commands::Attest { action: AttestCommands::Sign { file } } => {
+            poll_manager.sign_attestation(&file).await?;
+        }
+        Commands::Attest { .. } => unreachable!("handled before connecting"),
+        Commands::Allowlist { action: AllowlistCommands::Build { holders_at: Some(block), token, min_balance, output, .. } } => {
+            poll_manager.build_from_holders(token, block, min_balance, &output).await?;
+        }
+        Commands::Allowlist { .. } => unreachable!("handled before connecting"),
+        Commands::Archive { .. } => unreachable!("handled before connecting"),
+        Commands::Completions { .. } | Commands::Doctor | Commands::Proxy { .. } | Commands::Template { .. } | Commands::Draft { .. } | Commands::Cache { .. } | Commands::Accounts { .. } | Commands::Key { .. } | Commands::Contracts { .. } | Commands::Policy { .. } | Commands::History { .. } | Commands::Plugins | Commands::Plugin(_) => unreachable!("handled before connecting"),
+        Commands::Github { action: GithubCommands::Sync } => {
+            poll_manager.github_sync().await?;
+        }
+        Commands::Github { .. } => unreachable!("handled before connecting"),
+        Commands::Site { action: SiteCommands::Build { out, title, full, base_url } } => {
+            poll_manager.build_site(&out, &title, full, base_url.as_deref()).await?;
+        }
+        Commands::Script { action: ScriptCommands::Run { file } } => {
+            poll_manager.run_script(&file).await?;
+        }
+        Commands::Hooks { action: HookCommands::Watch { interval, from_block } } => {
+            poll_manager.watch_hooks(interval, from_block).await?;
+        }
+        Commands::Hooks { .. } => unreachable!("handled before connecting"),
+        Commands::Bot { platform: BotCommands::Telegram { interval } } => {
+            poll_manager.run_telegram_bot(interval).await?;
+        }
+        Commands::Bot { platform: BotCommands::Matrix } => {
+            poll_manager.run_matrix_bot().await?;
+        }
+        Commands::Serve { listen, operator } => {
+            Arc::new(poll_manager).serve(listen, operator.as_deref()).await?;
+        }
+        Commands::Grpc { listen } => {
+            Arc::new(poll_manager).serve_grpc(listen).await?;
+        }
+        Commands::PollIds => {
+            poll_manager.print_poll_ids().await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// `--all-contracts`: `list` or `analytics` once per registered contract
+async fn run_all_contracts(command: Commands, connection: &connection::Connection) -> Result<()> {
+    let registered = contracts::all()?;
+    if registered.is_empty() {
+        bail_user!("No contracts registered; add them with `contracts add <name> <address>`");
+    }
+
+    match command {
+        Commands::List { category, tag, active_only, format, select, query } => {
+            for (name, address) in &registered {
+                if !format.eq_ignore_ascii_case("ndjson") && !select.is_set() {
+                    say!("\n{} {} ({:?})", "🏛️ ".cyan().bold(), name.yellow().bold(), address);
+                }
+                let manager = PollManager::new(connection, &format!("{:?}", address)).await?;
+                manager.list_enhanced_polls(category.clone(), tag.clone(), active_only, &format, &select, &query).await?;
+            }
+        }
+        Commands::Analytics { poll_id: None } => {
+            let mut summaries = Vec::with_capacity(registered.len());
+            for (name, address) in &registered {
+                let manager = PollManager::new(connection, &format!("{:?}", address)).await?;
+                summaries.push(manager.contract_summary(name).await?);
+            }
+            contracts::print_summaries(&summaries);
+        }
+        _ => bail_user!("--all-contracts works with `list` and `analytics` (without --poll-id)"),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::abi::Token;
+
+    const CREATED_AT: u64 = 1_704_067_200;
+
+    fn mock_manager() -> (PollManager<Provider<MockProvider>>, MockProvider) {
+        let (provider, mock) = Provider::mocked();
+        let wallet: LocalWallet = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80".parse().unwrap();
+        (PollManager::with_client(Arc::new(provider), wallet, Address::zero()), mock)
+    }
+
+    /// Queue canned `getPoll` and `getPollResults` responses, in the order they are called
+    fn mock_poll(mock: &MockProvider, options: &[&str], status: u8, min_participation: u64, results: &[u64]) {
+        let total_votes: u64 = results.iter().sum();
+        let poll = Token::Tuple(vec![
+            Token::Uint(7.into()),
+            Token::String("Which option?".to_string()),
+            Token::Array(options.iter().map(|o| Token::String(o.to_string())).collect()),
+            Token::Address(Address::zero()),
+            Token::Uint(CREATED_AT.into()),
+            Token::Uint((CREATED_AT + 86_400).into()),
+            Token::Uint(status.into()),
+            Token::Uint(0.into()),
+            Token::Uint(1.into()),
+            Token::Uint(min_participation.into()),
+            Token::Uint(total_votes.into()),
+            Token::Uint(total_votes.into()),
+            Token::String(String::new()),
+            Token::Array(vec![]),
+            Token::Uint(0.into()),
+            Token::Bool(false),
+            Token::Uint(0.into()),
+        ]);
+        let poll_results = [
+            Token::Array(results.iter().map(|v| Token::Uint((*v).into())).collect()),
+            Token::Uint(total_votes.into()),
+            Token::Uint(total_votes.into()),
+        ];
+
+        // MockProvider answers requests last-in, first-out
+        mock.push::<Bytes, _>(Bytes::from(ethers::abi::encode(&poll_results))).unwrap();
+        mock.push::<Bytes, _>(Bytes::from(ethers::abi::encode(&[poll]))).unwrap();
+    }
+
+    fn sample_export() -> PollExport {
+        PollExport {
+            id: 7,
+            question: "Ship v2, or wait?".to_string(),
+            creator: "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266".to_string(),
+            created_at: "2024-01-01 00:00:00 UTC".to_string(),
+            end_time: "2024-01-08 00:00:00 UTC".to_string(),
+            created_at_unix: 1_704_067_200,
+            end_time_unix: 1_704_672_000,
+            is_active: false,
+            poll_type: "Standard".to_string(),
+            winner_metric: "votes".to_string(),
+            total_votes: 4,
+            total_weight: U256::from(4),
+            options: vec!["Ship".to_string(), "Wait, then ship".to_string()],
+            votes: vec![3, 1],
+            weights: vec![U256::from(3), U256::from(1)],
+            min_participation: 5,
+            quorum_status: quorum::QuorumState::Failed,
+        }
+    }
+
+    #[test]
+    fn impersonated_calls_are_sent_from_the_impersonated_address() {
+        let (mut manager, _) = mock_manager();
+        let own = manager.as_sender(manager.contract.close_poll(U256::zero()));
+        assert_eq!(own.tx.from(), None, "the wallet fills in and signs its own transactions");
+
+        let whale = Address::from_low_u64_be(0xbeef);
+        manager.sender = whale;
+        let impersonated = manager.as_sender(manager.contract.close_poll(U256::zero()));
+        assert_eq!(impersonated.tx.from(), Some(&whale));
+    }
+
+    #[test]
+    fn poll_type_round_trips() {
+        for (i, name) in POLL_TYPES.iter().enumerate() {
+            let value = poll_type_to_u8(name).unwrap();
+            assert_eq!(value as usize, i);
+            assert_eq!(u8_to_poll_type(value).to_lowercase(), *name);
+        }
+        assert_eq!(poll_type_to_u8("WEIGHTED").unwrap(), 1);
+        assert_eq!(u8_to_poll_type(9), "Unknown");
+    }
+
+    #[test]
+    fn category_round_trips() {
+        for (i, name) in CATEGORIES.iter().enumerate() {
+            let value = category_to_u8(name).unwrap();
+            assert_eq!(value as usize, i);
+            assert_eq!(u8_to_category(value).to_lowercase(), *name);
+        }
+        assert_eq!(u8_to_category(5), "Unknown");
+    }
+
+    #[test]
+    fn invalid_enum_names_are_user_errors() {
+        let err = poll_type_to_u8("ranked").unwrap_err();
+        assert_eq!(error::classify(&err), error::ErrorKind::User);
+        let err = category_to_u8("sports").unwrap_err();
+        assert_eq!(error::classify(&err), error::ErrorKind::User);
+    }
+
+    #[test]
+    fn status_names_match_contract_enum() {
+        let names: Vec<&str> = (0..=5).map(u8_to_status).collect();
+        assert_eq!(names, ["Active", "Closed", "Expired", "Cancelled", "Archived", "Unknown"]);
+    }
+
+    #[test]
+    fn csv_has_one_row_per_option() {
+        let csv = render_csv(&sample_export(), CsvOptions::default()).unwrap();
+        let lines: Vec<&str> = csv.split_terminator("\r\n").collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], "id,question,creator,created_at,end_time,is_active,poll_type,winner_metric,total_votes,total_weight,option,votes,weight,min_participation,quorum_status");
+        assert!(lines[1].starts_with("7,\"Ship v2, or wait?\",0xf39f"));
+        assert!(lines[1].ends_with(",false,Standard,votes,4,4,Ship,3,3,5,failed"));
+        assert!(lines[2].ends_with(",false,Standard,votes,4,4,\"Wait, then ship\",1,1,5,failed"));
+    }
+
+    #[test]
+    fn csv_round_trips_quotes_and_newlines() {
+        let mut export = sample_export();
+        export.question = "Say \"yes\"\nor no?".to_string();
+        let options = CsvOptions { delimiter: b';', header: false };
+        let csv = render_csv(&export, options).unwrap();
+
+        let mut reader = csv::ReaderBuilder::new().delimiter(b';').has_headers(false).from_reader(csv.as_bytes());
+        let rows: Vec<csv::StringRecord> = reader.records().collect::<std::result::Result<_, _>>().unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(&rows[0][1], "Say \"yes\"\nor no?");
+        assert_eq!(&rows[1][10], "Wait, then ship");
+    }
+
+    #[test]
+    fn parses_delimiters() {
+        assert_eq!(parse_delimiter(";"), Ok(b';'));
+        assert_eq!(parse_delimiter("tab"), Ok(b'\t'));
+        assert!(parse_delimiter("::").is_err());
+        assert!(parse_delimiter("\"").is_err());
+    }
+
+    #[test]
+    fn json_export_round_trips() {
+        let export = sample_export();
+        let json = serde_json::to_string_pretty(&export).unwrap();
+        let parsed: PollExport = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.id, export.id);
+        assert_eq!(parsed.options, export.options);
+        assert_eq!(parsed.votes, export.votes);
+
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["total_votes"], 4);
+        assert_eq!(value["is_active"], false);
+        assert_eq!(value["end_time_unix"], 1_704_672_000);
+        assert_eq!(value["quorum_status"], "failed");
+    }
+
+    #[test]
+    fn markdown_lists_every_option() {
+        let markdown = render_markdown(&sample_export());
+        assert!(markdown.starts_with("# Poll #7: Ship v2, or wait?"));
+        assert!(markdown.contains("| 0 | Ship | 3 |"));
+        assert!(markdown.contains("| 1 | Wait, then ship | 1 |"));
+        assert!(markdown.contains("- **Quorum:** 4 of 5 votes (failed)"));
+    }
+
+    #[tokio::test]
+    async fn analytics_from_mocked_results() {
+        let (manager, mock) = mock_manager();
+        mock_poll(&mock, &["A", "B", "C"], 1, 20, &[5, 3, 2]);
+
+        let analytics = manager.poll_analytics(7).await.unwrap();
+        assert_eq!(analytics.total_votes, 10);
+        assert_eq!(analytics.leading_option, "A");
+        assert!((analytics.margin - 20.0).abs() < 1e-9);
+        assert!((analytics.participation_rate - 50.0).abs() < 1e-9);
+        assert_eq!(analytics.time_remaining.as_deref(), Some("Closed"));
+        assert_eq!(analytics.quorum.state, quorum::QuorumState::Failed);
+
+        let shares: Vec<f64> = analytics.options_detail.iter().map(|d| d.percentage).collect();
+        assert_eq!(shares, [50.0, 30.0, 20.0]);
+    }
+
+    #[tokio::test]
+    async fn analytics_without_votes() {
+        let (manager, mock) = mock_manager();
+        mock_poll(&mock, &["Yes", "No"], 1, 0, &[0, 0]);
+
+        let analytics = manager.poll_analytics(7).await.unwrap();
+        assert_eq!(analytics.total_votes, 0);
+        assert_eq!(analytics.leading_option, "");
+        assert_eq!(analytics.margin, 0.0);
+        assert_eq!(analytics.participation_rate, 100.0);
+        assert!(analytics.options_detail.iter().all(|d| d.percentage == 0.0));
+    }
+
+    #[tokio::test]
+    async fn export_from_mocked_results() {
+        let (manager, mock) = mock_manager();
+        mock_poll(&mock, &["Yes", "No"], 1, 0, &[4, 1]);
+
+        let export = manager.build_poll_export(7).await.unwrap();
+        assert_eq!(export.id, 7);
+        assert!(!export.is_active);
+        assert_eq!(export.total_votes, 5);
+        assert_eq!(export.votes, [4, 1]);
+        assert_eq!(export.created_at, "2024-01-01 00:00:00 UTC");
+        assert_eq!(export.created_at_unix, 1_704_067_200);
+        assert_eq!(export.quorum_status, quorum::QuorumState::NotRequired);
+    }
+
+    #[test]
+    fn weighted_tally_ranks_by_weight() {
+        let options = vec!["Few whales".to_string(), "Many minnows".to_string()];
+        let weights = [U256::from(10), U256::from(3)];
+        let tally = tally::PollTally::new(&options, &weights, &[1, 3], 4, U256::from(13), true);
+
+        assert_eq!(tally.winner_metric(), "weight");
+        assert_eq!(tally.ranked()[0].option, "Few whales");
+        assert_eq!(tally.options[1].vote_share, 75.0);
+        assert!((tally.options[0].weight_share - 76.923).abs() < 1e-3);
+    }
+}
@@ -0,0 +1,144 @@
+use std::collections::HashSet;
+
+use anyhow::Result;
+use colored::*;
+use ethers::prelude::*;
+
+use crate::error::bail_user;
+use crate::spec::PollSpec;
+use crate::{duration, events, templates, PollManager};
+
+/// Options longer than this are hard to read in lists and on explorers
+pub const MAX_OPTION_LENGTH: usize = 80;
+/// Durations outside this window are allowed by the contract but usually a typo
+pub const SUSPICIOUS_SHORT: u64 = 3_600;
+pub const SUSPICIOUS_LONG: u64 = 90 * 86_400;
+
+/// Words typed twice in a row ("the the"), a common typo in immutable questions
+fn repeated_words(text: &str) -> Vec<String> {
+    let words: Vec<String> = text.split_whitespace()
+        .map(|w| w.trim_matches(|c: char| !c.is_alphanumeric()).to_lowercase())
+        .collect();
+    words.windows(2)
+        .filter(|pair| !pair[0].is_empty() && pair[0] == pair[1])
+        .map(|pair| pair[0].clone())
+        .collect()
+}
+
+/// Content problems worth a second look before a poll becomes immutable.
+///
+/// `known_tags` is `None` when no tag vocabulary is available; otherwise tags
+/// outside it are flagged so typos do not fragment tag filters.
+pub fn lint(spec: &PollSpec, duration_seconds: u64, known_tags: Option<&HashSet<String>>) -> Vec<String> {
+    let mut issues = Vec::new();
+
+    for word in repeated_words(&spec.question) {
+        issues.push(format!("Question repeats the word \"{}\"", word));
+    }
+    if spec.question.contains("  ") || spec.question.trim() != spec.question {
+        issues.push("Question has stray whitespace".to_string());
+    }
+
+    let mut seen = HashSet::new();
+    for (i, option) in spec.options.iter().enumerate() {
+        if option.trim().is_empty() {
+            issues.push(format!("Option {} is empty", i));
+        } else if !seen.insert(option.trim().to_lowercase()) {
+            issues.push(format!("Option {} duplicates \"{}\"", i, option));
+        }
+        if option.chars().count() > MAX_OPTION_LENGTH {
+            issues.push(format!("Option {} is {} characters (limit {})", i, option.chars().count(), MAX_OPTION_LENGTH));
+        }
+    }
+
+    if spec.category.eq_ignore_ascii_case("governance") && spec.description.as_deref().is_none_or(|d| d.trim().is_empty()) {
+        issues.push("Governance polls should have a --description".to_string());
+    }
+
+    if duration_seconds < SUSPICIOUS_SHORT {
+        issues.push(format!("Duration {} is under {}", duration::format(duration_seconds), duration::format(SUSPICIOUS_SHORT)));
+    } else if duration_seconds > SUSPICIOUS_LONG {
+        issues.push(format!("Duration {} is over {}", duration::format(duration_seconds), duration::format(SUSPICIOUS_LONG)));
+    }
+
+    if let Some(known) = known_tags.filter(|k| !k.is_empty()) {
+        for tag in spec.tags.iter().filter(|t| !known.contains(*t)) {
+            issues.push(format!("Tag \"{}\" has not been used before", tag));
+        }
+    }
+
+    issues
+}
+
+impl<M: Middleware + 'static> PollManager<M> {
+    /// Tags used by existing polls and saved templates, or `None` if the logs are unavailable
+    async fn known_tags(&self) -> Option<HashSet<String>> {
+        let mut tags = self.fetch_created_tags().await.ok()?;
+        if let Ok(saved) = templates::all() {
+            tags.extend(saved.into_values().flat_map(|t| t.tags));
+        }
+        Some(tags)
+    }
+
+    /// Lint a poll, then create it; any finding aborts unless `force` is set
+    pub async fn create_checked(&self, spec: &PollSpec, force: bool) -> Result<events::CreatedPoll> {
+        let duration_seconds = spec.duration_seconds()?;
+        let known_tags = if spec.tags.is_empty() { None } else { self.known_tags().await };
+        let issues = lint(spec, duration_seconds, known_tags.as_ref());
+
+        if !issues.is_empty() {
+            say!("{}", "⚠️  Content check:".yellow().bold());
+            for issue in &issues {
+                say!("  - {}", issue);
+            }
+            if !force {
+                bail_user!("{} content issue(s) found; fix them or pass --force to create anyway", issues.len());
+            }
+            say!("{}", "Continuing anyway (--force)".yellow());
+        }
+
+        self.create_from_spec(spec).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::PollFields;
+
+    fn spec(question: &str, options: &str) -> PollSpec {
+        PollFields { question: Some(question.to_string()), options: Some(options.to_string()), ..Default::default() }
+            .resolve(None)
+            .unwrap()
+    }
+
+    #[test]
+    fn clean_poll_passes() {
+        assert!(lint(&spec("Adopt the roadmap?", "Yes,No"), 7 * 86_400, None).is_empty());
+    }
+
+    #[test]
+    fn flags_option_problems() {
+        let long = "x".repeat(MAX_OPTION_LENGTH + 1);
+        let issues = lint(&spec("Pick one", &format!("Yes,,yes,{}", long)), 7 * 86_400, None);
+        assert_eq!(issues.len(), 3, "{:?}", issues);
+        assert!(issues[0].contains("Option 1 is empty"));
+        assert!(issues[1].contains("duplicates"));
+        assert!(issues[2].contains("characters"));
+    }
+
+    #[test]
+    fn flags_question_duration_description_and_tags() {
+        let mut poll = spec("Adopt the the roadmap?", "Yes,No");
+        poll.category = "governance".to_string();
+        poll.tags = vec!["roadmap".to_string(), "roadmapp".to_string()];
+        let known: HashSet<String> = ["roadmap".to_string()].into();
+
+        let issues = lint(&poll, 120 * 86_400, Some(&known));
+        assert!(issues.iter().any(|i| i.contains("repeats the word \"the\"")));
+        assert!(issues.iter().any(|i| i.contains("--description")));
+        assert!(issues.iter().any(|i| i.contains("over 90d")));
+        assert!(issues.iter().any(|i| i.contains("\"roadmapp\"")));
+        assert!(!issues.iter().any(|i| i.contains("\"roadmap\"")));
+    }
+}
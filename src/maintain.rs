@@ -0,0 +1,226 @@
+use anyhow::Result;
+use colored::*;
+use ethers::prelude::*;
+
+use crate::confirm::confirm;
+use crate::error::bail_user;
+use crate::numbers::SaturatingU64;
+use crate::preview::truncate;
+use crate::{format_timestamp, numbers, output, redact, PollManager};
+
+/// What `close-expired` will do with one expired poll
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Close,
+    /// closePoll is restricted to the poll's creator
+    NotCreator,
+    /// closePoll reverts while totalVotes is below minParticipation
+    ShortOfQuorum,
+    /// Gas estimation failed, so the transaction would revert
+    WouldRevert(String),
+    /// Closable, but past --max-gas; left for the next run
+    OverBudget,
+}
+
+impl Verdict {
+    fn describe(&self) -> String {
+        match self {
+            Verdict::Close => "close".to_string(),
+            Verdict::NotCreator => "skip: not your poll".to_string(),
+            Verdict::ShortOfQuorum => "skip: short of quorum".to_string(),
+            Verdict::WouldRevert(reason) => format!("skip: would revert ({})", reason),
+            Verdict::OverBudget => "later: over gas budget".to_string(),
+        }
+    }
+}
+
+/// A poll past its end time whose status is still Active
+#[derive(Debug, Clone)]
+pub struct ExpiredPoll {
+    pub poll_id: u64,
+    pub question: String,
+    pub end_time: u64,
+    pub total_votes: u64,
+    /// minParticipation, shown next to the votes when set
+    pub min_participation: u64,
+    /// Estimated gas for closePoll; 0 when it was not estimated
+    pub gas: u64,
+    pub verdict: Verdict,
+}
+
+/// Whether `signer` may close the poll, before estimating gas
+fn verdict(creator: Address, signer: Address, total_votes: u64, min_participation: u64) -> Verdict {
+    if creator != signer {
+        Verdict::NotCreator
+    } else if min_participation > 0 && total_votes < min_participation {
+        Verdict::ShortOfQuorum
+    } else {
+        Verdict::Close
+    }
+}
+
+/// Keep closing polls, longest-expired first, while their gas fits in `max_gas`; defer the rest
+fn apply_budget(polls: &mut [ExpiredPoll], max_gas: Option<u64>) {
+    let Some(max_gas) = max_gas else { return };
+    let mut used = 0u64;
+    for poll in polls.iter_mut().filter(|poll| poll.verdict == Verdict::Close) {
+        if used.saturating_add(poll.gas) > max_gas {
+            poll.verdict = Verdict::OverBudget;
+        } else {
+            used += poll.gas;
+        }
+    }
+}
+
+fn print_plan(polls: &[ExpiredPoll], max_gas: Option<u64>) {
+    say!("\n{}", "🧹 EXPIRED POLLS STILL OPEN".cyan().bold().underline());
+    say!("{}", "═".repeat(60).cyan());
+    say!("{:>5}  {:<32}  {:<17}  {:>7}  {:>9}  {}", "Poll", "Question", "Ended", "Votes", "Gas", "Action");
+    for poll in polls {
+        let votes = match poll.min_participation {
+            0 => numbers::format_integer(poll.total_votes),
+            min => format!("{}/{}", numbers::format_integer(poll.total_votes), numbers::format_integer(min)),
+        };
+        let gas = if poll.gas == 0 { "-".to_string() } else { numbers::format_integer(poll.gas) };
+        let action = match poll.verdict {
+            Verdict::Close => poll.verdict.describe().green().bold(),
+            Verdict::OverBudget => poll.verdict.describe().yellow(),
+            _ => poll.verdict.describe().dimmed(),
+        };
+        say!("{:>5}  {:<32}  {:<17}  {:>7}  {:>9}  {}",
+            format!("#{}", poll.poll_id), truncate(&poll.question, 32), format_timestamp(poll.end_time), votes, gas, action);
+    }
+
+    let closing: Vec<&ExpiredPoll> = polls.iter().filter(|poll| poll.verdict == Verdict::Close).collect();
+    let gas: u64 = closing.iter().map(|poll| poll.gas).sum();
+    match max_gas {
+        Some(max_gas) => say!("\n{} {} polls, {} of {} gas", "To close:".yellow().bold(), closing.len(),
+            numbers::format_integer(gas), numbers::format_integer(max_gas)),
+        None => say!("\n{} {} polls, {} gas", "To close:".yellow().bold(), closing.len(), numbers::format_integer(gas)),
+    }
+}
+
+impl<M: Middleware + 'static> PollManager<M> {
+    /// Every poll past its end time but still Active, judged for the signer and `max_gas`
+    async fn expired_polls(&self, max_gas: Option<u64>) -> Result<Vec<ExpiredPoll>> {
+        let now = chrono::Utc::now().timestamp() as u64;
+        let poll_count = self.contract.poll_count().call().await?.to_u64_saturating();
+        let progress = output::spinner("🔍 Looking for expired polls...");
+        let mut polls = Vec::new();
+        for poll_id in 0..poll_count {
+            // Read fresh: closePoll does not check the status, so a stale cache would close a poll twice
+            let poll = self.contract.get_poll(U256::from(poll_id)).call().await?;
+            let end_time = poll.5.to_u64_saturating();
+            if poll.6 != 0 || end_time > now {
+                continue;
+            }
+            let (total_votes, min_participation) = (poll.10.to_u64_saturating(), poll.9.to_u64_saturating());
+            let mut expired = ExpiredPoll {
+                poll_id,
+                question: poll.1,
+                end_time,
+                total_votes,
+                min_participation,
+                gas: 0,
+                verdict: verdict(poll.3, self.sender, total_votes, min_participation),
+            };
+            if expired.verdict == Verdict::Close {
+                let call = self.as_sender(self.contract.close_poll(U256::from(poll_id)));
+                match call.estimate_gas().await {
+                    Ok(gas) => expired.gas = gas.as_u64(),
+                    Err(e) => expired.verdict = Verdict::WouldRevert(redact::redact(&e.to_string())),
+                }
+            }
+            polls.push(expired);
+        }
+        progress.finish_and_clear();
+
+        polls.sort_by_key(|poll| (poll.end_time, poll.poll_id));
+        apply_budget(&mut polls, max_gas);
+        Ok(polls)
+    }
+
+    /// `maintain close-expired`: close the signer's polls that ended but are still Active, within a gas budget
+    pub async fn close_expired(&self, max_gas: Option<u64>, dry_run: bool, yes: bool) -> Result<()> {
+        if max_gas == Some(0) {
+            bail_user!("--max-gas must be more than zero");
+        }
+        let polls = self.expired_polls(max_gas).await?;
+        if polls.is_empty() {
+            say!("No expired polls are still open.");
+            return Ok(());
+        }
+        print_plan(&polls, max_gas);
+
+        let closing: Vec<&ExpiredPoll> = polls.iter().filter(|poll| poll.verdict == Verdict::Close).collect();
+        if closing.is_empty() {
+            say!("Nothing this signer can close.");
+            return Ok(());
+        }
+        if dry_run {
+            say!("{}", "Dry run; nothing was sent.".yellow());
+            return Ok(());
+        }
+        if !confirm(&format!("Close {} polls?", closing.len()), yes)? {
+            return Ok(());
+        }
+
+        let (total, mut closed) = (closing.len(), 0);
+        for poll in closing {
+            let call = self.as_sender(self.contract.close_poll(U256::from(poll.poll_id)));
+            // Keep going after a failure; every poll is read fresh again on the next run
+            let result = match self.send_call(&call).await {
+                Ok(tx) => tx.await.map_err(anyhow::Error::from),
+                Err(e) => Err(e),
+            };
+            self.cache.invalidate(poll.poll_id);
+            match result {
+                Ok(Some(receipt)) => {
+                    closed += 1;
+                    say!("{} #{} in {:?}", "✅ Closed poll".green().bold(), poll.poll_id, receipt.transaction_hash);
+                }
+                Ok(None) => eprintln!("{} #{}: transaction was dropped before it was mined",
+                    output::render("❌ Poll".to_string()).red(), poll.poll_id),
+                Err(e) => eprintln!("{} #{}: {}", output::render("❌ Poll".to_string()).red(), poll.poll_id, redact::redact(&format!("{:#}", e))),
+            }
+        }
+        say!("{} {} of {} polls", "🧹 Closed".cyan().bold(), closed, total);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expired(poll_id: u64, gas: u64, verdict: Verdict) -> ExpiredPoll {
+        ExpiredPoll {
+            poll_id,
+            question: String::new(),
+            end_time: poll_id,
+            total_votes: 0,
+            min_participation: 0,
+            gas,
+            verdict,
+        }
+    }
+
+    #[test]
+    fn judges_and_budgets_expired_polls() {
+        let (me, them) = (Address::repeat_byte(1), Address::repeat_byte(2));
+        assert_eq!(verdict(them, me, 10, 0), Verdict::NotCreator);
+        assert_eq!(verdict(me, me, 4, 5), Verdict::ShortOfQuorum);
+        assert_eq!(verdict(me, me, 5, 5), Verdict::Close);
+        assert_eq!(verdict(me, me, 0, 0), Verdict::Close);
+
+        let mut polls = vec![
+            expired(0, 40_000, Verdict::Close),
+            expired(1, 0, Verdict::NotCreator),
+            expired(2, 40_000, Verdict::Close),
+            expired(3, 15_000, Verdict::Close),
+        ];
+        apply_budget(&mut polls, Some(60_000));
+        let verdicts: Vec<Verdict> = polls.into_iter().map(|poll| poll.verdict).collect();
+        assert_eq!(verdicts, [Verdict::Close, Verdict::NotCreator, Verdict::OverBudget, Verdict::Close]);
+    }
+}
@@ -0,0 +1,205 @@
+use anyhow::{Context, Result};
+use colored::*;
+use ethers::prelude::*;
+use serde_json::{json, Value};
+
+use crate::bot::{escape_html, BotCommand, ResultsView, HELP};
+use crate::error::{bail_config, CliError};
+use crate::{output, redact, PollManager};
+
+/// What room messages start with to reach the bot, e.g. `!poll results 7`
+const PREFIX: &str = "!poll";
+
+/// How long one sync waits for new messages, in milliseconds
+const SYNC_TIMEOUT_MS: u64 = 30_000;
+
+/// A Matrix account on MATRIX_HOMESERVER, acting with MATRIX_ACCESS_TOKEN
+pub struct MatrixClient {
+    homeserver: url::Url,
+    access_token: String,
+    /// MATRIX_ROOM_ID: where notifications go
+    pub room: Option<String>,
+    http: reqwest::Client,
+}
+
+impl MatrixClient {
+    pub fn from_env() -> Result<Self> {
+        let var = |name: &str| std::env::var(name).ok().filter(|value| !value.is_empty());
+        let Some(homeserver) = var("MATRIX_HOMESERVER") else {
+            bail_config!("MATRIX_HOMESERVER must be set, e.g. https://matrix.example.org");
+        };
+        let homeserver = url::Url::parse(&homeserver)
+            .map_err(|_| CliError::Config(format!("Invalid MATRIX_HOMESERVER '{}'", homeserver)))?;
+        let Some(access_token) = var("MATRIX_ACCESS_TOKEN") else {
+            bail_config!("MATRIX_ACCESS_TOKEN must be set to the bot account's access token");
+        };
+        redact::register(&access_token);
+        Ok(Self { homeserver, access_token, room: var("MATRIX_ROOM_ID"), http: reqwest::Client::new() })
+    }
+
+    /// A client-server API URL; segments are percent-encoded, so room IDs can be passed as they are
+    fn url(&self, segments: &[&str]) -> url::Url {
+        let mut url = self.homeserver.clone();
+        url.path_segments_mut()
+            .expect("http(s) URLs have a path")
+            .pop_if_empty()
+            .extend(["_matrix", "client", "v3"])
+            .extend(segments);
+        url
+    }
+
+    async fn request(&self, request: reqwest::RequestBuilder, what: &str) -> Result<Value> {
+        let response = request.bearer_auth(&self.access_token).send().await.context("Failed to reach the Matrix homeserver")?;
+        let status = response.status();
+        let body: Value = response.json().await.context("Matrix homeserver returned invalid JSON")?;
+        if !status.is_success() {
+            let error = body["error"].as_str().unwrap_or("unknown error");
+            return Err(CliError::Rpc(format!("Matrix {} failed ({}): {}", what, status, error)).into());
+        }
+        Ok(body)
+    }
+
+    /// Post a notice to `room`; `html` is the formatted version of `text`. Returns the event ID.
+    pub async fn send(&self, room: &str, text: &str, html: Option<&str>) -> Result<String> {
+        let mut content = json!({ "msgtype": "m.notice", "body": text });
+        if let Some(html) = html {
+            content["format"] = json!("org.matrix.custom.html");
+            content["formatted_body"] = json!(html);
+        }
+        let transaction = format!("polling-cli-{}", chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default());
+        let url = self.url(&["rooms", room, "send", "m.room.message", &transaction]);
+        let response = self.request(self.http.put(url).json(&content), "send").await?;
+        Ok(response["event_id"].as_str().unwrap_or_default().to_string())
+    }
+
+    /// Post to MATRIX_ROOM_ID
+    pub async fn notify(&self, text: &str) -> Result<String> {
+        let Some(room) = &self.room else {
+            bail_config!("MATRIX_ROOM_ID must be set to the room notifications go to");
+        };
+        self.send(room, text, None).await
+    }
+
+    async fn whoami(&self) -> Result<String> {
+        let response = self.request(self.http.get(self.url(&["account", "whoami"])), "whoami").await?;
+        Ok(response["user_id"].as_str().unwrap_or_default().to_string())
+    }
+
+    async fn sync(&self, since: Option<&str>, timeout_ms: u64) -> Result<Value> {
+        let mut url = self.url(&["sync"]);
+        url.query_pairs_mut().append_pair("timeout", &timeout_ms.to_string());
+        if let Some(since) = since {
+            url.query_pairs_mut().append_pair("since", since);
+        }
+        self.request(self.http.get(url), "sync").await
+    }
+}
+
+/// A command in a room message, e.g. `!poll results 7`; `None` for messages not meant for the bot
+fn parse_message(body: &str) -> Option<Option<BotCommand>> {
+    let rest = body.trim().strip_prefix(PREFIX)?;
+    if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+    Some(BotCommand::parse(&rest.split_whitespace().collect::<Vec<_>>()))
+}
+
+/// A results message in Matrix's HTML subset
+fn render_html(view: &ResultsView) -> String {
+    format!("<b>{}</b><pre><code>{}</code></pre><p>{}<br><i>{}</i></p>",
+        escape_html(&view.title), escape_html(&view.chart.join("\n")), escape_html(&view.summary), escape_html(&view.updated))
+}
+
+impl<M: Middleware + 'static> PollManager<M> {
+    async fn matrix_command(&self, client: &MatrixClient, room: &str, command: Option<BotCommand>) -> Result<()> {
+        match command {
+            Some(BotCommand::Polls) => {
+                let polls = self.open_polls().await?;
+                let text = if polls.is_empty() {
+                    "No polls are open right now.".to_string()
+                } else {
+                    polls.iter().map(|(poll_id, question)| format!("#{} {}", poll_id, question)).collect::<Vec<_>>().join("\n")
+                };
+                client.send(room, &text, None).await?;
+            }
+            Some(BotCommand::Results(poll_id)) => {
+                let view = self.results_view(poll_id).await?;
+                client.send(room, &view.plain(), Some(&render_html(&view))).await?;
+            }
+            Some(BotCommand::Help) | None => {
+                let help = HELP.lines().map(|line| format!("{} {}", PREFIX, line)).collect::<Vec<_>>().join("\n");
+                client.send(room, &help, None).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// `bot matrix`: answer `!poll` commands in the rooms the account has joined, until Ctrl-C
+    pub async fn run_matrix_bot(&self) -> Result<()> {
+        let client = MatrixClient::from_env()?;
+        let me = client.whoami().await?;
+        // Start from now; commands sent while the bot was down are not replayed
+        let mut since = client.sync(None, 0).await?["next_batch"].as_str().map(str::to_string);
+        say!("{} {} (Ctrl-C to stop)", "🤖 Matrix bot running as".cyan().bold(), me.yellow());
+        say!("Invite it to a room and send `{} help`.", PREFIX);
+
+        loop {
+            let response = tokio::select! {
+                response = client.sync(since.as_deref(), SYNC_TIMEOUT_MS) => response,
+                _ = tokio::signal::ctrl_c() => return Ok(()),
+            };
+            let response = match response {
+                Ok(response) => response,
+                Err(e) => {
+                    // Keep running through homeserver hiccups
+                    eprintln!("{} {:#}", output::render("⚠️  Matrix sync failed:".to_string()).yellow(), e);
+                    tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+                    continue;
+                }
+            };
+            since = response["next_batch"].as_str().map(str::to_string).or(since);
+
+            let Some(rooms) = response["rooms"]["join"].as_object() else {
+                continue;
+            };
+            for (room, joined) in rooms {
+                let events = joined["timeline"]["events"].as_array().cloned().unwrap_or_default();
+                for event in events {
+                    if event["type"] != "m.room.message" || event["sender"].as_str() == Some(me.as_str()) {
+                        continue;
+                    }
+                    let Some(command) = event["content"]["body"].as_str().and_then(parse_message) else {
+                        continue;
+                    };
+                    if let Err(e) = self.matrix_command(&client, room, command).await {
+                        eprintln!("{} {:#}", output::render("⚠️  Command failed:".to_string()).yellow(), e);
+                        let _ = client.send(room, &redact::redact(&format!("⚠️ {:#}", e)), None).await;
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_prefixed_commands_and_encodes_room_ids() {
+        assert_eq!(parse_message("!poll results 7"), Some(Some(BotCommand::Results(7))));
+        assert_eq!(parse_message("  !poll polls "), Some(Some(BotCommand::Polls)));
+        assert_eq!(parse_message("!poll"), Some(None));
+        assert_eq!(parse_message("!polling results 7"), None);
+        assert_eq!(parse_message("results 7"), None);
+
+        let client = MatrixClient {
+            homeserver: url::Url::parse("https://matrix.example.org/").unwrap(),
+            access_token: String::new(),
+            room: None,
+            http: reqwest::Client::new(),
+        };
+        assert_eq!(client.url(&["rooms", "!abc:example.org", "send"]).as_str(),
+            "https://matrix.example.org/_matrix/client/v3/rooms/!abc:example.org/send");
+    }
+}
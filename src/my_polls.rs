@@ -0,0 +1,177 @@
+use std::io::{BufRead, IsTerminal, Write};
+
+use anyhow::Result;
+use colored::*;
+use ethers::prelude::*;
+
+use crate::numbers::SaturatingU64;
+use crate::{duration, numbers, output, quorum, timezone, PollManager};
+
+/// Where a creator's poll stands, from the creator's point of view
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PollState {
+    Active,
+    /// Past its end time but still open on-chain until someone calls closePoll
+    NeedsClosing,
+    Closed,
+}
+
+impl PollState {
+    pub fn classify(status: u8, end_time: u64, now: u64) -> Self {
+        match status {
+            0 if now > end_time => PollState::NeedsClosing,
+            0 => PollState::Active,
+            _ => PollState::Closed,
+        }
+    }
+
+    fn label(self) -> ColoredString {
+        match self {
+            PollState::Active => output::status("🟢", "Active").green(),
+            PollState::NeedsClosing => output::status("🟠", "Ended, needs closing").yellow().bold(),
+            PollState::Closed => output::status("🔴", "Closed").red(),
+        }
+    }
+}
+
+pub(crate) struct CreatorPoll {
+    pub poll_id: u64,
+    pub question: String,
+    pub created_at: u64,
+    pub end_time: u64,
+    pub state: PollState,
+    pub total_votes: u64,
+    pub min_participation: u64,
+}
+
+impl CreatorPoll {
+    fn quorum_line(&self) -> ColoredString {
+        match quorum::reached(self.total_votes, self.min_participation) {
+            None => format!("{} votes (no quorum set)", numbers::format_integer(self.total_votes)).normal(),
+            Some(true) => format!("{} / {} votes, reached", numbers::format_integer(self.total_votes), numbers::format_integer(self.min_participation)).green(),
+            Some(false) => format!(
+                "{} / {} votes, {} short",
+                numbers::format_integer(self.total_votes),
+                numbers::format_integer(self.min_participation),
+                numbers::format_integer(self.min_participation - self.total_votes)
+            ).red(),
+        }
+    }
+}
+
+/// Ask a question on the terminal and return the trimmed answer (empty on EOF)
+pub(crate) fn prompt(question: &str) -> Result<String> {
+    print!("{} ", output::render(question.to_string()));
+    std::io::stdout().flush()?;
+    let mut answer = String::new();
+    std::io::stdin().lock().read_line(&mut answer)?;
+    Ok(answer.trim().to_string())
+}
+
+impl<M: Middleware + 'static> PollManager<M> {
+    /// Polls created by the wallet, classified as of `now`
+    pub(crate) async fn creator_polls(&self, now: u64) -> Result<Vec<CreatorPoll>> {
+        let created_polls = self.contract
+            .get_user_created_polls(self.sender)
+            .call()
+            .await?;
+
+        let mut polls = Vec::with_capacity(created_polls.len());
+        for poll_id in created_polls {
+            let poll_data = self.poll_data(poll_id).await?;
+            let (_, total_votes, _) = self.poll_results(poll_id).await?;
+            let end_time = poll_data.5.to_u64_saturating();
+
+            polls.push(CreatorPoll {
+                poll_id: poll_id.to_u64_saturating(),
+                question: poll_data.1.clone(),
+                created_at: poll_data.4.to_u64_saturating(),
+                end_time,
+                state: PollState::classify(poll_data.6, end_time, now),
+                total_votes: total_votes.to_u64_saturating(),
+                min_participation: poll_data.9.to_u64_saturating(),
+            });
+        }
+        Ok(polls)
+    }
+
+    /// List the caller's polls with what needs attention, offering to close
+    /// ended polls and extend open polls that are short of quorum
+    pub async fn my_polls(&self, no_prompt: bool) -> Result<()> {
+        let now = chrono::Utc::now().timestamp() as u64;
+        let polls = self.creator_polls(now).await?;
+
+        say!("\n📝 Your Created Polls:");
+        if polls.is_empty() {
+            say!("You haven't created any polls yet.");
+            return Ok(());
+        }
+
+        for poll in &polls {
+            say!("\nPoll #{}: {}", poll.poll_id, poll.question);
+            say!("  Status: {}", poll.state.label());
+            match poll.state {
+                PollState::Active => say!("  Ends: {}", timezone::relative_to(poll.end_time, now)),
+                _ => say!("  Ended: {}", timezone::relative_to(poll.end_time, now)),
+            }
+            say!("  Quorum: {}", poll.quorum_line());
+        }
+
+        let needs_closing = polls.iter().filter(|p| p.state == PollState::NeedsClosing).count();
+        let short_of_quorum = polls.iter()
+            .filter(|p| p.state == PollState::Active && quorum::reached(p.total_votes, p.min_participation) == Some(false))
+            .count();
+        say!("\n{} {} need closing, {} open short of quorum",
+            "Summary:".cyan().bold(), needs_closing, short_of_quorum);
+
+        // Prompts need someone at the keyboard; scripts get the report only
+        let interactive = std::io::stdin().is_terminal() && !output::is_plain();
+        if no_prompt || !interactive || needs_closing + short_of_quorum == 0 {
+            return Ok(());
+        }
+
+        say!();
+        for poll in &polls {
+            match poll.state {
+                PollState::NeedsClosing => {
+                    let answer = prompt(&format!("Close poll #{} \"{}\" now? [y/N]", poll.poll_id, poll.question))?;
+                    if answer.eq_ignore_ascii_case("y") || answer.eq_ignore_ascii_case("yes") {
+                        if let Err(e) = self.close_poll(poll.poll_id).await {
+                            say!("{} {:#}", "❌ Could not close poll:".red(), e);
+                        }
+                    }
+                }
+                PollState::Active if quorum::reached(poll.total_votes, poll.min_participation) == Some(false) => {
+                    let answer = prompt(&format!("Extend poll #{} by (e.g. 2d, blank to skip):", poll.poll_id))?;
+                    if answer.is_empty() {
+                        continue;
+                    }
+                    let result = match duration::parse(&answer) {
+                        Ok(seconds) => self.extend_poll(poll.poll_id, Some(seconds), None, true).await,
+                        Err(e) => Err(anyhow::anyhow!(e)),
+                    };
+                    if let Err(e) = result {
+                        say!("{} {:#}", "❌ Could not extend poll:".red(), e);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_poll_state() {
+        assert_eq!(PollState::classify(0, 200, 100), PollState::Active);
+        assert_eq!(PollState::classify(0, 200, 200), PollState::Active);
+        assert_eq!(PollState::classify(0, 200, 201), PollState::NeedsClosing);
+        assert_eq!(PollState::classify(1, 200, 100), PollState::Closed);
+        assert_eq!(PollState::classify(1, 200, 300), PollState::Closed);
+    }
+}
@@ -0,0 +1,114 @@
+use ethers::types::U256;
+
+pub use polling_analytics::numbers::Separators;
+use polling_analytics::numbers::{format_amount_digits, group_digits};
+
+/// Panic-free narrowing of chain integers, where `as_u64` would panic on overflow
+pub trait SaturatingU64 {
+    /// The value, or `u64::MAX` when it does not fit
+    fn to_u64_saturating(&self) -> u64;
+}
+
+impl SaturatingU64 for U256 {
+    fn to_u64_saturating(&self) -> u64 {
+        if *self > U256::from(u64::MAX) { u64::MAX } else { self.low_u64() }
+    }
+}
+
+/// An integer of any size with the locale's thousands separators
+pub fn format_integer(value: impl Into<U256>) -> String {
+    group_digits(&value.into().to_string(), Separators::from_env().group)
+}
+
+/// A token amount scaled by `decimals`, rounded to `precision` fractional digits
+pub fn format_amount(value: U256, decimals: u32, precision: usize) -> String {
+    format_amount_with(value, decimals, precision, Separators::from_env())
+}
+
+pub fn format_amount_with(value: U256, decimals: u32, precision: usize, separators: Separators) -> String {
+    format_amount_digits(&value.to_string(), decimals, precision, separators)
+}
+
+/// Serialize U256 values as decimal strings so JSON exports keep full precision
+pub mod decimal {
+    use ethers::types::U256;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(value: &U256, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&value.to_string())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<U256, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        U256::from_dec_str(&text).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Like `decimal`, for lists of values
+pub mod decimal_vec {
+    use ethers::types::U256;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(values: &[U256], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(values.iter().map(|v| v.to_string()))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<U256>, D::Error> {
+        Vec::<String>::deserialize(deserializer)?
+            .iter()
+            .map(|text| U256::from_dec_str(text).map_err(serde::de::Error::custom))
+            .collect()
+    }
+}
+
+/// Like `decimal`, for optional values
+pub mod decimal_option {
+    use ethers::types::U256;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(value: &Option<U256>, serializer: S) -> Result<S::Ok, S::Error> {
+        match value {
+            Some(value) => serializer.serialize_some(&value.to_string()),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<U256>, D::Error> {
+        Option::<String>::deserialize(deserializer)?
+            .map(|text| U256::from_dec_str(&text).map_err(serde::de::Error::custom))
+            .transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn groups_full_u256_range() {
+        assert_eq!(group_digits("0", ','), "0");
+        assert_eq!(group_digits("999", ','), "999");
+        assert_eq!(group_digits("1000", ','), "1,000");
+        assert_eq!(group_digits(&U256::MAX.to_string(), ',').len(), 78 + 25);
+    }
+
+    #[test]
+    fn scales_and_rounds_token_amounts() {
+        let one_and_half = U256::from(1_500_000_000_000_000_000u64);
+        assert_eq!(format_amount_with(one_and_half, 18, 2, Separators::EN), "1.50");
+        assert_eq!(format_amount_with(U256::from(999_995u64), 6, 2, Separators::EN), "1.00");
+        assert_eq!(format_amount_with(U256::from(5u64), 18, 4, Separators::EN), "0.0000");
+        assert_eq!(format_amount_with(U256::from(1_234_567u64), 0, 0, Separators::EN), "1,234,567");
+        assert_eq!(format_amount_with(U256::from(12u64), 1, 3, Separators::EN), "1.200");
+
+        let german = Separators::for_locale("de_DE.UTF-8");
+        let large = U256::exp10(24) + U256::exp10(17) * 5;
+        assert_eq!(format_amount_with(large, 18, 1, german), "1.000.000,5");
+    }
+
+    #[test]
+    fn saturates_instead_of_panicking() {
+        assert_eq!(U256::from(42).to_u64_saturating(), 42);
+        assert_eq!(U256::MAX.to_u64_saturating(), u64::MAX);
+    }
+}
@@ -0,0 +1,192 @@
+use std::io::{IsTerminal, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use indicatif::{ProgressBar, ProgressStyle};
+use tabled::{Table, Tabled};
+
+static PLAIN: AtomicBool = AtomicBool::new(false);
+static ACCESSIBLE: AtomicBool = AtomicBool::new(false);
+
+/// `println!` for human-readable lines: renders through `output::render`, so plain
+/// mode strips emoji and box drawing. Machine-readable output (JSON, CSV, Markdown,
+/// feeds) is written with plain `println!` instead.
+macro_rules! say {
+    () => {
+        ::std::println!()
+    };
+    ($($arg:tt)*) => {
+        ::std::println!("{}", $crate::output::render(format!($($arg)*)))
+    };
+}
+
+/// Decide once at startup how output is rendered.
+///
+/// Colors are disabled by `--no-color`, `--plain`, a non-empty `NO_COLOR` or a
+/// non-terminal stdout; spinners and emoji are disabled by `--plain` or a
+/// non-terminal stdout (cron jobs, CI logs, pipes). `--accessible` (or a
+/// non-empty `POLL_ACCESSIBLE`) implies plain output and also spells out
+/// statuses and linearizes tables for screen readers.
+pub fn init(no_color: bool, plain: bool, accessible: bool) {
+    let interactive = std::io::stdout().is_terminal();
+    let no_color_env = std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty());
+    let accessible = accessible || std::env::var_os("POLL_ACCESSIBLE").is_some_and(|v| !v.is_empty());
+    let plain = plain || accessible;
+
+    if no_color || plain || no_color_env || !interactive {
+        colored::control::set_override(false);
+    }
+    PLAIN.store(plain || !interactive, Ordering::Relaxed);
+    ACCESSIBLE.store(accessible, Ordering::Relaxed);
+}
+
+pub fn is_plain() -> bool {
+    PLAIN.load(Ordering::Relaxed)
+}
+
+pub fn is_accessible() -> bool {
+    ACCESSIBLE.load(Ordering::Relaxed)
+}
+
+/// A poll status such as `🟢 Active`, or `[ACTIVE]` in accessible mode
+pub fn status(emoji: &str, text: &str) -> String {
+    if is_accessible() {
+        format!("[{}]", text.to_uppercase())
+    } else {
+        format!("{} {}", emoji, text)
+    }
+}
+
+/// Rows as `Header: value` lines, one blank-line-separated block per row, for screen readers
+pub fn linear<H: AsRef<str>>(headers: &[H], rows: &[Vec<String>]) -> String {
+    rows.iter()
+        .map(|row| {
+            headers.iter().zip(row)
+                .map(|(header, value)| format!("{}: {}", header.as_ref(), value.trim()))
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// A table of `rows`, linearized in accessible mode
+pub fn table<T: Tabled>(rows: impl IntoIterator<Item = T>) -> String {
+    if !is_accessible() {
+        return Table::new(rows).to_string();
+    }
+    let rows: Vec<Vec<String>> = rows.into_iter()
+        .map(|row| row.fields().into_iter().map(|field| field.into_owned()).collect())
+        .collect();
+    linear(&T::headers(), &rows)
+}
+
+/// Write one NDJSON record and flush it, so tools reading the stream get each record as soon as it
+/// is ready.
+pub fn ndjson<T: serde::Serialize>(record: &T) -> anyhow::Result<()> {
+    line(&serde_json::to_string(record)?)
+}
+
+/// Write one machine-readable line and flush it. A reader that stops early (e.g. `| head`) ends
+/// the run quietly.
+pub fn line(text: &str) -> anyhow::Result<()> {
+    let mut stdout = std::io::stdout().lock();
+    match writeln!(stdout, "{}", text).and_then(|_| stdout.flush()) {
+        Err(e) if e.kind() == std::io::ErrorKind::BrokenPipe => std::process::exit(0),
+        result => Ok(result?),
+    }
+}
+
+/// Progress spinner, or a single log line when running non-interactively
+pub fn spinner(message: &str) -> ProgressBar {
+    if is_plain() {
+        say!("{}", message);
+        return ProgressBar::hidden();
+    }
+
+    let pb = ProgressBar::new_spinner();
+    pb.set_style(ProgressStyle::default_spinner().template("{spinner:.green} {msg}").unwrap());
+    pb.set_message(message.to_string());
+    pb.enable_steady_tick(std::time::Duration::from_millis(100));
+    pb
+}
+
+fn is_emoji(c: char) -> bool {
+    matches!(c as u32,
+        0x1F000..=0x1FAFF
+        | 0x2300..=0x23FF
+        | 0x2600..=0x27BF
+        | 0x2B00..=0x2BFF
+        | 0x2139
+        | 0x203C
+        | 0x2049
+        | 0x200D
+        | 0xFE0F)
+}
+
+/// Words read out in place of emoji that carry meaning, in accessible mode
+fn spoken(c: char) -> Option<&'static str> {
+    match c {
+        '✅' => Some("[OK]"),
+        '❌' => Some("[ERROR]"),
+        '⚠' => Some("[WARNING]"),
+        _ => None,
+    }
+}
+
+/// Strip emoji (and the spacing after them) and turn box drawing into ASCII in plain mode.
+/// Accessible mode also names warning and success emoji, drops bar-chart blocks and
+/// blanks out lines that are only rules.
+pub fn render(line: String) -> String {
+    if !is_plain() {
+        return line;
+    }
+    let accessible = is_accessible();
+
+    let mut rendered = String::with_capacity(line.len());
+    let mut after_emoji = false;
+    for c in line.chars() {
+        if accessible {
+            if let Some(word) = spoken(c) {
+                rendered.push_str(word);
+                rendered.push(' ');
+                after_emoji = true;
+                continue;
+            }
+            if matches!(c, '\u{2580}'..='\u{259F}') {
+                continue;
+            }
+        }
+        if is_emoji(c) {
+            after_emoji = true;
+            continue;
+        }
+        if after_emoji && c == ' ' {
+            continue;
+        }
+        after_emoji = false;
+        rendered.push(match c {
+            '═' => '=',
+            '\u{2500}'..='\u{257F}' => '-',
+            '→' => '>',
+            other => other,
+        });
+    }
+    if accessible && !rendered.is_empty() && rendered.chars().all(|c| matches!(c, '=' | '-')) {
+        rendered.clear();
+    }
+    rendered
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn linear_tables_read_as_one_record_per_row() {
+        let rows = vec![
+            vec!["general".to_string(), "  3".to_string()],
+            vec!["finance".to_string(), "1".to_string()],
+        ];
+        assert_eq!(linear(&["Category", "Active"], &rows), "Category: general\nActive: 3\n\nCategory: finance\nActive: 1");
+    }
+}
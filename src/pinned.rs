@@ -0,0 +1,161 @@
+use std::fmt;
+
+use anyhow::Result;
+use colored::*;
+use ethers::prelude::*;
+
+use crate::error::{bail_config, bail_user};
+use crate::numbers::SaturatingU64;
+use crate::{timezone, PollManager};
+
+/// How settled a block is, from the node's `safe` and `finalized` tags
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Finality {
+    Finalized,
+    /// Unlikely to be reorganised, but not yet finalized
+    Safe,
+    /// Newer than the safe block; a reorg could still change what was read
+    Unfinalized,
+    /// The node does not report `safe`/`finalized` (e.g. pre-merge or some L2s)
+    Unknown,
+}
+
+impl fmt::Display for Finality {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Finality::Finalized => "finalized",
+            Finality::Safe => "safe, not yet finalized",
+            Finality::Unfinalized => "not final, may still be reorganised",
+            Finality::Unknown => "finality unknown",
+        })
+    }
+}
+
+impl Finality {
+    /// Finality of block `number` given the node's finalized and safe heads
+    pub fn of(number: u64, finalized: Option<u64>, safe: Option<u64>) -> Self {
+        match (finalized, safe) {
+            (Some(finalized), _) if number <= finalized => Finality::Finalized,
+            (_, Some(safe)) if number <= safe => Finality::Safe,
+            (None, None) => Finality::Unknown,
+            _ => Finality::Unfinalized,
+        }
+    }
+
+    fn colored(&self) -> ColoredString {
+        match self {
+            Finality::Finalized => self.to_string().green(),
+            Finality::Safe => self.to_string().yellow(),
+            Finality::Unfinalized => self.to_string().red(),
+            Finality::Unknown => self.to_string().dimmed(),
+        }
+    }
+}
+
+/// The block that reads are evaluated at with `--block` or `--finalized`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PinnedBlock {
+    pub number: u64,
+    pub timestamp: u64,
+    /// What was asked for: a number or a tag such as `finalized`
+    pub requested: BlockNumber,
+    pub finality: Finality,
+}
+
+impl PinnedBlock {
+    pub fn id(&self) -> BlockId {
+        BlockId::Number(self.number.into())
+    }
+
+    /// `block 123 (2026-01-01 12:00 UTC, 3 hours ago)`, naming the tag when one was used
+    pub fn describe(&self) -> String {
+        let tag = match self.requested {
+            BlockNumber::Number(_) => String::new(),
+            tag => format!(" [{}]", tag),
+        };
+        format!("block {}{} ({})", self.number, tag, timezone::format_with_relative(self.timestamp))
+    }
+
+    /// Context for a failed read at this block: most nodes prune old state
+    pub fn archive_hint(&self) -> String {
+        format!("Could not read state at block {}; historical state needs an archive node", self.number)
+    }
+}
+
+/// Commands that only read state, and so can be evaluated at a past or finalized block
+pub const PINNABLE_COMMANDS: &str = "view, results, chart, token-balance, analytics and snapshot";
+
+impl<M: Middleware + 'static> PollManager<M> {
+    /// Number of the block a tag points at, or `None` when the node does not support the tag
+    async fn tagged_block(&self, tag: BlockNumber) -> Option<u64> {
+        self.client.get_block(tag).await.ok().flatten().and_then(|block| block.number).map(|n| n.as_u64())
+    }
+
+    /// Evaluate every read at `block` instead of the head of the chain
+    pub async fn pin_block(&mut self, block: BlockNumber) -> Result<()> {
+        let found = match self.client.get_block(block).await {
+            Ok(Some(found)) => found,
+            Ok(None) => bail_user!("Block {} does not exist on this chain yet", block),
+            Err(e) if matches!(block, BlockNumber::Finalized | BlockNumber::Safe) => {
+                bail_config!("This node does not report a {} block ({}); try --block with a number instead", block, e)
+            }
+            Err(e) => return Err(e.into()),
+        };
+        let Some(number) = found.number.map(|n| n.as_u64()) else {
+            bail_user!("Block {} is not mined yet; pin reads to a mined block", block);
+        };
+        let finality = match block {
+            BlockNumber::Finalized => Finality::Finalized,
+            _ => Finality::of(number, self.tagged_block(BlockNumber::Finalized).await, self.tagged_block(BlockNumber::Safe).await),
+        };
+        self.pinned = Some(PinnedBlock { number, timestamp: found.timestamp.to_u64_saturating(), requested: block, finality });
+        Ok(())
+    }
+
+    /// Where the data in an output was read: the pinned block and its finality, or the moving head
+    pub fn read_at(&self) -> String {
+        match self.pinned {
+            Some(pinned) => format!("{}, {}", pinned.describe(), pinned.finality.colored()),
+            None => format!("latest block, {}", "not final; pass --finalized for settled results".yellow()),
+        }
+    }
+
+    /// Evaluate `call` at the pinned block, if any
+    pub(crate) fn at_block<D: ethers::abi::Detokenize>(&self, call: ContractCall<M, D>) -> ContractCall<M, D> {
+        match self.pinned {
+            Some(pinned) => call.block(pinned.id()),
+            None => call,
+        }
+    }
+
+    /// The pinned block's timestamp, otherwise the wall clock
+    pub(crate) fn now(&self) -> u64 {
+        match self.pinned {
+            Some(pinned) => pinned.timestamp,
+            None => chrono::Utc::now().timestamp() as u64,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn describes_tags_but_not_plain_numbers() {
+        let pinned = PinnedBlock { number: 42, timestamp: 0, requested: BlockNumber::Finalized, finality: Finality::Finalized };
+        assert!(pinned.describe().starts_with("block 42 [finalized] ("));
+        let pinned = PinnedBlock { requested: BlockNumber::Number(42.into()), ..pinned };
+        assert!(pinned.describe().starts_with("block 42 ("));
+        assert_eq!(pinned.id(), BlockId::Number(42.into()));
+    }
+
+    #[test]
+    fn finality_from_safe_and_finalized_heads() {
+        assert_eq!(Finality::of(90, Some(100), Some(110)), Finality::Finalized);
+        assert_eq!(Finality::of(105, Some(100), Some(110)), Finality::Safe);
+        assert_eq!(Finality::of(120, Some(100), Some(110)), Finality::Unfinalized);
+        assert_eq!(Finality::of(120, Some(100), None), Finality::Unfinalized);
+        assert_eq!(Finality::of(120, None, None), Finality::Unknown);
+    }
+}
@@ -0,0 +1,158 @@
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use anyhow::{Context, Result};
+use colored::*;
+
+use crate::connection::ConnectionArgs;
+use crate::error::bail_user;
+use crate::store;
+
+/// Executables named `polling-cli-<name>` become the `<name>` command
+pub const PREFIX: &str = "polling-cli-";
+
+/// Plugins named `polling-cli-export-<format>` add `export -f <format>`
+const EXPORTER: &str = "export-";
+
+/// `POLL_CLI_HOME/plugins`, then every PATH entry; the first plugin found under a name wins
+fn search_dirs() -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    if let Ok(home) = store::data_dir() {
+        dirs.push(home.join("plugins"));
+    }
+    if let Some(path) = std::env::var_os("PATH") {
+        dirs.extend(std::env::split_paths(&path));
+    }
+    dirs
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    path.metadata().map(|meta| meta.is_file() && meta.permissions().mode() & 0o111 != 0).unwrap_or(false)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
+/// The plugin name in an executable's file name, e.g. `turnout` for `polling-cli-turnout`
+fn plugin_name(file_name: &str) -> Option<&str> {
+    let name = file_name.strip_prefix(PREFIX)?;
+    let name = name.strip_suffix(std::env::consts::EXE_SUFFIX).unwrap_or(name);
+    (!name.is_empty() && !name.contains('.')).then_some(name)
+}
+
+/// Every plugin that can be run, by name
+pub fn discover() -> BTreeMap<String, PathBuf> {
+    let mut plugins = BTreeMap::new();
+    for dir in search_dirs() {
+        let Ok(entries) = std::fs::read_dir(&dir) else { continue };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Some(name) = entry.file_name().to_str().and_then(plugin_name).map(str::to_string) else { continue };
+            if is_executable(&path) {
+                plugins.entry(name).or_insert(path);
+            }
+        }
+    }
+    plugins
+}
+
+fn find(name: &str) -> Option<PathBuf> {
+    search_dirs().into_iter()
+        .map(|dir| dir.join(format!("{}{}{}", PREFIX, name, std::env::consts::EXE_SUFFIX)))
+        .find(|path| is_executable(path))
+}
+
+/// What a plugin is told about the host. POLL_CLI_BIN lets it run this CLI for contract reads
+/// and rendering (e.g. `$POLL_CLI_BIN export -p 3 -f json`) against the same RPC endpoint and
+/// contract, with the connection flags given before the plugin's name applied.
+fn host_env(connection: &ConnectionArgs) -> Result<Vec<(&'static str, String)>> {
+    let mut env = vec![("POLL_CLI_HOME", store::data_dir()?.display().to_string())];
+    if let Ok(bin) = std::env::current_exe() {
+        env.push(("POLL_CLI_BIN", bin.display().to_string()));
+    }
+    if let Some(rpc_url) = &connection.rpc_url {
+        env.push(("RPC_URL", rpc_url.clone()));
+    }
+    if connection.contract_address.is_some() || connection.contract.is_some() {
+        env.push(("CONTRACT_ADDRESS", connection.contract_address()?));
+    }
+    Ok(env)
+}
+
+/// Run the plugin for a command the CLI does not know, passing it the remaining arguments.
+/// A failing plugin has already reported why, so its exit code is passed on as is.
+pub fn run_plugin(args: &[String], connection: &ConnectionArgs) -> Result<()> {
+    let Some((name, rest)) = args.split_first() else {
+        bail_user!("No command given; see --help");
+    };
+    let Some(path) = find(name) else {
+        bail_user!("Unknown command '{}': no {}{} plugin on PATH or in POLL_CLI_HOME/plugins (see `plugins`)", name, PREFIX, name);
+    };
+    let status = Command::new(&path)
+        .args(rest)
+        .envs(host_env(connection)?)
+        .status()
+        .with_context(|| format!("Failed to run plugin {}", path.display()))?;
+    if !status.success() {
+        std::process::exit(status.code().unwrap_or(1));
+    }
+    Ok(())
+}
+
+/// Render a poll export with the `polling-cli-export-<format>` plugin: the export's JSON goes to
+/// its stdin and its stdout is the result. `None` when no such plugin is installed.
+pub fn run_exporter(format: &str, export_json: &str) -> Result<Option<Vec<u8>>> {
+    let Some(path) = find(&format!("{}{}", EXPORTER, format)) else {
+        return Ok(None);
+    };
+    let mut child = Command::new(&path)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("Failed to run export plugin {}", path.display()))?;
+    child.stdin.take().expect("stdin is piped").write_all(export_json.as_bytes())?;
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        anyhow::bail!("Export plugin {} failed with {}", path.display(), output.status);
+    }
+    Ok(Some(output.stdout))
+}
+
+/// `plugins`: list installed plugins and where they were found
+pub fn list_plugins() -> Result<()> {
+    let plugins = discover();
+    say!("\n{}", "🧩 Plugins:".cyan().bold());
+    if plugins.is_empty() {
+        say!("None installed. Put an executable named {}<name> on PATH or in {}.", PREFIX, store::data_dir()?.join("plugins").display());
+        return Ok(());
+    }
+    for (name, path) in &plugins {
+        let usage = match name.strip_prefix(EXPORTER) {
+            Some(format) => format!("export -f {}", format),
+            None => name.clone(),
+        };
+        say!("  {} {}", usage.yellow(), path.display().to_string().dimmed());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_plugins_after_the_prefix() {
+        let exe = std::env::consts::EXE_SUFFIX;
+        assert_eq!(plugin_name(&format!("polling-cli-turnout{}", exe)), Some("turnout"));
+        assert_eq!(plugin_name(&format!("polling-cli-export-xlsx{}", exe)), Some("export-xlsx"));
+        assert_eq!(plugin_name("polling-cli-"), None);
+        assert_eq!(plugin_name("polling-cli-notes.txt"), None);
+        assert_eq!(plugin_name("cargo-polling"), None);
+    }
+}
@@ -0,0 +1,285 @@
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+
+use anyhow::Result;
+use colored::*;
+use ethers::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{bail_config, bail_user};
+use crate::{contracts, duration, store, PollManager, CATEGORIES};
+
+const POLICY_FILE: &str = "policy.json";
+
+/// Chain id on which `block_mainnet_writes` applies
+const MAINNET: u64 = 1;
+
+/// Settings that can be set with `policy set`
+pub const KEYS: &str = "max-gas-price, block-mainnet-writes, token-gated-categories, min-poll-duration, max-poll-duration";
+
+/// Client-side limits on what this machine may send. Unset fields impose nothing.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Policy {
+    /// Refuse to send while the network gas price is above this, in gwei
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_gas_price_gwei: Option<f64>,
+    /// Refuse transactions on Ethereum mainnet unless `--allow-mainnet` is passed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_mainnet_writes: Option<bool>,
+    /// Categories whose polls must be token gated
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token_gated_categories: Option<Vec<String>>,
+    /// Shortest poll that may be created, e.g. `2d`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_poll_duration: Option<String>,
+    /// Longest a poll may run from creation to its end, extensions included, e.g. `30d`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_poll_duration: Option<String>,
+}
+
+/// `policy.json`: defaults, and overrides per registered contract name
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PolicyFile {
+    pub default: Policy,
+    pub contracts: BTreeMap<String, Policy>,
+}
+
+impl Policy {
+    /// This policy with any field it leaves unset taken from `fallback`
+    fn or(self, fallback: &Policy) -> Policy {
+        Policy {
+            max_gas_price_gwei: self.max_gas_price_gwei.or(fallback.max_gas_price_gwei),
+            block_mainnet_writes: self.block_mainnet_writes.or(fallback.block_mainnet_writes),
+            token_gated_categories: self.token_gated_categories.or_else(|| fallback.token_gated_categories.clone()),
+            min_poll_duration: self.min_poll_duration.or_else(|| fallback.min_poll_duration.clone()),
+            max_poll_duration: self.max_poll_duration.or_else(|| fallback.max_poll_duration.clone()),
+        }
+    }
+
+    fn set(&mut self, key: &str, value: Option<&str>) -> Result<()> {
+        match key {
+            "max-gas-price" => self.max_gas_price_gwei = match value.map(str::parse::<f64>) {
+                None => None,
+                Some(Ok(gwei)) if gwei > 0.0 => Some(gwei),
+                Some(_) => bail_user!("max-gas-price is a positive number of gwei"),
+            },
+            "block-mainnet-writes" => self.block_mainnet_writes = match value.map(str::parse::<bool>) {
+                None => None,
+                Some(Ok(block)) => Some(block),
+                Some(Err(_)) => bail_user!("block-mainnet-writes is true or false"),
+            },
+            "token-gated-categories" => self.token_gated_categories = match value {
+                None => None,
+                Some(list) => {
+                    let categories = crate::spec::split_list(&list.to_lowercase());
+                    if let Some(unknown) = categories.iter().find(|c| !CATEGORIES.contains(&c.as_str())) {
+                        bail_user!("Unknown category '{}'; use {}", unknown, CATEGORIES.join(", "));
+                    }
+                    Some(categories)
+                }
+            },
+            "min-poll-duration" => self.min_poll_duration = match value {
+                None => None,
+                Some(text) => {
+                    duration::parse(text).map_err(|e| crate::error::CliError::User(format!("min-poll-duration: {}", e)))?;
+                    Some(text.to_string())
+                }
+            },
+            "max-poll-duration" => self.max_poll_duration = match value {
+                None => None,
+                Some(text) => {
+                    duration::parse(text).map_err(|e| crate::error::CliError::User(format!("max-poll-duration: {}", e)))?;
+                    Some(text.to_string())
+                }
+            },
+            _ => bail_user!("Unknown policy '{}'; use {}", key, KEYS),
+        }
+        Ok(())
+    }
+
+    /// Check a poll about to be created
+    pub fn check_create(&self, category: &str, token_address: Address, duration_seconds: u64) -> Result<()> {
+        if let Some(categories) = &self.token_gated_categories {
+            if token_address.is_zero() && categories.iter().any(|c| c.eq_ignore_ascii_case(category)) {
+                bail_user!("Policy: {} polls must be token gated; pass --token-address and --min-token-balance", category.to_lowercase());
+            }
+        }
+        if let Some(minimum) = &self.min_poll_duration {
+            let minimum = match duration::parse(minimum) {
+                Ok(seconds) => seconds,
+                Err(e) => bail_config!("{}: min_poll_duration: {}", POLICY_FILE, e),
+            };
+            if duration_seconds < minimum {
+                bail_user!("Policy: polls must run at least {}; this one runs {}", duration::format(minimum), duration::format(duration_seconds));
+            }
+        }
+        self.check_total_duration(duration_seconds)
+    }
+
+    /// The longest total duration allowed, in seconds; `None` when unset
+    pub fn max_total_duration(&self) -> Result<Option<u64>> {
+        match &self.max_poll_duration {
+            Some(maximum) => match duration::parse(maximum) {
+                Ok(seconds) => Ok(Some(seconds)),
+                Err(e) => bail_config!("{}: max_poll_duration: {}", POLICY_FILE, e),
+            },
+            None => Ok(None),
+        }
+    }
+
+    /// Check a poll's total duration, from creation to its (new) end
+    pub fn check_total_duration(&self, duration_seconds: u64) -> Result<()> {
+        if let Some(maximum) = self.max_total_duration()? {
+            if duration_seconds > maximum {
+                bail_user!("Policy: polls may run at most {} in total; this one would run {}", duration::format(maximum), duration::format(duration_seconds));
+            }
+        }
+        Ok(())
+    }
+
+    /// Check a transaction about to be sent on `chain_id` at `gas_price_gwei`
+    pub fn check_send(&self, chain_id: u64, gas_price_gwei: Option<f64>, allow_mainnet: bool) -> Result<()> {
+        if chain_id == MAINNET && self.block_mainnet_writes == Some(true) && !allow_mainnet {
+            bail_user!("Policy: transactions on Ethereum mainnet are blocked; pass --allow-mainnet to send this one");
+        }
+        if let (Some(max), Some(gas_price)) = (self.max_gas_price_gwei, gas_price_gwei) {
+            if gas_price > max {
+                bail_user!("Policy: gas price is {:.2} gwei, above the {} gwei limit; wait for cheaper gas or raise max-gas-price", gas_price, max);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The policy in force for this run, and whether `--allow-mainnet` was given
+static ACTIVE: OnceLock<(Policy, bool)> = OnceLock::new();
+
+pub fn load() -> Result<PolicyFile> {
+    store::load(POLICY_FILE)
+}
+
+/// Use the policy for `contract_address`: its registered name's overrides on top of the defaults
+pub fn init(contract_address: &str, allow_mainnet: bool) -> Result<()> {
+    let file = load()?;
+    let name = contract_address.parse::<Address>().ok()
+        .and_then(|address| contracts::all().ok()?.into_iter().find(|(_, a)| *a == address).map(|(name, _)| name));
+    let policy = match name.and_then(|name| file.contracts.get(&name).cloned()) {
+        Some(overrides) => overrides.or(&file.default),
+        None => file.default,
+    };
+    let _ = ACTIVE.set((policy, allow_mainnet));
+    Ok(())
+}
+
+pub fn active() -> Policy {
+    ACTIVE.get().map(|(policy, _)| policy.clone()).unwrap_or_default()
+}
+
+/// `policy set` / `policy unset`: change a default, or an override for one registered contract
+pub fn set_policy(key: &str, value: Option<&str>, contract: Option<&str>) -> Result<()> {
+    let mut file = load()?;
+    let policy = match contract {
+        Some(name) => {
+            if !contracts::all()?.contains_key(name) {
+                bail_user!("No contract named '{}'; policies apply to registered contracts (see `contracts add`)", name);
+            }
+            file.contracts.entry(name.to_string()).or_default()
+        }
+        None => &mut file.default,
+    };
+    policy.set(key, value)?;
+    file.contracts.retain(|_, policy| *policy != Policy::default());
+    store::save(POLICY_FILE, &file)?;
+
+    let scope = contract.map(|name| format!(" for {}", name)).unwrap_or_default();
+    match value {
+        Some(value) => say!("{} {} = {}{}", "✅ Policy".green().bold(), key.yellow(), value, scope),
+        None => say!("{} {}{}", "🗑️  Removed policy".green().bold(), key.yellow(), scope),
+    }
+    Ok(())
+}
+
+fn print_policy(policy: &Policy) {
+    if *policy == Policy::default() {
+        say!("  (none)");
+        return;
+    }
+    if let Some(gwei) = policy.max_gas_price_gwei {
+        say!("  max-gas-price: {} gwei", gwei);
+    }
+    if let Some(block) = policy.block_mainnet_writes {
+        say!("  block-mainnet-writes: {}", block);
+    }
+    if let Some(categories) = &policy.token_gated_categories {
+        say!("  token-gated-categories: {}", categories.join(", "));
+    }
+    if let Some(minimum) = &policy.min_poll_duration {
+        say!("  min-poll-duration: {}", minimum);
+    }
+    if let Some(maximum) = &policy.max_poll_duration {
+        say!("  max-poll-duration: {}", maximum);
+    }
+}
+
+/// `policy show`: the defaults and every per-contract override
+pub fn show_policy() -> Result<()> {
+    let file = load()?;
+    say!("\n{}", "🛡️  Policy".cyan().bold());
+    say!("{}", "Defaults:".yellow().bold());
+    print_policy(&file.default);
+    for (name, overrides) in &file.contracts {
+        say!("{} {}", "Contract".yellow().bold(), name.yellow().bold());
+        print_policy(overrides);
+    }
+    Ok(())
+}
+
+impl<M: Middleware + 'static> PollManager<M> {
+    /// Refuse to send a transaction the active policy forbids
+    pub(crate) async fn check_send_policy(&self) -> Result<()> {
+        let Some((policy, allow_mainnet)) = ACTIVE.get() else { return Ok(()) };
+        let gas_price_gwei = match policy.max_gas_price_gwei {
+            Some(_) => Some(crate::u256_to_f64(self.client.get_gas_price().await?) / 1e9),
+            None => None,
+        };
+        policy.check_send(self.wallet.chain_id(), gas_price_gwei, *allow_mainnet)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overrides_fall_back_to_defaults() {
+        let defaults = Policy { max_gas_price_gwei: Some(50.0), min_poll_duration: Some("1d".to_string()), ..Default::default() };
+        let grants = Policy { max_gas_price_gwei: Some(20.0), ..Default::default() }.or(&defaults);
+        assert_eq!(grants.max_gas_price_gwei, Some(20.0));
+        assert_eq!(grants.min_poll_duration.as_deref(), Some("1d"));
+    }
+
+    #[test]
+    fn violations_are_refused() {
+        let mut policy = Policy::default();
+        policy.set("token-gated-categories", Some("Governance")).unwrap();
+        policy.set("min-poll-duration", Some("2d")).unwrap();
+        policy.set("block-mainnet-writes", Some("true")).unwrap();
+        policy.set("max-gas-price", Some("30")).unwrap();
+        policy.set("max-poll-duration", Some("30d")).unwrap();
+        assert!(policy.set("max-gas", Some("1")).is_err());
+        assert!(policy.set("max-poll-duration", Some("soon")).is_err());
+
+        assert!(policy.check_create("governance", Address::zero(), 3 * 86_400).is_err());
+        assert!(policy.check_create("governance", Address::repeat_byte(1), 3 * 86_400).is_ok());
+        assert!(policy.check_create("general", Address::zero(), 86_400).is_err());
+        assert!(policy.check_create("general", Address::zero(), 31 * 86_400).is_err());
+        assert!(policy.check_total_duration(30 * 86_400).is_ok());
+
+        assert!(policy.check_send(1, Some(10.0), false).is_err());
+        assert!(policy.check_send(1, Some(10.0), true).is_ok());
+        assert!(policy.check_send(11155111, Some(45.0), false).is_err());
+        assert!(Policy::default().check_send(1, Some(500.0), false).is_ok());
+    }
+}
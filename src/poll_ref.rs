@@ -0,0 +1,173 @@
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::Result;
+use ethers::prelude::*;
+
+use crate::connection::ConnectionArgs;
+use crate::contracts;
+use crate::error::bail_user;
+
+const SCHEME: &str = "polling";
+
+/// Block explorers whose contract pages can be pasted as poll references, by host
+const EXPLORERS: [(&str, u64); 9] = [
+    ("etherscan.io", 1),
+    ("sepolia.etherscan.io", 11155111),
+    ("holesky.etherscan.io", 17000),
+    ("optimistic.etherscan.io", 10),
+    ("arbiscan.io", 42161),
+    ("basescan.org", 8453),
+    ("sepolia.basescan.org", 84532),
+    ("polygonscan.com", 137),
+    ("gnosisscan.io", 100),
+];
+
+/// A poll given on the command line: a bare id, a `polling://<chain>/<contract>/<id>` link,
+/// or an explorer URL for the contract with the poll in `?poll=<id>` or `#<id>`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PollRef {
+    pub id: u64,
+    pub chain_id: Option<u64>,
+    pub contract: Option<Address>,
+}
+
+/// The shareable link for a poll
+pub fn link(chain_id: u64, contract: Address, poll_id: u64) -> String {
+    format!("{}://{}/{:?}/{}", SCHEME, chain_id, contract, poll_id)
+}
+
+fn parse_id(text: &str) -> Result<u64, String> {
+    text.parse().map_err(|_| format!("'{}' is not a poll id", text))
+}
+
+fn parse_contract(text: &str) -> Result<Address, String> {
+    text.parse().map_err(|_| format!("'{}' is not a contract address", text))
+}
+
+impl FromStr for PollRef {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let text = text.trim();
+        if let Ok(id) = text.parse() {
+            return Ok(Self { id, chain_id: None, contract: None });
+        }
+        let url = url::Url::parse(text).map_err(|_| format!("'{}' is not a poll id, polling:// link or explorer URL", text))?;
+        let segments: Vec<&str> = url.path_segments().into_iter().flatten().filter(|s| !s.is_empty()).collect();
+
+        if url.scheme() == SCHEME {
+            let host = url.host_str().unwrap_or_default();
+            let chain_id = host.parse().map_err(|_| format!("'{}' is not a chain id; links look like polling://1/0x…/12", host))?;
+            let [contract, id] = segments[..] else {
+                return Err(format!("Expected polling://<chain id>/<contract>/<poll id>, got '{}'", text));
+            };
+            return Ok(Self { id: parse_id(id)?, chain_id: Some(chain_id), contract: Some(parse_contract(contract)?) });
+        }
+
+        if !matches!(url.scheme(), "http" | "https") {
+            return Err(format!("Unsupported link scheme '{}'; use {}:// or an explorer URL", url.scheme(), SCHEME));
+        }
+        let contract = match segments[..] {
+            ["address", contract, ..] => parse_contract(contract)?,
+            ["tx", ..] => return Err("Transaction links don't say which poll they touched; share the poll's polling:// link".to_string()),
+            _ => return Err(format!("'{}' is not a contract page; expected …/address/<contract>?poll=<id>", text)),
+        };
+        let id = url.query_pairs()
+            .find(|(key, _)| matches!(key.as_ref(), "poll" | "pollId" | "poll_id"))
+            .map(|(_, value)| value.into_owned())
+            .or_else(|| url.fragment().map(|f| f.trim_start_matches("poll").trim_start_matches(['-', '=']).to_string()))
+            .ok_or_else(|| format!("'{}' does not name a poll; add ?poll=<id>", text))?;
+        let host = url.host_str().unwrap_or_default();
+        let host = host.strip_prefix("www.").unwrap_or(host);
+        let chain_id = EXPLORERS.iter().find(|(explorer, _)| *explorer == host).map(|(_, chain)| *chain);
+        Ok(Self { id: parse_id(&id)?, chain_id, contract: Some(contract) })
+    }
+}
+
+impl fmt::Display for PollRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.chain_id, self.contract) {
+            (Some(chain_id), Some(contract)) => f.write_str(&link(chain_id, contract, self.id)),
+            _ => write!(f, "{}", self.id),
+        }
+    }
+}
+
+impl ConnectionArgs {
+    /// Point this invocation at the chain and contract a pasted link names.
+    /// Flags that disagree with the link are refused rather than silently overridden;
+    /// `RPC_URL_<chain id>` supplies the endpoint for a linked chain other than RPC_URL's.
+    pub fn follow_link(&mut self, poll: &PollRef) -> Result<()> {
+        if let Some(linked) = poll.contract {
+            if self.all_contracts {
+                bail_user!("A poll link names one contract; drop --all-contracts");
+            }
+            let given = match (&self.contract_address, &self.contract) {
+                (Some(address), _) => Some(parse_contract(address).map_err(|e| anyhow::anyhow!(e))?),
+                (None, Some(name)) => Some(contracts::resolve(name)?),
+                (None, None) => None,
+            };
+            match given {
+                Some(given) if given != linked => {
+                    bail_user!("The link is for contract {:?}, but the flags point at {:?}", linked, given)
+                }
+                Some(_) => {}
+                None => self.contract_address = Some(format!("{:?}", linked)),
+            }
+        }
+        if let Some(linked) = poll.chain_id {
+            match self.chain_id {
+                Some(given) if given != linked => {
+                    bail_user!("The link is for chain {}, but --chain-id {} was given", linked, given)
+                }
+                _ => self.chain_id = Some(linked),
+            }
+            if self.rpc_url.is_none() {
+                if let Ok(url) = std::env::var(format!("RPC_URL_{}", linked)) {
+                    self.rpc_url = Some(url);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTRACT: &str = "0x5FbDB2315678afecb367f032d93F642f64180aa3";
+
+    #[test]
+    fn parses_ids_links_and_explorer_urls() {
+        let contract: Address = CONTRACT.parse().unwrap();
+        assert_eq!("7".parse::<PollRef>().unwrap(), PollRef { id: 7, chain_id: None, contract: None });
+
+        let linked: PollRef = link(31337, contract, 12).parse().unwrap();
+        assert_eq!(linked, PollRef { id: 12, chain_id: Some(31337), contract: Some(contract) });
+        assert_eq!(linked.to_string(), link(31337, contract, 12));
+
+        let explorer: PollRef = format!("https://www.basescan.org/address/{}?poll=3", CONTRACT).parse().unwrap();
+        assert_eq!(explorer, PollRef { id: 3, chain_id: Some(8453), contract: Some(contract) });
+        let fragment: PollRef = format!("https://explorer.example/address/{}#poll-4", CONTRACT).parse().unwrap();
+        assert_eq!(fragment, PollRef { id: 4, chain_id: None, contract: Some(contract) });
+
+        assert!("polling://1/12".parse::<PollRef>().is_err());
+        assert!(format!("https://etherscan.io/address/{}", CONTRACT).parse::<PollRef>().is_err());
+        assert!("https://etherscan.io/tx/0xabc".parse::<PollRef>().is_err());
+    }
+
+    #[test]
+    fn links_fill_in_the_connection_but_do_not_override_flags() {
+        let poll: PollRef = format!("polling://5/{}/1", CONTRACT).parse().unwrap();
+        let mut args = ConnectionArgs::default();
+        args.follow_link(&poll).unwrap();
+        assert_eq!((args.chain_id, args.contract_address), (Some(5), Some(CONTRACT.to_lowercase())));
+
+        let mut args = ConnectionArgs { chain_id: Some(1), ..Default::default() };
+        assert!(args.follow_link(&poll).is_err());
+        let mut args = ConnectionArgs { contract_address: Some(format!("{:?}", Address::zero())), ..Default::default() };
+        assert!(args.follow_link(&poll).is_err());
+    }
+}
@@ -0,0 +1,170 @@
+use std::sync::{Arc, OnceLock};
+
+use anyhow::{Context, Result};
+use ethers::prelude::*;
+use resvg::{tiny_skia, usvg};
+
+use crate::chart::{self, Series, PALETTE};
+use crate::feed::xml_escape;
+use crate::numbers::SaturatingU64;
+use crate::timezone::{self, DisplayZone};
+use crate::{u8_to_status, PollManager};
+
+/// The size Open Graph and Twitter cards are displayed at
+const WIDTH: u32 = 1200;
+const HEIGHT: u32 = 630;
+/// Options drawn on a card; the rest are counted in the footer
+const TOP_OPTIONS: usize = 4;
+const QUESTION_LINE: usize = 38;
+const LABEL_CHARS: usize = 24;
+const FONTS: &str = "DejaVu Sans, Helvetica, Arial, Liberation Sans, sans-serif";
+
+/// What a poll's social preview image shows
+pub struct Card<'a> {
+    pub site: &'a str,
+    pub poll_id: u64,
+    pub status: &'a str,
+    pub end_time: u64,
+    /// Results under the deciding metric, titled with the question
+    pub series: &'a Series,
+}
+
+/// `text` cut to at most `chars` characters, ending in an ellipsis when cut
+pub(crate) fn truncate(text: &str, chars: usize) -> String {
+    if text.chars().count() <= chars {
+        return text.to_string();
+    }
+    let cut: String = text.chars().take(chars.saturating_sub(1)).collect();
+    format!("{}…", cut.trim_end())
+}
+
+/// Word-wrap `text` into at most `lines` lines of `width` characters
+pub(crate) fn wrap(text: &str, width: usize, lines: usize) -> Vec<String> {
+    let mut wrapped: Vec<String> = Vec::new();
+    let mut words = text.split_whitespace();
+    while let Some(word) = words.next() {
+        let fits = wrapped.last().is_some_and(|line: &String| line.chars().count() + 1 + word.chars().count() <= width);
+        if fits {
+            let line = wrapped.last_mut().expect("fits a line");
+            line.push(' ');
+            line.push_str(word);
+        } else if wrapped.len() == lines {
+            let last = wrapped.last_mut().expect("lines > 0");
+            let rest: Vec<&str> = std::iter::once(word).chain(words).collect();
+            *last = truncate(&format!("{} {}", last, rest.join(" ")), width);
+            break;
+        } else {
+            wrapped.push(truncate(word, width));
+        }
+    }
+    wrapped
+}
+
+impl Card<'_> {
+    pub fn svg(&self) -> String {
+        let mut svg = format!(concat!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\" ",
+            "font-family=\"{fonts}\" fill=\"#222\">\n",
+            "  <rect width=\"100%\" height=\"100%\" fill=\"#fff\"/>\n",
+            "  <rect width=\"16\" height=\"100%\" fill=\"{accent}\"/>\n",
+            "  <text x=\"60\" y=\"72\" font-size=\"28\" fill=\"#666\">{site} · Poll #{id}</text>\n"),
+            w = WIDTH, h = HEIGHT, fonts = FONTS, accent = PALETTE[0], site = xml_escape(self.site), id = self.poll_id);
+
+        let question = wrap(&self.series.title, QUESTION_LINE, 2);
+        let mut y = 140;
+        for line in &question {
+            svg.push_str(&format!("  <text x=\"60\" y=\"{}\" font-size=\"52\" font-weight=\"bold\">{}</text>\n", y, xml_escape(line)));
+            y += 62;
+        }
+        let deadline = if self.status == "Active" { "Voting ends" } else { "Voting ended" };
+        svg.push_str(&format!("  <text x=\"60\" y=\"{}\" font-size=\"28\" fill=\"#666\">{} {} · {}</text>\n",
+            y + 4, deadline, timezone::format_in(self.end_time, DisplayZone::Utc), xml_escape(self.status)));
+
+        let mut top: Vec<&(String, f64)> = self.series.points.iter().collect();
+        top.sort_by(|a, b| b.1.total_cmp(&a.1));
+        let (total, max) = (self.series.total(), self.series.max());
+        const LABEL: f64 = 60.0;
+        const BAR: f64 = 470.0;
+        const PLOT: f64 = 520.0;
+        let mut row = y as f64 + 48.0;
+        for (i, (label, value)) in top.iter().take(TOP_OPTIONS).enumerate() {
+            let length = if max > 0.0 { value / max * PLOT } else { 0.0 };
+            svg.push_str(&format!("  <text x=\"{:.0}\" y=\"{:.0}\" font-size=\"30\">{}</text>\n", LABEL, row + 32.0, xml_escape(&truncate(label, LABEL_CHARS))));
+            svg.push_str(&format!("  <rect x=\"{:.0}\" y=\"{:.0}\" width=\"{:.1}\" height=\"40\" rx=\"6\" fill=\"{}\"/>\n",
+                BAR, row + 4.0, length.max(4.0), PALETTE[i % PALETTE.len()]));
+            svg.push_str(&format!("  <text x=\"{:.1}\" y=\"{:.0}\" font-size=\"28\" fill=\"#444\">{:.1}%</text>\n",
+                BAR + length.max(4.0) + 12.0, row + 34.0, polling_analytics::stats::share(*value, total)));
+            row += 58.0;
+        }
+
+        let mut footer = format!("{} {}", chart::format_value(total), self.series.metric);
+        if top.len() > TOP_OPTIONS {
+            footer.push_str(&format!(" · +{} more", top.len() - TOP_OPTIONS));
+        }
+        svg.push_str(&format!("  <text x=\"60\" y=\"{}\" font-size=\"26\" fill=\"#666\">{}</text>\n</svg>\n", HEIGHT - 36, xml_escape(&footer)));
+        svg
+    }
+
+    /// The card as a PNG, with text drawn in system fonts
+    pub fn png(&self) -> Result<Vec<u8>> {
+        render_png(&self.svg())
+    }
+}
+
+/// System fonts, loaded once per run since scanning them is the slow part of rendering
+fn fonts() -> Arc<usvg::fontdb::Database> {
+    static FONTS: OnceLock<Arc<usvg::fontdb::Database>> = OnceLock::new();
+    FONTS.get_or_init(|| {
+        let mut fonts = usvg::fontdb::Database::new();
+        fonts.load_system_fonts();
+        Arc::new(fonts)
+    }).clone()
+}
+
+pub fn render_png(svg: &str) -> Result<Vec<u8>> {
+    let options = usvg::Options { fontdb: fonts(), ..Default::default() };
+    let tree = usvg::Tree::from_str(svg, &options).context("Invalid preview SVG")?;
+    let size = tree.size().to_int_size();
+    let mut pixmap = tiny_skia::Pixmap::new(size.width(), size.height()).context("Preview image has no area")?;
+    resvg::render(&tree, tiny_skia::Transform::default(), &mut pixmap.as_mut());
+    pixmap.encode_png().context("Failed to encode preview PNG")
+}
+
+impl<M: Middleware + 'static> PollManager<M> {
+    /// `export -f png`: a poll's social preview card
+    pub async fn poll_card_png(&self, poll_id: u64, site: &str) -> Result<Vec<u8>> {
+        let poll = self.poll_data(U256::from(poll_id)).await?;
+        let tally = self.poll_tally(poll_id, &poll.2, poll.7).await?;
+        let end_time = poll.5.to_u64_saturating();
+        let status = if poll.6 == 0 && self.now() >= end_time { "Ended" } else { u8_to_status(poll.6) };
+        let series = Series::from_tally(&poll.1, &tally);
+        Card { site, poll_id, status, end_time, series: &series }.png()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cards_show_the_question_and_top_options() {
+        assert_eq!(wrap("Should we ship the new voting contract this week?", 20, 2),
+            vec!["Should we ship the", "new voting contract…"]);
+        assert_eq!(wrap("Short?", 20, 2), vec!["Short?"]);
+
+        let series = Series {
+            title: "Fund <grants>?".to_string(),
+            metric: "votes",
+            points: ["A", "B", "C", "D", "E"].iter().zip([1.0, 5.0, 2.0, 0.0, 2.0]).map(|(o, v)| (o.to_string(), v)).collect(),
+        };
+        let card = Card { site: "DAO", poll_id: 7, status: "Active", end_time: 1_700_000_000, series: &series };
+        let svg = card.svg();
+        assert!(svg.contains("Fund &lt;grants&gt;?") && svg.contains("DAO · Poll #7"));
+        assert!(svg.contains("Voting ends 2023-11-14 22:13:20 UTC"));
+        assert!(svg.find(">B<") < svg.find(">C<") && !svg.contains(">D<"), "top options by result");
+        assert!(svg.contains("10 votes · +1 more"));
+
+        let png = render_png(&svg).unwrap();
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+    }
+}
@@ -0,0 +1,242 @@
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+
+use anyhow::{Context, Result};
+use colored::*;
+use ethers::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{bail_config, CliError};
+use crate::numbers::SaturatingU64;
+use crate::{output, store, u256_to_f64, PollManager};
+
+const PRICES_FILE: &str = "prices.json";
+
+/// How long a fetched price is reused; keeps back-to-back commands under free API rate limits
+const CACHE_TTL_SECS: u64 = 300;
+
+/// Chainlink answers older than this are treated as unavailable
+const MAX_FEED_AGE_SECS: u64 = 86_400;
+
+abigen!(
+    ChainlinkAggregator,
+    r#"[
+        function latestRoundData() external view returns (uint80 roundId, int256 answer, uint256 startedAt, uint256 updatedAt, uint80 answeredInRound)
+        function decimals() external view returns (uint8)
+    ]"#
+);
+
+/// Currency fiat equivalents are shown in, or `off` to skip price lookups entirely
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Fiat {
+    Usd,
+    Eur,
+    Off,
+}
+
+impl Fiat {
+    pub fn code(self) -> &'static str {
+        match self {
+            Fiat::Usd => "usd",
+            Fiat::Eur => "eur",
+            Fiat::Off => "off",
+        }
+    }
+
+    /// `$12.34` or `€12.34`
+    pub fn format(self, amount: f64) -> String {
+        match self {
+            Fiat::Eur => format!("€{:.2}", amount),
+            _ => format!("${:.2}", amount),
+        }
+    }
+}
+
+static FIAT: OnceLock<Fiat> = OnceLock::new();
+
+/// Set the display currency once at startup; defaults to USD
+pub fn init(fiat: Option<Fiat>) {
+    let _ = FIAT.set(fiat.unwrap_or(Fiat::Usd));
+}
+
+pub fn fiat() -> Fiat {
+    *FIAT.get().unwrap_or(&Fiat::Usd)
+}
+
+/// ` (≈ $12.34)` for `amount` units priced at `price`, or nothing without a price
+pub fn approx(amount: f64, price: Option<f64>) -> String {
+    match price {
+        Some(price) => format!(" (≈ {})", fiat().format(amount * price)),
+        None => String::new(),
+    }
+}
+
+/// Where prices come from, configured through environment variables
+#[derive(Debug, Clone)]
+enum PriceSource {
+    /// CoinGecko's simple price API
+    CoinGecko { api_url: String },
+    /// A Chainlink ETH/<fiat> aggregator on the connected chain
+    Chainlink { feed: Address },
+}
+
+impl PriceSource {
+    /// PRICE_SOURCE (coingecko, chainlink) with PRICE_API_URL or CHAINLINK_ETH_USD_FEED / CHAINLINK_ETH_EUR_FEED
+    fn from_env(fiat: Fiat) -> Result<Self> {
+        match std::env::var("PRICE_SOURCE").unwrap_or_else(|_| "coingecko".to_string()).to_lowercase().as_str() {
+            "coingecko" => Ok(PriceSource::CoinGecko {
+                api_url: std::env::var("PRICE_API_URL").unwrap_or_else(|_| "https://api.coingecko.com".to_string()),
+            }),
+            "chainlink" => {
+                let variable = format!("CHAINLINK_ETH_{}_FEED", fiat.code().to_uppercase());
+                let Ok(feed) = std::env::var(&variable) else {
+                    bail_config!("{} must be set to read prices from Chainlink", variable);
+                };
+                match feed.parse() {
+                    Ok(feed) => Ok(PriceSource::Chainlink { feed }),
+                    Err(_) => bail_config!("{} is not a valid address: {}", variable, feed),
+                }
+            }
+            other => bail_config!("Invalid PRICE_SOURCE '{}'. Use: coingecko, chainlink", other),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct CachedPrice {
+    price: f64,
+    fetched_at: u64,
+}
+
+fn is_fresh(fetched_at: u64, now: u64) -> bool {
+    now.saturating_sub(fetched_at) < CACHE_TTL_SECS
+}
+
+fn cached(key: &str, now: u64) -> Option<f64> {
+    let prices: BTreeMap<String, CachedPrice> = store::load(PRICES_FILE).ok()?;
+    prices.get(key).filter(|p| is_fresh(p.fetched_at, now)).map(|p| p.price)
+}
+
+/// Best effort: a read-only data directory only costs an extra lookup next time
+fn remember(key: &str, price: f64, now: u64) {
+    let mut prices: BTreeMap<String, CachedPrice> = store::load(PRICES_FILE).unwrap_or_default();
+    prices.retain(|_, p| is_fresh(p.fetched_at, now));
+    prices.insert(key.to_string(), CachedPrice { price, fetched_at: now });
+    let _ = store::save(PRICES_FILE, &prices);
+}
+
+async fn coingecko_get(url: String) -> Result<serde_json::Value> {
+    let response = reqwest::Client::new()
+        .get(url)
+        .send()
+        .await
+        .context("Failed to reach the price API")?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(CliError::Rpc(format!("Price API returned {}", status)).into());
+    }
+    Ok(response.json().await?)
+}
+
+impl<M: Middleware + 'static> PollManager<M> {
+    async fn fetch_eth_price(&self, fiat: Fiat) -> Result<f64> {
+        match PriceSource::from_env(fiat)? {
+            PriceSource::CoinGecko { api_url } => {
+                let url = format!("{}/api/v3/simple/price?ids=ethereum&vs_currencies={}", api_url.trim_end_matches('/'), fiat.code());
+                let body = coingecko_get(url).await?;
+                body["ethereum"][fiat.code()].as_f64()
+                    .ok_or_else(|| CliError::Rpc(format!("Price API returned no ETH/{} price", fiat.code().to_uppercase())).into())
+            }
+            PriceSource::Chainlink { feed } => {
+                let aggregator = ChainlinkAggregator::new(feed, self.client.clone());
+                let (_, answer, _, updated_at, _) = aggregator.latest_round_data().call().await?;
+                let decimals = aggregator.decimals().call().await?;
+                let now = chrono::Utc::now().timestamp() as u64;
+                if !answer.is_positive() || now.saturating_sub(updated_at.to_u64_saturating()) > MAX_FEED_AGE_SECS {
+                    return Err(CliError::Rpc(format!("Chainlink feed {:?} has no fresh answer", feed)).into());
+                }
+                Ok(u256_to_f64(answer.into_raw()) / 10f64.powi(decimals as i32))
+            }
+        }
+    }
+
+    async fn fetch_token_price(&self, token: Address, fiat: Fiat) -> Result<Option<f64>> {
+        let PriceSource::CoinGecko { api_url } = PriceSource::from_env(fiat)? else {
+            // Chainlink only covers ETH here; tokens would each need their own feed
+            return Ok(None);
+        };
+        let platform = std::env::var("PRICE_PLATFORM").unwrap_or_else(|_| "ethereum".to_string());
+        let address = format!("{:?}", token);
+        let url = format!(
+            "{}/api/v3/simple/token_price/{}?contract_addresses={}&vs_currencies={}",
+            api_url.trim_end_matches('/'), platform, address, fiat.code()
+        );
+        let body = coingecko_get(url).await?;
+        Ok(body[address.as_str()][fiat.code()].as_f64())
+    }
+
+    /// Price of one ETH in the `--fiat` currency; `None` when fiat display is off or
+    /// the lookup fails, which is reported on stderr so JSON output stays clean
+    pub async fn eth_price(&self) -> Option<f64> {
+        let fiat = fiat();
+        if fiat == Fiat::Off {
+            return None;
+        }
+        let key = format!("eth:{}", fiat.code());
+        let now = chrono::Utc::now().timestamp() as u64;
+        if let Some(price) = cached(&key, now) {
+            return Some(price);
+        }
+        match self.fetch_eth_price(fiat).await {
+            Ok(price) => {
+                remember(&key, price, now);
+                Some(price)
+            }
+            Err(e) => {
+                eprintln!("{} {:#}", output::render("⚠️  No ETH price:".to_string()).yellow(), e);
+                None
+            }
+        }
+    }
+
+    /// Price of one whole `token` in the `--fiat` currency, when the price source lists it
+    pub async fn token_price(&self, token: Address) -> Option<f64> {
+        let fiat = fiat();
+        if fiat == Fiat::Off {
+            return None;
+        }
+        let key = format!("token:{:?}:{}", token, fiat.code());
+        let now = chrono::Utc::now().timestamp() as u64;
+        if let Some(price) = cached(&key, now) {
+            return Some(price);
+        }
+        match self.fetch_token_price(token, fiat).await {
+            Ok(Some(price)) => {
+                remember(&key, price, now);
+                Some(price)
+            }
+            Ok(None) => None,
+            Err(e) => {
+                eprintln!("{} {:#}", output::render("⚠️  No token price:".to_string()).yellow(), e);
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_fiat_amounts() {
+        assert_eq!(Fiat::Usd.format(1234.5), "$1234.50");
+        assert_eq!(Fiat::Eur.format(0.126), "€0.13");
+    }
+
+    #[test]
+    fn cached_prices_expire() {
+        assert!(is_fresh(1_000, 1_000 + CACHE_TTL_SECS - 1));
+        assert!(!is_fresh(1_000, 1_000 + CACHE_TTL_SECS));
+    }
+}
@@ -0,0 +1,187 @@
+use std::collections::BTreeMap;
+
+use anyhow::{Context, Result};
+use colored::*;
+use ethers::prelude::*;
+use ethers::utils::keccak256;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{bail_user, CliError};
+use crate::ipfs::IpfsClient;
+use crate::{store, timezone, PollManager};
+
+const PROFILES_FILE: &str = "delegate_profiles.json";
+
+/// A delegate's self-description, covered by their signature
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DelegateProfile {
+    pub address: Address,
+    pub statement: String,
+    pub contact: Option<String>,
+    pub focus_areas: Vec<String>,
+    pub updated_at: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignedProfile {
+    pub profile: DelegateProfile,
+    /// EIP-191 personal signature by `profile.address` over keccak256 of the JSON-serialized profile
+    pub signature: String,
+}
+
+impl DelegateProfile {
+    pub fn hash(&self) -> Result<H256> {
+        Ok(H256::from(keccak256(serde_json::to_vec(self)?)))
+    }
+}
+
+impl SignedProfile {
+    /// Check the profile was signed by the address it describes
+    pub fn verify(&self) -> Result<()> {
+        let signature: Signature = self.signature.parse()
+            .map_err(|_| CliError::User("Profile signature is malformed".to_string()))?;
+        if signature.verify(self.profile.hash()?.as_bytes(), self.profile.address).is_err() {
+            bail_user!("Profile for {:?} is not signed by that address", self.profile.address);
+        }
+        Ok(())
+    }
+}
+
+/// A profile known locally and the IPFS CID it came from
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfileRef {
+    pub cid: String,
+    pub profile: DelegateProfile,
+}
+
+/// Profiles by lowercase address
+type ProfileBook = BTreeMap<String, ProfileRef>;
+
+fn key(address: Address) -> String {
+    format!("{:?}", address)
+}
+
+fn remember(profile: &SignedProfile, cid: &str) -> Result<()> {
+    let mut book: ProfileBook = store::load(PROFILES_FILE)?;
+    book.insert(key(profile.profile.address), ProfileRef { cid: cid.to_string(), profile: profile.profile.clone() });
+    store::save(PROFILES_FILE, &book)
+}
+
+/// The locally known profile of `address`, if any
+pub fn lookup(address: Address) -> Option<ProfileRef> {
+    let book: ProfileBook = store::load(PROFILES_FILE).ok()?;
+    book.get(&key(address)).cloned()
+}
+
+fn print_profile(entry: &ProfileRef) {
+    let profile = &entry.profile;
+    say!("{} {}", "Statement:".yellow().bold(), profile.statement);
+    if let Some(contact) = &profile.contact {
+        say!("{} {}", "Contact:".yellow().bold(), contact);
+    }
+    if !profile.focus_areas.is_empty() {
+        say!("{} {}", "Focus Areas:".yellow().bold(), profile.focus_areas.join(", "));
+    }
+    say!("{} {} (ipfs://{})", "Profile Updated:".yellow().bold(), timezone::format(profile.updated_at), entry.cid);
+}
+
+/// One-line summary for `delegation` output
+pub fn summary(address: Address) -> Option<String> {
+    let entry = lookup(address)?;
+    let mut line = entry.profile.statement.lines().next().unwrap_or_default().to_string();
+    if line.chars().count() > 80 {
+        line = format!("{}…", line.chars().take(79).collect::<String>());
+    }
+    if !entry.profile.focus_areas.is_empty() {
+        line.push_str(&format!(" [{}]", entry.profile.focus_areas.join(", ")));
+    }
+    Some(line)
+}
+
+/// `delegates fetch`: download a profile from IPFS, check its signature and keep it locally
+pub async fn fetch_profile(cid: &str) -> Result<()> {
+    let client = IpfsClient::from_env()?;
+    let link = client.gateway_link(cid);
+    let response = reqwest::get(&link).await.context("Failed to reach the IPFS gateway")?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(CliError::Rpc(format!("IPFS gateway returned {} for {}", status, cid)).into());
+    }
+    let signed: SignedProfile = serde_json::from_slice(&response.bytes().await?)
+        .map_err(|e| CliError::User(format!("{} is not a delegate profile: {}", cid, e)))?;
+    signed.verify()?;
+    remember(&signed, cid)?;
+
+    say!("{} {:?}", "✅ Verified profile of".green().bold(), signed.profile.address);
+    print_profile(&ProfileRef { cid: cid.to_string(), profile: signed.profile });
+    Ok(())
+}
+
+/// `delegates show`: print a locally known profile
+pub fn show_profile(address: Address) -> Result<()> {
+    let Some(entry) = lookup(address) else {
+        bail_user!("No profile known for {:?}; fetch it with `delegates fetch <cid>`", address);
+    };
+    say!("\n{} {:?}", "🪪 Delegate".cyan().bold(), address);
+    print_profile(&entry);
+    Ok(())
+}
+
+impl<M: Middleware + 'static> PollManager<M> {
+    /// `delegates publish`: sign the caller's profile, pin it to IPFS and keep the reference
+    pub async fn publish_profile(&self, statement: &str, contact: Option<String>, focus_areas: Vec<String>) -> Result<()> {
+        if self.sender != self.wallet.address() {
+            bail_user!("Profiles are signed by the key; --impersonate cannot publish one");
+        }
+        if statement.trim().is_empty() {
+            bail_user!("--statement cannot be empty");
+        }
+        let profile = DelegateProfile {
+            address: self.wallet.address(),
+            statement: statement.trim().to_string(),
+            contact,
+            focus_areas,
+            updated_at: chrono::Utc::now().timestamp() as u64,
+        };
+        let signature = self.wallet.sign_message(profile.hash()?.as_bytes()).await?;
+        let signed = SignedProfile { profile, signature: format!("0x{}", signature) };
+
+        let client = IpfsClient::from_env()?;
+        say!("{}", "📤 Publishing delegate profile to IPFS...".cyan().bold());
+        let cid = client.pin("delegate-profile.json", "application/json", serde_json::to_string_pretty(&signed)?.as_bytes()).await?;
+        remember(&signed, &cid)?;
+
+        say!("{}", "✅ Profile published!".green().bold());
+        say!("{} {}", "CID:".cyan().bold(), cid.yellow());
+        say!("{} {}", "Gateway:".cyan().bold(), client.gateway_link(&cid).white());
+        say!("Delegators can load it with `delegates fetch {}`.", cid);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::utils::hash_message;
+
+    #[test]
+    fn profiles_verify_only_for_their_signer() {
+        let wallet: LocalWallet = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80".parse().unwrap();
+        let profile = DelegateProfile {
+            address: wallet.address(),
+            statement: "Fiscal conservative".to_string(),
+            contact: None,
+            focus_areas: vec!["treasury".to_string()],
+            updated_at: 1_700_000_000,
+        };
+        let signature = wallet.sign_hash(hash_message(profile.hash().unwrap().as_bytes())).unwrap();
+        let mut signed = SignedProfile { profile, signature: format!("0x{}", signature) };
+        assert!(signed.verify().is_ok());
+
+        signed.profile.statement = "Spend freely".to_string();
+        assert!(signed.verify().is_err(), "edited after signing");
+        signed.profile.statement = "Fiscal conservative".to_string();
+        signed.profile.address = Address::repeat_byte(9);
+        assert!(signed.verify().is_err(), "claims someone else's address");
+    }
+}
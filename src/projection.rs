@@ -0,0 +1,148 @@
+use anyhow::Result;
+use colored::*;
+use ethers::prelude::*;
+use polling_analytics::stats::{expected_additional, leader_hold_probability, poisson_at_least};
+use serde::{Deserialize, Serialize};
+
+use crate::numbers::SaturatingU64;
+use crate::error::bail_user;
+use crate::velocity::{bucket_votes, recent_rate};
+use crate::{u256_to_f64, PollManager};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OutcomeProjection {
+    pub poll_id: u64,
+    pub question: String,
+    pub model: String,
+    pub is_open: bool,
+    pub hours_remaining: f64,
+    pub current_votes: u64,
+    pub votes_per_hour: f64,
+    pub expected_additional_votes: f64,
+    pub projected_final_votes: u64,
+    pub min_participation: u64,
+    pub quorum_probability: f64,
+    pub leader: Option<String>,
+    pub runner_up: Option<String>,
+    pub leader_hold_probability: f64,
+}
+
+impl<M: Middleware + 'static> PollManager<M> {
+    pub async fn project_outcome(&self, poll_id: u64, model: &str, format: &str, output_path: Option<String>) -> Result<()> {
+        let poll_data = self.poll_data(U256::from(poll_id)).await?;
+        let (results, _total_votes, _total_weight) = self.poll_results(U256::from(poll_id)).await?;
+        let votes = self.fetch_vote_events(poll_id).await?;
+
+        let created_at = poll_data.4.to_u64_saturating();
+        let end_time = poll_data.5.to_u64_saturating();
+        let now = chrono::Utc::now().timestamp() as u64;
+        let is_open = poll_data.6 == 0 && now < end_time;
+        let until = if is_open { now } else { end_time };
+
+        let current_votes = votes.len() as u64;
+        let elapsed_hours = (until.saturating_sub(created_at) as f64 / 3600.0).max(1.0 / 60.0);
+        let hours_remaining = if is_open { (end_time - now) as f64 / 3600.0 } else { 0.0 };
+
+        let timestamps: Vec<u64> = votes.iter().map(|v| v.timestamp).collect();
+        let hourly = bucket_votes(&timestamps, created_at, until, 3600);
+        let overall_rate = current_votes as f64 / elapsed_hours;
+        let recent = recent_rate(&hourly, 3600, until);
+
+        let Some(expected_additional_votes) = expected_additional(model, overall_rate, recent, hours_remaining) else {
+            bail_user!("Invalid projection model. Use: linear, recent, decay");
+        };
+        let min_participation = poll_data.9.to_u64_saturating();
+        let needed = min_participation.saturating_sub(current_votes);
+
+        let tally: Vec<f64> = results.iter().map(|v| u256_to_f64(*v)).collect();
+        let mut ranked: Vec<usize> = (0..tally.len()).collect();
+        ranked.sort_by(|a, b| tally[*b].partial_cmp(&tally[*a]).unwrap_or(std::cmp::Ordering::Equal));
+        let option_name = |rank: usize| ranked.get(rank)
+            .filter(|i| tally[**i] > 0.0)
+            .and_then(|i| poll_data.2.get(*i).cloned());
+
+        let projection = OutcomeProjection {
+            poll_id,
+            question: poll_data.1.clone(),
+            model: model.to_lowercase(),
+            is_open,
+            hours_remaining,
+            current_votes,
+            votes_per_hour: if model.eq_ignore_ascii_case("linear") { overall_rate } else { recent },
+            expected_additional_votes,
+            projected_final_votes: current_votes + expected_additional_votes.round() as u64,
+            min_participation,
+            quorum_probability: poisson_at_least(expected_additional_votes, needed),
+            leader: option_name(0),
+            runner_up: option_name(1),
+            leader_hold_probability: leader_hold_probability(&tally, current_votes, expected_additional_votes),
+        };
+
+        match format.to_lowercase().as_str() {
+            "json" => {
+                let json_output = serde_json::to_string_pretty(&projection)?;
+                if let Some(path) = output_path {
+                    std::fs::write(&path, &json_output)?;
+                    say!("{} {}", "✅ Exported to:".green().bold(), path.yellow());
+                } else {
+                    println!("{}", json_output);
+                }
+            }
+            "table" => print_projection(&projection),
+            _ => bail_user!("Unsupported format: {}. Use table or json", format),
+        }
+
+        Ok(())
+    }
+}
+
+fn probability_colored(probability: f64) -> ColoredString {
+    let text = format!("{:.1}%", probability * 100.0);
+    if probability >= 0.8 {
+        text.green().bold()
+    } else if probability >= 0.5 {
+        text.yellow().bold()
+    } else {
+        text.red().bold()
+    }
+}
+
+fn print_projection(projection: &OutcomeProjection) {
+    say!("\n{}", "🔮 OUTCOME PROJECTION".cyan().bold().underline());
+    say!("{}", "═".repeat(60).cyan());
+    say!("{} {} - {}", "Poll ID:".yellow().bold(), projection.poll_id.to_string().white(), projection.question.white().bold());
+    say!("{} {}", "Model:".yellow().bold(), projection.model.white());
+
+    if !projection.is_open {
+        say!("{}", "ℹ️  Poll has ended; figures reflect the final state.".yellow());
+    }
+
+    say!("\n{}", "📈 TURNOUT".cyan().bold());
+    say!("{}", "─".repeat(60).cyan());
+    say!("{} {}", "Current Votes:".yellow().bold(), projection.current_votes.to_string().green());
+    say!("{} {:.2}", "Votes/Hour:".yellow().bold(), projection.votes_per_hour);
+    say!("{} {:.1}", "Hours Remaining:".yellow().bold(), projection.hours_remaining);
+    say!("{} {}", "Projected Final Votes:".yellow().bold(), projection.projected_final_votes.to_string().cyan());
+
+    say!("\n{}", "🎯 QUORUM".cyan().bold());
+    say!("{}", "─".repeat(60).cyan());
+    if projection.min_participation == 0 {
+        say!("{} {}", "Min Participation:".yellow().bold(), "None required".white());
+    } else {
+        say!("{} {}", "Min Participation:".yellow().bold(), projection.min_participation.to_string().white());
+        say!("{} {}", "Probability Reached:".yellow().bold(), probability_colored(projection.quorum_probability));
+    }
+
+    say!("\n{}", "🏁 LEADER".cyan().bold());
+    say!("{}", "─".repeat(60).cyan());
+    match &projection.leader {
+        Some(leader) => {
+            say!("{} {}", "Current Leader:".yellow().bold(), leader.green().bold());
+            if let Some(runner_up) = &projection.runner_up {
+                say!("{} {}", "Runner-up:".yellow().bold(), runner_up.white());
+            }
+            say!("{} {}", "Probability Leader Holds:".yellow().bold(), probability_colored(projection.leader_hold_probability));
+        }
+        None => say!("{} {}", "Current Leader:".yellow().bold(), "No votes yet".red()),
+    }
+}
@@ -0,0 +1,354 @@
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use colored::*;
+use ethers::prelude::*;
+use serde_json::{json, Value};
+use tokio::io::BufReader;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{mpsc, oneshot, Mutex};
+use tokio::time::Instant;
+
+use crate::error::bail_config;
+use crate::http;
+
+pub const DEFAULT_LISTEN: &str = "127.0.0.1:8546";
+
+/// How long contract reads wait for others to share one upstream batch
+const BATCH_WINDOW: Duration = Duration::from_millis(10);
+const MAX_BATCH: usize = 50;
+
+/// How long a fetched head block number is reused
+const HEAD_TTL: Duration = Duration::from_secs(1);
+
+/// Pinned reads kept before the cache starts over
+const MAX_CACHED: usize = 10_000;
+
+/// Largest request body accepted, well above any JSON-RPC batch the CLI sends
+const MAX_BODY: usize = 4 * 1024 * 1024;
+
+/// Whether a JSON-RPC call's answer can be reused, and for how long
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Cacheability {
+    /// The answer never changes: chain id, or a contract read at a fixed block
+    Forever(String),
+    /// A contract read at `latest`: valid until the next block
+    UntilNextBlock(String),
+    /// Head block number, answered from the proxy's own head tracking
+    Head,
+    Never,
+}
+
+fn classify(method: &str, params: &Value, contract: Address) -> Cacheability {
+    match method {
+        "eth_chainId" | "net_version" => Cacheability::Forever(method.to_string()),
+        "eth_blockNumber" => Cacheability::Head,
+        "eth_call" => {
+            let to = params[0]["to"].as_str().and_then(|to| to.parse::<Address>().ok());
+            if to != Some(contract) {
+                return Cacheability::Never;
+            }
+            let key = format!("eth_call:{}", params[0]);
+            match &params[1] {
+                Value::Null => Cacheability::UntilNextBlock(key),
+                Value::String(tag) if tag == "latest" => Cacheability::UntilNextBlock(key),
+                // A block number; the named tags below it still move
+                Value::String(tag) if tag.starts_with("0x") => Cacheability::Forever(format!("{}@{}", key, tag)),
+                Value::Object(block) if block.contains_key("blockHash") => Cacheability::Forever(format!("{}@{}", key, params[1])),
+                _ => Cacheability::Never,
+            }
+        }
+        _ => Cacheability::Never,
+    }
+}
+
+fn rpc_error(id: &Value, message: impl std::fmt::Display) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "error": { "code": -32603, "message": message.to_string() } })
+}
+
+#[derive(Default)]
+struct Stats {
+    calls: AtomicU64,
+    cache_hits: AtomicU64,
+    upstream_requests: AtomicU64,
+    batched_calls: AtomicU64,
+}
+
+struct Upstream {
+    client: reqwest::Client,
+    url: String,
+    stats: Arc<Stats>,
+}
+
+impl Upstream {
+    async fn send(&self, body: &Value) -> Result<Value> {
+        self.stats.upstream_requests.fetch_add(1, Ordering::Relaxed);
+        Ok(self.client.post(&self.url).json(body).send().await?.error_for_status()?.json().await?)
+    }
+
+    /// One call on its own, answered under the caller's id
+    async fn call(&self, id: &Value, method: &str, params: &Value) -> Value {
+        match self.send(&json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params })).await {
+            Ok(mut response) => {
+                response["id"] = id.clone();
+                response
+            }
+            Err(e) => rpc_error(id, e),
+        }
+    }
+}
+
+type Queued = ((String, Value), oneshot::Sender<Value>);
+
+/// Collect contract reads arriving within `BATCH_WINDOW` into one upstream JSON-RPC batch
+async fn run_batcher(upstream: Arc<Upstream>, mut queue: mpsc::Receiver<Queued>) {
+    while let Some(first) = queue.recv().await {
+        let mut batch = vec![first];
+        let deadline = Instant::now() + BATCH_WINDOW;
+        while batch.len() < MAX_BATCH {
+            match tokio::time::timeout_at(deadline, queue.recv()).await {
+                Ok(Some(queued)) => batch.push(queued),
+                _ => break,
+            }
+        }
+
+        let upstream = upstream.clone();
+        tokio::spawn(async move {
+            if batch.len() > 1 {
+                let body: Vec<Value> = batch.iter().enumerate()
+                    .map(|(i, ((method, params), _))| json!({ "jsonrpc": "2.0", "id": i, "method": method, "params": params }))
+                    .collect();
+                // Providers without batch support answer with a single error object; fall back to one by one
+                if let Ok(Value::Array(responses)) = upstream.send(&Value::Array(body)).await {
+                    upstream.stats.batched_calls.fetch_add(batch.len() as u64, Ordering::Relaxed);
+                    let mut by_id: HashMap<u64, Value> = responses.into_iter()
+                        .filter_map(|response| Some((response["id"].as_u64()?, response)))
+                        .collect();
+                    for (i, (_, reply)) in batch.into_iter().enumerate() {
+                        let response = by_id.remove(&(i as u64)).unwrap_or_else(|| rpc_error(&json!(i), "missing from the upstream batch response"));
+                        let _ = reply.send(response);
+                    }
+                    return;
+                }
+            }
+            for ((method, params), reply) in batch {
+                let _ = reply.send(upstream.call(&json!(1), &method, &params).await);
+            }
+        });
+    }
+}
+
+struct Proxy {
+    upstream: Arc<Upstream>,
+    contract: Address,
+    batcher: mpsc::Sender<Queued>,
+    head: Mutex<Option<(Value, Instant)>>,
+    forever: Mutex<HashMap<String, Value>>,
+    /// Reads at `latest`, for the head block they were made at
+    current: Mutex<(Value, HashMap<String, Value>)>,
+    stats: Arc<Stats>,
+}
+
+impl Proxy {
+    /// The head block number, fetched at most once per `HEAD_TTL`
+    async fn head(&self) -> Result<Value, Value> {
+        let mut head = self.head.lock().await;
+        if let Some((number, fetched)) = head.as_ref() {
+            if fetched.elapsed() < HEAD_TTL {
+                return Ok(number.clone());
+            }
+        }
+        let response = self.upstream.call(&json!(1), "eth_blockNumber", &json!([])).await;
+        match response.get("result") {
+            Some(number) => {
+                *head = Some((number.clone(), Instant::now()));
+                Ok(number.clone())
+            }
+            None => Err(response["error"].clone()),
+        }
+    }
+
+    /// Send a contract read through the batcher
+    async fn batched(&self, id: &Value, method: &str, params: &Value) -> Value {
+        let (reply, answer) = oneshot::channel();
+        if self.batcher.send(((method.to_string(), params.clone()), reply)).await.is_err() {
+            return rpc_error(id, "proxy is shutting down");
+        }
+        let mut response = answer.await.unwrap_or_else(|_| rpc_error(id, "upstream batch was dropped"));
+        response["id"] = id.clone();
+        response
+    }
+
+    async fn handle_call(&self, call: Value) -> Value {
+        self.stats.calls.fetch_add(1, Ordering::Relaxed);
+        let id = call["id"].clone();
+        let method = call["method"].as_str().unwrap_or_default().to_string();
+        let params = call.get("params").cloned().unwrap_or(json!([]));
+        let hit = |result: Value| {
+            self.stats.cache_hits.fetch_add(1, Ordering::Relaxed);
+            json!({ "jsonrpc": "2.0", "id": id, "result": result })
+        };
+
+        match classify(&method, &params, self.contract) {
+            Cacheability::Never => self.upstream.call(&id, &method, &params).await,
+            Cacheability::Head => match self.head().await {
+                Ok(number) => json!({ "jsonrpc": "2.0", "id": id, "result": number }),
+                Err(error) => json!({ "jsonrpc": "2.0", "id": id, "error": error }),
+            },
+            Cacheability::Forever(key) => {
+                if let Some(result) = self.forever.lock().await.get(&key) {
+                    return hit(result.clone());
+                }
+                let response = self.batched(&id, &method, &params).await;
+                if let Some(result) = response.get("result") {
+                    let mut forever = self.forever.lock().await;
+                    if forever.len() >= MAX_CACHED {
+                        forever.clear();
+                    }
+                    forever.insert(key, result.clone());
+                }
+                response
+            }
+            Cacheability::UntilNextBlock(key) => {
+                let head = match self.head().await {
+                    Ok(head) => head,
+                    Err(_) => return self.batched(&id, &method, &params).await,
+                };
+                {
+                    let mut current = self.current.lock().await;
+                    if current.0 != head {
+                        *current = (head.clone(), HashMap::new());
+                    } else if let Some(result) = current.1.get(&key) {
+                        return hit(result.clone());
+                    }
+                }
+                let response = self.batched(&id, &method, &params).await;
+                if let Some(result) = response.get("result") {
+                    let mut current = self.current.lock().await;
+                    if current.0 == head {
+                        current.1.insert(key, result.clone());
+                    }
+                }
+                response
+            }
+        }
+    }
+
+    /// A JSON-RPC body: one call or a batch of them
+    async fn handle_body(self: &Arc<Self>, body: &[u8]) -> Value {
+        let request: Value = match serde_json::from_slice(body) {
+            Ok(request) => request,
+            Err(e) => return json!({ "jsonrpc": "2.0", "id": null, "error": { "code": -32700, "message": format!("Parse error: {}", e) } }),
+        };
+        match request {
+            Value::Array(calls) => {
+                let handles: Vec<_> = calls.into_iter()
+                    .map(|call| {
+                        let proxy = self.clone();
+                        tokio::spawn(async move { proxy.handle_call(call).await })
+                    })
+                    .collect();
+                let mut responses = Vec::with_capacity(handles.len());
+                for handle in handles {
+                    responses.push(handle.await.unwrap_or_else(|e| rpc_error(&Value::Null, e)));
+                }
+                Value::Array(responses)
+            }
+            call => self.handle_call(call).await,
+        }
+    }
+}
+
+async fn serve_connection(proxy: Arc<Proxy>, stream: TcpStream) -> Result<()> {
+    let mut reader = BufReader::new(stream);
+    while let Some(request) = http::read_request(&mut reader, MAX_BODY).await? {
+        let (status, body) = if request.method == "POST" {
+            ("200 OK", serde_json::to_vec(&proxy.handle_body(&request.body).await)?)
+        } else {
+            ("405 Method Not Allowed", b"JSON-RPC over POST only\n".to_vec())
+        };
+        http::write_response(reader.get_mut(), status, "application/json", &body, request.keep_alive).await?;
+        if !request.keep_alive {
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// `proxy`: serve a caching JSON-RPC endpoint in front of `rpc_url` until Ctrl-C
+pub async fn serve(rpc_url: &str, contract: &str, listen: SocketAddr) -> Result<()> {
+    let contract: Address = match contract.parse() {
+        Ok(address) => address,
+        Err(_) => bail_config!("Invalid CONTRACT_ADDRESS '{}'", contract),
+    };
+    let listener = match TcpListener::bind(listen).await {
+        Ok(listener) => listener,
+        Err(e) => bail_config!("Could not listen on {}: {}", listen, e),
+    };
+
+    let stats = Arc::new(Stats::default());
+    let upstream = Arc::new(Upstream { client: reqwest::Client::new(), url: rpc_url.to_string(), stats: stats.clone() });
+    let (batcher, queue) = mpsc::channel(MAX_BATCH * 4);
+    tokio::spawn(run_batcher(upstream.clone(), queue));
+    let proxy = Arc::new(Proxy {
+        upstream,
+        contract,
+        batcher,
+        head: Mutex::new(None),
+        forever: Mutex::new(HashMap::new()),
+        current: Mutex::new((Value::Null, HashMap::new())),
+        stats: stats.clone(),
+    });
+
+    say!("{} {} → {}", "🛰️  Proxying".cyan().bold(), format!("http://{}", listen).yellow(), rpc_url);
+    say!("Caching reads of {:?}. Point the CLI at it with `--rpc-url http://{}`; Ctrl-C to stop.", contract, listen);
+
+    loop {
+        tokio::select! {
+            accepted = listener.accept() => {
+                let (stream, _) = accepted?;
+                let proxy = proxy.clone();
+                tokio::spawn(async move {
+                    if let Err(e) = serve_connection(proxy, stream).await {
+                        eprintln!("{} {:#}", crate::output::render("⚠️  Proxy connection failed:".to_string()).yellow(), e);
+                    }
+                });
+            }
+            _ = tokio::signal::ctrl_c() => break,
+        }
+    }
+
+    let calls = stats.calls.load(Ordering::Relaxed);
+    let hits = stats.cache_hits.load(Ordering::Relaxed);
+    say!("\n{} {} calls served, {} from cache, {} upstream requests ({} calls sent in batches)",
+        "📊 Proxy summary:".cyan().bold(), calls, hits,
+        stats.upstream_requests.load(Ordering::Relaxed), stats.batched_calls.load(Ordering::Relaxed));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_contract_reads_and_chain_id_are_cached() {
+        let contract: Address = "0x5FbDB2315678afecb367f032d93F642f64180aa3".parse().unwrap();
+        let call = |to: &str, block: Value| json!([{ "to": to, "data": "0x1a8cbcaa" }, block]);
+        let ours = "0x5fbdb2315678afecb367f032d93f642f64180aa3";
+
+        assert!(matches!(classify("eth_call", &call(ours, json!("latest")), contract), Cacheability::UntilNextBlock(_)));
+        assert!(matches!(classify("eth_call", &call(ours, json!("0x10")), contract), Cacheability::Forever(_)));
+        assert_eq!(classify("eth_call", &call(ours, json!("pending")), contract), Cacheability::Never);
+        assert_eq!(classify("eth_call", &call("0x0000000000000000000000000000000000000001", json!("0x10")), contract), Cacheability::Never);
+        assert_eq!(classify("eth_chainId", &json!([]), contract), Cacheability::Forever("eth_chainId".to_string()));
+        assert_eq!(classify("eth_blockNumber", &json!([]), contract), Cacheability::Head);
+        assert_eq!(classify("eth_sendRawTransaction", &json!(["0x00"]), contract), Cacheability::Never);
+
+        // The same read at two blocks is two entries
+        assert_ne!(classify("eth_call", &call(ours, json!("0x10")), contract), classify("eth_call", &call(ours, json!("0x11")), contract));
+    }
+}
@@ -0,0 +1,126 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use futures_util::{SinkExt, StreamExt};
+use tokio::io::AsyncWriteExt;
+use tokio::net::TcpStream;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio_tungstenite::tungstenite::handshake::derive_accept_key;
+use tokio_tungstenite::tungstenite::protocol::Role;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::WebSocketStream;
+
+use crate::http;
+use crate::indexer::IndexedEvent;
+
+/// Events held for each subscriber before a slow one starts missing them
+pub const BUFFER: usize = 256;
+
+/// How often an idle SSE stream gets a comment line, so proxies keep it open and a client that
+/// left is noticed
+const KEEPALIVE: Duration = Duration::from_secs(15);
+
+/// The poll a subscriber asked for with `?poll_id=`, if any
+pub fn poll_filter(request: &http::Request) -> std::result::Result<Option<u64>, String> {
+    request.query.get("poll_id")
+        .map(|id| id.parse().map_err(|_| format!("'{}' is not a poll id", id)))
+        .transpose()
+}
+
+/// Whether a subscriber gets `event`: poll events only, and only its poll's when it chose one
+fn wanted(event: &IndexedEvent, poll_id: Option<u64>) -> bool {
+    match event.event.poll_id() {
+        Some(id) => poll_id.is_none_or(|wanted| wanted == id),
+        None => false,
+    }
+}
+
+/// One server-sent event: the event name, an ID of block and log index, and the event as JSON
+fn sse_frame(event: &IndexedEvent) -> Result<String> {
+    let json = serde_json::to_value(event)?;
+    let name = json["event"].as_str().unwrap_or("message").to_string();
+    Ok(format!("id: {}-{}\nevent: {}\ndata: {}\n\n", event.block_number, event.log_index, name, json))
+}
+
+/// The next event for a subscriber; `None` once the server stops. A subscriber too slow to keep
+/// up skips what it missed rather than holding up the others.
+async fn next_event(events: &mut broadcast::Receiver<IndexedEvent>, poll_id: Option<u64>) -> Option<IndexedEvent> {
+    loop {
+        match events.recv().await {
+            Ok(event) if wanted(&event, poll_id) => return Some(event),
+            Ok(_) | Err(RecvError::Lagged(_)) => continue,
+            Err(RecvError::Closed) => return None,
+        }
+    }
+}
+
+/// `GET /events` as `text/event-stream`, until the client disconnects
+pub async fn stream_sse(mut stream: TcpStream, mut events: broadcast::Receiver<IndexedEvent>, poll_id: Option<u64>) -> Result<()> {
+    stream.write_all(b"HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-cache\r\nConnection: keep-alive\r\nAccess-Control-Allow-Origin: *\r\n\r\n: connected\n\n").await?;
+    loop {
+        let frame = tokio::select! {
+            event = next_event(&mut events, poll_id) => match event {
+                Some(event) => sse_frame(&event)?,
+                None => return Ok(()),
+            },
+            _ = tokio::time::sleep(KEEPALIVE) => ": keepalive\n\n".to_string(),
+        };
+        // A write fails once the client has gone
+        if stream.write_all(frame.as_bytes()).await.is_err() {
+            return Ok(());
+        }
+    }
+}
+
+/// `GET /events` upgraded to a WebSocket: each event is a JSON text message. Anything the client
+/// sends is ignored; pings are answered.
+pub async fn stream_websocket(mut stream: TcpStream, key: &str, mut events: broadcast::Receiver<IndexedEvent>, poll_id: Option<u64>) -> Result<()> {
+    let accept = derive_accept_key(key.as_bytes());
+    stream.write_all(format!("HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Accept: {}\r\n\r\n", accept).as_bytes()).await?;
+    let mut socket = WebSocketStream::from_raw_socket(stream, Role::Server, None).await;
+    loop {
+        tokio::select! {
+            event = next_event(&mut events, poll_id) => {
+                let Some(event) = event else {
+                    let _ = socket.close(None).await;
+                    return Ok(());
+                };
+                if socket.send(Message::Text(serde_json::to_string(&event)?)).await.is_err() {
+                    return Ok(());
+                }
+            }
+            message = socket.next() => match message {
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => return Ok(()),
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indexer::ContractEvent;
+    use ethers::types::{Address, H256};
+
+    #[test]
+    fn frames_poll_events_for_their_subscribers() {
+        let at = |event| IndexedEvent {
+            block_number: 12,
+            block_hash: H256::zero(),
+            transaction_hash: H256::zero(),
+            log_index: 3,
+            timestamp: 1_700_000_000,
+            event,
+        };
+        let closed = at(ContractEvent::PollStatusChanged { poll_id: 2, status: 1 });
+        let frame = sse_frame(&closed).unwrap();
+        assert!(frame.starts_with("id: 12-3\nevent: poll_status_changed\ndata: {"), "{}", frame);
+        assert!(frame.ends_with("}\n\n"));
+
+        assert!(wanted(&closed, None));
+        assert!(wanted(&closed, Some(2)));
+        assert!(!wanted(&closed, Some(3)));
+        assert!(!wanted(&at(ContractEvent::DelegateSet { delegator: Address::zero(), delegate: Address::zero() }), None));
+    }
+}
@@ -0,0 +1,425 @@
+use std::cmp::Ordering;
+use std::str::FromStr;
+
+use anyhow::Result;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::duration;
+use crate::error::bail_user;
+use crate::fields::lookup;
+
+/// `--filter` and `--sort-by` for `list`, evaluated against the fields `list -f ndjson` prints
+#[derive(Debug, Clone, Default, clap::Args)]
+pub struct QueryArgs {
+    /// Only polls matching an expression, e.g. 'total_votes>100 && category==governance && ends_within(48h)'
+    #[arg(long)]
+    pub filter: Option<Filter>,
+    /// Sort by these fields, in order; a leading - sorts descending, e.g. end_time,-total_votes
+    #[arg(long, value_delimiter = ',', allow_hyphen_values = true)]
+    pub sort_by: Vec<SortKey>,
+}
+
+impl QueryArgs {
+    /// Whether `record` passes `--filter`; `now` is what `ends_within` and `created_within` count from
+    pub fn matches<T: Serialize>(&self, record: &T, now: u64) -> Result<bool> {
+        match &self.filter {
+            Some(filter) => filter.expr.eval(&serde_json::to_value(record)?, now),
+            None => Ok(true),
+        }
+    }
+
+    /// `records` in `--sort-by` order; records that tie keep their order
+    pub fn sort<T: Serialize>(&self, records: Vec<T>) -> Result<Vec<T>> {
+        if self.sort_by.is_empty() {
+            return Ok(records);
+        }
+        let mut keyed = Vec::with_capacity(records.len());
+        for record in records {
+            let value = serde_json::to_value(&record)?;
+            let keys = self.sort_by.iter().map(|key| lookup(&value, &key.path).cloned()).collect::<Result<Vec<_>>>()?;
+            keyed.push((keys, record));
+        }
+        keyed.sort_by(|(a, _), (b, _)| {
+            self.sort_by.iter().zip(a.iter().zip(b))
+                .map(|(key, (a, b))| if key.descending { order(a, b).reverse() } else { order(a, b) })
+                .find(|ordering| ordering.is_ne())
+                .unwrap_or(Ordering::Equal)
+        });
+        Ok(keyed.into_iter().map(|(_, record)| record).collect())
+    }
+}
+
+/// One `--sort-by` field
+#[derive(Debug, Clone)]
+pub struct SortKey {
+    path: String,
+    descending: bool,
+}
+
+impl FromStr for SortKey {
+    type Err = String;
+
+    fn from_str(value: &str) -> std::result::Result<Self, String> {
+        let value = value.trim();
+        let (path, descending) = match value.strip_prefix('-') {
+            Some(path) => (path, true),
+            None => (value.strip_prefix('+').unwrap_or(value), false),
+        };
+        if path.is_empty() {
+            return Err("sort field is empty".to_string());
+        }
+        Ok(Self { path: path.to_string(), descending })
+    }
+}
+
+/// Numbers by value, text ignoring case, lists by length
+fn order(a: &Value, b: &Value) -> Ordering {
+    match (a, b) {
+        (Value::Number(a), Value::Number(b)) => a.as_f64().unwrap_or_default().total_cmp(&b.as_f64().unwrap_or_default()),
+        (Value::String(a), Value::String(b)) => a.to_lowercase().cmp(&b.to_lowercase()),
+        (Value::Bool(a), Value::Bool(b)) => a.cmp(b),
+        (Value::Array(a), Value::Array(b)) => a.len().cmp(&b.len()),
+        _ => a.to_string().cmp(&b.to_string()),
+    }
+}
+
+/// A parsed `--filter` expression
+#[derive(Debug, Clone)]
+pub struct Filter {
+    expr: Expr,
+}
+
+impl FromStr for Filter {
+    type Err = String;
+
+    fn from_str(value: &str) -> std::result::Result<Self, String> {
+        let mut parser = Parser { tokens: tokenize(value)?, pos: 0, depth: 0 };
+        let expr = parser.or()?;
+        match parser.tokens.get(parser.pos) {
+            Some(token) => Err(format!("unexpected {} in filter", token)),
+            None => Ok(Self { expr }),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Op {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    /// `~=`: text contains, ignoring case
+    Contains,
+}
+
+#[derive(Debug, Clone)]
+enum Expr {
+    Or(Box<Expr>, Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+    Compare { path: String, op: Op, literal: String },
+    /// A bare field: true unless false, zero, empty or null
+    Truthy(String),
+    /// Ends between now and this many seconds from now
+    EndsWithin(u64),
+    /// Created at most this many seconds ago
+    CreatedWithin(u64),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word(String),
+    Text(String),
+    Op(Op),
+    And,
+    Or,
+    Not,
+    Open,
+    Close,
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Token::Word(word) => write!(f, "`{}`", word),
+            Token::Text(text) => write!(f, "\"{}\"", text),
+            Token::Op(_) => write!(f, "comparison"),
+            Token::And => write!(f, "`&&`"),
+            Token::Or => write!(f, "`||`"),
+            Token::Not => write!(f, "`!`"),
+            Token::Open => write!(f, "`(`"),
+            Token::Close => write!(f, "`)`"),
+        }
+    }
+}
+
+fn tokenize(input: &str) -> std::result::Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+    while let Some(c) = chars.next() {
+        let next = chars.peek().copied();
+        let token = match (c, next) {
+            (c, _) if c.is_whitespace() => continue,
+            ('(', _) => Token::Open,
+            (')', _) => Token::Close,
+            ('&', Some('&')) => { chars.next(); Token::And }
+            ('|', Some('|')) => { chars.next(); Token::Or }
+            ('=', Some('=')) => { chars.next(); Token::Op(Op::Eq) }
+            ('=', _) => Token::Op(Op::Eq),
+            ('!', Some('=')) => { chars.next(); Token::Op(Op::Ne) }
+            ('!', _) => Token::Not,
+            ('<', Some('=')) => { chars.next(); Token::Op(Op::Le) }
+            ('<', _) => Token::Op(Op::Lt),
+            ('>', Some('=')) => { chars.next(); Token::Op(Op::Ge) }
+            ('>', _) => Token::Op(Op::Gt),
+            ('~', Some('=')) => { chars.next(); Token::Op(Op::Contains) }
+            ('"' | '\'', _) => {
+                let mut text = String::new();
+                loop {
+                    match chars.next() {
+                        Some(end) if end == c => break,
+                        Some(ch) => text.push(ch),
+                        None => return Err(format!("unclosed {} in filter", c)),
+                    }
+                }
+                Token::Text(text)
+            }
+            (c, _) if c.is_alphanumeric() || matches!(c, '_' | '.' | '-' | ':') => {
+                let mut word = c.to_string();
+                while let Some(&ch) = chars.peek() {
+                    if !(ch.is_alphanumeric() || matches!(ch, '_' | '.' | '-' | ':')) {
+                        break;
+                    }
+                    word.push(ch);
+                    chars.next();
+                }
+                Token::Word(word)
+            }
+            (c, _) => return Err(format!("unexpected `{}` in filter", c)),
+        };
+        tokens.push(token);
+    }
+    Ok(tokens)
+}
+
+/// Deepest nesting of `!` and `(` a filter may use, so a hostile one can't overflow the stack
+const MAX_DEPTH: usize = 64;
+
+/// Recursive descent: `||` binds loosest, then `&&`, then `!`
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    /// `!` and `(` currently open around the token at `pos`
+    depth: usize,
+}
+
+impl Parser {
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn eat(&mut self, token: &Token) -> bool {
+        let found = self.tokens.get(self.pos) == Some(token);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn or(&mut self) -> std::result::Result<Expr, String> {
+        let mut left = self.and()?;
+        while self.eat(&Token::Or) {
+            left = Expr::Or(Box::new(left), Box::new(self.and()?));
+        }
+        Ok(left)
+    }
+
+    fn and(&mut self) -> std::result::Result<Expr, String> {
+        let mut left = self.unary()?;
+        while self.eat(&Token::And) {
+            left = Expr::And(Box::new(left), Box::new(self.unary()?));
+        }
+        Ok(left)
+    }
+
+    /// Parse a nested expression one level deeper, refusing past MAX_DEPTH
+    fn nested<T>(&mut self, parse: impl FnOnce(&mut Self) -> std::result::Result<T, String>) -> std::result::Result<T, String> {
+        if self.depth >= MAX_DEPTH {
+            return Err("filter is nested too deeply".to_string());
+        }
+        self.depth += 1;
+        let parsed = parse(self);
+        self.depth -= 1;
+        parsed
+    }
+
+    fn unary(&mut self) -> std::result::Result<Expr, String> {
+        match self.next() {
+            Some(Token::Not) => Ok(Expr::Not(Box::new(self.nested(Self::unary)?))),
+            Some(Token::Open) => {
+                let expr = self.nested(Self::or)?;
+                if !self.eat(&Token::Close) {
+                    return Err("missing `)` in filter".to_string());
+                }
+                Ok(expr)
+            }
+            Some(Token::Word(word)) if self.eat(&Token::Open) => {
+                let argument = match self.next() {
+                    Some(Token::Word(argument) | Token::Text(argument)) => argument,
+                    _ => return Err(format!("{}() takes a duration, e.g. {}(48h)", word, word)),
+                };
+                if !self.eat(&Token::Close) {
+                    return Err(format!("missing `)` after {}({}", word, argument));
+                }
+                let seconds = duration::parse(&argument)?;
+                match word.as_str() {
+                    "ends_within" => Ok(Expr::EndsWithin(seconds)),
+                    "created_within" => Ok(Expr::CreatedWithin(seconds)),
+                    _ => Err(format!("unknown function `{}` (use ends_within or created_within)", word)),
+                }
+            }
+            Some(Token::Word(path)) => match self.tokens.get(self.pos).cloned() {
+                Some(Token::Op(op)) => {
+                    self.pos += 1;
+                    match self.next() {
+                        Some(Token::Word(literal) | Token::Text(literal)) => Ok(Expr::Compare { path, op, literal }),
+                        _ => Err(format!("`{}` is compared with nothing", path)),
+                    }
+                }
+                _ => Ok(Expr::Truthy(path)),
+            },
+            Some(token) => Err(format!("expected a field, `!` or `(` but found {}", token)),
+            None => Err("filter ends early".to_string()),
+        }
+    }
+}
+
+impl Expr {
+    fn eval(&self, record: &Value, now: u64) -> Result<bool> {
+        Ok(match self {
+            Expr::Or(left, right) => left.eval(record, now)? || right.eval(record, now)?,
+            Expr::And(left, right) => left.eval(record, now)? && right.eval(record, now)?,
+            Expr::Not(inner) => !inner.eval(record, now)?,
+            Expr::Compare { path, op, literal } => compare(lookup(record, path)?, *op, literal, path)?,
+            Expr::Truthy(path) => match lookup(record, path)? {
+                Value::Null => false,
+                Value::Bool(b) => *b,
+                Value::Number(n) => n.as_f64() != Some(0.0),
+                Value::String(s) => !s.is_empty(),
+                Value::Array(items) => !items.is_empty(),
+                Value::Object(map) => !map.is_empty(),
+            },
+            Expr::EndsWithin(seconds) => {
+                let end_time = lookup(record, "end_time")?.as_u64().unwrap_or_default();
+                end_time >= now && end_time - now <= *seconds
+            }
+            Expr::CreatedWithin(seconds) => {
+                let created_at = lookup(record, "created_at")?.as_u64().unwrap_or_default();
+                created_at.saturating_add(*seconds) >= now
+            }
+        })
+    }
+}
+
+fn ordered(op: Op, ordering: Ordering) -> bool {
+    match op {
+        Op::Eq => ordering.is_eq(),
+        Op::Ne => ordering.is_ne(),
+        Op::Lt => ordering.is_lt(),
+        Op::Le => ordering.is_le(),
+        Op::Gt => ordering.is_gt(),
+        Op::Ge => ordering.is_ge(),
+        Op::Contains => false,
+    }
+}
+
+/// `field op literal`, with the literal read as the field's type. Lists compare by membership.
+fn compare(value: &Value, op: Op, literal: &str, path: &str) -> Result<bool> {
+    Ok(match value {
+        Value::Array(items) => {
+            let mut any = false;
+            for item in items {
+                any |= compare(item, if op == Op::Ne { Op::Eq } else { op }, literal, path)?;
+            }
+            match op {
+                Op::Eq | Op::Contains => any,
+                Op::Ne => !any,
+                _ => bail_user!("`{}` is a list; compare it with ==, != or ~=", path),
+            }
+        }
+        Value::Number(number) => {
+            let Ok(literal) = literal.parse::<f64>() else {
+                bail_user!("`{}` is a number, so `{}` can't be compared with it", path, literal);
+            };
+            if op == Op::Contains {
+                bail_user!("`{}` is a number; ~= only works on text and lists", path);
+            }
+            ordered(op, number.as_f64().unwrap_or_default().total_cmp(&literal))
+        }
+        Value::Bool(b) => {
+            let Ok(literal) = literal.parse::<bool>() else {
+                bail_user!("`{}` is true or false, not `{}`", path, literal);
+            };
+            match op {
+                Op::Eq => *b == literal,
+                Op::Ne => *b != literal,
+                _ => bail_user!("`{}` is true or false; compare it with == or !=", path),
+            }
+        }
+        Value::String(text) => match op {
+            Op::Contains => text.to_lowercase().contains(&literal.to_lowercase()),
+            _ => ordered(op, text.to_lowercase().cmp(&literal.to_lowercase())),
+        },
+        Value::Null => match op {
+            Op::Eq => literal == "null",
+            Op::Ne => literal != "null",
+            _ => false,
+        },
+        Value::Object(_) => bail_user!("`{}` has fields of its own; compare one of them, e.g. {}.<field>", path, path),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filters_and_sorts_records() {
+        let polls = vec![
+            serde_json::json!({ "id": 0, "category": "Governance", "total_votes": 150, "end_time": 1_000_100, "created_at": 10, "tags": ["treasury"] }),
+            serde_json::json!({ "id": 1, "category": "Finance", "total_votes": 300, "end_time": 1_000_100, "created_at": 999_000, "tags": [] }),
+            serde_json::json!({ "id": 2, "category": "Governance", "total_votes": 40, "end_time": 1_500_000, "created_at": 10, "tags": ["Treasury", "q3"] }),
+        ];
+        let now = 1_000_000;
+        let ids = |filter: &str| {
+            let query = QueryArgs { filter: Some(filter.parse().unwrap()), sort_by: vec![] };
+            polls.iter().filter(|poll| query.matches(poll, now).unwrap()).map(|poll| poll["id"].as_u64().unwrap()).collect::<Vec<_>>()
+        };
+        assert_eq!(ids("total_votes>100 && category==governance && ends_within(48h)"), vec![0]);
+        assert_eq!(ids("tags==treasury && !(id=0)"), vec![2]);
+        assert_eq!(ids("created_within(1d) || total_votes <= 40"), vec![1, 2]);
+        assert_eq!(ids("category ~= 'GOV' && tags"), vec![0, 2]);
+
+        for bad in ["total_votes >", "ends_within(soon)", "(id==1", "votes_cast(1h)", "id == 1 1"] {
+            assert!(bad.parse::<Filter>().is_err(), "{}", bad);
+        }
+        let deep = |opener: &str, closer: &str, depth: usize| format!("{}id==1{}", opener.repeat(depth), closer.repeat(depth));
+        assert!(deep("(", ")", 64).parse::<Filter>().is_ok());
+        assert!(deep("!", "", 64).parse::<Filter>().is_ok());
+        assert_eq!(deep("(", ")", 65).parse::<Filter>().err().as_deref(), Some("filter is nested too deeply"));
+        assert_eq!(deep("!", "", 100_000).parse::<Filter>().err().as_deref(), Some("filter is nested too deeply"));
+        let wrong_type = QueryArgs { filter: Some("total_votes==many".parse().unwrap()), sort_by: vec![] };
+        assert!(wrong_type.matches(&polls[0], now).is_err());
+
+        let sorted = QueryArgs { filter: None, sort_by: vec!["end_time".parse().unwrap(), "-total_votes".parse().unwrap()] };
+        let order: Vec<u64> = sorted.sort(polls.clone()).unwrap().iter().map(|poll| poll["id"].as_u64().unwrap()).collect();
+        assert_eq!(order, vec![1, 0, 2]);
+        let unknown = QueryArgs { filter: None, sort_by: vec!["votes".parse().unwrap()] };
+        assert!(unknown.sort(polls).is_err());
+    }
+}
@@ -0,0 +1,135 @@
+use std::fmt;
+
+use colored::*;
+use serde::{Deserialize, Serialize};
+
+pub use polling_analytics::stats::quorum_reached as reached;
+
+/// Width of the progress bar drawn by `Quorum::bar`
+const BAR_WIDTH: usize = 20;
+
+/// Whether a poll has met, or looks set to meet, its `minParticipation`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QuorumState {
+    /// The poll sets no minimum participation
+    NotRequired,
+    Reached,
+    /// Still open and the current vote rate reaches quorum before the end
+    OnTrack,
+    /// Still open but the current vote rate falls short by the end
+    AtRisk,
+    /// Ended without reaching quorum
+    Failed,
+}
+
+impl fmt::Display for QuorumState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            QuorumState::NotRequired => "not required",
+            QuorumState::Reached => "reached",
+            QuorumState::OnTrack => "on track",
+            QuorumState::AtRisk => "at risk",
+            QuorumState::Failed => "failed",
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Quorum {
+    pub min_participation: u64,
+    pub votes: u64,
+    /// Votes as a percentage of `min_participation`, capped at 100
+    pub progress: f64,
+    /// Votes expected by the end time if the average rate so far continues
+    pub projected_votes: u64,
+    pub state: QuorumState,
+}
+
+impl Quorum {
+    /// Assess quorum for a poll created at `created_at` that ends at `end_time`;
+    /// `open` is false once the poll is closed on-chain
+    pub fn assess(votes: u64, min_participation: u64, created_at: u64, end_time: u64, open: bool, now: u64) -> Self {
+        let open = open && now < end_time;
+        let projected_votes = if open {
+            // At least a minute elapsed, so a poll voted on in its first seconds doesn't project wildly
+            let elapsed = now.saturating_sub(created_at).max(60) as f64;
+            let rate = votes as f64 / elapsed;
+            votes.saturating_add((rate * (end_time - now) as f64).round() as u64)
+        } else {
+            votes
+        };
+
+        let state = match reached(votes, min_participation) {
+            None => QuorumState::NotRequired,
+            Some(true) => QuorumState::Reached,
+            Some(false) if !open => QuorumState::Failed,
+            Some(false) if projected_votes >= min_participation => QuorumState::OnTrack,
+            Some(false) => QuorumState::AtRisk,
+        };
+        let progress = if min_participation > 0 {
+            (votes as f64 / min_participation as f64 * 100.0).min(100.0)
+        } else {
+            100.0
+        };
+
+        Self { min_participation, votes, progress, projected_votes, state }
+    }
+
+    /// Progress bar toward quorum, e.g. `[████████░░░░░░░░░░░░]`
+    pub fn bar(&self) -> String {
+        let filled = ((self.progress / 100.0) * BAR_WIDTH as f64).round() as usize;
+        format!("[{}{}]", "█".repeat(filled), "░".repeat(BAR_WIDTH - filled))
+    }
+
+    /// One-line summary with the bar, counts and state, or `None` when no quorum is set
+    pub fn summary(&self) -> Option<String> {
+        if self.state == QuorumState::NotRequired {
+            return None;
+        }
+        let state = match self.state {
+            QuorumState::Reached | QuorumState::OnTrack => self.state.to_string().green(),
+            QuorumState::AtRisk => format!("at risk: projected {} by the end", self.projected_votes).yellow().bold(),
+            _ => self.state.to_string().red(),
+        };
+        Some(format!("{} {} / {} votes ({:.0}%), {}",
+            self.bar(), self.votes, self.min_participation, self.progress, state))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOUR: u64 = 3_600;
+
+    #[test]
+    fn quorum_states() {
+        // 10 votes in the first 10 hours, 10 hours to go: projects 20
+        let on_track = Quorum::assess(10, 20, 0, 20 * HOUR, true, 10 * HOUR);
+        assert_eq!(on_track.state, QuorumState::OnTrack);
+        assert_eq!(on_track.projected_votes, 20);
+        assert_eq!(on_track.progress, 50.0);
+
+        assert_eq!(Quorum::assess(10, 25, 0, 20 * HOUR, true, 10 * HOUR).state, QuorumState::AtRisk);
+        assert_eq!(Quorum::assess(25, 25, 0, 20 * HOUR, true, 10 * HOUR).state, QuorumState::Reached);
+        assert_eq!(Quorum::assess(10, 25, 0, 20 * HOUR, true, 30 * HOUR).state, QuorumState::Failed);
+        assert_eq!(Quorum::assess(10, 25, 0, 20 * HOUR, false, 10 * HOUR).state, QuorumState::Failed);
+        assert_eq!(Quorum::assess(10, 0, 0, 20 * HOUR, true, 10 * HOUR).state, QuorumState::NotRequired);
+    }
+
+    #[test]
+    fn bar_fills_with_progress() {
+        let quorum = Quorum::assess(30, 20, 0, HOUR, false, HOUR);
+        assert_eq!(quorum.progress, 100.0);
+        assert_eq!(quorum.bar(), format!("[{}]", "█".repeat(BAR_WIDTH)));
+        assert_eq!(Quorum::assess(5, 20, 0, HOUR, false, HOUR).bar(), format!("[{}{}]", "█".repeat(5), "░".repeat(15)));
+    }
+
+    #[test]
+    fn reached_only_applies_when_set() {
+        assert_eq!(reached(0, 0), None);
+        assert_eq!(reached(4, 5), Some(false));
+        assert_eq!(reached(5, 5), Some(true));
+    }
+}
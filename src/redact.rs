@@ -0,0 +1,92 @@
+use std::sync::Mutex;
+
+use ethers::signers::coins_bip39::{English, Wordlist};
+
+/// Shortest run of BIP-39 words treated as a mnemonic
+const MNEMONIC_WORDS: usize = 12;
+
+const REDACTED: &str = "[REDACTED]";
+
+/// Secrets read this run (private keys, keystore passwords), lowercased and without `0x`
+static SECRETS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// Remember a secret so it is scrubbed from errors and the audit log
+pub fn register(secret: &str) {
+    let secret = secret.trim().trim_start_matches("0x").to_lowercase();
+    // Very short strings would redact ordinary words; keys and real passwords are longer
+    if secret.len() < 8 {
+        return;
+    }
+    if let Ok(mut secrets) = SECRETS.lock() {
+        if !secrets.contains(&secret) {
+            secrets.push(secret);
+        }
+    }
+}
+
+fn is_bip39_word(token: &str) -> bool {
+    let word = token.trim_matches(|c: char| !c.is_ascii_alphabetic()).to_lowercase();
+    !word.is_empty() && English::get_all().binary_search(&word.as_str()).is_ok()
+}
+
+/// Replace runs of 12 or more BIP-39 words, which are almost certainly a mnemonic
+fn redact_mnemonics(text: &str) -> String {
+    let tokens: Vec<&str> = text.split(' ').collect();
+    let mut out: Vec<&str> = Vec::with_capacity(tokens.len());
+    let mut i = 0;
+    while i < tokens.len() {
+        let run = tokens[i..].iter().take_while(|token| is_bip39_word(token)).count();
+        if run >= MNEMONIC_WORDS {
+            out.push("[REDACTED MNEMONIC]");
+            i += run;
+        } else {
+            out.push(tokens[i]);
+            i += 1;
+        }
+    }
+    out.join(" ")
+}
+
+/// `text` with registered secrets and anything that looks like a mnemonic replaced
+pub fn redact(text: &str) -> String {
+    let mut text = text.to_string();
+    if let Ok(secrets) = SECRETS.lock() {
+        for secret in secrets.iter() {
+            let lower = text.to_lowercase();
+            // Lowercasing ASCII keeps byte offsets, so matches map back onto `text`
+            if lower.len() != text.len() {
+                text = text.replace(secret.as_str(), REDACTED);
+                continue;
+            }
+            let mut scrubbed = String::with_capacity(text.len());
+            let mut last = 0;
+            for (start, _) in lower.match_indices(secret.as_str()) {
+                scrubbed.push_str(&text[last..start]);
+                scrubbed.push_str(REDACTED);
+                last = start + secret.len();
+            }
+            scrubbed.push_str(&text[last..]);
+            text = scrubbed;
+        }
+    }
+    text.lines().map(redact_mnemonics).collect::<Vec<_>>().join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scrubs_registered_keys_and_mnemonics() {
+        let key = "0xAC0974BEC39A17E36BA4A6B4D238FF944BACB478CBED5EFCAE784D7BF4F2FF80";
+        register(key);
+        assert_eq!(redact(&format!("Invalid PRIVATE_KEY {}!", key)), "Invalid PRIVATE_KEY 0x[REDACTED]!");
+        assert_eq!(redact(&key[2..].to_lowercase()), REDACTED);
+
+        let phrase = "test test test test test test test test test test test junk";
+        assert_eq!(redact(&format!("mnemonic: {} (imported)", phrase)), "mnemonic: [REDACTED MNEMONIC] (imported)");
+        // Ordinary sentences use some list words, but not twelve in a row
+        let sentence = "Vote on the budget before the deadline closes next week please";
+        assert_eq!(redact(sentence), sentence);
+    }
+}
@@ -0,0 +1,228 @@
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use ethers::prelude::*;
+use ethers::types::transaction::eip2718::TypedTransaction;
+use ethers::utils::keccak256;
+use serde_json::{json, Value};
+
+use crate::error::{bail_config, bail_user, CliError};
+use crate::{audit, redact, PollManager};
+
+/// A relayer that pays gas for members' transactions. The member still signs, so the
+/// contract sees their address; the relayer funds the sender and broadcasts the signed transaction.
+#[derive(Debug, Clone)]
+pub struct Sponsor {
+    url: String,
+    api_key: Option<String>,
+}
+
+/// A private transaction relay such as Flashbots Protect, which keeps transactions out of
+/// the public mempool until they are included
+#[derive(Debug, Clone)]
+pub struct PrivateRelay {
+    url: String,
+    /// Where to ask whether a transaction was included, failed or is still pending
+    status_url: Option<String>,
+}
+
+/// How write transactions leave this machine
+#[derive(Debug, Clone)]
+pub enum Route {
+    /// Straight to the RPC endpoint, paid by the signer
+    Public,
+    /// Through the relayer at SPONSOR_URL (`--sponsor`)
+    Sponsored(Sponsor),
+    /// Through a private relay (`--private-tx`)
+    Private(PrivateRelay),
+}
+
+/// Flashbots Protect RPC and status API per chain
+const FLASHBOTS: [(u64, &str, &str); 3] = [
+    (1, "https://rpc.flashbots.net", "https://protect.flashbots.net/tx/"),
+    (11155111, "https://rpc-sepolia.flashbots.net", "https://protect-sepolia.flashbots.net/tx/"),
+    (17000, "https://rpc-holesky.flashbots.net", "https://protect-holesky.flashbots.net/tx/"),
+];
+
+/// Flashbots Protect retries for 25 blocks before giving up; allow a little longer
+const PRIVATE_TIMEOUT: Duration = Duration::from_secs(360);
+const STATUS_INTERVAL: Duration = Duration::from_secs(4);
+
+static ROUTE: OnceLock<Route> = OnceLock::new();
+
+/// Pick the route for this run's transactions on `chain_id`
+pub fn init(sponsor: bool, private_tx: bool, chain_id: u64) -> Result<()> {
+    let route = if private_tx {
+        let flashbots = FLASHBOTS.iter().find(|(chain, _, _)| *chain == chain_id);
+        match (std::env::var("PRIVATE_TX_URL").ok().filter(|url| !url.is_empty()), flashbots) {
+            (Some(url), _) => Route::Private(PrivateRelay { url, status_url: None }),
+            (None, Some((_, url, status_url))) => Route::Private(PrivateRelay { url: url.to_string(), status_url: Some(status_url.to_string()) }),
+            (None, None) => bail_config!("No private relay known for chain {}; set PRIVATE_TX_URL to one", chain_id),
+        }
+    } else if sponsor {
+        let Some(url) = std::env::var("SPONSOR_URL").ok().filter(|url| !url.is_empty()) else {
+            bail_config!("--sponsor needs a relayer; set SPONSOR_URL to its endpoint");
+        };
+        let api_key = std::env::var("SPONSOR_API_KEY").ok().filter(|key| !key.is_empty());
+        if let Some(key) = &api_key {
+            redact::register(key);
+        }
+        Route::Sponsored(Sponsor { url, api_key })
+    } else {
+        Route::Public
+    };
+    let _ = ROUTE.set(route);
+    Ok(())
+}
+
+/// Whether `--sponsor` or `--private-tx` is routing this run's transactions
+pub fn rerouted() -> bool {
+    !matches!(ROUTE.get(), None | Some(Route::Public))
+}
+
+/// The hash a relayer reports for a relayed transaction; `fallback` when it reports none
+fn relayed_hash(response: &Value, fallback: H256) -> Result<H256> {
+    match response.get("transactionHash").or_else(|| response.get("hash")) {
+        None | Some(Value::Null) => Ok(fallback),
+        Some(hash) => serde_json::from_value(hash.clone())
+            .map_err(|_| CliError::Rpc(format!("Relayer returned an invalid transaction hash: {}", hash)).into()),
+    }
+}
+
+/// What a private relay reports about a transaction it holds
+#[derive(Debug, PartialEq)]
+enum PrivateStatus {
+    Pending,
+    Included,
+    Failed(String),
+}
+
+fn private_status(response: &Value) -> PrivateStatus {
+    match response.get("status").and_then(Value::as_str).unwrap_or("UNKNOWN") {
+        "INCLUDED" => PrivateStatus::Included,
+        status @ ("FAILED" | "CANCELLED") => PrivateStatus::Failed(status.to_lowercase()),
+        _ => PrivateStatus::Pending,
+    }
+}
+
+impl PrivateRelay {
+    async fn send(&self, raw: Bytes) -> Result<H256> {
+        let relay = Provider::<Http>::try_from(self.url.as_str())
+            .map_err(|e| crate::error::CliError::Config(format!("Invalid PRIVATE_TX_URL {}: {}", self.url, e)))?;
+        let pending = relay.send_raw_transaction(raw).await
+            .map_err(|e| CliError::Rpc(format!("Private relay rejected the transaction: {}", e)))?;
+        Ok(pending.tx_hash())
+    }
+
+    /// Wait until `hash` is mined. The public node cannot see it until then, so its own
+    /// pending-transaction tracking would report it dropped.
+    async fn wait_for_inclusion<M: Middleware>(&self, client: &M, hash: H256) -> Result<()> {
+        let started = Instant::now();
+        loop {
+            if client.get_transaction_receipt(hash).await.ok().flatten().is_some() {
+                return Ok(());
+            }
+            if let Some(status_url) = &self.status_url {
+                let response = reqwest::get(format!("{}{:?}", status_url, hash)).await;
+                if let Ok(response) = response {
+                    if let PrivateStatus::Failed(status) = private_status(&response.json().await.unwrap_or(Value::Null)) {
+                        return Err(CliError::Rpc(format!("Private relay {} transaction {:?}; nothing was sent publicly", status, hash)).into());
+                    }
+                }
+            }
+            if started.elapsed() >= PRIVATE_TIMEOUT {
+                return Err(CliError::Rpc(format!("Transaction {:?} was not included within {}s; the private relay may still include it",
+                    hash, PRIVATE_TIMEOUT.as_secs())).into());
+            }
+            tokio::time::sleep(STATUS_INTERVAL).await;
+        }
+    }
+}
+
+impl Sponsor {
+    /// Hand a signed transaction to the relayer, which tops up `from` with gas and broadcasts it
+    async fn relay(&self, chain_id: u64, from: Address, tx: &TypedTransaction, raw: Bytes) -> Result<H256> {
+        let body = json!({
+            "chainId": chain_id,
+            "from": from,
+            "to": tx.to_addr(),
+            "data": tx.data(),
+            "gas": tx.gas(),
+            "signedTransaction": raw,
+        });
+        let mut request = reqwest::Client::new().post(&self.url).json(&body);
+        if let Some(key) = &self.api_key {
+            request = request.bearer_auth(key);
+        }
+        let response = request.send().await.map_err(|e| CliError::Rpc(format!("Relayer unreachable: {}", e)))?;
+        let status = response.status();
+        if !status.is_success() {
+            let text = response.text().await.unwrap_or_default();
+            return Err(CliError::Rpc(format!("Relayer refused to sponsor the transaction ({}): {}", status, text.trim())).into());
+        }
+        let response: Value = response.json().await.unwrap_or(Value::Null);
+        relayed_hash(&response, H256(keccak256(&raw)))
+    }
+}
+
+impl<M: Middleware + 'static> PollManager<M> {
+    /// Fill and sign `call` locally, for routes that broadcast it somewhere other than the RPC endpoint
+    async fn sign_call<D: ethers::abi::Detokenize>(&self, call: &ContractCall<M, D>, flag: &str) -> Result<(TypedTransaction, Bytes)> {
+        if self.sender != self.wallet.address() {
+            bail_user!("{} cannot be combined with --impersonate; it needs a transaction signed by the key", flag);
+        }
+        let mut tx = call.tx.clone();
+        tx.set_from(self.wallet.address());
+        tx.set_chain_id(self.wallet.chain_id());
+        self.client.fill_transaction(&mut tx, None).await?;
+        let signature = self.wallet.sign_transaction(&tx).await?;
+        let raw = tx.rlp_signed(&signature);
+        Ok((tx, raw))
+    }
+
+    /// Guard, sign and send a contract call along this run's route, recording it for the audit log
+    pub(crate) async fn send_call<'a, D: ethers::abi::Detokenize>(&'a self, call: &'a ContractCall<M, D>) -> Result<PendingTransaction<'a, M::Provider>> {
+        self.guard_send(call).await?;
+        let tx = match ROUTE.get().unwrap_or(&Route::Public) {
+            Route::Public => call.send().await?,
+            Route::Sponsored(sponsor) => {
+                let (tx, raw) = self.sign_call(call, "--sponsor").await?;
+                let hash = sponsor.relay(self.wallet.chain_id(), self.wallet.address(), &tx, raw).await?;
+                PendingTransaction::new(hash, self.client.provider())
+            }
+            Route::Private(relay) => {
+                let (_, raw) = self.sign_call(call, "--private-tx").await?;
+                let hash = relay.send(raw).await?;
+                audit::record_tx(hash);
+                relay.wait_for_inclusion(self.client.as_ref(), hash).await?;
+                return Ok(PendingTransaction::new(hash, self.client.provider()));
+            }
+        };
+        audit::record_tx(tx.tx_hash());
+        Ok(tx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn relayer_hashes_fall_back_to_the_signed_transaction() {
+        let signed = H256::repeat_byte(0xaa);
+        let reported = H256::repeat_byte(0xbb);
+        assert_eq!(relayed_hash(&json!({ "transactionHash": reported }), signed).unwrap(), reported);
+        assert_eq!(relayed_hash(&json!({ "hash": reported }), signed).unwrap(), reported);
+        assert_eq!(relayed_hash(&json!({ "status": "queued" }), signed).unwrap(), signed);
+        assert!(relayed_hash(&json!({ "transactionHash": "pending" }), signed).is_err());
+    }
+
+    #[test]
+    fn private_relay_statuses() {
+        assert_eq!(private_status(&json!({ "status": "INCLUDED" })), PrivateStatus::Included);
+        assert_eq!(private_status(&json!({ "status": "CANCELLED" })), PrivateStatus::Failed("cancelled".to_string()));
+        assert_eq!(private_status(&json!({ "status": "UNKNOWN" })), PrivateStatus::Pending);
+        assert_eq!(private_status(&Value::Null), PrivateStatus::Pending);
+    }
+}
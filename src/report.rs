@@ -0,0 +1,289 @@
+use std::collections::HashSet;
+
+use anyhow::{Context, Result};
+use ethers::prelude::*;
+use printpdf::{BuiltinFont, Color, IndirectFontRef, Mm, PdfDocument, PdfDocumentReference, PdfLayerReference, Rect, Rgb};
+
+use crate::certify::CertifiedResults;
+use crate::chart::PALETTE;
+use crate::numbers::{self, SaturatingU64};
+use crate::preview::wrap;
+use crate::tally::PollTally;
+use crate::timezone::{self, DisplayZone};
+use crate::{u256_to_f64, u8_to_poll_type, PollManager};
+
+/// A4, in millimetres
+const PAGE_WIDTH: f32 = 210.0;
+const PAGE_HEIGHT: f32 = 297.0;
+const MARGIN: f32 = 20.0;
+/// Where values start in label/value rows
+const VALUE_X: f32 = MARGIN + 48.0;
+const BAR_WIDTH: f32 = 80.0;
+const PT_TO_MM: f32 = 0.3528;
+
+impl CertifiedResults {
+    /// Tally the snapshot, counting votes from its VoteCast events for weighted polls
+    pub fn tally(&self) -> PollTally {
+        let weighted = self.poll_type != u8_to_poll_type(0);
+        let counts: Vec<u64> = if weighted {
+            let mut counts = vec![0u64; self.options.len()];
+            for vote in &self.votes {
+                if let Some(count) = counts.get_mut(vote.option_index as usize) {
+                    *count += 1;
+                }
+            }
+            counts
+        } else {
+            self.results.iter().map(|votes| votes.to_u64_saturating()).collect()
+        };
+        PollTally::new(&self.options, &self.results, &counts, self.total_votes.to_u64_saturating(), self.total_weight, weighted)
+    }
+}
+
+/// `#4e79a7` as a PDF fill color
+fn rgb(hex: &str) -> Color {
+    let channel = |i: usize| u8::from_str_radix(hex.get(i..i + 2).unwrap_or("00"), 16).unwrap_or(0) as f32 / 255.0;
+    Color::Rgb(Rgb::new(channel(1), channel(3), channel(5), None))
+}
+
+/// Lays text out top to bottom, starting a new page when one fills up
+struct Writer {
+    doc: PdfDocumentReference,
+    layer: PdfLayerReference,
+    regular: IndirectFontRef,
+    bold: IndirectFontRef,
+    mono: IndirectFontRef,
+    /// Baseline of the next line, from the bottom of the page
+    y: f32,
+}
+
+impl Writer {
+    fn new(title: &str) -> Result<Self> {
+        let (doc, page, layer) = PdfDocument::new(title, Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Report");
+        let doc = doc.with_creator("polling-cli").with_producer("polling-cli");
+        let layer = doc.get_page(page).get_layer(layer);
+        let font = |font| doc.add_builtin_font(font).context("Failed to add PDF font");
+        Ok(Self {
+            regular: font(BuiltinFont::Helvetica)?,
+            bold: font(BuiltinFont::HelveticaBold)?,
+            mono: font(BuiltinFont::Courier)?,
+            layer,
+            doc,
+            y: PAGE_HEIGHT - MARGIN,
+        })
+    }
+
+    /// Start a new page unless `height` more millimetres fit on this one
+    fn reserve(&mut self, height: f32) {
+        if self.y - height < MARGIN {
+            let (page, layer) = self.doc.add_page(Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Report");
+            self.layer = self.doc.get_page(page).get_layer(layer);
+            self.y = PAGE_HEIGHT - MARGIN;
+        }
+    }
+
+    fn text_at(&self, x: f32, text: &str, size: f32, font: &IndirectFontRef) {
+        self.layer.use_text(text, size, Mm(x), Mm(self.y), font);
+    }
+
+    /// One line at the left margin, moving down by its height
+    fn line(&mut self, text: &str, size: f32, bold: bool) {
+        let height = size * PT_TO_MM * 1.5;
+        self.reserve(height);
+        let font = if bold { &self.bold } else { &self.regular };
+        self.text_at(MARGIN, text, size, font);
+        self.y -= height;
+    }
+
+    fn heading(&mut self, text: &str) {
+        self.y -= 4.0;
+        self.reserve(20.0);
+        self.line(text, 13.0, true);
+    }
+
+    fn row(&mut self, label: &str, value: &str) {
+        self.reserve(6.0);
+        self.text_at(MARGIN, label, 10.0, &self.bold);
+        self.text_at(VALUE_X, value, 10.0, &self.regular);
+        self.y -= 6.0;
+    }
+
+    /// A hash or address, in a fixed-width font so it can be compared character by character
+    fn hash_row(&mut self, label: &str, value: &str) {
+        self.reserve(6.0);
+        self.text_at(MARGIN, label, 10.0, &self.bold);
+        self.text_at(VALUE_X, value, 8.5, &self.mono);
+        self.y -= 6.0;
+    }
+
+    fn cells(&mut self, cells: &[(f32, String)], bold: bool) {
+        self.reserve(6.0);
+        let font = if bold { &self.bold } else { &self.regular };
+        for (x, text) in cells {
+            self.text_at(*x, text, 10.0, font);
+        }
+        self.y -= 6.0;
+    }
+
+    fn bar(&mut self, label: &str, fraction: f64, color: &str, value: &str) {
+        self.reserve(7.0);
+        self.text_at(MARGIN, label, 10.0, &self.regular);
+        let length = (fraction as f32 * BAR_WIDTH).max(0.5);
+        self.layer.set_fill_color(rgb(color));
+        self.layer.add_rect(Rect::new(Mm(VALUE_X), Mm(self.y - 1.0), Mm(VALUE_X + length), Mm(self.y + 3.5)));
+        self.layer.set_fill_color(rgb("#000000"));
+        self.text_at(VALUE_X + length + 3.0, value, 10.0, &self.regular);
+        self.y -= 7.0;
+    }
+
+    fn finish(self) -> Result<Vec<u8>> {
+        self.doc.save_to_bytes().context("Failed to write PDF")
+    }
+}
+
+/// A formal results report for a closed poll: details, tallies, a chart, participation and the
+/// hash `certify` would sign for the same snapshot. `read_at` is the snapshot block's timestamp.
+pub fn render(payload: &CertifiedResults, read_at: u64) -> Result<Vec<u8>> {
+    let tally = payload.tally();
+    let hash = payload.hash()?;
+    let date = |ts: u64| timezone::format_in(ts, DisplayZone::Utc);
+    let mut pdf = Writer::new(&format!("Poll #{} results", payload.poll_id))?;
+
+    pdf.line("Poll Results Report", 20.0, true);
+    pdf.line(&format!("Poll #{} on chain {} · contract {:?}", payload.poll_id, payload.chain_id, payload.contract), 9.0, false);
+    pdf.y -= 3.0;
+    for line in wrap(&payload.question, 70, usize::MAX) {
+        pdf.line(&line, 14.0, true);
+    }
+
+    pdf.heading("Poll details");
+    for (label, value) in [
+        ("Type", payload.poll_type.clone()),
+        ("Category", payload.category.clone()),
+        ("Status", payload.status.clone()),
+        ("Created", date(payload.created_at)),
+        ("Voting ended", date(payload.end_time)),
+        ("Min. participation", numbers::format_integer(payload.min_participation)),
+    ] {
+        pdf.row(label, &value);
+    }
+    pdf.hash_row("Creator", &format!("{:?}", payload.creator));
+    for (i, line) in wrap(&payload.options.join(" · "), 90, usize::MAX).iter().enumerate() {
+        pdf.row(if i == 0 { "Options" } else { "" }, line);
+    }
+
+    pdf.heading("Results");
+    let mut header = vec![(MARGIN, "Option".to_string()), (MARGIN + 75.0, "Votes".to_string()), (MARGIN + 95.0, "Share".to_string())];
+    if tally.weighted {
+        header.extend([(MARGIN + 115.0, "Weight".to_string()), (MARGIN + 145.0, "Weight share".to_string())]);
+    }
+    pdf.cells(&header, true);
+    for option in &tally.options {
+        let mut cells = vec![
+            (MARGIN, wrap(&option.option, 38, 1).concat()),
+            (MARGIN + 75.0, numbers::format_integer(option.votes)),
+            (MARGIN + 95.0, format!("{:.1}%", option.vote_share)),
+        ];
+        if tally.weighted {
+            cells.extend([(MARGIN + 115.0, numbers::format_integer(option.weight)), (MARGIN + 145.0, format!("{:.1}%", option.weight_share))]);
+        }
+        pdf.cells(&cells, false);
+    }
+    pdf.y -= 2.0;
+    let outcome = match tally.leader() {
+        Some(leader) => format!("Outcome: {} leads by {}.", leader.option, tally.winner_metric()),
+        None => format!("Outcome: no single option leads by {}.", tally.winner_metric()),
+    };
+    pdf.line(&outcome, 11.0, true);
+
+    pdf.heading(&format!("Chart (by {})", tally.winner_metric()));
+    let value = |option: &crate::tally::OptionTally| if tally.weighted { u256_to_f64(option.weight) } else { option.votes as f64 };
+    let max = tally.options.iter().map(value).fold(0.0, f64::max);
+    for (i, option) in tally.options.iter().enumerate() {
+        let fraction = if max > 0.0 { value(option) / max } else { 0.0 };
+        pdf.bar(&wrap(&option.option, 25, 1).concat(), fraction, PALETTE[i % PALETTE.len()], &format!("{:.1}%", tally.deciding_share(option)));
+    }
+
+    pdf.heading("Participation");
+    let voters: HashSet<Address> = payload.votes.iter().map(|vote| vote.voter).collect();
+    pdf.row("Total votes", &numbers::format_integer(tally.total_votes));
+    pdf.row("Unique voters", &numbers::format_integer(voters.len() as u64));
+    if tally.weighted {
+        pdf.row("Total weight", &numbers::format_integer(tally.total_weight));
+    }
+
+    pdf.heading("Certification");
+    pdf.row("Snapshot block", &format!("{} ({})", payload.block_number, date(read_at)));
+    pdf.hash_row("Block hash", &format!("{:?}", payload.block_hash));
+    pdf.hash_row("Payload hash", &format!("{:?}", hash));
+    pdf.y -= 2.0;
+    for line in wrap(concat!(
+        "The payload hash is the keccak256 of the JSON results payload, including every VoteCast event up to the ",
+        "snapshot block, as produced by `certify`. Run `certify` on the same block for an operator-signed certificate."), 100, usize::MAX) {
+        pdf.line(&line, 9.0, false);
+    }
+    pdf.line(&format!("Generated {}", date(payload.certified_at)), 9.0, false);
+
+    pdf.finish()
+}
+
+impl<M: Middleware + 'static> PollManager<M> {
+    /// `export -f pdf`: the results report of a closed poll
+    pub async fn poll_report_pdf(&self, poll_id: u64) -> Result<Vec<u8>> {
+        let (payload, read_at) = self.certified_results(poll_id).await?;
+        render(&payload, read_at)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::VoteRecord;
+
+    #[test]
+    fn reports_tally_the_snapshot_across_pages() {
+        let vote = |voter: u64, option_index: u64| VoteRecord {
+            poll_id: 3,
+            voter: Address::from_low_u64_be(voter),
+            option_index,
+            weight: U256::from(5),
+            block_number: 10,
+            transaction_hash: H256::zero(),
+            timestamp: 0,
+        };
+        let mut payload = CertifiedResults {
+            chain_id: 31337,
+            contract: Address::zero(),
+            poll_id: 3,
+            question: "Adopt the budget?".to_string(),
+            options: vec!["Yes".to_string(), "No".to_string()],
+            creator: Address::zero(),
+            created_at: 1_700_000_000,
+            end_time: 1_700_086_400,
+            status: "Closed".to_string(),
+            poll_type: u8_to_poll_type(1).to_string(),
+            category: "Governance".to_string(),
+            min_participation: U256::zero(),
+            results: vec![U256::from(10), U256::from(5)],
+            total_votes: U256::from(3),
+            total_weight: U256::from(15),
+            block_number: 10,
+            block_hash: H256::zero(),
+            certified_at: 1_700_090_000,
+            votes: vec![vote(1, 0), vote(2, 0), vote(1, 1)],
+        };
+        let tally = payload.tally();
+        assert!(tally.weighted);
+        assert_eq!(tally.options.iter().map(|o| o.votes).collect::<Vec<_>>(), vec![2, 1]);
+        assert_eq!(tally.leader().unwrap().option, "Yes");
+
+        let pdf = render(&payload, 1_700_090_000).unwrap();
+        assert!(pdf.starts_with(b"%PDF-"));
+        let pages = |pdf: &[u8]| pdf.windows(b"/Type/Page/".len()).filter(|w| *w == b"/Type/Page/").count();
+        assert_eq!(pages(&pdf), 1);
+
+        payload.options = (0..80).map(|i| format!("Option {}", i)).collect();
+        assert!(pages(&render(&payload, 1_700_090_000).unwrap()) > 1, "long results continue on new pages");
+        assert_eq!(rgb("#ff0000"), Color::Rgb(Rgb::new(1.0, 0.0, 0.0, None)));
+    }
+}
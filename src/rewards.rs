@@ -0,0 +1,180 @@
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+use colored::*;
+use ethers::abi::{encode_packed, Token};
+use ethers::prelude::*;
+use ethers::utils::keccak256;
+use serde::{Deserialize, Serialize};
+
+use crate::allowlist::MerkleTree;
+use crate::error::bail_user;
+use crate::{numbers, PollManager};
+
+/// How a reward pool is split between a poll's voters
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum RewardScheme {
+    /// The same amount for every voter
+    Flat,
+    /// In proportion to each voter's vote weight
+    Weight,
+    /// In proportion to the square root of each voter's weight, so large holders gain less
+    Quadratic,
+}
+
+/// Split `pool` between `weights`, rounding down; what rounding leaves over is not distributed
+pub fn split(pool: U256, weights: &[U256], scheme: RewardScheme) -> Vec<U256> {
+    let shares: Vec<U256> = match scheme {
+        RewardScheme::Flat => vec![U256::one(); weights.len()],
+        RewardScheme::Weight => weights.to_vec(),
+        RewardScheme::Quadratic => weights.iter().map(|weight| weight.integer_sqrt()).collect(),
+    };
+    let total = shares.iter().fold(U256::zero(), |total, share| total.saturating_add(*share));
+    if total.is_zero() {
+        return vec![U256::zero(); weights.len()];
+    }
+    // Multiply first for precision; fall back to dividing first if that would overflow
+    shares.iter()
+        .map(|share| pool.checked_mul(*share).map(|product| product / total).unwrap_or_else(|| pool / total * share))
+        .collect()
+}
+
+/// Leaf of a claim as Uniswap's MerkleDistributor checks it: keccak256(abi.encodePacked(index, account, amount))
+pub fn claim_leaf(index: u64, account: Address, amount: U256) -> H256 {
+    let packed = encode_packed(&[Token::Uint(U256::from(index)), Token::Address(account), Token::Uint(amount)])
+        .expect("uints and addresses always pack");
+    H256(keccak256(packed))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Claim {
+    pub index: u64,
+    pub amount: U256,
+    pub proof: Vec<H256>,
+}
+
+/// The JSON layout of Uniswap's merkle-distributor, read by most airdrop claim contracts and UIs
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Distribution {
+    pub merkle_root: H256,
+    pub token_total: U256,
+    pub claims: BTreeMap<Address, Claim>,
+}
+
+impl Distribution {
+    /// Index claims in address order, leaving out zero amounts
+    pub fn new(amounts: &BTreeMap<Address, U256>) -> Self {
+        let entries: Vec<(u64, Address, U256)> = amounts.iter()
+            .filter(|(_, amount)| !amount.is_zero())
+            .enumerate()
+            .map(|(index, (account, amount))| (index as u64, *account, *amount))
+            .collect();
+        let tree = MerkleTree::new(entries.iter().map(|(index, account, amount)| claim_leaf(*index, *account, *amount)).collect());
+        let claims = entries.iter()
+            .map(|(index, account, amount)| {
+                let proof = tree.proof(claim_leaf(*index, *account, *amount)).expect("every claim is a leaf");
+                (*account, Claim { index: *index, amount: *amount, proof })
+            })
+            .collect();
+        Distribution {
+            merkle_root: tree.root(),
+            token_total: entries.iter().fold(U256::zero(), |total, (_, _, amount)| total.saturating_add(*amount)),
+            claims,
+        }
+    }
+}
+
+fn render_csv(amounts: &BTreeMap<Address, U256>, weights: &BTreeMap<Address, U256>) -> Result<String> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record(["address", "weight", "amount"])?;
+    for (account, amount) in amounts {
+        writer.write_record([format!("{:?}", account), weights[account].to_string(), amount.to_string()])?;
+    }
+    Ok(String::from_utf8(writer.into_inner()?)?)
+}
+
+impl<M: Middleware + 'static> PollManager<M> {
+    /// `rewards`: split a reward pool between a closed poll's voters and export the payouts
+    pub async fn poll_rewards(&self, poll_id: u64, pool: u64, decimals: u8, scheme: RewardScheme, format: &str, output_path: Option<String>) -> Result<()> {
+        let format = format.to_lowercase();
+        if !matches!(format.as_str(), "table" | "csv" | "json") {
+            bail_user!("Unsupported format: {}. Use table, csv or json", format);
+        }
+        if pool == 0 {
+            bail_user!("--pool must be more than zero");
+        }
+        let poll = self.poll_data(U256::from(poll_id)).await?;
+        if poll.6 == 0 {
+            bail_user!("Poll #{} is still open; rewards are split once it is closed and the votes are final", poll_id);
+        }
+
+        let mut weights: BTreeMap<Address, U256> = BTreeMap::new();
+        for vote in self.fetch_vote_events(poll_id).await? {
+            let weight = weights.entry(vote.voter).or_default();
+            *weight = weight.saturating_add(vote.weight);
+        }
+        if weights.is_empty() {
+            bail_user!("Poll #{} has no votes to reward", poll_id);
+        }
+        // 10^78 no longer fits in a U256
+        let unit = (decimals < 78).then(|| U256::exp10(decimals as usize));
+        let Some(pool_units) = unit.and_then(|unit| U256::from(pool).checked_mul(unit)) else {
+            bail_user!("A pool of {} with {} decimals is too large", pool, decimals);
+        };
+        let amounts: BTreeMap<Address, U256> = weights.keys().copied()
+            .zip(split(pool_units, &weights.values().copied().collect::<Vec<_>>(), scheme))
+            .collect();
+        let distributed = amounts.values().fold(U256::zero(), |total, amount| total.saturating_add(*amount));
+
+        let rendered = match format.as_str() {
+            "csv" => render_csv(&amounts, &weights)?,
+            "json" => serde_json::to_string_pretty(&Distribution::new(&amounts))?,
+            _ => {
+                say!("\n{}", "🎁 PARTICIPATION REWARDS".cyan().bold().underline());
+                say!("{}", "═".repeat(60).cyan());
+                say!("{} {} - {}", "Poll ID:".yellow().bold(), poll_id, poll.1.white().bold());
+                say!("{} {} split {:?} between {} voters", "Pool:".yellow().bold(), numbers::format_integer(pool), scheme, weights.len());
+                for (account, amount) in &amounts {
+                    say!("  {:?} {}", account, numbers::format_amount(*amount, decimals as u32, 4));
+                }
+                say!("{} {} (rounding leaves {} base units)", "Distributed:".yellow().bold(),
+                    numbers::format_amount(distributed, decimals as u32, 4), pool_units - distributed);
+                say!("Use -f json for a Merkle distribution or -f csv for a payout list.");
+                return Ok(());
+            }
+        };
+        match output_path {
+            Some(path) => {
+                std::fs::write(&path, rendered)?;
+                say!("{} {} voters to {}", "✅ Rewards exported for".green().bold(), amounts.len(), path.yellow());
+            }
+            None => println!("{}", rendered.trim_end()),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::allowlist::verify;
+
+    #[test]
+    fn splits_pool_and_builds_claimable_distribution() {
+        let weights = [U256::from(1), U256::from(4), U256::from(16)];
+        assert_eq!(split(U256::from(900), &weights, RewardScheme::Flat), [U256::from(300); 3]);
+        assert_eq!(split(U256::from(2100), &weights, RewardScheme::Weight), [U256::from(100), U256::from(400), U256::from(1600)]);
+        // Square roots 1, 2 and 4
+        assert_eq!(split(U256::from(700), &weights, RewardScheme::Quadratic), [U256::from(100), U256::from(200), U256::from(400)]);
+        assert_eq!(split(U256::MAX, &weights[..1], RewardScheme::Weight), [U256::MAX]);
+
+        let amounts: BTreeMap<Address, U256> = [(Address::repeat_byte(1), U256::from(5)), (Address::repeat_byte(2), U256::zero()), (Address::repeat_byte(3), U256::from(7))].into();
+        let distribution = Distribution::new(&amounts);
+        assert_eq!(distribution.claims.len(), 2);
+        assert_eq!(distribution.token_total, U256::from(12));
+        for (account, claim) in &distribution.claims {
+            assert!(verify(&claim.proof, distribution.merkle_root, claim_leaf(claim.index, *account, claim.amount)));
+        }
+    }
+}
@@ -0,0 +1,103 @@
+use std::collections::BTreeMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use colored::*;
+use ethers::providers::{Http, HttpClientError, JsonRpcClient};
+use serde::{de::DeserializeOwned, Serialize};
+use tokio::time::Instant;
+
+use crate::output;
+
+/// Requests-per-second cap, set once at startup; `None` sends as fast as the node answers
+static MAX_RPS: OnceLock<Option<f64>> = OnceLock::new();
+
+/// When the next request may be sent under the cap
+static NEXT_SLOT: tokio::sync::Mutex<Option<Instant>> = tokio::sync::Mutex::const_new(None);
+
+/// Calls made this run, by JSON-RPC method
+static CALLS: Mutex<BTreeMap<String, u64>> = Mutex::new(BTreeMap::new());
+
+pub fn init(max_rps: Option<f64>) {
+    let _ = MAX_RPS.set(max_rps.filter(|rps| *rps > 0.0));
+}
+
+/// HTTP transport that honours `--max-rps` and counts calls per method
+#[derive(Debug, Clone)]
+pub struct MeteredHttp {
+    inner: Http,
+}
+
+impl MeteredHttp {
+    pub fn new(url: &str) -> Result<Self, url::ParseError> {
+        Ok(Self { inner: Http::new(url::Url::parse(url)?) })
+    }
+}
+
+/// Wait for the next free slot under the cap
+async fn throttle() {
+    let Some(rps) = MAX_RPS.get().copied().flatten() else { return };
+    let interval = Duration::from_secs_f64(1.0 / rps);
+    let wait_until = {
+        let mut next = NEXT_SLOT.lock().await;
+        let now = Instant::now();
+        let slot = next.map_or(now, |next| next.max(now));
+        *next = Some(slot + interval);
+        slot
+    };
+    tokio::time::sleep_until(wait_until).await;
+}
+
+#[async_trait]
+impl JsonRpcClient for MeteredHttp {
+    type Error = HttpClientError;
+
+    async fn request<T, R>(&self, method: &str, params: T) -> Result<R, Self::Error>
+    where
+        T: std::fmt::Debug + Serialize + Send + Sync,
+        R: DeserializeOwned + Send,
+    {
+        throttle().await;
+        if let Ok(mut calls) = CALLS.lock() {
+            *calls.entry(method.to_string()).or_insert(0) += 1;
+        }
+        self.inner.request(method, params).await
+    }
+}
+
+/// Calls made so far this run, by method
+pub fn calls() -> BTreeMap<String, u64> {
+    CALLS.lock().map(|calls| calls.clone()).unwrap_or_default()
+}
+
+/// `--rpc-stats`: RPC calls per method, on stderr so piped output stays clean
+pub fn print_summary() {
+    let calls = calls();
+    let total: u64 = calls.values().sum();
+    eprintln!("\n{}", output::render(format!("📡 RPC calls: {}", total)).cyan().bold());
+    let mut by_count: Vec<_> = calls.into_iter().collect();
+    by_count.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    for (method, count) in by_count {
+        eprintln!("  {:<32} {:>6}", method, count);
+    }
+    if let Some(rps) = MAX_RPS.get().copied().flatten() {
+        eprintln!("  {}", format!("(limited to {} requests/second)", rps).dimmed());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn throttle_spaces_requests_by_the_cap() {
+        init(Some(100.0));
+        let start = Instant::now();
+        for _ in 0..5 {
+            throttle().await;
+        }
+        // The first request goes out at once; the next four wait 10ms each
+        assert!(start.elapsed() >= Duration::from_millis(40));
+    }
+}
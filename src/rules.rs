@@ -0,0 +1,302 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use colored::*;
+use ethers::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{bail_config, bail_user};
+use crate::numbers::SaturatingU64;
+use crate::{duration, output, redact, store, u8_to_category, PollManager};
+
+const RULES_FILE: &str = "rules.json";
+const QUEUE_FILE: &str = "rule_queue.json";
+
+/// One voting rule. Every condition that is set must hold; the first matching rule decides.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Rule {
+    pub name: String,
+    /// Poll category, e.g. governance
+    #[serde(default)]
+    pub category: Option<String>,
+    /// The poll carries at least one of these tags
+    #[serde(default)]
+    pub tags_include: Vec<String>,
+    /// Only polls created by one of these addresses
+    #[serde(default)]
+    pub creator_in: Vec<Address>,
+    /// Never polls created by these addresses
+    #[serde(default)]
+    pub creator_not_in: Vec<Address>,
+    /// Case-insensitive text the question must contain
+    #[serde(default)]
+    pub question_contains: Option<String>,
+    /// Name of the option to vote for
+    pub vote: String,
+    /// Cast the vote without asking; otherwise it is queued for `rules approve`
+    #[serde(default)]
+    pub auto: bool,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RuleFile {
+    #[serde(default)]
+    pub rules: Vec<Rule>,
+}
+
+/// What a rule looks at
+pub struct PollFacts<'a> {
+    pub category: &'a str,
+    pub tags: &'a [String],
+    pub creator: Address,
+    pub question: &'a str,
+}
+
+impl Rule {
+    pub fn matches(&self, poll: &PollFacts) -> bool {
+        self.category.as_ref().is_none_or(|category| category.eq_ignore_ascii_case(poll.category))
+            && (self.tags_include.is_empty()
+                || self.tags_include.iter().any(|tag| poll.tags.iter().any(|t| t.eq_ignore_ascii_case(tag))))
+            && (self.creator_in.is_empty() || self.creator_in.contains(&poll.creator))
+            && !self.creator_not_in.contains(&poll.creator)
+            && self.question_contains.as_ref().is_none_or(|text| poll.question.to_lowercase().contains(&text.to_lowercase()))
+    }
+
+    /// Index of the option this rule votes for
+    pub fn option_index(&self, options: &[String]) -> Option<usize> {
+        options.iter().position(|option| option.trim().eq_ignore_ascii_case(self.vote.trim()))
+    }
+}
+
+/// The first rule in `rules` that matches `poll`
+pub fn first_match<'a>(rules: &'a [Rule], poll: &PollFacts) -> Option<&'a Rule> {
+    rules.iter().find(|rule| rule.matches(poll))
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Decision {
+    /// Waiting for `rules approve` or `rules dismiss`
+    Pending,
+    Voted(H256),
+    Dismissed,
+    Failed(String),
+}
+
+/// A rule's verdict on one poll
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Recommendation {
+    pub id: u64,
+    pub chain_id: u64,
+    pub contract: Address,
+    pub poll_id: u64,
+    pub question: String,
+    pub rule: String,
+    pub option_index: u64,
+    pub option: String,
+    pub auto: bool,
+    pub decision: Decision,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct RuleQueue {
+    next_id: u64,
+    items: BTreeMap<u64, Recommendation>,
+}
+
+impl RuleQueue {
+    fn get_mut(&mut self, id: u64) -> Result<&mut Recommendation> {
+        match self.items.get_mut(&id) {
+            Some(item) => Ok(item),
+            None => bail_user!("No recommendation #{}; see `rules queue`", id),
+        }
+    }
+}
+
+/// Read the rules from `file`, or `rules.json` in the data directory
+pub fn load_rules(file: Option<&Path>) -> Result<Vec<Rule>> {
+    let path: PathBuf = match file {
+        Some(path) => path.to_path_buf(),
+        None => store::data_dir()?.join(RULES_FILE),
+    };
+    if file.is_some() && !path.exists() {
+        bail_config!("Rules file {} not found", path.display());
+    }
+    let rules: RuleFile = store::load_from(&path)?;
+    for rule in &rules.rules {
+        if let Some(category) = &rule.category {
+            if crate::category_to_u8(category).is_err() {
+                bail_config!("{}: rule '{}' has unknown category '{}'", path.display(), rule.name, category);
+            }
+        }
+    }
+    Ok(rules.rules)
+}
+
+pub fn show_queue() -> Result<()> {
+    let queue: RuleQueue = store::load(QUEUE_FILE)?;
+
+    say!("\n{}", "📋 Rule Recommendations:".cyan().bold());
+    if queue.items.is_empty() {
+        say!("Nothing yet. Write rules to {} and run `rules check`.", RULES_FILE);
+        return Ok(());
+    }
+    for item in queue.items.values() {
+        let decision = match &item.decision {
+            Decision::Pending => "pending approval".yellow(),
+            Decision::Voted(tx) => format!("voted in {:?}", tx).green(),
+            Decision::Dismissed => "dismissed".normal(),
+            Decision::Failed(error) => format!("failed: {}", error).red(),
+        };
+        say!("\n#{}: poll #{} {}", item.id, item.poll_id, item.question);
+        say!("  Vote: {} (option {}) by rule '{}'", item.option.yellow().bold(), item.option_index, item.rule);
+        say!("  Status: {}", decision);
+    }
+    Ok(())
+}
+
+pub fn dismiss(id: u64) -> Result<()> {
+    let mut queue: RuleQueue = store::load(QUEUE_FILE)?;
+    let item = queue.get_mut(id)?;
+    if item.decision != Decision::Pending {
+        bail_user!("Recommendation #{} is no longer pending", id);
+    }
+    item.decision = Decision::Dismissed;
+    store::save(QUEUE_FILE, &queue)?;
+    say!("{} #{}", "🗑️  Dismissed recommendation".green().bold(), id);
+    Ok(())
+}
+
+impl<M: Middleware + 'static> PollManager<M> {
+    /// Run `rules` over active polls not yet voted on or decided, voting or queueing per rule
+    async fn evaluate_rules(&self, rules: &[Rule]) -> Result<()> {
+        let mut queue: RuleQueue = store::load(QUEUE_FILE)?;
+        let (chain_id, contract) = (self.wallet.chain_id(), self.contract.address());
+        let now = chrono::Utc::now().timestamp() as u64;
+        let poll_count = self.contract.poll_count().call().await?.to_u64_saturating();
+
+        for poll_id in 0..poll_count {
+            if queue.items.values().any(|item| item.chain_id == chain_id && item.contract == contract && item.poll_id == poll_id) {
+                continue;
+            }
+            let poll = self.poll_data(U256::from(poll_id)).await?;
+            if poll.6 != 0 || poll.5.to_u64_saturating() <= now {
+                continue;
+            }
+            let facts = PollFacts { category: u8_to_category(poll.8), tags: &poll.13, creator: poll.3, question: &poll.1 };
+            let Some(rule) = first_match(rules, &facts) else { continue };
+            let Some(option_index) = rule.option_index(&poll.2) else {
+                eprintln!("{} rule '{}' matches poll #{} but it has no option '{}'",
+                    output::render("⚠️ ".to_string()).yellow(), rule.name, poll_id, rule.vote);
+                continue;
+            };
+            if self.contract.has_user_voted(U256::from(poll_id), self.sender).call().await? {
+                continue;
+            }
+
+            queue.next_id += 1;
+            let mut item = Recommendation {
+                id: queue.next_id,
+                chain_id,
+                contract,
+                poll_id,
+                question: poll.1.clone(),
+                rule: rule.name.clone(),
+                option_index: option_index as u64,
+                option: poll.2[option_index].clone(),
+                auto: rule.auto,
+                decision: Decision::Pending,
+            };
+            if rule.auto {
+                say!("{} #{}: {} (rule '{}')", "🤖 Voting on poll".cyan().bold(), poll_id, item.option.yellow().bold(), rule.name);
+                item.decision = match self.cast_vote(item.poll_id, item.option_index).await {
+                    Ok(tx) => {
+                        say!("{} {:?}", "✅ Vote cast in".green().bold(), tx);
+                        Decision::Voted(tx)
+                    }
+                    Err(e) => {
+                        let error = redact::redact(&format!("{:#}", e));
+                        eprintln!("{} poll #{}: {}", output::render("❌ Auto-vote failed on".to_string()).red(), poll_id, error);
+                        Decision::Failed(error)
+                    }
+                };
+            } else {
+                say!("{} #{}: poll #{} {} → {} (rule '{}')", "📋 Recommendation".cyan().bold(),
+                    item.id, poll_id, item.question, item.option.yellow().bold(), rule.name);
+            }
+            queue.items.insert(item.id, item);
+            // Save after each poll so a crash cannot vote twice
+            store::save(QUEUE_FILE, &queue)?;
+        }
+        Ok(())
+    }
+
+    /// `rules check`: evaluate once, or with `watch` keep evaluating new polls at that interval
+    pub async fn check_rules(&self, file: Option<&Path>, watch: Option<u64>) -> Result<()> {
+        let rules = load_rules(file)?;
+        if rules.is_empty() {
+            bail_config!("No rules defined; add them to {} (or pass --file)", RULES_FILE);
+        }
+        let Some(interval) = watch else {
+            return self.evaluate_rules(&rules).await;
+        };
+        if interval == 0 {
+            bail_user!("--watch interval must be longer than zero");
+        }
+        say!("{} every {} (Ctrl-C to stop)", "🤖 Evaluating rules".cyan().bold(), duration::format(interval));
+        loop {
+            if let Err(e) = self.evaluate_rules(&rules).await {
+                eprintln!("{} {:#}", output::render("⚠️  Check failed:".to_string()).yellow(), e);
+            }
+            tokio::time::sleep(std::time::Duration::from_secs(interval)).await;
+        }
+    }
+
+    /// `rules approve`: cast a queued recommendation
+    pub async fn approve_recommendation(&self, id: u64) -> Result<()> {
+        let mut queue: RuleQueue = store::load(QUEUE_FILE)?;
+        let item = queue.get_mut(id)?;
+        if item.decision != Decision::Pending {
+            bail_user!("Recommendation #{} is no longer pending", id);
+        }
+        if item.chain_id != self.wallet.chain_id() || item.contract != self.contract.address() {
+            bail_user!("Recommendation #{} is for contract {:?} on chain {}; connect to it to approve", id, item.contract, item.chain_id);
+        }
+        say!("{} #{} option {} ({})", "🗳️ Voting on poll".cyan().bold(), item.poll_id, item.option_index, item.option.yellow());
+        let tx = self.cast_vote(item.poll_id, item.option_index).await?;
+        item.decision = Decision::Voted(tx);
+        store::save(QUEUE_FILE, &queue)?;
+        say!("{}", "✅ Vote cast successfully!".green().bold());
+        say!("{} {:?}", "Transaction hash:".cyan().bold(), tx);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_matching_rule_decides() {
+        let treasurer = Address::repeat_byte(0x7);
+        let rules: RuleFile = serde_json::from_value(serde_json::json!({ "rules": [
+            { "name": "treasury-abstain", "category": "governance", "tags_include": ["treasury"],
+              "creator_not_in": [treasurer], "vote": "Abstain" },
+            { "name": "upgrades", "question_contains": "upgrade", "vote": "Yes", "auto": true },
+        ]})).unwrap();
+        let tags = vec!["Treasury".to_string()];
+        let poll = |creator, question| PollFacts { category: "Governance", tags: &tags, creator, question };
+
+        let stranger = poll(Address::repeat_byte(0x1), "Fund the grants round?");
+        assert_eq!(first_match(&rules.rules, &stranger).map(|r| r.name.as_str()), Some("treasury-abstain"));
+        assert!(first_match(&rules.rules, &poll(treasurer, "Fund the grants round?")).is_none());
+        assert_eq!(first_match(&rules.rules, &poll(treasurer, "Upgrade the proxy?")).map(|r| r.auto), Some(true));
+
+        let options = ["Yes".to_string(), "No".to_string(), "abstain".to_string()];
+        assert_eq!(rules.rules[0].option_index(&options), Some(2));
+        assert!(serde_json::from_str::<RuleFile>(r#"{"rules":[{"name":"x","vote":"Yes","when":"now"}]}"#).is_err());
+    }
+}
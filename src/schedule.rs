@@ -0,0 +1,233 @@
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+use colored::*;
+use ethers::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::error::bail_user;
+use crate::numbers::SaturatingU64;
+use crate::{duration, output, redact, store, timezone, PollManager};
+
+const SCHEDULE_FILE: &str = "schedule.json";
+
+/// When a scheduled vote becomes due
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Trigger {
+    /// Unix timestamp (`vote --at`)
+    At(u64),
+    /// Block number (`vote --at-block`)
+    AtBlock(u64),
+}
+
+impl Trigger {
+    fn is_due(&self, now: u64, block: u64) -> bool {
+        match *self {
+            Trigger::At(timestamp) => now >= timestamp,
+            Trigger::AtBlock(number) => block >= number,
+        }
+    }
+
+    fn describe(&self) -> String {
+        match *self {
+            Trigger::At(timestamp) => timezone::format_with_relative(timestamp),
+            Trigger::AtBlock(number) => format!("block {}", number),
+        }
+    }
+}
+
+/// A vote stored locally and cast by `schedule run` once its trigger is reached
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduledVote {
+    pub id: u64,
+    pub poll_id: u64,
+    pub option: u64,
+    pub trigger: Trigger,
+    pub chain_id: u64,
+    pub contract: Address,
+    pub signer: Address,
+    pub created_at: u64,
+    pub cast_tx: Option<H256>,
+    pub error: Option<String>,
+}
+
+impl ScheduledVote {
+    fn is_pending(&self) -> bool {
+        self.cast_tx.is_none() && self.error.is_none()
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ScheduleStore {
+    next_id: u64,
+    votes: BTreeMap<u64, ScheduledVote>,
+}
+
+fn now() -> u64 {
+    chrono::Utc::now().timestamp() as u64
+}
+
+pub fn list_scheduled() -> Result<()> {
+    let book: ScheduleStore = store::load(SCHEDULE_FILE)?;
+
+    say!("\n{}", "⏰ Scheduled Votes:".cyan().bold());
+    if book.votes.is_empty() {
+        say!("Nothing scheduled. Queue a vote with `vote -p <id> -o <option> --at <time>`.");
+        return Ok(());
+    }
+    for vote in book.votes.values() {
+        let status = match (&vote.cast_tx, &vote.error) {
+            (Some(tx), _) => format!("cast in {:?}", tx).green(),
+            (None, Some(error)) => format!("failed: {}", error).red(),
+            (None, None) => "pending".yellow(),
+        };
+        say!("\n#{}: poll #{} option {} from {:?}", vote.id, vote.poll_id, vote.option, vote.signer);
+        say!("  When: {}", vote.trigger.describe());
+        say!("  Contract: {:?} (chain {})", vote.contract, vote.chain_id);
+        say!("  Status: {}", status);
+    }
+    Ok(())
+}
+
+pub fn cancel_scheduled(id: u64) -> Result<()> {
+    let mut book: ScheduleStore = store::load(SCHEDULE_FILE)?;
+    match book.votes.get(&id) {
+        None => bail_user!("No scheduled vote #{}; see `schedule list`", id),
+        Some(vote) if !vote.is_pending() => bail_user!("Scheduled vote #{} already ran; nothing to cancel", id),
+        Some(_) => {}
+    }
+    book.votes.remove(&id);
+    store::save(SCHEDULE_FILE, &book)?;
+    say!("{} #{}", "🗑️  Cancelled scheduled vote".green().bold(), id);
+    Ok(())
+}
+
+impl<M: Middleware + 'static> PollManager<M> {
+    /// Check a vote can still be cast when `trigger` fires, then store it for `schedule run`
+    pub async fn schedule_vote(&self, poll_id: u64, option: u64, trigger: Trigger) -> Result<()> {
+        let poll_data = self.poll_data(U256::from(poll_id)).await?;
+        if poll_data.6 != 0 {
+            bail_user!("Poll #{} is closed", poll_id);
+        }
+        if option as usize >= poll_data.2.len() {
+            bail_user!("Poll #{} has options 0-{}; there is no option {}", poll_id, poll_data.2.len().saturating_sub(1), option);
+        }
+        let end_time = poll_data.5.to_u64_saturating();
+        match trigger {
+            Trigger::At(timestamp) if timestamp <= now() => bail_user!("--at is in the past; vote now instead"),
+            Trigger::At(timestamp) if timestamp >= end_time => {
+                bail_user!("Poll #{} ends {}, before the scheduled time", poll_id, timezone::format_with_relative(end_time));
+            }
+            Trigger::AtBlock(number) if number <= self.client.get_block_number().await?.as_u64() => {
+                bail_user!("Block {} has already been mined; vote now instead", number);
+            }
+            _ => {}
+        }
+
+        let mut book: ScheduleStore = store::load(SCHEDULE_FILE)?;
+        book.next_id += 1;
+        let id = book.next_id;
+        book.votes.insert(id, ScheduledVote {
+            id,
+            poll_id,
+            option,
+            trigger,
+            chain_id: self.wallet.chain_id(),
+            contract: self.contract.address(),
+            signer: self.sender,
+            created_at: now(),
+            cast_tx: None,
+            error: None,
+        });
+        store::save(SCHEDULE_FILE, &book)?;
+
+        say!("{} #{}: poll #{} option {} at {}", "⏰ Scheduled vote".green().bold(), id, poll_id, option, trigger.describe());
+        say!("Keep `schedule run --watch 1m` running (or run `schedule run` from cron) with the same signer to cast it.");
+        Ok(())
+    }
+
+    /// Cast every scheduled vote that is due for this chain, contract and signer
+    async fn run_due_votes(&self) -> Result<usize> {
+        let mut book: ScheduleStore = store::load(SCHEDULE_FILE)?;
+        let (now, block) = (now(), self.client.get_block_number().await?.as_u64());
+        let due: Vec<u64> = book.votes.values()
+            .filter(|vote| vote.is_pending() && vote.trigger.is_due(now, block))
+            .filter(|vote| vote.chain_id == self.wallet.chain_id() && vote.contract == self.contract.address() && vote.signer == self.sender)
+            .map(|vote| vote.id)
+            .collect();
+
+        for id in &due {
+            let (poll_id, option) = {
+                let vote = &book.votes[id];
+                (vote.poll_id, vote.option)
+            };
+            let result = match self.poll_data(U256::from(poll_id)).await {
+                Ok(poll_data) if poll_data.6 != 0 => Err(format!("poll #{} closed before the vote was due", poll_id)),
+                Ok(_) => {
+                    say!("{} #{} option {}", "🗳️ Casting scheduled vote on poll".cyan().bold(), poll_id, option);
+                    self.cast_vote(poll_id, option).await.map_err(|e| format!("{:#}", e))
+                }
+                Err(e) => Err(format!("{:#}", e)),
+            };
+            let vote = book.votes.get_mut(id).expect("due votes come from the book");
+            match result {
+                Ok(tx) => {
+                    say!("{} {:?}", "✅ Vote cast in".green().bold(), tx);
+                    vote.cast_tx = Some(tx);
+                }
+                Err(error) => {
+                    eprintln!("{} #{}: {}", output::render("❌ Scheduled vote".to_string()).red(), id, redact::redact(&error));
+                    vote.error = Some(redact::redact(&error));
+                }
+            }
+            // Save after each vote so a crash cannot cast it twice
+            store::save(SCHEDULE_FILE, &book)?;
+        }
+        Ok(due.len())
+    }
+
+    /// Vote without the spinner and output of `vote`, for votes cast unattended; returns the transaction hash
+    pub(crate) async fn cast_vote(&self, poll_id: u64, option: u64) -> Result<H256> {
+        let contract_call = self.contract.vote(U256::from(poll_id), U256::from(option));
+        let contract_call = self.as_sender(contract_call);
+        let tx = self.send_call(&contract_call).await?;
+        let receipt = tx.await?.ok_or_else(|| anyhow::anyhow!("Transaction was dropped before it was mined"))?;
+        self.cache.invalidate(poll_id);
+        Ok(receipt.transaction_hash)
+    }
+
+    /// `schedule run`: cast due votes once, or with `watch` keep checking at that interval
+    pub async fn run_schedule(&self, watch: Option<u64>) -> Result<()> {
+        let Some(interval) = watch else {
+            if self.run_due_votes().await? == 0 {
+                say!("No scheduled votes are due.");
+            }
+            return Ok(());
+        };
+        if interval == 0 {
+            bail_user!("--watch interval must be longer than zero");
+        }
+        say!("{} every {} (Ctrl-C to stop)", "⏰ Watching for due votes".cyan().bold(), duration::format(interval));
+        loop {
+            // Keep running through RPC hiccups; failed votes are recorded and not retried
+            if let Err(e) = self.run_due_votes().await {
+                eprintln!("{} {:#}", output::render("⚠️  Check failed:".to_string()).yellow(), e);
+            }
+            tokio::time::sleep(std::time::Duration::from_secs(interval)).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn triggers_fire_at_their_time_or_block() {
+        assert!(!Trigger::At(1_000).is_due(999, 50));
+        assert!(Trigger::At(1_000).is_due(1_000, 0));
+        assert!(!Trigger::AtBlock(50).is_due(u64::MAX, 49));
+        assert!(Trigger::AtBlock(50).is_due(0, 51));
+    }
+}
@@ -0,0 +1,152 @@
+use std::path::Path;
+use std::rc::Rc;
+
+use anyhow::{Context, Result};
+use ethers::prelude::*;
+use rhai::{Array, Dynamic, Engine, EvalAltResult, Map};
+
+use crate::error::CliError;
+use crate::numbers::SaturatingU64;
+use crate::PollManager;
+
+/// Most Rhai operations one script may run, so a runaway loop ends with an error
+const MAX_OPERATIONS: u64 = 50_000_000;
+
+/// Longest string one script may build, in bytes
+const MAX_STRING_SIZE: usize = 1 << 20;
+
+/// Most elements one array or object map may hold
+const MAX_COLLECTION_SIZE: usize = 100_000;
+
+/// What a script can read: every poll as `export -f json` has it, and the contract's event index
+struct Snapshot {
+    polls: Vec<Dynamic>,
+    events: Vec<Dynamic>,
+}
+
+fn to_dynamic<T: serde::Serialize>(value: &T) -> Result<Dynamic> {
+    rhai::serde::to_dynamic(value).map_err(|e| anyhow::anyhow!("{}", e))
+}
+
+fn poll(snapshot: &Snapshot, id: i64) -> Result<&Dynamic, Box<EvalAltResult>> {
+    usize::try_from(id).ok()
+        .and_then(|id| snapshot.polls.get(id))
+        .ok_or_else(|| format!("no poll #{}; there are {}", id, snapshot.polls.len()).into())
+}
+
+/// `[#{option, votes, weight}]` for a poll map from `get_poll`
+fn results(poll: &Dynamic) -> Array {
+    let Some(poll) = poll.read_lock::<Map>() else {
+        return Array::new();
+    };
+    let list = |key: &str| poll.get(key).and_then(|value| value.clone().try_cast::<Array>()).unwrap_or_default();
+    let (options, votes, weights) = (list("options"), list("votes"), list("weights"));
+    options.into_iter().enumerate()
+        .map(|(i, option)| {
+            let mut row = Map::new();
+            row.insert("option".into(), option);
+            row.insert("votes".into(), votes.get(i).cloned().unwrap_or(Dynamic::from_int(0)));
+            row.insert("weight".into(), weights.get(i).cloned().unwrap_or(Dynamic::from("0")));
+            Dynamic::from_map(row)
+        })
+        .collect()
+}
+
+/// A Rhai engine with the poll API and no access to files, modules or the network
+fn engine(snapshot: Rc<Snapshot>) -> Engine {
+    let mut engine = Engine::new();
+    engine.set_module_resolver(rhai::module_resolvers::DummyModuleResolver::new());
+    engine.set_max_operations(MAX_OPERATIONS);
+    engine.set_max_call_levels(64);
+    // Doubling a string in a loop runs out of memory long before it runs out of operations
+    engine.set_max_string_size(MAX_STRING_SIZE);
+    engine.set_max_array_size(MAX_COLLECTION_SIZE);
+    engine.set_max_map_size(MAX_COLLECTION_SIZE);
+    engine.set_max_expr_depths(64, 32);
+    engine.on_print(|text| println!("{}", text));
+    engine.on_debug(|text, _, position| eprintln!("[{}] {}", position, text));
+
+    let polls = snapshot.clone();
+    engine.register_fn("poll_count", move || polls.polls.len() as i64);
+    let polls = snapshot.clone();
+    engine.register_fn("get_poll", move |id: i64| poll(&polls, id).cloned());
+    let polls = snapshot.clone();
+    engine.register_fn("get_results", move |id: i64| poll(&polls, id).map(results));
+    let events = snapshot.clone();
+    engine.register_fn("events", move || Array::from(events.events.clone()));
+    let events = snapshot;
+    engine.register_fn("events", move |kind: &str| {
+        events.events.iter()
+            .filter(|event| event.read_lock::<Map>().and_then(|event| event.get("event").map(|name| name.to_string() == kind)).unwrap_or(false))
+            .cloned()
+            .collect::<Array>()
+    });
+    engine
+}
+
+/// Print what a script evaluated to: strings as they are, anything else as JSON
+fn print_result(value: Dynamic) -> Result<()> {
+    if value.is_unit() {
+        return Ok(());
+    }
+    if value.is_string() {
+        println!("{}", value);
+        return Ok(());
+    }
+    let json: serde_json::Value = rhai::serde::from_dynamic(&value).map_err(|e| anyhow::anyhow!("{}", e))?;
+    println!("{}", serde_json::to_string_pretty(&json)?);
+    Ok(())
+}
+
+impl<M: Middleware + 'static> PollManager<M> {
+    /// `script run`: evaluate a Rhai script against every poll and the synced event index
+    pub async fn run_script(&self, file: &Path) -> Result<()> {
+        let source = std::fs::read_to_string(file).with_context(|| format!("Failed to read {}", file.display()))?;
+        // Compile first so a typo fails before anything is read from the chain
+        let ast = Engine::new().compile(&source)
+            .map_err(|e| CliError::User(format!("{}: {}", file.display(), e)))?;
+
+        let count = self.contract.poll_count().call().await?.to_u64_saturating();
+        let mut polls = Vec::new();
+        for poll_id in 0..count {
+            polls.push(to_dynamic(&self.build_poll_export(poll_id).await?)?);
+        }
+        let index = self.event_index().await?;
+        let events = index.events.iter().map(to_dynamic).collect::<Result<_>>()?;
+
+        let engine = engine(Rc::new(Snapshot { polls, events }));
+        let value = engine.eval_ast::<Dynamic>(&ast)
+            .map_err(|e| CliError::User(format!("{}: {}", file.display(), e)))?;
+        print_result(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scripts_read_polls_and_events() {
+        let poll = serde_json::json!({ "id": 0, "question": "Ship it?", "options": ["Yes", "No"], "votes": [3, 1], "weights": ["3", "1"] });
+        let vote = serde_json::json!({ "event": "vote_cast", "poll_id": 0, "option_index": 0 });
+        let created = serde_json::json!({ "event": "poll_created", "poll_id": 0 });
+        let snapshot = Snapshot {
+            polls: vec![to_dynamic(&poll).unwrap()],
+            events: vec![to_dynamic(&created).unwrap(), to_dynamic(&vote).unwrap()],
+        };
+        let engine = engine(Rc::new(snapshot));
+
+        let leader: String = engine.eval(r#"
+            let best = ();
+            for row in get_results(0) { if best == () || row.votes > best.votes { best = row; } }
+            `${get_poll(0).question} -> ${best.option}`
+        "#).unwrap();
+        assert_eq!(leader, "Ship it? -> Yes");
+        assert_eq!(engine.eval::<i64>(r#"events("vote_cast").len() + poll_count()"#).unwrap(), 2);
+        assert!(engine.eval::<Dynamic>("get_poll(5)").is_err());
+        assert!(engine.eval::<Dynamic>(r#"import "os" as os;"#).is_err(), "scripts cannot load modules");
+        assert!(engine.eval::<Dynamic>("loop {}").is_err(), "runaway scripts are stopped");
+        assert!(engine.eval::<Dynamic>(r#"let s = "x"; loop { s += s; }"#).is_err(), "allocations are capped");
+        assert!(engine.eval::<Dynamic>("let a = []; loop { a += a; a.push(1); }").is_err());
+    }
+}
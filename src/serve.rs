@@ -0,0 +1,444 @@
+use std::collections::BTreeMap;
+use std::net::SocketAddr;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use colored::*;
+use ethers::prelude::*;
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha2::Sha256;
+use tokio::io::BufReader;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{broadcast, Mutex};
+
+use crate::error::{self, bail_config, ErrorKind};
+use crate::indexer::IndexedEvent;
+use crate::spec::PollFields;
+use crate::{audit, drafts, http, lint, output, push, redact, PollManager};
+
+pub const DEFAULT_LISTEN: &str = "127.0.0.1:8547";
+
+/// Largest request body accepted; operator requests are a few dozen bytes
+const MAX_BODY: usize = 64 * 1024;
+
+/// How long a client gets to send its request before the connection is dropped
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How often new contract events are looked for while anyone is subscribed to `/events`
+const EVENT_INTERVAL: Duration = Duration::from_secs(5);
+
+/// What an operator request may do
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OperatorAction {
+    /// Cast a vote from this instance's signing account
+    Vote,
+}
+
+/// One pre-approved operation for one API key
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Operation {
+    /// Name of the key allowed to request it
+    pub key: String,
+    pub action: OperatorAction,
+    pub poll_id: u64,
+    /// Options that may be voted for; any option when empty
+    #[serde(default)]
+    pub options: Vec<u64>,
+}
+
+/// The operator file: API keys by name, and the only operations they may request
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct OperatorFile {
+    /// Environment variable holding each key, by key name, so the file itself holds no secrets
+    pub keys: BTreeMap<String, String>,
+    #[serde(default)]
+    pub operations: Vec<Operation>,
+}
+
+/// Operator mode: the keys themselves, read from the environment, and the allowlist
+#[derive(Debug)]
+pub struct Operator {
+    keys: Vec<(String, String)>,
+    operations: Vec<Operation>,
+}
+
+/// Compare secrets in time independent of where they differ
+fn secrets_match(given: &str, expected: &str) -> bool {
+    given.len() == expected.len() && given.bytes().zip(expected.bytes()).fold(0, |diff, (a, b)| diff | (a ^ b)) == 0
+}
+
+impl Operator {
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = match std::fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) => bail_config!("Could not read operator file {}: {}", path.display(), e),
+        };
+        let file: OperatorFile = match serde_yaml::from_str(&content) {
+            Ok(file) => file,
+            Err(e) => bail_config!("{} is not a valid operator file: {}", path.display(), e),
+        };
+        Self::from_file(file, |name| std::env::var(name).ok())
+    }
+
+    fn from_file(file: OperatorFile, env: impl Fn(&str) -> Option<String>) -> Result<Self> {
+        let mut keys = Vec::new();
+        for (name, variable) in &file.keys {
+            let Some(key) = env(variable).filter(|key| !key.is_empty()) else {
+                bail_config!("{} must hold the API key for '{}'", variable, name);
+            };
+            if key.len() < 16 {
+                bail_config!("The API key for '{}' in {} is too short; use at least 16 characters", name, variable);
+            }
+            redact::register(&key);
+            keys.push((name.clone(), key));
+        }
+        if let Some(operation) = file.operations.iter().find(|operation| !file.keys.contains_key(&operation.key)) {
+            bail_config!("Operation on poll #{} names key '{}', which is not under keys", operation.poll_id, operation.key);
+        }
+        Ok(Self { keys, operations: file.operations })
+    }
+
+    /// The name of the key in an `Authorization: Bearer <key>` header
+    fn authenticate(&self, authorization: Option<&str>) -> Option<&str> {
+        let given = authorization?.strip_prefix("Bearer ")?.trim();
+        self.keys.iter().find(|(_, key)| secrets_match(given, key)).map(|(name, _)| name.as_str())
+    }
+
+    fn allowed<'a>(&'a self, client: &'a str) -> impl Iterator<Item = &'a Operation> {
+        self.operations.iter().filter(move |operation| operation.key == client)
+    }
+
+    /// Whether `client` may vote `option` on `poll_id`
+    pub fn permits_vote(&self, client: &str, poll_id: u64, option: u64) -> bool {
+        self.allowed(client).any(|operation| {
+            operation.action == OperatorAction::Vote
+                && operation.poll_id == poll_id
+                && (operation.options.is_empty() || operation.options.contains(&option))
+        })
+    }
+}
+
+/// Whether `signature`, written `sha256=<hex>` as GitHub sends it in X-Hub-Signature-256,
+/// is the HMAC-SHA256 of `body` under `secret`
+pub fn signature_valid(secret: &[u8], body: &[u8], signature: Option<&str>) -> bool {
+    let Some(hex) = signature.and_then(|signature| signature.trim().strip_prefix("sha256=")) else {
+        return false;
+    };
+    let Ok(expected) = ethers::utils::hex::decode(hex) else {
+        return false;
+    };
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("HMAC takes keys of any length");
+    mac.update(body);
+    mac.verify_slice(&expected).is_ok()
+}
+
+/// What this server accepts besides reads
+pub struct Server {
+    pub operator: Option<Operator>,
+    /// POLL_WEBHOOK_SECRET: enables `POST /hooks/create-poll`
+    pub webhook_secret: Option<String>,
+    /// New contract events, for every `/events` subscriber
+    pub events: broadcast::Sender<IndexedEvent>,
+    /// Held while an operator vote is sent, so two votes never race for a nonce
+    votes: Mutex<()>,
+}
+
+impl Server {
+    pub fn new(operator_file: Option<&Path>) -> Result<Self> {
+        let operator = operator_file.map(Operator::load).transpose()?;
+        let webhook_secret = std::env::var("POLL_WEBHOOK_SECRET").ok().filter(|secret| !secret.is_empty());
+        if let Some(secret) = &webhook_secret {
+            if secret.len() < 16 {
+                bail_config!("POLL_WEBHOOK_SECRET is too short; use at least 16 characters");
+            }
+            redact::register(secret);
+        }
+        let (events, _) = broadcast::channel(push::BUFFER);
+        Ok(Self { operator, webhook_secret, events, votes: Mutex::new(()) })
+    }
+}
+
+/// `POST /hooks/create-poll`: queue a signed poll manifest as a draft; it goes on-chain only
+/// after the usual `draft approve` and `draft submit`
+fn create_poll_hook(secret: &str, request: &http::Request) -> Response {
+    if !signature_valid(secret.as_bytes(), &request.body, request.header("x-hub-signature-256")) {
+        eprintln!("{}", output::render("⚠️  Rejected a webhook with a missing or invalid signature".to_string()).yellow());
+        return error_response("401 Unauthorized", "Missing or invalid X-Hub-Signature-256");
+    }
+    let spec = match std::str::from_utf8(&request.body).map_err(anyhow::Error::from).and_then(PollFields::parse_manifest) {
+        Ok(spec) => spec,
+        Err(e) => {
+            let outcome = redact::redact(&format!("Invalid input: {:#}", e));
+            audit::append_operation("serve hook create-poll", Vec::new(), "webhook", Vec::new(), outcome, ErrorKind::User.exit_code());
+            return error_response("400 Bad Request", format!("Invalid poll manifest: {:#}", e));
+        }
+    };
+    let warnings = lint::lint(&spec, spec.duration_seconds().unwrap_or_default(), None);
+    let question = spec.question.clone();
+    match drafts::add_draft(spec) {
+        Ok(id) => {
+            let args = vec!["draft".to_string(), "show".to_string(), id.to_string()];
+            audit::append_operation("serve hook create-poll", args, "webhook", Vec::new(), "ok".to_string(), 0);
+            say!("{} {} {}", "📝 Webhook queued draft".green().bold(), format!("#{}", id).yellow(), question);
+            ("202 Accepted", json!({ "draft_id": id, "status": "pending review", "warnings": warnings }))
+        }
+        Err(e) => error_response("500 Internal Server Error", format!("{:#}", e)),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct VoteRequest {
+    poll_id: u64,
+    option: u64,
+}
+
+/// An HTTP response: status line and JSON body
+type Response = (&'static str, Value);
+
+fn error_response(status: &'static str, message: impl std::fmt::Display) -> Response {
+    (status, json!({ "error": redact::redact(&message.to_string()) }))
+}
+
+fn status_for(kind: ErrorKind) -> &'static str {
+    match kind {
+        ErrorKind::User => "400 Bad Request",
+        ErrorKind::Revert => "409 Conflict",
+        ErrorKind::Rpc => "502 Bad Gateway",
+        ErrorKind::Config | ErrorKind::General => "500 Internal Server Error",
+    }
+}
+
+/// `GET /events`: hand the connection to its own task, which pushes new poll events as
+/// server-sent events or, when the client asks to upgrade, WebSocket messages
+async fn subscribe(server: &Server, mut stream: TcpStream, request: &http::Request) -> Result<()> {
+    let poll_id = match push::poll_filter(request) {
+        Ok(poll_id) => poll_id,
+        Err(message) => {
+            let (status, body) = error_response("400 Bad Request", message);
+            return http::write_response(&mut stream, status, "application/json", &serde_json::to_vec(&body)?, false).await;
+        }
+    };
+    let events = server.events.subscribe();
+    let websocket_key = request.header("upgrade")
+        .filter(|upgrade| upgrade.eq_ignore_ascii_case("websocket"))
+        .and_then(|_| request.header("sec-websocket-key"))
+        .map(str::to_string);
+    tokio::spawn(async move {
+        let result = match websocket_key {
+            Some(key) => push::stream_websocket(stream, &key, events, poll_id).await,
+            None => push::stream_sse(stream, events, poll_id).await,
+        };
+        if let Err(e) = result {
+            eprintln!("{} {:#}", output::render("⚠️  Event stream failed:".to_string()).yellow(), e);
+        }
+    });
+    Ok(())
+}
+
+impl<M: Middleware + 'static> PollManager<M> {
+    async fn route(&self, server: &Server, request: &http::Request) -> Response {
+        let segments: Vec<&str> = request.path.trim_matches('/').split('/').collect();
+        match (request.method.as_str(), segments.as_slice()) {
+            ("GET", ["health"]) => ("200 OK", json!({
+                "status": "ok",
+                "chain_id": self.wallet.chain_id(),
+                "contract": self.contract.address(),
+                "operator": server.operator.is_some(),
+                "webhooks": server.webhook_secret.is_some(),
+                "event_subscribers": server.events.receiver_count(),
+            })),
+            ("GET", ["polls", id]) => {
+                let Ok(poll_id) = id.parse::<u64>() else {
+                    return error_response("404 Not Found", format!("'{}' is not a poll id", id));
+                };
+                match self.build_poll_export(poll_id).await {
+                    Ok(export) => ("200 OK", json!(export)),
+                    Err(e) => error_response(status_for(error::classify(&e)), format!("{:#}", e)),
+                }
+            }
+            ("POST", ["hooks", "create-poll"]) => match &server.webhook_secret {
+                Some(secret) => create_poll_hook(secret, request),
+                None => error_response("404 Not Found", "Webhooks are off; set POLL_WEBHOOK_SECRET"),
+            },
+            (_, ["operator", ..]) => {
+                let Some(operator) = &server.operator else {
+                    return error_response("404 Not Found", "Operator mode is off; start serve with --operator");
+                };
+                let Some(client) = operator.authenticate(request.header("authorization")) else {
+                    return error_response("401 Unauthorized", "Missing or unknown API key");
+                };
+                match (request.method.as_str(), &segments[1..]) {
+                    ("GET", ["operations"]) => ("200 OK", json!(operator.allowed(client).collect::<Vec<_>>())),
+                    ("POST", ["vote"]) => {
+                        let _sending = server.votes.lock().await;
+                        self.operator_vote(operator, client, &request.body).await
+                    }
+                    _ => error_response("404 Not Found", format!("No operator endpoint {} {}", request.method, request.path)),
+                }
+            }
+            _ => error_response("404 Not Found", format!("No endpoint {} {}", request.method, request.path)),
+        }
+    }
+
+    /// `POST /operator/vote`: cast a pre-approved vote, logging the attempt whether or not it is allowed
+    async fn operator_vote(&self, operator: &Operator, client: &str, body: &[u8]) -> Response {
+        let vote: VoteRequest = match serde_json::from_slice(body) {
+            Ok(vote) => vote,
+            Err(e) => return error_response("400 Bad Request", format!("Expected {{\"poll_id\": N, \"option\": N}}: {}", e)),
+        };
+        let args = vec!["vote".to_string(), "-p".to_string(), vote.poll_id.to_string(), "-o".to_string(), vote.option.to_string()];
+        if !operator.permits_vote(client, vote.poll_id, vote.option) {
+            let message = format!("'{}' may not vote option {} on poll #{}", client, vote.option, vote.poll_id);
+            audit::append_operation("serve vote", args, client, Vec::new(), format!("Denied: {}", message), ErrorKind::User.exit_code());
+            say!("{} {}", "⛔ Denied:".red().bold(), message);
+            return error_response("403 Forbidden", message);
+        }
+
+        say!("{} {} option {} on poll #{}", "🗳️  Operator vote by".cyan().bold(), client.yellow(), vote.option, vote.poll_id);
+        match self.cast_vote(vote.poll_id, vote.option).await {
+            Ok(hash) => {
+                audit::append_operation("serve vote", args, client, vec![hash], "ok".to_string(), 0);
+                say!("{} {:?}", "✅ Vote cast:".green().bold(), hash);
+                ("200 OK", json!({ "poll_id": vote.poll_id, "option": vote.option, "transaction": hash }))
+            }
+            Err(e) => {
+                let kind = error::classify(&e);
+                let outcome = redact::redact(&format!("{}: {:#}", kind.label(), e));
+                audit::append_operation("serve vote", args, client, Vec::new(), outcome, kind.exit_code());
+                eprintln!("{} {:#}", output::render("⚠️  Operator vote failed:".to_string()).yellow(), e);
+                error_response(status_for(kind), format!("{:#}", e))
+            }
+        }
+    }
+
+    async fn serve_request(&self, server: &Server, stream: TcpStream) -> Result<()> {
+        let mut reader = BufReader::new(stream);
+        let request = match tokio::time::timeout(READ_TIMEOUT, http::read_request(&mut reader, MAX_BODY)).await {
+            Ok(request) => request?,
+            Err(_) => anyhow::bail!("client sent no request within {}s", READ_TIMEOUT.as_secs()),
+        };
+        let Some(request) = request else {
+            return Ok(());
+        };
+        if request.method == "GET" && request.path.trim_matches('/') == "events" {
+            return subscribe(server, reader.into_inner(), &request).await;
+        }
+        let (status, body) = self.route(server, &request).await;
+        http::write_response(reader.get_mut(), status, "application/json", &serde_json::to_vec(&body)?, false).await
+    }
+
+    /// Broadcast the contract's poll events since `next_block` to `/events` subscribers. With
+    /// nobody subscribed only the position moves on, so nothing is fetched for no one.
+    async fn push_events(&self, server: &Server, next_block: &mut Option<u64>) -> Result<()> {
+        let Some(from) = *next_block else {
+            *next_block = Some(self.client.get_block_number().await?.as_u64() + 1);
+            return Ok(());
+        };
+        if server.events.receiver_count() == 0 {
+            *next_block = Some((self.client.get_block_number().await?.as_u64() + 1).max(from));
+            return Ok(());
+        }
+        let (events, next) = self.new_events(from).await?;
+        for event in events {
+            // Fails only when the last subscriber has just left
+            let _ = server.events.send(event);
+        }
+        *next_block = Some(next);
+        Ok(())
+    }
+
+    /// `serve`: answer HTTP requests about polls; with an operator file, carry out the operations
+    /// it allows, and with POLL_WEBHOOK_SECRET, queue signed poll manifests as drafts. Requests
+    /// are handled concurrently, each on its own task; operator votes take turns so they never race for a nonce.
+    pub async fn serve(self: Arc<Self>, listen: SocketAddr, operator_file: Option<&Path>) -> Result<()> {
+        let server = Arc::new(Server::new(operator_file)?);
+        let listener = match TcpListener::bind(listen).await {
+            Ok(listener) => listener,
+            Err(e) => bail_config!("Could not listen on {}: {}", listen, e),
+        };
+
+        say!("{} {}", "🌐 Serving".cyan().bold(), format!("http://{}", listen).yellow());
+        say!("GET /health, GET /polls/<id>, GET /events (SSE or WebSocket); Ctrl-C to stop.");
+        if let Some(operator) = &server.operator {
+            say!("{} {} keys, {} pre-approved operations as {:?}: GET /operator/operations, POST /operator/vote",
+                "🔐 Operator mode:".cyan().bold(), operator.keys.len(), operator.operations.len(), self.sender);
+        }
+        if server.webhook_secret.is_some() {
+            say!("{} POST /hooks/create-poll queues signed poll manifests as drafts", "🪝 Webhooks:".cyan().bold());
+        }
+
+        let mut event_check = tokio::time::interval(EVENT_INTERVAL);
+        let mut next_block = None;
+        loop {
+            tokio::select! {
+                accepted = listener.accept() => {
+                    let (stream, _) = accepted?;
+                    // A slow client or a vote waiting to be mined must not hold up anyone else
+                    let (manager, server) = (self.clone(), server.clone());
+                    tokio::spawn(async move {
+                        if let Err(e) = manager.serve_request(&server, stream).await {
+                            eprintln!("{} {:#}", output::render("⚠️  Request failed:".to_string()).yellow(), e);
+                        }
+                    });
+                }
+                _ = event_check.tick() => {
+                    if let Err(e) = self.push_events(&server, &mut next_block).await {
+                        eprintln!("{} {:#}", output::render("⚠️  Event check failed:".to_string()).yellow(), e);
+                    }
+                }
+                _ = tokio::signal::ctrl_c() => break,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_allowlisted_votes_for_known_keys() {
+        let file: OperatorFile = serde_yaml::from_str("
+keys:
+  discord-bot: BOT_KEY
+  ops: OPS_KEY
+operations:
+  - { key: discord-bot, action: vote, poll_id: 7, options: [0] }
+  - { key: ops, action: vote, poll_id: 8 }
+").unwrap();
+        let env = |name: &str| Some(format!("{}-0123456789abcdef", name));
+        let operator = Operator::from_file(file.clone(), env).unwrap();
+
+        assert_eq!(operator.authenticate(Some("Bearer BOT_KEY-0123456789abcdef")), Some("discord-bot"));
+        assert_eq!(operator.authenticate(Some("Bearer BOT_KEY-0123456789abcdeX")), None);
+        assert_eq!(operator.authenticate(Some("BOT_KEY-0123456789abcdef")), None);
+        assert_eq!(operator.authenticate(None), None);
+
+        assert!(operator.permits_vote("discord-bot", 7, 0));
+        assert!(!operator.permits_vote("discord-bot", 7, 1));
+        assert!(!operator.permits_vote("discord-bot", 8, 0));
+        assert!(operator.permits_vote("ops", 8, 3), "no options listed allows any option");
+
+        assert!(Operator::from_file(file.clone(), |_| Some("short".to_string())).is_err());
+        assert!(Operator::from_file(file, |_| None).is_err());
+    }
+
+    #[test]
+    fn checks_github_style_webhook_signatures() {
+        // HMAC-SHA256 of "hello" under "secret"
+        let signature = "sha256=88aab3ede8d3adf94d26ab90d3bafd4a2083070c3bcce9c014ee04a443847c0b";
+        assert!(signature_valid(b"secret", b"hello", Some(signature)));
+        assert!(!signature_valid(b"secret", b"hello!", Some(signature)));
+        assert!(!signature_valid(b"other", b"hello", Some(signature)));
+        assert!(!signature_valid(b"secret", b"hello", Some(&signature["sha256=".len()..])));
+        assert!(!signature_valid(b"secret", b"hello", None));
+    }
+}
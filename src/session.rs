@@ -0,0 +1,118 @@
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use colored::*;
+use ethers::prelude::*;
+
+use crate::error::bail_user;
+use crate::{duration, numbers, output, PollManager};
+
+/// Bounds on what one run may sign once the key is unlocked, for long-running commands
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Limits {
+    /// Refuse to sign once the key has been unlocked this long
+    pub timeout: Option<u64>,
+    pub max_transactions: Option<u64>,
+    /// Total gas limit across every transaction sent
+    pub max_gas: Option<u64>,
+}
+
+impl Limits {
+    fn is_set(&self) -> bool {
+        self.timeout.is_some() || self.max_transactions.is_some() || self.max_gas.is_some()
+    }
+}
+
+#[derive(Debug, Default)]
+struct Usage {
+    transactions: u64,
+    gas: u64,
+}
+
+static LIMITS: OnceLock<(Limits, Instant)> = OnceLock::new();
+static USAGE: Mutex<Usage> = Mutex::new(Usage { transactions: 0, gas: 0 });
+
+/// Start the session clock; called once the signer is unlocked
+pub fn init(limits: Limits) {
+    if limits.is_set() {
+        let _ = LIMITS.set((limits, Instant::now()));
+    }
+}
+
+/// Whether a transaction using up to `gas` fits the session, counting it if so
+fn reserve(limits: &Limits, elapsed: Duration, usage: &mut Usage, gas: u64) -> Result<()> {
+    if let Some(timeout) = limits.timeout {
+        if elapsed.as_secs() >= timeout {
+            bail_user!("Session expired: the key was unlocked {} ago (--session-timeout {}); restart to unlock it again",
+                duration::format(elapsed.as_secs()), duration::format(timeout));
+        }
+    }
+    if let Some(max) = limits.max_transactions {
+        if usage.transactions >= max {
+            bail_user!("Session limit reached: {} of {} transactions already sent (--session-max-txs)", usage.transactions, max);
+        }
+    }
+    if let Some(max) = limits.max_gas {
+        if usage.gas.saturating_add(gas) > max {
+            bail_user!("Session limit reached: this transaction needs up to {} gas and only {} of {} remain (--session-max-gas)",
+                numbers::format_integer(gas), numbers::format_integer(max.saturating_sub(usage.gas)), numbers::format_integer(max));
+        }
+    }
+    usage.transactions += 1;
+    usage.gas = usage.gas.saturating_add(gas);
+    Ok(())
+}
+
+/// Transactions and gas used against the session limits, on stderr; nothing when no limits are set
+pub fn print_summary() {
+    let Some((limits, started)) = LIMITS.get() else { return };
+    let usage = USAGE.lock().unwrap_or_else(|e| e.into_inner());
+    let of = |max: Option<u64>| max.map(|max| format!(" of {}", numbers::format_integer(max))).unwrap_or_default();
+    eprintln!("{} {}{} transactions, {}{} gas, unlocked for {}",
+        output::render("🔐 Session:".to_string()).cyan().bold(),
+        usage.transactions, of(limits.max_transactions),
+        numbers::format_integer(usage.gas), of(limits.max_gas),
+        duration::format(started.elapsed().as_secs()));
+}
+
+impl<M: Middleware + 'static> PollManager<M> {
+    /// Everything checked before a transaction is signed: the policy, then the session limits
+    pub(crate) async fn guard_send<D: ethers::abi::Detokenize>(&self, call: &ContractCall<M, D>) -> Result<()> {
+        self.check_send_policy().await?;
+        let Some((limits, started)) = LIMITS.get() else { return Ok(()) };
+        let gas = match limits.max_gas {
+            Some(_) => call.estimate_gas().await?.as_u64(),
+            None => 0,
+        };
+        let mut usage = USAGE.lock().unwrap_or_else(|e| e.into_inner());
+        reserve(limits, started.elapsed(), &mut usage, gas)
+    }
+
+    /// `guard_send` for something whose gas is already known, such as a UserOperation
+    pub(crate) async fn guard_send_gas(&self, gas: u64) -> Result<()> {
+        self.check_send_policy().await?;
+        let Some((limits, started)) = LIMITS.get() else { return Ok(()) };
+        let mut usage = USAGE.lock().unwrap_or_else(|e| e.into_inner());
+        reserve(limits, started.elapsed(), &mut usage, gas)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sessions_stop_at_their_limits() {
+        let limits = Limits { timeout: Some(3600), max_transactions: Some(2), max_gas: Some(300_000) };
+        let mut usage = Usage::default();
+        assert!(reserve(&limits, Duration::from_secs(10), &mut usage, 120_000).is_ok());
+        assert!(reserve(&limits, Duration::from_secs(10), &mut usage, 200_000).is_err(), "over the gas budget");
+        assert!(reserve(&limits, Duration::from_secs(10), &mut usage, 100_000).is_ok());
+        assert!(reserve(&limits, Duration::from_secs(10), &mut usage, 1).is_err(), "over the transaction count");
+        assert_eq!((usage.transactions, usage.gas), (2, 220_000));
+
+        let mut fresh = Usage::default();
+        assert!(reserve(&limits, Duration::from_secs(3600), &mut fresh, 1).is_err(), "expired");
+    }
+}
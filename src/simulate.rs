@@ -0,0 +1,311 @@
+use std::collections::HashMap;
+use std::net::{TcpListener, TcpStream};
+use std::process::{Child, Command, Stdio};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use colored::*;
+use ethers::prelude::*;
+use serde::Serialize;
+
+use crate::error::{bail_config, bail_user};
+use crate::numbers::SaturatingU64;
+use crate::tally::PollTally;
+use crate::{numbers, output, poll_quorum, quorum, PollManager};
+
+/// How long a fresh fork gets to start answering RPC calls
+const FORK_STARTUP: Duration = Duration::from_secs(30);
+
+/// Ether given to each hypothetical voter on the fork so gas is never the reason a vote fails
+const VOTER_BALANCE: &str = "0xde0b6b3a7640000";
+
+/// `--vote ADDRESS=OPTION`
+pub fn parse_hypothetical_vote(value: &str) -> std::result::Result<(Address, u64), String> {
+    let (voter, option) = value.split_once('=').ok_or("expected ADDRESS=OPTION, e.g. 0xabc…=2")?;
+    let voter = voter.trim().parse::<Address>().map_err(|_| format!("'{}' is not an address", voter.trim()))?;
+    let option = option.trim().parse::<u64>().map_err(|_| format!("'{}' is not an option index", option.trim()))?;
+    Ok((voter, option))
+}
+
+/// Delegates with the most delegators, most first; ties in address order
+pub fn top_delegates(delegations: &std::collections::BTreeMap<Address, Address>, n: usize) -> Vec<(Address, usize)> {
+    let mut counts: HashMap<Address, usize> = HashMap::new();
+    for delegate in delegations.values() {
+        *counts.entry(*delegate).or_insert(0) += 1;
+    }
+    let mut ranked: Vec<(Address, usize)> = counts.into_iter().collect();
+    ranked.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    ranked.truncate(n);
+    ranked
+}
+
+/// A throwaway Anvil node forked from the real chain, killed on drop
+struct Fork {
+    process: Child,
+    rpc_url: String,
+}
+
+impl Fork {
+    fn spawn(anvil: &str, fork_url: &str, block: u64) -> Result<Self> {
+        let port = TcpListener::bind("127.0.0.1:0")?.local_addr()?.port();
+        let process = match Command::new(anvil)
+            .args(["--fork-url", fork_url, "--fork-block-number", &block.to_string(), "--port", &port.to_string(), "--silent"])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+        {
+            Ok(process) => process,
+            Err(e) => bail_config!("`simulate` forks the chain with Anvil; could not run '{}' ({}). Install Foundry or pass --anvil <path>", anvil, e),
+        };
+        let mut fork = Fork { process, rpc_url: format!("http://127.0.0.1:{}", port) };
+
+        let deadline = Instant::now() + FORK_STARTUP;
+        while TcpStream::connect(("127.0.0.1", port)).is_err() {
+            if let Ok(Some(status)) = fork.process.try_wait() {
+                bail_config!("Anvil exited ({}) before the fork of {} was ready", status, fork_url);
+            }
+            if Instant::now() > deadline {
+                bail_config!("Anvil did not start listening within {}s", FORK_STARTUP.as_secs());
+            }
+            std::thread::sleep(Duration::from_millis(100));
+        }
+        Ok(fork)
+    }
+}
+
+impl Drop for Fork {
+    fn drop(&mut self) {
+        let _ = self.process.kill();
+        let _ = self.process.wait();
+    }
+}
+
+/// One hypothetical vote and what the fork made of it
+#[derive(Debug, Clone, Serialize)]
+pub struct SimulatedVote {
+    pub voter: Address,
+    pub option_index: u64,
+    pub option: String,
+    pub cast: bool,
+    pub weight: Option<u64>,
+    /// Why the vote was not cast, e.g. the voter already voted
+    pub error: Option<String>,
+}
+
+/// Leader and quorum of a tally
+#[derive(Debug, Clone, Serialize)]
+pub struct Outcome {
+    pub leader: Option<String>,
+    pub quorum: quorum::Quorum,
+    pub tally: PollTally,
+}
+
+impl Outcome {
+    fn new(tally: PollTally, quorum: quorum::Quorum) -> Self {
+        let leader = tally.leader().map(|leader| leader.option.clone());
+        Self { leader, quorum, tally }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SimulationReport {
+    pub poll_id: u64,
+    pub question: String,
+    /// Block the fork was taken at
+    pub fork_block: u64,
+    pub votes: Vec<SimulatedVote>,
+    pub before: Outcome,
+    pub after: Outcome,
+    pub outcome_changed: bool,
+}
+
+impl<M: Middleware + 'static> PollManager<M> {
+    /// `simulate`: replay hypothetical votes on a local fork and compare the outcome with the real one
+    pub async fn simulate(
+        &self,
+        rpc_url: &str,
+        poll_id: u64,
+        mut votes: Vec<(Address, u64)>,
+        top_delegates_option: Option<(usize, u64)>,
+        anvil: &str,
+        format: &str,
+    ) -> Result<()> {
+        if !matches!(format.to_lowercase().as_str(), "table" | "json") {
+            bail_user!("Unsupported format: {}. Use table or json", format);
+        }
+        let poll_data = self.poll_data(U256::from(poll_id)).await?;
+        let now = chrono::Utc::now().timestamp() as u64;
+        if poll_data.6 != 0 || now >= poll_data.5.to_u64_saturating() {
+            bail_user!("Poll {} is no longer open, so no further votes can change it", poll_id);
+        }
+        for (_, option) in &votes {
+            if *option as usize >= poll_data.2.len() {
+                bail_user!("Option {} does not exist; poll {} has options 0..{}", option, poll_id, poll_data.2.len() - 1);
+            }
+        }
+
+        let index = self.event_index().await?;
+        if let Some((n, option)) = top_delegates_option {
+            if option as usize >= poll_data.2.len() {
+                bail_user!("Option {} does not exist; poll {} has options 0..{}", option, poll_id, poll_data.2.len() - 1);
+            }
+            let delegates = top_delegates(&index.delegations(), n);
+            if delegates.is_empty() {
+                bail_user!("Nobody has delegated on this contract yet, so there are no top delegates to simulate");
+            }
+            votes.extend(delegates.into_iter().map(|(delegate, _)| (delegate, option)));
+        }
+        if votes.is_empty() {
+            bail_user!("Nothing to simulate; pass --vote ADDRESS=OPTION or --top-delegates N --option O");
+        }
+
+        let before = Outcome::new(
+            self.poll_tally(poll_id, &poll_data.2, poll_data.7).await?,
+            poll_quorum(poll_data.10.to_u64_saturating(), poll_data.9, poll_data.4, poll_data.5, poll_data.6, now),
+        );
+
+        let fork_block = self.client.get_block_number().await?.as_u64();
+        let progress = output::spinner(&format!("Forking at block {}...", fork_block));
+        let fork = Fork::spawn(anvil, rpc_url, fork_block)?;
+        let provider = match Provider::<Http>::try_from(fork.rpc_url.as_str()) {
+            Ok(provider) => Arc::new(provider),
+            Err(e) => bail_config!("Invalid fork URL '{}': {}", fork.rpc_url, e),
+        };
+        let forked = PollManager::with_client(provider.clone(), self.wallet.clone(), self.contract.address());
+
+        let mut simulated = Vec::with_capacity(votes.len());
+        let mut logs = Vec::new();
+        for (voter, option_index) in votes {
+            progress.set_message(format!("Voting option {} as {:?}...", option_index, voter));
+            provider.request::<_, ()>("anvil_impersonateAccount", [voter]).await?;
+            provider.request::<_, ()>("anvil_setBalance", (voter, VOTER_BALANCE)).await?;
+
+            let call = forked.contract.vote(U256::from(poll_id), U256::from(option_index)).from(voter);
+            let mut vote = SimulatedVote {
+                voter,
+                option_index,
+                option: poll_data.2[option_index as usize].clone(),
+                cast: false,
+                weight: None,
+                error: None,
+            };
+            match call.send().await {
+                Ok(pending) => match pending.await? {
+                    Some(receipt) => {
+                        vote.cast = true;
+                        logs.extend(receipt.logs);
+                    }
+                    None => vote.error = Some("transaction was dropped".to_string()),
+                },
+                Err(e) => vote.error = Some(e.to_string()),
+            }
+            simulated.push(vote);
+        }
+
+        // The fork's votes join a copy of the real index; nothing is written to disk
+        let mut fork_index = (*index).clone();
+        let fork_events = forked.decode_logs(logs).await?;
+        for vote in simulated.iter_mut() {
+            vote.weight = fork_events.iter().find_map(|e| match e.event {
+                crate::indexer::ContractEvent::VoteCast { poll_id: id, voter, weight, .. } if id == poll_id && voter == vote.voter => Some(weight.to_u64_saturating()),
+                _ => None,
+            });
+        }
+        fork_index.events.extend(fork_events);
+        if let Ok(mut slot) = forked.index.lock() {
+            *slot = Some(Arc::new(fork_index));
+        }
+
+        let fork_poll = forked.poll_data(U256::from(poll_id)).await?;
+        let after = Outcome::new(
+            forked.poll_tally(poll_id, &fork_poll.2, fork_poll.7).await?,
+            poll_quorum(fork_poll.10.to_u64_saturating(), fork_poll.9, fork_poll.4, fork_poll.5, fork_poll.6, now),
+        );
+        progress.finish_and_clear();
+        drop(fork);
+
+        let report = SimulationReport {
+            poll_id,
+            question: poll_data.1.clone(),
+            fork_block,
+            votes: simulated,
+            outcome_changed: before.leader != after.leader || before.quorum.state != after.quorum.state,
+            before,
+            after,
+        };
+        match format.to_lowercase().as_str() {
+            "json" => println!("{}", serde_json::to_string_pretty(&report)?),
+            _ => print_simulation(&report),
+        }
+        Ok(())
+    }
+}
+
+fn print_simulation(report: &SimulationReport) {
+    say!("\n{}", "🧪 WHAT-IF SIMULATION".cyan().bold().underline());
+    say!("{}", "═".repeat(60).cyan());
+    say!("{} {} - {}", "Poll ID:".yellow().bold(), report.poll_id.to_string().white(), report.question.white().bold());
+    say!("{} {} {}", "Forked at block:".yellow().bold(), report.fork_block, "(nothing was sent to the real chain)".dimmed());
+
+    say!("\n{}", "🗳️  HYPOTHETICAL VOTES".cyan().bold());
+    say!("{}", "─".repeat(60).cyan());
+    for vote in &report.votes {
+        match (&vote.error, vote.weight) {
+            (Some(error), _) => say!("  {} {:?} → {}: {}", "✗".red(), vote.voter, vote.option, error.red()),
+            (None, Some(weight)) => say!("  {} {:?} → {} (weight {})", "✓".green(), vote.voter, vote.option, numbers::format_integer(weight)),
+            (None, None) => say!("  {} {:?} → {}", "✓".green(), vote.voter, vote.option),
+        }
+    }
+
+    let tally = &report.after.tally;
+    say!("\n{} {}", "📊 RESULTS".cyan().bold(), format!("(by {})", tally.winner_metric()).dimmed());
+    say!("{}", "─".repeat(60).cyan());
+    for (before, after) in report.before.tally.options.iter().zip(&tally.options) {
+        let (was, now) = if tally.weighted {
+            (numbers::format_integer(before.weight), numbers::format_integer(after.weight))
+        } else {
+            (numbers::format_integer(before.votes), numbers::format_integer(after.votes))
+        };
+        let change = if was == now { String::new() } else { format!("(was {})", was) };
+        say!("  {:<24} {:>8} {:>5.1}% {}", after.option, now, tally.deciding_share(after), change.dimmed());
+    }
+
+    let leader = |outcome: &Outcome| outcome.leader.clone().unwrap_or_else(|| "none (tie or no votes)".to_string());
+    say!("\n{}", "🏁 OUTCOME".cyan().bold());
+    say!("{}", "─".repeat(60).cyan());
+    say!("{} {} → {}", "Leader:".yellow().bold(), leader(&report.before), leader(&report.after).bold());
+    if report.after.quorum.state != quorum::QuorumState::NotRequired {
+        say!("{} {} → {}", "Quorum:".yellow().bold(), report.before.quorum.state, report.after.quorum.state.to_string().bold());
+    }
+    if report.outcome_changed {
+        say!("{}", "⚠️  These votes would change the outcome.".yellow().bold());
+    } else {
+        say!("{}", "✅ The outcome would stay the same.".green());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_hypothetical_votes() {
+        let (voter, option) = parse_hypothetical_vote("0x70997970C51812dc3A010C7d01b50e0d17dc79C8=2").unwrap();
+        assert_eq!(voter, "0x70997970C51812dc3A010C7d01b50e0d17dc79C8".parse::<Address>().unwrap());
+        assert_eq!(option, 2);
+        assert!(parse_hypothetical_vote("0x70997970C51812dc3A010C7d01b50e0d17dc79C8").is_err());
+        assert!(parse_hypothetical_vote("alice=1").is_err());
+    }
+
+    #[test]
+    fn ranks_delegates_by_delegator_count() {
+        let a = Address::from_low_u64_be(1);
+        let b = Address::from_low_u64_be(2);
+        let delegations = (10..13u64).map(|i| (Address::from_low_u64_be(i), b))
+            .chain([(Address::from_low_u64_be(20), a)])
+            .collect();
+        assert_eq!(top_delegates(&delegations, 5), vec![(b, 3), (a, 1)]);
+        assert_eq!(top_delegates(&delegations, 1), vec![(b, 3)]);
+    }
+}
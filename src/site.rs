@@ -0,0 +1,361 @@
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use colored::*;
+use ethers::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::chart::{self, Series};
+use crate::feed::xml_escape;
+use crate::numbers::SaturatingU64;
+use crate::preview::Card;
+use crate::tally::PollTally;
+use crate::timezone::{self, DisplayZone};
+use crate::{numbers, poll_quorum, poll_ref, store, u8_to_category, u8_to_status, PollManager};
+
+/// Build state and every poll's summary, kept in the output directory between builds
+const SITE_FILE: &str = "site.json";
+
+const STYLE: &str = "\
+body { font-family: Helvetica, Arial, sans-serif; max-width: 60rem; margin: 2rem auto; padding: 0 1rem; color: #222; }
+a { color: #4e79a7; }
+table { border-collapse: collapse; width: 100%; margin: 1rem 0; }
+th, td { text-align: left; padding: 0.4rem 0.6rem; border-bottom: 1px solid #ddd; }
+td.num { text-align: right; font-variant-numeric: tabular-nums; }
+.status { font-size: 0.85rem; padding: 0.1rem 0.4rem; border-radius: 0.3rem; background: #eee; }
+.status.Active { background: #dff0d8; }
+.tags a { margin-right: 0.5rem; }
+nav, footer { font-size: 0.9rem; color: #666; margin: 1rem 0; }
+svg { max-width: 100%; height: auto; }
+";
+
+/// What a poll page was built from. Any new event for the poll, or its voting period running
+/// out, changes it and the page is rebuilt.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct Fingerprint {
+    events: usize,
+    /// Block and log index of the poll's latest indexed event
+    last_event: Option<(u64, u64)>,
+    ended: bool,
+}
+
+/// A poll as the index, category and tag pages list it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PollSummary {
+    id: u64,
+    question: String,
+    status: String,
+    category: String,
+    tags: Vec<String>,
+    end_time: u64,
+    total_votes: u64,
+    leader: Option<String>,
+    fingerprint: Fingerprint,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SiteState {
+    chain_id: u64,
+    contract: Address,
+    polls: Vec<PollSummary>,
+}
+
+/// A lowercase, URL-safe file name for a category or tag
+fn slug(name: &str) -> String {
+    let slug: String = name.to_lowercase().chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    let slug = slug.split('-').filter(|part| !part.is_empty()).collect::<Vec<_>>().join("-");
+    if slug.is_empty() { "untitled".to_string() } else { slug }
+}
+
+/// A full page; `root` is the relative path back to the site root, `head` extra `<head>` tags
+fn page(site_title: &str, title: &str, root: &str, head: &str, body: &str) -> String {
+    format!(concat!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n",
+        "<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n",
+        "<title>{title} · {site}</title>\n<link rel=\"stylesheet\" href=\"{root}style.css\">\n{head}</head>\n<body>\n",
+        "<nav><a href=\"{root}index.html\">{site}</a></nav>\n{body}",
+        "<footer>Read from the chain by polling-cli. Data: <a href=\"{root}site.json\">site.json</a></footer>\n</body>\n</html>\n"),
+        title = xml_escape(title), site = xml_escape(site_title), root = root, head = head, body = body)
+}
+
+fn poll_table(polls: &[&PollSummary], root: &str) -> String {
+    let mut html = String::from("<table>\n<tr><th>#</th><th>Question</th><th>Status</th><th>Category</th><th>Votes</th><th>Leading</th><th>Ends</th></tr>\n");
+    for poll in polls {
+        html.push_str(&format!(
+            "<tr><td>{id}</td><td><a href=\"{root}polls/{id}.html\">{question}</a></td><td><span class=\"status {status}\">{status}</span></td>\
+             <td><a href=\"{root}categories/{category_slug}.html\">{category}</a></td><td class=\"num\">{votes}</td><td>{leader}</td><td>{ends}</td></tr>\n",
+            id = poll.id, root = root, question = xml_escape(&poll.question), status = xml_escape(&poll.status),
+            category_slug = slug(&poll.category), category = xml_escape(&poll.category),
+            votes = numbers::format_integer(poll.total_votes), leader = xml_escape(poll.leader.as_deref().unwrap_or("–")),
+            ends = timezone::format_in(poll.end_time, DisplayZone::Utc)));
+    }
+    html.push_str("</table>\n");
+    html
+}
+
+fn list_page(site_title: &str, heading: &str, polls: &[&PollSummary]) -> String {
+    let body = format!("<h1>{}</h1>\n{}", xml_escape(heading), poll_table(polls, "../"));
+    page(site_title, heading, "../", "", &body)
+}
+
+fn index_page(site_title: &str, state: &SiteState) -> String {
+    let polls: Vec<&PollSummary> = state.polls.iter().rev().collect();
+    let mut categories: BTreeMap<&str, usize> = BTreeMap::new();
+    let mut tags: BTreeMap<&str, usize> = BTreeMap::new();
+    for poll in &state.polls {
+        *categories.entry(&poll.category).or_default() += 1;
+        for tag in &poll.tags {
+            *tags.entry(tag).or_default() += 1;
+        }
+    }
+    let links = |dir: &str, names: &BTreeMap<&str, usize>| names.iter()
+        .map(|(name, count)| format!("<a href=\"{}/{}.html\">{}</a> ({})", dir, slug(name), xml_escape(name), count))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let mut body = format!("<h1>{}</h1>\n<p>{} polls on <code>{:?}</code> (chain {}).</p>\n",
+        xml_escape(site_title), state.polls.len(), state.contract, state.chain_id);
+    if !categories.is_empty() {
+        body.push_str(&format!("<p>Categories: {}</p>\n", links("categories", &categories)));
+    }
+    if !tags.is_empty() {
+        body.push_str(&format!("<p class=\"tags\">Tags: {}</p>\n", links("tags", &tags)));
+    }
+    body.push_str(&poll_table(&polls, ""));
+    page(site_title, "All polls", "", "", &body)
+}
+
+fn results_table(tally: &PollTally) -> String {
+    let mut html = String::from("<table>\n<tr><th>Option</th><th>Votes</th><th>Share</th>");
+    if tally.weighted {
+        html.push_str("<th>Weight</th><th>Weight share</th>");
+    }
+    html.push_str("</tr>\n");
+    for option in &tally.options {
+        html.push_str(&format!("<tr><td>{}</td><td class=\"num\">{}</td><td class=\"num\">{:.1}%</td>",
+            xml_escape(&option.option), numbers::format_integer(option.votes), option.vote_share));
+        if tally.weighted {
+            html.push_str(&format!("<td class=\"num\">{}</td><td class=\"num\">{:.1}%</td>", numbers::format_integer(option.weight), option.weight_share));
+        }
+        html.push_str("</tr>\n");
+    }
+    html.push_str("</table>\n");
+    html
+}
+
+/// Open Graph and Twitter card tags for a poll page. Crawlers want absolute URLs, so the image
+/// is only linked relatively when no `base_url` is known.
+fn preview_meta(site_title: &str, poll: &PollSummary, base_url: Option<&str>) -> String {
+    let (image, url) = match base_url.map(|base| base.trim_end_matches('/')) {
+        Some(base) => (format!("{}/polls/{}.png", base, poll.id), Some(format!("{}/polls/{}.html", base, poll.id))),
+        None => (format!("{}.png", poll.id), None),
+    };
+    let mut description = format!("{} · {} votes", poll.status, numbers::format_integer(poll.total_votes));
+    if let Some(leader) = &poll.leader {
+        description.push_str(&format!(" · Leading: {}", leader));
+    }
+    let mut tags = vec![
+        ("og:type", "website".to_string()),
+        ("og:site_name", site_title.to_string()),
+        ("og:title", poll.question.clone()),
+        ("og:description", description),
+        ("og:image", image),
+    ];
+    if let Some(url) = url {
+        tags.push(("og:url", url));
+    }
+    let mut html: String = tags.iter()
+        .map(|(property, content)| format!("<meta property=\"{}\" content=\"{}\">\n", property, xml_escape(content)))
+        .collect();
+    html.push_str("<meta name=\"twitter:card\" content=\"summary_large_image\">\n");
+    html
+}
+
+fn write(out: &Path, relative: &str, contents: impl AsRef<[u8]>) -> Result<()> {
+    let path = out.join(relative);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&path, contents).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+impl<M: Middleware + 'static> PollManager<M> {
+    /// Write a poll's page, preview image and JSON, returning its summary for the list pages
+    async fn build_poll_page(&self, out: &Path, site_title: &str, base_url: Option<&str>, poll_id: u64, fingerprint: Fingerprint) -> Result<PollSummary> {
+        let poll = self.poll_data(U256::from(poll_id)).await?;
+        let tally = self.poll_tally(poll_id, &poll.2, poll.7).await?;
+        let export = self.build_poll_export(poll_id).await?;
+        let end_time = poll.5.to_u64_saturating();
+        let status = if poll.6 == 0 && fingerprint.ended { "Ended" } else { u8_to_status(poll.6) };
+        let quorum = poll_quorum(tally.total_votes, poll.9, poll.4, poll.5, poll.6, self.now());
+        let summary = PollSummary {
+            id: poll_id,
+            question: poll.1.clone(),
+            status: status.to_string(),
+            category: u8_to_category(poll.8).to_string(),
+            tags: poll.13.clone(),
+            end_time,
+            total_votes: tally.total_votes,
+            leader: tally.leader().map(|leader| leader.option.clone()),
+            fingerprint,
+        };
+
+        let mut body = format!("<h1>#{} {}</h1>\n<p><span class=\"status {}\">{}</span> · <a href=\"../categories/{}.html\">{}</a></p>\n",
+            poll_id, xml_escape(&summary.question), status, status, slug(&summary.category), xml_escape(&summary.category));
+        if !poll.12.is_empty() {
+            body.push_str(&format!("<p>{}</p>\n", xml_escape(&poll.12).replace('\n', "<br>\n")));
+        }
+        let series = Series::from_tally(&summary.question, &tally);
+        body.push_str(&chart::svg_bars(&series));
+        body.push_str(&results_table(&tally));
+        body.push_str(&format!("<p>Total votes: {}. Winner decided by {}.", numbers::format_integer(tally.total_votes), tally.winner_metric()));
+        if let Some(quorum) = quorum.summary() {
+            body.push_str(&format!(" Quorum: {}.", xml_escape(&quorum)));
+        }
+        body.push_str("</p>\n<table>\n");
+        for (label, value) in [
+            ("Created", timezone::format_in(poll.4.to_u64_saturating(), DisplayZone::Utc)),
+            ("Ends", timezone::format_in(end_time, DisplayZone::Utc)),
+            ("Creator", format!("{:?}", poll.3)),
+            ("Reference", poll_ref::link(self.wallet.chain_id(), self.contract.address(), poll_id)),
+        ] {
+            body.push_str(&format!("<tr><th>{}</th><td><code>{}</code></td></tr>\n", label, xml_escape(&value)));
+        }
+        body.push_str("</table>\n");
+        if !summary.tags.is_empty() {
+            let tags: Vec<String> = summary.tags.iter()
+                .map(|tag| format!("<a href=\"../tags/{}.html\">{}</a>", slug(tag), xml_escape(tag)))
+                .collect();
+            body.push_str(&format!("<p class=\"tags\">Tags: {}</p>\n", tags.join(" ")));
+        }
+        body.push_str(&format!("<p><a href=\"{}.json\">Raw data (JSON)</a></p>\n", poll_id));
+
+        let card = Card { site: site_title, poll_id, status, end_time, series: &series };
+        write(out, &format!("polls/{}.png", poll_id), card.png()?)?;
+        let head = preview_meta(site_title, &summary, base_url);
+        write(out, &format!("polls/{}.html", poll_id), page(site_title, &summary.question, "../", &head, &body))?;
+        write(out, &format!("polls/{}.json", poll_id), serde_json::to_string_pretty(&export)?)?;
+        Ok(summary)
+    }
+
+    /// `site build`: render every poll into a static site under `out`. Only polls with new events
+    /// in the local index since the last build, or whose voting period has ended since, are read
+    /// from the chain again; `full` rebuilds everything.
+    pub async fn build_site(&self, out: &Path, site_title: &str, full: bool, base_url: Option<&str>) -> Result<()> {
+        let progress = crate::output::spinner("🔄 Syncing contract events...");
+        let index = self.event_index().await;
+        progress.finish_and_clear();
+        let index = index?;
+
+        let state_path = out.join(SITE_FILE);
+        let previous: SiteState = if full || !state_path.exists() { SiteState::default() } else { store::load_from(&state_path)? };
+        let same_contract = previous.chain_id == self.wallet.chain_id() && previous.contract == self.contract.address();
+        let mut previous: HashMap<u64, PollSummary> = if same_contract {
+            previous.polls.into_iter().map(|poll| (poll.id, poll)).collect()
+        } else {
+            HashMap::new()
+        };
+
+        let mut events: HashMap<u64, (usize, (u64, u64))> = HashMap::new();
+        for event in &index.events {
+            if let Some(poll_id) = event.event.poll_id() {
+                let entry = events.entry(poll_id).or_insert((0, (0, 0)));
+                entry.0 += 1;
+                entry.1 = entry.1.max((event.block_number, event.log_index));
+            }
+        }
+
+        let count = self.contract.poll_count().call().await?.to_u64_saturating();
+        let now = self.now();
+        let mut state = SiteState { chain_id: self.wallet.chain_id(), contract: self.contract.address(), polls: Vec::new() };
+        let mut rebuilt = 0;
+        for poll_id in 0..count {
+            let (events, last_event) = events.get(&poll_id).map_or((0, None), |(n, last)| (*n, Some(*last)));
+            let ended = previous.get(&poll_id).is_some_and(|poll| poll.fingerprint.ended);
+            let mut fingerprint = Fingerprint { events, last_event, ended };
+            match previous.remove(&poll_id) {
+                // The end time is only known once the poll is read, so an open poll is checked against the cached one
+                Some(poll) if poll.fingerprint == fingerprint && (ended || now < poll.end_time) => state.polls.push(poll),
+                _ => {
+                    let end_time = self.poll_data(U256::from(poll_id)).await?.5.to_u64_saturating();
+                    fingerprint.ended = now >= end_time;
+                    state.polls.push(self.build_poll_page(out, site_title, base_url, poll_id, fingerprint).await?);
+                    rebuilt += 1;
+                }
+            }
+        }
+
+        let mut categories: BTreeMap<String, Vec<&PollSummary>> = BTreeMap::new();
+        let mut tags: BTreeMap<String, Vec<&PollSummary>> = BTreeMap::new();
+        for poll in state.polls.iter().rev() {
+            categories.entry(poll.category.clone()).or_default().push(poll);
+            for tag in &poll.tags {
+                tags.entry(tag.clone()).or_default().push(poll);
+            }
+        }
+        for (category, polls) in &categories {
+            write(out, &format!("categories/{}.html", slug(category)), list_page(site_title, &format!("Category: {}", category), polls))?;
+        }
+        for (tag, polls) in &tags {
+            write(out, &format!("tags/{}.html", slug(tag)), list_page(site_title, &format!("Tag: {}", tag), polls))?;
+        }
+        write(out, "index.html", index_page(site_title, &state))?;
+        write(out, "style.css", STYLE)?;
+        // GitHub Pages would otherwise run the files through Jekyll
+        write(out, ".nojekyll", "")?;
+        store::save_to(&state_path, &state)?;
+
+        say!("{} {}: {} polls, {} rebuilt from the chain", "✅ Site built in".green().bold(),
+            out.display().to_string().yellow(), state.polls.len(), rebuilt);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(id: u64, category: &str, tags: &[&str]) -> PollSummary {
+        PollSummary {
+            id,
+            question: format!("Question <{}>?", id),
+            status: "Active".to_string(),
+            category: category.to_string(),
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+            end_time: 1_700_000_000,
+            total_votes: 12,
+            leader: None,
+            fingerprint: Fingerprint { events: 0, last_event: None, ended: false },
+        }
+    }
+
+    #[test]
+    fn links_polls_categories_and_tags() {
+        assert_eq!(slug("Treasury & Grants"), "treasury-grants");
+        assert_eq!(slug("Q3-2025"), "q3-2025");
+        assert_eq!(slug("🚀"), "untitled");
+
+        let state = SiteState {
+            chain_id: 31337,
+            contract: Address::zero(),
+            polls: vec![summary(0, "Governance", &["Treasury & Grants"]), summary(1, "General", &[])],
+        };
+        let html = index_page("Results", &state);
+        assert!(html.contains("<a href=\"polls/1.html\">Question &lt;1&gt;?</a>"));
+        assert!(html.find("polls/1.html") < html.find("polls/0.html"), "newest first");
+        assert!(html.contains("<a href=\"categories/governance.html\">Governance</a> (1)"));
+        assert!(html.contains("<a href=\"tags/treasury-grants.html\">Treasury &amp; Grants</a> (1)"));
+        assert!(html.contains("<td class=\"num\">12</td>"));
+
+        let tag_page = list_page("Results", "Tag: x", &[&state.polls[0]]);
+        assert!(tag_page.contains("href=\"../polls/0.html\"") && tag_page.contains("href=\"../style.css\""));
+
+        let meta = preview_meta("Results", &state.polls[0], Some("https://dao.example/results/"));
+        assert!(meta.contains("<meta property=\"og:image\" content=\"https://dao.example/results/polls/0.png\">"));
+        assert!(meta.contains("<meta property=\"og:title\" content=\"Question &lt;0&gt;?\">"));
+        assert!(preview_meta("Results", &state.polls[0], None).contains("content=\"0.png\""));
+    }
+}
@@ -0,0 +1,176 @@
+use anyhow::{Context, Result};
+use colored::*;
+use ethers::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::numbers::SaturatingU64;
+use crate::error::bail_user;
+use crate::{u256_to_f64, GovernanceToken, PollManager};
+
+/// Voting strategy entry of a Snapshot proposal
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotStrategy {
+    pub name: String,
+    #[serde(default)]
+    pub network: Option<String>,
+    #[serde(default)]
+    pub params: serde_json::Value,
+}
+
+/// Subset of the Snapshot.org proposal schema that maps onto an on-chain poll
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotProposal {
+    pub space: String,
+    #[serde(rename = "type")]
+    pub voting_type: String,
+    pub title: String,
+    #[serde(default)]
+    pub body: String,
+    #[serde(default)]
+    pub discussion: String,
+    pub choices: Vec<String>,
+    pub start: u64,
+    pub end: u64,
+    #[serde(default)]
+    pub snapshot: String,
+    #[serde(default)]
+    pub strategies: Vec<SnapshotStrategy>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scores: Option<Vec<f64>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scores_total: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub votes: Option<u64>,
+}
+
+/// On-chain poll type and optional voting token implied by a Snapshot proposal
+fn poll_type_for_proposal(proposal: &SnapshotProposal) -> Result<(&'static str, Option<String>)> {
+    let token = proposal.strategies.iter()
+        .find(|s| s.name == "erc20-balance-of" || s.name == "erc20-votes")
+        .and_then(|s| s.params.get("address"))
+        .and_then(|a| a.as_str())
+        .map(|a| a.to_string());
+
+    let poll_type = match proposal.voting_type.as_str() {
+        "quadratic" => "quadratic",
+        "single-choice" | "basic" if token.is_some() => "weighted",
+        "single-choice" | "basic" => "standard",
+        other => bail_user!("Snapshot voting type '{}' has no on-chain equivalent. Supported: single-choice, basic, quadratic", other),
+    };
+
+    Ok((poll_type, token))
+}
+
+impl<M: Middleware + 'static> PollManager<M> {
+    pub async fn snapshot_export(&self, poll_id: u64, space: &str, output_path: Option<String>) -> Result<()> {
+        let poll_data = self.poll_data(U256::from(poll_id)).await?;
+        let now = chrono::Utc::now().timestamp() as u64;
+        if poll_data.6 == 0 && now < poll_data.5.to_u64_saturating() {
+            bail_user!("Poll {} is still active; only closed polls can be exported to Snapshot", poll_id);
+        }
+
+        let (results, total_votes, total_weight) = self.poll_results(U256::from(poll_id)).await?;
+
+        let created = self.contract
+            .poll_created_filter()
+            .topic1(U256::from(poll_id))
+            .from_block(0u64)
+            .query_with_meta()
+            .await?;
+        let snapshot_block = created.first().map(|(_, meta)| meta.block_number.to_string()).unwrap_or_default();
+
+        // getPoll leaves out the token gate; the public polls() getter has it
+        let gate = self.contract.polls(U256::from(poll_id)).call().await?;
+        let (requires_token, poll_token) = (gate.11, gate.12);
+        let (voting_type, strategy) = match poll_data.7 {
+            2 => ("quadratic", None),
+            1 if requires_token => ("single-choice", Some(poll_token)),
+            _ => ("single-choice", None),
+        };
+        let strategies = match strategy {
+            Some(address) => vec![SnapshotStrategy {
+                name: "erc20-balance-of".to_string(),
+                network: Some(self.wallet.chain_id().to_string()),
+                params: serde_json::json!({
+                    "address": format!("{:?}", address),
+                    "decimals": GovernanceToken::new(address, self.client.clone()).decimals().call().await?,
+                }),
+            }],
+            None => vec![SnapshotStrategy {
+                name: "ticket".to_string(),
+                network: Some(self.wallet.chain_id().to_string()),
+                params: serde_json::json!({ "symbol": "VOTE" }),
+            }],
+        };
+
+        let proposal = SnapshotProposal {
+            space: space.to_string(),
+            voting_type: voting_type.to_string(),
+            title: poll_data.1.clone(),
+            body: poll_data.12.clone(),
+            discussion: String::new(),
+            choices: poll_data.2.clone(),
+            start: poll_data.4.to_u64_saturating(),
+            end: poll_data.5.to_u64_saturating(),
+            snapshot: snapshot_block,
+            strategies,
+            state: Some("closed".to_string()),
+            scores: Some(results.iter().map(|v| u256_to_f64(*v)).collect()),
+            scores_total: Some(u256_to_f64(total_weight)),
+            votes: Some(total_votes.to_u64_saturating()),
+        };
+
+        let json_output = serde_json::to_string_pretty(&proposal)?;
+        if let Some(path) = output_path {
+            std::fs::write(&path, &json_output)?;
+            say!("{} {}", "✅ Snapshot proposal exported to:".green().bold(), path.yellow());
+        } else {
+            println!("{}", json_output);
+        }
+
+        Ok(())
+    }
+
+    pub async fn snapshot_import(&self, file: &str, category: &str, min_participation: u64) -> Result<()> {
+        let content = std::fs::read_to_string(file).with_context(|| format!("Failed to read {}", file))?;
+        let proposal: SnapshotProposal = serde_json::from_str(&content)
+            .with_context(|| format!("{} is not a valid Snapshot proposal", file))?;
+
+        if proposal.choices.len() < 2 {
+            bail_user!("Snapshot proposal must have at least 2 choices");
+        }
+        if proposal.end <= proposal.start {
+            bail_user!("Snapshot proposal ends before it starts");
+        }
+
+        let (poll_type, token_address) = poll_type_for_proposal(&proposal)?;
+
+        // Preserve the Snapshot voting window length
+        let duration_seconds = proposal.end - proposal.start;
+
+        let description = if proposal.discussion.is_empty() {
+            proposal.body.clone()
+        } else {
+            format!("{}\n\nDiscussion: {}", proposal.body, proposal.discussion)
+        };
+        let tags = format!("snapshot,{}", proposal.space);
+
+        say!("{} {}", "📥 Importing Snapshot proposal from".cyan().bold(), file.yellow());
+        self.create_enhanced_poll(
+            proposal.title.clone(),
+            proposal.choices.clone(),
+            duration_seconds,
+            poll_type,
+            category,
+            min_participation,
+            token_address,
+            0,
+            Some(description),
+            Some(tags),
+        ).await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,320 @@
+use std::io::Read;
+
+use anyhow::{Context, Result};
+use clap::Args;
+use ethers::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::error::bail_user;
+use crate::templates::PollTemplate;
+use crate::{duration, events, format_timestamp, PollManager, CATEGORIES, POLL_TYPES};
+
+pub const DEFAULT_DURATION: u64 = 7 * 86_400;
+pub const DEFAULT_POLL_TYPE: &str = "standard";
+pub const DEFAULT_CATEGORY: &str = "general";
+
+/// Poll definition flags shared by `create`, `template save/apply` and drafts.
+///
+/// Everything is optional so a template can fill in what was not given on the
+/// command line; `resolve` applies the built-in defaults last.
+#[derive(Args, Debug, Clone, Default)]
+pub struct PollFields {
+    /// Question for the poll
+    #[arg(short, long)]
+    pub question: Option<String>,
+    /// Poll options (comma-separated)
+    #[arg(short, long)]
+    pub options: Option<String>,
+    /// Duration such as 3d12h, 90m or 2w; a bare number is hours [default: 7d]
+    #[arg(short, long, value_parser = duration::parse, conflicts_with = "ends_at")]
+    pub duration: Option<u64>,
+    /// Absolute end time instead of a duration, e.g. 2025-07-01T18:00Z
+    #[arg(long, value_parser = duration::parse_ends_at)]
+    pub ends_at: Option<u64>,
+    /// Poll type: standard, weighted, quadratic [default: standard]
+    #[arg(short = 't', long, value_parser = POLL_TYPES, ignore_case = true)]
+    pub poll_type: Option<String>,
+    /// Category: general, governance, technical, community, finance [default: general]
+    #[arg(short = 'c', long, value_parser = CATEGORIES, ignore_case = true)]
+    pub category: Option<String>,
+    /// Minimum participation required [default: 0]
+    #[arg(short = 'm', long)]
+    pub min_participation: Option<u64>,
+    /// Token address for weighted/quadratic voting (optional)
+    #[arg(long)]
+    pub token_address: Option<String>,
+    /// Minimum token balance required to vote [default: 0]
+    #[arg(long)]
+    pub min_token_balance: Option<u64>,
+    /// Extended description of the poll
+    #[arg(long)]
+    pub description: Option<String>,
+    /// Tags for the poll (comma-separated)
+    #[arg(long)]
+    pub tags: Option<String>,
+    /// Read poll fields from a YAML/JSON file, or `-` for stdin; flags override the file
+    #[arg(long, value_name = "PATH")]
+    pub from_file: Option<String>,
+}
+
+/// A list written either as a YAML sequence or a comma-separated string
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum ListField {
+    Items(Vec<String>),
+    Text(String),
+}
+
+impl ListField {
+    fn into_flag(self) -> String {
+        match self {
+            ListField::Items(items) => items.join(","),
+            ListField::Text(text) => text,
+        }
+    }
+}
+
+/// A duration written as `3d12h` or as a bare number of hours
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum DurationField {
+    Hours(u64),
+    Text(String),
+}
+
+/// On-disk form of `PollFields`, e.g. `poll.yaml`:
+///
+/// ```yaml
+/// question: "Fund grant #42?"   # quote text containing " #"
+/// description: |
+///   Multi-line context for voters.
+/// options: [For, Against, Abstain]
+/// duration: 5d
+/// category: governance
+/// tags: [grants]
+/// ```
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PollFile {
+    question: Option<String>,
+    options: Option<ListField>,
+    duration: Option<DurationField>,
+    ends_at: Option<String>,
+    poll_type: Option<String>,
+    category: Option<String>,
+    min_participation: Option<u64>,
+    token_address: Option<String>,
+    min_token_balance: Option<u64>,
+    description: Option<String>,
+    tags: Option<ListField>,
+}
+
+fn choice(value: Option<String>, allowed: &[&str], field: &str) -> Result<Option<String>> {
+    match value.map(|v| v.to_lowercase()) {
+        Some(v) if !allowed.contains(&v.as_str()) => bail_user!("Invalid {} '{}'. Use: {}", field, v, allowed.join(", ")),
+        other => Ok(other),
+    }
+}
+
+/// Parse a poll file's contents; YAML is a superset of JSON, so both work
+fn parse_poll_file(content: &str) -> Result<PollFields> {
+    let file: PollFile = match serde_yaml::from_str(content) {
+        Ok(file) => file,
+        Err(e) => bail_user!("Invalid poll file: {}", e),
+    };
+    let duration = match file.duration {
+        Some(DurationField::Hours(hours)) => Some(duration::parse(&hours.to_string())),
+        Some(DurationField::Text(text)) => Some(duration::parse(&text)),
+        None => None,
+    };
+    let duration = match duration.transpose() {
+        Ok(duration) => duration,
+        Err(e) => bail_user!("Invalid duration in poll file: {}", e),
+    };
+    let ends_at = match file.ends_at.as_deref().map(duration::parse_ends_at).transpose() {
+        Ok(ends_at) => ends_at,
+        Err(e) => bail_user!("Invalid ends_at in poll file: {}", e),
+    };
+
+    Ok(PollFields {
+        question: file.question.map(|q| q.trim().to_string()),
+        options: file.options.map(ListField::into_flag),
+        duration,
+        ends_at,
+        poll_type: choice(file.poll_type, &POLL_TYPES, "poll_type")?,
+        category: choice(file.category, &CATEGORIES, "category")?,
+        min_participation: file.min_participation,
+        token_address: file.token_address,
+        min_token_balance: file.min_token_balance,
+        description: file.description.map(|d| d.trim_end().to_string()),
+        tags: file.tags.map(ListField::into_flag),
+        from_file: None,
+    })
+}
+
+/// A complete poll definition, ready to submit
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PollSpec {
+    pub question: String,
+    pub options: Vec<String>,
+    /// Seconds from submission; ignored when `ends_at` is set
+    pub duration: u64,
+    pub ends_at: Option<u64>,
+    pub poll_type: String,
+    pub category: String,
+    pub min_participation: u64,
+    pub token_address: Option<String>,
+    pub min_token_balance: u64,
+    pub description: Option<String>,
+    pub tags: Vec<String>,
+}
+
+pub fn split_list(list: &str) -> Vec<String> {
+    list.split(',').map(|s| s.trim().to_string()).filter(|s| !s.is_empty()).collect()
+}
+
+impl PollFields {
+    /// Fill fields not given as flags from `--from-file`, if set
+    pub fn load_file(self) -> Result<Self> {
+        let Some(path) = &self.from_file else { return Ok(self) };
+        let content = if path == "-" {
+            let mut content = String::new();
+            std::io::stdin().read_to_string(&mut content).context("Failed to read poll from stdin")?;
+            content
+        } else {
+            std::fs::read_to_string(path).with_context(|| format!("Failed to read {}", path))?
+        };
+        Ok(self.or(parse_poll_file(&content)?))
+    }
+
+    /// A complete poll from a poll file's contents, e.g. a manifest sent to `serve`
+    pub fn parse_manifest(content: &str) -> Result<PollSpec> {
+        parse_poll_file(content)?.resolve_loaded(None)
+    }
+
+    /// Each field from `self`, falling back to `other`
+    pub(crate) fn or(self, other: PollFields) -> Self {
+        // An explicit --duration or --ends-at on the command line replaces both from the file
+        let timing_flag = self.duration.is_some() || self.ends_at.is_some();
+        PollFields {
+            question: self.question.or(other.question),
+            options: self.options.or(other.options),
+            duration: if timing_flag { self.duration } else { other.duration },
+            ends_at: if timing_flag { self.ends_at } else { other.ends_at },
+            poll_type: self.poll_type.or(other.poll_type),
+            category: self.category.or(other.category),
+            min_participation: self.min_participation.or(other.min_participation),
+            token_address: self.token_address.or(other.token_address),
+            min_token_balance: self.min_token_balance.or(other.min_token_balance),
+            description: self.description.or(other.description),
+            tags: self.tags.or(other.tags),
+            from_file: None,
+        }
+    }
+
+    /// Fill unset fields from `--from-file`, then `template`, then the built-in defaults
+    pub fn resolve(self, template: Option<&PollTemplate>) -> Result<PollSpec> {
+        let fields = self.load_file()?;
+        fields.resolve_loaded(template)
+    }
+
+    fn resolve_loaded(self, template: Option<&PollTemplate>) -> Result<PollSpec> {
+        let Some(question) = self.question.filter(|q| !q.trim().is_empty()) else {
+            bail_user!("A question is required (--question)");
+        };
+        let options = match (self.options, template) {
+            // Empty entries are kept so linting can point them out
+            (Some(options), _) => options.split(',').map(|s| s.trim().to_string()).collect(),
+            (None, Some(template)) => template.options.clone(),
+            (None, None) => bail_user!("Options are required (--options), or use a template"),
+        };
+        if options.len() < 2 {
+            bail_user!("Poll must have at least 2 options");
+        }
+
+        Ok(PollSpec {
+            question,
+            options,
+            duration: self.duration.or(template.map(|t| t.duration)).unwrap_or(DEFAULT_DURATION),
+            ends_at: self.ends_at,
+            poll_type: self.poll_type.or(template.map(|t| t.poll_type.clone())).unwrap_or_else(|| DEFAULT_POLL_TYPE.to_string()),
+            category: self.category.or(template.map(|t| t.category.clone())).unwrap_or_else(|| DEFAULT_CATEGORY.to_string()),
+            min_participation: self.min_participation.or(template.map(|t| t.min_participation)).unwrap_or_default(),
+            token_address: self.token_address.or(template.and_then(|t| t.token_address.clone())),
+            min_token_balance: self.min_token_balance.or(template.map(|t| t.min_token_balance)).unwrap_or_default(),
+            description: self.description.or(template.and_then(|t| t.description.clone())),
+            tags: match self.tags {
+                Some(tags) => split_list(&tags),
+                None => template.map(|t| t.tags.clone()).unwrap_or_default(),
+            },
+        })
+    }
+}
+
+impl PollSpec {
+    /// Seconds to pass to `createPoll`; an absolute end is measured from now
+    pub fn duration_seconds(&self) -> Result<u64> {
+        match self.ends_at {
+            Some(ends_at) => match ends_at.checked_sub(chrono::Utc::now().timestamp() as u64) {
+                Some(seconds) if seconds > 0 => Ok(seconds),
+                _ => bail_user!("End time {} is in the past", format_timestamp(ends_at)),
+            },
+            None => Ok(self.duration),
+        }
+    }
+}
+
+impl<M: Middleware + 'static> PollManager<M> {
+    pub async fn create_from_spec(&self, spec: &PollSpec) -> Result<events::CreatedPoll> {
+        let tags = (!spec.tags.is_empty()).then(|| spec.tags.join(","));
+        self.create_enhanced_poll(
+            spec.question.clone(),
+            spec.options.clone(),
+            spec.duration_seconds()?,
+            &spec.poll_type,
+            &spec.category,
+            spec.min_participation,
+            spec.token_address.clone(),
+            spec.min_token_balance,
+            spec.description.clone(),
+            tags,
+        ).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POLL_YAML: &str = "question: \"Fund grant #42?\"\ndescription: |\n  Line one.\n  Line two, with \"quotes\".\noptions: [For, Against, Abstain]\nduration: 5d\ncategory: Governance\ntags: grants, q3\n";
+
+    #[test]
+    fn parses_yaml_poll_files() {
+        let fields = parse_poll_file(POLL_YAML).unwrap();
+        let spec = fields.resolve(None).unwrap();
+        assert_eq!(spec.question, "Fund grant #42?");
+        assert_eq!(spec.description.as_deref(), Some("Line one.\nLine two, with \"quotes\"."));
+        assert_eq!(spec.options, ["For", "Against", "Abstain"]);
+        assert_eq!(spec.duration, 5 * 86_400);
+        assert_eq!(spec.category, "governance");
+        assert_eq!(spec.tags, ["grants", "q3"]);
+    }
+
+    #[test]
+    fn json_and_bare_hours_work_too() {
+        let fields = parse_poll_file(r#"{"question": "Ship?", "options": "Yes,No", "duration": 24}"#).unwrap();
+        assert_eq!(fields.duration, Some(24 * 3_600));
+        assert!(parse_poll_file("question: Ship?\noptoins: [Yes, No]\n").is_err());
+        assert!(parse_poll_file("poll_type: ranked\n").is_err());
+    }
+
+    #[test]
+    fn flags_override_file() {
+        let flags = PollFields { question: Some("Fund grant #43?".to_string()), ends_at: Some(1_751_392_800), ..Default::default() };
+        let spec = flags.or(parse_poll_file(POLL_YAML).unwrap()).resolve(None).unwrap();
+        assert_eq!(spec.question, "Fund grant #43?");
+        assert_eq!(spec.options.len(), 3);
+        assert_eq!(spec.ends_at, Some(1_751_392_800));
+        assert_eq!(spec.duration, DEFAULT_DURATION);
+    }
+}
@@ -0,0 +1,48 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::error::bail_config;
+
+/// Directory for local CLI state (templates, drafts, ...): `POLL_CLI_HOME`,
+/// else `polling-cli` under the platform data directory
+pub fn data_dir() -> Result<PathBuf> {
+    let dir = match std::env::var_os("POLL_CLI_HOME").filter(|v| !v.is_empty()) {
+        Some(dir) => PathBuf::from(dir),
+        None => match dirs::data_dir() {
+            Some(dir) => dir.join("polling-cli"),
+            None => bail_config!("No data directory on this platform; set POLL_CLI_HOME"),
+        },
+    };
+    std::fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    Ok(dir)
+}
+
+/// Read a JSON file, or the default value if it does not exist yet
+pub fn load_from<T: DeserializeOwned + Default>(path: &Path) -> Result<T> {
+    match std::fs::read_to_string(path) {
+        Ok(content) => serde_json::from_str(&content).with_context(|| format!("{} is corrupt", path.display())),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(T::default()),
+        Err(e) => Err(e).with_context(|| format!("Failed to read {}", path.display())),
+    }
+}
+
+/// Write a JSON file through a temporary file, so a crash never leaves it half-written
+pub fn save_to<T: Serialize>(path: &Path, value: &T) -> Result<()> {
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, serde_json::to_string_pretty(value)?).with_context(|| format!("Failed to write {}", tmp.display()))?;
+    std::fs::rename(&tmp, path).with_context(|| format!("Failed to replace {}", path.display()))?;
+    Ok(())
+}
+
+/// `load_from` for a file in the data directory
+pub fn load<T: DeserializeOwned + Default>(file: &str) -> Result<T> {
+    load_from(&data_dir()?.join(file))
+}
+
+/// `save_to` for a file in the data directory
+pub fn save<T: Serialize>(file: &str, value: &T) -> Result<()> {
+    save_to(&data_dir()?.join(file), value)
+}
@@ -0,0 +1,303 @@
+use std::collections::BTreeMap;
+
+use anyhow::{Context, Result};
+use colored::*;
+use ethers::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::error::bail_user;
+use crate::events::VoteRecord;
+use crate::identity::{IdentityScore, IdentityScorer};
+use crate::numbers::SaturatingU64;
+use crate::{u256_to_f64, PollManager};
+
+/// An address that had sent at most this many transactions before its vote counts as fresh
+const FRESH_NONCE: u64 = 1;
+/// Fresh voters this many blocks apart or closer form one cluster
+const CLUSTER_WINDOW: u64 = 3;
+const CLUSTER_MIN_VOTERS: usize = 3;
+/// Fresh voters sharing one first funder at or above this count are flagged
+const SHARED_FUNDER_MIN: usize = 3;
+/// Funding lookups cost a binary search over archive state each; bound them
+const MAX_FUNDER_LOOKUPS: usize = 100;
+/// Identity scores looked up per report, one API request each
+const MAX_SCORE_LOOKUPS: usize = 100;
+/// The closing stretch of a poll, as a fraction of its duration
+const LATE_FRACTION: f64 = 0.1;
+/// Share of all weight arriving in the closing stretch that counts as a spike
+const LATE_SHARE_FLAG: f64 = 50.0;
+
+/// Fresh voters whose votes landed within a few blocks of each other
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BlockCluster {
+    pub first_block: u64,
+    pub last_block: u64,
+    pub voters: Vec<Address>,
+}
+
+/// Fresh voters first funded by the same address
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FunderGroup {
+    pub funder: Address,
+    pub voters: Vec<Address>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SybilReport {
+    pub poll_id: u64,
+    pub question: String,
+    pub voters: usize,
+    pub fresh_voters: Vec<Address>,
+    pub clusters: Vec<BlockCluster>,
+    pub shared_funders: Vec<FunderGroup>,
+    /// Fresh voters whose funder was not looked up because of the lookup cap
+    pub funders_skipped: usize,
+    /// Percent of all weight cast in the last tenth of the poll
+    pub late_weight_share: f64,
+    /// External identity scores of the first voters; empty without IDENTITY_SCORE_URL
+    pub identity_scores: Vec<IdentityScore>,
+    /// Voters whose score was not looked up because of the lookup cap
+    pub scores_skipped: usize,
+    pub flags: Vec<String>,
+    /// low, medium or high: how many kinds of pattern were found
+    pub risk: String,
+}
+
+/// Group fresh votes `(block, voter)` that fall within `CLUSTER_WINDOW` blocks of the group's first
+pub fn block_clusters(fresh_votes: &[(u64, Address)]) -> Vec<BlockCluster> {
+    let mut sorted = fresh_votes.to_vec();
+    sorted.sort();
+    let mut clusters: Vec<BlockCluster> = Vec::new();
+    for (block, voter) in sorted {
+        match clusters.last_mut() {
+            Some(cluster) if block - cluster.first_block <= CLUSTER_WINDOW => {
+                cluster.last_block = block;
+                cluster.voters.push(voter);
+            }
+            _ => clusters.push(BlockCluster { first_block: block, last_block: block, voters: vec![voter] }),
+        }
+    }
+    clusters.retain(|cluster| cluster.voters.len() >= CLUSTER_MIN_VOTERS);
+    clusters
+}
+
+/// Funders shared by at least `SHARED_FUNDER_MIN` of the `(voter, funder)` pairs
+pub fn shared_funders(funded: &[(Address, Address)]) -> Vec<FunderGroup> {
+    let mut by_funder: BTreeMap<Address, Vec<Address>> = BTreeMap::new();
+    for (voter, funder) in funded {
+        by_funder.entry(*funder).or_default().push(*voter);
+    }
+    let mut groups: Vec<FunderGroup> = by_funder.into_iter()
+        .filter(|(_, voters)| voters.len() >= SHARED_FUNDER_MIN)
+        .map(|(funder, voters)| FunderGroup { funder, voters })
+        .collect();
+    groups.sort_by_key(|group| std::cmp::Reverse(group.voters.len()));
+    groups
+}
+
+/// Percent of the total weight cast in the last `LATE_FRACTION` of `created_at..end_time`
+pub fn late_weight_share(votes: &[VoteRecord], created_at: u64, end_time: u64) -> f64 {
+    let total: f64 = votes.iter().map(|v| u256_to_f64(v.weight)).sum();
+    if total == 0.0 || end_time <= created_at {
+        return 0.0;
+    }
+    let late_from = end_time - ((end_time - created_at) as f64 * LATE_FRACTION) as u64;
+    let late: f64 = votes.iter().filter(|v| v.timestamp >= late_from).map(|v| u256_to_f64(v.weight)).sum();
+    late / total * 100.0
+}
+
+fn print_report(report: &SybilReport) {
+    say!("\n{}", "🕵️  ELECTION REVIEW".cyan().bold().underline());
+    say!("{}", "═".repeat(60).cyan());
+    say!("{} {} - {}", "Poll ID:".yellow().bold(), report.poll_id.to_string().white(), report.question.white().bold());
+    say!("{} {}", "Voters:".yellow().bold(), report.voters);
+    say!("{} {} (at most {} earlier transaction{})", "Fresh Addresses:".yellow().bold(),
+        report.fresh_voters.len(), FRESH_NONCE, if FRESH_NONCE == 1 { "" } else { "s" });
+    say!("{} {:.1}% of weight in the last {:.0}% of the poll", "Late Weight:".yellow().bold(), report.late_weight_share, LATE_FRACTION * 100.0);
+
+    if !report.clusters.is_empty() {
+        say!("\n{}", "🧱 FRESH VOTERS IN THE SAME BLOCKS".cyan().bold());
+        for cluster in &report.clusters {
+            say!("  Blocks {}-{}: {} fresh voters", cluster.first_block, cluster.last_block, cluster.voters.len());
+            for voter in &cluster.voters {
+                say!("    {:?}", voter);
+            }
+        }
+    }
+    if !report.shared_funders.is_empty() {
+        say!("\n{}", "💸 SHARED FIRST FUNDERS".cyan().bold());
+        for group in &report.shared_funders {
+            say!("  {:?} funded {} voters", group.funder, group.voters.len());
+            for voter in &group.voters {
+                say!("    {:?}", voter);
+            }
+        }
+    }
+    if report.funders_skipped > 0 {
+        say!("{}", format!("Funders of {} fresh voters were not looked up (limit {}).", report.funders_skipped, MAX_FUNDER_LOOKUPS).yellow());
+    }
+    if !report.identity_scores.is_empty() {
+        let failing: Vec<&IdentityScore> = report.identity_scores.iter().filter(|s| s.passing == Some(false)).collect();
+        let average = report.identity_scores.iter().map(|s| s.score).sum::<f64>() / report.identity_scores.len() as f64;
+        say!("\n{}", "🪪 IDENTITY SCORES".cyan().bold());
+        say!("  {} voters scored, average {:.1}, {} below the minimum", report.identity_scores.len(), average, failing.len());
+        for score in failing {
+            say!("    {:?} {:.1}", score.address, score.score);
+        }
+        if report.scores_skipped > 0 {
+            say!("{}", format!("{} voters were not scored (limit {}).", report.scores_skipped, MAX_SCORE_LOOKUPS).yellow());
+        }
+    }
+
+    say!("\n{}", "🚩 FINDINGS".cyan().bold());
+    if report.flags.is_empty() {
+        say!("  No suspicious patterns found.");
+    }
+    for flag in &report.flags {
+        say!("  - {}", flag);
+    }
+    let risk = match report.risk.as_str() {
+        "high" => report.risk.red().bold(),
+        "medium" => report.risk.yellow().bold(),
+        _ => report.risk.green().bold(),
+    };
+    say!("{} {}", "Risk:".yellow().bold(), risk);
+}
+
+impl<M: Middleware + 'static> PollManager<M> {
+    /// First address to send `voter` ETH: the first block where its balance is non-zero,
+    /// found by binary search over archive state, then the transfer in that block
+    async fn first_funder(&self, voter: Address, before_block: u64) -> Result<Option<Address>> {
+        let (mut low, mut high) = (0u64, before_block);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if self.client.get_balance(voter, Some(BlockId::Number(mid.into()))).await?.is_zero() {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        let Some(block) = self.client.get_block_with_txs(low).await? else { return Ok(None) };
+        Ok(block.transactions.iter().find(|tx| tx.to == Some(voter) && !tx.value.is_zero()).map(|tx| tx.from))
+    }
+
+    /// `audit`: look for sybil and last-minute patterns in a poll's votes
+    pub async fn sybil_report(&self, poll_id: u64, format: &str, output_path: Option<String>) -> Result<()> {
+        let json = match format.to_lowercase().as_str() {
+            "json" => true,
+            "table" => false,
+            _ => bail_user!("Unsupported format: {}. Use table or json", format),
+        };
+
+        let poll = self.poll_data(U256::from(poll_id)).await?;
+        let votes = self.fetch_vote_events(poll_id).await?;
+
+        let mut fresh_votes = Vec::new();
+        for vote in &votes {
+            let nonce = self.client
+                .get_transaction_count(vote.voter, Some(BlockId::Number(vote.block_number.saturating_sub(1).into())))
+                .await
+                .context("Reading past nonces needs an archive node")?;
+            if nonce.as_u64() <= FRESH_NONCE {
+                fresh_votes.push((vote.block_number, vote.voter));
+            }
+        }
+
+        let mut funded = Vec::new();
+        for (block, voter) in fresh_votes.iter().take(MAX_FUNDER_LOOKUPS) {
+            if let Some(funder) = self.first_funder(*voter, *block).await? {
+                funded.push((*voter, funder));
+            }
+        }
+
+        let mut identity_scores = Vec::new();
+        if let Some(scorer) = IdentityScorer::from_env()? {
+            for vote in votes.iter().take(MAX_SCORE_LOOKUPS) {
+                identity_scores.push(scorer.score(vote.voter).await?);
+            }
+        }
+
+        let clusters = block_clusters(&fresh_votes);
+        let funders = shared_funders(&funded);
+        let late_share = late_weight_share(&votes, poll.4.to_u64_saturating(), poll.5.to_u64_saturating());
+
+        let mut flags = Vec::new();
+        if !clusters.is_empty() {
+            let voters: usize = clusters.iter().map(|c| c.voters.len()).sum();
+            flags.push(format!("{} fresh addresses voted in {} tight block cluster{}", voters, clusters.len(), if clusters.len() == 1 { "" } else { "s" }));
+        }
+        if !funders.is_empty() {
+            let voters: usize = funders.iter().map(|g| g.voters.len()).sum();
+            flags.push(format!("{} fresh addresses share {} first funder{}", voters, funders.len(), if funders.len() == 1 { "" } else { "s" }));
+        }
+        if late_share >= LATE_SHARE_FLAG {
+            flags.push(format!("{:.1}% of the weight arrived in the last {:.0}% of the poll", late_share, LATE_FRACTION * 100.0));
+        }
+        let failing = identity_scores.iter().filter(|score| score.passing == Some(false)).count();
+        if failing > 0 {
+            flags.push(format!("{} voter{} below IDENTITY_MIN_SCORE", failing, if failing == 1 { " scores" } else { "s score" }));
+        }
+        let risk = match flags.len() {
+            0 => "low",
+            1 => "medium",
+            _ => "high",
+        };
+
+        let report = SybilReport {
+            poll_id,
+            question: poll.1.clone(),
+            voters: votes.len(),
+            fresh_voters: fresh_votes.iter().map(|(_, voter)| *voter).collect(),
+            clusters,
+            shared_funders: funders,
+            funders_skipped: fresh_votes.len().saturating_sub(MAX_FUNDER_LOOKUPS),
+            late_weight_share: late_share,
+            scores_skipped: if identity_scores.is_empty() { 0 } else { votes.len().saturating_sub(MAX_SCORE_LOOKUPS) },
+            identity_scores,
+            flags,
+            risk: risk.to_string(),
+        };
+
+        if json {
+            let rendered = serde_json::to_string_pretty(&report)?;
+            match output_path {
+                Some(path) => {
+                    std::fs::write(&path, rendered)?;
+                    say!("{} {}", "✅ Exported to:".green().bold(), path.yellow());
+                }
+                None => println!("{}", rendered),
+            }
+        } else {
+            print_report(&report);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vote(timestamp: u64, weight: u64) -> VoteRecord {
+        VoteRecord {
+            poll_id: 0, voter: Address::zero(), option_index: 0, weight: U256::from(weight),
+            block_number: timestamp, transaction_hash: H256::zero(), timestamp,
+        }
+    }
+
+    #[test]
+    fn flags_clusters_shared_funders_and_late_weight() {
+        let a = |n: u8| Address::repeat_byte(n);
+        let clusters = block_clusters(&[(100, a(1)), (102, a(2)), (103, a(3)), (110, a(4)), (200, a(5)), (201, a(6))]);
+        assert_eq!(clusters, [BlockCluster { first_block: 100, last_block: 103, voters: vec![a(1), a(2), a(3)] }]);
+
+        let funder = a(9);
+        let groups = shared_funders(&[(a(1), funder), (a(2), funder), (a(3), a(8)), (a(4), funder)]);
+        assert_eq!(groups, [FunderGroup { funder, voters: vec![a(1), a(2), a(4)] }]);
+
+        // Poll runs 0..1000, so the last tenth starts at 900
+        let votes = [vote(100, 10), vote(899, 30), vote(950, 60)];
+        assert!((late_weight_share(&votes, 0, 1_000) - 60.0).abs() < 1e-9);
+        assert_eq!(late_weight_share(&[], 0, 1_000), 0.0);
+    }
+}
@@ -0,0 +1,228 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use anyhow::Result;
+use colored::*;
+use ethers::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::error::bail_user;
+use crate::numbers::SaturatingU64;
+use crate::{numbers, timezone, u8_to_status, PollManager};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TagCount {
+    pub tag: String,
+    pub polls: usize,
+    pub latest_poll_id: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaggedPoll {
+    pub poll_id: u64,
+    pub question: String,
+    pub created_at: u64,
+    pub status: String,
+    pub total_votes: u64,
+    pub winner: Option<String>,
+    /// Winner's share under the poll's deciding metric (votes or weight)
+    pub winner_share: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TagStats {
+    pub tag: String,
+    pub polls: usize,
+    pub total_votes: u64,
+    pub average_votes: f64,
+    /// Percent change in average turnout from the older half of polls to the newer half
+    pub turnout_trend: Option<f64>,
+    pub history: Vec<TaggedPoll>,
+}
+
+/// Tag usage counts, most used first
+pub fn count_tags(poll_tags: &[(u64, Vec<String>)]) -> Vec<TagCount> {
+    let mut polls_by_tag: BTreeMap<&str, BTreeSet<u64>> = BTreeMap::new();
+    for (poll_id, tags) in poll_tags {
+        for tag in tags {
+            polls_by_tag.entry(tag.as_str()).or_default().insert(*poll_id);
+        }
+    }
+
+    let mut counts: Vec<TagCount> = polls_by_tag.into_iter()
+        .map(|(tag, polls)| TagCount {
+            tag: tag.to_string(),
+            polls: polls.len(),
+            latest_poll_id: polls.last().copied().unwrap_or_default(),
+        })
+        .collect();
+    counts.sort_by_key(|c| std::cmp::Reverse(c.polls));
+    counts
+}
+
+/// Polls carrying `tag`, ignoring case, so `tags stats Treasury` finds `treasury`
+pub fn polls_with_tag(poll_tags: &[(u64, Vec<String>)], tag: &str) -> Vec<u64> {
+    let ids: BTreeSet<u64> = poll_tags.iter()
+        .filter(|(_, tags)| tags.iter().any(|t| t.eq_ignore_ascii_case(tag)))
+        .map(|(poll_id, _)| *poll_id)
+        .collect();
+    ids.into_iter().collect()
+}
+
+/// Percent change in mean turnout between the older and newer half of `votes` (oldest first)
+pub fn turnout_trend(votes: &[u64]) -> Option<f64> {
+    if votes.len() < 2 {
+        return None;
+    }
+    let mean = |slice: &[u64]| slice.iter().sum::<u64>() as f64 / slice.len() as f64;
+    let (older, newer) = votes.split_at(votes.len() / 2);
+    let before = mean(older);
+    (before > 0.0).then(|| (mean(newer) - before) / before * 100.0)
+}
+
+fn write_json<T: Serialize>(value: &T, output_path: Option<String>) -> Result<()> {
+    let json_output = serde_json::to_string_pretty(value)?;
+    if let Some(path) = output_path {
+        std::fs::write(&path, &json_output)?;
+        say!("{} {}", "✅ Exported to:".green().bold(), path.yellow());
+    } else {
+        println!("{}", json_output);
+    }
+    Ok(())
+}
+
+impl<M: Middleware + 'static> PollManager<M> {
+    pub async fn list_tags(&self, format: &str, output_path: Option<String>) -> Result<()> {
+        let counts = count_tags(&self.fetch_poll_tags().await?);
+
+        match format.to_lowercase().as_str() {
+            "json" => write_json(&counts, output_path)?,
+            "table" => {
+                say!("\n{}", "🏷️  TAGS".cyan().bold().underline());
+                say!("{}", "═".repeat(50).cyan());
+                if counts.is_empty() {
+                    say!("No polls have been tagged yet.");
+                }
+                for count in &counts {
+                    say!("{:<30} {:>4} poll{} (latest #{})",
+                        count.tag.white().bold(),
+                        count.polls,
+                        if count.polls == 1 { " " } else { "s" },
+                        count.latest_poll_id
+                    );
+                }
+            }
+            _ => bail_user!("Unsupported format: {}. Use table or json", format),
+        }
+        Ok(())
+    }
+
+    pub async fn tag_stats(&self, tag: &str, format: &str, output_path: Option<String>) -> Result<()> {
+        let poll_tags = self.fetch_poll_tags().await?;
+        let poll_ids = polls_with_tag(&poll_tags, tag);
+        if poll_ids.is_empty() {
+            let similar: Vec<String> = count_tags(&poll_tags).into_iter()
+                .map(|c| c.tag)
+                .filter(|t| t.to_lowercase().contains(&tag.to_lowercase()) || tag.to_lowercase().contains(&t.to_lowercase()))
+                .collect();
+            if similar.is_empty() {
+                bail_user!("No polls tagged '{}'; see `tags list`", tag);
+            }
+            bail_user!("No polls tagged '{}'. Did you mean: {}?", tag, similar.join(", "));
+        }
+
+        let now = chrono::Utc::now().timestamp() as u64;
+        let mut history = Vec::with_capacity(poll_ids.len());
+        for poll_id in poll_ids {
+            let poll = self.poll_data(U256::from(poll_id)).await?;
+            let tally = self.poll_tally(poll_id, &poll.2, poll.7).await?;
+            let ranked = tally.ranked();
+            let winner = ranked.first().filter(|o| tally.deciding_share(o) > 0.0);
+            let status = if poll.6 == 0 && poll.5.to_u64_saturating() < now { "Ended" } else { u8_to_status(poll.6) };
+
+            history.push(TaggedPoll {
+                poll_id,
+                question: poll.1.clone(),
+                created_at: poll.4.to_u64_saturating(),
+                status: status.to_string(),
+                total_votes: tally.total_votes,
+                winner: winner.map(|o| o.option.clone()),
+                winner_share: winner.map(|o| tally.deciding_share(o)).unwrap_or_default(),
+            });
+        }
+        history.sort_by_key(|p| (p.created_at, p.poll_id));
+
+        let votes: Vec<u64> = history.iter().map(|p| p.total_votes).collect();
+        let total_votes: u64 = votes.iter().sum();
+        let stats = TagStats {
+            tag: tag.to_string(),
+            polls: history.len(),
+            total_votes,
+            average_votes: total_votes as f64 / history.len() as f64,
+            turnout_trend: turnout_trend(&votes),
+            history,
+        };
+
+        match format.to_lowercase().as_str() {
+            "json" => write_json(&stats, output_path)?,
+            "table" => print_tag_stats(&stats),
+            _ => bail_user!("Unsupported format: {}. Use table or json", format),
+        }
+        Ok(())
+    }
+}
+
+fn print_tag_stats(stats: &TagStats) {
+    say!("\n{} {}", "🏷️  TAG".cyan().bold().underline(), stats.tag.cyan().bold().underline());
+    say!("{}", "═".repeat(60).cyan());
+    say!("{} {}", "Polls:".yellow().bold(), stats.polls.to_string().white());
+    say!("{} {}", "Total Votes:".yellow().bold(), numbers::format_integer(stats.total_votes).green());
+    say!("{} {:.1}", "Average Votes:".yellow().bold(), stats.average_votes);
+    if let Some(trend) = stats.turnout_trend {
+        let text = format!("{:+.1}% (newer vs older polls)", trend);
+        say!("{} {}", "Turnout Trend:".yellow().bold(), if trend >= 0.0 { text.green() } else { text.red() });
+    }
+
+    say!("\n{}", "📅 HISTORY".cyan().bold());
+    say!("{}", "─".repeat(60).cyan());
+    for poll in &stats.history {
+        let outcome = match &poll.winner {
+            Some(winner) => format!("{} ({:.1}%)", winner, poll.winner_share),
+            None => "no votes".to_string(),
+        };
+        say!("#{} {} [{}] {}", poll.poll_id, poll.question.white().bold(), poll.status, timezone::format(poll.created_at));
+        say!("    {} votes → {}", numbers::format_integer(poll.total_votes), outcome.green());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn poll_tags() -> Vec<(u64, Vec<String>)> {
+        vec![
+            (0, vec!["treasury".to_string(), "q1".to_string()]),
+            (1, vec!["treasury".to_string()]),
+            (2, vec!["Treasury".to_string(), "grants".to_string()]),
+        ]
+    }
+
+    #[test]
+    fn counts_tags_by_usage() {
+        let counts = count_tags(&poll_tags());
+        assert_eq!(counts[0], TagCount { tag: "treasury".to_string(), polls: 2, latest_poll_id: 1 });
+        assert_eq!(counts.len(), 4);
+    }
+
+    #[test]
+    fn matches_tags_ignoring_case() {
+        assert_eq!(polls_with_tag(&poll_tags(), "TREASURY"), [0, 1, 2]);
+        assert!(polls_with_tag(&poll_tags(), "treas").is_empty());
+    }
+
+    #[test]
+    fn turnout_trend_compares_halves() {
+        assert_eq!(turnout_trend(&[10, 10, 15, 15]), Some(50.0));
+        assert_eq!(turnout_trend(&[5]), None);
+        assert_eq!(turnout_trend(&[0, 4]), None);
+    }
+}
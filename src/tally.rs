@@ -0,0 +1,104 @@
+use anyhow::Result;
+use ethers::prelude::*;
+use polling_analytics::stats::share;
+use serde::{Deserialize, Serialize};
+
+use crate::numbers::SaturatingU64;
+use crate::{u256_to_f64, PollManager};
+
+/// Vote count and weight for one option
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OptionTally {
+    pub option: String,
+    pub votes: u64,
+    #[serde(with = "crate::numbers::decimal")]
+    pub weight: U256,
+    pub vote_share: f64,
+    pub weight_share: f64,
+}
+
+/// Results of a poll with both head-count and weight-based shares.
+///
+/// `getPollResults` returns per-option weight sums; for standard polls every vote
+/// weighs 1 so those equal the vote counts, otherwise counts come from VoteCast events.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PollTally {
+    pub weighted: bool,
+    pub total_votes: u64,
+    #[serde(with = "crate::numbers::decimal")]
+    pub total_weight: U256,
+    pub options: Vec<OptionTally>,
+}
+
+impl PollTally {
+    pub fn new(options: &[String], weights: &[U256], counts: &[u64], total_votes: u64, total_weight: U256, weighted: bool) -> Self {
+        let options = options.iter().enumerate()
+            .map(|(i, option)| {
+                let weight = weights.get(i).copied().unwrap_or_default();
+                let votes = counts.get(i).copied().unwrap_or_default();
+                OptionTally {
+                    option: option.clone(),
+                    votes,
+                    weight,
+                    vote_share: share(votes as f64, total_votes as f64),
+                    weight_share: share(u256_to_f64(weight), u256_to_f64(total_weight)),
+                }
+            })
+            .collect();
+
+        Self { weighted, total_votes, total_weight, options }
+    }
+
+    /// Which metric decides the winner: weight for weighted/quadratic polls, votes otherwise
+    pub fn winner_metric(&self) -> &'static str {
+        if self.weighted { "weight" } else { "votes" }
+    }
+
+    /// The deciding share of an option under `winner_metric`
+    pub fn deciding_share(&self, option: &OptionTally) -> f64 {
+        if self.weighted { option.weight_share } else { option.vote_share }
+    }
+
+    /// Options ordered by the deciding metric, highest first
+    pub fn ranked(&self) -> Vec<&OptionTally> {
+        let mut ranked: Vec<&OptionTally> = self.options.iter().collect();
+        if self.weighted {
+            ranked.sort_by_key(|o| std::cmp::Reverse(o.weight));
+        } else {
+            ranked.sort_by_key(|o| std::cmp::Reverse(o.votes));
+        }
+        ranked
+    }
+
+    /// The option ahead under the deciding metric; `None` on a tie for first or before any votes
+    pub fn leader(&self) -> Option<&OptionTally> {
+        let deciding = |o: &OptionTally| if self.weighted { o.weight } else { U256::from(o.votes) };
+        match self.ranked().as_slice() {
+            [first, second, ..] if deciding(first) == deciding(second) => None,
+            [first, ..] if !deciding(first).is_zero() => Some(*first),
+            _ => None,
+        }
+    }
+}
+
+impl<M: Middleware + 'static> PollManager<M> {
+    /// Tally a poll given its options and type (`getPoll` fields 2 and 7)
+    pub async fn poll_tally(&self, poll_id: u64, options: &[String], poll_type: u8) -> Result<PollTally> {
+        let (weights, total_votes, total_weight) = self.poll_results(U256::from(poll_id)).await?;
+        let weighted = poll_type != 0;
+
+        let counts: Vec<u64> = if weighted {
+            let mut counts = vec![0u64; options.len()];
+            for vote in self.fetch_vote_events(poll_id).await? {
+                if let Some(count) = counts.get_mut(vote.option_index as usize) {
+                    *count += 1;
+                }
+            }
+            counts
+        } else {
+            weights.iter().map(|w| w.to_u64_saturating()).collect()
+        };
+
+        Ok(PollTally::new(options, &weights, &counts, total_votes.to_u64_saturating(), total_weight, weighted))
+    }
+}
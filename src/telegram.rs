@@ -0,0 +1,272 @@
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use colored::*;
+use ethers::prelude::*;
+use serde_json::{json, Value};
+
+use crate::bot::{escape_html, BotCommand, ResultsView, HELP};
+use crate::error::{bail_config, bail_user, CliError};
+use crate::{duration, output, redact, PollManager};
+
+/// Results messages kept up to date at once; the oldest stops updating first
+const MAX_LIVE: usize = 100;
+
+/// Longest button label Telegram shows in full on most phones
+const MAX_BUTTON: usize = 48;
+
+/// The Telegram Bot API for the bot whose token is in TELEGRAM_BOT_TOKEN
+struct TelegramClient {
+    base_url: String,
+    http: reqwest::Client,
+}
+
+/// A results message that is edited as the poll changes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct LiveMessage {
+    chat_id: i64,
+    message_id: i64,
+    poll_id: u64,
+    end_time: u64,
+}
+
+impl TelegramClient {
+    fn from_env() -> Result<Self> {
+        let Some(token) = std::env::var("TELEGRAM_BOT_TOKEN").ok().filter(|token| !token.is_empty()) else {
+            bail_config!("TELEGRAM_BOT_TOKEN must be set; create a bot with @BotFather to get one");
+        };
+        redact::register(&token);
+        let api_url = std::env::var("TELEGRAM_API_URL").unwrap_or_else(|_| "https://api.telegram.org".to_string());
+        Ok(Self {
+            base_url: format!("{}/bot{}", api_url.trim_end_matches('/'), token),
+            http: reqwest::Client::new(),
+        })
+    }
+
+    async fn call(&self, method: &str, params: Value) -> Result<Value> {
+        let response: Value = self.http.post(format!("{}/{}", self.base_url, method))
+            .json(&params)
+            .send()
+            .await
+            .context("Failed to reach Telegram")?
+            .json()
+            .await
+            .context("Telegram returned invalid JSON")?;
+        if response["ok"] != json!(true) {
+            let description = response["description"].as_str().unwrap_or("unknown error");
+            return Err(CliError::Rpc(format!("Telegram {} failed: {}", method, description)).into());
+        }
+        Ok(response["result"].clone())
+    }
+
+    async fn send(&self, chat_id: i64, text: &str, keyboard: Option<Value>) -> Result<i64> {
+        let mut params = json!({ "chat_id": chat_id, "text": text, "parse_mode": "HTML" });
+        if let Some(keyboard) = keyboard {
+            params["reply_markup"] = keyboard;
+        }
+        let message = self.call("sendMessage", params).await?;
+        Ok(message["message_id"].as_i64().unwrap_or_default())
+    }
+
+    async fn edit(&self, chat_id: i64, message_id: i64, text: &str, keyboard: Option<Value>) -> Result<()> {
+        let mut params = json!({ "chat_id": chat_id, "message_id": message_id, "text": text, "parse_mode": "HTML" });
+        if let Some(keyboard) = keyboard {
+            params["reply_markup"] = keyboard;
+        }
+        match self.call("editMessageText", params).await {
+            // Nothing changed since the last edit
+            Err(e) if format!("{:#}", e).contains("message is not modified") => Ok(()),
+            result => result.map(|_| ()),
+        }
+    }
+}
+
+/// A results message in Telegram's HTML
+fn render(view: &ResultsView) -> String {
+    format!("<b>{}</b>\n<pre>{}</pre>\n{}\n<i>{}</i>",
+        escape_html(&view.title), escape_html(&view.chart.join("\n")), escape_html(&view.summary), escape_html(&view.updated))
+}
+
+/// The refresh button under an open poll's results
+fn results_keyboard(view: &ResultsView) -> Option<Value> {
+    (!view.is_final).then(|| json!({ "inline_keyboard": [[{ "text": "🔄 Refresh", "callback_data": format!("refresh:{}", view.poll_id) }]] }))
+}
+
+/// One button per open poll, each sending its results
+fn polls_keyboard(polls: &[(u64, String)]) -> Value {
+    let rows: Vec<Value> = polls.iter()
+        .map(|(poll_id, question)| {
+            let mut label = format!("#{} {}", poll_id, question);
+            if label.chars().count() > MAX_BUTTON {
+                label = format!("{}…", label.chars().take(MAX_BUTTON - 1).collect::<String>());
+            }
+            json!([{ "text": label, "callback_data": format!("results:{}", poll_id) }])
+        })
+        .collect();
+    json!({ "inline_keyboard": rows })
+}
+
+/// The command in a message such as `/results@PollBot 7`; `None` for messages that are not commands
+fn parse_message(text: &str) -> Option<Option<BotCommand>> {
+    let text = text.trim().strip_prefix('/')?;
+    let mut words: Vec<&str> = text.split_whitespace().collect();
+    if let Some(first) = words.first_mut() {
+        *first = first.split('@').next().unwrap_or_default();
+    }
+    Some(BotCommand::parse(&words))
+}
+
+/// Keep `message` live, dropping the oldest live message when there are too many
+fn track(live: &mut VecDeque<LiveMessage>, message: LiveMessage) {
+    live.retain(|m| (m.chat_id, m.message_id) != (message.chat_id, message.message_id));
+    if live.len() == MAX_LIVE {
+        live.pop_front();
+    }
+    live.push_back(message);
+}
+
+impl<M: Middleware + 'static> PollManager<M> {
+    /// Send a poll's results to a chat, or edit them into `message_id`; returns the message to keep live
+    async fn telegram_results(&self, client: &TelegramClient, chat_id: i64, poll_id: u64, message_id: Option<i64>) -> Result<Option<LiveMessage>> {
+        let view = self.results_view(poll_id).await?;
+        let text = render(&view);
+        let message_id = match message_id {
+            Some(message_id) => {
+                client.edit(chat_id, message_id, &text, results_keyboard(&view)).await?;
+                message_id
+            }
+            None => client.send(chat_id, &text, results_keyboard(&view)).await?,
+        };
+        Ok((!view.is_final).then_some(LiveMessage { chat_id, message_id, poll_id, end_time: view.end_time }))
+    }
+
+    async fn telegram_update(&self, client: &TelegramClient, update: &Value, live: &mut VecDeque<LiveMessage>) -> Result<()> {
+        if let Some(query) = update.get("callback_query") {
+            client.call("answerCallbackQuery", json!({ "callback_query_id": query["id"] })).await?;
+            let chat_id = query["message"]["chat"]["id"].as_i64().unwrap_or_default();
+            let data = query["data"].as_str().unwrap_or_default();
+            let (action, poll_id) = data.split_once(':').unwrap_or_default();
+            let Ok(poll_id) = poll_id.parse::<u64>() else {
+                return Ok(());
+            };
+            let message_id = match action {
+                "refresh" => query["message"]["message_id"].as_i64(),
+                _ => None,
+            };
+            if let Some(message) = self.telegram_results(client, chat_id, poll_id, message_id).await? {
+                track(live, message);
+            }
+            return Ok(());
+        }
+
+        let message = &update["message"];
+        let chat_id = message["chat"]["id"].as_i64().unwrap_or_default();
+        let Some(command) = message["text"].as_str().and_then(parse_message) else {
+            return Ok(());
+        };
+        match command {
+            Some(BotCommand::Polls) => {
+                let polls = self.open_polls().await?;
+                if polls.is_empty() {
+                    client.send(chat_id, "No polls are open right now.", None).await?;
+                } else {
+                    client.send(chat_id, "<b>Open polls</b> - tap one for its results", Some(polls_keyboard(&polls))).await?;
+                }
+            }
+            Some(BotCommand::Results(poll_id)) => {
+                if let Some(message) = self.telegram_results(client, chat_id, poll_id, None).await? {
+                    track(live, message);
+                }
+            }
+            Some(BotCommand::Help) | None => {
+                let commands = HELP.lines().map(|line| format!("/{}", line)).collect::<Vec<_>>().join("\n");
+                client.send(chat_id, &escape_html(&commands), None).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Edit the live messages of polls that changed or ended since the last check
+    async fn refresh_live(&self, client: &TelegramClient, live: &mut VecDeque<LiveMessage>, checked_to: &mut u64) -> Result<()> {
+        let head = self.client.get_block_number().await?.as_u64();
+        let changed = self.changed_polls(*checked_to + 1, head).await?;
+        *checked_to = head;
+        let now = chrono::Utc::now().timestamp() as u64;
+        for message in live.clone() {
+            if !changed.contains(&message.poll_id) && message.end_time > now {
+                continue;
+            }
+            let still_live = self.telegram_results(client, message.chat_id, message.poll_id, Some(message.message_id)).await?;
+            if still_live.is_none() {
+                live.retain(|m| *m != message);
+            }
+        }
+        Ok(())
+    }
+
+    /// `bot telegram`: answer poll commands and keep results messages current until Ctrl-C
+    pub async fn run_telegram_bot(&self, interval: u64) -> Result<()> {
+        if interval == 0 {
+            bail_user!("--interval must be longer than zero");
+        }
+        let client = TelegramClient::from_env()?;
+        let me = client.call("getMe", json!({})).await?;
+        say!("{} @{} (Ctrl-C to stop)", "🤖 Telegram bot running as".cyan().bold(), me["username"].as_str().unwrap_or("?").yellow());
+        say!("Live results refresh on new votes, checked every {}.", duration::format(interval));
+
+        let mut offset = 0i64;
+        let mut live = VecDeque::new();
+        let mut checked_to = self.client.get_block_number().await?.as_u64();
+        loop {
+            // Long-poll for up to one interval, so the chain is checked between messages
+            let request = client.call("getUpdates", json!({ "offset": offset, "timeout": interval, "allowed_updates": ["message", "callback_query"] }));
+            let updates = tokio::select! {
+                updates = tokio::time::timeout(Duration::from_secs(interval + 10), request) => updates,
+                _ = tokio::signal::ctrl_c() => return Ok(()),
+            };
+            match updates {
+                Ok(Ok(Value::Array(updates))) => {
+                    for update in &updates {
+                        offset = offset.max(update["update_id"].as_i64().unwrap_or_default() + 1);
+                        // One failed command must not stop the bot; tell the chat what went wrong
+                        if let Err(e) = self.telegram_update(&client, update, &mut live).await {
+                            eprintln!("{} {:#}", output::render("⚠️  Command failed:".to_string()).yellow(), e);
+                            let chat_id = update["message"]["chat"]["id"].as_i64()
+                                .or_else(|| update["callback_query"]["message"]["chat"]["id"].as_i64());
+                            if let Some(chat_id) = chat_id {
+                                let _ = client.send(chat_id, &escape_html(&redact::redact(&format!("⚠️ {:#}", e))), None).await;
+                            }
+                        }
+                    }
+                }
+                Ok(Ok(_)) => {}
+                Ok(Err(e)) => eprintln!("{} {:#}", output::render("⚠️  Telegram:".to_string()).yellow(), e),
+                Err(_) => eprintln!("{}", output::render("⚠️  Telegram did not answer in time".to_string()).yellow()),
+            }
+            if let Err(e) = self.refresh_live(&client, &mut live, &mut checked_to).await {
+                eprintln!("{} {:#}", output::render("⚠️  Live update failed:".to_string()).yellow(), e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_commands_addressed_to_the_bot() {
+        assert_eq!(parse_message("/results@PollBot 7"), Some(Some(BotCommand::Results(7))));
+        assert_eq!(parse_message("/polls"), Some(Some(BotCommand::Polls)));
+        assert_eq!(parse_message("/vote 7"), Some(None));
+        assert_eq!(parse_message("results 7"), None);
+
+        let view = ResultsView {
+            poll_id: 3, title: "📊 Poll #3: A <b> & c?".to_string(), chart: vec!["Yes │█ 1".to_string()],
+            summary: "1 votes".to_string(), updated: "Updated now".to_string(), end_time: 0, is_final: true,
+        };
+        assert!(render(&view).starts_with("<b>📊 Poll #3: A &lt;b&gt; &amp; c?</b>\n<pre>Yes"));
+        assert_eq!(results_keyboard(&view), None, "final results need no refresh button");
+    }
+}
@@ -0,0 +1,158 @@
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+use colored::*;
+use serde::{Deserialize, Serialize};
+
+use crate::error::bail_user;
+use crate::spec::{split_list, PollFields, DEFAULT_CATEGORY, DEFAULT_DURATION, DEFAULT_POLL_TYPE};
+use crate::{duration, store};
+
+const TEMPLATES_FILE: &str = "templates.json";
+
+/// A reusable poll definition: everything except the question
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PollTemplate {
+    pub options: Vec<String>,
+    pub duration: u64,
+    pub poll_type: String,
+    pub category: String,
+    pub min_participation: u64,
+    pub token_address: Option<String>,
+    pub min_token_balance: u64,
+    pub description: Option<String>,
+    pub tags: Vec<String>,
+}
+
+impl PollTemplate {
+    pub fn from_fields(fields: PollFields) -> Result<Self> {
+        let fields = fields.load_file()?;
+        if fields.question.is_some() {
+            bail_user!("Templates do not store a question; pass it when applying the template");
+        }
+        if fields.ends_at.is_some() {
+            bail_user!("Templates store a duration, not an absolute --ends-at");
+        }
+        let options = fields.options.as_deref().map(split_list).unwrap_or_default();
+        if options.len() < 2 {
+            bail_user!("A template needs at least 2 options (--options)");
+        }
+
+        Ok(Self {
+            options,
+            duration: fields.duration.unwrap_or(DEFAULT_DURATION),
+            poll_type: fields.poll_type.unwrap_or_else(|| DEFAULT_POLL_TYPE.to_string()),
+            category: fields.category.unwrap_or_else(|| DEFAULT_CATEGORY.to_string()),
+            min_participation: fields.min_participation.unwrap_or_default(),
+            token_address: fields.token_address,
+            min_token_balance: fields.min_token_balance.unwrap_or_default(),
+            description: fields.description,
+            tags: fields.tags.as_deref().map(split_list).unwrap_or_default(),
+        })
+    }
+}
+
+/// Every saved template by name
+pub fn all() -> Result<BTreeMap<String, PollTemplate>> {
+    store::load(TEMPLATES_FILE)
+}
+
+/// Look up a saved template by name
+pub fn get(name: &str) -> Result<PollTemplate> {
+    let templates = all()?;
+    match templates.get(name) {
+        Some(template) => Ok(template.clone()),
+        None if templates.is_empty() => bail_user!("No template named '{}'; save one with `template save`", name),
+        None => bail_user!(
+            "No template named '{}'. Available: {}",
+            name,
+            templates.keys().cloned().collect::<Vec<_>>().join(", ")
+        ),
+    }
+}
+
+pub fn save_template(name: &str, fields: PollFields) -> Result<()> {
+    let template = PollTemplate::from_fields(fields)?;
+    let mut templates = all()?;
+    let replaced = templates.insert(name.to_string(), template).is_some();
+    store::save(TEMPLATES_FILE, &templates)?;
+
+    let verb = if replaced { "updated" } else { "saved" };
+    say!("{} {} {}", "✅ Template".green().bold(), name.yellow(), verb.green().bold());
+    Ok(())
+}
+
+pub fn list_templates() -> Result<()> {
+    let templates = all()?;
+
+    say!("\n{}", "📐 Poll Templates:".cyan().bold());
+    if templates.is_empty() {
+        say!("No templates saved yet. Create one with `template save <name> --options ...`.");
+        return Ok(());
+    }
+
+    for (name, template) in &templates {
+        say!("\n{}", name.yellow().bold());
+        say!("  Options: {}", template.options.join(" / "));
+        say!("  Type: {} | Category: {} | Duration: {}", template.poll_type, template.category, duration::format(template.duration));
+        if !template.tags.is_empty() {
+            say!("  Tags: {}", template.tags.join(", "));
+        }
+        if template.min_participation > 0 {
+            say!("  Min participation: {}", template.min_participation);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn proposal() -> PollTemplate {
+        PollTemplate::from_fields(PollFields {
+            options: Some("For, Against, Abstain".to_string()),
+            category: Some("governance".to_string()),
+            duration: Some(5 * 86_400),
+            tags: Some("proposal,dao".to_string()),
+            ..Default::default()
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn template_fills_unset_fields() {
+        let template = proposal();
+        let spec = PollFields { question: Some("Fund grant #42".to_string()), ..Default::default() }
+            .resolve(Some(&template))
+            .unwrap();
+
+        assert_eq!(spec.options, ["For", "Against", "Abstain"]);
+        assert_eq!(spec.category, "governance");
+        assert_eq!(spec.poll_type, "standard");
+        assert_eq!(spec.duration, 5 * 86_400);
+        assert_eq!(spec.tags, ["proposal", "dao"]);
+    }
+
+    #[test]
+    fn flags_override_template() {
+        let spec = PollFields {
+            question: Some("Fund grant #43".to_string()),
+            duration: Some(3_600),
+            tags: Some("urgent".to_string()),
+            ..Default::default()
+        }
+        .resolve(Some(&proposal()))
+        .unwrap();
+
+        assert_eq!(spec.duration, 3_600);
+        assert_eq!(spec.tags, ["urgent"]);
+    }
+
+    #[test]
+    fn templates_reject_questions() {
+        let fields = PollFields { question: Some("Q".to_string()), options: Some("A,B".to_string()), ..Default::default() };
+        assert!(PollTemplate::from_fields(fields).is_err());
+        assert!(PollFields { options: Some("A,B".to_string()), ..Default::default() }.resolve(None).is_err());
+    }
+}
@@ -0,0 +1,122 @@
+use std::sync::OnceLock;
+
+use chrono::{DateTime, Local, Utc};
+use chrono_tz::Tz;
+
+use crate::duration;
+
+/// Timezone human-readable timestamps are shown in
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DisplayZone {
+    Utc,
+    Local,
+    Named(Tz),
+}
+
+impl std::str::FromStr for DisplayZone {
+    type Err = String;
+
+    /// `utc`, `local` (the system zone, honoring `TZ`) or an IANA name such as `Europe/Berlin`
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "utc" | "z" => Ok(DisplayZone::Utc),
+            "local" => Ok(DisplayZone::Local),
+            _ => value.trim().parse::<Tz>()
+                .map(DisplayZone::Named)
+                .map_err(|_| format!("'{}' is not a timezone (use utc, local or an IANA name like Europe/Berlin)", value)),
+        }
+    }
+}
+
+static ZONE: OnceLock<DisplayZone> = OnceLock::new();
+
+/// Set the display zone once at startup; defaults to UTC so log output stays comparable
+pub fn init(zone: Option<DisplayZone>) {
+    let _ = ZONE.set(zone.unwrap_or(DisplayZone::Utc));
+}
+
+fn zone() -> DisplayZone {
+    *ZONE.get().unwrap_or(&DisplayZone::Utc)
+}
+
+/// Absolute time in the display zone, e.g. `2025-07-01 20:00:00 CEST`
+pub fn format_in(timestamp: u64, zone: DisplayZone) -> String {
+    let utc = DateTime::<Utc>::from_timestamp(timestamp as i64, 0).unwrap_or_default();
+    match zone {
+        DisplayZone::Utc => utc.format("%Y-%m-%d %H:%M:%S UTC").to_string(),
+        DisplayZone::Local => utc.with_timezone(&Local).format("%Y-%m-%d %H:%M:%S %:z").to_string(),
+        DisplayZone::Named(tz) => utc.with_timezone(&tz).format("%Y-%m-%d %H:%M:%S %Z").to_string(),
+    }
+}
+
+pub fn format(timestamp: u64) -> String {
+    format_in(timestamp, zone())
+}
+
+fn plural(amount: u64, unit: &str) -> String {
+    if amount == 1 { format!("1 {}", unit) } else { format!("{} {}s", amount, unit) }
+}
+
+/// Relative phrasing against `now`: `in 2d 4h` for the future, `3 weeks ago` for the past
+pub fn relative_to(timestamp: u64, now: u64) -> String {
+    if timestamp.abs_diff(now) < 60 {
+        return "just now".to_string();
+    }
+
+    if timestamp > now {
+        // Keep the two largest units, which is precise enough for a deadline
+        let remaining = duration::format(timestamp - now);
+        let largest: Vec<&str> = remaining.split(' ').take(2).collect();
+        return format!("in {}", largest.join(" "));
+    }
+
+    let elapsed = now - timestamp;
+    let phrase = match elapsed {
+        s if s < 3_600 => plural(s / 60, "minute"),
+        s if s < 86_400 => plural(s / 3_600, "hour"),
+        s if s < 14 * 86_400 => plural(s / 86_400, "day"),
+        s if s < 60 * 86_400 => plural(s / (7 * 86_400), "week"),
+        s if s < 365 * 86_400 => plural(s / (30 * 86_400), "month"),
+        s => plural(s / (365 * 86_400), "year"),
+    };
+    format!("{} ago", phrase)
+}
+
+pub fn relative(timestamp: u64) -> String {
+    relative_to(timestamp, Utc::now().timestamp() as u64)
+}
+
+/// Absolute and relative time together, e.g. `2025-07-01 18:00:00 UTC (in 2d 4h)`
+pub fn format_with_relative(timestamp: u64) -> String {
+    format!("{} ({})", format(timestamp), relative(timestamp))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_zones() {
+        assert_eq!("UTC".parse::<DisplayZone>(), Ok(DisplayZone::Utc));
+        assert_eq!("local".parse::<DisplayZone>(), Ok(DisplayZone::Local));
+        assert_eq!("Europe/Berlin".parse::<DisplayZone>(), Ok(DisplayZone::Named(chrono_tz::Europe::Berlin)));
+        assert!("Mars/Olympus".parse::<DisplayZone>().is_err());
+    }
+
+    #[test]
+    fn formats_in_named_zone() {
+        let berlin = DisplayZone::Named(chrono_tz::Europe::Berlin);
+        assert_eq!(format_in(1_751_392_800, DisplayZone::Utc), "2025-07-01 18:00:00 UTC");
+        assert_eq!(format_in(1_751_392_800, berlin), "2025-07-01 20:00:00 CEST");
+    }
+
+    #[test]
+    fn phrases_relative_times() {
+        let now = 1_751_392_800;
+        assert_eq!(relative_to(now + 2 * 86_400 + 4 * 3_600 + 59, now), "in 2d 4h");
+        assert_eq!(relative_to(now + 90 * 60, now), "in 1h 30m");
+        assert_eq!(relative_to(now - 21 * 86_400, now), "3 weeks ago");
+        assert_eq!(relative_to(now - 3_600, now), "1 hour ago");
+        assert_eq!(relative_to(now - 10, now), "just now");
+    }
+}
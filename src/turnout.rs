@@ -0,0 +1,213 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use anyhow::Result;
+use colored::*;
+use ethers::prelude::*;
+use polling_analytics::stats;
+use serde::Serialize;
+
+use crate::error::bail_user;
+use crate::events::VoteRecord;
+use crate::{numbers, u256_to_f64, u8_to_poll_type, PollManager};
+
+/// A poll's votes set against the token's holders at its start block
+#[derive(Debug, Default, PartialEq)]
+struct Participation {
+    voters: usize,
+    votes: usize,
+    cast_weight: U256,
+    holders: usize,
+    supply: U256,
+    /// Voters who held tokens at the start block
+    holders_voted: usize,
+    /// Tokens those voters held at the start block
+    held_by_voters: U256,
+}
+
+fn participation(votes: &[VoteRecord], balances: &BTreeMap<Address, U256>) -> Participation {
+    let voters: BTreeSet<Address> = votes.iter().map(|vote| vote.voter).collect();
+    let held: Vec<U256> = voters.iter().filter_map(|voter| balances.get(voter).copied()).collect();
+    Participation {
+        voters: voters.len(),
+        votes: votes.len(),
+        cast_weight: votes.iter().fold(U256::zero(), |total, vote| total.saturating_add(vote.weight)),
+        holders: balances.len(),
+        supply: balances.values().fold(U256::zero(), |total, balance| total.saturating_add(*balance)),
+        holders_voted: held.len(),
+        held_by_voters: held.iter().fold(U256::zero(), |total, balance| total.saturating_add(*balance)),
+    }
+}
+
+fn percent(part: U256, total: U256) -> f64 {
+    stats::share(u256_to_f64(part), u256_to_f64(total))
+}
+
+/// Cast weight as a share of `supply`: EnhancedPolls counts weight as balance / 1e18 whatever the
+/// token's decimals, the supply is in base units
+fn weight_share(cast_weight: U256, supply: U256) -> f64 {
+    percent(cast_weight.saturating_mul(U256::exp10(18)), supply)
+}
+
+#[derive(Debug, Serialize)]
+pub struct TurnoutReport {
+    pub poll_id: u64,
+    pub question: String,
+    pub poll_type: String,
+    pub token: Address,
+    pub decimals: u8,
+    /// Block the poll was created in, where holders and supply were measured
+    pub start_block: u64,
+    pub holders: usize,
+    /// Tokens in circulation at the start block, replayed from Transfer events
+    #[serde(with = "numbers::decimal")]
+    pub supply: U256,
+    pub voters: usize,
+    pub votes: usize,
+    #[serde(with = "numbers::decimal")]
+    pub cast_weight: U256,
+    pub holders_voted: usize,
+    #[serde(with = "numbers::decimal")]
+    pub held_by_voters: U256,
+    /// Voters as a share of holders
+    pub voter_turnout: f64,
+    /// Holders who voted, as a share of holders
+    pub holder_turnout: f64,
+    /// Tokens held by voters, as a share of the supply
+    pub supply_turnout: f64,
+    /// Cast weight (balance / 1e18 per voter) as a share of the supply; weighted polls only
+    pub weight_turnout: Option<f64>,
+}
+
+fn print_turnout(report: &TurnoutReport) {
+    let decimals = report.decimals as u32;
+    say!("\n{}", format!("🗳️  TURNOUT: POLL #{}", report.poll_id).cyan().bold().underline());
+    say!("{}", "═".repeat(60).cyan());
+    say!("{} {}", "Question:".yellow().bold(), report.question);
+    say!("{} {}", "Type:".yellow().bold(), report.poll_type);
+    say!("{} {:?}", "Token:".yellow().bold(), report.token);
+    say!("{} {}", "Start Block:".yellow().bold(), report.start_block);
+
+    say!("\n{}", "👥 HOLDER BASE".cyan().bold());
+    say!("{}", "─".repeat(60).cyan());
+    say!("{} {}", "Holders:".yellow().bold(), numbers::format_integer(report.holders as u64));
+    say!("{} {}", "Supply:".yellow().bold(), numbers::format_amount(report.supply, decimals, 2));
+
+    say!("\n{}", "📊 PARTICIPATION".cyan().bold());
+    say!("{}", "─".repeat(60).cyan());
+    say!("{} {} of {} holders ({:.2}%)", "Voters:".yellow().bold(),
+        numbers::format_integer(report.voters as u64).green(), numbers::format_integer(report.holders as u64), report.voter_turnout);
+    say!("{} {} ({:.2}% of holders)", "Holders Who Voted:".yellow().bold(),
+        numbers::format_integer(report.holders_voted as u64).green(), report.holder_turnout);
+    say!("{} {} ({:.2}% of supply)", "Held by Voters:".yellow().bold(),
+        numbers::format_amount(report.held_by_voters, decimals, 2).green(), report.supply_turnout);
+    match report.weight_turnout {
+        Some(share) => say!("{} {} ({:.2}% of supply)", "Cast Weight:".yellow().bold(),
+            numbers::format_integer(report.cast_weight).green(), share),
+        None => say!("{} {} across {} votes", "Cast Weight:".yellow().bold(),
+            numbers::format_integer(report.cast_weight), numbers::format_integer(report.votes as u64)),
+    }
+    if report.voters > report.holders_voted {
+        say!("{}", format!("ℹ️  {} voter(s) held no tokens at the start block", report.voters - report.holders_voted).yellow());
+    }
+}
+
+impl<M: Middleware + 'static> PollManager<M> {
+    /// `turnout`: a poll's voters and weight against the token's holders and supply when it started
+    pub async fn turnout(&self, poll_id: u64, token: Option<Address>, format: &str, output_path: Option<String>) -> Result<()> {
+        let json = match format.to_lowercase().as_str() {
+            "json" => true,
+            "table" => false,
+            _ => bail_user!("Unsupported format: {}. Use table or json", format),
+        };
+        let token = self.token_or_governance(token)?;
+        let poll = self.poll_data(U256::from(poll_id)).await?;
+        let Some(start_block) = self.event_index().await?.created_block(poll_id) else {
+            bail_user!("No PollCreated event indexed for poll {}; run `sync` first", poll_id);
+        };
+
+        let balances = self.holder_balances(token.address(), start_block).await?;
+        let votes = self.fetch_vote_events(poll_id).await?;
+        let counted = participation(&votes, &balances);
+        let decimals = self.at_block(token.decimals()).call().await?;
+        let report = TurnoutReport {
+            poll_id,
+            question: poll.1,
+            poll_type: u8_to_poll_type(poll.7).to_string(),
+            token: token.address(),
+            decimals,
+            start_block,
+            holders: counted.holders,
+            supply: counted.supply,
+            voters: counted.voters,
+            votes: counted.votes,
+            cast_weight: counted.cast_weight,
+            holders_voted: counted.holders_voted,
+            held_by_voters: counted.held_by_voters,
+            voter_turnout: stats::share(counted.voters as f64, counted.holders as f64),
+            holder_turnout: stats::share(counted.holders_voted as f64, counted.holders as f64),
+            supply_turnout: percent(counted.held_by_voters, counted.supply),
+            weight_turnout: (poll.7 == 1).then(|| weight_share(counted.cast_weight, counted.supply)),
+        };
+
+        if !json {
+            print_turnout(&report);
+            return Ok(());
+        }
+        let rendered = serde_json::to_string_pretty(&report)?;
+        match output_path {
+            Some(path) => {
+                std::fs::write(&path, rendered)?;
+                say!("{} {}", "✅ Exported to:".green().bold(), path.yellow());
+            }
+            None => println!("{}", rendered),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vote(voter: u8, weight: u64) -> VoteRecord {
+        VoteRecord {
+            poll_id: 0,
+            voter: Address::repeat_byte(voter),
+            option_index: 0,
+            weight: U256::from(weight),
+            block_number: 0,
+            transaction_hash: H256::zero(),
+            timestamp: 0,
+        }
+    }
+
+    #[test]
+    fn measures_votes_against_holders_and_supply() {
+        let balances = BTreeMap::from([
+            (Address::repeat_byte(1), U256::from(600)),
+            (Address::repeat_byte(2), U256::from(300)),
+            (Address::repeat_byte(3), U256::from(100)),
+        ]);
+        // Voter 9 held nothing; voter 1 voted twice in a multi-choice poll
+        let counted = participation(&[vote(1, 600), vote(1, 600), vote(3, 100), vote(9, 1)], &balances);
+        assert_eq!(counted, Participation {
+            voters: 3,
+            votes: 4,
+            cast_weight: U256::from(1_301),
+            holders: 3,
+            supply: U256::from(1_000),
+            holders_voted: 2,
+            held_by_voters: U256::from(700),
+        });
+        assert_eq!(percent(counted.held_by_voters, counted.supply), 70.0);
+
+        // 18-decimal balances: vote weight is whole tokens, the supply base units
+        let tokens = |amount: u64| U256::from(amount) * U256::exp10(18);
+        let balances = BTreeMap::from([(Address::repeat_byte(1), tokens(600)), (Address::repeat_byte(2), tokens(400))]);
+        let counted = participation(&[vote(1, 600), vote(2, 400)], &balances);
+        assert_eq!((counted.supply, counted.held_by_voters), (tokens(1_000), tokens(1_000)));
+        assert_eq!(percent(counted.held_by_voters, counted.supply), 100.0);
+        assert_eq!(weight_share(counted.cast_weight, counted.supply), 100.0);
+        assert_eq!(weight_share(U256::from(250), counted.supply), 25.0);
+    }
+}
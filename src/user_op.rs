@@ -0,0 +1,320 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use clap::Args;
+use colored::*;
+use ethers::abi::{self, Token};
+use ethers::prelude::*;
+use ethers::types::transaction::eip2718::TypedTransaction;
+use ethers::utils::{id, keccak256};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::error::{bail_config, bail_user, CliError};
+use crate::numbers::SaturatingU64;
+use crate::{audit, output, relay, PollManager};
+
+/// Canonical ERC-4337 v0.6 EntryPoint, deployed at the same address on every chain
+pub const ENTRY_POINT_V06: &str = "0x5FF137D4b0FDCD49DcA30c7CF57E578a026d2789";
+
+/// Placeholder signature for gas estimation: well-formed, so accounts that recover it
+/// spend the same gas validating as with a real one
+const DUMMY_SIGNATURE: &str = "0xfffffffffffffffffffffffffffffff0000000000000000000000000000000007aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa1c";
+
+/// How long to wait for a bundler to include a UserOperation
+const INCLUSION_TIMEOUT: Duration = Duration::from_secs(180);
+const POLL_INTERVAL: Duration = Duration::from_secs(3);
+
+/// Voting from a smart account instead of the signing key's own address
+#[derive(Args, Debug, Clone)]
+pub struct SmartAccountArgs {
+    /// Vote from this ERC-4337 smart account (owned by the signing key) through a bundler
+    #[arg(long, value_name = "ADDRESS")]
+    pub smart_account: Option<Address>,
+
+    /// Bundler endpoint to use instead of BUNDLER_URL
+    #[arg(long, requires = "smart_account")]
+    pub bundler_url: Option<String>,
+
+    /// Paymaster endpoint (pm_sponsorUserOperation) that sponsors the gas, instead of PAYMASTER_URL
+    #[arg(long, requires = "smart_account")]
+    pub paymaster_url: Option<String>,
+
+    /// EntryPoint contract the account uses
+    #[arg(long, default_value = ENTRY_POINT_V06)]
+    pub entry_point: Address,
+}
+
+/// An ERC-4337 v0.6 UserOperation, serialized the way bundlers expect
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserOperation {
+    pub sender: Address,
+    pub nonce: U256,
+    pub init_code: Bytes,
+    pub call_data: Bytes,
+    pub call_gas_limit: U256,
+    pub verification_gas_limit: U256,
+    pub pre_verification_gas: U256,
+    pub max_fee_per_gas: U256,
+    pub max_priority_fee_per_gas: U256,
+    pub paymaster_and_data: Bytes,
+    pub signature: Bytes,
+}
+
+impl UserOperation {
+    /// The hash the account owner signs: the packed operation bound to an EntryPoint and chain
+    pub fn hash(&self, entry_point: Address, chain_id: u64) -> H256 {
+        let packed = abi::encode(&[
+            Token::Address(self.sender),
+            Token::Uint(self.nonce),
+            Token::FixedBytes(keccak256(&self.init_code).to_vec()),
+            Token::FixedBytes(keccak256(&self.call_data).to_vec()),
+            Token::Uint(self.call_gas_limit),
+            Token::Uint(self.verification_gas_limit),
+            Token::Uint(self.pre_verification_gas),
+            Token::Uint(self.max_fee_per_gas),
+            Token::Uint(self.max_priority_fee_per_gas),
+            Token::FixedBytes(keccak256(&self.paymaster_and_data).to_vec()),
+        ]);
+        H256(keccak256(abi::encode(&[
+            Token::FixedBytes(keccak256(packed).to_vec()),
+            Token::Address(entry_point),
+            Token::Uint(U256::from(chain_id)),
+        ])))
+    }
+
+    /// Most gas the operation can use, counted against `--session-max-gas`
+    pub fn gas_limit(&self) -> U256 {
+        self.call_gas_limit.saturating_add(self.verification_gas_limit).saturating_add(self.pre_verification_gas)
+    }
+
+    /// Take the gas fields a bundler or paymaster returned, keeping ours for any it leaves out
+    fn apply_gas(&mut self, fields: &Value) -> Result<()> {
+        for (name, slot) in [
+            ("callGasLimit", &mut self.call_gas_limit),
+            ("verificationGasLimit", &mut self.verification_gas_limit),
+            ("preVerificationGas", &mut self.pre_verification_gas),
+        ] {
+            if let Some(value) = fields.get(name) {
+                *slot = quantity(value)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Calldata for the account's `execute(dest, value, func)`, the call SimpleAccount-style accounts make on the owner's behalf
+pub fn execute_call_data(target: Address, value: U256, data: Bytes) -> Bytes {
+    let mut call_data = id("execute(address,uint256,bytes)").to_vec();
+    call_data.extend(abi::encode(&[Token::Address(target), Token::Uint(value), Token::Bytes(data.to_vec())]));
+    call_data.into()
+}
+
+/// A JSON-RPC quantity, which bundlers return either as a hex string or a plain number
+fn quantity(value: &Value) -> Result<U256> {
+    let parsed = match value {
+        Value::String(text) if text.starts_with("0x") => U256::from_str_radix(&text[2..], 16).ok(),
+        Value::String(text) => U256::from_dec_str(text).ok(),
+        Value::Number(number) => number.as_u64().map(U256::from),
+        _ => None,
+    };
+    parsed.ok_or_else(|| CliError::Rpc(format!("Bundler returned an invalid gas value: {}", value)).into())
+}
+
+/// A bundler or paymaster endpoint speaking the ERC-4337 JSON-RPC methods
+struct Endpoint {
+    client: reqwest::Client,
+    url: String,
+    name: &'static str,
+}
+
+impl Endpoint {
+    fn new(url: String, name: &'static str) -> Self {
+        Self { client: reqwest::Client::new(), url, name }
+    }
+
+    async fn request<T: DeserializeOwned>(&self, method: &str, params: Value) -> Result<T> {
+        let body = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+        let response: Value = self.client.post(&self.url).json(&body).send().await?.error_for_status()?.json().await?;
+        if let Some(error) = response.get("error") {
+            let message = error.get("message").and_then(Value::as_str).unwrap_or("unknown error");
+            return Err(CliError::Rpc(format!("{} rejected {}: {}", self.name, method, message)).into());
+        }
+        Ok(serde_json::from_value(response.get("result").cloned().unwrap_or(Value::Null))?)
+    }
+}
+
+fn endpoint_url(flag: &Option<String>, var: &str) -> Option<String> {
+    flag.clone().or_else(|| std::env::var(var).ok()).filter(|url| !url.is_empty())
+}
+
+impl<M: Middleware + 'static> PollManager<M> {
+    /// EntryPoint nonce for `account` under the default key
+    async fn user_op_nonce(&self, entry_point: Address, account: Address) -> Result<U256> {
+        let mut data = id("getNonce(address,uint192)").to_vec();
+        data.extend(abi::encode(&[Token::Address(account), Token::Uint(U256::zero())]));
+        let call: TypedTransaction = TransactionRequest::new().to(entry_point).data(data).into();
+        let result = self.client.call(&call, None).await.map_err(|e| CliError::Rpc(format!("EntryPoint getNonce failed: {}", e)))?;
+        Ok(U256::from_big_endian(&result))
+    }
+
+    /// Refuse accounts that report an `owner()` other than the signing key; accounts without one are left to validation
+    async fn check_account_owner(&self, account: Address) -> Result<()> {
+        let call: TypedTransaction = TransactionRequest::new().to(account).data(id("owner()").to_vec()).into();
+        if let Ok(result) = self.client.call(&call, None).await {
+            if result.len() == 32 {
+                let owner = Address::from_slice(&result[12..]);
+                if owner != self.wallet.address() {
+                    bail_user!("Smart account {:?} is owned by {:?}, not the signing key {:?}", account, owner, self.wallet.address());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Vote from an ERC-4337 smart account: wrap `vote` in the account's `execute`, sign the
+    /// UserOperation with the owner key and hand it to a bundler, optionally paymaster-sponsored
+    pub async fn vote_from_smart_account(&self, poll_id: u64, option_index: u64, args: &SmartAccountArgs) -> Result<()> {
+        let Some(account) = args.smart_account else { bail_user!("--smart-account is required") };
+        let Some(bundler_url) = endpoint_url(&args.bundler_url, "BUNDLER_URL") else {
+            bail_config!("Voting from a smart account needs a bundler; set BUNDLER_URL or pass --bundler-url");
+        };
+        if relay::rerouted() {
+            bail_user!("--sponsor and --private-tx route ordinary transactions; a smart account's vote goes to the bundler (use --paymaster-url for gas)");
+        }
+        if self.sender != self.wallet.address() {
+            bail_user!("--smart-account cannot be combined with --impersonate; the owner key must sign the UserOperation");
+        }
+        if self.client.get_code(account, None).await?.is_empty() {
+            bail_user!("No contract at {:?}; deploy the smart account before voting from it", account);
+        }
+        self.check_account_owner(account).await?;
+
+        say!("{} {} {} {} {} {:?}", "🗳️ Voting on poll".cyan().bold(), poll_id.to_string().yellow(),
+            "with option".cyan().bold(), option_index.to_string().yellow(), "from smart account".cyan().bold(), account);
+        let pb = output::spinner("Building UserOperation...");
+
+        let vote = self.contract.vote(U256::from(poll_id), U256::from(option_index));
+        let vote_data = vote.calldata().ok_or_else(|| anyhow::anyhow!("could not encode vote calldata"))?;
+        let (max_fee_per_gas, max_priority_fee_per_gas) = self.client.estimate_eip1559_fees(None).await?;
+        let mut op = UserOperation {
+            sender: account,
+            nonce: self.user_op_nonce(args.entry_point, account).await?,
+            init_code: Bytes::default(),
+            call_data: execute_call_data(self.contract.address(), U256::zero(), vote_data),
+            call_gas_limit: U256::zero(),
+            verification_gas_limit: U256::zero(),
+            pre_verification_gas: U256::zero(),
+            max_fee_per_gas,
+            max_priority_fee_per_gas,
+            paymaster_and_data: Bytes::default(),
+            signature: DUMMY_SIGNATURE.parse()?,
+        };
+
+        let bundler = Endpoint::new(bundler_url, "Bundler");
+        let entry_point = format!("{:?}", args.entry_point);
+        let estimate: Value = bundler.request("eth_estimateUserOperationGas", json!([op, entry_point])).await?;
+        op.apply_gas(&estimate)?;
+
+        if let Some(paymaster_url) = endpoint_url(&args.paymaster_url, "PAYMASTER_URL") {
+            pb.set_message("Requesting paymaster sponsorship...");
+            let paymaster = Endpoint::new(paymaster_url, "Paymaster");
+            let sponsored: Value = paymaster.request("pm_sponsorUserOperation", json!([op, entry_point])).await?;
+            // Older paymasters return just paymasterAndData; newer ones also re-estimate gas
+            let paymaster_and_data = match &sponsored {
+                Value::String(data) => data.as_str(),
+                other => {
+                    op.apply_gas(other)?;
+                    other.get("paymasterAndData").and_then(Value::as_str).unwrap_or_default()
+                }
+            };
+            op.paymaster_and_data = paymaster_and_data.parse()
+                .map_err(|_| CliError::Rpc("Paymaster returned no paymasterAndData".to_string()))?;
+            if op.paymaster_and_data.is_empty() {
+                return Err(CliError::Rpc("Paymaster declined to sponsor this UserOperation".to_string()).into());
+            }
+        }
+
+        // The limits come from the bundler and paymaster; a nonsensical one must not panic
+        self.guard_send_gas(op.gas_limit().to_u64_saturating()).await?;
+        let hash = op.hash(args.entry_point, self.wallet.chain_id());
+        op.signature = self.wallet.sign_message(hash.as_bytes()).await?.to_vec().into();
+
+        pb.set_message("Submitting to bundler...");
+        let op_hash: H256 = bundler.request("eth_sendUserOperation", json!([op, entry_point])).await?;
+        pb.set_message("Waiting for inclusion...");
+
+        let started = std::time::Instant::now();
+        let receipt = loop {
+            let receipt: Value = bundler.request("eth_getUserOperationReceipt", json!([op_hash])).await?;
+            if !receipt.is_null() {
+                break receipt;
+            }
+            if started.elapsed() >= INCLUSION_TIMEOUT {
+                pb.finish_and_clear();
+                return Err(CliError::Rpc(format!("UserOperation {:?} was not included within {}s; the bundler may still include it",
+                    op_hash, INCLUSION_TIMEOUT.as_secs())).into());
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        };
+        pb.finish_and_clear();
+        self.cache.invalidate(poll_id);
+
+        let tx_hash = receipt.pointer("/receipt/transactionHash").cloned()
+            .and_then(|hash| serde_json::from_value::<H256>(hash).ok());
+        if let Some(tx_hash) = tx_hash {
+            audit::record_tx(tx_hash);
+        }
+        if receipt.get("success").and_then(Value::as_bool) == Some(false) {
+            let reason = receipt.get("reason").and_then(Value::as_str).unwrap_or("no reason given");
+            bail_user!("UserOperation {:?} was included but the vote reverted: {}", op_hash, reason);
+        }
+
+        say!("{}", "✅ Vote cast successfully!".green().bold());
+        say!("{} {:?}", "UserOperation hash:".cyan().bold(), op_hash);
+        if let Some(tx_hash) = tx_hash {
+            say!("{} {:?}", "Transaction hash:".cyan().bold(), tx_hash);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wraps_calls_and_binds_the_hash_to_chain_and_entry_point() {
+        let target = Address::repeat_byte(0x11);
+        let call_data = execute_call_data(target, U256::zero(), Bytes::from(vec![0xab, 0xcd]));
+        assert_eq!(&call_data[..4], &[0xb6, 0x1d, 0x27, 0xf6]);
+        assert_eq!(&call_data[16..36], target.as_bytes());
+
+        let mut op = UserOperation {
+            sender: Address::repeat_byte(0x22),
+            nonce: U256::from(7),
+            init_code: Bytes::default(),
+            call_data,
+            call_gas_limit: U256::from(100_000),
+            verification_gas_limit: U256::from(80_000),
+            pre_verification_gas: U256::from(50_000),
+            max_fee_per_gas: U256::from(2_000_000_000u64),
+            max_priority_fee_per_gas: U256::from(1_000_000_000u64),
+            paymaster_and_data: Bytes::default(),
+            signature: DUMMY_SIGNATURE.parse().unwrap(),
+        };
+        let entry_point: Address = ENTRY_POINT_V06.parse().unwrap();
+        let hash = op.hash(entry_point, 11155111);
+        assert_ne!(hash, op.hash(entry_point, 1));
+        assert_ne!(hash, op.hash(Address::zero(), 11155111));
+        op.signature = Bytes::default();
+        assert_eq!(hash, op.hash(entry_point, 11155111), "the signature is not part of what is signed");
+
+        op.apply_gas(&json!({ "callGasLimit": "0x30d40", "preVerificationGas": 60000 })).unwrap();
+        assert_eq!(op.gas_limit(), U256::from(200_000 + 80_000 + 60_000));
+        op.apply_gas(&json!({ "callGasLimit": format!("{:#x}", U256::MAX) })).unwrap();
+        assert_eq!(op.gas_limit().to_u64_saturating(), u64::MAX, "an absurd bundler estimate does not panic");
+    }
+}
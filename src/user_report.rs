@@ -0,0 +1,254 @@
+use std::collections::btree_map::{BTreeMap, Entry};
+
+use anyhow::Result;
+use colored::*;
+use ethers::prelude::*;
+use serde::Serialize;
+
+use crate::error::bail_user;
+use crate::indexer::ContractEvent;
+use crate::my_polls::PollState;
+use crate::numbers::SaturatingU64;
+use crate::quorum::QuorumState;
+use crate::{format_timestamp, numbers, poll_quorum, profiles, PollManager};
+
+/// A poll the user created, with how it turned out so far
+#[derive(Debug, Clone, Serialize)]
+pub struct CreatedPoll {
+    pub poll_id: u64,
+    pub question: String,
+    /// active, needs_closing or closed
+    pub state: String,
+    pub total_votes: u64,
+    pub quorum: QuorumState,
+    /// The winning option once the poll has ended, the leading one while it runs; absent on a tie or without votes
+    pub leader: Option<String>,
+    /// The leader's share of the deciding metric (votes, or weight for weighted polls)
+    pub leader_share: Option<f64>,
+}
+
+/// One VoteCast event by the user
+#[derive(Debug, Clone, Serialize)]
+pub struct CastVote {
+    pub poll_id: u64,
+    pub question: String,
+    pub option_index: u64,
+    pub option: String,
+    #[serde(with = "numbers::decimal")]
+    pub weight: U256,
+    pub block_number: u64,
+    pub timestamp: u64,
+    pub transaction_hash: H256,
+}
+
+/// Everything known about one address: contract stats, polls, votes, delegation and voting power
+#[derive(Debug, Clone, Serialize)]
+pub struct UserReport {
+    pub address: Address,
+    pub profile: Option<String>,
+    pub polls_created: u64,
+    pub polls_voted: u64,
+    #[serde(with = "numbers::decimal")]
+    pub total_voting_weight: U256,
+    /// Governance token voting power; absent without a governance token
+    #[serde(with = "numbers::decimal_option")]
+    pub voting_power: Option<U256>,
+    pub delegate: Option<Address>,
+    pub delegators: Vec<Address>,
+    pub created: Vec<CreatedPoll>,
+    pub votes: Vec<CastVote>,
+}
+
+fn state_name(state: PollState) -> &'static str {
+    match state {
+        PollState::Active => "active",
+        PollState::NeedsClosing => "needs_closing",
+        PollState::Closed => "closed",
+    }
+}
+
+/// A created poll's outcome in one line, e.g. "Closed · Yes won with 62.5% · 8 votes · quorum reached"
+fn describe(poll: &CreatedPoll) -> String {
+    let (state, verb) = match poll.state.as_str() {
+        "active" => ("Active", "leading"),
+        "needs_closing" => ("Ended, needs closing", "won"),
+        _ => ("Closed", "won"),
+    };
+    let outcome = match (&poll.leader, poll.leader_share) {
+        (Some(leader), Some(share)) => format!("{} {} with {:.1}%", leader, verb, share),
+        _ if poll.total_votes == 0 => "no votes".to_string(),
+        _ => "tied".to_string(),
+    };
+    let mut line = format!("{} · {} · {} votes", state, outcome, numbers::format_integer(poll.total_votes));
+    if poll.quorum != QuorumState::NotRequired {
+        line.push_str(&format!(" · quorum {}", poll.quorum));
+    }
+    line
+}
+
+fn print_user_report(report: &UserReport) {
+    say!("\n{}", "👤 USER REPORT".cyan().bold().underline());
+    say!("{}", "═".repeat(60).cyan());
+    say!("{} {:?}", "Address:".yellow().bold(), report.address);
+    if let Some(profile) = &report.profile {
+        say!("{} {}", "Profile:".yellow().bold(), profile);
+    }
+    say!("{} {}", "Polls Created:".yellow().bold(), report.polls_created.to_string().green());
+    say!("{} {}", "Polls Voted On:".yellow().bold(), report.polls_voted.to_string().green());
+    say!("{} {}", "Total Voting Weight:".yellow().bold(), numbers::format_integer(report.total_voting_weight).cyan());
+    if let Some(power) = report.voting_power {
+        say!("{} {}", "Voting Power:".yellow().bold(), numbers::format_amount(power, 18, 2));
+    }
+
+    say!("\n{}", "👥 DELEGATION".cyan().bold());
+    say!("{}", "─".repeat(60).cyan());
+    match report.delegate {
+        Some(delegate) => say!("{} {:?}", "Delegated To:".yellow().bold(), delegate),
+        None => say!("{} {}", "Delegated To:".yellow().bold(), "None".red()),
+    }
+    if report.delegators.is_empty() {
+        say!("{} {}", "Delegators:".yellow().bold(), "None".red());
+    } else {
+        say!("{} {}", "Delegators:".yellow().bold(), report.delegators.len().to_string().green());
+        for delegator in &report.delegators {
+            say!("  {:?}", delegator);
+        }
+    }
+
+    say!("\n{}", "📋 CREATED POLLS".cyan().bold());
+    say!("{}", "─".repeat(60).cyan());
+    if report.created.is_empty() {
+        say!("None.");
+    }
+    for poll in &report.created {
+        say!("#{} {}", poll.poll_id, poll.question.white().bold());
+        say!("  {}", describe(poll));
+    }
+
+    say!("\n{}", "🗳️  VOTES".cyan().bold());
+    say!("{}", "─".repeat(60).cyan());
+    if report.votes.is_empty() {
+        say!("None indexed.");
+    }
+    for vote in &report.votes {
+        let weight = if vote.weight == U256::one() { String::new() } else { format!(" (weight {})", numbers::format_integer(vote.weight)) };
+        say!("{} #{} {} → {}{}", format_timestamp(vote.timestamp), vote.poll_id, vote.question, vote.option.green().bold(), weight);
+    }
+}
+
+impl<M: Middleware + 'static> PollManager<M> {
+    /// `user-report`: one address's stats, polls, votes, delegation and voting power together
+    pub async fn user_report(&self, address: Option<Address>, format: &str, output_path: Option<String>) -> Result<()> {
+        let json = match format.to_lowercase().as_str() {
+            "json" => true,
+            "table" => false,
+            _ => bail_user!("Unsupported format: {}. Use table or json", format),
+        };
+        let address = address.unwrap_or(self.sender);
+        let now = self.now();
+
+        let (polls_created, polls_voted, total_voting_weight) = self.at_block(self.contract.get_user_stats(address)).call().await?;
+        let delegate = Some(self.at_block(self.contract.get_delegate(address)).call().await?).filter(|delegate| !delegate.is_zero());
+        let delegators = self.at_block(self.contract.get_delegators(address)).call().await?;
+        let voting_power = match &self.governance_token {
+            Some(token) => Some(self.at_block(token.get_voting_power(address)).call().await?),
+            None => None,
+        };
+
+        let mut created = Vec::new();
+        for poll_id in self.at_block(self.contract.get_user_created_polls(address)).call().await? {
+            let poll = self.poll_data(poll_id).await?;
+            let poll_id = poll_id.to_u64_saturating();
+            let tally = self.poll_tally(poll_id, &poll.2, poll.7).await?;
+            let quorum = poll_quorum(tally.total_votes, poll.9, poll.4, poll.5, poll.6, now);
+            let leader = tally.leader();
+            created.push(CreatedPoll {
+                poll_id,
+                question: poll.1,
+                state: state_name(PollState::classify(poll.6, poll.5.to_u64_saturating(), now)).to_string(),
+                total_votes: tally.total_votes,
+                quorum: quorum.state,
+                leader: leader.map(|option| option.option.clone()),
+                leader_share: leader.map(|option| tally.deciding_share(option)),
+            });
+        }
+
+        let index = self.event_index().await?;
+        let mut polls = BTreeMap::new();
+        let mut votes = Vec::new();
+        for event in &index.events {
+            if self.pinned.is_some_and(|pinned| event.block_number > pinned.number) {
+                break;
+            }
+            let ContractEvent::VoteCast { poll_id, voter, option_index, weight } = event.event else { continue };
+            if voter != address {
+                continue;
+            }
+            let poll = match polls.entry(poll_id) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => entry.insert(self.poll_data(U256::from(poll_id)).await?),
+            };
+            votes.push(CastVote {
+                poll_id,
+                question: poll.1.clone(),
+                option_index,
+                option: poll.2.get(option_index as usize).cloned().unwrap_or_else(|| format!("Option {}", option_index)),
+                weight,
+                block_number: event.block_number,
+                timestamp: event.timestamp,
+                transaction_hash: event.transaction_hash,
+            });
+        }
+
+        let report = UserReport {
+            address,
+            profile: profiles::summary(address),
+            polls_created: polls_created.to_u64_saturating(),
+            polls_voted: polls_voted.to_u64_saturating(),
+            total_voting_weight,
+            voting_power,
+            delegate,
+            delegators,
+            created,
+            votes,
+        };
+        if !json {
+            print_user_report(&report);
+            return Ok(());
+        }
+        let rendered = serde_json::to_string_pretty(&report)?;
+        match output_path {
+            Some(path) => {
+                std::fs::write(&path, rendered)?;
+                say!("{} {}", "✅ Exported to:".green().bold(), path.yellow());
+            }
+            None => println!("{}", rendered),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn describes_created_poll_outcomes() {
+        let poll = CreatedPoll {
+            poll_id: 1,
+            question: "Ship it?".to_string(),
+            state: "closed".to_string(),
+            total_votes: 8,
+            quorum: QuorumState::Reached,
+            leader: Some("Yes".to_string()),
+            leader_share: Some(62.5),
+        };
+        assert_eq!(describe(&poll), "Closed · Yes won with 62.5% · 8 votes · quorum reached");
+
+        let running = CreatedPoll { state: "active".to_string(), quorum: QuorumState::NotRequired, ..poll.clone() };
+        assert_eq!(describe(&running), "Active · Yes leading with 62.5% · 8 votes");
+
+        let tied = CreatedPoll { state: "needs_closing".to_string(), leader: None, leader_share: None, quorum: QuorumState::Failed, ..poll };
+        assert_eq!(describe(&tied), "Ended, needs closing · tied · 8 votes · quorum failed");
+    }
+}
@@ -0,0 +1,218 @@
+use anyhow::Result;
+use colored::*;
+use ethers::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::numbers::SaturatingU64;
+use crate::error::bail_user;
+use crate::{format_timestamp, PollManager};
+
+const CHART_WIDTH: usize = 40;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VelocityBucket {
+    pub start: u64,
+    pub start_label: String,
+    pub votes: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct VelocityReport {
+    pub poll_id: u64,
+    pub question: String,
+    pub bucket: String,
+    pub bucket_seconds: u64,
+    pub total_votes: u64,
+    pub buckets: Vec<VelocityBucket>,
+    pub peak: Option<VelocityBucket>,
+    pub momentum: String,
+    pub recent_votes_per_hour: f64,
+    pub projected_final_votes: u64,
+}
+
+fn bucket_seconds(bucket: &str) -> Result<u64> {
+    match bucket.to_lowercase().as_str() {
+        "hourly" => Ok(3600),
+        "daily" => Ok(86400),
+        _ => bail_user!("Invalid bucket size. Use: hourly, daily"),
+    }
+}
+
+/// Count vote timestamps into fixed windows starting at `start` and covering up to `until`
+pub(crate) fn bucket_votes(timestamps: &[u64], start: u64, until: u64, size: u64) -> Vec<VelocityBucket> {
+    let span = until.saturating_sub(start);
+    let count = (span / size + 1) as usize;
+    let mut buckets: Vec<VelocityBucket> = (0..count)
+        .map(|i| {
+            let bucket_start = start + i as u64 * size;
+            VelocityBucket {
+                start: bucket_start,
+                start_label: format_timestamp(bucket_start),
+                votes: 0,
+            }
+        })
+        .collect();
+
+    for &ts in timestamps {
+        let index = (ts.saturating_sub(start) / size) as usize;
+        if let Some(bucket) = buckets.get_mut(index.min(count - 1)) {
+            bucket.votes += 1;
+        }
+    }
+
+    buckets
+}
+
+/// Compare the later half of the buckets against the earlier half
+fn momentum(buckets: &[VelocityBucket]) -> &'static str {
+    if buckets.len() < 2 {
+        return "insufficient data";
+    }
+
+    let mid = buckets.len() / 2;
+    let earlier: u64 = buckets[..mid].iter().map(|b| b.votes).sum();
+    let later: u64 = buckets[mid..].iter().map(|b| b.votes).sum();
+    let earlier_rate = earlier as f64 / mid as f64;
+    let later_rate = later as f64 / (buckets.len() - mid) as f64;
+
+    if later_rate > earlier_rate * 1.15 {
+        "increasing"
+    } else if later_rate < earlier_rate * 0.85 {
+        "decreasing"
+    } else {
+        "steady"
+    }
+}
+
+/// Votes per hour over the most recent (up to three) complete buckets. The bucket still open at
+/// `until` holds only part of its window's votes, so it is left out unless it is the only one.
+pub(crate) fn recent_rate(buckets: &[VelocityBucket], size: u64, until: u64) -> f64 {
+    let open = buckets.last().is_some_and(|last| until < last.start + size);
+    match buckets {
+        [] => 0.0,
+        [only] if open => only.votes as f64 / until.saturating_sub(only.start).max(1) as f64 * 3600.0,
+        _ => {
+            let complete = if open { &buckets[..buckets.len() - 1] } else { buckets };
+            let window = complete.len().min(3);
+            let votes: u64 = complete[complete.len() - window..].iter().map(|b| b.votes).sum();
+            votes as f64 / (window as u64 * size) as f64 * 3600.0
+        }
+    }
+}
+
+impl<M: Middleware + 'static> PollManager<M> {
+    pub async fn voting_velocity(&self, poll_id: u64, bucket: &str, format: &str, output_path: Option<String>) -> Result<()> {
+        let size = bucket_seconds(bucket)?;
+        let poll_data = self.poll_data(U256::from(poll_id)).await?;
+        let votes = self.fetch_vote_events(poll_id).await?;
+
+        let created_at = poll_data.4.to_u64_saturating();
+        let end_time = poll_data.5.to_u64_saturating();
+        let now = chrono::Utc::now().timestamp() as u64;
+        let is_open = poll_data.6 == 0 && now < end_time;
+        let until = if is_open { now } else { end_time };
+
+        let timestamps: Vec<u64> = votes.iter().map(|v| v.timestamp).collect();
+        let buckets = bucket_votes(&timestamps, created_at, until, size);
+        let peak = buckets.iter().filter(|b| b.votes > 0).max_by_key(|b| b.votes).cloned();
+        let rate = recent_rate(&buckets, size, until);
+        let total_votes = votes.len() as u64;
+        let projected_final_votes = if is_open {
+            total_votes + (rate * (end_time - now) as f64 / 3600.0).round() as u64
+        } else {
+            total_votes
+        };
+
+        let report = VelocityReport {
+            poll_id,
+            question: poll_data.1.clone(),
+            bucket: bucket.to_lowercase(),
+            bucket_seconds: size,
+            total_votes,
+            momentum: momentum(&buckets).to_string(),
+            recent_votes_per_hour: rate,
+            projected_final_votes,
+            peak,
+            buckets,
+        };
+
+        match format.to_lowercase().as_str() {
+            "json" => {
+                let json_output = serde_json::to_string_pretty(&report)?;
+                if let Some(path) = output_path {
+                    std::fs::write(&path, &json_output)?;
+                    say!("{} {}", "✅ Exported to:".green().bold(), path.yellow());
+                } else {
+                    println!("{}", json_output);
+                }
+            }
+            "table" => print_velocity(&report, is_open),
+            _ => bail_user!("Unsupported format: {}. Use table or json", format),
+        }
+
+        Ok(())
+    }
+}
+
+fn print_velocity(report: &VelocityReport, is_open: bool) {
+    say!("\n{}", "⚡ VOTING VELOCITY".cyan().bold().underline());
+    say!("{}", "═".repeat(60).cyan());
+    say!("{} {} - {}", "Poll ID:".yellow().bold(), report.poll_id.to_string().white(), report.question.white().bold());
+    say!("{} {}", "Total Votes:".yellow().bold(), report.total_votes.to_string().green().bold());
+    say!("{} {}", "Bucket:".yellow().bold(), report.bucket.white());
+
+    say!("\n{}", "📈 VOTES PER BUCKET".cyan().bold());
+    say!("{}", "─".repeat(60).cyan());
+
+    let max_votes = report.buckets.iter().map(|b| b.votes).max().unwrap_or(0);
+    for bucket in &report.buckets {
+        let bar_length = if max_votes > 0 {
+            (bucket.votes as usize * CHART_WIDTH) / max_votes as usize
+        } else {
+            0
+        };
+        say!("{} │{} {}",
+            bucket.start_label.white(),
+            "█".repeat(bar_length).green(),
+            bucket.votes.to_string().yellow()
+        );
+    }
+
+    say!("\n{}", "🔎 SUMMARY".cyan().bold());
+    say!("{}", "─".repeat(60).cyan());
+    match &report.peak {
+        Some(peak) => say!("{} {} ({} votes)", "Peak Activity:".yellow().bold(), peak.start_label.white(), peak.votes.to_string().green()),
+        None => say!("{} {}", "Peak Activity:".yellow().bold(), "No votes yet".red()),
+    }
+    let momentum = match report.momentum.as_str() {
+        "increasing" => report.momentum.green(),
+        "decreasing" => report.momentum.red(),
+        _ => report.momentum.white(),
+    };
+    say!("{} {}", "Momentum:".yellow().bold(), momentum);
+    say!("{} {:.2}", "Recent Votes/Hour:".yellow().bold(), report.recent_votes_per_hour);
+    if is_open {
+        say!("{} {}", "Projected Final Turnout:".yellow().bold(), report.projected_final_votes.to_string().cyan());
+    } else {
+        say!("{} {}", "Final Turnout:".yellow().bold(), report.projected_final_votes.to_string().cyan());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recent_rate_leaves_out_the_open_bucket() {
+        // Four complete hours of 6, 6, 6, 6 votes, then 1 vote ten minutes into the fifth
+        let timestamps: Vec<u64> = (0..24).map(|i| i * 600).chain([4 * 3600 + 60]).collect();
+        let buckets = bucket_votes(&timestamps, 0, 4 * 3600 + 600, 3600);
+        assert_eq!(buckets.len(), 5);
+        assert_eq!(recent_rate(&buckets, 3600, 4 * 3600 + 600), 6.0);
+        // Once the hour is over it counts
+        assert_eq!(recent_rate(&bucket_votes(&timestamps, 0, 5 * 3600, 3600), 3600, 5 * 3600), 13.0 / 10800.0 * 3600.0);
+        // A poll younger than one bucket is rated over the time it has been open
+        assert_eq!(recent_rate(&bucket_votes(&[60, 120], 0, 1800, 3600), 3600, 1800), 4.0);
+        assert_eq!(recent_rate(&[], 3600, 0), 0.0);
+    }
+}
@@ -0,0 +1,136 @@
+use anyhow::Result;
+use colored::*;
+use ethers::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::error::bail_user;
+use crate::events::vote_cast_from_receipt;
+use crate::numbers::SaturatingU64;
+use crate::{format_timestamp, numbers, PollManager, VoteCastFilter};
+
+/// What a voter can keep or share to show their vote was counted
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VoteProof {
+    pub chain_id: u64,
+    pub contract: Address,
+    pub poll_id: u64,
+    pub question: String,
+    pub voter: Address,
+    /// `hasUserVoted` at verification time
+    pub has_voted: bool,
+    pub option_index: u64,
+    pub option: String,
+    #[serde(with = "numbers::decimal")]
+    pub weight: U256,
+    pub transaction_hash: H256,
+    pub block_number: u64,
+    pub block_hash: H256,
+    pub cast_at: u64,
+    pub confirmations: u64,
+    pub verified_at: u64,
+}
+
+impl<M: Middleware + 'static> PollManager<M> {
+    /// Locate the VoteCast event for `voter`, in `tx` when given, otherwise in the poll's logs
+    async fn find_vote_cast(&self, poll_id: u64, voter: Address, tx: Option<H256>) -> Result<Option<(VoteCastFilter, H256, u64, H256)>> {
+        if let Some(tx_hash) = tx {
+            let Some(receipt) = self.client.get_transaction_receipt(tx_hash).await? else {
+                bail_user!("Transaction {:?} not found; it may still be pending or on another network", tx_hash);
+            };
+            if receipt.status == Some(U64::zero()) {
+                bail_user!("Transaction {:?} reverted, so no vote was recorded by it", tx_hash);
+            }
+            let Some(event) = vote_cast_from_receipt(&receipt, self.contract.address(), poll_id, voter) else {
+                bail_user!("Transaction {:?} did not cast a vote on poll {} for {:?}", tx_hash, poll_id, voter);
+            };
+            let block_number = receipt.block_number.map(|n| n.as_u64()).unwrap_or_default();
+            return Ok(Some((event, tx_hash, block_number, receipt.block_hash.unwrap_or_default())));
+        }
+
+        let logs = self.contract
+            .vote_cast_filter()
+            .topic1(U256::from(poll_id))
+            .topic2(voter)
+            .from_block(0u64)
+            .query_with_meta()
+            .await?;
+        Ok(logs.into_iter().last().map(|(event, meta)| {
+            (event, meta.transaction_hash, meta.block_number.as_u64(), meta.block_hash)
+        }))
+    }
+
+    pub async fn verify_vote(&self, poll_id: u64, address: Option<String>, tx: Option<H256>, format: &str, output_path: Option<String>) -> Result<()> {
+        let voter = match address {
+            Some(addr) => addr.parse::<Address>()?,
+            None => self.sender,
+        };
+        let json = match format.to_lowercase().as_str() {
+            "json" => true,
+            "table" => false,
+            _ => bail_user!("Unsupported format: {}. Use table or json", format),
+        };
+        if !json {
+            say!("{} {} {} {:?}", "🔎 Verifying vote on poll".cyan().bold(), poll_id.to_string().yellow(), "by".cyan().bold(), voter);
+        }
+
+        let poll_data = self.poll_data(U256::from(poll_id)).await?;
+        let has_voted = self.contract.has_user_voted(U256::from(poll_id), voter).call().await?;
+        let found = self.find_vote_cast(poll_id, voter, tx).await?;
+
+        let Some((event, transaction_hash, block_number, block_hash)) = found else {
+            if has_voted {
+                bail_user!("{:?} is marked as voted on poll {}, but no VoteCast event was found; pass --tx to check a specific transaction", voter, poll_id);
+            }
+            bail_user!("No vote recorded for {:?} on poll {}", voter, poll_id);
+        };
+
+        let latest = self.client.get_block_number().await?.as_u64();
+        let option_index = event.option_index.to_u64_saturating();
+        let proof = VoteProof {
+            chain_id: self.wallet.chain_id(),
+            contract: self.contract.address(),
+            poll_id,
+            question: poll_data.1.clone(),
+            voter,
+            has_voted,
+            option_index,
+            option: poll_data.2.get(option_index as usize).cloned().unwrap_or_default(),
+            weight: event.weight,
+            transaction_hash,
+            block_number,
+            block_hash,
+            cast_at: self.block_timestamp(block_number).await?,
+            confirmations: latest.saturating_sub(block_number) + 1,
+            verified_at: chrono::Utc::now().timestamp() as u64,
+        };
+
+        let bundle = serde_json::to_string_pretty(&proof)?;
+        if let Some(path) = &output_path {
+            std::fs::write(path, &bundle)?;
+        }
+        if json {
+            match output_path {
+                Some(path) => say!("{} {}", "✅ Exported to:".green().bold(), path.yellow()),
+                None => println!("{}", bundle),
+            }
+            return Ok(());
+        }
+
+        if proof.has_voted {
+            say!("{}", "✅ Your vote was recorded!".green().bold());
+        } else {
+            // The log exists but the contract no longer reports the vote, e.g. after a reset
+            say!("{}", "⚠️  A VoteCast event exists but hasUserVoted is false".yellow().bold());
+        }
+        say!("{} {}", "Question:".cyan().bold(), proof.question.white());
+        say!("{} {} (#{})", "Option:".cyan().bold(), proof.option.green().bold(), proof.option_index);
+        say!("{} {}", "Weight:".cyan().bold(), numbers::format_integer(proof.weight).yellow());
+        say!("{} {:?}", "Transaction:".cyan().bold(), proof.transaction_hash);
+        say!("{} {} ({} confirmations)", "Block:".cyan().bold(), proof.block_number, proof.confirmations);
+        say!("{} {}", "Cast At:".cyan().bold(), format_timestamp(proof.cast_at).white());
+        if let Some(path) = output_path {
+            say!("{} {}", "📦 Proof bundle:".cyan().bold(), path.yellow());
+        }
+        Ok(())
+    }
+}
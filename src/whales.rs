@@ -0,0 +1,231 @@
+use std::collections::{HashMap, HashSet};
+
+use anyhow::Result;
+use colored::*;
+use ethers::prelude::*;
+use polling_analytics::stats::{gini, nakamoto_coefficient};
+use serde::{Deserialize, Serialize};
+
+use crate::error::bail_user;
+use crate::events::VoteRecord;
+use crate::{u256_to_f64, u8_to_poll_type, PollManager};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VoterWeight {
+    pub rank: usize,
+    pub voter: Address,
+    pub option_index: u64,
+    pub option: String,
+    pub weight: f64,
+    pub share: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WhaleReport {
+    pub poll_id: u64,
+    pub question: String,
+    pub poll_type: String,
+    pub voter_count: usize,
+    pub total_weight: f64,
+    pub gini: f64,
+    /// Smallest number of voters whose combined weight exceeds half of the total
+    pub nakamoto_coefficient: usize,
+    pub top_n: usize,
+    pub top_n_share: f64,
+    pub top_voters: Vec<VoterWeight>,
+    pub winner: Option<String>,
+    pub top_n_choice: Option<String>,
+    pub winner_without_top_n: Option<String>,
+    pub top_n_decisive: bool,
+}
+
+/// Index of the option with the most weight, if any weight was cast at all
+fn leading_option(votes: &[&VoteRecord], option_count: usize) -> Option<usize> {
+    let mut tally = vec![0.0; option_count];
+    for vote in votes {
+        if let Some(slot) = tally.get_mut(vote.option_index as usize) {
+            *slot += u256_to_f64(vote.weight);
+        }
+    }
+
+    tally.iter()
+        .enumerate()
+        .filter(|(_, w)| **w > 0.0)
+        .max_by(|a, b| a.1.partial_cmp(b.1).unwrap_or(std::cmp::Ordering::Equal))
+        .map(|(i, _)| i)
+}
+
+/// Each voter's combined weight and the option of their heaviest vote, heaviest voter first.
+/// Repeat events from one address (multi-choice polls) merge into a single voter.
+fn rank_voters(votes: &[VoteRecord]) -> Vec<(Address, f64, u64)> {
+    let mut per_voter: HashMap<Address, (f64, U256, u64)> = HashMap::new();
+    for vote in votes {
+        let entry = per_voter.entry(vote.voter).or_insert((0.0, U256::zero(), vote.option_index));
+        entry.0 += u256_to_f64(vote.weight);
+        if vote.weight > entry.1 {
+            (entry.1, entry.2) = (vote.weight, vote.option_index);
+        }
+    }
+    let mut ranked: Vec<(Address, f64, u64)> = per_voter.into_iter().map(|(voter, (weight, _, option))| (voter, weight, option)).collect();
+    ranked.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal).then(a.0.cmp(&b.0)));
+    ranked
+}
+
+impl<M: Middleware + 'static> PollManager<M> {
+    pub async fn whale_analysis(&self, poll_id: u64, top: usize, format: &str, output_path: Option<String>) -> Result<()> {
+        let poll_data = self.poll_data(U256::from(poll_id)).await?;
+        let options = poll_data.2.clone();
+        let poll_type = poll_data.7;
+
+        if poll_type != 1 && poll_type != 2 {
+            say!("{} {}",
+                "⚠️  Poll is not weighted or quadratic; every vote carries weight 1.".yellow(),
+                format!("(type: {})", u8_to_poll_type(poll_type)).white()
+            );
+        }
+
+        let votes = self.fetch_vote_events(poll_id).await?;
+        let ranked = rank_voters(&votes);
+        let weights: Vec<f64> = ranked.iter().map(|(_, weight, _)| *weight).collect();
+        let total_weight: f64 = weights.iter().sum();
+
+        let top_n = top.min(ranked.len());
+        let top_voters: Vec<VoterWeight> = ranked.iter()
+            .take(top_n)
+            .enumerate()
+            .map(|(i, (voter, weight, option_index))| VoterWeight {
+                rank: i + 1,
+                voter: *voter,
+                option_index: *option_index,
+                option: options.get(*option_index as usize).cloned().unwrap_or_default(),
+                weight: *weight,
+                share: if total_weight > 0.0 { weight / total_weight * 100.0 } else { 0.0 },
+            })
+            .collect();
+        let top_n_share: f64 = top_voters.iter().map(|v| v.share).sum();
+
+        // Split by address so every event of a top voter counts on their side
+        let top_addresses: HashSet<Address> = top_voters.iter().map(|v| v.voter).collect();
+        let all: Vec<&VoteRecord> = votes.iter().collect();
+        let (whales, rest): (Vec<&VoteRecord>, Vec<&VoteRecord>) = votes.iter().partition(|vote| top_addresses.contains(&vote.voter));
+
+        let winner = leading_option(&all, options.len());
+        let top_n_choice = leading_option(&whales, options.len());
+        let winner_without_top_n = leading_option(&rest, options.len());
+        let top_n_decisive = winner.is_some() && winner != winner_without_top_n;
+        let option_name = |index: Option<usize>| index.and_then(|i| options.get(i).cloned());
+
+        let report = WhaleReport {
+            poll_id,
+            question: poll_data.1.clone(),
+            poll_type: u8_to_poll_type(poll_type).to_string(),
+            voter_count: ranked.len(),
+            total_weight,
+            gini: gini(&weights),
+            nakamoto_coefficient: nakamoto_coefficient(&weights),
+            top_n,
+            top_n_share,
+            top_voters,
+            winner: option_name(winner),
+            top_n_choice: option_name(top_n_choice),
+            winner_without_top_n: option_name(winner_without_top_n),
+            top_n_decisive,
+        };
+
+        match format.to_lowercase().as_str() {
+            "json" => {
+                let json_output = serde_json::to_string_pretty(&report)?;
+                if let Some(path) = output_path {
+                    std::fs::write(&path, &json_output)?;
+                    say!("{} {}", "✅ Exported to:".green().bold(), path.yellow());
+                } else {
+                    println!("{}", json_output);
+                }
+            }
+            "table" => print_whale_report(&report),
+            _ => bail_user!("Unsupported format: {}. Use table or json", format),
+        }
+
+        Ok(())
+    }
+}
+
+fn print_whale_report(report: &WhaleReport) {
+    say!("\n{}", "🐋 WHALE INFLUENCE ANALYSIS".cyan().bold().underline());
+    say!("{}", "═".repeat(60).cyan());
+    say!("{} {} - {}", "Poll ID:".yellow().bold(), report.poll_id.to_string().white(), report.question.white().bold());
+    say!("{} {}", "Type:".yellow().bold(), report.poll_type.white());
+    say!("{} {}", "Voters:".yellow().bold(), report.voter_count.to_string().green());
+    say!("{} {:.0}", "Total Weight:".yellow().bold(), report.total_weight);
+
+    say!("\n{}", "🏆 TOP VOTERS BY WEIGHT".cyan().bold());
+    say!("{}", "─".repeat(60).cyan());
+    if report.top_voters.is_empty() {
+        say!("No votes have been cast yet.");
+        return;
+    }
+    for voter in &report.top_voters {
+        say!("{:>3}. {:?} → {} | {:.0} ({:.1}%)",
+            voter.rank,
+            voter.voter,
+            voter.option.white().bold(),
+            voter.weight,
+            voter.share
+        );
+    }
+
+    say!("\n{}", "📐 CONCENTRATION".cyan().bold());
+    say!("{}", "─".repeat(60).cyan());
+    let gini = format!("{:.3}", report.gini);
+    let gini = if report.gini >= 0.6 { gini.red() } else if report.gini >= 0.4 { gini.yellow() } else { gini.green() };
+    say!("{} {}", "Gini Coefficient:".yellow().bold(), gini);
+    say!("{} {}", "Nakamoto Coefficient:".yellow().bold(), report.nakamoto_coefficient.to_string().white());
+    say!("{} {:.1}%", format!("Top {} Share:", report.top_n).yellow().bold(), report.top_n_share);
+
+    say!("\n{}", "🎯 OUTCOME IMPACT".cyan().bold());
+    say!("{}", "─".repeat(60).cyan());
+    let none = || "None".to_string();
+    say!("{} {}", "Current Winner:".yellow().bold(), report.winner.clone().unwrap_or_else(none).green().bold());
+    say!("{} {}", format!("Top {} Preferred:", report.top_n).yellow().bold(), report.top_n_choice.clone().unwrap_or_else(none).white());
+    say!("{} {}", format!("Winner Without Top {}:", report.top_n).yellow().bold(), report.winner_without_top_n.clone().unwrap_or_else(none).white());
+    if report.top_n_decisive {
+        say!("{}", format!("⚠️  The top {} voters alone determined the outcome", report.top_n).red().bold());
+    } else {
+        say!("{}", format!("✅ The outcome holds without the top {} voters", report.top_n).green());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vote(voter: u8, option_index: u64, weight: u64) -> VoteRecord {
+        VoteRecord {
+            poll_id: 0,
+            voter: Address::repeat_byte(voter),
+            option_index,
+            weight: U256::from(weight),
+            block_number: 0,
+            transaction_hash: H256::zero(),
+            timestamp: 0,
+        }
+    }
+
+    #[test]
+    fn ranks_voters_by_combined_weight() {
+        // Voter 1 split 60 across two options, outweighing voter 2's single 50
+        let votes = [vote(2, 1, 50), vote(1, 0, 40), vote(1, 1, 20), vote(3, 1, 5)];
+        let ranked = rank_voters(&votes);
+        assert_eq!(ranked, vec![
+            (Address::repeat_byte(1), 60.0, 0),
+            (Address::repeat_byte(2), 50.0, 1),
+            (Address::repeat_byte(3), 5.0, 1),
+        ]);
+
+        let top: HashSet<Address> = ranked.iter().take(1).map(|(voter, _, _)| *voter).collect();
+        let (whales, rest): (Vec<&VoteRecord>, Vec<&VoteRecord>) = votes.iter().partition(|vote| top.contains(&vote.voter));
+        assert_eq!(whales.len(), 2, "both of the top voter's events leave with them");
+        assert_eq!(leading_option(&whales, 2), Some(0));
+        assert_eq!(leading_option(&rest, 2), Some(1));
+    }
+}
@@ -0,0 +1,196 @@
+use anyhow::Result;
+use colored::*;
+use ethers::prelude::*;
+use polling_analytics::stats::share;
+use rust_xlsxwriter::{ExcelDateTime, Format, FormatAlign, Workbook, Worksheet};
+
+use crate::events::VoteRecord;
+use crate::numbers::SaturatingU64;
+use crate::{u256_to_f64, PollExport, PollManager};
+
+const DATE_FORMAT: &str = "yyyy-mm-dd hh:mm:ss";
+const SHARE_FORMAT: &str = "0.0%";
+
+/// Cell formats shared by every sheet
+struct Formats {
+    header: Format,
+    date: Format,
+    share: Format,
+}
+
+impl Formats {
+    fn new() -> Self {
+        Self {
+            header: Format::new().set_bold().set_background_color("#DDE6F0").set_align(FormatAlign::Center),
+            date: Format::new().set_num_format(DATE_FORMAT),
+            share: Format::new().set_num_format(SHARE_FORMAT),
+        }
+    }
+}
+
+/// A bold header row that stays in view and filters the rows below it
+fn header(sheet: &mut Worksheet, formats: &Formats, columns: &[&str], rows: usize) -> Result<()> {
+    for (col, name) in columns.iter().enumerate() {
+        sheet.write_string_with_format(0, col as u16, *name, &formats.header)?;
+    }
+    sheet.set_freeze_panes(1, 0)?;
+    sheet.autofilter(0, 0, rows as u32, columns.len() as u16 - 1)?;
+    Ok(())
+}
+
+fn date(sheet: &mut Worksheet, formats: &Formats, row: u32, col: u16, timestamp: u64) -> Result<()> {
+    let datetime = ExcelDateTime::from_timestamp(timestamp as i64)?;
+    sheet.write_datetime_with_format(row, col, &datetime, &formats.date)?;
+    Ok(())
+}
+
+fn polls_sheet(workbook: &mut Workbook, formats: &Formats, polls: &[(PollExport, Vec<VoteRecord>)]) -> Result<()> {
+    let sheet = workbook.add_worksheet().set_name("Polls")?;
+    header(sheet, formats, &[
+        "ID", "Question", "Creator", "Created (UTC)", "Ends (UTC)", "Active", "Type", "Winner metric",
+        "Total votes", "Total weight", "Unique voters", "Min participation", "Quorum",
+    ], polls.len())?;
+    for (i, (poll, votes)) in polls.iter().enumerate() {
+        let row = i as u32 + 1;
+        let mut voters: Vec<Address> = votes.iter().map(|vote| vote.voter).collect();
+        voters.sort();
+        voters.dedup();
+        let quorum = serde_json::to_value(poll.quorum_status)?;
+        sheet.write_number(row, 0, poll.id as f64)?;
+        sheet.write_string(row, 1, &poll.question)?;
+        sheet.write_string(row, 2, &poll.creator)?;
+        date(sheet, formats, row, 3, poll.created_at_unix)?;
+        date(sheet, formats, row, 4, poll.end_time_unix)?;
+        sheet.write_boolean(row, 5, poll.is_active)?;
+        sheet.write_string(row, 6, &poll.poll_type)?;
+        sheet.write_string(row, 7, &poll.winner_metric)?;
+        sheet.write_number(row, 8, poll.total_votes as f64)?;
+        sheet.write_number(row, 9, u256_to_f64(poll.total_weight))?;
+        sheet.write_number(row, 10, voters.len() as f64)?;
+        sheet.write_number(row, 11, poll.min_participation as f64)?;
+        sheet.write_string(row, 12, quorum.as_str().unwrap_or_default())?;
+    }
+    sheet.autofit();
+    Ok(())
+}
+
+/// One sheet of option tallies per poll
+fn results_sheet(workbook: &mut Workbook, formats: &Formats, poll: &PollExport) -> Result<()> {
+    let sheet = workbook.add_worksheet().set_name(format!("Poll {}", poll.id))?;
+    header(sheet, formats, &["Option", "Votes", "Vote share", "Weight", "Weight share"], poll.options.len())?;
+    let total_weight = u256_to_f64(poll.total_weight);
+    for (i, option) in poll.options.iter().enumerate() {
+        let row = i as u32 + 1;
+        let votes = poll.votes.get(i).copied().unwrap_or_default() as f64;
+        let weight = poll.weights.get(i).copied().map(u256_to_f64).unwrap_or_default();
+        sheet.write_string(row, 0, option)?;
+        sheet.write_number(row, 1, votes)?;
+        sheet.write_number_with_format(row, 2, share(votes, poll.total_votes as f64) / 100.0, &formats.share)?;
+        sheet.write_number(row, 3, weight)?;
+        sheet.write_number_with_format(row, 4, share(weight, total_weight) / 100.0, &formats.share)?;
+    }
+    sheet.autofit();
+    Ok(())
+}
+
+/// Every VoteCast event, one row per vote
+fn votes_sheet(workbook: &mut Workbook, formats: &Formats, polls: &[(PollExport, Vec<VoteRecord>)]) -> Result<()> {
+    let total: usize = polls.iter().map(|(_, votes)| votes.len()).sum();
+    let sheet = workbook.add_worksheet().set_name("Votes")?;
+    header(sheet, formats, &["Poll ID", "Voter", "Option index", "Option", "Weight", "Block", "Time (UTC)", "Transaction"], total)?;
+    let mut row = 1;
+    for (poll, votes) in polls {
+        for vote in votes {
+            let option = poll.options.get(vote.option_index as usize).map(String::as_str).unwrap_or_default();
+            sheet.write_number(row, 0, vote.poll_id as f64)?;
+            sheet.write_string(row, 1, format!("{:?}", vote.voter))?;
+            sheet.write_number(row, 2, vote.option_index as f64)?;
+            sheet.write_string(row, 3, option)?;
+            sheet.write_number(row, 4, u256_to_f64(vote.weight))?;
+            sheet.write_number(row, 5, vote.block_number as f64)?;
+            date(sheet, formats, row, 6, vote.timestamp)?;
+            sheet.write_string(row, 7, format!("{:?}", vote.transaction_hash))?;
+            row += 1;
+        }
+    }
+    sheet.autofit();
+    Ok(())
+}
+
+/// A workbook with a Polls overview, a tally sheet per poll and the Votes list
+fn workbook(polls: &[(PollExport, Vec<VoteRecord>)]) -> Result<Vec<u8>> {
+    let formats = Formats::new();
+    let mut workbook = Workbook::new();
+    polls_sheet(&mut workbook, &formats, polls)?;
+    for (poll, _) in polls {
+        results_sheet(&mut workbook, &formats, poll)?;
+    }
+    votes_sheet(&mut workbook, &formats, polls)?;
+    Ok(workbook.save_to_buffer()?)
+}
+
+impl<M: Middleware + 'static> PollManager<M> {
+    /// `export -f xlsx`: one poll, or every poll when `poll_id` is `None`
+    pub async fn export_xlsx(&self, poll_id: Option<u64>, output_path: Option<String>) -> Result<()> {
+        let ids: Vec<u64> = match poll_id {
+            Some(id) => vec![id],
+            None => (0..self.contract.poll_count().call().await?.to_u64_saturating()).collect(),
+        };
+        say!("{} {} {}", "📊 Exporting".cyan().bold(), ids.len().to_string().yellow(), "poll(s) to xlsx".cyan().bold());
+
+        let mut polls = Vec::with_capacity(ids.len());
+        for id in ids {
+            polls.push((self.build_poll_export(id).await?, self.fetch_vote_events(id).await?));
+        }
+        let rendered = workbook(&polls)?;
+        if let Some(path) = output_path {
+            std::fs::write(&path, &rendered)?;
+            say!("{} {}", "✅ Exported to:".green().bold(), path.yellow());
+        } else {
+            std::io::Write::write_all(&mut std::io::stdout(), &rendered)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::quorum::QuorumState;
+
+    #[test]
+    fn writes_a_sheet_per_poll() {
+        let poll = |id: u64| PollExport {
+            id,
+            question: format!("Budget {}?", id),
+            creator: format!("{:?}", Address::zero()),
+            created_at: String::new(),
+            end_time: String::new(),
+            created_at_unix: 1_700_000_000,
+            end_time_unix: 1_700_086_400,
+            is_active: false,
+            poll_type: "Standard".to_string(),
+            winner_metric: "votes".to_string(),
+            total_votes: 2,
+            total_weight: U256::from(2),
+            options: vec!["Yes".to_string(), "No".to_string()],
+            votes: vec![2, 0],
+            weights: vec![U256::from(2), U256::zero()],
+            min_participation: 0,
+            quorum_status: QuorumState::NotRequired,
+        };
+        let vote = VoteRecord {
+            poll_id: 1,
+            voter: Address::from_low_u64_be(1),
+            option_index: 0,
+            weight: U256::one(),
+            block_number: 9,
+            transaction_hash: H256::zero(),
+            timestamp: 1_700_000_100,
+        };
+        let xlsx = workbook(&[(poll(0), vec![]), (poll(1), vec![vote.clone(), vote])]).unwrap();
+        assert!(xlsx.starts_with(b"PK\x03\x04"));
+        // Sheet names are unique per poll, so every poll gets its own sheet
+        assert!(workbook(&[(poll(4), vec![]), (poll(4), vec![])]).is_err());
+    }
+}
@@ -0,0 +1,123 @@
+//! Shared harness: a throwaway Anvil node with EnhancedPolls deployed from `Counter/`.
+//!
+//! Tests using it are `#[ignore]`d so a plain `cargo test` reports them as skipped when
+//! Foundry is not installed; `cargo test -- --ignored` runs them and fails if it is missing.
+
+use std::net::{TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Output, Stdio};
+use std::time::{Duration, Instant};
+
+/// Anvil's first two deterministic accounts
+pub const DEPLOYER_KEY: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+pub const DEPLOYER: &str = "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266";
+pub const VOTER_KEY: &str = "0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d";
+pub const VOTER: &str = "0x70997970C51812dc3A010C7d01b50e0d17dc79C8";
+
+pub struct Anvil {
+    process: Child,
+    pub rpc_url: String,
+    pub contract: String,
+    /// POLL_CLI_HOME for this node, so indexes and caches never outlive it
+    home: PathBuf,
+}
+
+fn on_path(tool: &str) -> bool {
+    Command::new(tool)
+        .arg("--version")
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|s| s.success())
+}
+
+fn free_port() -> u16 {
+    TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port()
+}
+
+impl Anvil {
+    /// Start Anvil and deploy EnhancedPolls; panics if Foundry is unavailable
+    pub fn spawn() -> Self {
+        assert!(on_path("anvil") && on_path("forge"), "anvil/forge not found on PATH; install Foundry to run these tests");
+
+        let port = free_port();
+        let process = Command::new("anvil")
+            .args(["--port", &port.to_string(), "--silent"])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .expect("failed to start anvil");
+
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while TcpStream::connect(("127.0.0.1", port)).is_err() {
+            assert!(Instant::now() < deadline, "anvil did not start listening on port {}", port);
+            std::thread::sleep(Duration::from_millis(100));
+        }
+
+        let rpc_url = format!("http://127.0.0.1:{}", port);
+        let home = std::env::temp_dir().join(format!("polling-cli-test-{}", port));
+        let mut anvil = Anvil { process, rpc_url, contract: String::new(), home };
+        anvil.contract = anvil.deploy();
+        anvil
+    }
+
+    fn deploy(&self) -> String {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("Counter");
+        let output = Command::new("forge")
+            .current_dir(&root)
+            .args([
+                "create", "src/EnhancedPolls.sol:EnhancedPolls",
+                "--rpc-url", &self.rpc_url,
+                "--private-key", DEPLOYER_KEY,
+                "--broadcast", "--json",
+                "--constructor-args", "0x0000000000000000000000000000000000000000",
+            ])
+            .output()
+            .expect("failed to run forge create");
+        assert!(output.status.success(), "forge create failed: {}", String::from_utf8_lossy(&output.stderr));
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let json = stdout.lines().rev()
+            .find_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+            .expect("forge create printed no JSON");
+        json["deployedTo"].as_str().expect("no deployedTo in forge output").to_string()
+    }
+
+    /// Run the CLI in plain mode as the account owning `private_key`
+    pub fn cli(&self, private_key: &str, args: &[&str]) -> Output {
+        Command::new(env!("CARGO_BIN_EXE_Rust_Foundry"))
+            .arg("--plain")
+            .args(args)
+            .env("RPC_URL", &self.rpc_url)
+            .env("PRIVATE_KEY", private_key)
+            .env("CONTRACT_ADDRESS", &self.contract)
+            .env("POLL_CLI_HOME", &self.home)
+            .env_remove("GOVERNANCE_TOKEN_ADDRESS")
+            // No price lookups against the public API from tests
+            .env("POLL_FIAT", "off")
+            .output()
+            .expect("failed to run polling-cli")
+    }
+
+    /// Like `cli`, but panics with the captured output unless the command succeeds
+    pub fn cli_ok(&self, private_key: &str, args: &[&str]) -> String {
+        let output = self.cli(private_key, args);
+        assert!(
+            output.status.success(),
+            "`{}` exited with {:?}\nstdout:\n{}\nstderr:\n{}",
+            args.join(" "),
+            output.status.code(),
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr),
+        );
+        String::from_utf8_lossy(&output.stdout).into_owned()
+    }
+}
+
+impl Drop for Anvil {
+    fn drop(&mut self) {
+        let _ = self.process.kill();
+        let _ = self.process.wait();
+        let _ = std::fs::remove_dir_all(&self.home);
+    }
+}
@@ -0,0 +1,131 @@
+//! End-to-end create/vote/close/delegate/export flows against a local Anvil node.
+
+mod common;
+
+use common::{Anvil, DEPLOYER, DEPLOYER_KEY, VOTER, VOTER_KEY};
+
+fn export_json(anvil: &Anvil, poll_id: &str) -> serde_json::Value {
+    let stdout = anvil.cli_ok(DEPLOYER_KEY, &["export", "-p", poll_id, "-f", "json"]);
+    let start = stdout.find('{').expect("export printed no JSON");
+    serde_json::from_str(&stdout[start..]).expect("export printed invalid JSON")
+}
+
+fn create_poll(anvil: &Anvil, question: &str) {
+    anvil.cli_ok(DEPLOYER_KEY, &["create", "-q", question, "-o", "Yes,No,Abstain", "-d", "24", "--tags", "test"]);
+}
+
+#[test]
+#[ignore = "needs anvil and forge on PATH; run with --ignored"]
+fn create_vote_close_and_export() {
+    let anvil = Anvil::spawn();
+
+    create_poll(&anvil, "Adopt the new roadmap?");
+    anvil.cli_ok(DEPLOYER_KEY, &["vote", "-p", "0", "-o", "0"]);
+    anvil.cli_ok(VOTER_KEY, &["vote", "-p", "0", "-o", "1"]);
+
+    let export = export_json(&anvil, "0");
+    assert_eq!(export["question"], "Adopt the new roadmap?");
+    assert_eq!(export["creator"].as_str().unwrap().to_lowercase(), DEPLOYER.to_lowercase());
+    assert_eq!(export["total_votes"], 2);
+    assert_eq!(export["votes"], serde_json::json!([1, 1, 0]));
+    assert_eq!(export["is_active"], true);
+
+    anvil.cli_ok(DEPLOYER_KEY, &["close", "-p", "0", "--yes"]);
+    assert_eq!(export_json(&anvil, "0")["is_active"], false);
+
+    let view = anvil.cli_ok(DEPLOYER_KEY, &["view", "-p", "0"]);
+    assert!(view.contains("Closed"), "view output missing status:\n{}", view);
+}
+
+#[test]
+#[ignore = "needs anvil and forge on PATH; run with --ignored"]
+fn double_vote_reverts_with_exit_code() {
+    let anvil = Anvil::spawn();
+
+    create_poll(&anvil, "Vote twice?");
+    anvil.cli_ok(VOTER_KEY, &["vote", "-p", "0", "-o", "0"]);
+
+    let second = anvil.cli(VOTER_KEY, &["vote", "-p", "0", "-o", "1"]);
+    assert_eq!(second.status.code(), Some(5), "expected a contract revert exit code");
+}
+
+#[test]
+#[ignore = "needs anvil and forge on PATH; run with --ignored"]
+fn delegate_votes_on_behalf_of_delegator() {
+    let anvil = Anvil::spawn();
+
+    create_poll(&anvil, "Delegated decision?");
+    anvil.cli_ok(VOTER_KEY, &["set-delegate", "-d", DEPLOYER]);
+    anvil.cli_ok(DEPLOYER_KEY, &["vote-delegate", "-p", "0", "-o", "2", "-d", VOTER]);
+
+    let export = export_json(&anvil, "0");
+    assert_eq!(export["votes"], serde_json::json!([0, 0, 1]));
+
+    anvil.cli_ok(VOTER_KEY, &["remove-delegate"]);
+}
+
+#[test]
+#[ignore = "needs anvil and forge on PATH; run with --ignored"]
+fn csv_and_markdown_exports() {
+    let anvil = Anvil::spawn();
+
+    create_poll(&anvil, "Which, exactly?");
+    anvil.cli_ok(DEPLOYER_KEY, &["vote", "-p", "0", "-o", "1"]);
+
+    let csv = anvil.cli_ok(DEPLOYER_KEY, &["export", "-p", "0", "-f", "csv"]);
+    assert!(csv.contains("id,question,creator,created_at,end_time,is_active,poll_type,winner_metric,total_votes,total_weight,option,votes,weight"));
+    assert!(csv.contains("\"Which, exactly?\""));
+    assert!(csv.contains(",No,1,1"));
+
+    let tsv = anvil.cli_ok(DEPLOYER_KEY, &["export", "-p", "0", "-f", "csv", "--delimiter", "tab", "--no-header"]);
+    assert!(!tsv.contains("id\tquestion"));
+    assert!(tsv.contains("\tWhich, exactly?\t"));
+
+    let markdown = anvil.cli_ok(DEPLOYER_KEY, &["export", "-p", "0", "-f", "markdown"]);
+    assert!(markdown.contains("# Poll #0: Which, exactly?"));
+}
+
+#[test]
+#[ignore = "needs anvil and forge on PATH; run with --ignored"]
+fn invalid_input_exits_with_usage_code() {
+    let anvil = Anvil::spawn();
+
+    let output = anvil.cli(DEPLOYER_KEY, &["create", "-q", "One option?", "-o", "Only"]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+#[ignore = "needs anvil and forge on PATH; run with --ignored"]
+fn simulate_votes_on_a_fork_only() {
+    let anvil = Anvil::spawn();
+
+    create_poll(&anvil, "What if?");
+    anvil.cli_ok(VOTER_KEY, &["set-delegate", "-d", DEPLOYER]);
+
+    let stdout = anvil.cli_ok(DEPLOYER_KEY, &["simulate", "-p", "0", "--top-delegates", "1", "--option", "1", "-f", "json"]);
+    let start = stdout.find('{').expect("simulate printed no JSON");
+    let report: serde_json::Value = serde_json::from_str(&stdout[start..]).expect("simulate printed invalid JSON");
+    assert_eq!(report["votes"][0]["voter"].as_str().unwrap().to_lowercase(), DEPLOYER.to_lowercase());
+    assert_eq!(report["after"]["leader"], "No");
+    assert_eq!(report["outcome_changed"], true);
+
+    assert_eq!(export_json(&anvil, "0")["total_votes"], 0);
+}
+
+#[test]
+#[ignore = "needs anvil and forge on PATH; run with --ignored"]
+fn results_at_a_past_block() {
+    let anvil = Anvil::spawn();
+
+    // Anvil mines one block per transaction: deploy (1), create (2), vote (3)
+    create_poll(&anvil, "Before or after?");
+    anvil.cli_ok(VOTER_KEY, &["vote", "-p", "0", "-o", "0"]);
+
+    let now = anvil.cli_ok(DEPLOYER_KEY, &["results", "-p", "0"]);
+    assert!(now.contains("Total votes: 1"), "results at head:\n{}", now);
+    let before = anvil.cli_ok(DEPLOYER_KEY, &["results", "-p", "0", "--block", "2"]);
+    assert!(before.contains("Total votes: 0"), "results at block 2:\n{}", before);
+
+    let write = anvil.cli(DEPLOYER_KEY, &["vote", "-p", "0", "-o", "1", "--block", "2"]);
+    assert_eq!(write.status.code(), Some(2));
+}